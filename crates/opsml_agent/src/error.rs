@@ -1,3 +1,4 @@
+use opsml_sql::error::SqlError;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -10,4 +11,37 @@ pub enum AgentError {
 
     #[error("Agent run error: {0}")]
     Run(String),
+
+    #[error("Agent session not found or expired: {0}")]
+    SessionNotFound(String),
+
+    #[error("Agent job store error: {0}")]
+    JobStore(#[from] SqlError),
+}
+
+#[derive(Error, Debug)]
+pub enum WorkflowError {
+    #[error("Invalid workflow: {0}")]
+    Invalid(String),
+
+    #[error("Missing required workflow input: {0}")]
+    MissingInput(String),
+
+    #[error("Invalid workflow input: {0}")]
+    InvalidInput(String),
+
+    #[error("Failed to resolve card: {0}")]
+    Resolve(String),
+
+    #[error("Template error: {0}")]
+    Template(String),
+
+    #[error("Step error: {0}")]
+    Step(String),
+
+    #[error("Unsupported workflow action: {0}")]
+    Unsupported(String),
+
+    #[error(transparent)]
+    Agent(#[from] AgentError),
 }
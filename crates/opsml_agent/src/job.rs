@@ -1,5 +1,5 @@
 use chrono::{DateTime, Utc};
-use opsml_types::contracts::JobStatus;
+use opsml_types::contracts::{AgentJobRecord, JobStatus};
 use serde_json::Value;
 
 #[derive(Debug, Clone)]
@@ -10,12 +10,25 @@ pub struct JobState {
     pub status: JobStatus,
     pub result: Option<Value>,
     pub error: Option<String>,
+    pub session_id: Option<String>,
+    /// Question the agent asked when the job ended in `NeedsInput`
+    pub prompt: Option<String>,
+    /// Server instance running the job
+    pub instance_id: String,
     pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
     pub duration_ms: Option<u64>,
 }
 
 impl JobState {
-    pub fn new(id: String, agent_id: String, owner: String) -> Self {
+    pub fn new(
+        id: String,
+        agent_id: String,
+        owner: String,
+        session_id: Option<String>,
+        instance_id: String,
+    ) -> Self {
+        let now = Utc::now();
         Self {
             id,
             agent_id,
@@ -23,8 +36,56 @@ impl JobState {
             status: JobStatus::Pending,
             result: None,
             error: None,
-            created_at: Utc::now(),
+            session_id,
+            prompt: None,
+            instance_id,
+            created_at: now,
+            updated_at: now,
             duration_ms: None,
         }
     }
+
+    /// Moves the job to a new status and bumps `updated_at`
+    pub fn transition(&mut self, status: JobStatus) {
+        self.status = status;
+        self.updated_at = Utc::now();
+    }
+}
+
+impl From<&JobState> for AgentJobRecord {
+    fn from(state: &JobState) -> Self {
+        Self {
+            job_id: state.id.clone(),
+            agent_id: state.agent_id.clone(),
+            owner: state.owner.clone(),
+            status: state.status.clone(),
+            result: state.result.clone(),
+            error: state.error.clone(),
+            duration_ms: state.duration_ms,
+            session_id: state.session_id.clone(),
+            prompt: state.prompt.clone(),
+            instance_id: state.instance_id.clone(),
+            created_at: state.created_at,
+            updated_at: state.updated_at,
+        }
+    }
+}
+
+impl From<AgentJobRecord> for JobState {
+    fn from(record: AgentJobRecord) -> Self {
+        Self {
+            id: record.job_id,
+            agent_id: record.agent_id,
+            owner: record.owner,
+            status: record.status,
+            result: record.result,
+            error: record.error,
+            session_id: record.session_id,
+            prompt: record.prompt,
+            instance_id: record.instance_id,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+            duration_ms: record.duration_ms,
+        }
+    }
 }
@@ -1,7 +1,12 @@
 pub mod error;
 pub mod job;
+pub mod session;
 pub mod store;
+pub mod template;
+pub mod workflow;
 
-pub use error::AgentError;
+pub use error::{AgentError, WorkflowError};
 pub use job::JobState;
+pub use session::{AgentSession, SessionContext};
 pub use store::AgentStore;
+pub use workflow::{ResolvedCard, StepAction, StepExecutor, WorkflowRunner};
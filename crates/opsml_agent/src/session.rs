@@ -0,0 +1,46 @@
+use chrono::Utc;
+use scouter_client::potato_head::prelude::SessionState;
+use std::sync::atomic::{AtomicI64, Ordering};
+use tokio::sync::Mutex;
+
+pub struct SessionContext {
+    pub state: SessionState,
+    /// Question the agent is waiting on, answered by the next turn
+    pub pending_prompt: Option<String>,
+}
+
+/// Conversation state carried across invocations of the same agent.
+/// Turns within a session run one at a time by holding the `context` lock
+pub struct AgentSession {
+    pub id: String,
+    pub agent_id: String,
+    pub owner: String,
+    pub context: Mutex<SessionContext>,
+    last_active_ms: AtomicI64,
+}
+
+impl AgentSession {
+    pub fn new(id: String, agent_id: String, owner: String) -> Self {
+        Self {
+            id,
+            agent_id,
+            owner,
+            context: Mutex::new(SessionContext {
+                state: SessionState::new(),
+                pending_prompt: None,
+            }),
+            last_active_ms: AtomicI64::new(Utc::now().timestamp_millis()),
+        }
+    }
+
+    /// Resets the idle timer
+    pub fn touch(&self) {
+        self.last_active_ms
+            .store(Utc::now().timestamp_millis(), Ordering::Relaxed);
+    }
+
+    pub fn is_expired(&self, ttl: chrono::Duration) -> bool {
+        let idle_ms = Utc::now().timestamp_millis() - self.last_active_ms.load(Ordering::Relaxed);
+        idle_ms > ttl.num_milliseconds()
+    }
+}
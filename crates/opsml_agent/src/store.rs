@@ -1,8 +1,12 @@
 use crate::error::AgentError;
 use crate::job::JobState;
+use crate::session::{AgentSession, SessionContext};
+use chrono::Utc;
 use dashmap::DashMap;
 use opsml_settings::config::AgentSettings;
-use opsml_types::contracts::{InvokeMetadata, InvokeResponse, JobStatus};
+use opsml_sql::enums::client::SqlClientEnum;
+use opsml_sql::traits::AgentJobLogicTrait;
+use opsml_types::contracts::{AgentJobRecord, InvokeMetadata, InvokeResponse, JobStatus};
 use opsml_utils::create_uuid7;
 use scouter_client::potato_head::prelude::{
     Agent, AgentRunOutcome, AgentRunner, LoadedSpec, PotatoSpec, SessionState, SpecLoader,
@@ -11,17 +15,125 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
-use std::time::Instant;
-use tokio::sync::RwLock;
-use tracing::{info, warn};
+use std::time::{Duration, Instant};
+use tokio::sync::{RwLock, Semaphore};
+use tokio::task::{AbortHandle, JoinHandle};
+use tracing::{error, info, warn};
 
 const BUILTIN_SKILL_SCAN: &str = include_str!("../specs/skill-scan.yaml");
 
+/// How often the reaper sweeps finished jobs and idle sessions past their TTL
+const JOB_REAP_INTERVAL_SECS: u64 = 300;
+
+/// In-flight jobs whose heartbeat is older than this are failed by any instance, so jobs of a
+/// server that went away for good do not stay running forever
+const JOB_STALE_SECS: i64 = 3 * JOB_REAP_INTERVAL_SECS as i64;
+
+/// Recorded on jobs that were pending or running when the server went down
+const INTERRUPTED_JOB_ERROR: &str = "Job interrupted because the server running it stopped";
+
+/// What an invocation runs: a registered agent or a test mock
+enum AgentTarget {
+    Agent(Arc<Agent>),
+    /// Answers with `response`, first asking `prompt` when one is set
+    Mock {
+        response: Value,
+        prompt: Option<String>,
+    },
+}
+
+/// Outcome of a single turn
+struct RunOutput {
+    result: Option<Value>,
+    /// Set when the agent paused to ask for input
+    prompt: Option<String>,
+}
+
+impl RunOutput {
+    fn status(&self) -> JobStatus {
+        if self.prompt.is_some() {
+            JobStatus::NeedsInput
+        } else {
+            JobStatus::Done
+        }
+    }
+}
+
+impl AgentTarget {
+    /// Runs one turn against the session context, recording any question the agent asks
+    async fn run(
+        &self,
+        input: &str,
+        context: &mut SessionContext,
+    ) -> Result<RunOutput, AgentError> {
+        let output = match self {
+            AgentTarget::Mock { response, prompt } => match prompt {
+                Some(prompt) if context.pending_prompt.is_none() => RunOutput {
+                    result: None,
+                    prompt: Some(prompt.clone()),
+                },
+                _ => RunOutput {
+                    result: Some(response.clone()),
+                    prompt: None,
+                },
+            },
+            AgentTarget::Agent(agent) => {
+                let outcome = agent
+                    .run(input, &mut context.state)
+                    .await
+                    .map_err(|e| AgentError::Run(e.to_string()))?;
+
+                match outcome {
+                    AgentRunOutcome::Complete(run_result) => RunOutput {
+                        result: run_result.final_response.response_value(),
+                        prompt: None,
+                    },
+                    AgentRunOutcome::NeedsInput { prompt, .. } => RunOutput {
+                        result: None,
+                        prompt: Some(prompt),
+                    },
+                }
+            }
+        };
+
+        context.pending_prompt = output.prompt.clone();
+        Ok(output)
+    }
+}
+
+/// Writes job state to the job store, if one is configured. Failures are logged rather than
+/// surfaced because they happen on background workers with no caller to report to
+async fn persist_job(sql_client: &Option<Arc<SqlClientEnum>>, state: &JobState) {
+    if let Some(sql_client) = sql_client
+        && let Err(e) = sql_client
+            .upsert_agent_job(&AgentJobRecord::from(state))
+            .await
+    {
+        error!("Failed to persist agent job {}: {e}", state.id);
+    }
+}
+
 pub struct AgentStore {
     agents: HashMap<String, Arc<Agent>>,
     jobs: Arc<DashMap<String, Arc<RwLock<JobState>>>>,
+    /// Abort handles for async jobs that are queued or running
+    handles: Arc<DashMap<String, AbortHandle>>,
+    /// Bounds how many async jobs run at once
+    workers: Arc<Semaphore>,
+    /// How long finished jobs are kept before cleanup
+    job_ttl: chrono::Duration,
+    sessions: Arc<DashMap<String, Arc<AgentSession>>>,
+    /// How long a session may sit idle before it expires
+    session_ttl: chrono::Duration,
+    /// Persists job state so it survives restarts. Jobs only live in memory when unset
+    sql_client: Option<Arc<SqlClientEnum>>,
+    /// Recorded on persisted jobs so restart recovery and heartbeats only touch jobs this
+    /// server ran
+    instance_id: String,
     /// Test-only: pre-programmed responses that bypass the real LLM call.
     mock_responses: Arc<DashMap<String, Value>>,
+    /// Test-only: questions a mocked agent asks before returning its response.
+    mock_prompts: Arc<DashMap<String, String>>,
 }
 
 impl AgentStore {
@@ -53,10 +165,24 @@ impl AgentStore {
         Ok(Self {
             agents,
             jobs: Arc::new(DashMap::new()),
+            handles: Arc::new(DashMap::new()),
+            workers: Arc::new(Semaphore::new(settings.max_concurrent_jobs.max(1))),
+            job_ttl: chrono::Duration::seconds(settings.job_ttl_secs as i64),
+            sessions: Arc::new(DashMap::new()),
+            session_ttl: chrono::Duration::seconds(settings.session_ttl_secs as i64),
+            sql_client: None,
+            instance_id: settings.instance_id.clone(),
             mock_responses: Arc::new(DashMap::new()),
+            mock_prompts: Arc::new(DashMap::new()),
         })
     }
 
+    /// Persist async jobs through the given sql client
+    pub fn with_job_store(mut self, sql_client: Arc<SqlClientEnum>) -> Self {
+        self.sql_client = Some(sql_client);
+        self
+    }
+
     async fn load_spec_str(
         yaml: &str,
         agents: &mut HashMap<String, Arc<Agent>>,
@@ -112,48 +238,139 @@ impl AgentStore {
         self.mock_responses.insert(agent_id.to_string(), response);
     }
 
-    pub async fn invoke(&self, id: &str, input: &str) -> Result<InvokeResponse, AgentError> {
-        let job_id = create_uuid7();
-        let start = Instant::now();
+    /// Make a mocked agent ask `prompt` before returning its mock response, so the
+    /// answer has to come in a follow-up turn on the same session. Used in tests only.
+    pub fn set_mock_prompt(&self, agent_id: &str, prompt: &str) {
+        self.mock_prompts
+            .insert(agent_id.to_string(), prompt.to_string());
+    }
 
+    fn target(&self, id: &str) -> Result<AgentTarget, AgentError> {
         // Mock responses bypass the real LLM call
         if let Some(mock_ref) = self.mock_responses.get(id) {
-            let duration_ms = start.elapsed().as_millis() as u64;
-            let mock_value = mock_ref.clone();
-            return Ok(InvokeResponse {
-                job_id,
-                status: JobStatus::Done,
-                result: Some(mock_value),
-                metadata: InvokeMetadata {
-                    agent_id: id.to_string(),
-                    invocation: "sync".to_string(),
-                    duration_ms: Some(duration_ms),
-                },
+            return Ok(AgentTarget::Mock {
+                response: mock_ref.clone(),
+                prompt: self.mock_prompts.get(id).map(|prompt| prompt.clone()),
             });
         }
 
-        let agent = self
-            .agents
+        self.agents
             .get(id)
-            .ok_or_else(|| AgentError::NotFound(id.to_string()))?;
+            .map(|agent| AgentTarget::Agent(agent.clone()))
+            .ok_or_else(|| AgentError::NotFound(id.to_string()))
+    }
 
-        let mut session = SessionState::new();
-        let outcome = agent
-            .run(input, &mut session)
-            .await
-            .map_err(|e| AgentError::Run(e.to_string()))?;
+    /// Resume the given session, or start a new one when `session_id` is `None`.
+    /// Sessions are bound to the agent and owner that started them
+    pub fn open_session(
+        &self,
+        id: &str,
+        session_id: Option<&str>,
+        owner: &str,
+    ) -> Result<Arc<AgentSession>, AgentError> {
+        let Some(session_id) = session_id else {
+            let session = Arc::new(AgentSession::new(
+                create_uuid7(),
+                id.to_string(),
+                owner.to_string(),
+            ));
+            self.sessions.insert(session.id.clone(), session.clone());
+            return Ok(session);
+        };
 
+        let session = self
+            .sessions
+            .get(session_id)
+            .map(|entry| entry.clone())
+            .filter(|session| {
+                session.agent_id == id
+                    && session.owner == owner
+                    && !session.is_expired(self.session_ttl)
+            })
+            .ok_or_else(|| AgentError::SessionNotFound(session_id.to_string()))?;
+
+        session.touch();
+        Ok(session)
+    }
+
+    /// Session a turn runs in. Without a session id the turn runs in a temporary session that
+    /// is only kept if the agent asks for input, so the answer can resume it
+    fn turn_session(
+        &self,
+        id: &str,
+        session_id: Option<&str>,
+        owner: &str,
+    ) -> Result<Arc<AgentSession>, AgentError> {
+        match session_id {
+            Some(_) => self.open_session(id, session_id, owner),
+            None => Ok(Arc::new(AgentSession::new(
+                create_uuid7(),
+                id.to_string(),
+                owner.to_string(),
+            ))),
+        }
+    }
+
+    /// Run a one-off invocation that does not retain any session state
+    pub async fn invoke(&self, id: &str, input: &str) -> Result<InvokeResponse, AgentError> {
+        let job_id = create_uuid7();
+        let start = Instant::now();
+
+        let mut context = SessionContext {
+            state: SessionState::new(),
+            pending_prompt: None,
+        };
+        let output = self.target(id)?.run(input, &mut context).await?;
         let duration_ms = start.elapsed().as_millis() as u64;
 
-        let result = match outcome {
-            AgentRunOutcome::Complete(run_result) => run_result.final_response.response_value(),
-            AgentRunOutcome::NeedsInput { .. } => None,
+        Ok(InvokeResponse {
+            job_id,
+            status: output.status(),
+            result: output.result,
+            error: None,
+            session_id: None,
+            prompt: output.prompt,
+            metadata: InvokeMetadata {
+                agent_id: id.to_string(),
+                invocation: "sync".to_string(),
+                duration_ms: Some(duration_ms),
+            },
+        })
+    }
+
+    /// Run one turn of a conversation. If the agent answers with `NeedsInput`, invoke again
+    /// with the returned session id and the answer as input to resume
+    pub async fn invoke_in_session(
+        &self,
+        id: &str,
+        input: &str,
+        session_id: Option<&str>,
+        owner: &str,
+    ) -> Result<InvokeResponse, AgentError> {
+        let target = self.target(id)?;
+        let session = self.turn_session(id, session_id, owner)?;
+        let job_id = create_uuid7();
+        let start = Instant::now();
+
+        let output = {
+            let mut context = session.context.lock().await;
+            target.run(input, &mut context).await?
         };
+        session.touch();
+        let duration_ms = start.elapsed().as_millis() as u64;
+
+        let keep_session = session_id.is_some() || output.prompt.is_some();
+        if session_id.is_none() && keep_session {
+            self.sessions.insert(session.id.clone(), session.clone());
+        }
 
         Ok(InvokeResponse {
             job_id,
-            status: JobStatus::Done,
-            result,
+            status: output.status(),
+            result: output.result,
+            error: None,
+            session_id: keep_session.then(|| session.id.clone()),
+            prompt: output.prompt,
             metadata: InvokeMetadata {
                 agent_id: id.to_string(),
                 invocation: "sync".to_string(),
@@ -162,12 +379,232 @@ impl AgentStore {
         })
     }
 
-    // TODO: populate self.jobs in invoke() when async dispatch is implemented.
-    // Currently the jobs map is always empty; get_job always returns None.
+    /// Queue an agent turn on the worker pool and return the pending job immediately.
+    /// Poll `get_job` for the outcome. Sessions follow the same rules as `invoke_in_session`,
+    /// so a sessionless job only reports a session id once the agent asks for input
+    pub async fn invoke_async(
+        &self,
+        id: &str,
+        input: &str,
+        session_id: Option<&str>,
+        owner: &str,
+    ) -> Result<JobState, AgentError> {
+        let target = self.target(id)?;
+        let session = self.turn_session(id, session_id, owner)?;
+        let keep_session = session_id.is_some();
+        let state = JobState::new(
+            create_uuid7(),
+            id.to_string(),
+            owner.to_string(),
+            keep_session.then(|| session.id.clone()),
+            self.instance_id.clone(),
+        );
+        let job_id = state.id.clone();
+
+        if let Some(sql_client) = &self.sql_client {
+            sql_client
+                .upsert_agent_job(&AgentJobRecord::from(&state))
+                .await?;
+        }
+
+        let job = Arc::new(RwLock::new(state.clone()));
+        self.jobs.insert(job_id.clone(), job.clone());
+
+        let workers = self.workers.clone();
+        let handles = self.handles.clone();
+        let sql_client = self.sql_client.clone();
+        let sessions = self.sessions.clone();
+        let input = input.to_string();
+        let task_job_id = job_id.clone();
+
+        let task = tokio::spawn(async move {
+            // turns within a session run one at a time. Take the session lock before a worker
+            // permit so queued turns of a busy session do not hold workers other jobs could use
+            let mut context = session.context.lock().await;
+
+            // only fails if the semaphore is closed, which never happens
+            let Ok(_permit) = workers.acquire_owned().await else {
+                return;
+            };
+
+            {
+                let mut state = job.write().await;
+                if state.status.is_finished() {
+                    // cancelled while queued
+                    return;
+                }
+                state.transition(JobStatus::Running);
+                persist_job(&sql_client, &state).await;
+            }
+
+            let start = Instant::now();
+            let outcome = target.run(&input, &mut context).await;
+            drop(context);
+            session.touch();
+
+            let mut state = job.write().await;
+            if !state.status.is_finished() {
+                state.duration_ms = Some(start.elapsed().as_millis() as u64);
+                match outcome {
+                    Ok(output) => {
+                        let status = output.status();
+                        if !keep_session && output.prompt.is_some() {
+                            sessions.insert(session.id.clone(), session.clone());
+                            state.session_id = Some(session.id.clone());
+                        }
+                        state.result = output.result;
+                        state.prompt = output.prompt;
+                        state.transition(status);
+                    }
+                    Err(e) => {
+                        warn!("Agent job {} failed: {e}", state.id);
+                        state.error = Some(e.to_string());
+                        state.transition(JobStatus::Failed);
+                    }
+                }
+                persist_job(&sql_client, &state).await;
+            }
+            handles.remove(&task_job_id);
+        });
+
+        self.handles.insert(job_id.clone(), task.abort_handle());
+        if task.is_finished() {
+            self.handles.remove(&job_id);
+        }
+
+        Ok(state)
+    }
+
+    /// Look up a job, falling back to the job store for jobs no longer held in memory
     pub async fn get_job(&self, job_id: &str) -> Option<JobState> {
-        let entry = self.jobs.get(job_id)?;
-        let state = entry.read().await;
-        Some(state.clone())
+        if let Some(job) = self.jobs.get(job_id).map(|entry| entry.clone()) {
+            return Some(job.read().await.clone());
+        }
+
+        let sql_client = self.sql_client.as_ref()?;
+        match sql_client.get_agent_job(job_id).await {
+            Ok(record) => record.map(JobState::from),
+            Err(e) => {
+                error!("Failed to load agent job {job_id}: {e}");
+                None
+            }
+        }
+    }
+
+    /// Cancel a queued or running job. Finished jobs are returned unchanged
+    pub async fn cancel_job(&self, job_id: &str) -> Result<Option<JobState>, AgentError> {
+        let Some(job) = self.jobs.get(job_id).map(|entry| entry.clone()) else {
+            return Ok(self.get_job(job_id).await);
+        };
+
+        let mut state = job.write().await;
+        if state.status.is_finished() {
+            return Ok(Some(state.clone()));
+        }
+
+        if let Some((_, handle)) = self.handles.remove(job_id) {
+            handle.abort();
+        }
+
+        state.error = Some("Job cancelled".to_string());
+        state.transition(JobStatus::Cancelled);
+
+        if let Some(sql_client) = &self.sql_client {
+            sql_client
+                .upsert_agent_job(&AgentJobRecord::from(&*state))
+                .await?;
+        }
+
+        Ok(Some(state.clone()))
+    }
+
+    /// Mark jobs this instance left pending or running before a restart as failed, along with
+    /// in-flight jobs of any instance whose heartbeat has gone stale. Call once at startup
+    pub async fn recover_interrupted_jobs(&self) -> Result<u64, AgentError> {
+        self.fail_interrupted_jobs(Some(&self.instance_id)).await
+    }
+
+    async fn fail_interrupted_jobs(&self, instance_id: Option<&str>) -> Result<u64, AgentError> {
+        let Some(sql_client) = &self.sql_client else {
+            return Ok(0);
+        };
+
+        let stale_before = Utc::now() - chrono::Duration::seconds(JOB_STALE_SECS);
+        let recovered = sql_client
+            .fail_incomplete_agent_jobs(INTERRUPTED_JOB_ERROR, instance_id, &stale_before)
+            .await?;
+
+        if recovered > 0 {
+            warn!("Marked {recovered} interrupted agent jobs as failed");
+        }
+        Ok(recovered)
+    }
+
+    /// Drop finished jobs that have outlived the job TTL, in memory and in the job store
+    pub async fn reap_expired_jobs(&self) -> Result<(), AgentError> {
+        let cutoff = Utc::now() - self.job_ttl;
+
+        let jobs = self
+            .jobs
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect::<Vec<_>>();
+
+        for (job_id, job) in jobs {
+            let state = job.read().await;
+            if state.status.is_finished() && state.updated_at < cutoff {
+                self.jobs.remove(&job_id);
+                self.handles.remove(&job_id);
+            }
+        }
+
+        if let Some(sql_client) = &self.sql_client {
+            sql_client.delete_expired_agent_jobs(&cutoff).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Keep this instance's in-flight jobs fresh in the job store and fail those other
+    /// instances abandoned
+    pub async fn heartbeat_jobs(&self) -> Result<(), AgentError> {
+        let Some(sql_client) = &self.sql_client else {
+            return Ok(());
+        };
+
+        sql_client.heartbeat_agent_jobs(&self.instance_id).await?;
+        self.fail_interrupted_jobs(None).await?;
+
+        Ok(())
+    }
+
+    /// Drop sessions that have been idle longer than the session TTL. Sessions with a turn
+    /// in progress are kept
+    pub fn reap_expired_sessions(&self) {
+        self.sessions.retain(|_, session| {
+            !session.is_expired(self.session_ttl) || session.context.try_lock().is_err()
+        });
+    }
+
+    /// Periodically heartbeat in-flight jobs and reap expired jobs and sessions in the background
+    pub fn spawn_job_reaper(self: &Arc<Self>) -> JoinHandle<()> {
+        let store = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(Duration::from_secs(JOB_REAP_INTERVAL_SECS));
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                ticker.tick().await;
+
+                if let Err(e) = store.heartbeat_jobs().await {
+                    error!("Failed to heartbeat agent jobs: {e}");
+                }
+                if let Err(e) = store.reap_expired_jobs().await {
+                    error!("Failed to reap expired agent jobs: {e}");
+                }
+                store.reap_expired_sessions();
+            }
+        })
     }
 }
 
@@ -234,4 +671,203 @@ mod tests {
         assert_eq!(resp.status, JobStatus::Done);
         assert_eq!(resp.result.unwrap()["result"], "ok");
     }
+
+    async fn wait_for_job(store: &AgentStore, job_id: &str) -> JobState {
+        for _ in 0..100 {
+            let job = store.get_job(job_id).await.unwrap();
+            if job.status.is_finished() {
+                return job;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("job {job_id} did not finish");
+    }
+
+    #[tokio::test]
+    async fn test_invoke_async_tracks_job() {
+        let store = AgentStore::new(&AgentSettings::default()).await.unwrap();
+        store.set_mock_response("custom-agent", serde_json::json!({"result": "ok"}));
+
+        let job = store
+            .invoke_async("custom-agent", "input", None, "guest")
+            .await
+            .unwrap();
+        assert_eq!(job.status, JobStatus::Pending);
+        assert_eq!(job.owner, "guest");
+
+        let job = wait_for_job(&store, &job.id).await;
+        assert_eq!(job.status, JobStatus::Done);
+        assert_eq!(job.result.unwrap()["result"], "ok");
+        assert!(job.duration_ms.is_some());
+
+        let err = store
+            .invoke_async("does-not-exist", "input", None, "guest")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AgentError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_queued_job() {
+        let settings = AgentSettings {
+            max_concurrent_jobs: 1,
+            ..Default::default()
+        };
+        let store = AgentStore::new(&settings).await.unwrap();
+        store.set_mock_response("custom-agent", serde_json::json!({"result": "ok"}));
+
+        // occupy the only worker so the job stays queued
+        let permit = store.workers.clone().acquire_owned().await.unwrap();
+        let job = store
+            .invoke_async("custom-agent", "input", None, "guest")
+            .await
+            .unwrap();
+
+        let cancelled = store.cancel_job(&job.id).await.unwrap().unwrap();
+        assert_eq!(cancelled.status, JobStatus::Cancelled);
+        drop(permit);
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let job = store.get_job(&job.id).await.unwrap();
+        assert_eq!(job.status, JobStatus::Cancelled);
+        assert!(job.result.is_none());
+
+        assert!(store.cancel_job("missing").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reap_expired_jobs() {
+        let settings = AgentSettings {
+            job_ttl_secs: 0,
+            ..Default::default()
+        };
+        let store = AgentStore::new(&settings).await.unwrap();
+        store.set_mock_response("custom-agent", serde_json::json!({"result": "ok"}));
+
+        let job = store
+            .invoke_async("custom-agent", "input", None, "guest")
+            .await
+            .unwrap();
+        wait_for_job(&store, &job.id).await;
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        store.reap_expired_jobs().await.unwrap();
+        assert!(store.get_job(&job.id).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_session_resumes_after_needs_input() {
+        let store = AgentStore::new(&AgentSettings::default()).await.unwrap();
+        store.set_mock_response("custom-agent", serde_json::json!({"result": "ok"}));
+        store.set_mock_prompt("custom-agent", "Which environment?");
+
+        let resp = store
+            .invoke_in_session("custom-agent", "deploy", None, "guest")
+            .await
+            .unwrap();
+        assert_eq!(resp.status, JobStatus::NeedsInput);
+        assert_eq!(resp.prompt.as_deref(), Some("Which environment?"));
+        assert!(resp.result.is_none());
+        let session_id = resp.session_id.unwrap();
+
+        // answering in the same session resumes the run
+        let resp = store
+            .invoke_in_session("custom-agent", "staging", Some(&session_id), "guest")
+            .await
+            .unwrap();
+        assert_eq!(resp.status, JobStatus::Done);
+        assert_eq!(resp.session_id.as_deref(), Some(session_id.as_str()));
+        assert_eq!(resp.result.unwrap()["result"], "ok");
+
+        // sessions are bound to their owner
+        let err = store
+            .invoke_in_session("custom-agent", "staging", Some(&session_id), "other")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AgentError::SessionNotFound(_)));
+
+        // one-off invocations surface the question without keeping a session
+        let resp = store.invoke("custom-agent", "deploy").await.unwrap();
+        assert_eq!(resp.status, JobStatus::NeedsInput);
+        assert!(resp.session_id.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sessionless_turn_is_not_kept() {
+        let store = AgentStore::new(&AgentSettings::default()).await.unwrap();
+        store.set_mock_response("custom-agent", serde_json::json!({"result": "ok"}));
+
+        let resp = store
+            .invoke_in_session("custom-agent", "input", None, "guest")
+            .await
+            .unwrap();
+        assert_eq!(resp.status, JobStatus::Done);
+        assert!(resp.session_id.is_none());
+        assert!(store.sessions.is_empty());
+
+        let job = store
+            .invoke_async("custom-agent", "input", None, "guest")
+            .await
+            .unwrap();
+        assert!(job.session_id.is_none());
+        let job = wait_for_job(&store, &job.id).await;
+        assert_eq!(job.status, JobStatus::Done);
+        assert!(job.session_id.is_none());
+        assert!(store.sessions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_async_job_needs_input() {
+        let store = AgentStore::new(&AgentSettings::default()).await.unwrap();
+        store.set_mock_response("custom-agent", serde_json::json!({"result": "ok"}));
+        store.set_mock_prompt("custom-agent", "Which environment?");
+
+        let job = store
+            .invoke_async("custom-agent", "deploy", None, "guest")
+            .await
+            .unwrap();
+        let job = wait_for_job(&store, &job.id).await;
+        assert_eq!(job.status, JobStatus::NeedsInput);
+        assert_eq!(job.prompt.as_deref(), Some("Which environment?"));
+
+        let job = store
+            .invoke_async(
+                "custom-agent",
+                "staging",
+                job.session_id.as_deref(),
+                "guest",
+            )
+            .await
+            .unwrap();
+        let job = wait_for_job(&store, &job.id).await;
+        assert_eq!(job.status, JobStatus::Done);
+        assert!(job.prompt.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sessions_expire() {
+        let settings = AgentSettings {
+            session_ttl_secs: 0,
+            ..Default::default()
+        };
+        let store = AgentStore::new(&settings).await.unwrap();
+        store.set_mock_response("custom-agent", serde_json::json!({"result": "ok"}));
+        store.set_mock_prompt("custom-agent", "Which environment?");
+
+        let resp = store
+            .invoke_in_session("custom-agent", "input", None, "guest")
+            .await
+            .unwrap();
+        let session_id = resp.session_id.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let err = store
+            .invoke_in_session("custom-agent", "input", Some(&session_id), "guest")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AgentError::SessionNotFound(_)));
+
+        store.reap_expired_sessions();
+        assert!(store.sessions.is_empty());
+    }
 }
@@ -0,0 +1,248 @@
+//! `{{ ... }}` templating for workflow step inputs, prompts, conditions and outputs.
+//!
+//! An expression is a dotted path rooted at either `inputs` (the workflow inputs) or the
+//! name of a completed step (that step's output), e.g. `{{ inputs.query }}` or
+//! `{{ classify.label }}`. Array elements are addressed by index: `{{ search.hits.0 }}`.
+use crate::error::WorkflowError;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+const OPEN: &str = "{{";
+const CLOSE: &str = "}}";
+
+/// Values visible to templates while a workflow runs
+pub struct TemplateContext<'a> {
+    pub inputs: &'a Map<String, Value>,
+    pub steps: &'a HashMap<String, Value>,
+}
+
+impl TemplateContext<'_> {
+    fn lookup(&self, expr: &str) -> Result<Value, WorkflowError> {
+        let mut parts = expr.split('.').map(str::trim);
+        let root = parts.next().unwrap_or_default();
+
+        let mut current = if root == "inputs" {
+            let Some(name) = parts.next() else {
+                return Ok(Value::Object(self.inputs.clone()));
+            };
+            self.inputs.get(name).ok_or_else(|| {
+                WorkflowError::Template(format!("unknown workflow input '{name}'"))
+            })?
+        } else {
+            self.steps.get(root).ok_or_else(|| {
+                WorkflowError::Template(format!("'{root}' is not an input or a completed step"))
+            })?
+        };
+
+        for part in parts {
+            current = match current {
+                // Skipped steps produce null; anything read from them is null too
+                Value::Null => return Ok(Value::Null),
+                Value::Object(map) => map.get(part),
+                Value::Array(items) => part.parse::<usize>().ok().and_then(|i| items.get(i)),
+                _ => None,
+            }
+            .ok_or_else(|| {
+                WorkflowError::Template(format!("'{expr}' does not resolve to a value"))
+            })?;
+        }
+
+        Ok(current.clone())
+    }
+}
+
+/// Split a template into literal text and expressions
+fn parse(template: &str) -> Result<Vec<Segment<'_>>, WorkflowError> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find(OPEN) {
+        if start > 0 {
+            segments.push(Segment::Text(&rest[..start]));
+        }
+        let after = &rest[start + OPEN.len()..];
+        let end = after
+            .find(CLOSE)
+            .ok_or_else(|| WorkflowError::Template(format!("unclosed '{OPEN}' in '{template}'")))?;
+        let expr = after[..end].trim();
+        if expr.is_empty() {
+            return Err(WorkflowError::Template(format!(
+                "empty expression in '{template}'"
+            )));
+        }
+        segments.push(Segment::Expr(expr));
+        rest = &after[end + CLOSE.len()..];
+    }
+
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest));
+    }
+    Ok(segments)
+}
+
+enum Segment<'a> {
+    Text(&'a str),
+    Expr(&'a str),
+}
+
+fn to_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Render a template to a JSON value.
+///
+/// A template consisting of a single expression keeps the referenced value's type, so
+/// `"{{ search.hits }}"` passes an array through unchanged. Anything else is rendered as a string.
+pub fn render(template: &str, ctx: &TemplateContext<'_>) -> Result<Value, WorkflowError> {
+    let segments = parse(template)?;
+    if let [Segment::Expr(expr)] = segments.as_slice() {
+        return ctx.lookup(expr);
+    }
+    render_segments(&segments, ctx).map(Value::String)
+}
+
+/// Render a template to a string, interpolating every expression
+pub fn render_string(template: &str, ctx: &TemplateContext<'_>) -> Result<String, WorkflowError> {
+    render_segments(&parse(template)?, ctx)
+}
+
+fn render_segments(
+    segments: &[Segment<'_>],
+    ctx: &TemplateContext<'_>,
+) -> Result<String, WorkflowError> {
+    let mut out = String::new();
+    for segment in segments {
+        match segment {
+            Segment::Text(text) => out.push_str(text),
+            Segment::Expr(expr) => out.push_str(&to_text(&ctx.lookup(expr)?)),
+        }
+    }
+    Ok(out)
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !matches!(
+            s.trim().to_lowercase().as_str(),
+            "" | "false" | "0" | "no" | "null"
+        ),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+fn unquote(s: &str) -> &str {
+    let s = s.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = s.strip_prefix(quote).and_then(|s| s.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+    s
+}
+
+/// Evaluate a step condition.
+///
+/// Supports `lhs == rhs` and `lhs != rhs` string comparisons (quotes around either side are
+/// ignored), otherwise the rendered value is tested for truthiness. `""`, `false`, `0`, `no`,
+/// `null` and empty arrays/objects are false.
+pub fn evaluate_condition(
+    condition: &str,
+    ctx: &TemplateContext<'_>,
+) -> Result<bool, WorkflowError> {
+    for (op, equal) in [(" == ", true), (" != ", false)] {
+        if let Some((lhs, rhs)) = condition.split_once(op) {
+            let lhs = render_string(lhs, ctx)?;
+            let rhs = render_string(rhs, ctx)?;
+            return Ok((unquote(&lhs) == unquote(&rhs)) == equal);
+        }
+    }
+
+    Ok(is_truthy(&render(condition.trim(), ctx)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fixtures() -> (Map<String, Value>, HashMap<String, Value>) {
+        let inputs = json!({"query": "refunds", "limit": 3})
+            .as_object()
+            .unwrap()
+            .clone();
+        let steps = HashMap::from([
+            (
+                "search".to_string(),
+                json!({"hits": ["a", "b"], "count": 2, "label": "spam"}),
+            ),
+            ("skipped".to_string(), Value::Null),
+        ]);
+        (inputs, steps)
+    }
+
+    #[test]
+    fn test_render_keeps_type_for_single_expression() {
+        let (inputs, steps) = fixtures();
+        let ctx = TemplateContext {
+            inputs: &inputs,
+            steps: &steps,
+        };
+
+        assert_eq!(render("{{ inputs.limit }}", &ctx).unwrap(), json!(3));
+        assert_eq!(render("{{search.hits}}", &ctx).unwrap(), json!(["a", "b"]));
+        assert_eq!(render("{{ search.hits.1 }}", &ctx).unwrap(), json!("b"));
+        assert_eq!(render("plain", &ctx).unwrap(), json!("plain"));
+    }
+
+    #[test]
+    fn test_render_interpolates_strings() {
+        let (inputs, steps) = fixtures();
+        let ctx = TemplateContext {
+            inputs: &inputs,
+            steps: &steps,
+        };
+
+        assert_eq!(
+            render("Find {{ inputs.query }} ({{ search.count }} hits)", &ctx).unwrap(),
+            json!("Find refunds (2 hits)")
+        );
+        assert_eq!(render_string("{{ skipped.text }}", &ctx).unwrap(), "");
+    }
+
+    #[test]
+    fn test_render_errors() {
+        let (inputs, steps) = fixtures();
+        let ctx = TemplateContext {
+            inputs: &inputs,
+            steps: &steps,
+        };
+
+        assert!(render("{{ inputs.missing }}", &ctx).is_err());
+        assert!(render("{{ unknown.value }}", &ctx).is_err());
+        assert!(render("{{ search.nope }}", &ctx).is_err());
+        assert!(render("{{ inputs.query", &ctx).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_condition() {
+        let (inputs, steps) = fixtures();
+        let ctx = TemplateContext {
+            inputs: &inputs,
+            steps: &steps,
+        };
+
+        assert!(evaluate_condition("{{ search.label }} == \"spam\"", &ctx).unwrap());
+        assert!(!evaluate_condition("{{ search.label }} != 'spam'", &ctx).unwrap());
+        assert!(evaluate_condition("{{ search.hits }}", &ctx).unwrap());
+        assert!(!evaluate_condition("{{ skipped }}", &ctx).unwrap());
+        assert!(!evaluate_condition("false", &ctx).unwrap());
+    }
+}
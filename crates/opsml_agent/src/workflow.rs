@@ -0,0 +1,776 @@
+//! Executes a [`WorkflowSpec`] as a DAG.
+//!
+//! The runner owns scheduling, templating, conditions and timeouts. Resolving card references
+//! and actually running a step are delegated to a [`StepExecutor`], so the same runner works
+//! against the server's registries and agent store or a test double.
+use crate::error::WorkflowError;
+use crate::template::{TemplateContext, evaluate_condition, render, render_string};
+use async_trait::async_trait;
+use chrono::Utc;
+use opsml_types::RegistryType;
+use opsml_types::contracts::{
+    CardRef, StepStatus, StepTrace, ToolSpec, WorkflowRun, WorkflowRunStatus, WorkflowSpec,
+    WorkflowStep,
+};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
+use tracing::{debug, instrument, warn};
+
+const DEFAULT_MAX_PARALLEL: usize = 4;
+
+/// A card reference pinned to a concrete registered version
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedCard {
+    pub space: String,
+    pub name: String,
+    pub version: String,
+    pub uid: String,
+}
+
+impl std::fmt::Display for ResolvedCard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}@{}", self.space, self.name, self.version)
+    }
+}
+
+/// The action a step performs once its card references are resolved
+#[derive(Debug, Clone)]
+pub enum StepAction {
+    Skill(ResolvedCard),
+    Agent(ResolvedCard),
+    Mcp(ResolvedCard),
+    Tool(Box<ToolSpec>),
+    /// A templated prompt, already rendered
+    Prompt(String),
+}
+
+impl StepAction {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            StepAction::Skill(_) => "skill",
+            StepAction::Agent(_) => "agent",
+            StepAction::Mcp(_) => "mcp",
+            StepAction::Tool(_) => "tool",
+            StepAction::Prompt(_) => "prompt",
+        }
+    }
+
+    pub fn card(&self) -> Option<&ResolvedCard> {
+        match self {
+            StepAction::Skill(card) | StepAction::Agent(card) | StepAction::Mcp(card) => Some(card),
+            StepAction::Tool(_) | StepAction::Prompt(_) => None,
+        }
+    }
+}
+
+/// Backend used by [`WorkflowRunner`] to look up cards and run step actions
+#[async_trait]
+pub trait StepExecutor: Send + Sync {
+    /// Resolve a card reference to the version that will be executed
+    async fn resolve(
+        &self,
+        card: &CardRef,
+        registry_type: &RegistryType,
+    ) -> Result<ResolvedCard, WorkflowError>;
+
+    /// Reject an action this executor cannot run. Called for every step before the run starts,
+    /// so an unsupported step fails the whole workflow instead of stopping it part way through
+    fn check(&self, _action: &StepAction) -> Result<(), WorkflowError> {
+        Ok(())
+    }
+
+    /// Run a single step with its rendered inputs and return the step output
+    async fn execute(
+        &self,
+        action: &StepAction,
+        inputs: &Map<String, Value>,
+    ) -> Result<Value, WorkflowError>;
+}
+
+/// Result of a spawned step, keyed back to the step index
+struct StepResult {
+    index: usize,
+    started_at: chrono::DateTime<Utc>,
+    duration_ms: u64,
+    outcome: Result<Value, (StepStatus, String)>,
+}
+
+pub struct WorkflowRunner {
+    executor: Arc<dyn StepExecutor>,
+    max_parallel: usize,
+}
+
+impl WorkflowRunner {
+    pub fn new(executor: Arc<dyn StepExecutor>) -> Self {
+        Self {
+            executor,
+            max_parallel: DEFAULT_MAX_PARALLEL,
+        }
+    }
+
+    /// Maximum number of steps executing at once
+    pub fn with_max_parallel(mut self, max_parallel: usize) -> Self {
+        self.max_parallel = max_parallel.max(1);
+        self
+    }
+
+    /// Run a workflow to completion.
+    ///
+    /// Invalid specs, bad inputs, unresolvable cards and steps the executor cannot run are
+    /// returned as errors before any step runs. Once execution starts, step failures are reported in the returned [`WorkflowRun`]:
+    /// the first failure stops new steps from being scheduled, steps already running are allowed
+    /// to finish, and everything that never ran is marked `Cancelled`.
+    #[instrument(skip_all)]
+    pub async fn run(
+        &self,
+        spec: &WorkflowSpec,
+        inputs: Map<String, Value>,
+    ) -> Result<WorkflowRun, WorkflowError> {
+        let started = Instant::now();
+
+        spec.validate()
+            .map_err(|e| WorkflowError::Invalid(e.to_string()))?;
+        if spec.steps.iter().any(|s| s.name == "inputs") {
+            return Err(WorkflowError::Invalid(
+                "'inputs' is reserved and cannot be used as a step name".into(),
+            ));
+        }
+
+        let inputs = resolve_inputs(spec, inputs)?;
+        let actions = self.resolve_actions(spec).await?;
+        for action in &actions {
+            self.executor.check(action)?;
+        }
+
+        let n = spec.steps.len();
+        let index: HashMap<&str, usize> = spec
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.name.as_str(), i))
+            .collect();
+        let mut pending_deps: Vec<usize> = spec.steps.iter().map(|s| s.depends_on.len()).collect();
+        let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); n];
+        for (i, step) in spec.steps.iter().enumerate() {
+            for dep in &step.depends_on {
+                dependents[index[dep.as_str()]].push(i);
+            }
+        }
+
+        let mut ready: VecDeque<usize> = (0..n).filter(|&i| pending_deps[i] == 0).collect();
+        let mut traces: Vec<Option<StepTrace>> = vec![None; n];
+        let mut step_outputs: HashMap<String, Value> = HashMap::new();
+        let mut failure: Option<String> = None;
+        let mut running: JoinSet<StepResult> = JoinSet::new();
+
+        let mut release = |i: usize, ready: &mut VecDeque<usize>| {
+            for &d in &dependents[i] {
+                pending_deps[d] -= 1;
+                if pending_deps[d] == 0 {
+                    ready.push_back(d);
+                }
+            }
+        };
+
+        loop {
+            while failure.is_none() && running.len() < self.max_parallel {
+                let Some(i) = ready.pop_front() else {
+                    break;
+                };
+                let step = &spec.steps[i];
+                let mut trace = new_trace(step, &actions[i]);
+                let ctx = TemplateContext {
+                    inputs: &inputs,
+                    steps: &step_outputs,
+                };
+
+                let prepared = match &step.condition {
+                    Some(condition) => evaluate_condition(condition, &ctx),
+                    None => Ok(true),
+                }
+                .and_then(|run| {
+                    if run {
+                        prepare_step(step, &actions[i], &ctx).map(Some)
+                    } else {
+                        Ok(None)
+                    }
+                });
+
+                match prepared {
+                    Ok(Some((action, step_inputs))) => {
+                        debug!("Starting workflow step '{}'", step.name);
+                        trace.inputs = step_inputs.clone();
+                        traces[i] = Some(trace);
+
+                        let executor = self.executor.clone();
+                        let timeout = step.timeout_seconds.map(Duration::from_secs);
+                        running.spawn(async move {
+                            execute_step(executor, i, action, step_inputs, timeout).await
+                        });
+                    }
+                    Ok(None) => {
+                        debug!("Skipping workflow step '{}'", step.name);
+                        trace.status = StepStatus::Skipped;
+                        traces[i] = Some(trace);
+                        step_outputs.insert(step.name.clone(), Value::Null);
+                        release(i, &mut ready);
+                    }
+                    Err(e) => {
+                        trace.status = StepStatus::Failed;
+                        trace.error = Some(e.to_string());
+                        traces[i] = Some(trace);
+                        failure = Some(format!("step '{}' failed: {e}", step.name));
+                    }
+                }
+            }
+
+            let Some(joined) = running.join_next().await else {
+                break;
+            };
+
+            let result = match joined {
+                Ok(result) => result,
+                Err(e) => {
+                    // A panicking executor loses its step index; the step is reported as cancelled
+                    warn!("Workflow step task failed: {e}");
+                    failure.get_or_insert_with(|| format!("workflow step task failed: {e}"));
+                    continue;
+                }
+            };
+
+            let step = &spec.steps[result.index];
+            let trace = traces[result.index]
+                .as_mut()
+                .expect("trace is recorded when a step starts");
+            trace.started_at = Some(result.started_at);
+            trace.duration_ms = Some(result.duration_ms);
+
+            match result
+                .outcome
+                .and_then(|value| shape_output(step, value).map_err(|e| (StepStatus::Failed, e)))
+            {
+                Ok(value) => {
+                    trace.status = StepStatus::Succeeded;
+                    trace.output = Some(value.clone());
+                    step_outputs.insert(step.name.clone(), value);
+                    release(result.index, &mut ready);
+                }
+                Err((status, error)) => {
+                    warn!("Workflow step '{}' {status}: {error}", step.name);
+                    trace.status = status;
+                    failure.get_or_insert_with(|| format!("step '{}' failed: {error}", step.name));
+                    trace.error = Some(error);
+                }
+            }
+        }
+
+        let steps: Vec<StepTrace> = traces
+            .into_iter()
+            .enumerate()
+            .map(|(i, trace)| trace.unwrap_or_else(|| new_trace(&spec.steps[i], &actions[i])))
+            .collect();
+
+        let mut outputs = Map::new();
+        if failure.is_none() {
+            let ctx = TemplateContext {
+                inputs: &inputs,
+                steps: &step_outputs,
+            };
+            for (name, template) in &spec.outputs {
+                match render(template, &ctx) {
+                    Ok(value) => {
+                        outputs.insert(name.clone(), value);
+                    }
+                    Err(e) => {
+                        failure = Some(format!("workflow output '{name}': {e}"));
+                        outputs.clear();
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(WorkflowRun {
+            status: if failure.is_none() {
+                WorkflowRunStatus::Succeeded
+            } else {
+                WorkflowRunStatus::Failed
+            },
+            steps,
+            outputs,
+            error: failure,
+            duration_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// Resolve every card reference up front so a missing card fails the run before any step executes
+    async fn resolve_actions(&self, spec: &WorkflowSpec) -> Result<Vec<StepAction>, WorkflowError> {
+        let mut actions = Vec::with_capacity(spec.steps.len());
+        for step in &spec.steps {
+            let action = if let Some(card) = &step.skill {
+                StepAction::Skill(self.executor.resolve(card, &RegistryType::Skill).await?)
+            } else if let Some(card) = &step.agent {
+                StepAction::Agent(self.executor.resolve(card, &RegistryType::Agent).await?)
+            } else if let Some(card) = &step.mcp {
+                StepAction::Mcp(self.executor.resolve(card, &RegistryType::Mcp).await?)
+            } else if let Some(tool) = &step.tool {
+                StepAction::Tool(Box::new(tool.clone()))
+            } else {
+                // validate() guarantees at least one action is set
+                StepAction::Prompt(step.prompt.clone().unwrap_or_default())
+            };
+            actions.push(action);
+        }
+        Ok(actions)
+    }
+}
+
+/// Trace for a step that has not run yet. Steps left in this state are reported as cancelled
+fn new_trace(step: &WorkflowStep, action: &StepAction) -> StepTrace {
+    StepTrace {
+        name: step.name.clone(),
+        status: StepStatus::Cancelled,
+        action: action.kind().to_string(),
+        card: action.card().map(|c| c.to_string()),
+        inputs: Map::new(),
+        output: None,
+        error: None,
+        started_at: None,
+        duration_ms: None,
+    }
+}
+
+/// Render a step's inputs, and its prompt when set. A prompt on a step with another action is
+/// passed to that action as the `prompt` input.
+fn prepare_step(
+    step: &WorkflowStep,
+    action: &StepAction,
+    ctx: &TemplateContext<'_>,
+) -> Result<(StepAction, Map<String, Value>), WorkflowError> {
+    let mut inputs = Map::new();
+    for (key, template) in &step.inputs {
+        inputs.insert(key.clone(), render(template, ctx)?);
+    }
+
+    let prompt = step
+        .prompt
+        .as_deref()
+        .map(|p| render_string(p, ctx))
+        .transpose()?;
+
+    let action = match (action, prompt) {
+        (StepAction::Prompt(_), Some(prompt)) => StepAction::Prompt(prompt),
+        (action, Some(prompt)) => {
+            inputs.insert("prompt".to_string(), Value::String(prompt));
+            action.clone()
+        }
+        (action, None) => action.clone(),
+    };
+
+    Ok((action, inputs))
+}
+
+async fn execute_step(
+    executor: Arc<dyn StepExecutor>,
+    index: usize,
+    action: StepAction,
+    inputs: Map<String, Value>,
+    timeout: Option<Duration>,
+) -> StepResult {
+    let started_at = Utc::now();
+    let start = Instant::now();
+
+    let execution = executor.execute(&action, &inputs);
+    let outcome = match timeout {
+        Some(limit) => match tokio::time::timeout(limit, execution).await {
+            Ok(result) => result.map_err(|e| (StepStatus::Failed, e.to_string())),
+            Err(_) => Err((
+                StepStatus::TimedOut,
+                format!("timed out after {}s", limit.as_secs()),
+            )),
+        },
+        None => execution
+            .await
+            .map_err(|e| (StepStatus::Failed, e.to_string())),
+    };
+
+    StepResult {
+        index,
+        started_at,
+        duration_ms: start.elapsed().as_millis() as u64,
+        outcome,
+    }
+}
+
+/// Check a step's output against its declared `outputs`.
+///
+/// Object outputs must contain every declared key. A non-object output is wrapped under the
+/// declared name when the step declares exactly one output.
+fn shape_output(step: &WorkflowStep, value: Value) -> Result<Value, String> {
+    if step.outputs.is_empty() {
+        return Ok(value);
+    }
+
+    match value {
+        Value::Object(map) => {
+            if let Some(missing) = step.outputs.iter().find(|key| !map.contains_key(*key)) {
+                return Err(format!("output is missing declared key '{missing}'"));
+            }
+            Ok(Value::Object(map))
+        }
+        value if step.outputs.len() == 1 => {
+            let mut map = Map::new();
+            map.insert(step.outputs[0].clone(), value);
+            Ok(Value::Object(map))
+        }
+        _ => Err(format!(
+            "output must be an object with keys {:?}",
+            step.outputs
+        )),
+    }
+}
+
+/// Apply defaults, enforce required inputs and coerce values to their declared type.
+///
+/// Values arriving as strings (e.g. from `--input key=value` on the CLI) are parsed for
+/// `number`, `boolean` and `json` inputs. Optional inputs without a value are set to null.
+fn resolve_inputs(
+    spec: &WorkflowSpec,
+    mut provided: Map<String, Value>,
+) -> Result<Map<String, Value>, WorkflowError> {
+    if let Some(unknown) = provided.keys().find(|k| !spec.inputs.contains_key(*k)) {
+        return Err(WorkflowError::InvalidInput(format!(
+            "'{unknown}' is not declared by the workflow"
+        )));
+    }
+
+    let mut resolved = Map::new();
+    for (name, input) in &spec.inputs {
+        let value = match provided.remove(name).or_else(|| input.default.clone()) {
+            Some(value) => coerce_input(name, &input.input_type, value)?,
+            None if input.required => return Err(WorkflowError::MissingInput(name.clone())),
+            None => Value::Null,
+        };
+        resolved.insert(name.clone(), value);
+    }
+    Ok(resolved)
+}
+
+fn coerce_input(name: &str, input_type: &str, value: Value) -> Result<Value, WorkflowError> {
+    let invalid = || WorkflowError::InvalidInput(format!("'{name}' must be of type {input_type}"));
+
+    match (input_type, value) {
+        ("string", value @ Value::String(_)) => Ok(value),
+        ("number", value @ Value::Number(_)) => Ok(value),
+        ("number", Value::String(s)) => serde_json::from_str::<serde_json::Number>(s.trim())
+            .map(Value::Number)
+            .map_err(|_| invalid()),
+        ("boolean", value @ Value::Bool(_)) => Ok(value),
+        ("boolean", Value::String(s)) => match s.trim().to_lowercase().as_str() {
+            "true" => Ok(Value::Bool(true)),
+            "false" => Ok(Value::Bool(false)),
+            _ => Err(invalid()),
+        },
+        ("json", Value::String(s)) => Ok(serde_json::from_str(&s).unwrap_or(Value::String(s))),
+        ("string" | "number" | "boolean", _) => Err(invalid()),
+        (_, value) => Ok(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opsml_types::contracts::WorkflowInput;
+    use serde_json::json;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Echoes its inputs back, tracking concurrency. Agent steps named `slow` sleep, agents
+    /// named `broken` fail and tool steps are unsupported.
+    #[derive(Default)]
+    struct EchoExecutor {
+        active: AtomicUsize,
+        peak: AtomicUsize,
+        order: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl StepExecutor for EchoExecutor {
+        async fn resolve(
+            &self,
+            card: &CardRef,
+            _registry_type: &RegistryType,
+        ) -> Result<ResolvedCard, WorkflowError> {
+            if card.name == "missing" {
+                return Err(WorkflowError::Resolve(card.name.clone()));
+            }
+            Ok(ResolvedCard {
+                space: card.space.clone(),
+                name: card.name.clone(),
+                version: card.version.clone().unwrap_or_else(|| "1.0.0".into()),
+                uid: format!("uid-{}", card.name),
+            })
+        }
+
+        fn check(&self, action: &StepAction) -> Result<(), WorkflowError> {
+            match action {
+                StepAction::Tool(tool) => Err(WorkflowError::Unsupported(tool.name.clone())),
+                _ => Ok(()),
+            }
+        }
+
+        async fn execute(
+            &self,
+            action: &StepAction,
+            inputs: &Map<String, Value>,
+        ) -> Result<Value, WorkflowError> {
+            let name = match action {
+                StepAction::Prompt(text) => return Ok(json!({ "text": text })),
+                other => other.card().map(|c| c.name.clone()).unwrap_or_default(),
+            };
+
+            let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(active, Ordering::SeqCst);
+            if name == "slow" {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+            } else {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            self.active.fetch_sub(1, Ordering::SeqCst);
+            self.order.lock().unwrap().push(name.clone());
+
+            if name == "broken" {
+                return Err(WorkflowError::Step("agent exploded".into()));
+            }
+            Ok(Value::Object(inputs.clone()))
+        }
+    }
+
+    fn agent_step(name: &str, agent: &str, depends_on: &[&str]) -> WorkflowStep {
+        WorkflowStep {
+            name: name.to_string(),
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            agent: Some(CardRef {
+                space: "space".into(),
+                name: agent.into(),
+                version: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn runner(executor: &Arc<EchoExecutor>) -> WorkflowRunner {
+        WorkflowRunner::new(executor.clone())
+    }
+
+    #[tokio::test]
+    async fn test_run_templates_outputs_in_dependency_order() {
+        let executor = Arc::new(EchoExecutor::default());
+        let mut first = agent_step("first", "a", &[]);
+        first.inputs = HashMap::from([("value".into(), "{{ inputs.count }}".into())]);
+        let mut second = agent_step("second", "b", &["first"]);
+        second.inputs = HashMap::from([("text".into(), "got {{ first.value }}".into())]);
+
+        let spec = WorkflowSpec {
+            inputs: HashMap::from([(
+                "count".into(),
+                WorkflowInput {
+                    input_type: "number".into(),
+                    required: true,
+                    ..Default::default()
+                },
+            )]),
+            steps: vec![second, first],
+            outputs: HashMap::from([("result".into(), "{{ second.text }}".into())]),
+        };
+
+        let inputs = json!({"count": "7"}).as_object().unwrap().clone();
+        let run = runner(&executor).run(&spec, inputs).await.unwrap();
+
+        assert_eq!(run.status, WorkflowRunStatus::Succeeded);
+        assert_eq!(*executor.order.lock().unwrap(), vec!["a", "b"]);
+        // traces follow spec order, not execution order
+        assert_eq!(run.steps[0].name, "second");
+        assert_eq!(run.steps[1].output, Some(json!({"value": 7})));
+        assert_eq!(run.steps[1].card.as_deref(), Some("space/a@1.0.0"));
+        assert_eq!(run.outputs["result"], json!("got 7"));
+    }
+
+    #[tokio::test]
+    async fn test_run_parallelism_is_bounded() {
+        let executor = Arc::new(EchoExecutor::default());
+        let spec = WorkflowSpec {
+            steps: (0..6)
+                .map(|i| agent_step(&format!("s{i}"), "slow", &[]))
+                .collect(),
+            ..Default::default()
+        };
+
+        let started = Instant::now();
+        let run = runner(&executor)
+            .with_max_parallel(3)
+            .run(&spec, Map::new())
+            .await
+            .unwrap();
+
+        assert_eq!(run.status, WorkflowRunStatus::Succeeded);
+        assert_eq!(executor.peak.load(Ordering::SeqCst), 3);
+        assert!(started.elapsed() < Duration::from_millis(1000));
+    }
+
+    #[tokio::test]
+    async fn test_run_condition_skips_step() {
+        let executor = Arc::new(EchoExecutor::default());
+        let mut classify = agent_step("classify", "a", &[]);
+        classify.inputs = HashMap::from([("label".into(), "ham".into())]);
+        let mut escalate = agent_step("escalate", "b", &["classify"]);
+        escalate.condition = Some("{{ classify.label }} == 'spam'".into());
+        let mut summarize = agent_step("summarize", "c", &["escalate"]);
+        summarize.inputs = HashMap::from([("escalated".into(), "{{ escalate.label }}".into())]);
+
+        let spec = WorkflowSpec {
+            steps: vec![classify, escalate, summarize],
+            ..Default::default()
+        };
+
+        let run = runner(&executor).run(&spec, Map::new()).await.unwrap();
+        assert_eq!(run.status, WorkflowRunStatus::Succeeded);
+        assert_eq!(run.steps[1].status, StepStatus::Skipped);
+        assert_eq!(run.steps[2].status, StepStatus::Succeeded);
+        assert_eq!(run.steps[2].output, Some(json!({"escalated": null})));
+    }
+
+    #[tokio::test]
+    async fn test_run_timeout_cancels_dependents() {
+        let executor = Arc::new(EchoExecutor::default());
+        let mut slow = agent_step("slow", "slow", &[]);
+        slow.timeout_seconds = Some(0);
+        let after = agent_step("after", "a", &["slow"]);
+
+        let spec = WorkflowSpec {
+            steps: vec![slow, after],
+            ..Default::default()
+        };
+
+        let run = runner(&executor).run(&spec, Map::new()).await.unwrap();
+        assert_eq!(run.status, WorkflowRunStatus::Failed);
+        assert_eq!(run.steps[0].status, StepStatus::TimedOut);
+        assert_eq!(run.steps[1].status, StepStatus::Cancelled);
+        assert!(run.outputs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run_failure_stops_scheduling() {
+        let executor = Arc::new(EchoExecutor::default());
+        let spec = WorkflowSpec {
+            steps: vec![
+                agent_step("broken", "broken", &[]),
+                agent_step("sibling", "slow", &[]),
+                agent_step("after", "a", &["sibling"]),
+            ],
+            ..Default::default()
+        };
+
+        let run = runner(&executor).run(&spec, Map::new()).await.unwrap();
+        assert_eq!(run.status, WorkflowRunStatus::Failed);
+        assert_eq!(run.steps[0].status, StepStatus::Failed);
+        assert!(run.error.unwrap().contains("agent exploded"));
+        // already running when the failure happened, so it finishes
+        assert_eq!(run.steps[1].status, StepStatus::Succeeded);
+        assert_eq!(run.steps[2].status, StepStatus::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_run_declared_outputs_and_prompt() {
+        let executor = Arc::new(EchoExecutor::default());
+        let draft = WorkflowStep {
+            name: "draft".into(),
+            prompt: Some("Write about {{ inputs.topic }}".into()),
+            outputs: vec!["text".into()],
+            ..Default::default()
+        };
+        let mut review = agent_step("review", "a", &["draft"]);
+        review.prompt = Some("Review: {{ draft.text }}".into());
+        review.outputs = vec!["verdict".into()];
+
+        let spec = WorkflowSpec {
+            inputs: HashMap::from([(
+                "topic".into(),
+                WorkflowInput {
+                    input_type: "string".into(),
+                    default: Some(json!("owls")),
+                    ..Default::default()
+                },
+            )]),
+            steps: vec![draft, review],
+            ..Default::default()
+        };
+
+        let run = runner(&executor).run(&spec, Map::new()).await.unwrap();
+        assert_eq!(
+            run.steps[0].output,
+            Some(json!({"text": "Write about owls"}))
+        );
+        assert_eq!(
+            run.steps[1].inputs["prompt"],
+            json!("Review: Write about owls")
+        );
+        assert_eq!(run.steps[1].status, StepStatus::Failed);
+        assert!(run.steps[1].error.as_ref().unwrap().contains("verdict"));
+    }
+
+    #[tokio::test]
+    async fn test_run_rejects_bad_inputs_and_cards() {
+        let executor = Arc::new(EchoExecutor::default());
+        let spec = WorkflowSpec {
+            inputs: HashMap::from([(
+                "flag".into(),
+                WorkflowInput {
+                    input_type: "boolean".into(),
+                    required: true,
+                    ..Default::default()
+                },
+            )]),
+            steps: vec![agent_step("step", "missing", &[])],
+            ..Default::default()
+        };
+
+        let err = runner(&executor).run(&spec, Map::new()).await.unwrap_err();
+        assert!(matches!(err, WorkflowError::MissingInput(_)));
+
+        let inputs = json!({"flag": "maybe"}).as_object().unwrap().clone();
+        let err = runner(&executor).run(&spec, inputs).await.unwrap_err();
+        assert!(matches!(err, WorkflowError::InvalidInput(_)));
+
+        let inputs = json!({"flag": "true"}).as_object().unwrap().clone();
+        let err = runner(&executor).run(&spec, inputs).await.unwrap_err();
+        assert!(matches!(err, WorkflowError::Resolve(_)));
+    }
+
+    #[tokio::test]
+    async fn test_run_rejects_unsupported_step_before_running() {
+        let executor = Arc::new(EchoExecutor::default());
+        let spec = WorkflowSpec {
+            steps: vec![
+                agent_step("first", "a", &[]),
+                WorkflowStep {
+                    name: "second".into(),
+                    depends_on: vec!["first".into()],
+                    tool: Some(ToolSpec {
+                        name: "lint".into(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        let err = runner(&executor).run(&spec, Map::new()).await.unwrap_err();
+        assert!(matches!(err, WorkflowError::Unsupported(_)));
+        assert!(executor.order.lock().unwrap().is_empty());
+    }
+}
@@ -1,4 +1,6 @@
 use crate::error::AuthError;
+use crate::group::GroupGrants;
+use crate::permission::UserPermissions;
 use crate::sso::SsoProvider;
 use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
 use opsml_sql::schemas::schema::User;
@@ -63,18 +65,22 @@ impl AuthManager {
             .collect()
     }
 
-    pub fn generate_jwt(&self, user: &User) -> Result<String, AuthError> {
+    /// Generates an access token whose claims carry the user's effective permissions,
+    /// i.e. their direct permissions merged with the roles granted through `grants`
+    pub fn generate_jwt(&self, user: &User, grants: &GroupGrants) -> Result<String, AuthError> {
         let expiration = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs()
             + 3600; // 1 hour
 
+        let effective = UserPermissions::resolve(user, grants);
+
         let claims = Claims {
-            sub: user.username.clone(),
+            sub: effective.username,
             exp: expiration as usize,
-            permissions: user.permissions.clone(),
-            group_permissions: user.group_permissions.clone(),
+            permissions: effective.permissions,
+            group_permissions: effective.group_permissions,
             salt: self.generate_salt(),
         };
 
@@ -160,25 +166,36 @@ impl AuthManager {
 }
 
 impl AuthManager {
-    pub async fn exchange_token_for_scouter(&self, user: &User) -> Result<String, AuthError> {
+    pub async fn exchange_token_for_scouter(
+        &self,
+        user: &User,
+        grants: &GroupGrants,
+    ) -> Result<String, AuthError> {
         // Generate a new token using Scouter's secret
-        let scouter_token = self.generate_jwt_with_secret(user, &self.scouter_secret)?;
+        let scouter_token = self.generate_jwt_with_secret(user, grants, &self.scouter_secret)?;
 
         Ok(scouter_token)
     }
 
-    fn generate_jwt_with_secret(&self, user: &User, secret: &str) -> Result<String, AuthError> {
+    fn generate_jwt_with_secret(
+        &self,
+        user: &User,
+        grants: &GroupGrants,
+        secret: &str,
+    ) -> Result<String, AuthError> {
         let expiration = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_secs()
             + 3600;
 
+        let effective = UserPermissions::resolve(user, grants);
+
         let claims = Claims {
-            sub: user.username.clone(),
+            sub: effective.username,
             exp: expiration as usize,
-            permissions: user.permissions.clone(),
-            group_permissions: user.group_permissions.clone(),
+            permissions: effective.permissions,
+            group_permissions: effective.group_permissions,
             salt: self.generate_salt(),
         };
 
@@ -28,4 +28,13 @@ pub enum AuthError {
 
     #[error("Failed to hash: {0}")]
     HashingError(String),
+
+    #[error("API token provided is invalid")]
+    InvalidApiToken,
+
+    #[error("API token has expired")]
+    ApiTokenExpired,
+
+    #[error("API token has been revoked")]
+    ApiTokenRevoked,
 }
@@ -0,0 +1,96 @@
+use opsml_types::contracts::{GroupRecord, GroupRole};
+
+/// Groups a user belongs to and the space roles those groups hold.
+/// Resolved from the database when issuing a JWT
+#[derive(Debug, Clone, Default)]
+pub struct GroupGrants {
+    pub groups: Vec<String>,
+    pub roles: Vec<GroupRole>,
+}
+
+impl GroupGrants {
+    pub fn new(groups: Vec<String>, roles: Vec<GroupRole>) -> Self {
+        Self { groups, roles }
+    }
+
+    /// Expands every space role into `<action>:<space>` permission strings
+    pub fn permissions(&self) -> Vec<String> {
+        self.roles
+            .iter()
+            .flat_map(|grant| grant.role.permissions(&grant.space))
+            .collect()
+    }
+}
+
+/// Membership changes needed to bring a user in line with their identity provider groups
+#[derive(Debug, Default, PartialEq)]
+pub struct SsoGroupSync {
+    pub join: Vec<String>,
+    pub leave: Vec<String>,
+}
+
+/// Maps identity provider group claims onto opsml groups.
+///
+/// Only groups with at least one `sso_groups` mapping are managed; membership of
+/// groups without a mapping is left to admins
+pub fn map_sso_groups(groups: &[GroupRecord], idp_groups: &[String]) -> SsoGroupSync {
+    let mut sync = SsoGroupSync::default();
+
+    for group in groups.iter().filter(|g| !g.sso_groups.is_empty()) {
+        if group.sso_groups.iter().any(|g| idp_groups.contains(g)) {
+            sync.join.push(group.name.clone());
+        } else {
+            sync.leave.push(group.name.clone());
+        }
+    }
+
+    sync
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opsml_types::contracts::SpaceRole;
+
+    fn group(name: &str, sso_groups: &[&str]) -> GroupRecord {
+        GroupRecord {
+            name: name.to_string(),
+            description: String::new(),
+            sso_groups: sso_groups.iter().map(|g| g.to_string()).collect(),
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_group_grant_permissions() {
+        let grants = GroupGrants::new(
+            vec!["ml-team".to_string()],
+            vec![GroupRole {
+                group_name: "ml-team".to_string(),
+                space: "team-a".to_string(),
+                role: SpaceRole::Editor,
+            }],
+        );
+
+        assert_eq!(grants.permissions(), vec!["read:team-a", "write:team-a"]);
+    }
+
+    #[test]
+    fn test_map_sso_groups() {
+        let groups = vec![
+            group("ml-team", &["okta-ml", "okta-ds"]),
+            group("platform", &["okta-platform"]),
+            group("manual", &[]),
+        ];
+
+        let sync = map_sso_groups(&groups, &["okta-ds".to_string()]);
+
+        assert_eq!(
+            sync,
+            SsoGroupSync {
+                join: vec!["ml-team".to_string()],
+                leave: vec!["platform".to_string()],
+            }
+        );
+    }
+}
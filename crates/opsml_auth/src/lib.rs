@@ -1,5 +1,7 @@
 pub mod auth;
 pub mod error;
+pub mod group;
 pub mod permission;
 pub mod sso;
+pub mod token;
 pub mod util;
@@ -1,3 +1,5 @@
+use crate::group::GroupGrants;
+use opsml_sql::schemas::schema::User;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -8,9 +10,12 @@ pub struct UserPermissions {
 }
 
 impl UserPermissions {
+    pub fn is_admin(&self) -> bool {
+        self.group_permissions.contains(&"admin".to_string())
+    }
+
     pub fn has_permission(&self, permission: &str) -> bool {
-        self.permissions.contains(&permission.to_string())
-            || self.group_permissions.contains(&"admin".to_string())
+        self.permissions.contains(&permission.to_string()) || self.is_admin()
     }
 
     pub fn has_read_permission(&self, space_id: &str) -> bool {
@@ -27,9 +32,75 @@ impl UserPermissions {
         self.has_permission(&format!("delete:{space_id}"))
             || self.permissions.contains(&"delete:all".to_string())
     }
+    /// Spaces these permissions can read, or `None` when every space is readable
+    pub fn readable_spaces(&self) -> Option<Vec<String>> {
+        if self.is_admin() || self.permissions.iter().any(|p| p == "read:all") {
+            return None;
+        }
+
+        Some(
+            self.permissions
+                .iter()
+                .filter_map(|p| p.strip_prefix("read:"))
+                .map(str::to_string)
+                .collect(),
+        )
+    }
+
+    /// Whether these permissions cover a single `<action>:<space>` permission,
+    /// either directly, through `<action>:all` or through admin
+    pub fn covers(&self, permission: &str) -> bool {
+        if self.has_permission(permission) {
+            return true;
+        }
+
+        match permission.split_once(':') {
+            Some((action, _)) => self.permissions.contains(&format!("{action}:all")),
+            None => false,
+        }
+    }
+
+    /// Restricts these permissions to the subset granted to an API token.
+    /// Group permissions are never carried over, so tokens cannot act as admin
+    pub fn scope_to_token(&self, token_permissions: &[String]) -> Self {
+        Self {
+            username: self.username.clone(),
+            permissions: token_permissions
+                .iter()
+                .filter(|permission| self.covers(permission))
+                .cloned()
+                .collect(),
+            group_permissions: Vec::new(),
+        }
+    }
 }
 
 impl UserPermissions {
+    /// Effective permissions for a user: their direct permissions plus everything
+    /// granted through group roles. Group names are added to `group_permissions`,
+    /// so membership of a group named `admin` grants admin
+    pub fn resolve(user: &User, grants: &GroupGrants) -> Self {
+        let mut permissions = user.permissions.clone();
+        for permission in grants.permissions() {
+            if !permissions.contains(&permission) {
+                permissions.push(permission);
+            }
+        }
+
+        let mut group_permissions = user.group_permissions.clone();
+        for group in &grants.groups {
+            if !group_permissions.contains(group) {
+                group_permissions.push(group.clone());
+            }
+        }
+
+        Self {
+            username: user.username.clone(),
+            permissions,
+            group_permissions,
+        }
+    }
+
     /// New users have read and write permissions
     pub fn new_with_default(username: String) -> Self {
         Self {
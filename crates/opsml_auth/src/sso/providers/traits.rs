@@ -150,6 +150,7 @@ pub trait SsoProviderExt {
         let claims = self.decode_jwt_with_validation(&token_response.id_token)?;
 
         let email = claims.email;
+        let groups = claims.groups;
 
         // check if preferred_username or name is available. if not, use email
         let username = if let Some(username) = claims.preferred_username {
@@ -160,7 +161,11 @@ pub trait SsoProviderExt {
             email.clone() // fallback to email if no username or name is available
         };
 
-        Ok(UserInfo { username, email })
+        Ok(UserInfo {
+            username,
+            email,
+            groups,
+        })
     }
 
     #[instrument(skip_all)]
@@ -175,6 +180,7 @@ pub trait SsoProviderExt {
         let claims = self.decode_jwt_with_validation(&token_response.id_token)?;
 
         let email = claims.email;
+        let groups = claims.groups;
 
         // check if preferred_username or name is available. if not, use email
         let username = if let Some(username) = claims.preferred_username {
@@ -185,7 +191,11 @@ pub trait SsoProviderExt {
             email.clone() // fallback to email if no username or name is available
         };
 
-        Ok(UserInfo { username, email })
+        Ok(UserInfo {
+            username,
+            email,
+            groups,
+        })
     }
 
     fn get_authorization_url(
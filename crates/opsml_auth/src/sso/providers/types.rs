@@ -112,4 +112,7 @@ pub struct IdTokenClaims {
     pub name: Option<String>,
     pub exp: u64,
     pub sub: String,
+    /// Identity provider groups (Okta `groups`, Keycloak group mapper)
+    #[serde(default)]
+    pub groups: Vec<String>,
 }
@@ -6,4 +6,6 @@ use serde::Deserialize;
 pub struct UserInfo {
     pub username: String,
     pub email: String,
+    #[serde(default)]
+    pub groups: Vec<String>,
 }
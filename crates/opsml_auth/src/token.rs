@@ -0,0 +1,94 @@
+use crate::error::AuthError;
+use rand::Rng;
+use rand::distr::Alphanumeric;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+/// Prefix used to distinguish API tokens from JWTs in the Authorization header
+pub const API_TOKEN_PREFIX: &str = "opsml_pat_";
+
+const TOKEN_ID_LENGTH: usize = 16;
+const TOKEN_SECRET_LENGTH: usize = 40;
+
+#[derive(Debug)]
+pub struct GeneratedApiToken {
+    /// Full token returned to the user. This is never persisted
+    pub token: String,
+    pub token_id: String,
+    pub token_hash: String,
+}
+
+fn random_string(len: usize) -> String {
+    rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+/// Hashes the secret part of an API token.
+/// Secrets are high-entropy random strings, so a fast digest is sufficient here
+pub fn hash_api_token_secret(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
+
+/// Generates a new API token of the form `opsml_pat_<token_id>_<secret>`
+pub fn generate_api_token() -> GeneratedApiToken {
+    let token_id = random_string(TOKEN_ID_LENGTH);
+    let secret = random_string(TOKEN_SECRET_LENGTH);
+
+    GeneratedApiToken {
+        token: format!("{API_TOKEN_PREFIX}{token_id}_{secret}"),
+        token_hash: hash_api_token_secret(&secret),
+        token_id,
+    }
+}
+
+pub fn is_api_token(token: &str) -> bool {
+    token.starts_with(API_TOKEN_PREFIX)
+}
+
+/// Splits an API token into its token id and secret
+pub fn parse_api_token(token: &str) -> Result<(&str, &str), AuthError> {
+    token
+        .strip_prefix(API_TOKEN_PREFIX)
+        .and_then(|rest| rest.split_once('_'))
+        .filter(|(id, secret)| !id.is_empty() && !secret.is_empty())
+        .ok_or(AuthError::InvalidApiToken)
+}
+
+/// Verifies the secret part of `token` against a stored hash.
+/// The hashes are compared in constant time so response timing does not leak the stored hash
+pub fn verify_api_token(token: &str, token_hash: &str) -> Result<(), AuthError> {
+    let (_, secret) = parse_api_token(token)?;
+
+    let matches: bool = hash_api_token_secret(secret)
+        .as_bytes()
+        .ct_eq(token_hash.as_bytes())
+        .into();
+    if matches {
+        Ok(())
+    } else {
+        Err(AuthError::InvalidApiToken)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_token_roundtrip() {
+        let generated = generate_api_token();
+        assert!(is_api_token(&generated.token));
+
+        let (token_id, _) = parse_api_token(&generated.token).unwrap();
+        assert_eq!(token_id, generated.token_id);
+
+        verify_api_token(&generated.token, &generated.token_hash).unwrap();
+
+        let tampered = format!("{}x", generated.token);
+        assert!(verify_api_token(&tampered, &generated.token_hash).is_err());
+        assert!(parse_api_token("opsml_pat_missing").is_err());
+    }
+}
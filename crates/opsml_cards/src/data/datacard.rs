@@ -1,4 +1,5 @@
 use crate::error::CardError;
+use crate::utils::card_username;
 use chrono::{DateTime, Utc};
 use opsml_types::{RegistryType, SaveName, Suffix};
 use opsml_utils::PyHelperFuncs;
@@ -8,7 +9,7 @@ use tracing::error;
 
 #[cfg(feature = "python")]
 use {
-    crate::utils::BaseArgs,
+    crate::utils::{BaseArgs, card_app_env},
     opsml_crypt::decrypt_directory,
     opsml_interfaces::{
         FeatureSchema,
@@ -17,7 +18,7 @@ use {
             NumpyData, PandasData, PolarsData, SqlData, TorchData,
         },
     },
-    opsml_storage::storage_client,
+    opsml_storage::{storage_client, verify_manifest},
     opsml_types::{
         DataType,
         contracts::{ArtifactKey, CardRecord, DataCardClientRecord},
@@ -174,7 +175,7 @@ impl DataCard {
             metadata,
             registry_type,
             artifact_key: None,
-            app_env: std::env::var("APP_ENV").unwrap_or_else(|_| "dev".to_string()),
+            app_env: card_app_env(),
             created_at: get_utc_datetime(),
             is_card: true,
             opsml_version: opsml_version::version(),
@@ -387,7 +388,7 @@ impl DataCard {
             auditcard_uid: self.metadata.auditcard_uid.clone(),
             interface_type: self.metadata.interface_metadata.interface_type.to_string(),
             opsml_version: self.opsml_version.clone(),
-            username: std::env::var("OPSML_USERNAME").unwrap_or_else(|_| "guest".to_string()),
+            username: card_username(),
         };
 
         Ok(CardRecord::Data(record))
@@ -485,11 +486,13 @@ impl DataCard {
 
     fn download_all_artifacts(&mut self, lpath: &Path) -> Result<(), CardError> {
         let decrypt_key = self.get_decryption_key()?;
-        let uri = self.artifact_key.as_ref().unwrap().storage_path();
+        let key = self.artifact_key.as_ref().unwrap();
+        let uri = key.storage_path();
 
         storage_client()?.get(lpath, &uri, true)?;
 
         decrypt_directory(lpath, &decrypt_key)?;
+        verify_manifest(lpath, key.manifest_sha256.as_deref())?;
 
         Ok(())
     }
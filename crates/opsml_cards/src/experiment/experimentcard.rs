@@ -1,5 +1,5 @@
 use crate::error::CardError;
-use crate::utils::BaseArgs;
+use crate::utils::{BaseArgs, card_app_env, card_username};
 use chrono::{DateTime, Utc};
 use opsml_crypt::decrypt_directory;
 use opsml_storage::storage_client;
@@ -143,7 +143,7 @@ impl ExperimentCard {
             service_card_uids: self.uids.service_card_uids.clone(),
             experimentcard_uids: self.uids.experimentcard_uids.clone(),
             opsml_version: self.opsml_version.clone(),
-            username: std::env::var("OPSML_USERNAME").unwrap_or_else(|_| "guest".to_string()),
+            username: card_username(),
             status: self.status.clone(),
         };
 
@@ -252,7 +252,7 @@ impl ExperimentCard {
             tags,
             registry_type,
             artifact_key: None,
-            app_env: std::env::var("APP_ENV").unwrap_or_else(|_| "dev".to_string()),
+            app_env: card_app_env(),
             created_at: get_utc_datetime(),
             compute_environment: ComputeEnvironment::new_rs()?,
             uids: UidMetadata::default(),
@@ -293,7 +293,7 @@ impl ExperimentCard {
             tags,
             registry_type,
             artifact_key: None,
-            app_env: std::env::var("APP_ENV").unwrap_or_else(|_| "dev".to_string()),
+            app_env: card_app_env(),
             created_at: get_utc_datetime(),
             compute_environment: ComputeEnvironment::new(py)?,
             uids: UidMetadata::default(),
@@ -336,18 +336,19 @@ impl ExperimentCard {
     }
 
     /// Download a specific artifact
-    #[pyo3(signature = (path, lpath=None))]
+    #[pyo3(signature = (path, lpath=None, progress=None))]
     pub fn download_artifact(
         &self,
         py: Python<'_>,
         path: PathBuf,
         lpath: Option<PathBuf>,
+        progress: Option<Py<PyAny>>,
     ) -> Result<(), CardError> {
         let func = py
             .import("opsml.experiment")?
             .getattr("download_artifact")?;
 
-        func.call1((&self.uid, path, lpath))?;
+        func.call1((&self.uid, path, lpath, progress))?;
 
         Ok(())
     }
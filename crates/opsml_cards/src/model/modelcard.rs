@@ -1,4 +1,5 @@
 use crate::error::CardError;
+use crate::utils::card_username;
 use chrono::{DateTime, Utc};
 use opsml_types::contracts::{ArtifactKey, CardRecord, ModelCardClientRecord};
 use opsml_types::interfaces::ModelInterfaceMetadata;
@@ -16,14 +17,14 @@ use tracing::error;
 #[cfg(feature = "python")]
 use {
     crate::model::error::interface_error,
-    crate::utils::BaseArgs,
+    crate::utils::{BaseArgs, card_app_env},
     opsml_crypt::decrypt_directory,
     opsml_interfaces::{
         CatBoostModel, HuggingFaceModel, LightGBMModel, LightningModel, ModelInterface,
         ModelLoadKwargs, ModelSaveKwargs, OnnxModel, OnnxSession, SklearnModel, TensorFlowModel,
         TorchModel, XGBoostModel, base::DriftProfileMap, error::ModelInterfaceError,
     },
-    opsml_storage::storage_client,
+    opsml_storage::{storage_client, verify_manifest},
     opsml_types::{DataType, ModelInterfaceType, ModelType, TaskType},
     opsml_utils::{create_tmp_path, extract_py_attr, get_utc_datetime},
     pyo3::types::{PyDict, PyList},
@@ -190,7 +191,7 @@ impl ModelCard {
             metadata,
             registry_type,
             artifact_key: None,
-            app_env: std::env::var("APP_ENV").unwrap_or_else(|_| "dev".to_string()),
+            app_env: card_app_env(),
             created_at: get_utc_datetime(),
             is_card: true,
             opsml_version: opsml_version::version(),
@@ -629,7 +630,7 @@ impl ModelCard {
             interface_type,
             task_type,
             opsml_version: self.opsml_version.clone(),
-            username: std::env::var("OPSML_USERNAME").unwrap_or_else(|_| "guest".to_string()),
+            username: card_username(),
         };
 
         Ok(CardRecord::Model(record))
@@ -653,11 +654,13 @@ impl ModelCard {
     #[cfg(feature = "python")]
     fn download_all_artifacts(&mut self, lpath: &Path) -> Result<(), CardError> {
         let decrypt_key = self.get_decryption_key()?;
-        let uri = self.artifact_key.as_ref().unwrap().storage_path();
+        let key = self.artifact_key.as_ref().unwrap();
+        let uri = key.storage_path();
 
         storage_client()?.get(lpath, &uri, true)?;
 
         decrypt_directory(lpath, &decrypt_key)?;
+        verify_manifest(lpath, key.manifest_sha256.as_deref())?;
 
         Ok(())
     }
@@ -1,7 +1,7 @@
 use crate::error::CardError;
 use crate::traits::OpsmlCard;
 use crate::traits::ProfileExt;
-use crate::utils::BaseArgs;
+use crate::utils::{BaseArgs, card_app_env, card_username};
 use chrono::{DateTime, Utc};
 use opsml_types::DriftProfileUri;
 use opsml_types::contracts::{CardRecord, PromptCardClientRecord};
@@ -409,7 +409,7 @@ impl PromptCard {
             tags: tags.unwrap_or_default(),
             metadata: PromptCardMetadata::default(),
             registry_type,
-            app_env: std::env::var("APP_ENV").unwrap_or_else(|_| "dev".to_string()),
+            app_env: card_app_env(),
             created_at: get_utc_datetime(),
             is_card: true,
             opsml_version: opsml_version::version(),
@@ -458,7 +458,7 @@ impl PromptCard {
             experimentcard_uid: self.metadata.experimentcard_uid.clone(),
             auditcard_uid: self.metadata.auditcard_uid.clone(),
             opsml_version: self.opsml_version.clone(),
-            username: std::env::var("OPSML_USERNAME").unwrap_or_else(|_| "guest".to_string()),
+            username: card_username(),
             content_hash: self.content_hash()?,
         };
 
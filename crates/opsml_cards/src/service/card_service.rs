@@ -1,7 +1,7 @@
 use crate::error::CardError;
 use crate::traits::OpsmlCard;
 use crate::traits::ProfileExt;
-use crate::utils::BaseArgs;
+use crate::utils::{BaseArgs, card_app_env, card_username};
 #[cfg(feature = "python")]
 use crate::{DataCard, ExperimentCard, ModelCard, PromptCard};
 use chrono::{DateTime, Utc};
@@ -230,7 +230,7 @@ impl ServiceCard {
             cards: CardList { cards },
             opsml_version: opsml_version::version(),
             card_objs: HashMap::new(),
-            app_env: std::env::var("APP_ENV").unwrap_or_else(|_| "dev".to_string()),
+            app_env: card_app_env(),
             is_card: true,
             registry_type,
             experimentcard_uid: None,
@@ -762,7 +762,7 @@ impl ServiceCard {
             uid: self.uid.clone(),
             cards: self.cards.to_card_entries(),
             opsml_version: self.opsml_version.clone(),
-            username: std::env::var("OPSML_USERNAME").unwrap_or_else(|_| "guest".to_string()),
+            username: card_username(),
             service_type: self.service_type.clone(),
             metadata: self.metadata.clone(),
             deployment: self.deploy.clone(),
@@ -813,7 +813,7 @@ impl ServiceCard {
             opsml_version: opsml_version::version(),
             #[cfg(feature = "python")]
             card_objs: HashMap::new(),
-            app_env: std::env::var("APP_ENV").unwrap_or_else(|_| "dev".to_string()),
+            app_env: card_app_env(),
             is_card: true,
             registry_type,
             experimentcard_uid: None,
@@ -3,6 +3,7 @@ use crate::error::CardError;
 use crate::skill::error::SkillError;
 use crate::traits::OpsmlCard;
 use crate::traits::ProfileExt;
+use crate::utils::{card_app_env, card_username};
 use chrono::{DateTime, Utc};
 use opsml_types::contracts::CardRecord;
 use opsml_types::contracts::SkillCardClientRecord;
@@ -356,7 +357,7 @@ impl SkillCard {
             license: self.skill.license.clone(),
             content_hash: self.calculate_content_hash()?,
             opsml_version: self.opsml_version.clone(),
-            username: std::env::var("OPSML_USERNAME").unwrap_or_else(|_| "guest".to_string()),
+            username: card_username(),
             download_count: 0,
             input_schema: self.input_schema.clone(),
             body: self.skill.body.clone(),
@@ -430,7 +431,7 @@ impl SkillCard {
             dependencies: dependencies.unwrap_or_default(),
             input_schema,
             registry_type,
-            app_env: std::env::var("APP_ENV").unwrap_or_else(|_| "dev".to_string()),
+            app_env: card_app_env(),
             created_at: get_utc_datetime(),
             is_card: true,
             opsml_version: opsml_version::version(),
@@ -3,6 +3,7 @@ use crate::BaseArgs;
 use crate::error::CardError;
 use crate::traits::OpsmlCard;
 use crate::traits::ProfileExt;
+use crate::utils::{card_app_env, card_username};
 use chrono::{DateTime, Utc};
 use opsml_agent_cli::AgentCliFramework;
 use opsml_types::contracts::subagent::SubAgentSpec;
@@ -92,7 +93,7 @@ impl SubAgentCard {
             uid: base_args.3,
             tags: tags.unwrap_or_default(),
             registry_type,
-            app_env: std::env::var("APP_ENV").unwrap_or_else(|_| "dev".to_string()),
+            app_env: card_app_env(),
             created_at: get_utc_datetime(),
             is_card: true,
             opsml_version: opsml_version::version(),
@@ -119,7 +120,7 @@ impl SubAgentCard {
             description: Some(self.spec.description.clone()),
             content_hash: Some(self.calculate_content_hash()?),
             opsml_version: self.opsml_version.clone(),
-            username: std::env::var("OPSML_USERNAME").unwrap_or_else(|_| "guest".to_string()),
+            username: card_username(),
             download_count: 0,
         };
 
@@ -3,6 +3,7 @@ use crate::BaseArgs;
 use crate::error::CardError;
 use crate::traits::OpsmlCard;
 use crate::traits::ProfileExt;
+use crate::utils::{card_app_env, card_username};
 use chrono::{DateTime, Utc};
 use opsml_types::contracts::tool::{
     ApiCallConfig, HookEvent, ShellScriptConfig, ToolSpec, ToolType,
@@ -140,7 +141,7 @@ impl ToolCard {
             uid: base_args.3,
             tags: tags.unwrap_or_default(),
             registry_type,
-            app_env: std::env::var("APP_ENV").unwrap_or_else(|_| "dev".to_string()),
+            app_env: card_app_env(),
             created_at: get_utc_datetime(),
             is_card: true,
             opsml_version: opsml_version::version(),
@@ -158,7 +159,7 @@ impl ToolCard {
             version: self.version.clone(),
             tags: self.tags.clone(),
             opsml_version: self.opsml_version.clone(),
-            username: std::env::var("OPSML_USERNAME").unwrap_or_else(|_| "guest".to_string()),
+            username: card_username(),
             tool_type: self.spec.tool_type.to_string(),
             args_schema: self.spec.args_schema.clone(),
             content_hash: Some(self.calculate_content_hash()?),
@@ -7,10 +7,37 @@ use opsml_utils::{clean_string, validate_name_space_pattern};
 
 pub type BaseArgsResult = (String, String, String, String);
 
+/// Environment recorded on new cards, as resolved by the opsml config from `APP_ENV`, the active
+/// profile and `opsml.toml`
+pub fn card_app_env() -> String {
+    app_state()
+        .config()
+        .map(|config| config.app_env.clone())
+        .unwrap_or_else(|_| "development".to_string())
+}
+
+/// User recorded on registered cards. Resolved the same way as the credentials used to register
+/// them, so the two always match
+pub fn card_username() -> String {
+    app_state()
+        .config()
+        .map(|config| config.auth_settings.username.clone())
+        .unwrap_or_else(|_| "guest".to_string())
+}
+
+/// Default space of the active client profile, used when no space is given
+pub fn default_space() -> Option<String> {
+    app_state()
+        .config()
+        .ok()
+        .and_then(|config| config.default_space.clone())
+}
+
 pub struct BaseArgs {}
 
 impl BaseArgs {
-    /// Create base arguments for a card. Attempt to pull from config if not provided.
+    /// Create base arguments for a card. Attempt to pull from config if not provided. A missing
+    /// space falls back to the default space of the active client profile
     ///
     /// # Arguments
     /// * `name` - Optional name of the card
@@ -70,6 +97,7 @@ impl BaseArgs {
             .as_ref()
             .map(|s| s.to_string())
             .or(config_value)
+            .or_else(|| (key == "space").then(default_space).flatten())
             .ok_or(TypeError::MissingKeyError)?)
     }
 
@@ -0,0 +1,116 @@
+use crate::cli::arg::{RotateKeysArgs, ScanStorageArgs};
+use crate::error::CliError;
+use opsml_colors::Colorize;
+use opsml_registry::registries::card::OpsmlCardRegistry;
+use opsml_types::RegistryType;
+use opsml_types::contracts::{
+    ConsistencyIssueKind, ConsistencyScanRequest, RotateArtifactKeysRequest,
+};
+
+/// Re-wrap every stored artifact key with the current key-encryption-key
+///
+/// # Arguments
+/// * `args` - The command line arguments
+pub fn rotate_artifact_keys(args: &RotateKeysArgs) -> Result<(), CliError> {
+    let registry = OpsmlCardRegistry::new(RegistryType::Artifact)?;
+
+    let response = registry.rotate_artifact_keys(&RotateArtifactKeysRequest {
+        batch_size: args.batch_size,
+    })?;
+
+    println!(
+        "{}",
+        Colorize::green(&format!(
+            "Re-wrapped {} artifact keys with key version {}",
+            response.rewrapped, response.key_version
+        ))
+    );
+
+    if response.skipped > 0 {
+        println!(
+            "{}",
+            Colorize::alert(&format!(
+                "{} keys changed during rotation. Run the command again to re-wrap them",
+                response.skipped
+            ))
+        );
+    }
+
+    Ok(())
+}
+
+/// Cross-reference server storage with artifact keys and card tables and print the findings
+///
+/// # Arguments
+/// * `args` - The command line arguments
+pub fn scan_storage(args: &ScanStorageArgs) -> Result<(), CliError> {
+    let registry = OpsmlCardRegistry::new(RegistryType::Artifact)?;
+
+    let report = registry.scan_storage(&ConsistencyScanRequest {
+        delete: args.delete,
+        space: args.space.clone(),
+    })?;
+
+    for issue in &report.issues {
+        let label = match issue.kind {
+            ConsistencyIssueKind::OrphanedObjects => "Orphaned objects",
+            ConsistencyIssueKind::OrphanedKey => "Orphaned key",
+            ConsistencyIssueKind::EmptyStorageKey => "Empty storage key",
+            ConsistencyIssueKind::MissingArtifacts => "Missing artifacts",
+            ConsistencyIssueKind::MissingKey => "Missing key",
+        };
+        let uid = issue
+            .uid
+            .as_ref()
+            .map(|uid| format!(" uid={uid}"))
+            .unwrap_or_default();
+        let deleted = if issue.deleted { " (deleted)" } else { "" };
+        println!(
+            "{} {}{} {} object(s), {} bytes{}",
+            Colorize::alert(label),
+            issue.storage_key,
+            uid,
+            issue.objects,
+            issue.bytes,
+            deleted
+        );
+    }
+
+    for usage in &report.spaces {
+        println!(
+            "{} {} object(s), {} bytes ({} orphaned)",
+            Colorize::purple(&usage.space),
+            usage.objects,
+            usage.bytes,
+            usage.orphaned_bytes
+        );
+    }
+
+    let summary = format!(
+        "Scanned {} object(s) and {} artifact key(s): {} orphaned prefix(es), {} orphaned key(s), {} card(s) missing artifacts, {} card(s) missing keys, {} empty storage key(s)",
+        report.objects_scanned,
+        report.keys_scanned,
+        report.count(ConsistencyIssueKind::OrphanedObjects),
+        report.count(ConsistencyIssueKind::OrphanedKey),
+        report.count(ConsistencyIssueKind::MissingArtifacts),
+        report.count(ConsistencyIssueKind::MissingKey),
+        report.count(ConsistencyIssueKind::EmptyStorageKey),
+    );
+    if report.issues.is_empty() {
+        println!("{}", Colorize::green(&summary));
+    } else {
+        println!("{}", Colorize::alert(&summary));
+    }
+
+    if report.dry_run && report.orphaned_bytes() > 0 {
+        println!(
+            "{}",
+            Colorize::alert(&format!(
+                "{} orphaned bytes can be reclaimed. Run again with --delete to remove them",
+                report.orphaned_bytes()
+            ))
+        );
+    }
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use crate::actions::agent_lock::{AgentLock, LockMode, scope_lock_path};
+use crate::actions::profile::space_or_default;
 use crate::actions::skill::find_card_json;
 use crate::cli::arg::{AgentInitArgs, AgentListArgs, AgentPullArgs, AgentPushArgs, PullTarget};
 use crate::error::CliError;
@@ -46,7 +48,7 @@ pub fn push_agent(args: &AgentPushArgs) -> Result<(), CliError> {
     let mut card: opsml_cards::SubAgentCard =
         parse_subagent_markdown(&content).map_err(|e| CliError::Error(e.to_string()))?;
 
-    card.space = clean_string(args.space.as_deref().unwrap_or(&card.space));
+    card.space = clean_string(&space_or_default(&args.space).unwrap_or_else(|| card.space.clone()));
     card.name = clean_string(&card.name);
 
     if let Some(tags) = &args.tags {
@@ -69,15 +71,27 @@ pub fn push_agent(args: &AgentPushArgs) -> Result<(), CliError> {
 
 #[instrument(skip_all)]
 pub fn pull_agent(args: &AgentPullArgs) -> Result<(), CliError> {
-    let (space, name) = parse_agent_identifier(&args.name, args.space.as_deref())?;
+    let (space, name) =
+        parse_agent_identifier(&args.name, space_or_default(&args.space).as_deref())?;
 
     let space_clean = clean_string(&space);
     let name_clean = clean_string(&name);
 
+    let mut lock = AgentLock::open(
+        &scope_lock_path(args.local)?,
+        LockMode::from_flags(args.frozen, false),
+    )?;
+    let version = lock.version_for(
+        &RegistryType::SubAgent,
+        &space_clean,
+        &name_clean,
+        args.version.as_deref(),
+    )?;
+
     let query_args = CardQueryArgs {
         space: Some(space_clean.clone()),
         name: Some(name_clean.clone()),
-        version: args.version.clone(),
+        version,
         registry_type: RegistryType::SubAgent,
         limit: Some(1),
         ..Default::default()
@@ -94,6 +108,15 @@ pub fn pull_agent(args: &AgentPullArgs) -> Result<(), CliError> {
     let card_json = find_card_json(&tmp_path, 0)?;
     let card = opsml_cards::SubAgentCard::from_path(card_json)?;
 
+    lock.check(
+        RegistryType::SubAgent,
+        &space_clean,
+        &name_clean,
+        &card.version,
+        &card.uid,
+        &card.calculate_content_hash()?,
+    )?;
+
     let installed_path = if let Some(target) = &args.target {
         let framework: &dyn AgentCliFramework = match target {
             PullTarget::ClaudeCode => &ClaudeCodeFramework,
@@ -113,6 +136,8 @@ pub fn pull_agent(args: &AgentPullArgs) -> Result<(), CliError> {
         output_path
     };
 
+    lock.save()?;
+
     println!(
         "{} {}/{} v{} -> {}",
         Colorize::green("Pulled"),
@@ -132,7 +157,7 @@ pub fn list_agents(args: &AgentListArgs) -> Result<(), CliError> {
         Colorize::green("subagent")
     );
 
-    let space = args.space.clone().map(|s| clean_string(&s));
+    let space = space_or_default(&args.space).map(|s| clean_string(&s));
     let name = args.name.clone().map(|n| clean_string(&n));
 
     let query_args = CardQueryArgs {
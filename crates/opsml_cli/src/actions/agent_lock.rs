@@ -0,0 +1,340 @@
+use crate::error::CliError;
+use opsml_toml::{AgentLockFile, LockedArtifact};
+use opsml_types::RegistryType;
+use std::path::{Path, PathBuf};
+
+/// How an install treats the agentic lockfile
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LockMode {
+    /// Install pinned versions when present and record anything new
+    Default,
+    /// Install only what is pinned, verify content hashes and never write the lockfile
+    Frozen,
+    /// Ignore pins, resolve fresh versions and rewrite their entries
+    Update,
+}
+
+impl LockMode {
+    pub fn from_flags(frozen: bool, update: bool) -> Self {
+        match (frozen, update) {
+            (true, _) => LockMode::Frozen,
+            (false, true) => LockMode::Update,
+            (false, false) => LockMode::Default,
+        }
+    }
+}
+
+/// Lockfile of the scope an install writes to: `./.opsml-skills.lock` for project installs,
+/// `~/.opsml/skills.lock` otherwise
+pub fn scope_lock_path(local: bool) -> Result<PathBuf, CliError> {
+    let yaml_path = if local {
+        PathBuf::from(".opsml-skills.yaml")
+    } else {
+        dirs::home_dir()
+            .ok_or_else(|| CliError::Error("Cannot determine home directory".into()))?
+            .join(".opsml")
+            .join("skills.yaml")
+    };
+    Ok(AgentLockFile::path_for(&yaml_path))
+}
+
+/// Lockfile opened for a single install command
+pub struct AgentLock {
+    path: PathBuf,
+    lock: AgentLockFile,
+    mode: LockMode,
+    changed: bool,
+}
+
+impl AgentLock {
+    pub fn open(path: &Path, mode: LockMode) -> Result<Self, CliError> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            lock: AgentLockFile::load_or_default(path)?,
+            mode,
+            changed: false,
+        })
+    }
+
+    pub fn mode(&self) -> LockMode {
+        self.mode
+    }
+
+    /// Drop skill entries that are no longer listed in skills.yaml. Only applied on update
+    pub fn retain_skills(&mut self, keep: &[(String, String)]) {
+        if self.mode != LockMode::Update {
+            return;
+        }
+
+        let before = self.lock.artifacts.len();
+        self.lock.artifacts.retain(|a| {
+            a.registry_type != RegistryType::Skill
+                || keep.iter().any(|(s, n)| *s == a.space && *n == a.name)
+        });
+        self.changed |= self.lock.artifacts.len() != before;
+    }
+
+    /// Pinned artifacts of a registry type, for resolvers to prefer over newer versions.
+    /// Empty on update, which resolves fresh versions
+    pub fn pins(&self, registry_type: &RegistryType) -> Vec<&LockedArtifact> {
+        if self.mode == LockMode::Update {
+            return Vec::new();
+        }
+
+        self.lock
+            .artifacts
+            .iter()
+            .filter(|artifact| artifact.registry_type == *registry_type)
+            .collect()
+    }
+
+    /// Version to request from the registry.
+    ///
+    /// Frozen installs always use the pin. Otherwise the pin wins unless the caller asked for
+    /// an exact version that differs from it, in which case the artifact is re-locked
+    pub fn version_for(
+        &self,
+        registry_type: &RegistryType,
+        space: &str,
+        name: &str,
+        requested: Option<&str>,
+    ) -> Result<Option<String>, CliError> {
+        let requested = requested.filter(|v| !v.is_empty() && *v != "latest");
+        let locked = self.lock.get(registry_type, space, name);
+
+        match self.mode {
+            LockMode::Frozen => locked
+                .map(|entry| Some(entry.version.clone()))
+                .ok_or_else(|| {
+                    CliError::LockEntryMissing(
+                        format!("{registry_type} {space}/{name}"),
+                        self.path.clone(),
+                    )
+                }),
+            LockMode::Update => Ok(requested.map(String::from)),
+            LockMode::Default => {
+                let explicit = requested.filter(|v| semver::Version::parse(v).is_ok());
+                match (locked, explicit) {
+                    (Some(entry), Some(v)) if entry.version != v => Ok(Some(v.to_string())),
+                    (Some(entry), _) => Ok(Some(entry.version.clone())),
+                    (None, _) => Ok(requested.map(String::from)),
+                }
+            }
+        }
+    }
+
+    /// Check a downloaded artifact against its pin and record it.
+    /// Must run before anything is written to disk
+    pub fn check(
+        &mut self,
+        registry_type: RegistryType,
+        space: &str,
+        name: &str,
+        version: &str,
+        uid: &str,
+        content_hash: &[u8],
+    ) -> Result<(), CliError> {
+        let content_hash = hex::encode(content_hash);
+        let artifact = format!("{registry_type} {space}/{name}");
+
+        let locked = self.lock.get(&registry_type, space, name);
+        if self.mode == LockMode::Frozen && locked.is_none() {
+            return Err(CliError::LockEntryMissing(artifact, self.path.clone()));
+        }
+
+        if let Some(entry) = locked
+            && self.mode != LockMode::Update
+        {
+            if self.mode == LockMode::Frozen && entry.version != version {
+                return Err(CliError::LockVersionMismatch(
+                    artifact,
+                    entry.version.clone(),
+                    version.to_string(),
+                ));
+            }
+
+            if entry.version == version && entry.content_hash != content_hash {
+                return Err(CliError::LockHashMismatch(
+                    artifact,
+                    version.to_string(),
+                    entry.content_hash.clone(),
+                    content_hash,
+                ));
+            }
+        }
+
+        if self.mode == LockMode::Frozen {
+            return Ok(());
+        }
+
+        self.changed |= self.lock.upsert(LockedArtifact {
+            space: space.to_string(),
+            name: name.to_string(),
+            registry_type,
+            version: version.to_string(),
+            uid: uid.to_string(),
+            content_hash,
+        });
+        Ok(())
+    }
+
+    /// Persist the lockfile if anything changed. Frozen installs never write it
+    pub fn save(&mut self) -> Result<(), CliError> {
+        if self.mode == LockMode::Frozen || !self.changed {
+            return Ok(());
+        }
+        self.lock.save(&self.path)?;
+        self.changed = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: [u8; 4] = [0xde, 0xad, 0xbe, 0xef];
+
+    fn open(dir: &Path, mode: LockMode) -> AgentLock {
+        AgentLock::open(&dir.join(".opsml-skills.lock"), mode).unwrap()
+    }
+
+    fn lock_reviewer(dir: &Path) {
+        let mut lock = open(dir, LockMode::Default);
+        lock.check(
+            RegistryType::Skill,
+            "team",
+            "reviewer",
+            "1.0.0",
+            "uid-1",
+            &HASH,
+        )
+        .unwrap();
+        lock.save().unwrap();
+    }
+
+    #[test]
+    fn test_default_mode_pins_and_records() {
+        let dir = tempfile::tempdir().unwrap();
+        lock_reviewer(dir.path());
+
+        let lock = open(dir.path(), LockMode::Default);
+        let version = lock
+            .version_for(&RegistryType::Skill, "team", "reviewer", None)
+            .unwrap();
+        assert_eq!(version.as_deref(), Some("1.0.0"));
+
+        // an explicit different version re-locks
+        let version = lock
+            .version_for(&RegistryType::Skill, "team", "reviewer", Some("1.2.0"))
+            .unwrap();
+        assert_eq!(version.as_deref(), Some("1.2.0"));
+
+        // unpinned artifacts fall through to the request
+        let version = lock
+            .version_for(&RegistryType::Tool, "team", "reviewer", Some("^1.0.0"))
+            .unwrap();
+        assert_eq!(version.as_deref(), Some("^1.0.0"));
+    }
+
+    #[test]
+    fn test_hash_mismatch_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        lock_reviewer(dir.path());
+
+        for mode in [LockMode::Default, LockMode::Frozen] {
+            let mut lock = open(dir.path(), mode);
+            let result = lock.check(
+                RegistryType::Skill,
+                "team",
+                "reviewer",
+                "1.0.0",
+                "uid-1",
+                &[0u8; 4],
+            );
+            assert!(matches!(result, Err(CliError::LockHashMismatch(..))));
+        }
+
+        // update accepts the new content
+        let mut lock = open(dir.path(), LockMode::Update);
+        lock.check(
+            RegistryType::Skill,
+            "team",
+            "reviewer",
+            "1.0.0",
+            "uid-1",
+            &[0u8; 4],
+        )
+        .unwrap();
+        lock.save().unwrap();
+
+        let reloaded = AgentLockFile::load(&dir.path().join(".opsml-skills.lock")).unwrap();
+        assert_eq!(reloaded.artifacts[0].content_hash, "00000000");
+    }
+
+    #[test]
+    fn test_frozen_requires_pin_and_never_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let lock = open(dir.path(), LockMode::Frozen);
+        assert!(matches!(
+            lock.version_for(&RegistryType::SubAgent, "team", "planner", None),
+            Err(CliError::LockEntryMissing(..))
+        ));
+
+        lock_reviewer(dir.path());
+        let mut lock = open(dir.path(), LockMode::Frozen);
+        assert!(matches!(
+            lock.check(
+                RegistryType::Skill,
+                "team",
+                "reviewer",
+                "1.1.0",
+                "uid-2",
+                &HASH
+            ),
+            Err(CliError::LockVersionMismatch(..))
+        ));
+        assert!(matches!(
+            lock.check(
+                RegistryType::Skill,
+                "team",
+                "planner",
+                "1.0.0",
+                "uid-3",
+                &HASH
+            ),
+            Err(CliError::LockEntryMissing(..))
+        ));
+
+        lock.check(
+            RegistryType::Skill,
+            "team",
+            "reviewer",
+            "1.0.0",
+            "uid-1",
+            &HASH,
+        )
+        .unwrap();
+        lock.save().unwrap();
+        assert_eq!(
+            AgentLockFile::load(&dir.path().join(".opsml-skills.lock"))
+                .unwrap()
+                .artifacts
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_update_retains_listed_skills() {
+        let dir = tempfile::tempdir().unwrap();
+        lock_reviewer(dir.path());
+
+        let mut lock = open(dir.path(), LockMode::Update);
+        lock.retain_skills(&[("team".to_string(), "planner".to_string())]);
+        lock.save().unwrap();
+
+        let reloaded = AgentLockFile::load(&dir.path().join(".opsml-skills.lock")).unwrap();
+        assert!(reloaded.artifacts.is_empty());
+    }
+}
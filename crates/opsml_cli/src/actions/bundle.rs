@@ -0,0 +1,105 @@
+use crate::cli::arg::{ExportArgs, ImportArgs};
+use crate::error::CliError;
+use opsml_colors::Colorize;
+use opsml_registry::bundle::{export_bundle, import_bundle, read_signing_key};
+
+/// Export cards from the current server into a signed bundle
+///
+/// # Arguments
+/// * `args` - The command line arguments
+pub fn export_cards(args: &ExportArgs) -> Result<(), CliError> {
+    let signing_key = read_signing_key(&args.signing_key)?;
+    let manifest = export_bundle(
+        &args.cards,
+        &args.output,
+        &signing_key,
+        args.include_profiles,
+    )?;
+
+    for card in &manifest.cards {
+        println!(
+            "{} {} {}/{} v{} ({} files, {} profiles)",
+            Colorize::green("Exported"),
+            Colorize::purple(&card.registry_type.to_string()),
+            card.space,
+            card.name,
+            card.version,
+            card.files.len(),
+            card.profiles.len()
+        );
+    }
+    println!(
+        "{}",
+        Colorize::green(&format!(
+            "Wrote {} card(s) to {}",
+            manifest.cards.len(),
+            args.output.display()
+        ))
+    );
+
+    Ok(())
+}
+
+/// Import a signed bundle into the current server and report conflicts
+///
+/// # Arguments
+/// * `args` - The command line arguments
+pub fn import_cards(args: &ImportArgs) -> Result<(), CliError> {
+    let signing_key = read_signing_key(&args.signing_key)?;
+    let report = import_bundle(
+        &args.bundle,
+        &signing_key,
+        args.fail_on_conflict,
+        !args.skip_profiles,
+    )?;
+
+    for card in &report.imported {
+        let version_note = if card.version != card.source_version {
+            format!(" (exported as v{})", card.source_version)
+        } else {
+            String::new()
+        };
+        println!(
+            "{} {} {}/{} v{}{} uid={}",
+            Colorize::green("Imported"),
+            Colorize::purple(&card.registry_type.to_string()),
+            card.space,
+            card.name,
+            card.version,
+            version_note,
+            card.uid
+        );
+    }
+    for conflict in &report.conflicts {
+        println!(
+            "{} {} {}/{} v{} already exists (uid={})",
+            Colorize::alert("Conflict"),
+            Colorize::purple(&conflict.registry_type.to_string()),
+            conflict.space,
+            conflict.name,
+            conflict.version,
+            conflict.existing_uid
+        );
+    }
+    for reference in &report.unresolved_references {
+        println!(
+            "{} {} {}={} is not on this server and was cleared",
+            Colorize::alert("Unresolved reference"),
+            reference.card,
+            reference.field,
+            reference.uid
+        );
+    }
+
+    println!(
+        "{}",
+        Colorize::green(&format!(
+            "Imported {} card(s), {} conflict(s), {} drift profile(s) registered",
+            report.imported.len(),
+            report.conflicts.len(),
+            report.profiles_registered
+        ))
+    );
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use crate::cli::arg::ConfigShowArgs;
+use crate::error::CliError;
+use opsml_colors::Colorize;
+use opsml_settings::OpsmlConfig;
+use opsml_settings::profile::ProfileStore;
+use opsml_settings::source::{ConfigSource, display_value};
+
+/// Print the resolved configuration with secrets masked
+///
+/// # Arguments
+/// * `args` - The command line arguments
+pub fn show_config(args: &ConfigShowArgs) -> Result<(), CliError> {
+    let mut source = ConfigSource::load()?;
+    ProfileStore::load()?.apply(&mut source)?;
+
+    let path = source
+        .path
+        .as_ref()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| "none (environment only)".to_string());
+    println!("{} {}", Colorize::green("Config file:"), path);
+    println!(
+        "{} {}",
+        Colorize::green("Profile:"),
+        source.profile.as_deref().unwrap_or("none")
+    );
+    println!(
+        "{} {}",
+        Colorize::green("Client profile:"),
+        source.client_profile.as_deref().unwrap_or("none")
+    );
+    println!();
+
+    if !args.effective {
+        for (key, value, origin) in source.entries() {
+            println!("{key} = {:?}  # {origin}", display_value(key, value));
+        }
+        return Ok(());
+    }
+
+    let config = OpsmlConfig::from_source(&source);
+    for (key, value) in config.effective_settings(&source) {
+        let origin = source
+            .origin(key)
+            .map(|origin| origin.to_string())
+            .unwrap_or_else(|| "default".to_string());
+        println!("{key} = {:?}  # {origin}", display_value(key, &value));
+    }
+
+    if let Err(e) = config.validate() {
+        println!();
+        println!("{} {}", Colorize::alert("Warning:"), e);
+    }
+
+    Ok(())
+}
@@ -32,6 +32,8 @@ fn configure_eager(args: &ConfigureArgs) -> Result<(), CliError> {
         quiet: false,
         targets: Some(args.target.to_pull_targets()),
         path: None, // both layers
+        frozen: false,
+        update: false,
     })
 }
 
@@ -1,9 +1,11 @@
 use crate::error::CliError;
 use base64::prelude::*;
 use opsml_colors::Colorize;
+use opsml_crypt::LocalKeyProvider;
 pub use opsml_crypt::{derive_master_key, generate_salt};
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
+use std::path::Path;
 
 /// Create a structured response for the generated key
 ///
@@ -61,3 +63,25 @@ pub fn generate_key(password: &str, rounds: u32) -> Result<(), CliError> {
 
     Ok(())
 }
+
+/// Append a new key-encryption-key to the server keyfile
+///
+/// # Arguments
+/// * `keyfile` - Path of the keyfile, created if it does not exist
+pub fn generate_kek(keyfile: &Path) -> Result<(), CliError> {
+    let version = LocalKeyProvider::append_new_key(keyfile)?;
+
+    println!(
+        "{}",
+        Colorize::green(&format!(
+            "Added key version {version} to {}",
+            keyfile.display()
+        ))
+    );
+    println!(
+        "Restart the server with OPSML_KEK_PATH={} and run `opsml admin rotate-keys` to re-wrap stored keys",
+        keyfile.display()
+    );
+
+    Ok(())
+}
@@ -11,7 +11,7 @@ use opsml_registry::{CardRegistries, CardRegistry};
 use opsml_service::{OpsmlServiceSpec, service::DEFAULT_SERVICE_FILENAME};
 use opsml_toml::{LockArtifact, LockFile};
 use opsml_types::IntegratedService;
-use opsml_types::contracts::{CardVariant, ServiceType};
+use opsml_types::contracts::{CardVariant, ServiceType, parse_version_alias};
 use opsml_types::{RegistryType, contracts::CardRecord};
 #[cfg(feature = "python")]
 use pyo3::prelude::*;
@@ -256,6 +256,29 @@ pub fn lock_service_card(spec: &mut OpsmlServiceSpec) -> Result<LockArtifact, Cl
         service_version,
     )?;
 
+    // A stage alias (e.g. `@production`) pins the lock to whichever version the alias
+    // currently points to. The aliased service is locked as-is and never re-registered
+    if let Some(alias) = service_version.and_then(|version| parse_version_alias(version)) {
+        let service =
+            existing_service.ok_or_else(|| CliError::UnresolvedStageAlias(alias.to_string()))?;
+        debug!(
+            "Pinning service {}/{} to @{} (v{})",
+            space,
+            name,
+            alias,
+            service.version()
+        );
+
+        return Ok(LockArtifact {
+            space: service.space().to_string(),
+            name: service.name().to_string(),
+            version: service.version().to_string(),
+            uid: service.uid().to_string(),
+            registry_type: RegistryType::from(&spec.service_type),
+            write_dir: get_write_dir(spec, "opsml_service"),
+        });
+    }
+
     match existing_service {
         None => {
             debug!("No existing service found, creating new service");
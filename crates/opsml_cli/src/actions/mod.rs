@@ -1,9 +1,14 @@
+pub mod admin;
 pub mod agent;
+pub mod agent_lock;
+pub mod bundle;
 pub mod cache;
+pub mod config;
 pub mod configure;
 pub mod download;
 pub mod generate;
 pub mod manifest;
+pub mod profile;
 pub mod skill;
 pub mod sync;
 pub mod ui;
@@ -11,13 +16,16 @@ pub mod ui;
 pub mod list;
 pub mod lock;
 pub mod register;
+pub mod resolve;
 pub mod tool;
 pub mod update_profile;
 pub mod utils;
 pub mod validate;
+pub mod verify;
+pub mod workflow;
 
 pub use download::{download_card, download_service};
-pub use generate::generate_key;
+pub use generate::{generate_kek, generate_key};
 pub use list::list_cards;
 pub use ui::start_ui;
 pub use update_profile::update_drift_profile_status;
@@ -0,0 +1,179 @@
+use crate::cli::arg::{LoginArgs, ProfileUseArgs};
+use crate::error::CliError;
+use opsml_cards::default_space;
+use opsml_client::{OpsmlApiClient, TokenCache, build_http_client};
+use opsml_colors::Colorize;
+use opsml_settings::config::ApiSettings;
+use opsml_settings::profile::{ClientProfile, ProfileStore};
+use opsml_types::RegistryType;
+use opsml_types::api::{RequestType, Routes};
+
+/// Profile used by `opsml login` when `--profile` is not given
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// `--space` if given, otherwise the default space of the active client profile
+pub fn space_or_default(space: &Option<String>) -> Option<String> {
+    space.clone().or_else(default_space)
+}
+
+/// Authenticate against a server and save the connection as a named profile
+///
+/// # Arguments
+/// * `name` - Profile to create or update
+/// * `args` - The command line arguments
+pub fn login(name: &str, args: &LoginArgs) -> Result<(), CliError> {
+    let mut store = ProfileStore::load()?;
+    let existing = store.profiles.get(name).cloned().unwrap_or_default();
+
+    let tracking_uri = args
+        .tracking_uri
+        .clone()
+        .or_else(|| Some(existing.tracking_uri.clone()).filter(|uri| !uri.is_empty()))
+        .ok_or_else(|| {
+            CliError::Error(format!(
+                "--tracking-uri is required to create profile {name}"
+            ))
+        })?;
+
+    let token = args
+        .token
+        .clone()
+        .or_else(|| existing.token.clone().filter(|_| args.username.is_none()));
+
+    let (username, password) = match (&token, &args.username) {
+        (Some(_), _) => (None, None),
+        (None, Some(username)) => {
+            let password = match &args.password {
+                Some(password) => password.clone(),
+                None => rpassword::prompt_password(format!("Password for {username}: "))?,
+            };
+            (Some(username.clone()), Some(password))
+        }
+        (None, None) => (existing.username.clone(), existing.password.clone()),
+    };
+
+    let profile = ClientProfile {
+        tracking_uri: tracking_uri.trim_end_matches('/').to_string(),
+        username,
+        password,
+        token,
+        default_space: args.default_space.clone().or(existing.default_space),
+        use_sso: args.sso || existing.use_sso,
+    };
+
+    // authenticate before saving so a typo does not leave a broken profile behind
+    let token_cache = store.token_cache_path(name);
+    if token_cache.exists() {
+        std::fs::remove_file(&token_cache)?;
+    }
+    authenticate(&profile, TokenCache::new(token_cache))?;
+
+    store.upsert(name, profile.clone())?;
+    store.save()?;
+
+    let identity = match (&profile.token, &profile.username) {
+        (Some(_), _) => "an API token".to_string(),
+        (None, Some(username)) => username.clone(),
+        (None, None) => "guest".to_string(),
+    };
+    println!(
+        "{} to {} as {} (profile {})",
+        Colorize::green("Logged in"),
+        profile.tracking_uri,
+        identity,
+        Colorize::purple(name)
+    );
+
+    if store.active.as_deref() != Some(name) {
+        println!("Run `opsml profile use {name}` to make it the default");
+    }
+
+    Ok(())
+}
+
+fn authenticate(profile: &ClientProfile, token_cache: TokenCache) -> Result<(), CliError> {
+    let settings = ApiSettings {
+        base_url: profile.tracking_uri.clone(),
+        opsml_dir: "opsml/api".to_string(),
+        username: profile
+            .username
+            .clone()
+            .unwrap_or_else(|| "guest".to_string()),
+        password: profile
+            .password
+            .clone()
+            .unwrap_or_else(|| "guest".to_string()),
+        prod_token: None,
+        use_sso: profile.use_sso,
+        token: profile.token.clone(),
+        token_cache: None,
+        ca_bundle: None,
+    };
+
+    let client = build_http_client(&settings)?;
+    let url = format!("{}/{}", settings.base_url, settings.opsml_dir);
+    let api_client =
+        OpsmlApiClient::with_auth(url, &client, profile.token.clone(), Some(token_cache))?;
+
+    // API tokens skip the login, so check them against a protected route
+    let query = format!("registry_type={}", RegistryType::Model);
+    let response = api_client.request(
+        Routes::CardSpaces,
+        RequestType::Get,
+        None,
+        Some(query),
+        None,
+    )?;
+    if !response.status().is_success() {
+        return Err(CliError::Error(format!(
+            "Authentication failed with status {}",
+            response.status()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Make a profile the default for commands run without `--profile`
+pub fn use_profile(args: &ProfileUseArgs) -> Result<(), CliError> {
+    let mut store = ProfileStore::load()?;
+    store.set_active(&args.name)?;
+    store.save()?;
+
+    println!(
+        "{} {}",
+        Colorize::green("Using profile"),
+        Colorize::purple(&args.name)
+    );
+    Ok(())
+}
+
+/// Print the saved profiles, marking the active one
+pub fn list_profiles() -> Result<(), CliError> {
+    let store = ProfileStore::load()?;
+
+    if store.profiles.is_empty() {
+        println!("No profiles found. Create one with `opsml login --profile <name>`");
+        return Ok(());
+    }
+
+    for (name, profile) in &store.profiles {
+        let marker = if store.active.as_deref() == Some(name.as_str()) {
+            "*"
+        } else {
+            " "
+        };
+        let space = profile
+            .default_space
+            .as_deref()
+            .map(|space| format!(" (space {space})"))
+            .unwrap_or_default();
+        println!(
+            "{marker} {} {}{space}",
+            Colorize::purple(name),
+            profile.tracking_uri
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,609 @@
+use crate::error::CliError;
+use opsml_registry::registries::agent::OpsmlAgentRegistry;
+use opsml_registry::registries::card::OpsmlCardRegistry;
+use opsml_semver::VersionRange;
+use opsml_types::RegistryType;
+use opsml_types::contracts::{
+    CardQueryArgs, CardRecord, DependencyKind, McpServer, McpTransport, ServiceQueryArgs,
+    ServiceType, SkillDependency,
+};
+use opsml_utils::clean_string;
+use semver::Version;
+use serde_json::{Value, json};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt::Display;
+
+/// Upper bound on re-selection rounds before resolution is considered unstable
+const MAX_RESOLUTION_PASSES: usize = 64;
+
+/// Versions fetched per skill when looking for candidates
+const MAX_CANDIDATE_VERSIONS: i32 = 500;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PackageKey {
+    pub space: String,
+    pub name: String,
+}
+
+impl PackageKey {
+    pub fn new(space: &str, name: &str) -> Self {
+        Self {
+            space: clean_string(space),
+            name: clean_string(name),
+        }
+    }
+
+    fn from_dependency(dep: &SkillDependency) -> Self {
+        Self::new(&dep.space, &dep.name)
+    }
+}
+
+impl Display for PackageKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.space, self.name)
+    }
+}
+
+/// A registered version of a skill along with the dependencies it declares
+#[derive(Debug, Clone)]
+pub struct SkillCandidate {
+    pub version: Version,
+    pub dependencies: Vec<SkillDependency>,
+}
+
+#[derive(Debug, Clone)]
+struct Requirement {
+    version_req: Option<String>,
+    required_by: String,
+}
+
+impl Display for Requirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let version_req = self.version_req.as_deref().unwrap_or("*");
+        write!(f, "{version_req} (required by {})", self.required_by)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedSkill {
+    pub key: PackageKey,
+    pub version: Version,
+}
+
+/// Outcome of resolving a skill's dependency graph
+#[derive(Debug)]
+pub struct Resolution {
+    /// Skills to install, dependencies before the skills that require them.
+    /// The requested skill is always last
+    pub skills: Vec<ResolvedSkill>,
+    pub mcp_servers: Vec<McpServer>,
+}
+
+/// Where candidate versions come from. Implemented over the registry for `opsml skill pull`
+pub trait DependencySource {
+    fn skill_candidates(&self, key: &PackageKey) -> Result<Vec<SkillCandidate>, CliError>;
+
+    fn mcp_servers(&self, key: &PackageKey) -> Result<Vec<McpServer>, CliError>;
+}
+
+pub struct RegistrySource {
+    skills: OpsmlCardRegistry,
+    agents: OpsmlAgentRegistry,
+}
+
+impl RegistrySource {
+    pub fn new() -> Result<Self, CliError> {
+        Ok(Self {
+            skills: OpsmlCardRegistry::new(RegistryType::Skill)?,
+            agents: OpsmlAgentRegistry::new()?,
+        })
+    }
+
+    /// Version of `key` the registry returns for a user supplied version, alias or range
+    pub fn find_version(
+        &self,
+        key: &PackageKey,
+        version: Option<&str>,
+    ) -> Result<Version, CliError> {
+        let query_args = CardQueryArgs {
+            space: Some(key.space.clone()),
+            name: Some(key.name.clone()),
+            version: version.map(String::from),
+            registry_type: RegistryType::Skill,
+            limit: Some(1),
+            ..Default::default()
+        };
+
+        let record = self
+            .skills
+            .list_cards(&query_args)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| CliError::DependencyNotFound(key.to_string()))?;
+
+        Version::parse(record.version())
+            .map_err(|e| CliError::Error(format!("Invalid version for {key}: {e}")))
+    }
+}
+
+impl DependencySource for RegistrySource {
+    fn skill_candidates(&self, key: &PackageKey) -> Result<Vec<SkillCandidate>, CliError> {
+        let query_args = CardQueryArgs {
+            space: Some(key.space.clone()),
+            name: Some(key.name.clone()),
+            registry_type: RegistryType::Skill,
+            limit: Some(MAX_CANDIDATE_VERSIONS),
+            ..Default::default()
+        };
+
+        let candidates = self
+            .skills
+            .list_cards(&query_args)?
+            .into_iter()
+            .filter_map(|record| match record {
+                CardRecord::Skill(skill) => {
+                    Version::parse(&skill.version)
+                        .ok()
+                        .map(|version| SkillCandidate {
+                            version,
+                            dependencies: skill.dependencies,
+                        })
+                }
+                _ => None,
+            })
+            .collect();
+
+        Ok(candidates)
+    }
+
+    fn mcp_servers(&self, key: &PackageKey) -> Result<Vec<McpServer>, CliError> {
+        let args = ServiceQueryArgs {
+            space: Some(key.space.clone()),
+            name: Some(key.name.clone()),
+            tags: None,
+            service_type: ServiceType::Mcp,
+        };
+
+        Ok(self.agents.list_mcp_servers(&args)?.servers)
+    }
+}
+
+/// Resolves the full dependency graph of `root`, pinned to `root_version`.
+///
+/// Every skill gets its `locked` version if that satisfies all requirements placed on it by the
+/// currently selected versions, otherwise the highest version that does. Selection is repeated until it no longer changes, since picking
+/// a different version of one skill can add or drop requirements on others.
+///
+/// # Errors
+///
+/// * `DependencyNotFound` - a required skill or MCP server is not registered
+/// * `DependencyConflict` - no registered version satisfies every requirement
+/// * `DependencyCycle` - the selected skills depend on each other in a loop
+pub fn resolve_dependencies(
+    source: &dyn DependencySource,
+    root: &PackageKey,
+    root_version: &Version,
+    locked: &HashMap<PackageKey, Version>,
+) -> Result<Resolution, CliError> {
+    let mut candidates: HashMap<PackageKey, Vec<SkillCandidate>> = HashMap::new();
+    let mut selected: BTreeMap<PackageKey, SkillCandidate> = BTreeMap::new();
+    let root_requirement = Requirement {
+        version_req: Some(format!("={root_version}")),
+        required_by: "command line".to_string(),
+    };
+
+    for _ in 0..MAX_RESOLUTION_PASSES {
+        let (requirements, _) = collect_requirements(root, &root_requirement, &selected);
+
+        let mut next = BTreeMap::new();
+        for (key, reqs) in &requirements {
+            let versions = match candidates.entry(key.clone()) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(source.skill_candidates(key)?),
+            };
+
+            let candidate = select_version(key, versions, reqs, locked.get(key), |c| {
+                Some(c.version.clone())
+            })?;
+            next.insert(key.clone(), candidate.clone());
+        }
+
+        let settled = next.len() == selected.len()
+            && next.iter().all(|(key, candidate)| {
+                selected
+                    .get(key)
+                    .is_some_and(|s| s.version == candidate.version)
+            });
+        selected = next;
+
+        if settled {
+            return finish_resolution(source, root, &root_requirement, &selected);
+        }
+    }
+
+    Err(CliError::Error(format!(
+        "Dependency resolution for {root} did not settle after {MAX_RESOLUTION_PASSES} passes"
+    )))
+}
+
+type Requirements = BTreeMap<PackageKey, Vec<Requirement>>;
+
+/// Walks the selected versions from `root` and gathers the requirements placed on every
+/// reachable skill and MCP server
+fn collect_requirements(
+    root: &PackageKey,
+    root_requirement: &Requirement,
+    selected: &BTreeMap<PackageKey, SkillCandidate>,
+) -> (Requirements, Requirements) {
+    let mut skills: Requirements = BTreeMap::new();
+    let mut mcp_servers: Requirements = BTreeMap::new();
+
+    let mut queue = VecDeque::from([(root.clone(), root_requirement.clone())]);
+    while let Some((key, requirement)) = queue.pop_front() {
+        let first_visit = !skills.contains_key(&key);
+        skills.entry(key.clone()).or_default().push(requirement);
+
+        let Some(candidate) = selected.get(&key).filter(|_| first_visit) else {
+            continue;
+        };
+
+        let required_by = format!("{key} v{}", candidate.version);
+        for dep in &candidate.dependencies {
+            let requirement = Requirement {
+                version_req: dep.version_req.clone(),
+                required_by: required_by.clone(),
+            };
+            match dep.kind {
+                DependencyKind::Skill => {
+                    queue.push_back((PackageKey::from_dependency(dep), requirement));
+                }
+                DependencyKind::McpServer => {
+                    mcp_servers
+                        .entry(PackageKey::from_dependency(dep))
+                        .or_default()
+                        .push(requirement);
+                }
+            }
+        }
+    }
+
+    (skills, mcp_servers)
+}
+
+/// Picks the `preferred` version if it satisfies every requirement, otherwise the highest
+/// version that does
+fn select_version<'a, T>(
+    key: &PackageKey,
+    candidates: &'a [T],
+    requirements: &[Requirement],
+    preferred: Option<&Version>,
+    version: impl Fn(&T) -> Option<Version>,
+) -> Result<&'a T, CliError> {
+    if candidates.is_empty() {
+        return Err(CliError::DependencyNotFound(key.to_string()));
+    }
+
+    let ranges = requirements
+        .iter()
+        .filter_map(|r| r.version_req.as_deref())
+        .filter(|r| !r.is_empty() && *r != "latest")
+        .map(|r| {
+            VersionRange::parse(r).map_err(|e| {
+                CliError::Error(format!("Invalid version requirement '{r}' for {key}: {e}"))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let matching = candidates
+        .iter()
+        .filter_map(|candidate| version(candidate).map(|v| (v, candidate)))
+        .filter(|(v, _)| ranges.iter().all(|range| range.matches(v)))
+        .collect::<Vec<_>>();
+
+    matching
+        .iter()
+        .find(|(v, _)| Some(v) == preferred)
+        .or_else(|| matching.iter().max_by(|(a, _), (b, _)| a.cmp(b)))
+        .map(|(_, candidate)| *candidate)
+        .ok_or_else(|| {
+            let requirements = requirements
+                .iter()
+                .map(Requirement::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            CliError::DependencyConflict(key.to_string(), requirements)
+        })
+}
+
+fn finish_resolution(
+    source: &dyn DependencySource,
+    root: &PackageKey,
+    root_requirement: &Requirement,
+    selected: &BTreeMap<PackageKey, SkillCandidate>,
+) -> Result<Resolution, CliError> {
+    let mut skills = Vec::with_capacity(selected.len());
+    let mut visiting = Vec::new();
+    order_skills(root, selected, &mut visiting, &mut skills)?;
+
+    let (_, mcp_requirements) = collect_requirements(root, root_requirement, selected);
+    let mut mcp_servers = Vec::with_capacity(mcp_requirements.len());
+    for (key, reqs) in &mcp_requirements {
+        let servers = source.mcp_servers(key)?;
+        let server = select_version(key, &servers, reqs, None, |s| {
+            Version::parse(&s.version).ok()
+        })?;
+        mcp_servers.push(server.clone());
+    }
+
+    Ok(Resolution {
+        skills,
+        mcp_servers,
+    })
+}
+
+/// Depth-first post-order over the selected skills, so dependencies come before dependents.
+/// Reaching a skill that is still on the current path means the graph has a cycle
+fn order_skills(
+    key: &PackageKey,
+    selected: &BTreeMap<PackageKey, SkillCandidate>,
+    visiting: &mut Vec<PackageKey>,
+    ordered: &mut Vec<ResolvedSkill>,
+) -> Result<(), CliError> {
+    if let Some(start) = visiting.iter().position(|k| k == key) {
+        let cycle = visiting[start..]
+            .iter()
+            .chain(std::iter::once(key))
+            .map(PackageKey::to_string)
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        return Err(CliError::DependencyCycle(cycle));
+    }
+
+    if ordered.iter().any(|s| &s.key == key) {
+        return Ok(());
+    }
+
+    let candidate = &selected[key];
+    visiting.push(key.clone());
+    for dep in &candidate.dependencies {
+        if dep.kind == DependencyKind::Skill {
+            order_skills(
+                &PackageKey::from_dependency(dep),
+                selected,
+                visiting,
+                ordered,
+            )?;
+        }
+    }
+    visiting.pop();
+
+    ordered.push(ResolvedSkill {
+        key: key.clone(),
+        version: candidate.version.clone(),
+    });
+
+    Ok(())
+}
+
+/// MCP config entry for a registered server. Only HTTP servers can be configured from the
+/// registry record; stdio servers need a launch command that the registry does not store
+pub fn mcp_entry(server: &McpServer) -> Option<Value> {
+    match server.config.transport {
+        McpTransport::Http => server
+            .urls
+            .first()
+            .map(|url| json!({ "type": "http", "url": url })),
+        McpTransport::Stdio => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opsml_types::contracts::McpConfig;
+
+    #[derive(Default)]
+    struct InMemorySource {
+        skills: HashMap<PackageKey, Vec<SkillCandidate>>,
+        mcp_servers: HashMap<PackageKey, Vec<McpServer>>,
+    }
+
+    impl InMemorySource {
+        fn skill(mut self, name: &str, version: &str, deps: Vec<SkillDependency>) -> Self {
+            self.skills
+                .entry(PackageKey::new("team", name))
+                .or_default()
+                .push(SkillCandidate {
+                    version: Version::parse(version).unwrap(),
+                    dependencies: deps,
+                });
+            self
+        }
+
+        fn mcp(mut self, name: &str, version: &str) -> Self {
+            self.mcp_servers
+                .entry(PackageKey::new("team", name))
+                .or_default()
+                .push(McpServer {
+                    space: "team".to_string(),
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    environment: "dev".to_string(),
+                    urls: vec![format!("http://localhost/{name}")],
+                    tags: vec![],
+                    config: McpConfig::new_rs(vec![], McpTransport::Http),
+                    description: None,
+                });
+            self
+        }
+    }
+
+    impl DependencySource for InMemorySource {
+        fn skill_candidates(&self, key: &PackageKey) -> Result<Vec<SkillCandidate>, CliError> {
+            Ok(self.skills.get(key).cloned().unwrap_or_default())
+        }
+
+        fn mcp_servers(&self, key: &PackageKey) -> Result<Vec<McpServer>, CliError> {
+            Ok(self.mcp_servers.get(key).cloned().unwrap_or_default())
+        }
+    }
+
+    fn skill_dep(name: &str, version_req: &str) -> SkillDependency {
+        SkillDependency::new_rs(
+            name.to_string(),
+            "team".to_string(),
+            DependencyKind::Skill,
+            Some(version_req.to_string()),
+        )
+    }
+
+    fn mcp_dep(name: &str, version_req: &str) -> SkillDependency {
+        SkillDependency::new_rs(
+            name.to_string(),
+            "team".to_string(),
+            DependencyKind::McpServer,
+            Some(version_req.to_string()),
+        )
+    }
+
+    fn resolve(source: &InMemorySource, name: &str, version: &str) -> Result<Resolution, CliError> {
+        resolve_dependencies(
+            source,
+            &PackageKey::new("team", name),
+            &Version::parse(version).unwrap(),
+            &HashMap::new(),
+        )
+    }
+
+    #[test]
+    fn test_resolve_transitive_skills_and_mcp_servers() {
+        let source = InMemorySource::default()
+            .skill(
+                "reviewer",
+                "1.0.0",
+                vec![
+                    skill_dep("formatter", ">=1.0.0, <2.0.0"),
+                    skill_dep("linter", ">=2.0.0, <3.0.0"),
+                    mcp_dep("search", "^0.3.0"),
+                ],
+            )
+            .skill("formatter", "1.0.0", vec![])
+            .skill("formatter", "1.4.0", vec![])
+            .skill("formatter", "2.0.0", vec![])
+            .skill("linter", "2.1.0", vec![skill_dep("formatter", "~1.4")])
+            .mcp("search", "0.3.2")
+            .mcp("search", "0.4.0");
+
+        let resolution = resolve(&source, "reviewer", "1.0.0").unwrap();
+        let skills = resolution
+            .skills
+            .iter()
+            .map(|s| format!("{} v{}", s.key, s.version))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            skills,
+            vec![
+                "team/formatter v1.4.0",
+                "team/linter v2.1.0",
+                "team/reviewer v1.0.0"
+            ]
+        );
+        assert_eq!(resolution.mcp_servers.len(), 1);
+        assert_eq!(resolution.mcp_servers[0].version, "0.3.2");
+    }
+
+    #[test]
+    fn test_resolve_reselects_when_requirements_tighten() {
+        // helper 2.0.0 pulls in a tighter requirement on util than helper 1.0.0 did
+        let source = InMemorySource::default()
+            .skill(
+                "root",
+                "1.0.0",
+                vec![skill_dep("helper", "*"), skill_dep("util", "*")],
+            )
+            .skill("helper", "2.0.0", vec![skill_dep("util", "<1.5.0")])
+            .skill("util", "1.2.0", vec![])
+            .skill("util", "1.9.0", vec![]);
+
+        let resolution = resolve(&source, "root", "1.0.0").unwrap();
+        let util = resolution
+            .skills
+            .iter()
+            .find(|s| s.key.name == "util")
+            .unwrap();
+        assert_eq!(util.version, Version::new(1, 2, 0));
+    }
+
+    #[test]
+    fn test_resolve_prefers_locked_versions() {
+        let locked_source = || {
+            InMemorySource::default()
+                .skill("root", "1.0.0", vec![skill_dep("helper", "^1.0.0")])
+                .skill("helper", "1.0.0", vec![])
+        };
+        let resolution = resolve(&locked_source(), "root", "1.0.0").unwrap();
+        let locked: HashMap<PackageKey, Version> = resolution
+            .skills
+            .into_iter()
+            .map(|skill| (skill.key, skill.version))
+            .collect();
+
+        // a newer in-range helper published after locking does not replace the pin
+        let source = locked_source().skill("helper", "1.1.0", vec![]);
+        let root = PackageKey::new("team", "root");
+        let resolution =
+            resolve_dependencies(&source, &root, &Version::new(1, 0, 0), &locked).unwrap();
+        assert_eq!(resolution.skills[0].version, Version::new(1, 0, 0));
+
+        // without a lock the newest version wins
+        let resolution = resolve(&source, "root", "1.0.0").unwrap();
+        assert_eq!(resolution.skills[0].version, Version::new(1, 1, 0));
+
+        // a pin the requirements no longer allow is replaced
+        let source = InMemorySource::default()
+            .skill("root", "1.0.0", vec![skill_dep("helper", ">=1.1.0")])
+            .skill("helper", "1.0.0", vec![])
+            .skill("helper", "1.1.0", vec![]);
+        let resolution =
+            resolve_dependencies(&source, &root, &Version::new(1, 0, 0), &locked).unwrap();
+        assert_eq!(resolution.skills[0].version, Version::new(1, 1, 0));
+    }
+
+    #[test]
+    fn test_resolve_conflict() {
+        let source = InMemorySource::default()
+            .skill(
+                "root",
+                "1.0.0",
+                vec![skill_dep("helper", "1.*"), skill_dep("util", ">=2.0.0")],
+            )
+            .skill("helper", "1.0.0", vec![skill_dep("util", "<2.0.0")])
+            .skill("util", "1.0.0", vec![])
+            .skill("util", "2.0.0", vec![]);
+
+        let err = resolve(&source, "root", "1.0.0").unwrap_err();
+        assert!(matches!(err, CliError::DependencyConflict(ref key, _) if key == "team/util"));
+        assert!(err.to_string().contains("required by team/helper v1.0.0"));
+    }
+
+    #[test]
+    fn test_resolve_cycle() {
+        let source = InMemorySource::default()
+            .skill("a", "1.0.0", vec![skill_dep("b", "*")])
+            .skill("b", "1.0.0", vec![skill_dep("a", "*")]);
+
+        let err = resolve(&source, "a", "1.0.0").unwrap_err();
+        assert!(matches!(err, CliError::DependencyCycle(_)));
+        assert!(err.to_string().contains("team/a -> team/b -> team/a"));
+    }
+
+    #[test]
+    fn test_resolve_missing_dependency() {
+        let source = InMemorySource::default().skill("root", "1.0.0", vec![mcp_dep("search", "*")]);
+
+        let err = resolve(&source, "root", "1.0.0").unwrap_err();
+        assert!(matches!(err, CliError::DependencyNotFound(ref key) if key == "team/search"));
+    }
+}
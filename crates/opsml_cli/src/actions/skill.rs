@@ -1,3 +1,6 @@
+use crate::actions::agent_lock::{AgentLock, LockMode, scope_lock_path};
+use crate::actions::profile::space_or_default;
+use crate::actions::resolve::{PackageKey, RegistrySource, mcp_entry, resolve_dependencies};
 use crate::cli::arg::{
     PullTarget, SkillInitArgs, SkillListArgs, SkillPullArgs, SkillPushArgs, SkillRemoveArgs,
 };
@@ -8,8 +11,10 @@ use opsml_registry::download::download_card_from_registry;
 use opsml_registry::registries::card::OpsmlCardRegistry;
 use opsml_registry::registry::CardRegistry;
 use opsml_types::RegistryType;
-use opsml_types::contracts::{CardList, CardQueryArgs, CardRecord};
+use opsml_types::contracts::{CardList, CardQueryArgs, CardRecord, McpServer};
 use opsml_utils::clean_string;
+use semver::Version;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use tracing::instrument;
 
@@ -78,7 +83,7 @@ pub fn push_skill(args: &SkillPushArgs) -> Result<(), CliError> {
         .map_err(|e| CliError::Error(e.to_string()))?;
 
     // Normalize space and name regardless of source (markdown or CLI override)
-    card.space = clean_string(args.space.as_deref().unwrap_or(&card.space));
+    card.space = clean_string(&space_or_default(&args.space).unwrap_or_else(|| card.space.clone()));
     card.name = clean_string(&card.name);
 
     if let Some(tags) = &args.tags {
@@ -103,17 +108,18 @@ pub fn push_skill(args: &SkillPushArgs) -> Result<(), CliError> {
     Ok(())
 }
 
-#[instrument(skip_all)]
-pub fn pull_skill(args: &SkillPullArgs) -> Result<(), CliError> {
-    let (space, name) = parse_skill_identifier(&args.name, args.space.as_deref())?;
-
-    let space_clean = clean_string(&space);
-    let name_clean = clean_string(&name);
-
+/// Download one resolved skill version, check it against the lockfile and write it under the
+/// directory of `output_path`
+fn install_skill(
+    key: &PackageKey,
+    version: &Version,
+    output_path: &std::path::Path,
+    lock: &mut AgentLock,
+) -> Result<(opsml_cards::SkillCard, PathBuf), CliError> {
     let query_args = CardQueryArgs {
-        space: Some(space_clean.clone()),
-        name: Some(name_clean.clone()),
-        version: args.version.clone(),
+        space: Some(key.space.clone()),
+        name: Some(key.name.clone()),
+        version: Some(version.to_string()),
         registry_type: RegistryType::Skill,
         limit: Some(1),
         ..Default::default()
@@ -131,21 +137,24 @@ pub fn pull_skill(args: &SkillPullArgs) -> Result<(), CliError> {
     let card_json = find_card_json(&tmp_path, 0)?;
     let card = opsml_cards::SkillCard::from_path(card_json)?;
 
-    let markdown = card.to_markdown()?;
-
-    let output_path = resolve_pull_path(
-        &name_clean,
-        args.output.as_ref(),
-        args.target.as_ref(),
-        args.local,
+    lock.check(
+        RegistryType::Skill,
+        &key.space,
+        &key.name,
+        &card.version,
+        &card.uid,
+        &card.calculate_content_hash()?,
     )?;
 
+    let markdown = card.to_markdown()?;
+
     let output_dir = output_path
         .parent()
-        .unwrap_or_else(|| std::path::Path::new("."));
+        .unwrap_or_else(|| std::path::Path::new("."))
+        .to_path_buf();
 
-    std::fs::create_dir_all(output_dir)?;
-    copy_downloaded_skill_files(&tmp_path, output_dir)?;
+    std::fs::create_dir_all(&output_dir)?;
+    copy_downloaded_skill_files(&tmp_path, &output_dir)?;
 
     let skill_md_path = output_dir.join("SKILL.md");
     if !skill_md_path.exists() {
@@ -155,7 +164,137 @@ pub fn pull_skill(args: &SkillPullArgs) -> Result<(), CliError> {
     // tmp_dir drops here, cleaning up automatically
     drop(tmp_dir);
 
-    // Auto-track: append to the appropriate skills.yaml unless --no-track
+    Ok((card, output_dir))
+}
+
+/// Output path for a dependency of the requested skill. With `--output`, dependencies are
+/// written next to the requested skill's directory
+fn resolve_dependency_path(
+    name: &str,
+    root_path: &std::path::Path,
+    args: &SkillPullArgs,
+) -> Result<PathBuf, CliError> {
+    if args.output.is_some() {
+        let root_dir = root_path.parent().unwrap_or(std::path::Path::new("."));
+        let parent = root_dir.parent().unwrap_or(std::path::Path::new("."));
+        return Ok(parent.join(name).join("SKILL.md"));
+    }
+
+    resolve_pull_path(name, None, args.target.as_ref(), args.local)
+}
+
+/// Merge the MCP servers required by the resolved skills into the target CLI's MCP config
+fn configure_mcp_servers(
+    servers: &[McpServer],
+    target: Option<&PullTarget>,
+) -> Result<(), CliError> {
+    if servers.is_empty() {
+        return Ok(());
+    }
+
+    let Some(target) = target else {
+        eprintln!(
+            "warn: {} required MCP server(s) were not configured; pass --target to add them to your CLI's MCP config",
+            servers.len()
+        );
+        return Ok(());
+    };
+
+    let framework = target.framework();
+    for server in servers {
+        let Some(entry) = mcp_entry(server) else {
+            eprintln!(
+                "warn: {}/{} uses the {} transport and must be added to {} manually",
+                server.space,
+                server.name,
+                server.config.transport,
+                framework.mcp_config_path().display()
+            );
+            continue;
+        };
+
+        framework
+            .merge_mcp_entry(&server.name, entry)
+            .map_err(|e| CliError::Error(e.to_string()))?;
+
+        println!(
+            "{} MCP server {}/{} v{} -> {}",
+            Colorize::green("Configured"),
+            Colorize::purple(&server.space),
+            server.name,
+            Colorize::green(&server.version),
+            framework.mcp_config_path().display(),
+        );
+    }
+
+    Ok(())
+}
+
+#[instrument(skip_all)]
+pub fn pull_skill(args: &SkillPullArgs) -> Result<(), CliError> {
+    let (space, name) =
+        parse_skill_identifier(&args.name, space_or_default(&args.space).as_deref())?;
+
+    let root = PackageKey::new(&space, &name);
+
+    let mode = LockMode::from_flags(args.frozen, false);
+    let mut lock = AgentLock::open(&scope_lock_path(args.local)?, mode)?;
+    let requested = lock.version_for(
+        &RegistryType::Skill,
+        &root.space,
+        &root.name,
+        args.version.as_deref(),
+    )?;
+
+    // resolve the whole dependency graph before writing anything to disk
+    let source = RegistrySource::new()?;
+    let root_version = source.find_version(&root, requested.as_deref())?;
+
+    // dependencies stay on their pins while the requirements allow it, so newly published
+    // versions neither break frozen pulls nor silently re-lock
+    let locked = lock
+        .pins(&RegistryType::Skill)
+        .into_iter()
+        .filter_map(|pin| {
+            Version::parse(&pin.version)
+                .ok()
+                .map(|version| (PackageKey::new(&pin.space, &pin.name), version))
+        })
+        .collect::<HashMap<_, _>>();
+    let resolution = resolve_dependencies(&source, &root, &root_version, &locked)?;
+
+    let root_path = resolve_pull_path(
+        &root.name,
+        args.output.as_ref(),
+        args.target.as_ref(),
+        args.local,
+    )?;
+
+    for skill in &resolution.skills {
+        let output_path = if skill.key == root {
+            root_path.clone()
+        } else {
+            resolve_dependency_path(&skill.key.name, &root_path, args)?
+        };
+
+        let (card, output_dir) =
+            install_skill(&skill.key, &skill.version, &output_path, &mut lock)?;
+
+        println!(
+            "{} {}/{} v{} -> {}",
+            Colorize::green("Pulled"),
+            Colorize::purple(&card.space),
+            card.name,
+            Colorize::green(&card.version),
+            output_dir.display(),
+        );
+    }
+
+    lock.save()?;
+
+    configure_mcp_servers(&resolution.mcp_servers, args.target.as_ref())?;
+
+    // Auto-track: record the resolved set in the appropriate skills.yaml unless --no-track
     if !args.no_track {
         let yaml_path = if args.local {
             PathBuf::from(".opsml-skills.yaml")
@@ -166,34 +305,46 @@ pub fn pull_skill(args: &SkillPullArgs) -> Result<(), CliError> {
                 .join("skills.yaml")
         };
 
-        let ref_entry = opsml_toml::ArtifactRef {
-            space: space_clean.clone(),
-            name: name_clean.clone(),
-            version: args.version.clone(),
-        };
+        // the requested skill keeps the version the user asked for; dependencies are pinned
+        let skills = resolution
+            .skills
+            .iter()
+            .rev()
+            .map(|skill| opsml_toml::ArtifactRef {
+                space: skill.key.space.clone(),
+                name: skill.key.name.clone(),
+                version: if skill.key == root {
+                    args.version.clone()
+                } else {
+                    Some(skill.version.to_string())
+                },
+            })
+            .collect::<Vec<_>>();
+
+        let mcp_servers = resolution
+            .mcp_servers
+            .iter()
+            .map(|server| opsml_toml::ArtifactRef {
+                space: server.space.clone(),
+                name: server.name.clone(),
+                version: Some(server.version.clone()),
+            })
+            .collect::<Vec<_>>();
 
         let registry_url = std::env::var("OPSML_TRACKING_URI").unwrap_or_default();
-        if let Err(e) =
-            opsml_toml::OpsmlSkillsYaml::append_skill(&yaml_path, &ref_entry, &registry_url)
-        {
+        if let Err(e) = opsml_toml::OpsmlSkillsYaml::append_resolved(
+            &yaml_path,
+            &skills,
+            &mcp_servers,
+            &registry_url,
+        ) {
             eprintln!(
-                "warn: failed to track {}/{} in {}: {e}",
-                space_clean,
-                name_clean,
+                "warn: failed to track {root} in {}: {e}",
                 yaml_path.display()
             );
         }
     }
 
-    println!(
-        "{} {}/{} v{} -> {}",
-        Colorize::green("Pulled"),
-        Colorize::purple(&card.space),
-        card.name,
-        Colorize::green(&card.version),
-        output_dir.display(),
-    );
-
     Ok(())
 }
 
@@ -300,7 +451,7 @@ pub(crate) fn find_card_json(dir: &std::path::Path, depth: usize) -> Result<Path
 pub fn list_skills(args: &SkillListArgs) -> Result<(), CliError> {
     println!("\nListing cards from {} registry", Colorize::green("skill"));
 
-    let space = args.space.clone().map(|s| clean_string(&s));
+    let space = space_or_default(&args.space).map(|s| clean_string(&s));
 
     let name = args.name.clone().map(|n| clean_string(&n));
 
@@ -335,7 +486,8 @@ pub fn list_skills(args: &SkillListArgs) -> Result<(), CliError> {
 
 #[instrument(skip_all)]
 pub fn remove_skill(args: &SkillRemoveArgs) -> Result<(), CliError> {
-    let (space_raw, name_raw) = parse_skill_identifier(&args.name, args.space.as_deref())?;
+    let (space_raw, name_raw) =
+        parse_skill_identifier(&args.name, space_or_default(&args.space).as_deref())?;
     let space = clean_string(&space_raw);
     let name = clean_string(&name_raw);
 
@@ -1,3 +1,4 @@
+use crate::actions::agent_lock::{AgentLock, LockMode};
 use crate::actions::cache::{CacheEntry, CacheManifest};
 use crate::actions::manifest::{SkillEntry, SkillManifest};
 use crate::actions::skill::find_card_json;
@@ -6,11 +7,10 @@ use crate::error::CliError;
 use chrono::{Duration, Utc};
 use opsml_colors::Colorize;
 use opsml_registry::download::download_card_from_registry;
-use opsml_toml::OpsmlSkillsYaml;
+use opsml_toml::{AgentLockFile, OpsmlSkillsYaml};
 use opsml_types::RegistryType;
 use opsml_types::contracts::CardQueryArgs;
 use opsml_utils::clean_string;
-use std::fmt::Write as FmtWrite;
 use std::path::{Path, PathBuf};
 use tracing::instrument;
 
@@ -111,6 +111,17 @@ fn sync_one_layer(
     let ttl = Duration::minutes(ttl_mins);
     let project_root = yaml_path.parent().unwrap_or(Path::new("."));
 
+    let mut lock = AgentLock::open(
+        &AgentLockFile::path_for(yaml_path),
+        LockMode::from_flags(args.frozen, args.update),
+    )?;
+    let listed = yaml
+        .skills
+        .iter()
+        .map(|s| (clean_string(&s.space), clean_string(&s.name)))
+        .collect::<Vec<_>>();
+    lock.retain_skills(&listed);
+
     let (mut pulled, mut skipped, mut not_found) = (0usize, 0usize, 0usize);
 
     for skill_ref in &yaml.skills {
@@ -128,7 +139,9 @@ fn sync_one_layer(
             format!("project:{}/{}/{}", abs.display(), space, name)
         };
 
-        if !args.force && is_cache_fresh(cache, &cache_key, ttl) {
+        // frozen and update installs always go back to the registry to check content
+        let use_cache = !args.force && lock.mode() == LockMode::Default;
+        if use_cache && is_cache_fresh(cache, &cache_key, ttl) {
             if !args.quiet {
                 println!("  {} {}/{} (cached)", Colorize::purple("Skip"), space, name);
             }
@@ -136,15 +149,17 @@ fn sync_one_layer(
             continue;
         }
 
-        let version = skill_ref
-            .version
-            .as_deref()
-            .filter(|v| !v.is_empty() && *v != "latest");
+        let version = lock.version_for(
+            &RegistryType::Skill,
+            &space,
+            &name,
+            skill_ref.version.as_deref(),
+        )?;
 
         let query_args = CardQueryArgs {
             space: Some(space.clone()),
             name: Some(name.clone()),
-            version: version.map(String::from),
+            version,
             registry_type: RegistryType::Skill,
             limit: Some(1),
             ..Default::default()
@@ -157,6 +172,8 @@ fn sync_one_layer(
 
         match download_card_from_registry(&query_args, tmp_dir.path().to_path_buf()) {
             Ok(()) => {}
+            // a frozen install must reproduce the lockfile exactly
+            Err(e) if lock.mode() == LockMode::Frozen => return Err(e.into()),
             Err(e) => {
                 // Treat download failures as "not found" — warn and skip
                 if !args.quiet {
@@ -187,13 +204,16 @@ fn sync_one_layer(
         let hash_bytes = card
             .calculate_content_hash()
             .map_err(|e| CliError::Error(format!("Failed to compute content hash: {e}")))?;
-        let hash: String =
-            hash_bytes
-                .iter()
-                .fold(String::with_capacity(hash_bytes.len() * 2), |mut s, b| {
-                    let _ = write!(s, "{b:02x}");
-                    s
-                });
+        let hash = hex::encode(&hash_bytes);
+
+        lock.check(
+            RegistryType::Skill,
+            &space,
+            &name,
+            &card.version,
+            &card.uid,
+            &hash_bytes,
+        )?;
 
         validate_artifact_name(&name)?;
         validate_artifact_name(&space)?;
@@ -251,6 +271,7 @@ fn sync_one_layer(
                 skill_ref.space, card.name
             );
         }
+        lock.save()?;
 
         if !args.quiet {
             println!(
@@ -264,6 +285,9 @@ fn sync_one_layer(
         pulled += 1;
     }
 
+    // pruned entries are only written here when every skill was skipped or missing
+    lock.save()?;
+
     // Always update generated_at so the startup hook knows sync ran recently,
     // even if every skill was a cache hit and no individual saves occurred.
     if let Err(e) = cache.save() {
@@ -514,6 +538,8 @@ mod tests {
             quiet: true,
             targets: None,
             path: Some(yaml_path),
+            frozen: false,
+            update: false,
         });
 
         // Empty skills list: loop body never executes.
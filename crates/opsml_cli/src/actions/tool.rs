@@ -1,3 +1,5 @@
+use crate::actions::agent_lock::{AgentLock, LockMode, scope_lock_path};
+use crate::actions::profile::space_or_default;
 use crate::actions::skill::find_card_json;
 use crate::cli::arg::{PullTarget, ToolInitArgs, ToolListArgs, ToolPullArgs, ToolPushArgs};
 use crate::error::CliError;
@@ -47,7 +49,7 @@ pub fn push_tool(args: &ToolPushArgs) -> Result<(), CliError> {
     let mut card: opsml_cards::ToolCard =
         parse_tool_markdown(&content).map_err(|e| CliError::Error(e.to_string()))?;
 
-    card.space = clean_string(args.space.as_deref().unwrap_or(&card.space));
+    card.space = clean_string(&space_or_default(&args.space).unwrap_or_else(|| card.space.clone()));
     card.name = clean_string(&card.name);
 
     if let Some(tags) = &args.tags {
@@ -70,15 +72,28 @@ pub fn push_tool(args: &ToolPushArgs) -> Result<(), CliError> {
 
 #[instrument(skip_all)]
 pub fn pull_tool(args: &ToolPullArgs) -> Result<(), CliError> {
-    let (space, name) = parse_tool_identifier(&args.name, args.space.as_deref())?;
+    let (space, name) =
+        parse_tool_identifier(&args.name, space_or_default(&args.space).as_deref())?;
 
     let space_clean = clean_string(&space);
     let name_clean = clean_string(&name);
 
+    // the tool is pinned in the lockfile of the scope it is installed into
+    let mut lock = AgentLock::open(
+        &scope_lock_path(!args.global)?,
+        LockMode::from_flags(args.frozen, false),
+    )?;
+    let version = lock.version_for(
+        &RegistryType::Tool,
+        &space_clean,
+        &name_clean,
+        args.version.as_deref(),
+    )?;
+
     let query_args = CardQueryArgs {
-        space: Some(space_clean),
-        name: Some(name_clean),
-        version: args.version.clone(),
+        space: Some(space_clean.clone()),
+        name: Some(name_clean.clone()),
+        version,
         registry_type: RegistryType::Tool,
         limit: Some(1),
         ..Default::default()
@@ -111,6 +126,15 @@ pub fn pull_tool(args: &ToolPullArgs) -> Result<(), CliError> {
     let card_json = find_card_json(&tmp_path, 0)?;
     let card = opsml_cards::ToolCard::from_path(card_json)?;
 
+    lock.check(
+        RegistryType::Tool,
+        &space_clean,
+        &name_clean,
+        &card.version,
+        &card.uid,
+        &card.calculate_content_hash()?,
+    )?;
+
     let output_dir = args.output.clone().unwrap_or_else(|| PathBuf::from("."));
     let is_hook = matches!(
         card.spec.tool_type,
@@ -137,6 +161,8 @@ pub fn pull_tool(args: &ToolPullArgs) -> Result<(), CliError> {
         )
         .map_err(|e| CliError::Error(e.to_string()))?;
 
+    lock.save()?;
+
     println!(
         "{} {}/{} v{} -> {}",
         Colorize::green("Pulled"),
@@ -153,7 +179,7 @@ pub fn pull_tool(args: &ToolPullArgs) -> Result<(), CliError> {
 pub fn list_tools(args: &ToolListArgs) -> Result<(), CliError> {
     println!("\nListing cards from {} registry", Colorize::green("tool"));
 
-    let space = args.space.clone().map(|s| clean_string(&s));
+    let space = space_or_default(&args.space).map(|s| clean_string(&s));
     let name = args.name.clone().map(|n| clean_string(&n));
 
     let query_args = CardQueryArgs {
@@ -0,0 +1,57 @@
+use crate::cli::arg::VerifyArgs;
+use crate::error::CliError;
+use opsml_colors::Colorize;
+use opsml_registry::download::verify_card_artifacts;
+
+/// Check stored cards against their artifact manifests and report missing or corrupted files
+///
+/// # Arguments
+/// * `args` - The command line arguments
+pub fn verify_cards(args: &VerifyArgs) -> Result<(), CliError> {
+    let mut failed = 0;
+
+    for query in &args.cards {
+        let result = verify_card_artifacts(query)?;
+        let registry = Colorize::purple(&query.registry_type.to_string());
+
+        match (result.files, result.is_ok()) {
+            (None, true) => println!(
+                "{} {} {} (saved without a manifest)",
+                Colorize::alert("Skipped"),
+                registry,
+                result.card
+            ),
+            (Some(files), true) => println!(
+                "{} {} {} ({} files)",
+                Colorize::green("Verified"),
+                registry,
+                result.card,
+                files
+            ),
+            (files, false) => {
+                failed += 1;
+                let summary = match files {
+                    Some(files) => format!("{} of {} files", result.issues.len(), files),
+                    // the card was uploaded with a manifest that can no longer be read
+                    None => "manifest missing".to_string(),
+                };
+                println!(
+                    "{} {} {} ({})",
+                    Colorize::alert("Failed"),
+                    registry,
+                    result.card,
+                    summary
+                );
+                for issue in &result.issues {
+                    println!("    {issue}");
+                }
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(CliError::VerificationFailed(failed));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use crate::actions::profile::space_or_default;
+use crate::cli::arg::WorkflowRunArgs;
+use crate::error::CliError;
+use opsml_colors::Colorize;
+use opsml_registry::registries::card::OpsmlCardRegistry;
+use opsml_service::OpsmlServiceSpec;
+use opsml_types::RegistryType;
+use opsml_types::contracts::{StepStatus, WorkflowRun, WorkflowRunRequest, WorkflowRunStatus};
+use serde_json::{Map, Value};
+
+/// Build the run request from either a local spec file or a registered workflow card
+fn build_request(args: &WorkflowRunArgs) -> Result<WorkflowRunRequest, CliError> {
+    // Inputs are sent as strings; the runner coerces them to each input's declared type
+    let inputs: Map<String, Value> = args
+        .inputs
+        .iter()
+        .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+        .collect();
+
+    if let Some(path) = &args.spec {
+        if !path.exists() {
+            return Err(CliError::SpecNotFound(path.clone()));
+        }
+        let spec = OpsmlServiceSpec::from_path(path)?;
+        let workflow = spec
+            .service
+            .as_ref()
+            .and_then(|service| service.workflow.clone())
+            .ok_or_else(|| {
+                CliError::Error(format!("{} does not define a workflow", path.display()))
+            })?;
+
+        return Ok(WorkflowRunRequest {
+            space: args
+                .space
+                .clone()
+                .unwrap_or_else(|| spec.space().to_string()),
+            name: args.name.clone().unwrap_or_else(|| spec.name.clone()),
+            version: None,
+            spec: Some(workflow),
+            inputs,
+        });
+    }
+
+    match (&space_or_default(&args.space), &args.name) {
+        (Some(space), Some(name)) => Ok(WorkflowRunRequest {
+            space: space.clone(),
+            name: name.clone(),
+            version: args.version.clone(),
+            spec: None,
+            inputs,
+        }),
+        _ => Err(CliError::Error(
+            "Provide --space and --name of a registered workflow, or --spec".to_string(),
+        )),
+    }
+}
+
+fn print_run(run: &WorkflowRun) -> Result<(), CliError> {
+    for step in &run.steps {
+        let status = match step.status {
+            StepStatus::Succeeded => Colorize::green(&step.status.to_string()),
+            StepStatus::Skipped | StepStatus::Cancelled => {
+                Colorize::purple(&step.status.to_string())
+            }
+            StepStatus::Failed | StepStatus::TimedOut => Colorize::alert(&step.status.to_string()),
+        };
+        let target = step.card.as_deref().unwrap_or(&step.action);
+        let duration = step
+            .duration_ms
+            .map(|ms| format!(" ({ms}ms)"))
+            .unwrap_or_default();
+
+        println!("{status} {} [{target}]{duration}", step.name);
+        if let Some(error) = &step.error {
+            println!("    {}", Colorize::alert(error));
+        }
+    }
+
+    match run.status {
+        WorkflowRunStatus::Succeeded => {
+            println!(
+                "{}",
+                Colorize::green(&format!("Workflow succeeded in {}ms", run.duration_ms))
+            );
+            println!("{}", serde_json::to_string_pretty(&run.outputs)?);
+        }
+        WorkflowRunStatus::Failed => {
+            println!(
+                "{}",
+                Colorize::alert(&format!(
+                    "Workflow failed: {}",
+                    run.error.as_deref().unwrap_or("unknown error")
+                ))
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Run a workflow on the OpsML server and print the per-step trace
+///
+/// # Arguments
+/// * `args` - The command line arguments
+pub fn run_workflow(args: &WorkflowRunArgs) -> Result<(), CliError> {
+    let request = build_request(args)?;
+    let registry = OpsmlCardRegistry::new(RegistryType::Service)?;
+
+    let run = registry.run_workflow(&request)?;
+    print_run(&run)?;
+
+    if run.status == WorkflowRunStatus::Failed {
+        return Err(CliError::Error(format!(
+            "Workflow {}/{} failed",
+            request.space, request.name
+        )));
+    }
+
+    Ok(())
+}
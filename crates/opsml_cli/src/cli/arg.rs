@@ -1,5 +1,6 @@
 use std::path::PathBuf;
 
+use crate::actions::profile::space_or_default;
 use crate::error::CliError;
 use clap::{Args, ValueEnum};
 use opsml_agent_cli::{
@@ -21,6 +22,37 @@ fn parse_version_type(s: &str) -> Result<VersionType, String> {
     })
 }
 
+fn parse_workflow_input(s: &str) -> Result<(String, String), String> {
+    s.split_once('=')
+        .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+        .ok_or_else(|| format!("Invalid input '{s}'. Expected key=value"))
+}
+
+/// Parse a card reference of the form `<registry>:<space>/<name>[@<version>]`
+fn parse_card_ref(s: &str) -> Result<CardQueryArgs, String> {
+    let invalid = || format!("Invalid card '{s}'. Expected <registry>:<space>/<name>[@<version>]");
+
+    let (registry, card) = s.split_once(':').ok_or_else(invalid)?;
+    let registry_type = RegistryType::from_string(&registry.trim().to_lowercase())
+        .map_err(|_| format!("Unknown registry '{registry}' in '{s}'"))?;
+    let (card, version) = match card.split_once('@') {
+        Some((card, version)) => (card, Some(version.trim().to_string())),
+        None => (card, None),
+    };
+    let (space, name) = card.split_once('/').ok_or_else(invalid)?;
+    if space.trim().is_empty() || name.trim().is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(CardQueryArgs {
+        space: Some(space.trim().to_string()),
+        name: Some(name.trim().to_string()),
+        version: version.filter(|v| !v.is_empty()),
+        registry_type,
+        ..Default::default()
+    })
+}
+
 fn parse_tool_type(s: &str) -> Result<opsml_types::contracts::tool::ToolType, String> {
     use opsml_types::contracts::tool::ToolType;
     match s {
@@ -65,7 +97,7 @@ pub struct LockArgs {
 
 #[derive(Args)]
 pub struct ListCards {
-    /// space name
+    /// space name. Defaults to the profile's default space unless --uid is given
     #[arg(long = "space")]
     pub space: Option<String>,
 
@@ -106,7 +138,12 @@ impl IntoQueryArgs for ListCards {
     fn into_query_args(&self, registry_type: RegistryType) -> Result<CardQueryArgs, CliError> {
         let name = self.name.clone().map(|name| clean_string(&name));
 
-        let space = self.space.clone().map(|space| clean_string(&space));
+        // a uid identifies the card in any space
+        let space = match self.uid {
+            Some(_) => self.space.clone(),
+            None => space_or_default(&self.space),
+        }
+        .map(|space| clean_string(&space));
 
         Ok(CardQueryArgs {
             registry_type,
@@ -184,7 +221,7 @@ impl IntoQueryArgs for DownloadCard {
     fn into_query_args(&self, registry_type: RegistryType) -> Result<CardQueryArgs, CliError> {
         let name = self.name.clone().map(|name| clean_string(&name));
 
-        let space = self.space.clone().map(|space| clean_string(&space));
+        let space = space_or_default(&self.space).map(|space| clean_string(&space));
 
         Ok(CardQueryArgs {
             uid: self.uid.clone(),
@@ -211,6 +248,139 @@ pub struct KeyArgs {
     pub rounds: u32,
 }
 
+#[derive(Args)]
+pub struct KekArgs {
+    /// Keyfile the new key-encryption-key is appended to (the server's OPSML_KEK_PATH)
+    #[arg(long = "keyfile")]
+    pub keyfile: PathBuf,
+}
+
+#[derive(Args)]
+pub struct RotateKeysArgs {
+    /// Number of artifact keys re-wrapped per batch
+    #[arg(long = "batch-size")]
+    pub batch_size: Option<i64>,
+}
+
+#[derive(Args)]
+pub struct ScanStorageArgs {
+    /// Remove orphaned objects and artifact keys. Without this flag the scan only reports
+    #[arg(long = "delete", default_value = "false")]
+    pub delete: bool,
+
+    /// Only scan this space
+    #[arg(long = "space")]
+    pub space: Option<String>,
+}
+
+#[derive(Args)]
+pub struct WorkflowRunArgs {
+    /// Space of the registered workflow service
+    #[arg(long = "space")]
+    pub space: Option<String>,
+
+    /// Name of the registered workflow service
+    #[arg(long = "name")]
+    pub name: Option<String>,
+
+    /// Version of the workflow service. Defaults to the latest
+    #[arg(long = "version")]
+    pub version: Option<String>,
+
+    /// Run the workflow defined in a local spec file instead of a registered card
+    #[arg(long = "spec")]
+    pub spec: Option<PathBuf>,
+
+    /// Workflow input as key=value. Repeat for multiple inputs
+    #[arg(long = "input", value_parser = parse_workflow_input)]
+    pub inputs: Vec<(String, String)>,
+}
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// Card to export as <registry>:<space>/<name>[@<version>], e.g. model:my-space/churn@1.2.0.
+    /// Repeat for multiple cards. Defaults to the latest version
+    #[arg(long = "card", required = true, value_parser = parse_card_ref)]
+    pub cards: Vec<CardQueryArgs>,
+
+    /// Path of the bundle to write
+    #[arg(long = "output", short = 'o')]
+    pub output: PathBuf,
+
+    /// File containing the key used to sign the bundle
+    #[arg(long = "signing-key")]
+    pub signing_key: PathBuf,
+
+    /// Include the scouter drift profiles saved with each card
+    #[arg(long = "include-profiles", default_value = "false")]
+    pub include_profiles: bool,
+}
+
+#[derive(Args)]
+pub struct ImportArgs {
+    /// Path of the bundle to import
+    pub bundle: PathBuf,
+
+    /// File containing the key the bundle was signed with
+    #[arg(long = "signing-key")]
+    pub signing_key: PathBuf,
+
+    /// Abort without importing anything if any card already exists on this server
+    #[arg(long = "fail-on-conflict", default_value = "false")]
+    pub fail_on_conflict: bool,
+
+    /// Do not register bundled drift profiles with scouter
+    #[arg(long = "skip-profiles", default_value = "false")]
+    pub skip_profiles: bool,
+}
+
+#[derive(Args)]
+pub struct VerifyArgs {
+    /// Card to verify as <registry>:<space>/<name>[@<version>], e.g. model:my-space/churn@1.2.0.
+    /// Repeat for multiple cards. Defaults to the latest version
+    #[arg(long = "card", required = true, value_parser = parse_card_ref)]
+    pub cards: Vec<CardQueryArgs>,
+}
+
+#[derive(Args)]
+pub struct LoginArgs {
+    /// Url of the OpsML server. Required when creating a profile
+    #[arg(long = "tracking-uri")]
+    pub tracking_uri: Option<String>,
+
+    /// Username to log in with. Prompts for the password when --password is not given
+    #[arg(long = "username")]
+    pub username: Option<String>,
+
+    #[arg(long = "password")]
+    pub password: Option<String>,
+
+    /// Personal API token to use instead of a username and password
+    #[arg(long = "token", conflicts_with_all = ["username", "password"])]
+    pub token: Option<String>,
+
+    /// Space used by commands run with this profile when --space is not given
+    #[arg(long = "default-space")]
+    pub default_space: Option<String>,
+
+    /// Authenticate the username and password through the server's SSO provider
+    #[arg(long = "sso", default_value = "false")]
+    pub sso: bool,
+}
+
+#[derive(Args)]
+pub struct ProfileUseArgs {
+    /// Name of the profile
+    pub name: String,
+}
+
+#[derive(Args)]
+pub struct ConfigShowArgs {
+    /// Print every setting with its resolved value, including defaults
+    #[arg(long = "effective", default_value = "false")]
+    pub effective: bool,
+}
+
 #[derive(Args, Clone)]
 #[cfg_attr(feature = "python", pyclass(skip_from_py_object))]
 pub struct ScouterArgs {
@@ -323,6 +493,15 @@ impl PullTarget {
         };
         result.map_err(|e| CliError::Error(e.to_string()))
     }
+
+    pub fn framework(&self) -> &'static dyn AgentCliFramework {
+        match self {
+            Self::ClaudeCode => &ClaudeCodeFramework,
+            Self::Codex => &CodexFramework,
+            Self::GeminiCli => &GeminiCliFramework,
+            Self::GithubCopilot => &CopilotFramework,
+        }
+    }
 }
 
 #[derive(Args, Clone)]
@@ -330,7 +509,7 @@ pub struct SkillPushArgs {
     /// Path to the skill markdown file
     pub path: PathBuf,
 
-    /// Override the space for registration
+    /// Override the space for registration. Defaults to the profile's default space
     #[arg(long = "space")]
     pub space: Option<String>,
 
@@ -356,7 +535,7 @@ pub struct SkillPullArgs {
     #[arg(long = "version")]
     pub version: Option<String>,
 
-    /// Space (alternative to space/name format)
+    /// Space (alternative to space/name format). Defaults to the profile's default space
     #[arg(long = "space")]
     pub space: Option<String>,
 
@@ -375,11 +554,15 @@ pub struct SkillPullArgs {
     /// Pull without adding to skills.yaml
     #[arg(long = "no-track", default_value = "false")]
     pub no_track: bool,
+
+    /// Install exactly what the lockfile pins and fail if the registry content changed
+    #[arg(long = "frozen", default_value = "false")]
+    pub frozen: bool,
 }
 
 #[derive(Args, Clone)]
 pub struct SkillListArgs {
-    /// Filter by space
+    /// Filter by space. Defaults to the profile's default space
     #[arg(long = "space")]
     pub space: Option<String>,
 
@@ -416,7 +599,7 @@ pub struct SkillRemoveArgs {
     /// Skill identifier (space/name or just name with --space)
     pub name: String,
 
-    /// Space (alternative to space/name format)
+    /// Space (alternative to space/name format). Defaults to the profile's default space
     #[arg(long = "space")]
     pub space: Option<String>,
 
@@ -431,7 +614,7 @@ pub struct SkillRemoveArgs {
 pub struct AgentPushArgs {
     /// Path to AGENT.md (YAML frontmatter + system prompt body)
     pub path: PathBuf,
-    /// Override space for registration
+    /// Override space for registration. Defaults to the profile's default space
     #[arg(long = "space")]
     pub space: Option<String>,
     /// Tags to apply (comma-separated)
@@ -456,11 +639,14 @@ pub struct AgentPullArgs {
     /// Write to current directory instead of home directory
     #[arg(long = "local", default_value = "false")]
     pub local: bool,
+    /// Install exactly what the lockfile pins and fail if the registry content changed
+    #[arg(long = "frozen", default_value = "false")]
+    pub frozen: bool,
 }
 
 #[derive(Args, Clone)]
 pub struct AgentListArgs {
-    /// Filter by space
+    /// Filter by space. Defaults to the profile's default space
     #[arg(long = "space")]
     pub space: Option<String>,
     /// Filter by agent name
@@ -501,6 +687,14 @@ pub struct SyncArgs {
     /// Path to .opsml-skills.yaml (defaults to ./.opsml-skills.yaml)
     #[arg(long = "path")]
     pub path: Option<PathBuf>,
+
+    /// Install exactly what the lockfile pins and fail if the registry content changed
+    #[arg(long = "frozen", default_value = "false", conflicts_with = "update")]
+    pub frozen: bool,
+
+    /// Ignore the lockfile pins, resolve from skills.yaml and rewrite the lockfile
+    #[arg(long = "update", default_value = "false")]
+    pub update: bool,
 }
 
 #[derive(Clone, ValueEnum)]
@@ -551,7 +745,7 @@ pub struct ConfigureArgs {
 pub struct ToolPushArgs {
     /// Path to TOOL.md (YAML frontmatter + body)
     pub path: PathBuf,
-    /// Override space for registration
+    /// Override space for registration. Defaults to the profile's default space
     #[arg(long = "space")]
     pub space: Option<String>,
     /// Tags to apply (comma-separated)
@@ -576,14 +770,18 @@ pub struct ToolPullArgs {
     /// Target CLI for hook registration (required for Hook tools)
     #[arg(long = "target")]
     pub target: Option<PullTarget>,
-    /// Register hook globally (~/.claude/settings.json etc.) instead of project-local
+    /// Install globally: register hooks in ~/.claude/settings.json etc. and pin the tool in
+    /// ~/.opsml/skills.lock instead of the project's settings and ./.opsml-skills.lock
     #[arg(long = "global", default_value_t = false)]
     pub global: bool,
+    /// Install exactly what the lockfile pins and fail if the registry content changed
+    #[arg(long = "frozen", default_value_t = false)]
+    pub frozen: bool,
 }
 
 #[derive(Args, Clone)]
 pub struct ToolListArgs {
-    /// Filter by space
+    /// Filter by space. Defaults to the profile's default space
     #[arg(long = "space")]
     pub space: Option<String>,
     /// Filter by name
@@ -626,4 +824,21 @@ mod tests {
         assert!(targets.contains(&PullTarget::GeminiCli));
         assert!(targets.contains(&PullTarget::GithubCopilot));
     }
+
+    #[test]
+    fn test_parse_card_ref() {
+        let args = parse_card_ref("model:my-space/churn@1.2.0").unwrap();
+        assert_eq!(args.registry_type, RegistryType::Model);
+        assert_eq!(args.space.as_deref(), Some("my-space"));
+        assert_eq!(args.name.as_deref(), Some("churn"));
+        assert_eq!(args.version.as_deref(), Some("1.2.0"));
+
+        let args = parse_card_ref("Data:my-space/churn").unwrap();
+        assert_eq!(args.registry_type, RegistryType::Data);
+        assert!(args.version.is_none());
+
+        assert!(parse_card_ref("my-space/churn").is_err());
+        assert!(parse_card_ref("model:churn").is_err());
+        assert!(parse_card_ref("widget:my-space/churn").is_err());
+    }
 }
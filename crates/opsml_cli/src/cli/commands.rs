@@ -1,9 +1,10 @@
 use crate::cli::arg::ScouterArgs;
 use crate::cli::arg::{
-    AgentInitArgs, AgentListArgs, AgentPullArgs, AgentPushArgs, ConfigureArgs, DownloadCard,
-    KeyArgs, ListCards, LockArgs, RegisterArgs, SkillInitArgs, SkillListArgs, SkillPullArgs,
-    SkillPushArgs, SkillRemoveArgs, SyncArgs, ToolInitArgs, ToolListArgs, ToolPullArgs,
-    ToolPushArgs, UiArgs,
+    AgentInitArgs, AgentListArgs, AgentPullArgs, AgentPushArgs, ConfigShowArgs, ConfigureArgs,
+    DownloadCard, ExportArgs, ImportArgs, KekArgs, KeyArgs, ListCards, LockArgs, LoginArgs,
+    ProfileUseArgs, RegisterArgs, RotateKeysArgs, ScanStorageArgs, SkillInitArgs, SkillListArgs,
+    SkillPullArgs, SkillPushArgs, SkillRemoveArgs, SyncArgs, ToolInitArgs, ToolListArgs,
+    ToolPullArgs, ToolPushArgs, UiArgs, VerifyArgs, WorkflowRunArgs,
 };
 use clap::Parser;
 use clap::Subcommand;
@@ -57,6 +58,10 @@ const STYLES: Styles = Styles::styled()
 #[command(about = "CLI tool for Interacting with OpsML")]
 #[command(propagate_version = false)]
 pub struct Cli {
+    /// Client profile to use instead of the active profile and OPSML_* environment variables
+    #[arg(long = "profile", global = true)]
+    pub profile: Option<String>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -173,6 +178,67 @@ pub enum Commands {
         #[command(subcommand)]
         command: ToolCommands,
     },
+
+    /// Server administration (requires admin permission)
+    ///
+    /// # Example
+    /// opsml admin rotate-keys
+    /// opsml admin scan-storage --space my-space
+    Admin {
+        #[command(subcommand)]
+        command: AdminCommands,
+    },
+
+    /// Run workflow services on the OpsML server
+    ///
+    /// # Example
+    /// opsml workflow run --space my-space --name triage --input ticket=1234
+    Workflow {
+        #[command(subcommand)]
+        command: WorkflowCommands,
+    },
+
+    /// Export cards and their artifacts into a signed bundle for import into another server
+    ///
+    /// # Example
+    /// opsml export --card model:my-space/churn@1.2.0 --card data:my-space/churn --output churn.zip --signing-key bundle.key
+    Export(ExportArgs),
+
+    /// Import a signed bundle created with `opsml export`
+    ///
+    /// # Example
+    /// opsml import churn.zip --signing-key bundle.key
+    Import(ImportArgs),
+
+    /// Download cards and check their artifacts against the SHA-256 manifest saved at registration
+    ///
+    /// # Example
+    /// opsml verify --card model:my-space/churn@1.2.0 --card data:my-space/churn
+    Verify(VerifyArgs),
+
+    /// Inspect the opsml.toml/opsml.yaml configuration
+    ///
+    /// # Example
+    /// opsml config show --effective
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+
+    /// Log in to an OpsML server and save the connection as a named profile
+    ///
+    /// # Example
+    /// opsml login --profile staging --tracking-uri https://opsml.staging.example.com --username me
+    Login(LoginArgs),
+
+    /// Manage client profiles
+    ///
+    /// # Example
+    /// opsml profile use prod
+    Profile {
+        #[command(subcommand)]
+        command: ProfileCommands,
+    },
 }
 
 #[derive(Subcommand)]
@@ -221,6 +287,48 @@ pub enum InstallCommands {
 #[derive(Subcommand)]
 pub enum GenerateCommands {
     Key(KeyArgs),
+
+    /// Append a new key-encryption-key to a keyfile. Restart the server to start using it,
+    /// then run `opsml admin rotate-keys`
+    Kek(KekArgs),
+}
+
+#[derive(Subcommand)]
+#[command(version = None)]
+pub enum AdminCommands {
+    /// Re-wrap every stored artifact key with the server's current key-encryption-key.
+    /// Artifacts are not re-encrypted
+    RotateKeys(RotateKeysArgs),
+
+    /// Cross-reference storage with artifact keys and card tables. Reports orphaned artifacts,
+    /// cards with missing artifacts and storage used per space. Pass --delete to remove orphans
+    ScanStorage(ScanStorageArgs),
+}
+
+#[derive(Subcommand)]
+#[command(version = None)]
+pub enum ProfileCommands {
+    /// Make a profile the default for commands run without --profile
+    Use(ProfileUseArgs),
+
+    /// List saved profiles. The active profile is marked with *
+    List,
+}
+
+#[derive(Subcommand)]
+#[command(version = None)]
+pub enum ConfigCommands {
+    /// Print the config file, active profile and settings with secrets masked.
+    /// Use --effective to include defaults and the origin of every value
+    Show(ConfigShowArgs),
+}
+
+#[derive(Subcommand)]
+#[command(version = None)]
+pub enum WorkflowCommands {
+    /// Run a workflow and print the per-step trace and outputs.
+    /// Use --spec to run the workflow from a local opsmlspec.yaml
+    Run(WorkflowRunArgs),
 }
 
 #[derive(Subcommand)]
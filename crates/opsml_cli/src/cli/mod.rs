@@ -2,6 +2,7 @@ pub mod arg;
 pub mod commands;
 
 pub use commands::{
-    AgentCommands, Cli, Commands, GenerateCommands, GetCommands, InstallCommands, LOGO_TEXT,
-    ListCommands, SkillCommands, ToolCommands,
+    AdminCommands, AgentCommands, Cli, Commands, ConfigCommands, GenerateCommands, GetCommands,
+    InstallCommands, LOGO_TEXT, ListCommands, ProfileCommands, SkillCommands, ToolCommands,
+    WorkflowCommands,
 };
@@ -30,6 +30,12 @@ pub enum CliError {
     #[error(transparent)]
     CryptError(#[from] CryptError),
 
+    #[error(transparent)]
+    SettingsError(#[from] opsml_settings::error::SettingsError),
+
+    #[error(transparent)]
+    ApiClientError(#[from] opsml_client::error::ApiClientError),
+
     #[error(transparent)]
     TypeError(#[from] TypeError),
 
@@ -98,6 +104,34 @@ pub enum CliError {
 
     #[error("OpsML spec file not found at path: {0}")]
     SpecNotFound(PathBuf),
+
+    #[error("Stage alias '{0}' did not resolve to a service card")]
+    UnresolvedStageAlias(String),
+
+    #[error("Dependency {0} was not found in the registry")]
+    DependencyNotFound(String),
+
+    #[error("No version of {0} satisfies every requirement: {1}")]
+    DependencyConflict(String, String),
+
+    #[error("Dependency cycle detected: {0}")]
+    DependencyCycle(String),
+
+    #[error(
+        "{0} is not pinned in lockfile {1}. Run `opsml skill sync --update` or pull without --frozen"
+    )]
+    LockEntryMissing(String, PathBuf),
+
+    #[error("{0} resolved to v{2} but the lockfile pins v{1}")]
+    LockVersionMismatch(String, String, String),
+
+    #[error(
+        "Content hash of {0} v{1} no longer matches the lockfile (locked {2}, registry {3}). Run `opsml skill sync --update` to accept the new content"
+    )]
+    LockHashMismatch(String, String, String, String),
+
+    #[error("{0} card(s) failed artifact verification")]
+    VerificationFailed(usize),
 }
 
 #[cfg(feature = "python")]
@@ -3,18 +3,25 @@ pub mod cli;
 pub mod error;
 mod hooks;
 
+use crate::actions::admin::{rotate_artifact_keys, scan_storage};
+use crate::actions::bundle::{export_cards, import_cards};
+use crate::actions::config::show_config;
 use crate::actions::configure::configure_cli;
+use crate::actions::profile::{DEFAULT_PROFILE, list_profiles, login, use_profile};
 use crate::actions::skill::{init_skill, list_skills, pull_skill, push_skill, remove_skill};
 use crate::actions::sync::sync_skills;
+use crate::actions::verify::verify_cards;
+use crate::actions::workflow::run_workflow;
 pub use crate::actions::{download_card, download_service, list_cards};
 use crate::cli::{
-    AgentCommands, Cli, Commands, GenerateCommands, GetCommands, InstallCommands, LOGO_TEXT,
-    ListCommands, SkillCommands, ToolCommands,
+    AdminCommands, AgentCommands, Cli, Commands, ConfigCommands, GenerateCommands, GetCommands,
+    InstallCommands, LOGO_TEXT, ListCommands, ProfileCommands, SkillCommands, ToolCommands,
+    WorkflowCommands,
 };
 
 pub use actions::update_drift_profile_status;
 pub use actions::{
-    generate_key,
+    generate_kek, generate_key,
     lock::install_service,
     register::register_service,
     ui::{start_ui, stop_ui},
@@ -27,6 +34,7 @@ pub use cli::arg::ScouterArgs;
 use cli::commands::ScouterCommands;
 use cli::commands::UiCommands;
 use opsml_colors::Colorize;
+use opsml_settings::profile::{ProfileStore, select_profile};
 use opsml_types::RegistryType;
 
 pub use actions::lock::lock_service;
@@ -34,6 +42,14 @@ pub use actions::lock::lock_service;
 pub fn run_cli(args: Vec<String>) -> anyhow::Result<()> {
     let cli = Cli::parse_from(args.into_iter().skip(1));
 
+    // `login` creates the profile, every other command must name an existing one
+    if let Some(profile) = &cli.profile
+        && !matches!(cli.command, Some(Commands::Login(_)))
+    {
+        ProfileStore::load()?.get(profile)?;
+        select_profile(profile);
+    }
+
     match &cli.command {
         Some(Commands::List { command }) => match command {
             ListCommands::Data(args) => {
@@ -107,6 +123,9 @@ pub fn run_cli(args: Vec<String>) -> anyhow::Result<()> {
                 generate_key(&password, args.rounds).context("Failed to generate key")?;
                 Ok(())
             }
+            GenerateCommands::Kek(args) => {
+                generate_kek(&args.keyfile).context("Failed to generate key-encryption-key")
+            }
         },
 
         Some(Commands::Scouter { command }) => match command {
@@ -191,6 +210,31 @@ pub fn run_cli(args: Vec<String>) -> anyhow::Result<()> {
                 actions::tool::init_tool(args).context("Failed to init tool")
             }
         },
+        Some(Commands::Admin { command }) => match command {
+            AdminCommands::RotateKeys(args) => {
+                rotate_artifact_keys(args).context("Failed to rotate artifact keys")
+            }
+            AdminCommands::ScanStorage(args) => {
+                scan_storage(args).context("Failed to scan storage")
+            }
+        },
+        Some(Commands::Workflow { command }) => match command {
+            WorkflowCommands::Run(args) => run_workflow(args).context("Failed to run workflow"),
+        },
+        Some(Commands::Export(args)) => export_cards(args).context("Failed to export cards"),
+        Some(Commands::Import(args)) => import_cards(args).context("Failed to import bundle"),
+        Some(Commands::Verify(args)) => verify_cards(args).context("Failed to verify cards"),
+        Some(Commands::Config { command }) => match command {
+            ConfigCommands::Show(args) => show_config(args).context("Failed to show config"),
+        },
+        Some(Commands::Login(args)) => {
+            login(cli.profile.as_deref().unwrap_or(DEFAULT_PROFILE), args)
+                .context("Failed to log in")
+        }
+        Some(Commands::Profile { command }) => match command {
+            ProfileCommands::Use(args) => use_profile(args).context("Failed to switch profile"),
+            ProfileCommands::List => list_profiles().context("Failed to list profiles"),
+        },
         None => {
             println!("No command provided");
             Ok(())
@@ -1,24 +1,41 @@
 use crate::error::ApiClientError;
 use opsml_settings::config::{ApiSettings, OpsmlStorageSettings};
+use opsml_settings::profile::write_private;
 use opsml_types::{
     api::{JwtToken, RequestType, Routes},
     contracts::{CompleteMultipartUpload, PresignedQuery, PresignedUrl},
 };
 
+use reqwest::StatusCode;
 use reqwest::blocking::{Client, Response, multipart::Form};
 use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
 use reqwest::{Client as AsyncClient, Response as AsyncResponse, multipart::Form as AsyncForm};
 use serde_json::Value;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::RwLock;
 use std::thread;
 use std::time::Duration;
-use tracing::{debug, error, instrument};
+use tracing::{debug, error, instrument, warn};
 const MAX_RETRIES: u32 = 3;
 const INITIAL_BACKOFF_MS: u64 = 100;
 const MAX_BACKOFF_MS: u64 = 5000;
 const TIMEOUT_SECS: u64 = 30;
 
+/// Additional root certificates from `OPSML_S3_CA_BUNDLE`, if set
+fn ca_certificates(settings: &ApiSettings) -> Result<Vec<reqwest::Certificate>, ApiClientError> {
+    let Some(path) = &settings.ca_bundle else {
+        return Ok(vec![]);
+    };
+
+    let pem = std::fs::read(path).map_err(|e| {
+        ApiClientError::CaBundleError(format!("failed to read {}: {}", path.display(), e))
+    })?;
+
+    reqwest::Certificate::from_pem_bundle(&pem)
+        .map_err(|e| ApiClientError::CaBundleError(e.to_string()))
+}
+
 /// Create a new HTTP client that can be shared across different clients
 pub fn build_http_client(settings: &ApiSettings) -> Result<Client, ApiClientError> {
     let mut headers = HeaderMap::new();
@@ -49,7 +66,13 @@ pub fn build_http_client(settings: &ApiSettings) -> Result<Client, ApiClientErro
         reqwest::header::USER_AGENT,
         HeaderValue::from_static("opsml-client/"),
     );
-    let client_builder = Client::builder().timeout(std::time::Duration::from_secs(TIMEOUT_SECS));
+    let mut client_builder =
+        Client::builder().timeout(std::time::Duration::from_secs(TIMEOUT_SECS));
+
+    // presigned storage urls are fetched with this client, so it has to trust the storage CA
+    for cert in ca_certificates(settings)? {
+        client_builder = client_builder.add_root_certificate(cert);
+    }
 
     let client = client_builder
         .default_headers(headers)
@@ -88,9 +111,14 @@ pub fn build_async_http_client(settings: &ApiSettings) -> Result<AsyncClient, Ap
         reqwest::header::USER_AGENT,
         HeaderValue::from_static("opsml-client/"),
     );
-    let client_builder =
+    let mut client_builder =
         AsyncClient::builder().timeout(std::time::Duration::from_secs(TIMEOUT_SECS));
 
+    // presigned storage urls are fetched with this client, so it has to trust the storage CA
+    for cert in ca_certificates(settings)? {
+        client_builder = client_builder.add_root_certificate(cert);
+    }
+
     let client = client_builder
         .default_headers(headers)
         .build()
@@ -99,6 +127,32 @@ pub fn build_async_http_client(settings: &ApiSettings) -> Result<AsyncClient, Ap
     Ok(client)
 }
 
+/// JWT persisted for a client profile, so tokens refreshed by the server survive across
+/// invocations instead of logging in again every time
+#[derive(Debug, Clone)]
+pub struct TokenCache {
+    path: PathBuf,
+}
+
+impl TokenCache {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn load(&self) -> Option<String> {
+        std::fs::read_to_string(&self.path)
+            .ok()
+            .map(|token| token.trim().to_string())
+            .filter(|token| !token.is_empty())
+    }
+
+    pub fn store(&self, token: &str) {
+        if let Err(e) = write_private(&self.path, token.as_bytes()) {
+            warn!("Failed to cache token at {}: {e}", self.path.display());
+        }
+    }
+}
+
 /// Main client for interacting with the OpsML API
 /// This client acquires a JWT token on creation, which is stored in a RwLock
 /// and used for all subsequent requests. All token refreshes are handled on the server side.
@@ -115,20 +169,40 @@ pub struct OpsmlApiClient {
     pub client: Client,
     base_path: String,
     auth_token: Arc<RwLock<String>>,
+    token_cache: Option<TokenCache>,
+    uses_api_token: bool,
 }
 
 impl OpsmlApiClient {
     pub fn new(url: String, client: &Client) -> Result<Self, ApiClientError> {
-        // setup headers
+        Self::with_auth(url, client, None, None)
+    }
+
+    /// Create a client that authenticates with a personal API token or a cached JWT, and only
+    /// logs in with the username and password headers when neither is available
+    pub fn with_auth(
+        url: String,
+        client: &Client,
+        api_token: Option<String>,
+        token_cache: Option<TokenCache>,
+    ) -> Result<Self, ApiClientError> {
+        let uses_api_token = api_token.is_some();
+        let token = api_token.or_else(|| token_cache.as_ref().and_then(TokenCache::load));
+        let needs_login = token.is_none();
+
         let api_client = Self {
             client: client.clone(),
             base_path: url,
-            auth_token: Arc::new(RwLock::new(String::new())),
+            auth_token: Arc::new(RwLock::new(token.unwrap_or_default())),
+            token_cache: token_cache.filter(|_| !uses_api_token),
+            uses_api_token,
         };
 
-        api_client.refresh_token().inspect_err(|e| {
-            error!("Failed to get JWT token: {e}");
-        })?;
+        if needs_login {
+            api_client.refresh_token().inspect_err(|e| {
+                error!("Failed to get JWT token: {e}");
+            })?;
+        }
 
         Ok(api_client)
     }
@@ -155,6 +229,9 @@ impl OpsmlApiClient {
 
         if let Ok(mut token_guard) = self.auth_token.write() {
             *token_guard = token.token;
+            if let Some(cache) = &self.token_cache {
+                cache.store(&token_guard);
+            }
         } else {
             error!("Failed to acquire write lock for token update");
             return Err(ApiClientError::UpdateAuthError);
@@ -172,7 +249,12 @@ impl OpsmlApiClient {
         {
             match self.auth_token.write() {
                 Ok(mut token_guard) => {
-                    *token_guard = new_token.to_string();
+                    if *token_guard != new_token {
+                        *token_guard = new_token.to_string();
+                        if let Some(cache) = &self.token_cache {
+                            cache.store(new_token);
+                        }
+                    }
                 }
                 Err(e) => {
                     error!("Failed to acquire write lock for jwt token update: {e}");
@@ -280,6 +362,7 @@ impl OpsmlApiClient {
     ) -> Result<Response, ApiClientError> {
         let mut attempt = 0;
         let mut backoff_ms = INITIAL_BACKOFF_MS;
+        let mut logged_in = false;
 
         loop {
             match self._request(
@@ -290,6 +373,18 @@ impl OpsmlApiClient {
                 headers.clone(),
             ) {
                 Ok(response) => {
+                    // a cached token may have outlived its refresh token, so log in again once
+                    if response.status() == StatusCode::UNAUTHORIZED
+                        && !self.uses_api_token
+                        && !logged_in
+                    {
+                        logged_in = true;
+                        if self.refresh_token().is_ok() {
+                            debug!("Request unauthorized, retrying with a new token");
+                            continue;
+                        }
+                    }
+
                     // handle if response is an error status code
                     if Self::is_retryable_status(response.status()) && attempt < max_retries {
                         debug!(
@@ -427,20 +522,40 @@ pub struct OpsmlApiAsyncClient {
     pub client: AsyncClient,
     base_path: String,
     auth_token: Arc<RwLock<String>>,
+    token_cache: Option<TokenCache>,
+    uses_api_token: bool,
 }
 
 impl OpsmlApiAsyncClient {
     pub async fn new(url: String, client: &AsyncClient) -> Result<Self, ApiClientError> {
-        // setup headers
+        Self::with_auth(url, client, None, None).await
+    }
+
+    /// Create a client that authenticates with a personal API token or a cached JWT, and only
+    /// logs in with the username and password headers when neither is available
+    pub async fn with_auth(
+        url: String,
+        client: &AsyncClient,
+        api_token: Option<String>,
+        token_cache: Option<TokenCache>,
+    ) -> Result<Self, ApiClientError> {
+        let uses_api_token = api_token.is_some();
+        let token = api_token.or_else(|| token_cache.as_ref().and_then(TokenCache::load));
+        let needs_login = token.is_none();
+
         let api_client = Self {
             client: client.clone(),
             base_path: url,
-            auth_token: Arc::new(RwLock::new(String::new())),
+            auth_token: Arc::new(RwLock::new(token.unwrap_or_default())),
+            token_cache: token_cache.filter(|_| !uses_api_token),
+            uses_api_token,
         };
 
-        api_client.refresh_token().await.inspect_err(|e| {
-            error!("Failed to get JWT token: {e}");
-        })?;
+        if needs_login {
+            api_client.refresh_token().await.inspect_err(|e| {
+                error!("Failed to get JWT token: {e}");
+            })?;
+        }
 
         Ok(api_client)
     }
@@ -469,6 +584,9 @@ impl OpsmlApiAsyncClient {
 
         if let Ok(mut token_guard) = self.auth_token.write() {
             *token_guard = token.token;
+            if let Some(cache) = &self.token_cache {
+                cache.store(&token_guard);
+            }
         } else {
             error!("Failed to acquire write lock for token update");
             return Err(ApiClientError::UpdateAuthError);
@@ -486,7 +604,12 @@ impl OpsmlApiAsyncClient {
         {
             match self.auth_token.write() {
                 Ok(mut token_guard) => {
-                    *token_guard = new_token.to_string();
+                    if *token_guard != new_token {
+                        *token_guard = new_token.to_string();
+                        if let Some(cache) = &self.token_cache {
+                            cache.store(new_token);
+                        }
+                    }
                 }
                 Err(e) => {
                     error!("Failed to acquire write lock for jwt token update: {e}");
@@ -599,6 +722,7 @@ impl OpsmlApiAsyncClient {
     ) -> Result<AsyncResponse, ApiClientError> {
         let mut attempt = 0;
         let mut backoff_ms = INITIAL_BACKOFF_MS;
+        let mut logged_in = false;
 
         loop {
             match self
@@ -612,6 +736,18 @@ impl OpsmlApiAsyncClient {
                 .await
             {
                 Ok(response) => {
+                    // a cached token may have outlived its refresh token, so log in again once
+                    if response.status() == StatusCode::UNAUTHORIZED
+                        && !self.uses_api_token
+                        && !logged_in
+                    {
+                        logged_in = true;
+                        if self.refresh_token().await.is_ok() {
+                            debug!("Request unauthorized, retrying with a new token");
+                            continue;
+                        }
+                    }
+
                     if Self::is_retryable_status(response.status()) && attempt < max_retries {
                         debug!(
                             "Request failed with retryable status {}, attempt {}/{}",
@@ -740,7 +876,16 @@ pub fn build_api_client(settings: &OpsmlStorageSettings) -> Result<OpsmlApiClien
         "{}/{}",
         settings.api_settings.base_url, settings.api_settings.opsml_dir
     );
-    OpsmlApiClient::new(url, &client)
+    OpsmlApiClient::with_auth(
+        url,
+        &client,
+        settings.api_settings.token.clone(),
+        settings
+            .api_settings
+            .token_cache
+            .clone()
+            .map(TokenCache::new),
+    )
 }
 
 pub async fn build_async_api_client(
@@ -752,7 +897,17 @@ pub async fn build_async_api_client(
         "{}/{}",
         settings.api_settings.base_url, settings.api_settings.opsml_dir
     );
-    OpsmlApiAsyncClient::new(url, &client).await
+    OpsmlApiAsyncClient::with_auth(
+        url,
+        &client,
+        settings.api_settings.token.clone(),
+        settings
+            .api_settings
+            .token_cache
+            .clone()
+            .map(TokenCache::new),
+    )
+    .await
 }
 
 #[cfg(test)]
@@ -782,6 +937,60 @@ mod tests {
         OpsmlApiClient::new(url, &client).unwrap()
     }
 
+    #[test]
+    fn test_build_http_client_ca_bundle() {
+        let mut settings = OpsmlStorageSettings::new("./opsml_registries").api_settings;
+        settings.ca_bundle = Some(PathBuf::from("missing-ca-bundle.pem"));
+
+        assert!(matches!(
+            build_http_client(&settings),
+            Err(ApiClientError::CaBundleError(_))
+        ));
+        assert!(matches!(
+            build_async_http_client(&settings),
+            Err(ApiClientError::CaBundleError(_))
+        ));
+    }
+
+    #[test]
+    fn test_api_client_token_cache() {
+        let mut server = Server::new();
+        let dir = tempfile::tempdir().unwrap();
+        let cache = TokenCache::new(dir.path().join("staging.jwt"));
+        cache.store("cached_token");
+
+        // the cached token is used without logging in
+        let login_mock = server
+            .mock("GET", "/opsml/api/auth/login")
+            .expect(0)
+            .create();
+
+        let validate_mock = server
+            .mock("GET", "/opsml/api/auth/validate")
+            .match_header("Authorization", "Bearer cached_token")
+            .with_status(200)
+            .with_header("Authorization", "Bearer refreshed_token")
+            .with_body(r#"{"is_authenticated": true}"#)
+            .create();
+
+        let settings = OpsmlStorageSettings::new("./opsml_registries");
+        let client = build_http_client(&settings.api_settings).unwrap();
+        let url = format!("{}/{}", server.url(), "opsml/api");
+        let api_client =
+            OpsmlApiClient::with_auth(url, &client, None, Some(cache.clone())).unwrap();
+
+        let response = api_client
+            .request(Routes::AuthValidate, RequestType::Get, None, None, None)
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        // the token refreshed by the server is persisted for the next invocation
+        assert_eq!(cache.load().unwrap(), "refreshed_token");
+
+        login_mock.assert();
+        validate_mock.assert();
+    }
+
     #[tokio::test]
     async fn test_api_client_no_auth() {
         let (mut server, server_url) = setup_server().await;
@@ -8,6 +8,9 @@ pub enum ApiClientError {
     #[error("Failed to create opsml client: {0}")]
     CreateClientError(#[source] reqwest::Error),
 
+    #[error("Invalid S3 CA bundle: {0}")]
+    CaBundleError(String),
+
     #[error(transparent)]
     RequestError(#[from] reqwest::Error),
 
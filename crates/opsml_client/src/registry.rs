@@ -255,6 +255,41 @@ impl ClientRegistry {
         Ok(exists.exists)
     }
 
+    /// Fetch the lineage graph around a card. The registry type of this client
+    /// is used for the root card
+    #[instrument(skip_all)]
+    pub fn get_lineage(
+        &self,
+        uid: &str,
+        depth: usize,
+        direction: LineageDirection,
+    ) -> Result<LineageGraph, RegistryError> {
+        let query = LineageQuery {
+            uid: uid.to_string(),
+            registry_type: Some(self.registry_type.clone()),
+            depth,
+            direction,
+        };
+        let query_string = serde_qs::to_string(&query)?;
+
+        let response = self
+            .api_client
+            .request(
+                Routes::CardLineage,
+                RequestType::Get,
+                None,
+                Some(query_string),
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to get card lineage {}", e);
+            })?;
+
+        response
+            .json::<LineageGraph>()
+            .map_err(RegistryError::RequestError)
+    }
+
     fn artifact_key(
         &self,
         uid: &str,
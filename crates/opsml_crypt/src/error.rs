@@ -29,4 +29,16 @@ pub enum CryptError {
 
     #[error("Failed to decrypt key using AES-256-GCM")]
     DecryptKeyError,
+
+    #[error("Unsupported encrypted file format version: {0}")]
+    UnsupportedFormatVersion(u8),
+
+    #[error("No key-encryption-key with version {0} is configured")]
+    UnknownKeyVersion(u32),
+
+    #[error("Invalid key file: {0}")]
+    KeyFileError(String),
+
+    #[error("Unknown key provider '{0}'. Supported providers: local")]
+    UnknownKeyProvider(String),
 }
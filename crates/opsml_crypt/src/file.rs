@@ -1,24 +1,45 @@
 use crate::error::CryptError;
+use crate::stream::{HEADER_SIZE, StreamHeader};
 use aes_gcm::Nonce;
 use aes_gcm::{
     Aes256Gcm,
     Key, // Or `Aes128Gcm`
-    aead::{Aead, AeadCore, KeyInit, OsRng},
+    aead::{Aead, KeyInit},
 };
 
 use opsml_utils::FileUtils;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use std::fs::{self, File};
-use std::io::{BufReader, BufWriter, Read, Write};
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
 use std::path::Path;
 use tracing::{debug, instrument};
 
+/// Chunk size of the legacy format. Legacy chunks are only ever read
 static CHUNK_SIZE: usize = 1024 * 1024 * 16; // 16 MiB chunk size
+/// Plaintext chunk size of the stream format. Kept small so range reads decrypt little extra data
+const STREAM_CHUNK_SIZE: u32 = 1024 * 1024; // 1 MiB chunk size
 const BUFFER_SIZE: usize = 1024 * 1024 * 2; // 2 MiB buffer size
 
-pub fn encrypt_file(input_path: &Path, key_bytes: &[u8]) -> Result<(), CryptError> {
+/// Fill `buf` from `reader`, returning fewer bytes only at end of input
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<usize, CryptError> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+fn encrypt_file_with_chunk_size(
+    input_path: &Path,
+    key_bytes: &[u8],
+    chunk_size: u32,
+) -> Result<(), CryptError> {
     let key = Key::<Aes256Gcm>::from_slice(key_bytes);
     let cipher = Aes256Gcm::new(key);
+    let header = StreamHeader::new(chunk_size);
 
     let temp_output_path = input_path.with_extension("enc.tmp");
     let input_file = File::open(input_path)?;
@@ -26,25 +47,24 @@ pub fn encrypt_file(input_path: &Path, key_bytes: &[u8]) -> Result<(), CryptErro
 
     let output_file = File::create(&temp_output_path)?;
     let mut output = BufWriter::with_capacity(BUFFER_SIZE, output_file);
+    output.write_all(&header.to_bytes())?;
 
-    let mut buffer = vec![0u8; CHUNK_SIZE]; // 16MiB chunk
+    let mut buffer = vec![0u8; chunk_size as usize];
+    let mut index = 0u64;
 
     loop {
-        let read_bytes = input.read(&mut buffer)?;
-        if read_bytes == 0 {
-            break;
-        }
-        let chunk = &buffer[..read_bytes];
+        let read_bytes = read_full(&mut input, &mut buffer)?;
+        // a short read or an exhausted reader means this is the final chunk.
+        // An empty file still gets one (empty) final chunk so truncation is detectable
+        let last = read_bytes < buffer.len() || input.fill_buf()?.is_empty();
 
-        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
-        let encrypted = cipher
-            .encrypt(&nonce, chunk)
-            .map_err(|e| CryptError::EncryptError(e.to_string()))?;
-
-        // Write nonce length, nonce, ciphertext length, ciphertext
-        output.write_all(nonce.as_slice())?;
-        output.write_all(&(encrypted.len() as u32).to_le_bytes())?;
+        let encrypted = header.seal(&cipher, index, last, &buffer[..read_bytes])?;
         output.write_all(&encrypted)?;
+
+        if last {
+            break;
+        }
+        index += 1;
     }
     output.flush()?;
     fs::rename(temp_output_path, input_path)?;
@@ -52,36 +72,102 @@ pub fn encrypt_file(input_path: &Path, key_bytes: &[u8]) -> Result<(), CryptErro
     Ok(())
 }
 
+/// Encrypt a file in place using the versioned stream format (see [`StreamHeader`])
+pub fn encrypt_file(input_path: &Path, key_bytes: &[u8]) -> Result<(), CryptError> {
+    encrypt_file_with_chunk_size(input_path, key_bytes, STREAM_CHUNK_SIZE)
+}
+
+/// Decrypt a file in place. Files written before the stream format are still supported
 pub fn decrypt_file(input_path: &Path, key_bytes: &[u8]) -> Result<(), CryptError> {
     let key = Key::<Aes256Gcm>::from_slice(key_bytes);
     let cipher = Aes256Gcm::new(key);
 
+    let file_len = fs::metadata(input_path)?.len();
     let temp_output_path = input_path.with_extension("dec.tmp");
     let input_file = File::open(input_path)?;
     let mut input = BufReader::with_capacity(BUFFER_SIZE, input_file);
     let output_file = File::create(&temp_output_path)?;
     let mut output = BufWriter::with_capacity(BUFFER_SIZE, output_file);
 
+    let decrypted = match StreamHeader::read(&mut input)? {
+        Some(header) => decrypt_stream(&cipher, &header, file_len, &mut input, &mut output),
+        None => {
+            input.rewind()?;
+            decrypt_legacy(&cipher, &mut input, &mut output)
+        }
+    };
+
+    if let Err(e) = decrypted.and_then(|_| output.flush().map_err(CryptError::from)) {
+        drop(output);
+        let _ = fs::remove_file(&temp_output_path);
+        return Err(e);
+    }
+    fs::rename(temp_output_path, input_path)?;
+
+    Ok(())
+}
+
+fn decrypt_stream<R: Read, W: Write>(
+    cipher: &Aes256Gcm,
+    header: &StreamHeader,
+    file_len: u64,
+    input: &mut R,
+    output: &mut W,
+) -> Result<(), CryptError> {
+    let ciphertext_len = file_len - HEADER_SIZE as u64;
+    let (chunks, _) = header.layout(ciphertext_len)?;
+    let mut ct_buf = vec![0u8; header.encrypted_chunk_size() as usize];
+
+    for index in 0..chunks {
+        let last = index == chunks - 1;
+        let ct_len = if last {
+            (ciphertext_len - index * header.encrypted_chunk_size()) as usize
+        } else {
+            ct_buf.len()
+        };
+
+        input.read_exact(&mut ct_buf[..ct_len])?;
+        let decrypted = header.open(cipher, index, last, &ct_buf[..ct_len])?;
+        output.write_all(&decrypted)?;
+    }
+
+    Ok(())
+}
+
+/// Read the next legacy chunk header. Returns `None` at end of input
+fn read_legacy_chunk_header<R: Read>(
+    input: &mut R,
+) -> Result<Option<([u8; 12], usize)>, CryptError> {
     let mut nonce_buf = [0u8; 12]; // AES-GCM nonce is always 12 bytes
     let mut len_buf = [0u8; 4]; // Use u32 instead of u64 for length
 
-    loop {
-        // Read nonce
-        match input.read_exact(&mut nonce_buf) {
-            Ok(_) => {}
-            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
-            Err(e) => return Err(CryptError::IoError(e)),
-        }
+    match input.read_exact(&mut nonce_buf) {
+        Ok(_) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(CryptError::IoError(e)),
+    }
 
-        input.read_exact(&mut len_buf)?;
-        let ct_len = u32::from_le_bytes(len_buf) as usize;
+    input.read_exact(&mut len_buf)?;
+    let ct_len = u32::from_le_bytes(len_buf) as usize;
 
-        if ct_len > CHUNK_SIZE + 16 {
-            // +16 for GCM tag
-            return Err(CryptError::DecryptError(format!(
-                "Invalid ciphertext length: {ct_len} bytes"
-            )));
-        }
+    if ct_len > CHUNK_SIZE + 16 {
+        // +16 for GCM tag
+        return Err(CryptError::DecryptError(format!(
+            "Invalid ciphertext length: {ct_len} bytes"
+        )));
+    }
+
+    Ok(Some((nonce_buf, ct_len)))
+}
+
+/// Decrypt the legacy format of independent chunks. It carries no chunk index or end marker,
+/// so reordering or truncation cannot be detected
+fn decrypt_legacy<R: Read, W: Write>(
+    cipher: &Aes256Gcm,
+    input: &mut R,
+    output: &mut W,
+) -> Result<(), CryptError> {
+    while let Some((nonce_buf, ct_len)) = read_legacy_chunk_header(input)? {
         let mut ct_buf = vec![0u8; ct_len];
         input.read_exact(&mut ct_buf)?;
 
@@ -92,12 +178,131 @@ pub fn decrypt_file(input_path: &Path, key_bytes: &[u8]) -> Result<(), CryptErro
 
         output.write_all(&decrypted)?;
     }
-    output.flush()?;
-    fs::rename(temp_output_path, input_path)?;
 
     Ok(())
 }
 
+/// Decrypt `length` bytes of plaintext starting at `offset` without decrypting the whole file.
+/// Only the chunks overlapping the range are authenticated and decrypted. The range is clamped
+/// to the end of the plaintext
+///
+/// # Arguments
+/// * `input_path` - A path to the encrypted file
+/// * `key_bytes` - A byte slice containing the key the file was encrypted with
+/// * `offset` - Plaintext offset of the first byte to return
+/// * `length` - Number of plaintext bytes to return
+///
+/// # Returns
+/// A Result containing either the decrypted bytes or a CryptError
+pub fn decrypt_file_range(
+    input_path: &Path,
+    key_bytes: &[u8],
+    offset: u64,
+    length: u64,
+) -> Result<Vec<u8>, CryptError> {
+    let key = Key::<Aes256Gcm>::from_slice(key_bytes);
+    let cipher = Aes256Gcm::new(key);
+
+    let file_len = fs::metadata(input_path)?.len();
+    let input_file = File::open(input_path)?;
+    let mut input = BufReader::with_capacity(BUFFER_SIZE, input_file);
+
+    match StreamHeader::read(&mut input)? {
+        Some(header) => {
+            decrypt_stream_range(&cipher, &header, file_len, &mut input, offset, length)
+        }
+        None => {
+            input.rewind()?;
+            decrypt_legacy_range(&cipher, &mut input, offset, length)
+        }
+    }
+}
+
+fn decrypt_stream_range<R: Read + Seek>(
+    cipher: &Aes256Gcm,
+    header: &StreamHeader,
+    file_len: u64,
+    input: &mut R,
+    offset: u64,
+    length: u64,
+) -> Result<Vec<u8>, CryptError> {
+    let ciphertext_len = file_len - HEADER_SIZE as u64;
+    let (chunks, plaintext_len) = header.layout(ciphertext_len)?;
+
+    let end = offset.saturating_add(length).min(plaintext_len);
+    if offset >= end {
+        return Ok(Vec::new());
+    }
+
+    let chunk_size = header.chunk_size as u64;
+    let first = offset / chunk_size;
+    let last_needed = (end - 1) / chunk_size;
+
+    input.seek(SeekFrom::Start(
+        HEADER_SIZE as u64 + first * header.encrypted_chunk_size(),
+    ))?;
+
+    let mut out = Vec::with_capacity((end - offset) as usize);
+    let mut ct_buf = vec![0u8; header.encrypted_chunk_size() as usize];
+
+    for index in first..=last_needed {
+        let last = index == chunks - 1;
+        let ct_len = if last {
+            (ciphertext_len - index * header.encrypted_chunk_size()) as usize
+        } else {
+            ct_buf.len()
+        };
+
+        input.read_exact(&mut ct_buf[..ct_len])?;
+        let decrypted = header.open(cipher, index, last, &ct_buf[..ct_len])?;
+
+        let chunk_start = index * chunk_size;
+        let from = offset.saturating_sub(chunk_start) as usize;
+        let to = ((end - chunk_start) as usize).min(decrypted.len());
+        out.extend_from_slice(&decrypted[from..to]);
+    }
+
+    Ok(out)
+}
+
+/// Legacy chunks are length-prefixed, so chunks before the range are skipped without decrypting
+fn decrypt_legacy_range<R: Read + Seek>(
+    cipher: &Aes256Gcm,
+    input: &mut R,
+    offset: u64,
+    length: u64,
+) -> Result<Vec<u8>, CryptError> {
+    let end = offset.saturating_add(length);
+    let mut out = Vec::new();
+    let mut chunk_start = 0u64;
+
+    while chunk_start < end {
+        let Some((nonce_buf, ct_len)) = read_legacy_chunk_header(input)? else {
+            break;
+        };
+        let chunk_end = chunk_start + ct_len.saturating_sub(16) as u64;
+
+        if chunk_end <= offset {
+            input.seek(SeekFrom::Current(ct_len as i64))?;
+            chunk_start = chunk_end;
+            continue;
+        }
+
+        let mut ct_buf = vec![0u8; ct_len];
+        input.read_exact(&mut ct_buf)?;
+        let decrypted = cipher
+            .decrypt(Nonce::from_slice(&nonce_buf), ct_buf.as_ref())
+            .map_err(|e| CryptError::DecryptError(e.to_string()))?;
+
+        let from = offset.saturating_sub(chunk_start) as usize;
+        let to = ((end - chunk_start) as usize).min(decrypted.len());
+        out.extend_from_slice(&decrypted[from..to]);
+        chunk_start = chunk_end;
+    }
+
+    Ok(out)
+}
+
 /// Encrypt all files in a directory
 /// This function will encrypt all files in a directory and its subdirectories
 ///
@@ -257,4 +462,136 @@ mod tests {
         assert_eq!(file_buffer, decrypted_file_buffer);
         assert_eq!(file_buffer2, decrypted_file_buffer2);
     }
+
+    const KEY: [u8; 32] = [7u8; 32];
+
+    fn sample_data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    /// Writes the pre-stream format: independent `nonce || len || ciphertext` chunks
+    fn encrypt_legacy(path: &Path, data: &[u8], chunk_size: usize) {
+        use aes_gcm::aead::{AeadCore, OsRng};
+
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&KEY));
+        let mut out = Vec::new();
+        for chunk in data.chunks(chunk_size) {
+            let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+            let encrypted = cipher.encrypt(&nonce, chunk).unwrap();
+            out.extend_from_slice(nonce.as_slice());
+            out.extend_from_slice(&(encrypted.len() as u32).to_le_bytes());
+            out.extend_from_slice(&encrypted);
+        }
+        fs::write(path, out).unwrap();
+    }
+
+    fn encrypted_sample(dir: &Path, data: &[u8], chunk_size: u32) -> std::path::PathBuf {
+        let path = dir.join("tensor.bin");
+        fs::write(&path, data).unwrap();
+        encrypt_file_with_chunk_size(&path, &KEY, chunk_size).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_stream_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // partial final chunk, exact multiple of the chunk size and an empty file
+        for len in [10_000, 4096, 0] {
+            let data = sample_data(len);
+            let path = encrypted_sample(dir.path(), &data, 1024);
+
+            let encrypted = fs::read(&path).unwrap();
+            assert!(encrypted.starts_with(crate::stream::MAGIC));
+            assert_ne!(encrypted, data);
+
+            decrypt_file(&path, &KEY).unwrap();
+            assert_eq!(fs::read(&path).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_stream_detects_reordering_and_truncation() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = sample_data(4096);
+        let path = encrypted_sample(dir.path(), &data, 1024);
+        let encrypted = fs::read(&path).unwrap();
+        let chunk = 1024 + crate::stream::TAG_SIZE;
+
+        // swap the first two chunks
+        let mut reordered = encrypted.clone();
+        let (first, rest) = reordered[HEADER_SIZE..].split_at_mut(chunk);
+        first.swap_with_slice(&mut rest[..chunk]);
+        fs::write(&path, &reordered).unwrap();
+        assert!(decrypt_file(&path, &KEY).is_err());
+
+        // drop the final chunk at a chunk boundary
+        fs::write(&path, &encrypted[..encrypted.len() - chunk]).unwrap();
+        assert!(decrypt_file(&path, &KEY).is_err());
+
+        // tamper with the chunk size in the header
+        let mut tampered = encrypted.clone();
+        tampered[crate::stream::MAGIC.len() + 1] ^= 1;
+        fs::write(&path, &tampered).unwrap();
+        assert!(decrypt_file(&path, &KEY).is_err());
+
+        // a failed decrypt leaves the ciphertext in place
+        assert_eq!(fs::read(&path).unwrap(), tampered);
+    }
+
+    #[test]
+    fn test_decrypt_legacy_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("legacy.bin");
+        let data = sample_data(10_000);
+        encrypt_legacy(&path, &data, 4096);
+
+        assert_eq!(
+            decrypt_file_range(&path, &KEY, 4000, 200).unwrap(),
+            data[4000..4200]
+        );
+
+        decrypt_file(&path, &KEY).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), data);
+    }
+
+    #[test]
+    fn test_decrypt_file_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = sample_data(10_000);
+        let path = encrypted_sample(dir.path(), &data, 1024);
+
+        // within one chunk, across chunks, at the tail, clamped past the end and empty
+        assert_eq!(
+            decrypt_file_range(&path, &KEY, 10, 20).unwrap(),
+            data[10..30]
+        );
+        assert_eq!(
+            decrypt_file_range(&path, &KEY, 1000, 2100).unwrap(),
+            data[1000..3100]
+        );
+        assert_eq!(
+            decrypt_file_range(&path, &KEY, 9216, 784).unwrap(),
+            data[9216..]
+        );
+        assert_eq!(
+            decrypt_file_range(&path, &KEY, 9990, 1000).unwrap(),
+            data[9990..]
+        );
+        assert!(
+            decrypt_file_range(&path, &KEY, 20_000, 10)
+                .unwrap()
+                .is_empty()
+        );
+
+        // a range read never touches chunks outside the range
+        let mut encrypted = fs::read(&path).unwrap();
+        encrypted[HEADER_SIZE + 5 * (1024 + crate::stream::TAG_SIZE)] ^= 1;
+        fs::write(&path, &encrypted).unwrap();
+        assert_eq!(
+            decrypt_file_range(&path, &KEY, 0, 1024).unwrap(),
+            data[..1024]
+        );
+        assert!(decrypt_file_range(&path, &KEY, 5 * 1024, 10).is_err());
+    }
 }
@@ -1,6 +1,9 @@
 pub mod error;
 pub mod file;
 pub mod key;
+pub mod provider;
+pub mod stream;
 
 pub use file::*;
 pub use key::*;
+pub use provider::*;
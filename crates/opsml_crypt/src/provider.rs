@@ -0,0 +1,237 @@
+use crate::error::CryptError;
+use crate::key::{decrypt_key, derive_encryption_key, encrypted_key};
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use rand::TryRngCore;
+use rand::rngs::OsRng;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+
+/// Key version of artifact keys wrapped with the card uid, the format used before envelope
+/// encryption and the form artifact keys are handed to clients in
+pub const LEGACY_KEY_VERSION: u32 = 0;
+
+/// Key version of the key-encryption-key derived from the server master key
+pub const MASTER_KEY_VERSION: u32 = 1;
+
+const MASTER_KEK_SALT: &[u8] = b"opsml-artifact-kek";
+const MASTER_KEK_INFO: &[u8] = b"kek-v1";
+
+/// Wraps and unwraps artifact data keys with a key-encryption-key (KEK).
+///
+/// Every KEK has a version. New data keys are always wrapped with `current_version`, while
+/// `unwrap_key` must keep accepting every version still referenced by stored keys until they have
+/// been re-wrapped. Cloud KMS backends implement this trait by delegating to the KMS encrypt and
+/// decrypt APIs
+pub trait KeyProvider: Send + Sync {
+    /// Version of the KEK used to wrap new data keys
+    fn current_version(&self) -> u32;
+
+    /// Wrap a data key with the current KEK. Returns the wrapped key and the KEK version
+    fn wrap_key(&self, data_key: &[u8]) -> Result<(Vec<u8>, u32), CryptError>;
+
+    /// Unwrap a data key that was wrapped with KEK `version`
+    fn unwrap_key(&self, wrapped_key: &[u8], version: u32) -> Result<Vec<u8>, CryptError>;
+}
+
+/// KEKs held in memory: version 1 is derived from the server master key and versions 2 and up
+/// are read from an optional keyfile. The highest version wraps new keys.
+///
+/// The keyfile holds one `<version>:<base64 32-byte key>` entry per line. Lines starting with `#`
+/// are ignored. Rotating means appending a new version and re-wrapping the stored keys; old
+/// versions must stay in the file until the rotation has finished
+pub struct LocalKeyProvider {
+    keys: BTreeMap<u32, [u8; 32]>,
+}
+
+// never print key material
+impl std::fmt::Debug for LocalKeyProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalKeyProvider")
+            .field("versions", &self.versions())
+            .finish()
+    }
+}
+
+impl LocalKeyProvider {
+    /// Provider with a single KEK derived from the server master key
+    pub fn from_master_key(master_key: &[u8]) -> Result<Self, CryptError> {
+        let kek = derive_encryption_key(master_key, MASTER_KEK_SALT, MASTER_KEK_INFO)?;
+        Ok(Self {
+            keys: BTreeMap::from([(MASTER_KEY_VERSION, kek)]),
+        })
+    }
+
+    /// Add the KEKs of a keyfile
+    pub fn with_keyfile(mut self, path: &Path) -> Result<Self, CryptError> {
+        let content = std::fs::read_to_string(path)?;
+
+        for (line_no, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = |reason: &str| {
+                CryptError::KeyFileError(format!("{}:{}: {reason}", path.display(), line_no + 1))
+            };
+
+            let (version, key) = line
+                .split_once(':')
+                .ok_or_else(|| invalid("expected <version>:<base64 key>"))?;
+            let version: u32 = version
+                .trim()
+                .parse()
+                .map_err(|_| invalid("version is not a number"))?;
+            if version <= MASTER_KEY_VERSION {
+                return Err(invalid("versions 0 and 1 are reserved"));
+            }
+
+            let key: [u8; 32] = BASE64_STANDARD
+                .decode(key.trim())
+                .map_err(|_| invalid("key is not valid base64"))?
+                .try_into()
+                .map_err(|_| invalid("key must be 32 bytes"))?;
+
+            if self.keys.insert(version, key).is_some() {
+                return Err(invalid("duplicate version"));
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Generate a new KEK and append it to `path` as the next version, creating the file if needed.
+    /// Returns the new version
+    pub fn append_new_key(path: &Path) -> Result<u32, CryptError> {
+        let existing = match path.exists() {
+            true => Self {
+                keys: BTreeMap::new(),
+            }
+            .with_keyfile(path)?,
+            false => Self {
+                keys: BTreeMap::new(),
+            },
+        };
+        let version = existing
+            .keys
+            .keys()
+            .next_back()
+            .map_or(MASTER_KEY_VERSION + 1, |v| v + 1);
+
+        let mut key = [0u8; 32];
+        OsRng
+            .try_fill_bytes(&mut key)
+            .map_err(|_| CryptError::GenerateSaltError)?;
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        writeln!(file, "{version}:{}", BASE64_STANDARD.encode(key))?;
+
+        Ok(version)
+    }
+
+    pub fn versions(&self) -> Vec<u32> {
+        self.keys.keys().copied().collect()
+    }
+}
+
+impl KeyProvider for LocalKeyProvider {
+    fn current_version(&self) -> u32 {
+        // from_master_key always inserts a key, so there is at least one version
+        self.keys
+            .keys()
+            .next_back()
+            .copied()
+            .unwrap_or(MASTER_KEY_VERSION)
+    }
+
+    fn wrap_key(&self, data_key: &[u8]) -> Result<(Vec<u8>, u32), CryptError> {
+        let version = self.current_version();
+        let kek = self
+            .keys
+            .get(&version)
+            .ok_or(CryptError::UnknownKeyVersion(version))?;
+        Ok((encrypted_key(kek, data_key)?, version))
+    }
+
+    fn unwrap_key(&self, wrapped_key: &[u8], version: u32) -> Result<Vec<u8>, CryptError> {
+        let kek = self
+            .keys
+            .get(&version)
+            .ok_or(CryptError::UnknownKeyVersion(version))?;
+        decrypt_key(kek, wrapped_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MASTER: [u8; 32] = [3u8; 32];
+
+    #[test]
+    fn test_master_key_provider_roundtrip() {
+        let provider = LocalKeyProvider::from_master_key(&MASTER).unwrap();
+        assert_eq!(provider.current_version(), MASTER_KEY_VERSION);
+
+        let data_key = [9u8; 32];
+        let (wrapped, version) = provider.wrap_key(&data_key).unwrap();
+        assert_eq!(version, MASTER_KEY_VERSION);
+        assert_eq!(provider.unwrap_key(&wrapped, version).unwrap(), data_key);
+
+        // the same master key always derives the same KEK
+        let other = LocalKeyProvider::from_master_key(&MASTER).unwrap();
+        assert_eq!(other.unwrap_key(&wrapped, version).unwrap(), data_key);
+        assert!(matches!(
+            other.unwrap_key(&wrapped, 7),
+            Err(CryptError::UnknownKeyVersion(7))
+        ));
+    }
+
+    #[test]
+    fn test_keyfile_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let keyfile = dir.path().join("kek");
+
+        let data_key = [9u8; 32];
+        let provider = LocalKeyProvider::from_master_key(&MASTER).unwrap();
+        let (wrapped_v1, _) = provider.wrap_key(&data_key).unwrap();
+
+        assert_eq!(LocalKeyProvider::append_new_key(&keyfile).unwrap(), 2);
+        assert_eq!(LocalKeyProvider::append_new_key(&keyfile).unwrap(), 3);
+
+        let provider = LocalKeyProvider::from_master_key(&MASTER)
+            .unwrap()
+            .with_keyfile(&keyfile)
+            .unwrap();
+        assert_eq!(provider.versions(), vec![1, 2, 3]);
+
+        // re-wrap an old key with the newest KEK
+        let unwrapped = provider.unwrap_key(&wrapped_v1, 1).unwrap();
+        let (wrapped_v3, version) = provider.wrap_key(&unwrapped).unwrap();
+        assert_eq!(version, 3);
+        assert_eq!(provider.unwrap_key(&wrapped_v3, 3).unwrap(), data_key);
+        assert!(provider.unwrap_key(&wrapped_v3, 2).is_err());
+    }
+
+    #[test]
+    fn test_keyfile_rejects_reserved_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let keyfile = dir.path().join("kek");
+        std::fs::write(
+            &keyfile,
+            format!("# comment\n1:{}\n", BASE64_STANDARD.encode([1u8; 32])),
+        )
+        .unwrap();
+
+        let provider = LocalKeyProvider::from_master_key(&MASTER).unwrap();
+        assert!(matches!(
+            provider.with_keyfile(&keyfile),
+            Err(CryptError::KeyFileError(_))
+        ));
+    }
+}
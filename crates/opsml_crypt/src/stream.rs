@@ -0,0 +1,180 @@
+use crate::error::CryptError;
+use aes_gcm::{
+    Aes256Gcm, Nonce,
+    aead::{Aead, AeadCore, OsRng, Payload},
+};
+use std::io::Read;
+
+/// Identifies files written in the versioned stream format. Files without it use the legacy
+/// layout of independent `nonce || len || ciphertext` chunks
+pub const MAGIC: &[u8; 8] = b"OPSMLENC";
+pub const FORMAT_VERSION: u8 = 1;
+
+const NONCE_PREFIX_SIZE: usize = 7;
+pub const HEADER_SIZE: usize = MAGIC.len() + 1 + 4 + NONCE_PREFIX_SIZE;
+pub const TAG_SIZE: usize = 16;
+
+/// Upper bound on the chunk size accepted from a header, so a corrupt header cannot force a huge allocation
+const MAX_CHUNK_SIZE: u32 = 1024 * 1024 * 64;
+
+/// Header of the stream format: `magic || version || chunk_size (u32 le) || nonce prefix`.
+///
+/// Chunks follow the STREAM construction: chunk `i` is sealed with the nonce
+/// `prefix || i (u32 be) || last` and the associated data `header || i (u32 be) || last`.
+/// Reordering, dropping or truncating chunks, or tampering with the header, fails authentication.
+/// Every chunk but the last holds exactly `chunk_size` plaintext bytes, so chunk offsets can be
+/// computed for range reads
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamHeader {
+    pub chunk_size: u32,
+    nonce_prefix: [u8; NONCE_PREFIX_SIZE],
+}
+
+impl StreamHeader {
+    pub fn new(chunk_size: u32) -> Self {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_SIZE];
+        nonce_prefix.copy_from_slice(&nonce[..NONCE_PREFIX_SIZE]);
+
+        Self {
+            chunk_size,
+            nonce_prefix,
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; HEADER_SIZE] {
+        let mut bytes = [0u8; HEADER_SIZE];
+        bytes[..MAGIC.len()].copy_from_slice(MAGIC);
+        bytes[MAGIC.len()] = FORMAT_VERSION;
+        bytes[MAGIC.len() + 1..MAGIC.len() + 5].copy_from_slice(&self.chunk_size.to_le_bytes());
+        bytes[MAGIC.len() + 5..].copy_from_slice(&self.nonce_prefix);
+        bytes
+    }
+
+    /// Read a header from the start of `reader`.
+    /// Returns `None` if the data does not start with the stream magic (legacy format)
+    pub fn read<R: Read>(reader: &mut R) -> Result<Option<Self>, CryptError> {
+        let mut bytes = [0u8; HEADER_SIZE];
+        let mut filled = 0;
+        while filled < HEADER_SIZE {
+            match reader.read(&mut bytes[filled..])? {
+                0 => break,
+                n => filled += n,
+            }
+        }
+
+        if filled < MAGIC.len() || &bytes[..MAGIC.len()] != MAGIC {
+            return Ok(None);
+        }
+
+        if filled < HEADER_SIZE {
+            return Err(CryptError::DecryptError(
+                "Truncated file header".to_string(),
+            ));
+        }
+
+        let version = bytes[MAGIC.len()];
+        if version != FORMAT_VERSION {
+            return Err(CryptError::UnsupportedFormatVersion(version));
+        }
+
+        let mut chunk_size = [0u8; 4];
+        chunk_size.copy_from_slice(&bytes[MAGIC.len() + 1..MAGIC.len() + 5]);
+        let chunk_size = u32::from_le_bytes(chunk_size);
+        if chunk_size == 0 || chunk_size > MAX_CHUNK_SIZE {
+            return Err(CryptError::DecryptError(format!(
+                "Invalid chunk size in header: {chunk_size} bytes"
+            )));
+        }
+
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_SIZE];
+        nonce_prefix.copy_from_slice(&bytes[MAGIC.len() + 5..]);
+
+        Ok(Some(Self {
+            chunk_size,
+            nonce_prefix,
+        }))
+    }
+
+    /// Size of a full chunk on disk
+    pub fn encrypted_chunk_size(&self) -> u64 {
+        self.chunk_size as u64 + TAG_SIZE as u64
+    }
+
+    /// Number of chunks and the plaintext length of a stream whose ciphertext (excluding the
+    /// header) is `ciphertext_len` bytes long
+    pub fn layout(&self, ciphertext_len: u64) -> Result<(u64, u64), CryptError> {
+        let full = self.encrypted_chunk_size();
+        let chunks = ciphertext_len.div_ceil(full).max(1);
+        let last_len = ciphertext_len - (chunks - 1) * full;
+
+        if last_len < TAG_SIZE as u64 {
+            return Err(CryptError::DecryptError(
+                "Ciphertext is truncated".to_string(),
+            ));
+        }
+
+        let plaintext_len = ciphertext_len - chunks * TAG_SIZE as u64;
+        Ok((chunks, plaintext_len))
+    }
+
+    fn nonce_and_aad(&self, index: u64, last: bool) -> Result<([u8; 12], Vec<u8>), CryptError> {
+        let index = u32::try_from(index).map_err(|_| {
+            CryptError::EncryptError("File exceeds the maximum number of chunks".to_string())
+        })?;
+
+        let mut nonce = [0u8; 12];
+        nonce[..NONCE_PREFIX_SIZE].copy_from_slice(&self.nonce_prefix);
+        nonce[NONCE_PREFIX_SIZE..11].copy_from_slice(&index.to_be_bytes());
+        nonce[11] = last as u8;
+
+        let mut aad = Vec::with_capacity(HEADER_SIZE + 5);
+        aad.extend_from_slice(&self.to_bytes());
+        aad.extend_from_slice(&index.to_be_bytes());
+        aad.push(last as u8);
+
+        Ok((nonce, aad))
+    }
+
+    pub fn seal(
+        &self,
+        cipher: &Aes256Gcm,
+        index: u64,
+        last: bool,
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, CryptError> {
+        let (nonce, aad) = self.nonce_and_aad(index, last)?;
+        cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|e| CryptError::EncryptError(e.to_string()))
+    }
+
+    pub fn open(
+        &self,
+        cipher: &Aes256Gcm,
+        index: u64,
+        last: bool,
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, CryptError> {
+        let (nonce, aad) = self.nonce_and_aad(index, last)?;
+        cipher
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| {
+                CryptError::DecryptError(format!(
+                    "Chunk {index} failed authentication (wrong key, or tampered, reordered or truncated ciphertext)"
+                ))
+            })
+    }
+}
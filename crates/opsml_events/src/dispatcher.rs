@@ -0,0 +1,126 @@
+use crate::error::EventError;
+use crate::sink::EventSink;
+use crate::webhook::{WebhookDeliverer, WebhookSink};
+use chrono::Utc;
+use opsml_settings::config::WebhookSettings;
+use opsml_sql::enums::client::SqlClientEnum;
+use opsml_sql::traits::EventLogicTrait;
+use opsml_types::contracts::RegistryEvent;
+use opsml_utils::create_uuid7;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{error, info, instrument};
+
+/// Outbox events relayed to sinks per poll
+const OUTBOX_BATCH_SIZE: i64 = 100;
+
+/// How long claimed outbox events are hidden from other instances
+const OUTBOX_CLAIM_SECS: i64 = 60;
+
+/// Background worker that drains the event outbox into the registered sinks and
+/// delivers queued webhook calls. Delivery is at-least-once
+pub struct EventDispatcher {
+    sql_client: Arc<SqlClientEnum>,
+    sinks: Vec<Arc<dyn EventSink>>,
+    deliverer: WebhookDeliverer,
+    poll_interval: Duration,
+}
+
+impl EventDispatcher {
+    /// Creates a dispatcher with the webhook sink registered
+    pub fn new(
+        sql_client: Arc<SqlClientEnum>,
+        settings: &WebhookSettings,
+    ) -> Result<Self, EventError> {
+        Ok(Self {
+            sinks: vec![Arc::new(WebhookSink::new(sql_client.clone()))],
+            deliverer: WebhookDeliverer::new(sql_client.clone(), settings)?,
+            poll_interval: Duration::from_millis(settings.poll_interval_ms),
+            sql_client,
+        })
+    }
+
+    /// Registers an additional sink, called after the sinks already registered
+    pub fn with_sink(mut self, sink: Arc<dyn EventSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Claims undispatched outbox events and hands them to every sink in order. Stops at the
+    /// first failing event and releases the rest of the claim, so that the next poll starts
+    /// again from that event. Returns the number of events dispatched
+    #[instrument(skip_all)]
+    pub async fn relay_events(&self) -> Result<usize, EventError> {
+        let claim_id = create_uuid7();
+        let now = Utc::now();
+        let events = self
+            .sql_client
+            .claim_undispatched_events(
+                &claim_id,
+                &now,
+                &(now + chrono::Duration::seconds(OUTBOX_CLAIM_SECS)),
+                OUTBOX_BATCH_SIZE,
+            )
+            .await
+            .map_err(EventError::LogEventError)?;
+
+        let mut dispatched = 0;
+        for event in events {
+            if let Err(e) = self.relay(&event).await {
+                if let Err(release_err) = self.sql_client.release_event_claim(&claim_id).await {
+                    error!("Failed to release outbox claim {claim_id}: {release_err}");
+                }
+                return Err(e);
+            }
+            dispatched += 1;
+        }
+
+        Ok(dispatched)
+    }
+
+    async fn relay(&self, event: &RegistryEvent) -> Result<(), EventError> {
+        for sink in &self.sinks {
+            sink.handle(event).await.map_err(|e| {
+                error!(
+                    "Sink {} failed for event {}: {e}",
+                    sink.name(),
+                    event.event_id
+                );
+                e
+            })?;
+        }
+
+        self.sql_client
+            .mark_event_dispatched(&event.event_id)
+            .await
+            .map_err(EventError::LogEventError)
+    }
+
+    /// Runs a single relay and delivery pass
+    pub async fn dispatch_once(&self) -> Result<(), EventError> {
+        self.relay_events().await?;
+        self.deliverer.deliver_due().await?;
+        Ok(())
+    }
+
+    pub fn start(self) -> JoinHandle<()> {
+        info!(
+            "Starting event dispatcher with {} sink(s), polling every {:?}",
+            self.sinks.len(),
+            self.poll_interval
+        );
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.poll_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.dispatch_once().await {
+                    error!("Event dispatch failed: {e}");
+                }
+            }
+        })
+    }
+}
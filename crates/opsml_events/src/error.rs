@@ -5,4 +5,13 @@ use thiserror::Error;
 pub enum EventError {
     #[error("Failed to log event")]
     LogEventError(#[source] SqlError),
+
+    #[error("Failed to build webhook http client")]
+    ClientError(#[source] reqwest::Error),
+
+    #[error("Invalid webhook url: {0}")]
+    InvalidWebhookUrl(String),
+
+    #[error("Event sink {0} failed: {1}")]
+    SinkError(String, String),
 }
@@ -1,6 +1,6 @@
 use futures::Stream;
-use opsml_sql::traits::{AuditLogicTrait, SpaceLogicTrait};
-use opsml_types::contracts::{AuditEvent, SpaceNameEvent};
+use opsml_sql::traits::{AuditLogicTrait, EventLogicTrait, SpaceLogicTrait};
+use opsml_types::contracts::{AuditEvent, RegistryEvent, SpaceNameEvent};
 use tokio::sync::broadcast;
 use tokio_stream::StreamExt;
 use tokio_stream::wrappers::BroadcastStream;
@@ -47,9 +47,25 @@ pub async fn insert_space_name_record(
     Ok(())
 }
 
+#[instrument(skip_all)]
+pub async fn record_registry_event(
+    event: &RegistryEvent,
+    sql_client: &SqlClientEnum,
+) -> Result<(), EventError> {
+    debug!("Recording registry event: {}", event.event_type);
+
+    sql_client.insert_registry_event(event).await.map_err(|e| {
+        error!("Failed to record registry event: {e}");
+        EventError::LogEventError(e)
+    })?;
+
+    Ok(())
+}
+
 #[derive(Clone)]
 pub struct EventBus {
     tx: broadcast::Sender<Event>,
+    outbox: Option<Arc<SqlClientEnum>>,
 }
 
 impl EventBus {
@@ -57,7 +73,14 @@ impl EventBus {
     pub fn new(capacity: usize) -> Self {
         debug!("Creating EventBus with capacity: {}", capacity);
         let (tx, _) = broadcast::channel(capacity);
-        Self { tx }
+        Self { tx, outbox: None }
+    }
+
+    /// Persists registry events to the outbox table before they are broadcast,
+    /// so they are delivered to sinks even if no one is subscribed or the server restarts
+    pub fn with_outbox(mut self, sql_client: Arc<SqlClientEnum>) -> Self {
+        self.outbox = Some(sql_client);
+        self
     }
 
     #[instrument(skip_all)]
@@ -66,6 +89,17 @@ impl EventBus {
         let _ = self.tx.send(event);
     }
 
+    /// Writes a registry event to the outbox, if configured, and then broadcasts it
+    #[instrument(skip_all)]
+    pub async fn publish_registry_event(&self, event: RegistryEvent) -> Result<(), EventError> {
+        if let Some(sql_client) = &self.outbox {
+            record_registry_event(&event, sql_client).await?;
+        }
+
+        self.publish(Event::Registry(event));
+        Ok(())
+    }
+
     pub fn subscribe(&self) -> impl Stream<Item = Event> {
         let rx = self.tx.subscribe();
         BroadcastStream::new(rx).filter_map(|result| result.ok())
@@ -1,6 +1,11 @@
+pub mod dispatcher;
 pub mod error;
 pub mod event;
+pub mod sink;
 pub mod types;
+pub mod webhook;
 
+pub use dispatcher::EventDispatcher;
 pub use event::EventBus;
+pub use sink::EventSink;
 pub use types::{AuditContext, Event, create_audit_event};
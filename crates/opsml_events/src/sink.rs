@@ -0,0 +1,15 @@
+use crate::error::EventError;
+use async_trait::async_trait;
+use opsml_types::contracts::RegistryEvent;
+
+/// Destination for registry events read from the outbox.
+///
+/// Sinks are called in registration order for every event. An event is only marked
+/// as dispatched once every sink has accepted it, so a sink returning an error will
+/// see the same event again on the next poll and must tolerate duplicates
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn handle(&self, event: &RegistryEvent) -> Result<(), EventError>;
+}
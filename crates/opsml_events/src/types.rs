@@ -2,7 +2,9 @@ use axum::http::HeaderMap;
 use headers::UserAgent;
 
 use opsml_types::RegistryType;
-use opsml_types::contracts::{AuditEvent, AuditStatus, Operation, ResourceType, SpaceNameEvent};
+use opsml_types::contracts::{
+    AuditEvent, AuditStatus, Operation, RegistryEvent, ResourceType, SpaceNameEvent,
+};
 
 use std::net::SocketAddr;
 
@@ -52,5 +54,7 @@ pub fn create_audit_event(
 pub enum Event {
     Audit(AuditEvent),
     SpaceName(SpaceNameEvent),
+    /// Registry change that has been written to the outbox
+    Registry(RegistryEvent),
     // Add other events as needed
 }
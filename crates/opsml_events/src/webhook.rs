@@ -0,0 +1,437 @@
+use crate::error::EventError;
+use crate::sink::EventSink;
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use opsml_settings::config::WebhookSettings;
+use opsml_sql::enums::client::SqlClientEnum;
+use opsml_sql::traits::EventLogicTrait;
+use opsml_types::contracts::{DeliveryStatus, RegistryEvent, Webhook, WebhookDelivery};
+use opsml_utils::create_uuid7;
+use rand::Rng;
+use rand::distr::Alphanumeric;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::header::CONTENT_TYPE;
+use sha2::Sha256;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, instrument, warn};
+
+pub const EVENT_HEADER: &str = "X-Opsml-Event";
+pub const DELIVERY_HEADER: &str = "X-Opsml-Delivery";
+pub const TIMESTAMP_HEADER: &str = "X-Opsml-Timestamp";
+pub const SIGNATURE_HEADER: &str = "X-Opsml-Signature";
+
+const SECRET_PREFIX: &str = "whsec_";
+const SECRET_LENGTH: usize = 40;
+
+/// Longest delay between two attempts of the same delivery (1 hour)
+const MAX_BACKOFF_SECS: u64 = 3600;
+
+/// Deliveries attempted per poll
+const DELIVERY_BATCH_SIZE: i64 = 100;
+
+pub fn generate_webhook_secret() -> String {
+    let secret: String = rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(SECRET_LENGTH)
+        .map(char::from)
+        .collect();
+
+    format!("{SECRET_PREFIX}{secret}")
+}
+
+/// Signs `{timestamp}.{body}` with HMAC-SHA256 and returns the `X-Opsml-Signature` value.
+/// Receivers should recompute the signature and reject stale timestamps to guard against replays
+pub fn sign_payload(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Delay before the next attempt after `attempts` failures. Doubles every attempt up to an hour
+pub fn retry_delay(backoff_secs: u64, attempts: i32) -> chrono::Duration {
+    let exponent = (attempts - 1).clamp(0, 20) as u32;
+    let secs = backoff_secs
+        .saturating_mul(2u64.pow(exponent))
+        .min(MAX_BACKOFF_SECS);
+
+    chrono::Duration::seconds(secs as i64)
+}
+
+/// Returns true for addresses a webhook must not reach unless private targets are allowed:
+/// loopback, private, link-local, shared, unspecified, broadcast, documentation and multicast
+pub fn is_internal_address(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [first, second, ..] = ip.octets();
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                // shared address space, 100.64.0.0/10
+                || (first == 100 && second & 0xc0 == 64)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_internal_address(&IpAddr::V4(ip)),
+            None => {
+                ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local()
+            }
+        },
+    }
+}
+
+/// Parses a webhook url, rejecting urls that are not http(s) and, unless `allow_private` is
+/// set, internal ip literals. Returns the host name when it still has to be resolved and checked
+fn parse_webhook_url(url: &str, allow_private: bool) -> Result<Option<String>, EventError> {
+    let url = reqwest::Url::parse(url)
+        .ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https"))
+        .ok_or_else(|| {
+            EventError::InvalidWebhookUrl("url must be an http or https url".to_string())
+        })?;
+
+    if allow_private {
+        return Ok(None);
+    }
+
+    let host = url
+        .host_str()
+        .map(|host| host.trim_start_matches('[').trim_end_matches(']'))
+        .ok_or_else(|| EventError::InvalidWebhookUrl("url has no host".to_string()))?;
+
+    match host.parse::<IpAddr>() {
+        Ok(ip) if is_internal_address(&ip) => Err(EventError::InvalidWebhookUrl(format!(
+            "{host} is an internal address"
+        ))),
+        Ok(_) => Ok(None),
+        Err(_) => Ok(Some(host.to_string())),
+    }
+}
+
+/// Resolves a host and fails if any of its addresses is internal
+async fn resolve_public_host(host: &str) -> Result<Vec<SocketAddr>, EventError> {
+    // the port is filled in by the connector
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, 0))
+        .await
+        .map_err(|e| EventError::InvalidWebhookUrl(format!("failed to resolve {host}: {e}")))?
+        .collect();
+
+    match addrs.iter().find(|addr| is_internal_address(&addr.ip())) {
+        Some(addr) => Err(EventError::InvalidWebhookUrl(format!(
+            "{host} resolves to the internal address {}",
+            addr.ip()
+        ))),
+        None => Ok(addrs),
+    }
+}
+
+/// Checks that a webhook url is http(s) and, unless `allow_private` is set, that its host only
+/// resolves to public addresses. Called when a webhook is registered; deliveries check the
+/// addresses they connect to through `PublicResolver`
+pub async fn validate_webhook_url(url: &str, allow_private: bool) -> Result<(), EventError> {
+    if let Some(host) = parse_webhook_url(url, allow_private)? {
+        resolve_public_host(&host).await?;
+    }
+    Ok(())
+}
+
+/// DNS resolver for webhook deliveries that refuses internal addresses. Checking the addresses
+/// the request actually connects to means a host cannot pass validation and then rebind to an
+/// internal address
+struct PublicResolver;
+
+impl PublicResolver {
+    async fn lookup(name: Name) -> Result<Addrs, Box<dyn std::error::Error + Send + Sync>> {
+        let addrs = resolve_public_host(name.as_str())
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(Box::new(addrs.into_iter()))
+    }
+}
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(Self::lookup(name))
+    }
+}
+
+/// Queues a delivery for every active webhook in the event's space subscribed to its type
+pub struct WebhookSink {
+    sql_client: Arc<SqlClientEnum>,
+}
+
+impl WebhookSink {
+    pub fn new(sql_client: Arc<SqlClientEnum>) -> Self {
+        Self { sql_client }
+    }
+}
+
+#[async_trait]
+impl EventSink for WebhookSink {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn handle(&self, event: &RegistryEvent) -> Result<(), EventError> {
+        let webhooks = self
+            .sql_client
+            .list_webhooks(&event.space)
+            .await
+            .map_err(EventError::LogEventError)?;
+
+        let now = Utc::now();
+        for webhook in webhooks.into_iter().map(Webhook::from) {
+            if !webhook.matches(event) {
+                continue;
+            }
+
+            self.sql_client
+                .insert_webhook_delivery(&webhook.id, &event.event_id, &now)
+                .await
+                .map_err(EventError::LogEventError)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Sends queued deliveries and reschedules failed ones with exponential backoff
+pub struct WebhookDeliverer {
+    sql_client: Arc<SqlClientEnum>,
+    client: reqwest::Client,
+    max_attempts: i32,
+    backoff_secs: u64,
+    allow_private_targets: bool,
+    claim_lease: chrono::Duration,
+}
+
+impl WebhookDeliverer {
+    pub fn new(
+        sql_client: Arc<SqlClientEnum>,
+        settings: &WebhookSettings,
+    ) -> Result<Self, EventError> {
+        let mut builder = reqwest::Client::builder()
+            .timeout(Duration::from_secs(settings.timeout_secs))
+            // a redirect could otherwise point the request at an internal address
+            .redirect(reqwest::redirect::Policy::none());
+        if !settings.allow_private_targets {
+            builder = builder.dns_resolver(Arc::new(PublicResolver));
+        }
+        let client = builder.build().map_err(EventError::ClientError)?;
+
+        Ok(Self {
+            sql_client,
+            client,
+            max_attempts: settings.max_attempts,
+            backoff_secs: settings.backoff_secs,
+            allow_private_targets: settings.allow_private_targets,
+            // deliveries are attempted one after another, so the claim must outlast a batch
+            // in which every request times out
+            claim_lease: chrono::Duration::seconds(
+                (settings.timeout_secs as i64 + 1) * DELIVERY_BATCH_SIZE,
+            ),
+        })
+    }
+
+    /// Claims and attempts every delivery that is due. Deliveries claimed by another instance
+    /// are skipped until their claim expires. Returns the number of deliveries attempted
+    #[instrument(skip_all)]
+    pub async fn deliver_due(&self) -> Result<usize, EventError> {
+        let now = Utc::now();
+        let due = self
+            .sql_client
+            .claim_due_webhook_deliveries(
+                &create_uuid7(),
+                &now,
+                &(now + self.claim_lease),
+                DELIVERY_BATCH_SIZE,
+            )
+            .await
+            .map_err(EventError::LogEventError)?;
+
+        let count = due.len();
+        for delivery in due {
+            self.attempt(delivery).await?;
+        }
+
+        Ok(count)
+    }
+
+    async fn attempt(&self, mut delivery: WebhookDelivery) -> Result<(), EventError> {
+        let webhook = self
+            .sql_client
+            .get_webhook(&delivery.webhook_id)
+            .await
+            .map_err(EventError::LogEventError)?;
+        let event = self
+            .sql_client
+            .get_registry_event(&delivery.event_id)
+            .await
+            .map_err(EventError::LogEventError)?;
+
+        delivery.attempts += 1;
+        delivery.next_attempt_at = Utc::now();
+
+        let outcome = match (webhook, event) {
+            (Some(webhook), Some(event)) if webhook.active => {
+                self.send(&webhook.url, &webhook.secret, &delivery, &event)
+                    .await
+            }
+            _ => {
+                // nothing left to deliver to, so retrying would not help
+                delivery.attempts = delivery.attempts.max(self.max_attempts);
+                Err("webhook or event no longer exists".to_string())
+            }
+        };
+
+        match outcome {
+            Ok(()) => {
+                debug!(
+                    "Delivered event {} to webhook {}",
+                    delivery.event_id, delivery.webhook_id
+                );
+                delivery.status = DeliveryStatus::Delivered;
+                delivery.last_error = None;
+            }
+            Err(e) => {
+                warn!(
+                    "Delivery of event {} to webhook {} failed (attempt {}): {e}",
+                    delivery.event_id, delivery.webhook_id, delivery.attempts
+                );
+                if delivery.attempts >= self.max_attempts {
+                    delivery.status = DeliveryStatus::Failed;
+                } else {
+                    delivery.next_attempt_at += retry_delay(self.backoff_secs, delivery.attempts);
+                }
+                delivery.last_error = Some(e);
+            }
+        }
+
+        self.sql_client
+            .update_webhook_delivery(&delivery)
+            .await
+            .map_err(EventError::LogEventError)
+    }
+
+    async fn send(
+        &self,
+        url: &str,
+        secret: &str,
+        delivery: &WebhookDelivery,
+        event: &RegistryEvent,
+    ) -> Result<(), String> {
+        // host names are checked by the resolver when the request connects
+        parse_webhook_url(url, self.allow_private_targets).map_err(|e| e.to_string())?;
+
+        let body = serde_json::to_vec(event).map_err(|e| e.to_string())?;
+        let timestamp = Utc::now().timestamp();
+
+        let response = self
+            .client
+            .post(url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, event.event_type.to_string())
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, sign_payload(secret, timestamp, &body))
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("webhook responded with {}", response.status()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_payload() {
+        let signature = sign_payload("secret", 1700000000, b"{}");
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(signature.len(), "sha256=".len() + 64);
+
+        // any change to the secret, timestamp or body changes the signature
+        assert_eq!(signature, sign_payload("secret", 1700000000, b"{}"));
+        assert_ne!(signature, sign_payload("other", 1700000000, b"{}"));
+        assert_ne!(signature, sign_payload("secret", 1700000001, b"{}"));
+        assert_ne!(signature, sign_payload("secret", 1700000000, b"[]"));
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(retry_delay(5, 1), chrono::Duration::seconds(5));
+        assert_eq!(retry_delay(5, 2), chrono::Duration::seconds(10));
+        assert_eq!(retry_delay(5, 4), chrono::Duration::seconds(40));
+        assert_eq!(
+            retry_delay(5, 100),
+            chrono::Duration::seconds(MAX_BACKOFF_SECS as i64)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_webhook_url() {
+        assert!(
+            validate_webhook_url("https://203.0.113.1/hook", true)
+                .await
+                .is_ok()
+        );
+        assert!(
+            validate_webhook_url("https://8.8.8.8/hook", false)
+                .await
+                .is_ok()
+        );
+        assert!(
+            validate_webhook_url("file:///etc/passwd", true)
+                .await
+                .is_err()
+        );
+
+        for url in [
+            "http://127.0.0.1:8080/hook",
+            "http://10.0.0.5/hook",
+            "http://192.168.1.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://100.64.0.1/hook",
+            "http://0.0.0.0/hook",
+            "http://[::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+            "http://localhost:8080/hook",
+        ] {
+            assert!(
+                validate_webhook_url(url, false).await.is_err(),
+                "{url} should be rejected"
+            );
+            assert!(validate_webhook_url(url, true).await.is_ok());
+        }
+    }
+
+    #[test]
+    fn test_generate_webhook_secret() {
+        let secret = generate_webhook_secret();
+        assert!(secret.starts_with(SECRET_PREFIX));
+        assert_eq!(secret.len(), SECRET_PREFIX.len() + SECRET_LENGTH);
+        assert_ne!(secret, generate_webhook_secret());
+    }
+}
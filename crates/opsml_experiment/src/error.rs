@@ -41,6 +41,12 @@ pub enum ExperimentError {
     #[error("Failed to insert parameter")]
     InsertParameterError(#[source] RegistryError),
 
+    #[error("Failed to flush logged metrics and parameters: {0}")]
+    MetricFlushError(String),
+
+    #[error("Metric queue is closed")]
+    MetricQueueClosedError,
+
     #[error("Failed to find artifact: {0}")]
     ArtifactNotFoundError(String),
 
@@ -1,4 +1,5 @@
 use crate::HardwareQueue;
+use crate::MetricQueue;
 use crate::error::ExperimentError;
 use chrono::{DateTime, Utc};
 use mime_guess::mime;
@@ -8,7 +9,7 @@ use opsml_registry::CardRegistries;
 use opsml_registry::registries::artifact::OpsmlArtifactRegistry;
 use opsml_registry::registries::experiment::OpsmlExperiment;
 use opsml_semver::VersionType;
-use opsml_storage::storage_client;
+use opsml_storage::{ProgressTracker, TransferProgress, storage_client};
 use opsml_types::CommonKwargs;
 use opsml_types::RegistryType;
 use opsml_types::cards::{CardStatus, ExperimentEvalMetrics, Metrics, Parameters};
@@ -186,6 +187,7 @@ pub struct Experiment {
     pub registries: CardRegistries,
     pub experiment_helper: OpsmlExperiment,
     pub hardware_queue: Option<HardwareQueue>,
+    pub metric_queue: Option<MetricQueue>,
     uid: String,
     artifact_key: ArtifactKey,
 }
@@ -234,10 +236,15 @@ impl Experiment {
             false => None,
         };
 
+        // metrics and parameters are buffered and written in bulk
+        let metric_queue =
+            MetricQueue::start(Arc::new(experiment_helper.clone()), experiment_uid.clone());
+
         Ok(Self {
             experiment: experiment.into_py_any(py)?,
             registries,
             hardware_queue,
+            metric_queue: Some(metric_queue),
             uid: experiment_uid,
             artifact_key,
             experiment_helper,
@@ -399,8 +406,45 @@ impl Experiment {
         if let Some(mut queue) = self.hardware_queue.take() {
             queue.stop();
         }
+
+        // flushes anything still buffered and reports failed flushes
+        if let Some(mut queue) = self.metric_queue.take() {
+            queue.stop()?;
+        }
         Ok(())
     }
+
+    /// Queues metrics for the background writer. Once the queue is stopped, metrics are inserted directly
+    fn insert_metrics(&self, metrics: Vec<Metric>) -> Result<(), ExperimentError> {
+        if let Some(queue) = &self.metric_queue {
+            return queue.log_metrics(metrics);
+        }
+
+        let metric_request = MetricRequest {
+            experiment_uid: self.uid.clone(),
+            metrics,
+        };
+
+        self.experiment_helper
+            .insert_metrics(&metric_request)
+            .map_err(ExperimentError::InsertMetricError)
+    }
+
+    /// Queues parameters for the background writer. Once the queue is stopped, parameters are inserted directly
+    fn insert_parameters(&self, parameters: Vec<Parameter>) -> Result<(), ExperimentError> {
+        if let Some(queue) = &self.metric_queue {
+            return queue.log_parameters(parameters);
+        }
+
+        let param_request = ParameterRequest {
+            experiment_uid: self.uid.clone(),
+            parameters,
+        };
+
+        self.experiment_helper
+            .insert_parameters(&param_request)
+            .map_err(ExperimentError::InsertParameterError)
+    }
 }
 
 #[pymethods]
@@ -509,15 +553,18 @@ impl Experiment {
         // Always perform cleanup regardless of success/failure
         debug!("Finalizing experiment with status: {:?}", card_status);
 
+        // Stop hardware monitoring and flush buffered metrics before the card is finalized.
+        // Flush errors are raised once the card has been updated
+        debug!("Stopping hardware and metric queues");
+        let stopped = slf.stop_queue();
+
         // Update experiment card
         let experiment = slf.experiment.clone_ref(py);
         let exp = experiment.bind(py);
         exp.setattr("status", card_status)?;
         slf.registries.experiment.update_card(exp)?;
 
-        // Stop hardware monitoring queue
-        debug!("Stopping hardware queue");
-        slf.stop_queue()?;
+        stopped?;
 
         debug!("Experiment finalized successfully");
 
@@ -534,36 +581,18 @@ impl Experiment {
         timestamp: Option<i64>,
         created_at: Option<DateTime<Utc>>,
     ) -> Result<(), ExperimentError> {
-        let metric_request = MetricRequest {
-            experiment_uid: self.uid.clone(),
-            metrics: vec![Metric {
-                name,
-                value,
-                step,
-                timestamp,
-                created_at,
-                is_eval: false,
-            }],
-        };
-
-        self.experiment_helper
-            .insert_metrics(&metric_request)
-            .map_err(ExperimentError::InsertMetricError)?;
-
-        Ok(())
+        self.insert_metrics(vec![Metric {
+            name,
+            value,
+            step,
+            timestamp,
+            created_at,
+            is_eval: false,
+        }])
     }
 
     pub fn log_metrics(&self, metrics: Vec<Metric>) -> Result<(), ExperimentError> {
-        let metric_request = MetricRequest {
-            experiment_uid: self.uid.clone(),
-            metrics,
-        };
-
-        self.experiment_helper
-            .insert_metrics(&metric_request)
-            .map_err(ExperimentError::InsertMetricError)?;
-
-        Ok(())
+        self.insert_metrics(metrics)
     }
 
     /// Logs evaluation metrics
@@ -596,16 +625,7 @@ impl Experiment {
         name: String,
         value: Bound<'_, PyAny>,
     ) -> Result<(), ExperimentError> {
-        let param_request = ParameterRequest {
-            experiment_uid: self.uid.clone(),
-            parameters: vec![Parameter::new(name, value)?],
-        };
-
-        self.experiment_helper
-            .insert_parameters(&param_request)
-            .map_err(ExperimentError::InsertParameterError)?;
-
-        Ok(())
+        self.insert_parameters(vec![Parameter::new(name, value)?])
     }
 
     /// Logs multiple parameters
@@ -631,16 +651,7 @@ impl Experiment {
             ));
         };
 
-        let param_request = ParameterRequest {
-            experiment_uid: self.uid.clone(),
-            parameters,
-        };
-
-        self.experiment_helper
-            .insert_parameters(&param_request)
-            .map_err(ExperimentError::InsertParameterError)?;
-
-        Ok(())
+        self.insert_parameters(parameters)
     }
 
     /// Logs an artifact from a path
@@ -1026,16 +1037,52 @@ pub fn get_experiment_parameters(
     Ok(Parameters { parameters })
 }
 
+/// Wrap an optional python callable as a transfer progress callback. The callable receives
+/// `(bytes_transferred, total_bytes, files_completed, total_files)`
+fn python_progress(callback: Option<Py<PyAny>>) -> ProgressTracker {
+    let Some(callback) = callback else {
+        return ProgressTracker::default();
+    };
+
+    ProgressTracker::new(Arc::new(move |progress: &TransferProgress| {
+        Python::attach(|py| {
+            let args = (
+                progress.bytes_transferred,
+                progress.total_bytes,
+                progress.files_completed,
+                progress.total_files,
+            );
+            if let Err(e) = callback.call1(py, args) {
+                warn!("Download progress callback failed: {e}");
+            }
+        })
+    }))
+}
+
 /// Download an artifact by name
 /// 1. Query the artifact registry for the artifact by name
 /// 2. Get the filename for the artifacts records
 /// 3. Download the artifact to the specified local path
+///
+/// The GIL is released while downloading so `progress` can be called from transfer threads
 #[pyfunction]
-#[pyo3(signature = (experiment_uid, path, lpath=None))]
+#[pyo3(signature = (experiment_uid, path, lpath=None, progress=None))]
 pub fn download_artifact(
+    py: Python<'_>,
+    experiment_uid: &str,
+    path: PathBuf,
+    lpath: Option<PathBuf>,
+    progress: Option<Py<PyAny>>,
+) -> Result<(), ExperimentError> {
+    let progress = python_progress(progress);
+    py.detach(|| download_experiment_artifact(experiment_uid, path, lpath, &progress))
+}
+
+fn download_experiment_artifact(
     experiment_uid: &str,
     path: PathBuf,
     lpath: Option<PathBuf>,
+    progress: &ProgressTracker,
 ) -> Result<(), ExperimentError> {
     // query card for space, name, version
 
@@ -1073,7 +1120,7 @@ pub fn download_artifact(
 
     let lpath = lpath.join(artifact.name);
     storage_client()?
-        .get(&lpath, &rpath, recursive)
+        .get_with_progress(&lpath, &rpath, recursive, progress)
         .inspect_err(|e| {
             error!("Failed to download artifacts: {e}");
         })?;
@@ -1,6 +1,8 @@
 pub mod error;
 pub mod experiment;
 pub mod hardware_queue;
+pub mod metric_queue;
 
 pub use experiment::*;
 pub use hardware_queue::*;
+pub use metric_queue::*;
@@ -0,0 +1,233 @@
+use crate::error::ExperimentError;
+use opsml_registry::registries::experiment::OpsmlExperiment;
+use opsml_state::app_state;
+use opsml_types::cards::{Metric, Parameter};
+use opsml_types::contracts::{MetricRequest, ParameterRequest};
+use std::sync::Arc;
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+use tokio::time::{self, Duration, MissedTickBehavior};
+use tracing::Instrument;
+use tracing::{debug, error, info_span};
+
+/// Number of buffered metrics and parameters that triggers a flush
+const FLUSH_SIZE: usize = 500;
+
+/// Longest time a logged metric or parameter waits in the buffer
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+enum QueueItem {
+    Metrics(Vec<Metric>),
+    Parameters(Vec<Parameter>),
+}
+
+#[derive(Default)]
+struct MetricBuffer {
+    metrics: Vec<Metric>,
+    parameters: Vec<Parameter>,
+}
+
+impl MetricBuffer {
+    fn push(&mut self, item: QueueItem) {
+        match item {
+            QueueItem::Metrics(metrics) => self.metrics.extend(metrics),
+            QueueItem::Parameters(parameters) => self.parameters.extend(parameters),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.metrics.len() + self.parameters.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.metrics.is_empty() && self.parameters.is_empty()
+    }
+}
+
+/// Writes everything buffered in one bulk request per kind.
+/// The registry helpers are blocking, so the inserts run on the blocking pool
+async fn flush(
+    registry: Arc<OpsmlExperiment>,
+    buffer: &mut MetricBuffer,
+    experiment_uid: &str,
+) -> Result<(), ExperimentError> {
+    if buffer.is_empty() {
+        return Ok(());
+    }
+
+    let metrics = std::mem::take(&mut buffer.metrics);
+    let parameters = std::mem::take(&mut buffer.parameters);
+    let experiment_uid = experiment_uid.to_string();
+
+    debug!(
+        "Flushing {} metrics and {} parameters",
+        metrics.len(),
+        parameters.len()
+    );
+
+    tokio::task::spawn_blocking(move || {
+        let inserted_metrics = match metrics.is_empty() {
+            true => Ok(()),
+            false => registry
+                .insert_metrics(&MetricRequest {
+                    experiment_uid: experiment_uid.clone(),
+                    metrics,
+                })
+                .map_err(ExperimentError::InsertMetricError),
+        };
+
+        let inserted_parameters = match parameters.is_empty() {
+            true => Ok(()),
+            false => registry
+                .insert_parameters(&ParameterRequest {
+                    experiment_uid,
+                    parameters,
+                })
+                .map_err(ExperimentError::InsertParameterError),
+        };
+
+        inserted_metrics.and(inserted_parameters)
+    })
+    .await
+    .map_err(|e| ExperimentError::Error(e.to_string()))?
+}
+
+async fn record_flush(
+    registry: Arc<OpsmlExperiment>,
+    buffer: &mut MetricBuffer,
+    experiment_uid: &str,
+    errors: &mut Vec<String>,
+) {
+    if let Err(e) = flush(registry, buffer, experiment_uid).await {
+        error!("Error flushing metrics: {:?}", e);
+        errors.push(e.to_string());
+    }
+}
+
+fn start_background_task(
+    registry: Arc<OpsmlExperiment>,
+    mut item_rx: mpsc::UnboundedReceiver<QueueItem>,
+    mut stop_rx: watch::Receiver<()>,
+    experiment_uid: String,
+) -> JoinHandle<Vec<String>> {
+    let state = app_state();
+
+    debug!("Starting metric queue");
+
+    let future = async move {
+        let mut buffer = MetricBuffer::default();
+        let mut errors = Vec::new();
+        let mut interval = time::interval(FLUSH_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                item = item_rx.recv() => {
+                    match item {
+                        Some(item) => {
+                            buffer.push(item);
+
+                            if buffer.len() >= FLUSH_SIZE {
+                                record_flush(registry.clone(), &mut buffer, &experiment_uid, &mut errors).await;
+                            }
+                        }
+                        // every sender is gone, so nothing else can be logged
+                        None => break,
+                    }
+                },
+                _ = interval.tick() => {
+                    record_flush(registry.clone(), &mut buffer, &experiment_uid, &mut errors).await;
+                },
+                _ = stop_rx.changed() => {
+                    debug!("Stopping metric queue");
+                    break;
+                }
+            }
+        }
+
+        // pick up anything logged before the stop signal
+        while let Ok(item) = item_rx.try_recv() {
+            buffer.push(item);
+        }
+        record_flush(registry, &mut buffer, &experiment_uid, &mut errors).await;
+
+        errors
+    };
+
+    state
+        .runtime
+        .spawn(future.instrument(info_span!("Metric Queue")))
+}
+
+/// Buffers logged metrics and parameters and writes them in bulk from a background task.
+/// Buffers are flushed every `FLUSH_SIZE` items, every `FLUSH_INTERVAL` and on stop or drop
+pub struct MetricQueue {
+    item_tx: mpsc::UnboundedSender<QueueItem>,
+    stop_tx: watch::Sender<()>,
+    handle: Option<JoinHandle<Vec<String>>>,
+}
+
+impl MetricQueue {
+    pub fn start(registry: Arc<OpsmlExperiment>, experiment_uid: String) -> Self {
+        let (item_tx, item_rx) = mpsc::unbounded_channel();
+        let (stop_tx, stop_rx) = watch::channel(());
+        let handle = start_background_task(registry, item_rx, stop_rx, experiment_uid);
+
+        Self {
+            item_tx,
+            stop_tx,
+            handle: Some(handle),
+        }
+    }
+
+    pub fn log_metrics(&self, metrics: Vec<Metric>) -> Result<(), ExperimentError> {
+        self.item_tx
+            .send(QueueItem::Metrics(metrics))
+            .map_err(|_| ExperimentError::MetricQueueClosedError)
+    }
+
+    pub fn log_parameters(&self, parameters: Vec<Parameter>) -> Result<(), ExperimentError> {
+        self.item_tx
+            .send(QueueItem::Parameters(parameters))
+            .map_err(|_| ExperimentError::MetricQueueClosedError)
+    }
+
+    /// Flushes the remaining buffer and waits for the background task to finish.
+    /// Returns an error if any flush over the lifetime of the queue failed
+    pub fn stop(&mut self) -> Result<(), ExperimentError> {
+        let _ = self.stop_tx.send(());
+
+        let Some(handle) = self.handle.take() else {
+            return Ok(());
+        };
+
+        let errors = app_state()
+            .block_on(handle)
+            .map_err(|e| ExperimentError::Error(e.to_string()))?;
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(ExperimentError::MetricFlushError(errors.join("; "))),
+        }
+    }
+}
+
+impl Drop for MetricQueue {
+    /// Flushes whatever is still buffered when the queue is dropped without being stopped,
+    /// e.g. when an experiment is not closed through its context manager
+    fn drop(&mut self) {
+        if self.handle.is_none() {
+            return;
+        }
+
+        // blocking inside a runtime would panic, so the final flush runs without waiting for it
+        if tokio::runtime::Handle::try_current().is_ok() {
+            let _ = self.stop_tx.send(());
+            return;
+        }
+
+        if let Err(e) = self.stop() {
+            error!("Failed to flush metric queue on drop: {e}");
+        }
+    }
+}
@@ -8,9 +8,10 @@ use serde_json::{Value, json};
 
 #[cfg(feature = "server")]
 use {
-    crate::protocol::{CardQueryArgs, RegistrySpaceRequest},
+    crate::protocol::{CardQueryArgs, CardSearchQuery, LineageQuery, RegistrySpaceRequest},
     opsml_auth::permission::UserPermissions,
     opsml_sql::enums::client::SqlClientEnum,
+    opsml_sql::enums::utils::search_readable_cards,
     opsml_sql::traits::CardLogicTrait,
     opsml_types::cards::CardTable,
     std::sync::Arc,
@@ -155,15 +156,30 @@ For Claude Desktop, set the header in claude_desktop_config.json under the serve
             },
             ToolDef {
                 name: "search_cards",
-                description: "Search cards by name across a registry type.",
+                description: "Ranked full-text search over card names, spaces, tags, READMEs and skill/tool/subagent descriptions. Returns the best matching version of each card with matched terms wrapped in <mark> tags.",
                 input_schema: json!({
                     "type": "object",
                     "properties": {
-                        "registry_type": { "type": "string", "description": "Registry type: model, data, experiment, prompt, or service" },
-                        "name": { "type": "string", "description": "Search term matched against card name" },
-                        "limit": { "type": "integer", "description": "Maximum number of results (default 20)" }
+                        "query": { "type": "string", "description": "Free-text search query" },
+                        "registry_type": { "type": "string", "description": "Restrict results to a registry type, e.g. model, data, skill or tool" },
+                        "space": { "type": "string", "description": "Restrict results to a space" },
+                        "limit": { "type": "integer", "description": "Maximum number of cards (default 20, max 100)" }
+                    },
+                    "required": ["query"]
+                }),
+            },
+            ToolDef {
+                name: "get_lineage",
+                description: "Walk upstream and downstream lineage from a card uid across registries (data, model, experiment, audit, prompt, service) and return a node/edge graph.",
+                input_schema: json!({
+                    "type": "object",
+                    "properties": {
+                        "uid": { "type": "string", "description": "Uid of the card to start from" },
+                        "registry_type": { "type": "string", "description": "Registry of the card. Searched across registries when omitted" },
+                        "depth": { "type": "integer", "description": "Number of hops to walk (default 2, max 10)" },
+                        "direction": { "type": "string", "enum": ["upstream", "downstream", "both"], "description": "Direction to walk (default both)" }
                     },
-                    "required": ["registry_type", "name"]
+                    "required": ["uid"]
                 }),
             },
         ]);
@@ -189,6 +205,8 @@ For Claude Desktop, set the header in claude_desktop_config.json under the serve
             ToolCall::ListSpaces(args) => self.tool_list_spaces(id, args, perms).await,
             #[cfg(feature = "server")]
             ToolCall::SearchCards(args) => self.tool_search_cards(id, args, perms).await,
+            #[cfg(feature = "server")]
+            ToolCall::GetLineage(args) => self.tool_get_lineage(id, args, perms).await,
             ToolCall::Unknown(name) => {
                 JsonRpcResponse::err(id, -32602, format!("Unknown tool: {name}"))
             }
@@ -373,17 +391,17 @@ For Claude Desktop, set the header in claude_desktop_config.json under the serve
     async fn tool_search_cards(
         &self,
         id: Option<Value>,
-        args: CardQueryArgs,
-        _perms: UserPermissions,
+        args: CardSearchQuery,
+        perms: UserPermissions,
     ) -> JsonRpcResponse {
         let sql = match self.sql.as_ref() {
             Some(s) => s,
             None => return JsonRpcResponse::err(id, -32603, "No database connection available"),
         };
-        let table = CardTable::from_registry_type(&args.registry_type);
-        match sql.query_cards(&table, &args).await {
-            Ok(results) => {
-                let text = serde_json::to_string_pretty(&results).unwrap_or_default();
+        let readable_spaces = perms.readable_spaces();
+        match search_readable_cards(sql, &args, readable_spaces.as_deref()).await {
+            Ok(response) => {
+                let text = serde_json::to_string_pretty(&response).unwrap_or_default();
                 JsonRpcResponse::ok(
                     id,
                     ToolCallResult {
@@ -391,7 +409,33 @@ For Claude Desktop, set the header in claude_desktop_config.json under the serve
                     },
                 )
             }
-            Err(e) => JsonRpcResponse::err(id, -32603, format!("Query failed: {e}")),
+            Err(e) => JsonRpcResponse::err(id, -32603, format!("Search failed: {e}")),
+        }
+    }
+
+    #[cfg(feature = "server")]
+    async fn tool_get_lineage(
+        &self,
+        id: Option<Value>,
+        args: LineageQuery,
+        perms: UserPermissions,
+    ) -> JsonRpcResponse {
+        let sql = match self.sql.as_ref() {
+            Some(s) => s,
+            None => return JsonRpcResponse::err(id, -32603, "No database connection available"),
+        };
+        match sql.get_lineage_graph(&args).await {
+            Ok(mut graph) => {
+                graph.retain_nodes(|node| perms.has_read_permission(&node.space));
+                let text = serde_json::to_string_pretty(&graph).unwrap_or_default();
+                JsonRpcResponse::ok(
+                    id,
+                    ToolCallResult {
+                        content: vec![TextContent::text(text)],
+                    },
+                )
+            }
+            Err(e) => JsonRpcResponse::err(id, -32603, format!("Lineage query failed: {e}")),
         }
     }
 }
@@ -3,7 +3,9 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 #[cfg(feature = "server")]
-pub use opsml_types::contracts::{CardQueryArgs, RegistrySpaceRequest};
+pub use opsml_types::contracts::{
+    CardQueryArgs, CardSearchQuery, LineageQuery, RegistrySpaceRequest,
+};
 
 // ---- JSON-RPC 2.0 response ----
 
@@ -131,7 +133,9 @@ pub enum ToolCall {
     #[cfg(feature = "server")]
     ListSpaces(RegistrySpaceRequest),
     #[cfg(feature = "server")]
-    SearchCards(CardQueryArgs),
+    SearchCards(CardSearchQuery),
+    #[cfg(feature = "server")]
+    GetLineage(LineageQuery),
     /// Unknown tool name — handler returns -32602.
     Unknown(String),
     /// Known tool with malformed arguments — handler returns -32602.
@@ -182,12 +186,19 @@ impl From<RawToolCall> for ToolCall {
                     reason: e.to_string(),
                 }),
             #[cfg(feature = "server")]
-            "search_cards" => serde_json::from_value::<CardQueryArgs>(args)
+            "search_cards" => serde_json::from_value::<CardSearchQuery>(args)
                 .map(ToolCall::SearchCards)
                 .unwrap_or_else(|e| ToolCall::InvalidArgs {
                     name: name.clone(),
                     reason: e.to_string(),
                 }),
+            #[cfg(feature = "server")]
+            "get_lineage" => serde_json::from_value::<LineageQuery>(args)
+                .map(ToolCall::GetLineage)
+                .unwrap_or_else(|e| ToolCall::InvalidArgs {
+                    name: name.clone(),
+                    reason: e.to_string(),
+                }),
             _ => ToolCall::Unknown(name),
         }
     }
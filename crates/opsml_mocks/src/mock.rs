@@ -260,9 +260,10 @@ impl OpsmlTestServer {
 
             println!("Starting Opsml Server...");
 
-            // set server env vars
+            // set server env vars. The test server runs with the default secrets, which it
+            // only accepts in an explicit development environment
             unsafe {
-                std::env::set_var("APP_ENV", "dev_server");
+                std::env::set_var("APP_ENV", "development");
             }
 
             if let Some(base_path) = &self.base_path {
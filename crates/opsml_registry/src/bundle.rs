@@ -0,0 +1,966 @@
+//! Signed export/import bundles for promoting cards between OpsML servers.
+//!
+//! A bundle is a zip archive containing:
+//! - `manifest.json` - the exported card records, their lineage references and file digests
+//! - `manifest.sig` - hex encoded HMAC-SHA256 of `manifest.json`, keyed with a shared signing key
+//! - `cards/<uid>/...` - the decrypted artifacts of each card
+//!
+//! Artifacts are stored decrypted because artifact keys are specific to the server that issued
+//! them. On import they are re-encrypted with the key the target server issues for the new card.
+use crate::download::download_card_artifacts;
+use crate::error::RegistryError;
+use crate::registry::CardRegistry;
+use crate::utils::upload_card_artifacts;
+use hmac::{Hmac, Mac};
+use opsml_semver::VersionType;
+use opsml_storage::ProgressTracker;
+use opsml_types::{
+    DriftProfileUri, IntegratedService, RegistryType, SaveName, Suffix,
+    contracts::{CardQueryArgs, CardRecord, DeleteCardRequest},
+};
+use opsml_utils::get_utc_datetime;
+use scouter_client::{DriftType, ProfileRequest};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use tempfile::TempDir;
+use tracing::{debug, error, instrument, warn};
+use walkdir::WalkDir;
+use zip::{CompressionMethod, ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+const MANIFEST_FILE: &str = "manifest.json";
+const SIGNATURE_FILE: &str = "manifest.sig";
+const CARDS_DIR: &str = "cards";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BundleFile {
+    /// Path relative to the card's artifact directory, `/` separated
+    pub path: String,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BundleProfile {
+    /// Path of the profile file relative to the card's artifact directory
+    pub path: String,
+    pub drift_type: DriftType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleCard {
+    pub registry_type: RegistryType,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+    /// Uid of the card on the source server
+    pub uid: String,
+    pub record: CardRecord,
+    /// Uids of other cards in the bundle this card references
+    #[serde(default)]
+    pub references: Vec<String>,
+    pub files: Vec<BundleFile>,
+    #[serde(default)]
+    pub profiles: Vec<BundleProfile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format_version: u32,
+    pub created_at: String,
+    pub opsml_version: String,
+    pub cards: Vec<BundleCard>,
+}
+
+/// A card that already existed on the target server and was not imported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleConflict {
+    pub registry_type: RegistryType,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+    pub existing_uid: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportedCard {
+    pub registry_type: RegistryType,
+    pub space: String,
+    pub name: String,
+    /// Version registered on the target. Differs from `source_version` only when the target
+    /// already had later versions of the card
+    pub version: String,
+    pub source_version: String,
+    pub source_uid: String,
+    pub uid: String,
+}
+
+/// A lineage reference that points at a card neither in the bundle nor on the target server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnresolvedReference {
+    pub card: String,
+    pub field: String,
+    pub uid: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub imported: Vec<ImportedCard>,
+    pub conflicts: Vec<BundleConflict>,
+    pub unresolved_references: Vec<UnresolvedReference>,
+    pub profiles_registered: usize,
+}
+
+fn display_card(card: &BundleCard) -> String {
+    format!(
+        "{} {}/{} v{}",
+        card.registry_type, card.space, card.name, card.version
+    )
+}
+
+fn sign(manifest: &[u8], key: &[u8]) -> Result<String, RegistryError> {
+    let mut mac = HmacSha256::new_from_slice(key)
+        .map_err(|e| RegistryError::InvalidBundle(format!("invalid signing key: {e}")))?;
+    mac.update(manifest);
+    Ok(hex::encode(mac.finalize().into_bytes()))
+}
+
+fn verify_signature(manifest: &[u8], signature: &str, key: &[u8]) -> Result<(), RegistryError> {
+    let signature =
+        hex::decode(signature.trim()).map_err(|_| RegistryError::BundleSignatureMismatch)?;
+    let mut mac = HmacSha256::new_from_slice(key)
+        .map_err(|e| RegistryError::InvalidBundle(format!("invalid signing key: {e}")))?;
+    mac.update(manifest);
+    mac.verify_slice(&signature)
+        .map_err(|_| RegistryError::BundleSignatureMismatch)
+}
+
+/// Read a signing key file. Surrounding whitespace is ignored so keys can be kept in text files
+pub fn read_signing_key(path: &Path) -> Result<Vec<u8>, RegistryError> {
+    let bytes = std::fs::read(path)?;
+    let key = bytes.trim_ascii();
+    if key.is_empty() {
+        return Err(RegistryError::InvalidBundle(format!(
+            "signing key {} is empty",
+            path.display()
+        )));
+    }
+    Ok(key.to_vec())
+}
+
+fn sha256_file(path: &Path) -> Result<String, RegistryError> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Keys of a card record that hold uids of other cards. A bare `uid` is only a reference below
+/// the top level, e.g. the cards bundled into a ServiceCard
+fn is_reference_key(key: &str, top_level: bool) -> bool {
+    key.ends_with("_uid") || key.ends_with("_uids") || (!top_level && key == "uid")
+}
+
+fn collect_references(value: &Value, top_level: bool, refs: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                if is_reference_key(key, top_level) {
+                    match value {
+                        Value::String(uid) => refs.push(uid.clone()),
+                        Value::Array(items) => refs.extend(
+                            items
+                                .iter()
+                                .filter_map(|item| item.as_str().map(str::to_string)),
+                        ),
+                        _ => {}
+                    }
+                } else {
+                    collect_references(value, false, refs);
+                }
+            }
+        }
+        Value::Array(items) => items
+            .iter()
+            .for_each(|item| collect_references(item, false, refs)),
+        _ => {}
+    }
+}
+
+/// Uids referenced by a card record
+fn record_references(record: &CardRecord) -> Result<Vec<String>, RegistryError> {
+    // CardRecord serializes as {"type": ..., "data": {...}}
+    let value = serde_json::to_value(record)?;
+    let mut refs = Vec::new();
+    if let Some(data) = value.get("data") {
+        collect_references(data, true, &mut refs);
+    }
+    Ok(refs)
+}
+
+/// Rewrite the references in a record using `uid_map`.
+///
+/// References missing from the map cannot be resolved on the target: single references are
+/// set to null and list entries are dropped. Each is returned as `(field, uid)`.
+fn remap_references(
+    value: &mut Value,
+    top_level: bool,
+    uid_map: &HashMap<String, String>,
+    unresolved: &mut Vec<(String, String)>,
+) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if !is_reference_key(key, top_level) {
+                    remap_references(value, false, uid_map, unresolved);
+                    continue;
+                }
+                match value {
+                    Value::String(uid) => match uid_map.get(uid.as_str()) {
+                        Some(new_uid) => *uid = new_uid.clone(),
+                        None => {
+                            unresolved.push((key.clone(), uid.clone()));
+                            *value = Value::Null;
+                        }
+                    },
+                    Value::Array(items) => items.retain_mut(|item| {
+                        let Value::String(uid) = item else {
+                            return true;
+                        };
+                        match uid_map.get(uid.as_str()) {
+                            Some(new_uid) => {
+                                *uid = new_uid.clone();
+                                true
+                            }
+                            None => {
+                                unresolved.push((key.clone(), uid.clone()));
+                                false
+                            }
+                        }
+                    }),
+                    _ => {}
+                }
+            }
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| remap_references(item, false, uid_map, unresolved)),
+        _ => {}
+    }
+}
+
+/// Replace every string value equal to a mapped uid. Used for saved card files, where uids are
+/// not confined to known reference fields
+fn replace_uids(value: &mut Value, uid_map: &HashMap<String, String>) {
+    match value {
+        Value::String(s) => {
+            if let Some(new_uid) = uid_map.get(s.as_str()) {
+                *s = new_uid.clone();
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|v| replace_uids(v, uid_map)),
+        Value::Object(map) => map.values_mut().for_each(|v| replace_uids(v, uid_map)),
+        _ => {}
+    }
+}
+
+/// Order cards so every card comes after the bundled cards it references
+fn import_order(cards: &[BundleCard]) -> Result<Vec<usize>, RegistryError> {
+    let index: HashMap<&str, usize> = cards
+        .iter()
+        .enumerate()
+        .map(|(i, card)| (card.uid.as_str(), i))
+        .collect();
+
+    let mut order = Vec::with_capacity(cards.len());
+    let mut placed = vec![false; cards.len()];
+    while order.len() < cards.len() {
+        let before = order.len();
+        for (i, card) in cards.iter().enumerate() {
+            let ready = !placed[i]
+                && card
+                    .references
+                    .iter()
+                    .filter_map(|uid| index.get(uid.as_str()))
+                    .all(|&dep| dep == i || placed[dep]);
+            if ready {
+                placed[i] = true;
+                order.push(i);
+            }
+        }
+        if order.len() == before {
+            return Err(RegistryError::InvalidBundle(
+                "cards in the bundle reference each other in a cycle".to_string(),
+            ));
+        }
+    }
+    Ok(order)
+}
+
+/// Find drift profiles saved with a card by looking for `drift_profile_uri_map` in its Card.json
+fn find_profiles(card_dir: &Path) -> Result<Vec<BundleProfile>, RegistryError> {
+    fn visit(value: &Value, profiles: &mut Vec<DriftProfileUri>) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    if key == "drift_profile_uri_map"
+                        && let Ok(uris) = serde_json::from_value::<HashMap<String, DriftProfileUri>>(
+                            value.clone(),
+                        )
+                    {
+                        profiles.extend(uris.into_values());
+                    } else {
+                        visit(value, profiles);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter().for_each(|item| visit(item, profiles)),
+            _ => {}
+        }
+    }
+
+    let card_json = card_dir.join(SaveName::Card).with_extension(Suffix::Json);
+    if !card_json.exists() {
+        return Ok(Vec::new());
+    }
+    let value: Value = serde_json::from_str(&std::fs::read_to_string(card_json)?)?;
+    let mut uris = Vec::new();
+    visit(&value, &mut uris);
+
+    Ok(uris
+        .into_iter()
+        .filter(|uri| card_dir.join(&uri.uri).is_file())
+        .map(|uri| BundleProfile {
+            path: uri.uri.to_string_lossy().replace('\\', "/"),
+            drift_type: uri.drift_type,
+        })
+        .collect())
+}
+
+fn list_files(dir: &Path) -> Result<Vec<BundleFile>, RegistryError> {
+    let mut files = Vec::new();
+    for entry in WalkDir::new(dir).sort_by_file_name() {
+        let entry = entry.map_err(|e| RegistryError::Error(e.to_string()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(dir)
+            .map_err(|e| RegistryError::Error(e.to_string()))?;
+        files.push(BundleFile {
+            path: relative.to_string_lossy().replace('\\', "/"),
+            sha256: sha256_file(entry.path())?,
+        });
+    }
+    Ok(files)
+}
+
+/// Resolve a card on the current server. Without a version the latest version is used
+fn resolve_card(args: &CardQueryArgs) -> Result<(CardRegistry, CardRecord), RegistryError> {
+    let registry = CardRegistry::rust_new(&args.registry_type)?;
+    let query = CardQueryArgs {
+        limit: Some(1),
+        sort_by_timestamp: Some(true),
+        ..args.clone()
+    };
+    let record = registry
+        .registry
+        .list_cards(&query)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            RegistryError::Error(format!(
+                "{} card {}/{}{} not found",
+                args.registry_type,
+                args.space.as_deref().unwrap_or_default(),
+                args.name.as_deref().unwrap_or_default(),
+                args.version
+                    .as_deref()
+                    .map(|v| format!(" v{v}"))
+                    .unwrap_or_default()
+            ))
+        })?;
+    Ok((registry, record))
+}
+
+/// Package cards from the current server into a signed bundle
+///
+/// # Arguments
+/// * `cards` - Cards to export. Each must identify a card by space/name and optionally version
+/// * `output` - Path of the bundle to write
+/// * `signing_key` - Key used to sign the manifest. The importing side needs the same key
+/// * `include_profiles` - Whether to include the scouter drift profiles saved with each card
+#[instrument(skip_all)]
+pub fn export_bundle(
+    cards: &[CardQueryArgs],
+    output: &Path,
+    signing_key: &[u8],
+    include_profiles: bool,
+) -> Result<BundleManifest, RegistryError> {
+    let staging = TempDir::new()?;
+    let mut bundle_cards: Vec<BundleCard> = Vec::new();
+
+    for args in cards {
+        let (registry, record) = resolve_card(args)?;
+        if bundle_cards.iter().any(|card| card.uid == record.uid()) {
+            continue;
+        }
+
+        let key = registry.registry.get_key(&CardQueryArgs {
+            uid: Some(record.uid().to_string()),
+            registry_type: args.registry_type.clone(),
+            ..Default::default()
+        })?;
+        let card_dir = staging.path().join(record.uid());
+        download_card_artifacts(&key, &card_dir, &ProgressTracker::default())?;
+        debug!("Downloaded artifacts for {}", record.uid());
+
+        let profiles = if include_profiles {
+            find_profiles(&card_dir)?
+        } else {
+            Vec::new()
+        };
+
+        bundle_cards.push(BundleCard {
+            registry_type: args.registry_type.clone(),
+            space: record.space().to_string(),
+            name: record.name().to_string(),
+            version: record.version().to_string(),
+            uid: record.uid().to_string(),
+            files: list_files(&card_dir)?,
+            references: Vec::new(),
+            profiles,
+            record,
+        });
+    }
+
+    // References are only tracked between bundled cards; anything else is resolved on import
+    let bundled: HashSet<String> = bundle_cards.iter().map(|card| card.uid.clone()).collect();
+    for card in bundle_cards.iter_mut() {
+        let mut refs = record_references(&card.record)?;
+        refs.retain(|uid| bundled.contains(uid) && *uid != card.uid);
+        refs.sort();
+        refs.dedup();
+        card.references = refs;
+    }
+
+    let manifest = BundleManifest {
+        format_version: BUNDLE_FORMAT_VERSION,
+        created_at: get_utc_datetime().to_rfc3339(),
+        opsml_version: env!("CARGO_PKG_VERSION").to_string(),
+        cards: bundle_cards,
+    };
+    let manifest_bytes = serde_json::to_vec_pretty(&manifest)?;
+
+    let mut zip = ZipWriter::new(File::create(output)?);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+
+    zip.start_file(MANIFEST_FILE, options)?;
+    zip.write_all(&manifest_bytes)?;
+    zip.start_file(SIGNATURE_FILE, options)?;
+    zip.write_all(sign(&manifest_bytes, signing_key)?.as_bytes())?;
+
+    for card in &manifest.cards {
+        let card_dir = staging.path().join(&card.uid);
+        for file in &card.files {
+            zip.start_file(format!("{CARDS_DIR}/{}/{}", card.uid, file.path), options)?;
+            let mut source = File::open(card_dir.join(&file.path))?;
+            std::io::copy(&mut source, &mut zip)?;
+        }
+    }
+    zip.finish()?;
+
+    Ok(manifest)
+}
+
+fn read_entry(archive: &mut ZipArchive<File>, name: &str) -> Result<Vec<u8>, RegistryError> {
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|_| RegistryError::InvalidBundle(format!("missing {name}")))?;
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Verify the bundle signature and the digest of every card file, extracting the bundle into
+/// `dir`. Nothing is registered until this has succeeded
+fn open_bundle(
+    bundle: &Path,
+    signing_key: &[u8],
+    dir: &Path,
+) -> Result<BundleManifest, RegistryError> {
+    let mut archive = ZipArchive::new(File::open(bundle)?)?;
+
+    let manifest_bytes = read_entry(&mut archive, MANIFEST_FILE)?;
+    let signature = String::from_utf8(read_entry(&mut archive, SIGNATURE_FILE)?)
+        .map_err(|_| RegistryError::BundleSignatureMismatch)?;
+    verify_signature(&manifest_bytes, &signature, signing_key)?;
+
+    let manifest: BundleManifest = serde_json::from_slice(&manifest_bytes)?;
+    if manifest.format_version > BUNDLE_FORMAT_VERSION {
+        return Err(RegistryError::InvalidBundle(format!(
+            "bundle format {} is newer than supported format {BUNDLE_FORMAT_VERSION}",
+            manifest.format_version
+        )));
+    }
+
+    // only the manifest covers card files, so anything it does not list is unsigned and would
+    // otherwise be uploaded with the card
+    let mut expected: HashSet<String> = [MANIFEST_FILE, SIGNATURE_FILE]
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    for card in &manifest.cards {
+        for file in &card.files {
+            expected.insert(format!("{CARDS_DIR}/{}/{}", card.uid, file.path));
+        }
+    }
+    if let Some(unexpected) = archive
+        .file_names()
+        .find(|name| !name.ends_with('/') && !expected.contains(*name))
+    {
+        return Err(RegistryError::InvalidBundle(format!(
+            "{unexpected} is not listed in the manifest"
+        )));
+    }
+
+    // `extract` rejects entries that would escape the target directory
+    archive.extract(dir)?;
+
+    for card in &manifest.cards {
+        let card_dir = dir.join(CARDS_DIR).join(&card.uid);
+        for file in &card.files {
+            let path = card_dir.join(&file.path);
+            if !path.is_file() || sha256_file(&path)? != file.sha256 {
+                return Err(RegistryError::BundleIntegrityError(format!(
+                    "{}/{}",
+                    card.uid, file.path
+                )));
+            }
+        }
+    }
+
+    Ok(manifest)
+}
+
+/// Find a card with the exact space/name/version on the target server
+fn find_existing(
+    registry: &CardRegistry,
+    card: &BundleCard,
+) -> Result<Option<CardRecord>, RegistryError> {
+    let records = registry.registry.list_cards(&CardQueryArgs {
+        space: Some(card.space.clone()),
+        name: Some(card.name.clone()),
+        version: Some(card.version.clone()),
+        registry_type: card.registry_type.clone(),
+        ..Default::default()
+    })?;
+    Ok(records
+        .into_iter()
+        .find(|record| record.version() == card.version))
+}
+
+/// Rewrite uids in the card's saved json files (the card itself and its drift profiles)
+fn rewrite_card_files(
+    card_dir: &Path,
+    card: &BundleCard,
+    uid_map: &HashMap<String, String>,
+) -> Result<(), RegistryError> {
+    let card_json = card_dir.join(SaveName::Card).with_extension(Suffix::Json);
+    let files = std::iter::once(card_json).chain(
+        card.profiles
+            .iter()
+            .map(|profile| card_dir.join(&profile.path)),
+    );
+
+    for path in files {
+        if !path.is_file() {
+            continue;
+        }
+        let mut value: Value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+        replace_uids(&mut value, uid_map);
+        std::fs::write(&path, serde_json::to_string(&value)?)?;
+    }
+    Ok(())
+}
+
+fn register_card(
+    registry: &CardRegistry,
+    card: &BundleCard,
+    card_dir: &Path,
+    uid_map: &mut HashMap<String, String>,
+    report: &mut ImportReport,
+) -> Result<(), RegistryError> {
+    let mut value = serde_json::to_value(&card.record)?;
+    let mut unresolved = Vec::new();
+    if let Some(data) = value.get_mut("data") {
+        remap_references(data, true, uid_map, &mut unresolved);
+    }
+    report
+        .unresolved_references
+        .extend(
+            unresolved
+                .into_iter()
+                .map(|(field, uid)| UnresolvedReference {
+                    card: display_card(card),
+                    field,
+                    uid,
+                }),
+        );
+    let record: CardRecord = serde_json::from_value(value)?;
+
+    let response = registry.registry.create_card(
+        record,
+        Some(card.version.clone()),
+        VersionType::Minor,
+        None,
+        None,
+    )?;
+    let new_uid = response.key.uid.clone();
+    uid_map.insert(card.uid.clone(), new_uid.clone());
+
+    if response.deduplicated {
+        // Identical content already exists on the target (SkillCards); reuse it
+        report.conflicts.push(BundleConflict {
+            registry_type: card.registry_type.clone(),
+            space: response.space,
+            name: response.name,
+            version: response.version,
+            existing_uid: new_uid,
+        });
+        return Ok(());
+    }
+
+    let upload = rewrite_card_files(card_dir, card, uid_map).and_then(|_| {
+        upload_card_artifacts(&registry.registry, card_dir.to_path_buf(), &response.key)
+    });
+    if let Err(e) = upload {
+        error!("Failed to upload artifacts for {}: {e}", display_card(card));
+        if let Err(delete_err) = registry.registry.delete_card(DeleteCardRequest {
+            uid: new_uid,
+            space: response.space,
+            registry_type: card.registry_type.clone(),
+        }) {
+            error!("Failed to roll back {}: {delete_err}", display_card(card));
+        }
+        return Err(e);
+    }
+
+    report.imported.push(ImportedCard {
+        registry_type: card.registry_type.clone(),
+        space: response.space,
+        name: response.name,
+        version: response.version,
+        source_version: card.version.clone(),
+        source_uid: card.uid.clone(),
+        uid: new_uid,
+    });
+    Ok(())
+}
+
+/// Register the drift profiles of an imported card. Profiles are registered inactive so an
+/// import never changes which profile is monitoring a live service
+fn register_profiles(
+    registry: &CardRegistry,
+    card: &BundleCard,
+    card_dir: &Path,
+) -> Result<usize, RegistryError> {
+    let mut registered = 0;
+    for profile in &card.profiles {
+        let request = ProfileRequest {
+            space: card.space.clone(),
+            drift_type: profile.drift_type.clone(),
+            profile: std::fs::read_to_string(card_dir.join(&profile.path))?,
+            version_request: None,
+            active: false,
+            deactivate_others: false,
+        };
+        registry.registry.insert_scouter_profile(&request)?;
+        registered += 1;
+    }
+    Ok(registered)
+}
+
+/// Import a signed bundle into the current server
+///
+/// Cards are registered in dependency order with their original space, name and version, and
+/// lineage references are rewritten to the uids issued by this server. A card whose
+/// space/name/version already exists is reported as a conflict and left untouched; references
+/// to it resolve to the existing card.
+///
+/// # Arguments
+/// * `bundle` - Path of the bundle
+/// * `signing_key` - Key the bundle was signed with
+/// * `fail_on_conflict` - Abort before registering anything if any card already exists
+/// * `import_profiles` - Whether to register bundled drift profiles with scouter
+#[instrument(skip_all)]
+pub fn import_bundle(
+    bundle: &Path,
+    signing_key: &[u8],
+    fail_on_conflict: bool,
+    import_profiles: bool,
+) -> Result<ImportReport, RegistryError> {
+    let workdir = TempDir::new()?;
+    let manifest = open_bundle(bundle, signing_key, workdir.path())?;
+    let order = import_order(&manifest.cards)?;
+
+    let mut registries: HashMap<String, CardRegistry> = HashMap::new();
+    let mut report = ImportReport::default();
+    let mut uid_map: HashMap<String, String> = HashMap::new();
+
+    // Check for conflicts up front so `fail_on_conflict` can abort before any writes
+    for card in &manifest.cards {
+        let registry_key = card.registry_type.to_string();
+        if !registries.contains_key(&registry_key) {
+            registries.insert(
+                registry_key.clone(),
+                CardRegistry::rust_new(&card.registry_type)?,
+            );
+        }
+        if let Some(existing) = find_existing(&registries[&registry_key], card)? {
+            uid_map.insert(card.uid.clone(), existing.uid().to_string());
+            report.conflicts.push(BundleConflict {
+                registry_type: card.registry_type.clone(),
+                space: card.space.clone(),
+                name: card.name.clone(),
+                version: card.version.clone(),
+                existing_uid: existing.uid().to_string(),
+            });
+        }
+    }
+
+    if fail_on_conflict && !report.conflicts.is_empty() {
+        let conflicts = report
+            .conflicts
+            .iter()
+            .map(|c| format!("{} {}/{} v{}", c.registry_type, c.space, c.name, c.version))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(RegistryError::BundleConflict(conflicts));
+    }
+
+    let conflicted: HashSet<String> = uid_map.keys().cloned().collect();
+    let mut scouter_available = None;
+
+    for i in order {
+        let card = &manifest.cards[i];
+        if conflicted.contains(&card.uid) {
+            continue;
+        }
+        let registry = &registries[&card.registry_type.to_string()];
+        let card_dir = workdir.path().join(CARDS_DIR).join(&card.uid);
+        register_card(registry, card, &card_dir, &mut uid_map, &mut report)?;
+
+        if !import_profiles || card.profiles.is_empty() {
+            continue;
+        }
+        let available = *scouter_available.get_or_insert_with(|| {
+            registry
+                .registry
+                .check_service_health(IntegratedService::Scouter)
+                .unwrap_or(false)
+        });
+        if available {
+            report.profiles_registered += register_profiles(registry, card, &card_dir)?;
+        } else {
+            warn!(
+                "Scouter is not available; skipping drift profiles for {}",
+                display_card(card)
+            );
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opsml_types::contracts::ModelCardClientRecord;
+    use serde_json::json;
+
+    fn bundle_card(uid: &str, references: &[&str]) -> BundleCard {
+        BundleCard {
+            registry_type: RegistryType::Model,
+            space: "space".to_string(),
+            name: uid.to_string(),
+            version: "1.0.0".to_string(),
+            uid: uid.to_string(),
+            record: CardRecord::Model(ModelCardClientRecord {
+                uid: uid.to_string(),
+                ..Default::default()
+            }),
+            references: references.iter().map(|s| s.to_string()).collect(),
+            files: Vec::new(),
+            profiles: Vec::new(),
+        }
+    }
+
+    fn write_bundle(path: &Path, manifest: &BundleManifest, entries: &[(&str, &[u8])]) {
+        let manifest_bytes = serde_json::to_vec(manifest).unwrap();
+        let mut zip = ZipWriter::new(File::create(path).unwrap());
+        let options = SimpleFileOptions::default();
+
+        zip.start_file(MANIFEST_FILE, options).unwrap();
+        zip.write_all(&manifest_bytes).unwrap();
+        zip.start_file(SIGNATURE_FILE, options).unwrap();
+        zip.write_all(sign(&manifest_bytes, b"secret").unwrap().as_bytes())
+            .unwrap();
+        for (name, contents) in entries {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(contents).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_open_bundle_rejects_unlisted_entries() {
+        let dir = TempDir::new().unwrap();
+        let contents = b"weights";
+
+        let mut card = bundle_card("a", &[]);
+        card.files.push(BundleFile {
+            path: "model.bin".to_string(),
+            sha256: hex::encode(Sha256::digest(contents)),
+        });
+        let manifest = BundleManifest {
+            format_version: BUNDLE_FORMAT_VERSION,
+            created_at: get_utc_datetime().to_rfc3339(),
+            opsml_version: env!("CARGO_PKG_VERSION").to_string(),
+            cards: vec![card],
+        };
+
+        let bundle = dir.path().join("bundle.zip");
+        write_bundle(&bundle, &manifest, &[("cards/a/model.bin", contents)]);
+        assert!(open_bundle(&bundle, b"secret", &dir.path().join("ok")).is_ok());
+
+        // an entry added without re-signing is rejected before anything is extracted
+        write_bundle(
+            &bundle,
+            &manifest,
+            &[
+                ("cards/a/model.bin", contents),
+                ("cards/a/extra.py", b"import os"),
+            ],
+        );
+        let extract_dir = dir.path().join("tampered");
+        let result = open_bundle(&bundle, b"secret", &extract_dir);
+        assert!(matches!(result, Err(RegistryError::InvalidBundle(_))));
+        assert!(
+            !extract_dir
+                .join(CARDS_DIR)
+                .join("a")
+                .join("extra.py")
+                .exists()
+        );
+    }
+
+    #[test]
+    fn test_signature_round_trip() {
+        let manifest = br#"{"cards": []}"#;
+        let signature = sign(manifest, b"secret").unwrap();
+
+        assert!(verify_signature(manifest, &signature, b"secret").is_ok());
+        assert!(verify_signature(manifest, &signature, b"other").is_err());
+        assert!(verify_signature(br#"{"cards": [1]}"#, &signature, b"secret").is_err());
+        assert!(verify_signature(manifest, "not-hex", b"secret").is_err());
+    }
+
+    #[test]
+    fn test_collect_references_skips_own_uid() {
+        let record = json!({
+            "uid": "self",
+            "datacard_uid": "data",
+            "experimentcard_uids": ["exp1", "exp2"],
+            "auditcard_uid": null,
+            "cards": [{"uid": "nested", "alias": "model"}]
+        });
+        let mut refs = Vec::new();
+        collect_references(&record, true, &mut refs);
+        refs.sort();
+
+        assert_eq!(refs, vec!["data", "exp1", "exp2", "nested"]);
+    }
+
+    #[test]
+    fn test_remap_references() {
+        let mut record = json!({
+            "uid": "self",
+            "datacard_uid": "data",
+            "auditcard_uid": "missing",
+            "experimentcard_uids": ["exp", "gone"],
+            "cards": [{"uid": "data", "alias": "d"}]
+        });
+        let uid_map = HashMap::from([
+            ("data".to_string(), "new-data".to_string()),
+            ("exp".to_string(), "new-exp".to_string()),
+        ]);
+        let mut unresolved = Vec::new();
+        remap_references(&mut record, true, &uid_map, &mut unresolved);
+
+        assert_eq!(
+            record,
+            json!({
+                "uid": "self",
+                "datacard_uid": "new-data",
+                "auditcard_uid": null,
+                "experimentcard_uids": ["new-exp"],
+                "cards": [{"uid": "new-data", "alias": "d"}]
+            })
+        );
+        unresolved.sort();
+        assert_eq!(
+            unresolved,
+            vec![
+                ("auditcard_uid".to_string(), "missing".to_string()),
+                ("experimentcard_uids".to_string(), "gone".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replace_uids() {
+        let mut card =
+            json!({"uid": "old", "metadata": {"experimentcard_uid": "exp", "name": "old-name"}});
+        let uid_map = HashMap::from([
+            ("old".to_string(), "new".to_string()),
+            ("exp".to_string(), "new-exp".to_string()),
+        ]);
+        replace_uids(&mut card, &uid_map);
+
+        assert_eq!(
+            card,
+            json!({"uid": "new", "metadata": {"experimentcard_uid": "new-exp", "name": "old-name"}})
+        );
+    }
+
+    #[test]
+    fn test_import_order() {
+        let cards = vec![
+            bundle_card("service", &["model"]),
+            bundle_card("model", &["data", "outside"]),
+            bundle_card("data", &[]),
+        ];
+        let order = import_order(&cards).unwrap();
+        let uids: Vec<&str> = order.iter().map(|&i| cards[i].uid.as_str()).collect();
+
+        assert_eq!(uids, vec!["data", "model", "service"]);
+
+        let cycle = vec![bundle_card("a", &["b"]), bundle_card("b", &["a"])];
+        assert!(import_order(&cycle).is_err());
+    }
+}
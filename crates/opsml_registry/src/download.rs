@@ -6,7 +6,10 @@ use opsml_cards::PromptCard;
 use opsml_cards::ServiceCard;
 use opsml_colors::Colorize;
 use opsml_crypt::decrypt_directory;
-use opsml_storage::{async_storage_client, storage_client};
+use opsml_storage::{
+    ArtifactManifest, ManifestIssue, ProgressTracker, async_storage_client, console_progress,
+    storage_client, verify_manifest,
+};
 use opsml_types::{
     RegistryType, SaveName, Suffix,
     cards::ServiceCardMapping,
@@ -18,17 +21,23 @@ use pyo3::prelude::*;
 use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
+use tempfile::TempDir;
 use tracing::{debug, error, instrument};
 /// Download all artifacts of a card
 ///
 /// # Arguments
 /// * `key` - ArtifactKey
 /// * `write_dir` - str
+/// * `progress` - ProgressTracker notified as files are downloaded
 ///
 /// # Returns
 ///
 /// Result<(), CliError>
-fn download_card_artifacts(key: &ArtifactKey, lpath: &Path) -> Result<(), RegistryError> {
+pub(crate) fn download_card_artifacts(
+    key: &ArtifactKey,
+    lpath: &Path,
+    progress: &ProgressTracker,
+) -> Result<(), RegistryError> {
     // get registry
     let decryption_key = key
         .get_crypt_key()
@@ -40,7 +49,7 @@ fn download_card_artifacts(key: &ArtifactKey, lpath: &Path) -> Result<(), Regist
     }
     // download card artifacts
     storage_client()?
-        .get(lpath, &rpath, true)
+        .get_with_progress(lpath, &rpath, true, progress)
         .inspect_err(|e| error!("Failed to download card artifacts: {:?}", e))?;
 
     decrypt_directory(lpath, &decryption_key).inspect_err(|e| {
@@ -50,9 +59,80 @@ fn download_card_artifacts(key: &ArtifactKey, lpath: &Path) -> Result<(), Regist
         );
     })?;
 
+    verify_manifest(lpath, key.manifest_sha256.as_deref())
+        .inspect_err(|e| error!("Card artifacts at {:?} failed verification: {}", lpath, e))?;
+
     Ok(())
 }
 
+/// Outcome of checking a stored card against its artifact manifest
+#[derive(Debug)]
+pub struct CardVerification {
+    /// Card as `{space}/{name}/v{version}`
+    pub card: String,
+    /// Number of files listed in the manifest. `None` if the card was saved without one
+    pub files: Option<usize>,
+    pub issues: Vec<ManifestIssue>,
+}
+
+impl CardVerification {
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Label a card as `{space}/{name}/v{version}` from its storage key, which is laid out as
+/// `{table}/{space}/{name}/v{version}`. Keys in any other shape fall back to `{space}/{uid}`
+fn card_label(key: &ArtifactKey) -> String {
+    match key.storage_key.split('/').collect::<Vec<_>>().as_slice() {
+        [_, space, name, version] => format!("{space}/{name}/{version}"),
+        _ => format!("{}/{}", key.space, key.uid),
+    }
+}
+
+/// Download a card into a temporary directory and check every file against its manifest.
+///
+/// Unlike a regular download, which fails on the first bad file, all missing and corrupted
+/// files are collected so they can be reported together
+///
+/// # Arguments
+/// * `args` - CardQueryArgs
+///
+/// # Errors
+/// RegistryError
+pub fn verify_card_artifacts(args: &CardQueryArgs) -> Result<CardVerification, RegistryError> {
+    let registry = OpsmlCardRegistry::new(args.registry_type.clone())?;
+    let key = registry.get_key(args)?;
+
+    let card = card_label(&key);
+
+    let tmp_dir = TempDir::new()?;
+    storage_client()?.get(tmp_dir.path(), &key.storage_path(), true)?;
+    decrypt_directory(tmp_dir.path(), &key.get_crypt_key()?)?;
+
+    // a manifest that is missing or was replaced is reported before the files it lists
+    let mut issues = match key.manifest_sha256.as_deref() {
+        Some(expected) => ArtifactManifest::check_digest(tmp_dir.path(), expected)?
+            .into_iter()
+            .collect(),
+        None => vec![],
+    };
+
+    let files = match ArtifactManifest::load(tmp_dir.path())? {
+        Some(manifest) => {
+            issues.extend(manifest.check(tmp_dir.path())?);
+            Some(manifest.files.len())
+        }
+        None => None,
+    };
+
+    Ok(CardVerification {
+        card,
+        files,
+        issues,
+    })
+}
+
 /// Download all artifacts of a card
 ///
 /// # Arguments
@@ -93,7 +173,8 @@ pub fn download_card_from_registry(
         Colorize::green(write_path.to_str().unwrap())
     );
 
-    download_card_artifacts(&key, &write_path)?;
+    let progress = ProgressTracker::new(console_progress("Downloading"));
+    download_card_artifacts(&key, &write_path, &progress)?;
 
     Ok(())
 }
@@ -156,7 +237,7 @@ pub fn download_service_sub_cards(
                 .join(&card.alias);
 
             // Download card artifacts
-            download_card_artifacts(&key, &card_path)?;
+            download_card_artifacts(&key, &card_path, &ProgressTracker::default())?;
             mapping.add_card_path(&card.alias, &card_path);
 
             // If modelcard or promptcard, load and process drift paths
@@ -192,7 +273,7 @@ pub fn download_service_from_registry(
     }
 
     // download service card card
-    download_card_artifacts(&key, write_path)
+    download_card_artifacts(&key, write_path, &ProgressTracker::default())
         .inspect_err(|e| error!("Failed to download card artifacts: {:?}", e))?;
 
     // read Card.json file
@@ -329,6 +410,7 @@ async fn async_download_card_artifacts(
         .await?;
 
     decrypt_directory(lpath, &decryption_key)?;
+    verify_manifest(lpath, key.manifest_sha256.as_deref())?;
 
     Ok(())
 }
@@ -155,6 +155,23 @@ pub enum RegistryError {
 
     #[error("Invalid registry type for drift profiles: {0}")]
     InvalidRegistryType(String),
+
+    #[error(transparent)]
+    ZipError(#[from] zip::result::ZipError),
+
+    #[error("Invalid bundle: {0}")]
+    InvalidBundle(String),
+
+    #[error(
+        "Bundle signature does not match. The bundle was modified or signed with a different key"
+    )]
+    BundleSignatureMismatch,
+
+    #[error("Bundle file {0} does not match its manifest digest")]
+    BundleIntegrityError(String),
+
+    #[error("Cards already exist on this server: {0}")]
+    BundleConflict(String),
 }
 
 #[cfg(feature = "python")]
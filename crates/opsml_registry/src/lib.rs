@@ -1,3 +1,4 @@
+pub mod bundle;
 pub mod download;
 pub mod error;
 pub mod registries;
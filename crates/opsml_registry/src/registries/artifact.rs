@@ -45,7 +45,8 @@ impl OpsmlArtifactRegistry {
 
                     // TODO (steven): Why clone config when we could use app state directly in server registry?
                     let server_registry = state.block_on(async {
-                        ServerArtifactRegistry::new(settings, db_settings).await
+                        ServerArtifactRegistry::new(settings, db_settings, config.app_env.clone())
+                            .await
                     })?;
 
                     Ok(Self::Server(server_registry))
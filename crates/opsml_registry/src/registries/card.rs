@@ -9,6 +9,10 @@ use opsml_settings::config::OpsmlMode;
 use opsml_state::{app_state, get_api_client};
 use opsml_types::contracts::{ArtifactKey, CardArgs, DeleteCardRequest};
 use opsml_types::contracts::{CardQueryArgs, CardRecord, CreateCardResponse};
+use opsml_types::contracts::{CardSearchQuery, CardSearchResponse};
+use opsml_types::contracts::{ConsistencyReport, ConsistencyScanRequest};
+use opsml_types::contracts::{RotateArtifactKeysRequest, RotateArtifactKeysResponse};
+use opsml_types::contracts::{WorkflowRun, WorkflowRunRequest};
 use opsml_types::*;
 use scouter_client::{
     ProfileRequest, ProfileStatusRequest, RegisteredProfileResponse, ScouterClient,
@@ -72,6 +76,8 @@ impl OpsmlCardRegistry {
                             settings,
                             db_settings,
                             scouter_client,
+                            config.auth_settings.username.clone(),
+                            config.app_env.clone(),
                         )
                         .await
                     })?;
@@ -159,6 +165,26 @@ impl OpsmlCardRegistry {
         }
     }
 
+    /// Record the digest of the manifest uploaded with a card's artifacts
+    pub fn set_artifact_manifest(
+        &self,
+        uid: &str,
+        registry_type: &RegistryType,
+        manifest_sha256: &str,
+    ) -> Result<(), RegistryError> {
+        match self {
+            Self::Client(client_registry) => {
+                client_registry.set_artifact_manifest(uid, registry_type, manifest_sha256)
+            }
+            #[cfg(feature = "server")]
+            Self::Server(server_registry) => app_state().block_on(async {
+                server_registry
+                    .set_artifact_manifest(uid, registry_type, manifest_sha256)
+                    .await
+            }),
+        }
+    }
+
     #[instrument(skip_all)]
     pub fn get_key(&self, args: &CardQueryArgs) -> Result<ArtifactKey, RegistryError> {
         match self {
@@ -181,6 +207,8 @@ impl OpsmlCardRegistry {
                         registry_type: key.registry_type,
                         encrypted_key: key.encrypted_key,
                         storage_key: key.storage_key,
+                        key_version: key.key_version,
+                        manifest_sha256: key.manifest_sha256,
                     })
                 })
             }
@@ -283,4 +311,56 @@ impl OpsmlCardRegistry {
             }),
         }
     }
+
+    /// Re-wrap all stored artifact keys with the current key-encryption-key
+    pub fn rotate_artifact_keys(
+        &self,
+        request: &RotateArtifactKeysRequest,
+    ) -> Result<RotateArtifactKeysResponse, RegistryError> {
+        match self {
+            Self::Client(client_registry) => client_registry.rotate_artifact_keys(request),
+            #[cfg(feature = "server")]
+            Self::Server(server_registry) => {
+                app_state().block_on(async { server_registry.rotate_artifact_keys(request).await })
+            }
+        }
+    }
+
+    /// Cross-reference server storage with artifact keys and card tables
+    pub fn scan_storage(
+        &self,
+        request: &ConsistencyScanRequest,
+    ) -> Result<ConsistencyReport, RegistryError> {
+        match self {
+            Self::Client(client_registry) => client_registry.scan_storage(request),
+            #[cfg(feature = "server")]
+            Self::Server(_) => Err(RegistryError::Error(
+                "Scanning storage requires an OpsML server; set OPSML_TRACKING_URI".to_string(),
+            )),
+        }
+    }
+
+    /// Run a workflow on the OpsML server and return the per-step trace
+    pub fn run_workflow(&self, request: &WorkflowRunRequest) -> Result<WorkflowRun, RegistryError> {
+        match self {
+            Self::Client(client_registry) => client_registry.run_workflow(request),
+            #[cfg(feature = "server")]
+            Self::Server(_) => Err(RegistryError::Error(
+                "Running a workflow requires an OpsML server; set OPSML_TRACKING_URI".to_string(),
+            )),
+        }
+    }
+    /// Ranked full-text search over card names, tags, READMEs and descriptions
+    pub fn search_cards(
+        &self,
+        query: &CardSearchQuery,
+    ) -> Result<CardSearchResponse, RegistryError> {
+        match self {
+            Self::Client(client_registry) => client_registry.search_cards(query),
+            #[cfg(feature = "server")]
+            Self::Server(server_registry) => {
+                app_state().block_on(async { server_registry.search_cards(query).await })
+            }
+        }
+    }
 }
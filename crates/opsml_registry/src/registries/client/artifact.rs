@@ -143,6 +143,40 @@ pub trait ArtifactExt: Registry {
 
         Ok(key)
     }
+
+    /// Record the digest of the manifest uploaded with a card's artifacts
+    fn set_artifact_manifest(
+        &self,
+        uid: &str,
+        registry_type: &RegistryType,
+        manifest_sha256: &str,
+    ) -> Result<(), RegistryError> {
+        let body = serde_json::to_value(ArtifactManifestRequest {
+            uid: uid.to_string(),
+            registry_type: registry_type.clone(),
+            manifest_sha256: manifest_sha256.to_string(),
+        })?;
+
+        let response = self
+            .client()
+            .request(
+                Routes::ArtifactManifest,
+                RequestType::Put,
+                Some(body),
+                None,
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to set artifact manifest {}", e);
+            })?;
+
+        if response.status() != 200 {
+            let error_text = response.text().map_err(RegistryError::RequestError)?;
+            return Err(ApiClientError::ServerError(error_text).into());
+        }
+
+        Ok(())
+    }
 }
 
 impl ArtifactExt for ClientArtifactRegistry {}
@@ -293,6 +293,123 @@ pub trait CardRegistry: Registry {
 
         Ok(hash_response.card)
     }
+
+    #[instrument(skip_all)]
+    fn rotate_artifact_keys(
+        &self,
+        request: &RotateArtifactKeysRequest,
+    ) -> Result<RotateArtifactKeysResponse, RegistryError> {
+        let body = serde_json::to_value(request)?;
+
+        let response = self
+            .client()
+            .request(
+                Routes::AdminRotateKeys,
+                RequestType::Post,
+                Some(body),
+                None,
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to rotate artifact keys {}", e);
+            })?;
+
+        if response.status() == 403 {
+            let error = response
+                .json::<ErrorResponse>()
+                .map_err(RegistryError::RequestError)?;
+
+            return Err(ApiClientError::ForbiddenError(error.error).into());
+        }
+
+        Ok(response.json::<RotateArtifactKeysResponse>()?)
+    }
+
+    #[instrument(skip_all)]
+    fn scan_storage(
+        &self,
+        request: &ConsistencyScanRequest,
+    ) -> Result<ConsistencyReport, RegistryError> {
+        let body = serde_json::to_value(request)?;
+
+        let response = self
+            .client()
+            .request(
+                Routes::AdminStorageScan,
+                RequestType::Post,
+                Some(body),
+                None,
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to scan storage {}", e);
+            })?;
+
+        if response.status() == 403 {
+            let error = response
+                .json::<ErrorResponse>()
+                .map_err(RegistryError::RequestError)?;
+
+            return Err(ApiClientError::ForbiddenError(error.error).into());
+        }
+
+        Ok(response.json::<ConsistencyReport>()?)
+    }
+
+    #[instrument(skip_all)]
+    fn run_workflow(&self, request: &WorkflowRunRequest) -> Result<WorkflowRun, RegistryError> {
+        let body = serde_json::to_value(request)?;
+
+        let response = self
+            .client()
+            .request(
+                Routes::WorkflowRun,
+                RequestType::Post,
+                Some(body),
+                None,
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to run workflow {}", e);
+            })?;
+
+        if response.status() == 403 {
+            let error = response
+                .json::<ErrorResponse>()
+                .map_err(RegistryError::RequestError)?;
+
+            return Err(ApiClientError::ForbiddenError(error.error).into());
+        }
+
+        // Invalid specs, bad inputs and unknown cards are rejected before any step runs
+        if !response.status().is_success() {
+            let error = response
+                .json::<ErrorResponse>()
+                .map_err(RegistryError::RequestError)?;
+
+            return Err(RegistryError::Error(error.error));
+        }
+
+        Ok(response.json::<WorkflowRun>()?)
+    }
+    #[instrument(skip_all)]
+    fn search_cards(&self, query: &CardSearchQuery) -> Result<CardSearchResponse, RegistryError> {
+        let query_string = serde_qs::to_string(query)?;
+        let response = self
+            .client()
+            .request(
+                Routes::CardSearch,
+                RequestType::Get,
+                None,
+                Some(query_string),
+                None,
+            )
+            .inspect_err(|e| {
+                error!("Failed to search cards {}", e);
+            })?;
+
+        Ok(response.json::<CardSearchResponse>()?)
+    }
 }
 
 pub trait ScouterRegistry: Registry {
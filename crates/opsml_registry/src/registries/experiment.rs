@@ -47,8 +47,10 @@ impl OpsmlExperiment {
                     let db_settings = config.database_settings.clone();
 
                     // TODO (steven): Why clone config when we could use app state directly in server registry?
-                    let server_registry = state
-                        .block_on(async { ServerExperiment::new(settings, db_settings).await })?;
+                    let app_env = config.app_env.clone();
+                    let server_registry = state.block_on(async {
+                        ServerExperiment::new(settings, db_settings, app_env).await
+                    })?;
 
                     Ok(Self::Server(server_registry))
                 }
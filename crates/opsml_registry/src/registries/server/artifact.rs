@@ -1,4 +1,5 @@
 use crate::error::RegistryError;
+use opsml_crypt::KeyProvider;
 use opsml_settings::DatabaseSettings;
 use opsml_settings::config::OpsmlStorageSettings;
 use opsml_sql::enums::client::get_sql_client;
@@ -11,6 +12,9 @@ pub struct ServerArtifactRegistry {
     sql_client: Arc<SqlClientEnum>,
     pub table_name: CardTable,
     pub storage_settings: OpsmlStorageSettings,
+    /// Environment recorded on logged artifacts
+    app_env: String,
+    key_provider: Arc<dyn KeyProvider>,
 }
 
 impl ServerArtifactRegistry {
@@ -24,14 +28,18 @@ impl ServerArtifactRegistry {
     pub async fn new(
         storage_settings: OpsmlStorageSettings,
         database_settings: DatabaseSettings,
+        app_env: String,
     ) -> Result<Self, RegistryError> {
         let sql_client = Arc::new(get_sql_client(&database_settings).await?);
         let table_name = CardTable::from_registry_type(&RegistryType::Artifact);
+        let key_provider = storage_settings.key_provider()?;
 
         Ok(Self {
             sql_client,
             table_name,
             storage_settings,
+            app_env,
+            key_provider,
         })
     }
 
@@ -45,7 +53,7 @@ impl ServerArtifactRegistry {
             .get_artifact_key(uid, &registry_type.to_string())
             .await?;
 
-        Ok(key)
+        Ok(key.open(&*self.key_provider)?)
     }
 
     pub async fn log_artifact(
@@ -72,8 +80,14 @@ impl ServerArtifactRegistry {
         )
         .await?;
 
-        let artifact_record =
-            ArtifactSqlRecord::new(space, name, version, media_type, artifact_type.to_string());
+        let artifact_record = ArtifactSqlRecord::new(
+            space,
+            name,
+            version,
+            media_type,
+            artifact_type.to_string(),
+            self.app_env.clone(),
+        );
 
         self.sql_client
             .insert_artifact_record(&artifact_record)
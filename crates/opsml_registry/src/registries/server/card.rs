@@ -1,24 +1,26 @@
 use crate::error::RegistryError;
-use opsml_crypt::{derive_encryption_key, encrypted_key, generate_salt};
-use opsml_semver::{VersionArgs, VersionType, VersionValidator, error::VersionError};
+use opsml_crypt::{KeyProvider, derive_encryption_key, generate_salt};
+use opsml_semver::{VersionType, error::VersionError};
 use opsml_settings::DatabaseSettings;
 use opsml_settings::OpsmlStorageSettings;
 use opsml_sql::enums::client::SqlClientEnum;
 use opsml_sql::enums::client::get_sql_client;
+use opsml_sql::enums::utils::{
+    get_next_version, rewrap_artifact_keys, search_readable_cards, trash_card,
+};
 use opsml_sql::schemas::*;
 use opsml_sql::traits::ArtifactLogicTrait;
 use opsml_sql::traits::CardLogicTrait;
-use opsml_storage::StorageClientEnum;
+use opsml_sql::traits::SearchLogicTrait;
 use opsml_types::IntegratedService;
 use opsml_types::RegistryMode;
 use opsml_types::{RegistryType, cards::CardTable, contracts::*};
-use opsml_utils::uid_to_byte_key;
 use scouter_client::RegisteredProfileResponse;
 use scouter_client::{ProfileRequest, ProfileStatusRequest, ScouterClient};
 use semver::Version;
 use sqlx::types::Json as SqlxJson;
 use std::sync::Arc;
-use tracing::{error, info, instrument};
+use tracing::{error, instrument};
 
 #[derive(Debug, Clone)]
 pub struct ServerCardRegistry {
@@ -27,6 +29,11 @@ pub struct ServerCardRegistry {
     pub registry_type: RegistryType,
     pub table_name: CardTable,
     pub storage_settings: OpsmlStorageSettings,
+    key_provider: Arc<dyn KeyProvider>,
+    /// Recorded as the user deleting cards
+    username: String,
+    /// Environment recorded on registered cards
+    app_env: String,
 }
 
 impl ServerCardRegistry {
@@ -42,9 +49,12 @@ impl ServerCardRegistry {
         storage_settings: OpsmlStorageSettings,
         database_settings: DatabaseSettings,
         scouter_client: Option<ScouterClient>,
+        username: String,
+        app_env: String,
     ) -> Result<Self, RegistryError> {
         let sql_client = Arc::new(get_sql_client(&database_settings).await?);
         let table_name = CardTable::from_registry_type(&registry_type);
+        let key_provider = storage_settings.key_provider()?;
 
         Ok(Self {
             sql_client,
@@ -52,6 +62,9 @@ impl ServerCardRegistry {
             registry_type,
             storage_settings,
             scouter_client,
+            key_provider,
+            username,
+            app_env,
         })
     }
 
@@ -108,16 +121,19 @@ impl ServerCardRegistry {
         pre_tag: Option<String>,
         build_tag: Option<String>,
     ) -> Result<CreateCardResponse, RegistryError> {
-        let version = self
-            .get_next_version(
-                card.space(),
-                card.name(),
-                version,
-                version_type,
-                pre_tag,
-                build_tag,
-            )
-            .await?;
+        // shared with the server so versions still in the trash are never registered again
+        let version_request = CardVersionRequest {
+            name: card.name().to_string(),
+            space: card.space().to_string(),
+            version,
+            version_type,
+            pre_tag,
+            build_tag,
+        };
+        let version =
+            get_next_version(self.sql_client.clone(), &self.table_name, version_request).await?;
+
+        let mut document = CardSearchDocument::from_record(&card, "", &version.to_string());
 
         let card = match card {
             CardRecord::Data(client_card) => {
@@ -132,6 +148,7 @@ impl ServerCardRegistry {
                     client_card.interface_type.to_string(),
                     client_card.opsml_version,
                     client_card.username,
+                    self.app_env.clone(),
                 );
                 ServerCard::Data(server_card)
             }
@@ -150,6 +167,7 @@ impl ServerCardRegistry {
                     client_card.task_type,
                     client_card.opsml_version,
                     client_card.username,
+                    self.app_env.clone(),
                 );
                 ServerCard::Model(server_card)
             }
@@ -168,6 +186,7 @@ impl ServerCardRegistry {
                     client_card.opsml_version,
                     client_card.username,
                     client_card.status,
+                    self.app_env.clone(),
                 );
                 ServerCard::Experiment(server_card)
             }
@@ -184,6 +203,7 @@ impl ServerCardRegistry {
                     client_card.experimentcard_uids,
                     client_card.opsml_version,
                     client_card.username,
+                    self.app_env.clone(),
                 );
                 ServerCard::Audit(server_card)
             }
@@ -198,6 +218,7 @@ impl ServerCardRegistry {
                     client_card.opsml_version,
                     client_card.username,
                     client_card.content_hash,
+                    self.app_env.clone(),
                 );
                 ServerCard::Prompt(server_card)
             }
@@ -216,6 +237,7 @@ impl ServerCardRegistry {
                     client_card.content_hash,
                     client_card.username,
                     client_card.tags,
+                    self.app_env.clone(),
                 );
                 ServerCard::Service(Box::new(server_card))
             }
@@ -234,6 +256,7 @@ impl ServerCardRegistry {
                     client_card.username,
                     client_card.content_hash,
                     client_card.input_schema,
+                    self.app_env.clone(),
                 );
                 ServerCard::Skill(server_card)
             }
@@ -249,6 +272,7 @@ impl ServerCardRegistry {
                     client_card.opsml_version,
                     client_card.username,
                     client_card.content_hash,
+                    self.app_env.clone(),
                 );
                 ServerCard::SubAgent(server_card)
             }
@@ -265,6 +289,7 @@ impl ServerCardRegistry {
                     client_card.opsml_version,
                     client_card.username,
                     client_card.content_hash,
+                    self.app_env.clone(),
                 );
                 ServerCard::Tool(server_card)
             }
@@ -272,6 +297,9 @@ impl ServerCardRegistry {
 
         self.sql_client.insert_card(&self.table_name, &card).await?;
 
+        document.uid = card.uid().to_string();
+        self.index_card(&document).await;
+
         let key = self
             .create_artifact_key(
                 card.uid(),
@@ -295,12 +323,15 @@ impl ServerCardRegistry {
                 registry_type: key.registry_type,
                 encrypted_key: key.encrypted_key,
                 storage_key: key.storage_key,
+                key_version: key.key_version,
+                manifest_sha256: key.manifest_sha256,
             },
         };
         Ok(response)
     }
 
     pub async fn update_card(&self, card: &CardRecord) -> Result<(), RegistryError> {
+        let document = CardSearchDocument::from_record(card, card.uid(), card.version());
         let card = card.clone();
         let card = match card {
             CardRecord::Data(client_card) => {
@@ -570,54 +601,52 @@ impl ServerCardRegistry {
         };
 
         self.sql_client.update_card(&self.table_name, &card).await?;
+        self.index_card(&document).await;
 
         Ok(())
     }
 
+    /// Index a card version for full-text search. Failures are logged because the index can be
+    /// rebuilt from the registry tables
+    async fn index_card(&self, document: &CardSearchDocument) {
+        if let Err(e) = self.sql_client.upsert_search_document(document).await {
+            error!("Failed to index card {} for search: {}", document.uid, e);
+        }
+    }
+
+    /// Move a card to the trash, the same as deleting it through the server. Artifacts are kept
+    /// until the trash is purged, and the version stays reserved until then
     #[instrument(skip_all)]
     pub async fn delete_card(
         &self,
         delete_request: DeleteCardRequest,
     ) -> Result<(), RegistryError> {
-        let args = CardQueryArgs {
-            uid: Some(delete_request.uid.to_string()),
-            registry_type: delete_request.registry_type.clone(),
-            ..Default::default()
-        };
-        // get key
-        let key = self.get_key(&args).await.inspect_err(|e| {
-            error!("Error getting key for delete request: {}", e);
+        trash_card(
+            &self.sql_client,
+            &delete_request.registry_type,
+            &delete_request.uid,
+            TrashReason::Deleted,
+            &self.username,
+        )
+        .await
+        .inspect_err(|e| {
+            error!(
+                "Failed to move card {} to the trash: {}",
+                delete_request.uid, e
+            );
         })?;
 
-        // get storage client and delete artifacts
-        let storage_client = StorageClientEnum::new(&self.storage_settings).await?;
-
-        // Delete saved artifacts if they exist
-        if storage_client.find(&key.storage_path()).await?.is_empty() {
-            info!("No files found at storage path. Skipping artifact deletion");
-        } else {
-            storage_client.rm(&key.storage_path(), true).await?;
-        }
-
-        // Delete the artifact key
-        self.sql_client
-            .delete_artifact_key(&delete_request.uid, &key.registry_type.to_string())
-            .await?;
-
-        // Delete the card from registry
-        self.sql_client
-            .delete_card(&self.table_name, &delete_request.uid)
-            .await?;
-
         Ok(())
     }
 
     pub async fn get_key(&self, args: &CardQueryArgs) -> Result<ArtifactKey, RegistryError> {
         let table = CardTable::from_registry_type(&args.registry_type);
-        Ok(self
+        let key = self
             .sql_client
             .get_card_key_for_loading(&table, args)
-            .await?)
+            .await?;
+
+        Ok(key.open(&*self.key_provider)?)
     }
 
     pub async fn check_uid_exists(&self, uid: &str) -> Result<bool, RegistryError> {
@@ -637,41 +666,20 @@ impl ServerCardRegistry {
             .get_artifact_key(uid, &registry_type.to_string())
             .await?;
 
-        Ok(key)
+        Ok(key.open(&*self.key_provider)?)
     }
 
-    async fn get_next_version(
+    pub async fn set_artifact_manifest(
         &self,
-        space: &str,
-        name: &str,
-        version: Option<String>,
-        version_type: VersionType,
-        pre_tag: Option<String>,
-        build_tag: Option<String>,
-    ) -> Result<Version, RegistryError> {
-        let versions = self
-            .sql_client
-            .get_versions(&self.table_name, space, name, version.clone())
+        uid: &str,
+        registry_type: &RegistryType,
+        manifest_sha256: &str,
+    ) -> Result<(), RegistryError> {
+        self.sql_client
+            .set_artifact_manifest(uid, &registry_type.to_string(), manifest_sha256)
             .await?;
 
-        // if no versions exist, return the default version
-        if versions.is_empty() {
-            return match &version {
-                Some(version_str) => Ok(VersionValidator::clean_version(version_str)?),
-                None => Ok(Version::new(0, 1, 0)),
-            };
-        }
-
-        let base_version = versions.first().unwrap().to_string();
-
-        let args = VersionArgs {
-            version: base_version,
-            version_type,
-            pre: pre_tag,
-            build: build_tag,
-        };
-
-        Ok(VersionValidator::bump_version(&args)?)
+        Ok(())
     }
 
     async fn create_artifact_key(
@@ -689,21 +697,21 @@ impl ServerCardRegistry {
             registry_type.as_bytes(),
         )?;
 
-        let uid_key = uid_to_byte_key(uid)?;
-
-        let encrypted_key = encrypted_key(&uid_key, &derived_key)?;
-
-        let artifact_key = ArtifactKey {
+        // the stored key is wrapped with the KEK, the returned key with the uid
+        let (wrapped_key, key_version) = self.key_provider.wrap_key(&derived_key)?;
+        let stored_key = ArtifactKey {
             uid: uid.to_string(),
             space: space.to_string(),
             registry_type: RegistryType::from_string(registry_type)?,
-            encrypted_key,
+            encrypted_key: wrapped_key,
             storage_key: storage_key.to_string(),
+            key_version,
+            manifest_sha256: None,
         };
 
-        self.sql_client.insert_artifact_key(&artifact_key).await?;
+        self.sql_client.insert_artifact_key(&stored_key).await?;
 
-        Ok(artifact_key)
+        Ok(stored_key.open(&*self.key_provider)?)
     }
 
     pub fn check_service_health(&self, service: IntegratedService) -> Result<bool, RegistryError> {
@@ -747,6 +755,14 @@ impl ServerCardRegistry {
         Ok(())
     }
 
+    #[instrument(skip_all)]
+    pub(crate) async fn rotate_artifact_keys(
+        &self,
+        request: &RotateArtifactKeysRequest,
+    ) -> Result<RotateArtifactKeysResponse, RegistryError> {
+        Ok(rewrap_artifact_keys(&self.sql_client, &*self.key_provider, request.batch_size).await?)
+    }
+
     #[instrument(skip_all)]
     pub(crate) async fn compare_card_hash(
         &self,
@@ -762,4 +778,11 @@ impl ServerCardRegistry {
                 error!("Error comparing card hash: {}", e);
             })?)
     }
+    #[instrument(skip_all)]
+    pub(crate) async fn search_cards(
+        &self,
+        query: &CardSearchQuery,
+    ) -> Result<CardSearchResponse, RegistryError> {
+        Ok(search_readable_cards(&self.sql_client, query, None).await?)
+    }
 }
@@ -20,6 +20,8 @@ pub struct ServerExperiment {
     sql_client: Arc<SqlClientEnum>,
     pub table_name: CardTable,
     pub storage_settings: OpsmlStorageSettings,
+    /// Environment recorded on logged artifacts
+    app_env: String,
 }
 
 impl ServerExperiment {
@@ -33,6 +35,7 @@ impl ServerExperiment {
     pub async fn new(
         storage_settings: OpsmlStorageSettings,
         database_settings: DatabaseSettings,
+        app_env: String,
     ) -> Result<Self, RegistryError> {
         let sql_client = Arc::new(get_sql_client(&database_settings).await?);
         let table_name = CardTable::from_registry_type(&RegistryType::Experiment);
@@ -41,6 +44,7 @@ impl ServerExperiment {
             sql_client,
             table_name,
             storage_settings,
+            app_env,
         })
     }
 
@@ -90,8 +94,14 @@ impl ServerExperiment {
         )
         .await?;
 
-        let artifact_record =
-            ArtifactSqlRecord::new(space, name, version, media_type, artifact_type.to_string());
+        let artifact_record = ArtifactSqlRecord::new(
+            space,
+            name,
+            version,
+            media_type,
+            artifact_type.to_string(),
+            self.app_env.clone(),
+        );
 
         self.sql_client
             .insert_artifact_record(&artifact_record)
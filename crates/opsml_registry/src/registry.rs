@@ -3,17 +3,19 @@ use crate::registries::card::OpsmlCardRegistry;
 use crate::utils::upload_card_artifacts;
 use crate::utils::verify_card_rs;
 #[cfg(feature = "python")]
-use crate::utils::{check_if_card, download_card, verify_card};
+use crate::utils::{check_if_card, download_card, refresh_manifest, verify_card};
 #[cfg(feature = "python")]
 use crate::utils::{upload_drift_profile_map, upload_profile};
 #[cfg(feature = "python")]
 use const_format::concatcp;
+use opsml_cards::default_space;
 use opsml_cards::traits::OpsmlCard;
 use opsml_cards::traits::ProfileExt;
 use opsml_colors::Colorize;
 #[cfg(feature = "python")]
 use opsml_interfaces::SaveKwargs;
 use opsml_semver::VersionType;
+use opsml_storage::write_manifest;
 use opsml_types::*;
 use opsml_types::{DriftArgs, cards::CardTable, contracts::*};
 use opsml_utils::clean_string;
@@ -52,6 +54,15 @@ const PROMPT_TAG: &str = concatcp!(SCOUTER_TAG_PREFIX, ".", PROMPT_KEY_ATTR);
 #[cfg(feature = "python")]
 const MODEL_TAG: &str = concatcp!(SCOUTER_TAG_PREFIX, ".", MODEL_KEY_ATTR);
 
+/// `space` if given, otherwise the default space of the active client profile. A uid identifies
+/// a card in any space, so lookups by uid are not scoped to the default space
+fn space_or_default(space: Option<String>, uid: Option<&str>) -> Option<String> {
+    match uid {
+        Some(_) => space,
+        None => space.or_else(default_space),
+    }
+}
+
 /// Set uid as a tag on the current active span
 #[cfg(feature = "python")]
 fn set_attribute_by_registry_type(
@@ -249,6 +260,7 @@ impl CardRegistry {
         if uid.is_none() && name.is_none() && space.is_none() && version.is_none() {
             return Err(RegistryError::MissingArgsError);
         }
+        let space = space_or_default(space, uid.as_deref());
 
         // Wrap all operations in a single block_on to handle async operations
         let key = self.registry.get_key(&CardQueryArgs {
@@ -286,7 +298,10 @@ impl CardRegistry {
 
         let tmp_path = Self::save_card(card, &self.registry_type)?;
 
-        upload_card_artifacts(tmp_path, &key)?;
+        // only part of the card is re-saved, so fold it into the stored manifest
+        refresh_manifest(&tmp_path, &key)?;
+
+        upload_card_artifacts(&self.registry, tmp_path, &key)?;
 
         Ok(())
     }
@@ -413,7 +428,7 @@ impl CardRegistry {
 
         // Save artifacts
         debug!("Uploading card artifacts");
-        upload_card_artifacts(tmp_path, &response.key)?;
+        upload_card_artifacts(registry, tmp_path, &response.key)?;
 
         Ok(())
     }
@@ -510,6 +525,8 @@ impl CardRegistry {
             }
         }
 
+        write_manifest(&tmp_path)?;
+
         Ok(tmp_path)
     }
 
@@ -786,7 +803,7 @@ impl CardRegistry {
 
         let name = name.map(|name| clean_string(&name));
 
-        let space = space.map(|space| clean_string(&space));
+        let space = space_or_default(space, uid.as_deref()).map(|space| clean_string(&space));
 
         let query_args = CardQueryArgs {
             uid,
@@ -884,7 +901,7 @@ impl CardRegistry {
 
         // Save artifacts
         debug!("Uploading card artifacts");
-        upload_card_artifacts(tmp_path, &response.key)?;
+        upload_card_artifacts(&self.registry, tmp_path, &response.key)?;
 
         Ok(())
     }
@@ -955,6 +972,7 @@ impl CardRegistry {
         let tmp_dir = TempDir::new()?;
         let tmp_path = tmp_dir.keep();
         card.save(tmp_path.clone())?;
+        write_manifest(&tmp_path)?;
         Ok(tmp_path)
     }
 
@@ -2,7 +2,7 @@ use crate::error::RegistryError;
 use crate::registries::card::OpsmlCardRegistry;
 use opsml_cards::traits::OpsmlCard;
 use opsml_crypt::encrypt_directory;
-use opsml_storage::storage_client;
+use opsml_storage::{ArtifactManifest, storage_client};
 use opsml_types::contracts::*;
 use opsml_types::*;
 use std::path::PathBuf;
@@ -17,6 +17,7 @@ use {
     pyo3::{IntoPyObjectExt, prelude::*},
     scouter_client::ProfileRequest,
     std::collections::HashMap,
+    std::path::Path,
     tempfile::TempDir,
 };
 
@@ -323,16 +324,25 @@ pub fn download_card<'py>(
 /// (1) create an artifact key to be used to encrypt data
 /// (2) save the card to a temporary directory (with encryption)
 /// (3) Transfer all files in the temporary directory to the storage system
+/// (4) Record the digest of the card's manifest with its artifact key
 ///
 /// # Arguments
-/// * `py` - Python interpreter
-/// * `card` - Card to save
-/// * `save_kwargs` - Optional save kwargs
+/// * `registry` - Registry the card is registered in
+/// * `path` - Directory holding the saved (unencrypted) card files
+/// * `key` - Artifact key of the card
 ///
 /// # Returns
 /// * `Result<(), RegistryError>` - Result
 #[instrument(skip_all)]
-pub fn upload_card_artifacts(path: PathBuf, key: &ArtifactKey) -> Result<(), RegistryError> {
+pub fn upload_card_artifacts(
+    registry: &OpsmlCardRegistry,
+    path: PathBuf,
+    key: &ArtifactKey,
+) -> Result<(), RegistryError> {
+    // the manifest lives in the same bucket as the files it covers, so its digest is kept
+    // outside of storage to catch a manifest that was removed or replaced
+    let manifest_sha256 = ArtifactManifest::digest(&path)?;
+
     // create temp path for saving
     // TODO: why is this named decrypt key?
     let encryption_key = key.get_crypt_key()?;
@@ -343,6 +353,44 @@ pub fn upload_card_artifacts(path: PathBuf, key: &ArtifactKey) -> Result<(), Reg
     storage_client()?.put(&path, &key.storage_path(), true)?;
     debug!("Saved card artifacts to storage");
 
+    if let Some(manifest_sha256) = manifest_sha256 {
+        registry.set_artifact_manifest(&key.uid, &key.registry_type, &manifest_sha256)?;
+        debug!("Recorded card manifest digest");
+    }
+
+    Ok(())
+}
+
+/// Merge a partially re-saved card directory into the card's stored manifest
+///
+/// Cards saved before manifests were introduced have none, in which case nothing is written
+/// and downloads of the card skip verification as before.
+///
+/// # Arguments
+/// * `path` - Directory holding the re-saved (unencrypted) card files
+/// * `key` - Artifact key of the card
+#[cfg(feature = "python")]
+#[instrument(skip_all)]
+pub fn refresh_manifest(path: &Path, key: &ArtifactKey) -> Result<(), RegistryError> {
+    let rpath = ArtifactManifest::path(&key.storage_path());
+    let storage = storage_client()?;
+
+    if !storage.exists(&rpath)? {
+        debug!("Card has no artifact manifest, skipping refresh");
+        return Ok(());
+    }
+
+    let tmp_dir = TempDir::new()?;
+    let lpath = ArtifactManifest::path(tmp_dir.path());
+    storage.get(&lpath, &rpath, false)?;
+    decrypt_directory(tmp_dir.path(), &key.get_crypt_key()?)?;
+
+    let Some(mut manifest) = ArtifactManifest::load(tmp_dir.path())? else {
+        return Ok(());
+    };
+    manifest.merge_dir(path)?;
+    manifest.save(path)?;
+
     Ok(())
 }
 
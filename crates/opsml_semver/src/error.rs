@@ -25,4 +25,7 @@ pub enum VersionError {
 
     #[error("Invalid version provided with exact syntax")]
     ExactSyntaxError,
+
+    #[error("Invalid version range expression: {0}")]
+    InvalidRangeExpression(String),
 }
@@ -1,3 +1,5 @@
 pub mod error;
+pub mod range;
 pub mod semver;
+pub use range::*;
 pub use semver::*;
@@ -0,0 +1,277 @@
+use crate::error::VersionError;
+use crate::semver::VersionParser;
+use semver::Version;
+use std::fmt::Display;
+
+/// Comparison operator used in a range expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparator {
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Exact,
+    NotEqual,
+}
+
+impl Comparator {
+    /// Splits a leading operator off a range term, e.g. `>=1.2.0` -> (GreaterEq, "1.2.0")
+    fn split(term: &str) -> Option<(Comparator, &str)> {
+        // two character operators must be checked first
+        [
+            (">=", Comparator::GreaterEq),
+            ("<=", Comparator::LessEq),
+            ("!=", Comparator::NotEqual),
+            ("==", Comparator::Exact),
+            (">", Comparator::Greater),
+            ("<", Comparator::Less),
+            ("=", Comparator::Exact),
+        ]
+        .into_iter()
+        .find_map(|(prefix, op)| term.strip_prefix(prefix).map(|rest| (op, rest.trim())))
+    }
+}
+
+impl Display for Comparator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Comparator::Greater => write!(f, ">"),
+            Comparator::GreaterEq => write!(f, ">="),
+            Comparator::Less => write!(f, "<"),
+            Comparator::LessEq => write!(f, "<="),
+            Comparator::Exact => write!(f, "="),
+            Comparator::NotEqual => write!(f, "!="),
+        }
+    }
+}
+
+/// A single `<op> <version>` constraint. Only the major, minor and patch components
+/// take part in comparisons, matching how versions are stored and queried in the registry
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionPredicate {
+    pub op: Comparator,
+    pub version: Version,
+}
+
+impl VersionPredicate {
+    fn new(op: Comparator, version: Version) -> Self {
+        Self { op, version }
+    }
+
+    pub fn key(&self) -> (u64, u64, u64) {
+        (self.version.major, self.version.minor, self.version.patch)
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        let candidate = (version.major, version.minor, version.patch);
+        let target = self.key();
+
+        match self.op {
+            Comparator::Greater => candidate > target,
+            Comparator::GreaterEq => candidate >= target,
+            Comparator::Less => candidate < target,
+            Comparator::LessEq => candidate <= target,
+            Comparator::Exact => candidate == target,
+            Comparator::NotEqual => candidate != target,
+        }
+    }
+}
+
+/// A compound version constraint such as `>=1.2.0, <2.0.0 || ^3.1`.
+///
+/// `||` separates alternatives and `,` separates constraints that must all hold.
+/// Each alternative is stored as a list of predicates that are AND-ed together
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionRange {
+    pub alternatives: Vec<Vec<VersionPredicate>>,
+}
+
+impl VersionRange {
+    /// Returns true if `version` uses range syntax (comparison operators, `,` or `||`)
+    /// rather than a single `*`, `^`, `~` or exact version
+    pub fn is_range_expression(version: &str) -> bool {
+        version.contains(['<', '>', '=', '!', ',']) || version.contains("||")
+    }
+
+    /// Parse a range expression
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any alternative is empty or any term is not a valid version constraint
+    pub fn parse(expression: &str) -> Result<VersionRange, VersionError> {
+        let alternatives = expression
+            .split("||")
+            .map(|alternative| {
+                let predicates = alternative
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|term| !term.is_empty())
+                    .map(Self::parse_term)
+                    .collect::<Result<Vec<_>, _>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>();
+
+                if predicates.is_empty() {
+                    return Err(VersionError::InvalidRangeExpression(expression.to_string()));
+                }
+
+                Ok(predicates)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(VersionRange { alternatives })
+    }
+
+    /// Returns true if `version` satisfies at least one alternative
+    pub fn matches(&self, version: &Version) -> bool {
+        self.alternatives
+            .iter()
+            .any(|predicates| predicates.iter().all(|p| p.matches(version)))
+    }
+
+    /// Parse a version that may be missing minor/patch components.
+    /// Returns the expanded version and the exclusive upper bound implied by the given parts
+    /// (`1.2` -> 1.2.0 and 1.3.0), or `None` if all three parts were provided
+    fn parse_partial(version: &str) -> Result<(Version, Option<Version>), VersionError> {
+        // pre-release and build metadata don't take part in range comparisons
+        let core = version.split(['-', '+']).next().unwrap_or_default();
+
+        let parts = core
+            .split('.')
+            .filter(|v| !v.is_empty() && *v != "*")
+            .map(str::parse::<u64>)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(VersionError::ParseError)?;
+
+        match parts.as_slice() {
+            [major] => Ok((
+                Version::new(*major, 0, 0),
+                Some(Version::new(major + 1, 0, 0)),
+            )),
+            [major, minor] => Ok((
+                Version::new(*major, *minor, 0),
+                Some(Version::new(*major, minor + 1, 0)),
+            )),
+            [major, minor, patch] => Ok((Version::new(*major, *minor, *patch), None)),
+            _ => Err(VersionError::InvalidVersionString(version.to_string())),
+        }
+    }
+
+    /// Expand a single term into one or more predicates.
+    /// Partial versions follow Cargo semantics, e.g. `>1.2` means `>=1.3.0` and `<=1.2` means `<1.3.0`
+    fn parse_term(term: &str) -> Result<Vec<VersionPredicate>, VersionError> {
+        let Some((op, version)) = Comparator::split(term) else {
+            // bare terms keep the existing `*`, `^`, `~` and exact semantics
+            let bounds = VersionParser::get_version_to_search(term)?;
+            let mut predicates = vec![VersionPredicate::new(
+                Comparator::GreaterEq,
+                bounds.lower_bound,
+            )];
+            if !bounds.no_upper_bound {
+                predicates.push(VersionPredicate::new(Comparator::Less, bounds.upper_bound));
+            }
+            return Ok(predicates);
+        };
+
+        let (lower, upper) = Self::parse_partial(version)?;
+
+        let predicates = match (op, upper) {
+            (_, None) => vec![VersionPredicate::new(op, lower)],
+            (Comparator::Greater, Some(upper)) => {
+                vec![VersionPredicate::new(Comparator::GreaterEq, upper)]
+            }
+            (Comparator::GreaterEq, Some(_)) => {
+                vec![VersionPredicate::new(Comparator::GreaterEq, lower)]
+            }
+            (Comparator::Less, Some(_)) => vec![VersionPredicate::new(Comparator::Less, lower)],
+            (Comparator::LessEq, Some(upper)) => {
+                vec![VersionPredicate::new(Comparator::Less, upper)]
+            }
+            (Comparator::Exact, Some(upper)) => vec![
+                VersionPredicate::new(Comparator::GreaterEq, lower),
+                VersionPredicate::new(Comparator::Less, upper),
+            ],
+            // excluding a partial version would need an OR inside an AND group
+            (Comparator::NotEqual, Some(_)) => {
+                return Err(VersionError::InvalidRangeExpression(format!(
+                    "{term} (!= requires a full major.minor.patch version)"
+                )));
+            }
+        };
+
+        Ok(predicates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(version: &str) -> Version {
+        Version::parse(version).unwrap()
+    }
+
+    #[test]
+    fn test_is_range_expression() {
+        assert!(VersionRange::is_range_expression(">=1.2.0, <2.0.0"));
+        assert!(VersionRange::is_range_expression("!=1.3.1"));
+        assert!(VersionRange::is_range_expression("^1.2 || ^2.0"));
+        assert!(!VersionRange::is_range_expression("^1.2.0"));
+        assert!(!VersionRange::is_range_expression("1.*"));
+        assert!(!VersionRange::is_range_expression("1.2.3"));
+    }
+
+    #[test]
+    fn test_version_range_compound() {
+        let range = VersionRange::parse(">=1.2.0, <2.0.0, !=1.3.1").unwrap();
+        assert_eq!(range.alternatives.len(), 1);
+        assert!(range.matches(&v("1.2.0")));
+        assert!(range.matches(&v("1.9.9")));
+        assert!(!range.matches(&v("1.3.1")));
+        assert!(!range.matches(&v("1.1.9")));
+        assert!(!range.matches(&v("2.0.0")));
+    }
+
+    #[test]
+    fn test_version_range_alternatives() {
+        let range = VersionRange::parse("<1.0.0 || >=3.0.0").unwrap();
+        assert_eq!(range.alternatives.len(), 2);
+        assert!(range.matches(&v("0.9.0")));
+        assert!(range.matches(&v("3.1.0")));
+        assert!(!range.matches(&v("2.0.0")));
+
+        // legacy syntax can be combined with ||
+        let range = VersionRange::parse("~1.1 || 2.*").unwrap();
+        assert!(range.matches(&v("1.1.5")));
+        assert!(range.matches(&v("2.4.0")));
+        assert!(!range.matches(&v("1.2.0")));
+    }
+
+    #[test]
+    fn test_version_range_partial_versions() {
+        let range = VersionRange::parse(">1.2").unwrap();
+        assert!(!range.matches(&v("1.2.9")));
+        assert!(range.matches(&v("1.3.0")));
+
+        let range = VersionRange::parse("<=1.2").unwrap();
+        assert!(range.matches(&v("1.2.9")));
+        assert!(!range.matches(&v("1.3.0")));
+
+        let range = VersionRange::parse("=1").unwrap();
+        assert!(range.matches(&v("1.9.0")));
+        assert!(!range.matches(&v("2.0.0")));
+
+        // pre-release tags are ignored when comparing
+        let range = VersionRange::parse(">=1.2.0-alpha").unwrap();
+        assert!(range.matches(&v("1.2.0")));
+    }
+
+    #[test]
+    fn test_version_range_invalid() {
+        assert!(VersionRange::parse(">=1.2.0 ||").is_err());
+        assert!(VersionRange::parse(">=abc").is_err());
+        assert!(VersionRange::parse("!=1.3").is_err());
+        assert!(VersionRange::parse(">=1.2.3.4").is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use crate::core::admin::utils::{recount_space_usage, scan_storage_consistency};
+use crate::core::auth::util::require_admin;
+use crate::core::error::{OpsmlServerError, internal_server_error};
+use crate::core::state::AppState;
+use anyhow::{Context, Result};
+use axum::{
+    Extension, Json, Router,
+    extract::State,
+    http::StatusCode,
+    routing::{post, put},
+};
+use opsml_auth::permission::UserPermissions;
+use opsml_sql::enums::utils::rewrap_artifact_keys;
+use opsml_sql::traits::SpaceLogicTrait;
+use opsml_types::contracts::{
+    ConsistencyReport, ConsistencyScanRequest, RotateArtifactKeysRequest,
+    RotateArtifactKeysResponse, SpaceQuotaRequest, SpaceRecord, UsageRecountRequest,
+    UsageRecountResponse,
+};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::Arc;
+use tracing::{error, info, instrument};
+
+type AdminResult<T> = Result<T, (StatusCode, Json<OpsmlServerError>)>;
+
+#[utoipa::path(
+    post,
+    path = "/opsml/api/admin/keys/rotate",
+    request_body = RotateArtifactKeysRequest,
+    responses(
+        (status = 200, description = "Artifact keys re-wrapped", body = RotateArtifactKeysResponse),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "admin"
+)]
+/// Re-wrap every stored artifact key with the current key-encryption-key
+///
+/// Artifacts are not re-encrypted. Keys still wrapped with the card uid by older servers are
+/// migrated as well. Requires admin permission
+#[instrument(skip_all)]
+pub(crate) async fn rotate_artifact_keys(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(req): Json<RotateArtifactKeysRequest>,
+) -> AdminResult<Json<RotateArtifactKeysResponse>> {
+    require_admin(&perms)?;
+
+    let response = rewrap_artifact_keys(&state.sql_client, &*state.key_provider, req.batch_size)
+        .await
+        .map_err(|e| {
+            error!("Failed to rotate artifact keys: {e}");
+            internal_server_error(e, "Failed to rotate artifact keys", None)
+        })?;
+
+    info!(
+        "{} re-wrapped {} artifact keys with key version {} ({} skipped)",
+        perms.username, response.rewrapped, response.key_version, response.skipped
+    );
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/opsml/api/admin/storage/scan",
+    request_body = ConsistencyScanRequest,
+    responses(
+        (status = 200, description = "Storage consistency report", body = ConsistencyReport),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "admin"
+)]
+/// Cross-reference registry storage with artifact keys and card tables
+///
+/// Reports orphaned objects and keys, cards with missing artifacts and storage used per space.
+/// Orphans are only removed when `delete` is set. Requires admin permission
+#[instrument(skip_all)]
+pub(crate) async fn scan_storage(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(req): Json<ConsistencyScanRequest>,
+) -> AdminResult<Json<ConsistencyReport>> {
+    require_admin(&perms)?;
+
+    let report = scan_storage_consistency(&state, &req).await.map_err(|e| {
+        error!("Storage consistency scan failed: {e}");
+        internal_server_error(e, "Storage consistency scan failed", None)
+    })?;
+
+    info!(
+        "{} scanned storage: {} issue(s), {} orphaned bytes{}",
+        perms.username,
+        report.issues.len(),
+        report.orphaned_bytes(),
+        if report.dry_run {
+            ""
+        } else {
+            ", orphans deleted"
+        }
+    );
+
+    Ok(Json(report))
+}
+
+#[utoipa::path(
+    post,
+    path = "/opsml/api/admin/storage/recount",
+    request_body = UsageRecountRequest,
+    responses(
+        (status = 200, description = "Recounted storage usage", body = UsageRecountResponse),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "admin"
+)]
+/// Recount the storage usage tracked for quotas from the objects in registry storage
+///
+/// Corrects usage that drifted from storage, for example after objects were removed outside of
+/// opsml. Requires admin permission
+#[instrument(skip_all)]
+pub(crate) async fn recount_storage_usage(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(req): Json<UsageRecountRequest>,
+) -> AdminResult<Json<UsageRecountResponse>> {
+    require_admin(&perms)?;
+
+    let usage = recount_space_usage(&state, req.space.as_deref())
+        .await
+        .map_err(|e| {
+            error!("Storage usage recount failed: {e}");
+            internal_server_error(e, "Storage usage recount failed", None)
+        })?;
+
+    info!(
+        "{} recounted storage usage of {} registries",
+        perms.username,
+        usage.len()
+    );
+
+    Ok(Json(UsageRecountResponse { usage }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/opsml/api/admin/space/quota",
+    request_body = SpaceQuotaRequest,
+    responses(
+        (status = 200, description = "Space record with the new quotas", body = SpaceRecord),
+        (status = 400, description = "Invalid quota", body = OpsmlServerError),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "admin"
+)]
+/// Set or clear the storage quotas of a space
+///
+/// Uploads past the soft quota succeed with a warning, uploads past the hard quota are rejected.
+/// Requires admin permission
+#[instrument(skip_all)]
+pub(crate) async fn set_space_quota(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(req): Json<SpaceQuotaRequest>,
+) -> AdminResult<Json<SpaceRecord>> {
+    require_admin(&perms)?;
+
+    let quotas = [req.soft_quota_bytes, req.hard_quota_bytes];
+    if quotas.iter().flatten().any(|quota| *quota < 0) {
+        return OpsmlServerError::bad_request("Quotas cannot be negative")
+            .into_response(StatusCode::BAD_REQUEST);
+    }
+    if let [Some(soft), Some(hard)] = quotas
+        && soft > hard
+    {
+        return OpsmlServerError::bad_request("Soft quota cannot be larger than the hard quota")
+            .into_response(StatusCode::BAD_REQUEST);
+    }
+
+    state
+        .sql_client
+        .set_space_quota(&req.space, req.soft_quota_bytes, req.hard_quota_bytes)
+        .await
+        .map_err(|e| {
+            error!("Failed to set space quota: {e}");
+            internal_server_error(e, "Failed to set space quota", None)
+        })?;
+
+    let record = state
+        .sql_client
+        .get_space_record(&req.space)
+        .await
+        .map_err(|e| {
+            error!("Failed to get space record: {e}");
+            internal_server_error(e, "Failed to get space record", None)
+        })?
+        .unwrap_or_default();
+
+    info!(
+        "{} set storage quotas of space {} to soft={:?} hard={:?}",
+        perms.username, req.space, req.soft_quota_bytes, req.hard_quota_bytes
+    );
+
+    Ok(Json(record))
+}
+
+pub async fn get_admin_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        Router::new()
+            .route(
+                &format!("{prefix}/admin/keys/rotate"),
+                post(rotate_artifact_keys),
+            )
+            .route(&format!("{prefix}/admin/storage/scan"), post(scan_storage))
+            .route(
+                &format!("{prefix}/admin/storage/recount"),
+                post(recount_storage_usage),
+            )
+            .route(&format!("{prefix}/admin/space/quota"), put(set_space_quota))
+    }));
+
+    match result {
+        Ok(router) => Ok(router),
+        Err(_) => {
+            error!("Failed to create admin router");
+            Err(anyhow::anyhow!("Failed to create admin router"))
+                .context("Panic occurred while creating the router")
+        }
+    }
+}
@@ -0,0 +1,569 @@
+use crate::core::error::ServerError;
+use crate::core::files::utils::{download_artifact, record_storage_usage, storage_path_owner};
+use crate::core::state::AppState;
+use chrono::{Duration, Utc};
+use opsml_sql::traits::{
+    ArtifactLogicTrait, CardLogicTrait, SearchLogicTrait, SpaceLogicTrait, TrashLogicTrait,
+};
+use opsml_types::contracts::{
+    ArtifactKey, ConsistencyIssue, ConsistencyIssueKind, ConsistencyReport, ConsistencyScanRequest,
+    FileInfo, SpaceRegistryUsage, SpaceStorageUsage, TrashQuery,
+};
+use opsml_types::{RegistryType, SaveName, Suffix, cards::CardTable};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::Arc;
+use tempfile::tempdir;
+use tokio::task::JoinHandle;
+use tracing::{error, info, instrument, warn};
+
+/// Registries whose artifacts are stored under `{table}/{space}/{name}/v{version}` prefixes
+const STORAGE_REGISTRIES: [RegistryType; 12] = [
+    RegistryType::Data,
+    RegistryType::Model,
+    RegistryType::Experiment,
+    RegistryType::Audit,
+    RegistryType::Prompt,
+    RegistryType::Service,
+    RegistryType::Mcp,
+    RegistryType::Agent,
+    RegistryType::Skill,
+    RegistryType::SubAgent,
+    RegistryType::Tool,
+    RegistryType::Artifact,
+];
+
+/// Number of artifact keys loaded per database round trip
+const KEY_BATCH_SIZE: i64 = 500;
+
+/// Upload reservations older than this belong to uploads that were never completed
+const UPLOAD_RESERVATION_TTL_HOURS: i64 = 24;
+
+/// An artifact key and the objects found under its storage prefix
+struct KeyUsage {
+    key: ArtifactKey,
+    referenced: bool,
+    objects: u64,
+    bytes: u64,
+}
+
+/// Objects under a card prefix that no artifact key points to
+#[derive(Default)]
+struct OrphanedPrefix {
+    paths: Vec<String>,
+    bytes: u64,
+}
+
+/// Space segment of a `{table}/{space}/...` storage path
+fn path_space(path: &str) -> String {
+    path.split('/').nth(1).unwrap_or_default().to_string()
+}
+
+/// Card prefix (`{table}/{space}/{name}/{version}`) an object would be stored under
+fn card_prefix(path: &str) -> String {
+    path.split('/').take(4).collect::<Vec<_>>().join("/")
+}
+
+/// Space and card name of a `{table}/{space}/{name}/README.md` path. READMEs are stored per card
+/// name rather than per version, under their own artifact key
+fn readme_card(path: &str) -> Option<(&str, &str)> {
+    let readme = format!("{}.{}", SaveName::ReadMe, Suffix::Md);
+    match path.split('/').collect::<Vec<_>>().as_slice() {
+        [_, space, name, file] if *file == readme => Some((*space, *name)),
+        _ => None,
+    }
+}
+
+/// Whether any version of a card, live or trashed, still exists
+async fn card_name_exists(
+    state: &AppState,
+    registry_type: &RegistryType,
+    space: &str,
+    name: &str,
+) -> Result<bool, ServerError> {
+    let table = CardTable::from_registry_type(registry_type);
+    if !state
+        .sql_client
+        .get_versions(&table, space, name, None)
+        .await?
+        .is_empty()
+    {
+        return Ok(true);
+    }
+
+    let trashed = state
+        .sql_client
+        .list_trash(&TrashQuery {
+            space: Some(space.to_string()),
+            registry_type: Some(registry_type.clone()),
+            deleted_before: None,
+        })
+        .await?;
+    Ok(trashed.iter().any(|record| record.name == name))
+}
+
+/// List every object under the registry prefixes in scope
+async fn list_registry_objects(
+    state: &AppState,
+    space: Option<&str>,
+) -> Result<Vec<FileInfo>, ServerError> {
+    let mut objects = Vec::new();
+
+    for registry_type in STORAGE_REGISTRIES.iter() {
+        let table = CardTable::from_registry_type(registry_type).to_string();
+        let prefix = match space {
+            Some(space) => format!("{table}/{space}"),
+            None => table,
+        };
+        let path = Path::new(&prefix);
+
+        if state.storage_client.exists(path).await? {
+            objects.extend(state.storage_client.find_info(path).await?);
+        }
+    }
+
+    Ok(objects)
+}
+
+/// Load artifact keys in scope, keyed by storage prefix, and flag keys without a storage prefix
+async fn load_artifact_keys(
+    state: &AppState,
+    space: Option<&str>,
+    report: &mut ConsistencyReport,
+) -> Result<HashMap<String, KeyUsage>, ServerError> {
+    let mut keys = HashMap::new();
+    let mut after_uid = String::new();
+
+    loop {
+        let batch = state
+            .sql_client
+            .get_artifact_keys(&after_uid, KEY_BATCH_SIZE)
+            .await?;
+        let Some(last) = batch.last() else {
+            break;
+        };
+        after_uid = last.uid.clone();
+
+        for key in batch {
+            if space.is_some_and(|space| space != key.space) {
+                continue;
+            }
+            report.keys_scanned += 1;
+
+            let storage_key = key.storage_key.trim_matches('/').to_string();
+            if storage_key.is_empty() {
+                report.issues.push(ConsistencyIssue {
+                    kind: ConsistencyIssueKind::EmptyStorageKey,
+                    space: key.space.clone(),
+                    storage_key,
+                    uid: Some(key.uid.clone()),
+                    registry_type: Some(key.registry_type.clone()),
+                    objects: 0,
+                    bytes: 0,
+                    deleted: false,
+                });
+                continue;
+            }
+
+            // README keys get a random uid and live as long as any version of the card.
+            // Trashed cards keep their key and artifacts until they are purged
+            let referenced = match readme_card(&storage_key) {
+                Some((card_space, card_name)) => {
+                    card_name_exists(state, &key.registry_type, card_space, card_name).await?
+                }
+                None => {
+                    let table = CardTable::from_registry_type(&key.registry_type);
+                    state.sql_client.check_uid_exists(&key.uid, &table).await?
+                        || state.sql_client.get_trash_record(&key.uid).await?.is_some()
+                }
+            };
+
+            keys.insert(
+                storage_key,
+                KeyUsage {
+                    key,
+                    referenced,
+                    objects: 0,
+                    bytes: 0,
+                },
+            );
+        }
+    }
+
+    Ok(keys)
+}
+
+/// Report registered cards that have no artifact key. A card row is inserted before its key, so
+/// a failed key insert leaves a card whose artifacts can never be encrypted or read. Artifact
+/// records are skipped, their keys are created per storage path on upload. A card registered while
+/// the scan runs can be reported before its key is inserted
+async fn find_unkeyed_cards(
+    state: &AppState,
+    space: Option<&str>,
+    report: &mut ConsistencyReport,
+) -> Result<(), ServerError> {
+    for registry_type in STORAGE_REGISTRIES.iter() {
+        if *registry_type == RegistryType::Artifact {
+            continue;
+        }
+        let table = CardTable::from_registry_type(registry_type);
+        let mut after_uid = String::new();
+
+        loop {
+            let batch = state
+                .sql_client
+                .get_cards_without_key(&table, &after_uid, KEY_BATCH_SIZE)
+                .await?;
+            let Some(last) = batch.last() else {
+                break;
+            };
+            after_uid = last.uid.clone();
+
+            for card in batch {
+                if space.is_some_and(|space| space != card.space) {
+                    continue;
+                }
+                report.issues.push(ConsistencyIssue {
+                    kind: ConsistencyIssueKind::MissingKey,
+                    storage_key: format!("{table}/{}/{}/v{}", card.space, card.name, card.version),
+                    space: card.space,
+                    uid: Some(card.uid),
+                    registry_type: Some(registry_type.clone()),
+                    objects: 0,
+                    bytes: 0,
+                    deleted: false,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Cross-reference registry storage with artifact keys and card tables.
+///
+/// Reports objects no artifact key points to, keys whose card is gone, keys without a storage
+/// prefix, registered cards without artifacts and registered cards without a key, along with
+/// storage totals per space. With `delete` set, orphaned objects and keys are removed. Cards with
+/// missing artifacts or keys are only reported.
+///
+/// Objects are listed before keys are loaded. Keys are created before anything is uploaded under
+/// them, so a card registered while the scan runs is never mistaken for an orphan
+#[instrument(skip_all)]
+pub async fn scan_storage_consistency(
+    state: &AppState,
+    request: &ConsistencyScanRequest,
+) -> Result<ConsistencyReport, ServerError> {
+    let space = request.space.as_deref();
+    let mut report = ConsistencyReport {
+        dry_run: !request.delete,
+        ..Default::default()
+    };
+
+    let objects = list_registry_objects(state, space).await?;
+    let mut keys = load_artifact_keys(state, space, &mut report).await?;
+    report.objects_scanned = objects.len() as u64;
+
+    let mut usage: BTreeMap<String, SpaceStorageUsage> = BTreeMap::new();
+    let mut orphaned: BTreeMap<String, OrphanedPrefix> = BTreeMap::new();
+
+    for object in objects {
+        let name = object.name.trim_start_matches('/');
+        let bytes = object.size.max(0) as u64;
+
+        // the object itself (a README) or the nearest ancestor that is a key's storage prefix
+        // owns the object
+        let owner = std::iter::once(name)
+            .chain(name.match_indices('/').rev().map(|(idx, _)| &name[..idx]))
+            .find(|prefix| keys.contains_key(*prefix))
+            .map(str::to_string);
+
+        let (space, is_orphaned) = match owner.and_then(|prefix| keys.get_mut(&prefix)) {
+            Some(entry) => {
+                entry.objects += 1;
+                entry.bytes += bytes;
+                (entry.key.space.clone(), !entry.referenced)
+            }
+            // READMEs sit outside every version prefix, only their key decides if they are orphaned
+            None if readme_card(name).is_some() => (path_space(name), false),
+            None => {
+                let prefix = orphaned.entry(card_prefix(name)).or_default();
+                prefix.paths.push(name.to_string());
+                prefix.bytes += bytes;
+                (path_space(name), true)
+            }
+        };
+
+        let space_usage = usage
+            .entry(space.clone())
+            .or_insert_with(|| SpaceStorageUsage {
+                space,
+                ..Default::default()
+            });
+        space_usage.objects += 1;
+        space_usage.bytes += bytes;
+        if is_orphaned {
+            space_usage.orphaned_bytes += bytes;
+        }
+    }
+
+    let scanned_tables: Vec<String> = STORAGE_REGISTRIES
+        .iter()
+        .map(|registry_type| CardTable::from_registry_type(registry_type).to_string())
+        .collect();
+
+    for (storage_key, entry) in keys {
+        let root = storage_key.split('/').next().unwrap_or_default();
+        let kind = if !entry.referenced {
+            ConsistencyIssueKind::OrphanedKey
+        } else if entry.objects == 0 && scanned_tables.iter().any(|table| table == root) {
+            ConsistencyIssueKind::MissingArtifacts
+        } else {
+            continue;
+        };
+
+        let mut deleted = false;
+        if request.delete && kind == ConsistencyIssueKind::OrphanedKey {
+            if entry.objects > 0 {
+                let recursive = readme_card(&storage_key).is_none();
+                state
+                    .storage_client
+                    .rm(Path::new(&storage_key), recursive)
+                    .await?;
+                record_storage_usage(&state.sql_client, &storage_key, -(entry.bytes as i64)).await;
+            }
+            state
+                .sql_client
+                .delete_artifact_key(&entry.key.uid, &entry.key.registry_type.to_string())
+                .await?;
+            deleted = true;
+        }
+
+        report.issues.push(ConsistencyIssue {
+            kind,
+            space: entry.key.space,
+            storage_key,
+            uid: Some(entry.key.uid),
+            registry_type: Some(entry.key.registry_type),
+            objects: entry.objects,
+            bytes: entry.bytes,
+            deleted,
+        });
+    }
+
+    for (prefix, orphan) in orphaned {
+        let mut deleted = false;
+        if request.delete {
+            // remove objects one by one so nothing outside this scan is touched
+            for path in &orphan.paths {
+                state.storage_client.rm(Path::new(path), false).await?;
+            }
+            record_storage_usage(&state.sql_client, &prefix, -(orphan.bytes as i64)).await;
+            deleted = true;
+        }
+
+        report.issues.push(ConsistencyIssue {
+            kind: ConsistencyIssueKind::OrphanedObjects,
+            space: path_space(&prefix),
+            storage_key: prefix,
+            uid: None,
+            registry_type: None,
+            objects: orphan.paths.len() as u64,
+            bytes: orphan.bytes,
+            deleted,
+        });
+    }
+
+    find_unkeyed_cards(state, space, &mut report).await?;
+
+    report
+        .issues
+        .sort_by(|a, b| (&a.space, &a.storage_key).cmp(&(&b.space, &b.storage_key)));
+    report.spaces = usage.into_values().collect();
+
+    Ok(report)
+}
+
+/// Recount the storage used per space and registry from the objects in registry storage and
+/// replace the usage tracked for quotas. Bytes reserved by uploads still in flight are added on
+/// top, and reservations of uploads that were never completed are dropped. Uploads that complete
+/// while the recount runs may be miscounted until the next recount
+#[instrument(skip_all)]
+pub async fn recount_space_usage(
+    state: &AppState,
+    space: Option<&str>,
+) -> Result<Vec<SpaceRegistryUsage>, ServerError> {
+    let in_scope = |record_space: &str| space.is_none_or(|space| space == record_space);
+
+    state
+        .sql_client
+        .delete_stale_upload_reservations(
+            &(Utc::now() - Duration::hours(UPLOAD_RESERVATION_TTL_HOURS)),
+        )
+        .await?;
+
+    // registries already tracked start from zero, so usage of emptied registries is cleared
+    let mut totals: BTreeMap<(String, String), SpaceRegistryUsage> = BTreeMap::new();
+    let mut add = |space: String, registry_type: RegistryType, bytes: i64| {
+        totals
+            .entry((space.clone(), registry_type.to_string()))
+            .or_insert_with(|| SpaceRegistryUsage {
+                space,
+                registry_type,
+                bytes: 0,
+            })
+            .bytes += bytes;
+    };
+
+    for record in state.sql_client.get_space_usage().await? {
+        if in_scope(&record.space) {
+            add(record.space, record.registry_type, 0);
+        }
+    }
+
+    for object in list_registry_objects(state, space).await? {
+        if let Some((space, registry_type)) = storage_path_owner(&object.name) {
+            add(space, registry_type, object.size.max(0));
+        }
+    }
+
+    for reservation in state.sql_client.get_upload_reservations().await? {
+        if in_scope(&reservation.space) {
+            add(
+                reservation.space,
+                reservation.registry_type,
+                reservation.bytes,
+            );
+        }
+    }
+
+    for usage in totals.values() {
+        state
+            .sql_client
+            .set_space_usage(&usage.space, &usage.registry_type, usage.bytes)
+            .await?;
+    }
+
+    Ok(totals.into_values().collect())
+}
+
+/// Backfill storage usage from storage when nothing has been tracked yet, such as after
+/// upgrading from a server that did not track usage
+pub fn spawn_usage_backfill_task(state: Arc<AppState>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        match state.sql_client.get_space_usage().await {
+            Ok(usage) if usage.is_empty() => match recount_space_usage(&state, None).await {
+                Ok(usage) => info!("Backfilled storage usage of {} registries", usage.len()),
+                Err(e) => error!("Failed to backfill storage usage: {e}"),
+            },
+            Ok(_) => {}
+            Err(e) => error!("Failed to get storage usage: {e}"),
+        }
+    })
+}
+
+/// Index the READMEs of cards that have none in the search index, such as READMEs written before
+/// card search existed. Returns the number of READMEs indexed
+#[instrument(skip_all)]
+pub async fn backfill_readme_index(state: &AppState) -> Result<u64, ServerError> {
+    let tmp_dir = tempdir()?;
+    let mut indexed = 0;
+    let mut after_uid = String::new();
+
+    loop {
+        let batch = state
+            .sql_client
+            .get_artifact_keys(&after_uid, KEY_BATCH_SIZE)
+            .await?;
+        let Some(last) = batch.last() else {
+            break;
+        };
+        after_uid = last.uid.clone();
+
+        for key in batch {
+            let storage_key = key.storage_key.trim_matches('/');
+            let Some((space, name)) = readme_card(storage_key) else {
+                continue;
+            };
+            if state
+                .sql_client
+                .get_search_readme(&key.registry_type, space, name)
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+
+            let lpath = tmp_dir.path().join(&key.uid);
+            if let Err(e) = download_artifact(
+                state.storage_client.clone(),
+                state.sql_client.clone(),
+                &*state.key_provider,
+                &lpath,
+                storage_key,
+                &key.registry_type.to_string(),
+                Some(&key.uid),
+            )
+            .await
+            {
+                warn!("Failed to read README {storage_key} for search: {e}");
+                continue;
+            }
+
+            let readme = std::fs::read_to_string(&lpath)?;
+            std::fs::remove_file(&lpath)?;
+            state
+                .sql_client
+                .update_search_readme(&key.registry_type, space, name, &readme)
+                .await?;
+            indexed += 1;
+        }
+    }
+
+    Ok(indexed)
+}
+
+/// Index existing READMEs for search in the background
+pub fn spawn_readme_index_backfill_task(state: Arc<AppState>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        match backfill_readme_index(&state).await {
+            Ok(0) => {}
+            Ok(indexed) => info!("Indexed {indexed} README(s) for search"),
+            Err(e) => error!("Failed to index READMEs for search: {e}"),
+        }
+    })
+}
+
+/// Periodically run the storage consistency scan
+pub fn spawn_storage_gc_task(state: Arc<AppState>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let settings = state.config.storage_gc_settings.clone();
+        let mut ticker =
+            tokio::time::interval(std::time::Duration::from_secs(settings.interval_secs));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        let request = ConsistencyScanRequest {
+            delete: settings.delete,
+            space: None,
+        };
+
+        loop {
+            ticker.tick().await;
+
+            match scan_storage_consistency(&state, &request).await {
+                Ok(report) if report.issues.is_empty() => {}
+                Ok(report) => warn!(
+                    "Storage scan found {} issue(s), {} orphaned bytes{}",
+                    report.issues.len(),
+                    report.orphaned_bytes(),
+                    if report.dry_run {
+                        ""
+                    } else {
+                        ", orphans deleted"
+                    }
+                ),
+                Err(e) => error!("Storage consistency scan failed: {e}"),
+            }
+        }
+    })
+}
@@ -6,16 +6,54 @@ use axum::{
     http::StatusCode,
     response::IntoResponse,
 };
+use opsml_agent::{AgentError, JobState};
 use opsml_auth::permission::UserPermissions;
 use opsml_events::AuditContext;
 use opsml_types::{
     RegistryType,
-    contracts::{InvokeRequest, InvokeResponse, Operation, ResourceType},
+    contracts::{
+        InvocationMode, InvokeMetadata, InvokeRequest, InvokeResponse, Operation, ResourceType,
+    },
 };
 use std::sync::Arc;
 use tracing::{error, instrument};
 
-/// Synchronously invoke an agent by id.
+fn job_response(job: JobState) -> InvokeResponse {
+    InvokeResponse {
+        job_id: job.id,
+        status: job.status,
+        result: job.result,
+        error: job.error,
+        session_id: job.session_id,
+        prompt: job.prompt,
+        metadata: InvokeMetadata {
+            agent_id: job.agent_id,
+            invocation: InvocationMode::Async.to_string(),
+            duration_ms: job.duration_ms,
+        },
+    }
+}
+
+/// Unknown or expired sessions are a client error rather than an agent failure
+fn invoke_error(e: AgentError, msg: &str) -> (StatusCode, Json<OpsmlServerError>) {
+    match e {
+        AgentError::SessionNotFound(session_id) => (
+            StatusCode::NOT_FOUND,
+            Json(OpsmlServerError::not_found(&format!(
+                "Session '{session_id}'"
+            ))),
+        ),
+        e => internal_server_error(e, msg, None),
+    }
+}
+
+fn can_access_job(job: &JobState, perms: &UserPermissions) -> bool {
+    job.owner == perms.username || perms.is_admin()
+}
+
+/// Invoke an agent by id. Sync invocations return the result; async invocations return a
+/// pending job that can be polled. Pass the returned `session_id` to continue a conversation,
+/// e.g. to answer an agent that stopped with `NeedsInput`.
 ///
 /// POST /opsml/api/v1/agent/{id}/invoke
 #[utoipa::path(
@@ -27,8 +65,9 @@ use tracing::{error, instrument};
     request_body = InvokeRequest,
     responses(
         (status = 200, description = "Agent invocation result", body = InvokeResponse),
+        (status = 202, description = "Async job accepted", body = InvokeResponse),
         (status = 403, description = "Forbidden", body = OpsmlServerError),
-        (status = 404, description = "Agent not found", body = OpsmlServerError),
+        (status = 404, description = "Agent or session not found", body = OpsmlServerError),
         (status = 500, description = "Internal error", body = OpsmlServerError),
     ),
     security(("bearer_token" = [])),
@@ -62,12 +101,30 @@ pub async fn invoke_agent(
         None => body.input.to_string(),
     };
 
-    let invoke_response = state.agent_store.invoke(&id, &input).await.map_err(|e| {
-        error!("Agent invoke failed for {id}: {e}");
-        internal_server_error(e, "Agent invocation failed", None)
-    })?;
-
-    let mut response = Json(invoke_response).into_response();
+    let mut response = match body.mode {
+        InvocationMode::Sync => {
+            let invoke_response = state
+                .agent_store
+                .invoke_in_session(&id, &input, body.session_id.as_deref(), &perms.username)
+                .await
+                .map_err(|e| {
+                    error!("Agent invoke failed for {id}: {e}");
+                    invoke_error(e, "Agent invocation failed")
+                })?;
+            Json(invoke_response).into_response()
+        }
+        InvocationMode::Async => {
+            let job = state
+                .agent_store
+                .invoke_async(&id, &input, body.session_id.as_deref(), &perms.username)
+                .await
+                .map_err(|e| {
+                    error!("Failed to queue agent job for {id}: {e}");
+                    invoke_error(e, "Failed to queue agent job")
+                })?;
+            (StatusCode::ACCEPTED, Json(job_response(job))).into_response()
+        }
+    };
     response.extensions_mut().insert(AuditContext {
         resource_id: id.clone(),
         resource_type: ResourceType::Card,
@@ -102,39 +159,101 @@ pub async fn invoke_agent(
 pub async fn get_agent_job(
     State(state): State<Arc<AppState>>,
     Extension(perms): Extension<UserPermissions>,
-    Path((_id, job_id)): Path<(String, String)>,
+    Path((id, job_id)): Path<(String, String)>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<OpsmlServerError>)> {
-    let job = state.agent_store.get_job(&job_id).await.ok_or_else(|| {
+    let job = state
+        .agent_store
+        .get_job(&job_id)
+        .await
+        .filter(|job| job.agent_id == id)
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(OpsmlServerError::not_found(&format!("Job '{job_id}'"))),
+            )
+        })?;
+
+    if !can_access_job(&job, &perms) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(OpsmlServerError::permission_denied()),
+        ));
+    }
+
+    let mut response = Json(job_response(job)).into_response();
+    response.extensions_mut().insert(AuditContext {
+        resource_id: job_id.clone(),
+        resource_type: ResourceType::Card,
+        metadata: format!("job_id={job_id} user={}", perms.username),
+        operation: Operation::Read,
+        registry_type: Some(RegistryType::SubAgent),
+        access_location: None,
+    });
+    Ok(response)
+}
+
+/// Cancel a queued or running agent job. Finished jobs are returned unchanged.
+///
+/// DELETE /opsml/api/v1/agent/{id}/jobs/{job_id}
+#[utoipa::path(
+    delete,
+    path = "/opsml/api/v1/agent/{id}/jobs/{job_id}",
+    params(
+        ("id" = String, Path, description = "Agent ID"),
+        ("job_id" = String, Path, description = "Job ID to cancel"),
+    ),
+    responses(
+        (status = 200, description = "Job state after cancellation", body = InvokeResponse),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 404, description = "Job not found", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "agentic"
+)]
+#[instrument(skip_all)]
+pub async fn cancel_agent_job(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Path((id, job_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, (StatusCode, Json<OpsmlServerError>)> {
+    let not_found = || {
         (
             StatusCode::NOT_FOUND,
             Json(OpsmlServerError::not_found(&format!("Job '{job_id}'"))),
         )
-    })?;
+    };
+
+    let job = state
+        .agent_store
+        .get_job(&job_id)
+        .await
+        .filter(|job| job.agent_id == id)
+        .ok_or_else(not_found)?;
 
-    if job.owner != perms.username && !perms.group_permissions.contains(&"admin".to_string()) {
+    if !can_access_job(&job, &perms) {
         return Err((
             StatusCode::FORBIDDEN,
             Json(OpsmlServerError::permission_denied()),
         ));
     }
 
-    let invoke_response = InvokeResponse {
-        job_id: job.id,
-        status: job.status,
-        result: job.result,
-        metadata: opsml_types::contracts::InvokeMetadata {
-            agent_id: job.agent_id.clone(),
-            invocation: "async".to_string(),
-            duration_ms: job.duration_ms,
-        },
-    };
+    let job = state
+        .agent_store
+        .cancel_job(&job_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to cancel agent job {job_id}: {e}");
+            internal_server_error(e, "Failed to cancel agent job", None)
+        })?
+        .ok_or_else(not_found)?;
 
-    let mut response = Json(invoke_response).into_response();
+    let mut response = Json(job_response(job)).into_response();
     response.extensions_mut().insert(AuditContext {
         resource_id: job_id.clone(),
         resource_type: ResourceType::Card,
         metadata: format!("job_id={job_id} user={}", perms.username),
-        operation: Operation::Read,
+        operation: Operation::Delete,
         registry_type: Some(RegistryType::SubAgent),
         access_location: None,
     });
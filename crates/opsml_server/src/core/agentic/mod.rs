@@ -1,3 +1,4 @@
 pub mod agent_route;
 pub mod route;
 pub mod schema;
+pub mod workflow_route;
@@ -1,6 +1,7 @@
-use crate::core::agentic::agent_route::{get_agent_job, invoke_agent};
+use crate::core::agentic::agent_route::{cancel_agent_job, get_agent_job, invoke_agent};
 use crate::core::agentic::schema::{ArtifactMeta, MapResponse};
-use crate::core::error::{OpsmlServerError, internal_server_error};
+use crate::core::agentic::workflow_route::run_workflow;
+use crate::core::error::{OpsmlServerError, ServerError, internal_server_error};
 use crate::core::state::AppState;
 use anyhow::Result;
 use axum::{
@@ -388,7 +389,7 @@ pub async fn get_marketplace_stats(
 }
 
 /// Download, decrypt, and convert a skill card artifact to markdown.
-async fn load_skill_markdown(
+pub(crate) async fn load_skill_markdown(
     state: &Arc<AppState>,
     uid: &str,
 ) -> Result<String, (StatusCode, Json<OpsmlServerError>)> {
@@ -398,6 +399,8 @@ async fn load_skill_markdown(
         .sql_client
         .get_artifact_key(uid, &registry_type)
         .await
+        .map_err(ServerError::from)
+        .and_then(|key| state.open_artifact_key(&key))
         .map_err(|e| {
             error!("Failed to get artifact key for {uid}: {e}");
             internal_server_error(e, "Failed to get artifact key", None)
@@ -597,6 +600,8 @@ async fn load_subagent_markdown(
         .sql_client
         .get_artifact_key(uid, &registry_type)
         .await
+        .map_err(ServerError::from)
+        .and_then(|key| state.open_artifact_key(&key))
         .map_err(|e| {
             error!("Failed to get artifact key for {uid}: {e}");
             internal_server_error(e, "Failed to get artifact key", None)
@@ -790,6 +795,8 @@ async fn load_tool_markdown(
         .sql_client
         .get_artifact_key(uid, &registry_type)
         .await
+        .map_err(ServerError::from)
+        .and_then(|key| state.open_artifact_key(&key))
         .map_err(|e| {
             error!("Failed to get artifact key for {uid}: {e}");
             internal_server_error(e, "Failed to get artifact key", None)
@@ -850,8 +857,9 @@ pub async fn get_agentic_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
         )
         .route(
             &format!("{prefix}/v1/agent/{{id}}/jobs/{{job_id}}"),
-            get(get_agent_job),
+            get(get_agent_job).delete(cancel_agent_job),
         )
+        .route(&format!("{prefix}/v1/workflow/run"), post(run_workflow))
         .route(
             &format!("{prefix}/v1/skill/{{space}}/{{name}}"),
             get(get_skill_latest),
@@ -0,0 +1,293 @@
+use crate::core::agentic::route::load_skill_markdown;
+use crate::core::error::{OpsmlServerError, internal_server_error};
+use crate::core::state::AppState;
+use async_trait::async_trait;
+use axum::{Extension, Json, extract::State, http::StatusCode, response::IntoResponse};
+use opsml_agent::{ResolvedCard, StepAction, StepExecutor, WorkflowError, WorkflowRunner};
+use opsml_auth::permission::UserPermissions;
+use opsml_events::AuditContext;
+use opsml_sql::schemas::CardResults;
+use opsml_sql::traits::CardLogicTrait;
+use opsml_types::{
+    CardTable, RegistryType,
+    contracts::{
+        CardQueryArgs, CardRef, JobStatus, Operation, ResourceType, WorkflowRun,
+        WorkflowRunRequest, WorkflowSpec,
+    },
+};
+use serde_json::{Map, Value, json};
+use std::sync::Arc;
+use tracing::{error, instrument};
+
+/// Executes workflow steps against this server's registries and agent store.
+///
+/// Card references are resolved with the caller's permissions, so a workflow can only run
+/// skills and agents the caller could read directly.
+struct ServerStepExecutor {
+    state: Arc<AppState>,
+    perms: UserPermissions,
+}
+
+impl ServerStepExecutor {
+    /// Id of the loaded agent that serves a resolved agent card. Agents are matched on the card
+    /// uid or on its pinned `space/name@version`, so a step always runs the version it resolved to
+    fn agent_id(&self, card: &ResolvedCard) -> Result<String, WorkflowError> {
+        [card.uid.clone(), card.to_string()]
+            .into_iter()
+            .find(|id| self.state.agent_store.has(id))
+            .ok_or_else(|| {
+                WorkflowError::Unsupported(format!(
+                    "agent {card} (uid {}) is registered but not loaded on this server",
+                    card.uid
+                ))
+            })
+    }
+}
+
+/// Reason a step the server runner has no backend for is rejected
+fn unsupported_step(action: &StepAction) -> String {
+    match (action, action.card()) {
+        (StepAction::Tool(tool), _) => format!(
+            "tool step '{}' cannot be executed by the server runner",
+            tool.name
+        ),
+        (_, Some(card)) => format!(
+            "{} step ({card}) cannot be executed by the server runner",
+            action.kind()
+        ),
+        (_, None) => format!(
+            "{} step cannot be executed by the server runner",
+            action.kind()
+        ),
+    }
+}
+
+#[async_trait]
+impl StepExecutor for ServerStepExecutor {
+    async fn resolve(
+        &self,
+        card: &CardRef,
+        registry_type: &RegistryType,
+    ) -> Result<ResolvedCard, WorkflowError> {
+        let display = format!("{registry_type} '{}/{}'", card.space, card.name);
+        if !self.perms.has_read_permission(&card.space) {
+            return Err(WorkflowError::Resolve(format!(
+                "{display}: permission denied"
+            )));
+        }
+
+        let args = CardQueryArgs {
+            space: Some(card.space.clone()),
+            name: Some(card.name.clone()),
+            version: card.version.clone(),
+            limit: Some(1),
+            sort_by_timestamp: Some(true),
+            registry_type: registry_type.clone(),
+            ..Default::default()
+        };
+        let results = self
+            .state
+            .sql_client
+            .query_cards(&CardTable::from_registry_type(registry_type), &args)
+            .await
+            .map_err(|e| WorkflowError::Resolve(format!("{display}: {e}")))?;
+
+        let (uid, version) = results
+            .uid_versions()
+            .first()
+            .map(|(uid, version)| (uid.to_string(), version.to_string()))
+            .ok_or_else(|| WorkflowError::Resolve(format!("{display} not found")))?;
+
+        Ok(ResolvedCard {
+            space: card.space.clone(),
+            name: card.name.clone(),
+            version,
+            uid,
+        })
+    }
+
+    fn check(&self, action: &StepAction) -> Result<(), WorkflowError> {
+        match action {
+            StepAction::Agent(card) => self.agent_id(card).map(|_| ()),
+            StepAction::Mcp(_) | StepAction::Tool(_) => {
+                Err(WorkflowError::Unsupported(unsupported_step(action)))
+            }
+            StepAction::Skill(_) | StepAction::Prompt(_) => Ok(()),
+        }
+    }
+
+    async fn execute(
+        &self,
+        action: &StepAction,
+        inputs: &Map<String, Value>,
+    ) -> Result<Value, WorkflowError> {
+        match action {
+            StepAction::Skill(card) => {
+                let content = load_skill_markdown(&self.state, &card.uid)
+                    .await
+                    .map_err(|(_, Json(e))| WorkflowError::Step(e.error))?;
+                Ok(json!({ "content": content }))
+            }
+            StepAction::Agent(card) => {
+                let id = self.agent_id(card)?;
+                let response = self
+                    .state
+                    .agent_store
+                    .invoke(&id, &Value::Object(inputs.clone()).to_string())
+                    .await?;
+                match response.status {
+                    JobStatus::Done => Ok(response.result.unwrap_or(Value::Null)),
+                    JobStatus::NeedsInput => Err(WorkflowError::Step(format!(
+                        "agent {card} asked for input: {}",
+                        response.prompt.unwrap_or_default()
+                    ))),
+                    status => Err(WorkflowError::Step(format!(
+                        "agent {card} finished with status {status}: {}",
+                        response.error.unwrap_or_default()
+                    ))),
+                }
+            }
+            StepAction::Prompt(text) => Ok(json!({ "text": text })),
+            StepAction::Mcp(_) | StepAction::Tool(_) => {
+                Err(WorkflowError::Unsupported(unsupported_step(action)))
+            }
+        }
+    }
+}
+
+fn bad_request(message: &str) -> (StatusCode, Json<OpsmlServerError>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(OpsmlServerError::bad_request(message)),
+    )
+}
+
+/// Load the workflow attached to a registered workflow ServiceCard
+async fn load_workflow_spec(
+    state: &AppState,
+    request: &WorkflowRunRequest,
+) -> Result<(String, WorkflowSpec), (StatusCode, Json<OpsmlServerError>)> {
+    let not_found = || {
+        (
+            StatusCode::NOT_FOUND,
+            Json(OpsmlServerError::not_found(&format!(
+                "Workflow '{}/{}'",
+                request.space, request.name
+            ))),
+        )
+    };
+
+    let args = CardQueryArgs {
+        space: Some(request.space.clone()),
+        name: Some(request.name.clone()),
+        version: request.version.clone(),
+        limit: Some(1),
+        sort_by_timestamp: Some(true),
+        registry_type: RegistryType::Service,
+        ..Default::default()
+    };
+    let results = state
+        .sql_client
+        .query_cards(&CardTable::Service, &args)
+        .await
+        .map_err(|e| {
+            error!("Failed to query workflow card: {e}");
+            internal_server_error(e, "Failed to query workflow card", None)
+        })?;
+
+    let CardResults::Service(cards) = results else {
+        return Err(not_found());
+    };
+    let card = cards.into_iter().next().ok_or_else(not_found)?;
+    let spec = card
+        .service_config
+        .and_then(|config| config.0.workflow)
+        .ok_or_else(|| bad_request("Service card does not define a workflow"))?;
+
+    Ok((card.uid, spec))
+}
+
+/// Run a workflow and return the per-step trace.
+///
+/// Runs the workflow attached to the ServiceCard identified by `space`/`name`/`version`, or the
+/// inline `spec` when provided. Steps run synchronously within the request; step failures are
+/// reported in the returned run rather than as an error status.
+///
+/// POST /opsml/api/v1/workflow/run
+#[utoipa::path(
+    post,
+    path = "/opsml/api/v1/workflow/run",
+    request_body = WorkflowRunRequest,
+    responses(
+        (status = 200, description = "Workflow run trace", body = WorkflowRun),
+        (status = 400, description = "Invalid workflow or inputs", body = OpsmlServerError),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 404, description = "Workflow or referenced card not found", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "agentic"
+)]
+#[instrument(skip_all)]
+pub async fn run_workflow(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(request): Json<WorkflowRunRequest>,
+) -> Result<impl IntoResponse, (StatusCode, Json<OpsmlServerError>)> {
+    // Running a workflow invokes agents, so it needs the same access as invoking one directly
+    if !perms.has_write_permission(&request.space) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(OpsmlServerError::permission_denied()),
+        ));
+    }
+
+    let (resource_id, spec) = match request.spec.clone() {
+        Some(spec) => (format!("{}/{}", request.space, request.name), spec),
+        None => {
+            if !perms.has_read_permission(&request.space) {
+                return Err((
+                    StatusCode::FORBIDDEN,
+                    Json(OpsmlServerError::permission_denied()),
+                ));
+            }
+            load_workflow_spec(&state, &request).await?
+        }
+    };
+
+    let executor = ServerStepExecutor {
+        state: state.clone(),
+        perms: perms.clone(),
+    };
+    let run = WorkflowRunner::new(Arc::new(executor))
+        .run(&spec, request.inputs)
+        .await
+        .map_err(|e| match e {
+            WorkflowError::Invalid(_)
+            | WorkflowError::MissingInput(_)
+            | WorkflowError::InvalidInput(_)
+            | WorkflowError::Unsupported(_) => bad_request(&e.to_string()),
+            WorkflowError::Resolve(_) => (
+                StatusCode::NOT_FOUND,
+                Json(OpsmlServerError::new(e.to_string())),
+            ),
+            e => {
+                error!("Workflow run failed: {e}");
+                internal_server_error(e, "Workflow run failed", None)
+            }
+        })?;
+
+    let mut response = Json(run).into_response();
+    response.extensions_mut().insert(AuditContext {
+        resource_id,
+        resource_type: ResourceType::Card,
+        metadata: format!(
+            "workflow={}/{} user={}",
+            request.space, request.name, perms.username
+        ),
+        operation: Operation::Create,
+        registry_type: Some(RegistryType::Service),
+        access_location: None,
+    });
+    Ok(response)
+}
@@ -1,13 +1,17 @@
+use crate::core::admin::utils::{
+    spawn_readme_index_backfill_task, spawn_storage_gc_task, spawn_usage_backfill_task,
+};
 use crate::core::audit::AuditEventHandler;
 use crate::core::router::create_router;
 use crate::core::setup::{initialize_default_user, setup_components};
 use crate::core::state::AppState;
+use crate::core::trash::utils::spawn_retention_task;
 use anyhow::Ok;
 use anyhow::Result;
 use axum::Router;
 use opsml_agent::AgentStore;
 use opsml_auth::auth::AuthManager;
-use opsml_events::EventBus;
+use opsml_events::{EventBus, EventDispatcher};
 use std::sync::Arc;
 use tracing::{info, warn};
 
@@ -15,6 +19,7 @@ pub async fn create_app_with_state() -> Result<(Router, Arc<AppState>)> {
     // setup components (config, logging, storage client)
     let (config, storage_client, sql_client, scouter_client) = setup_components().await?;
     let storage_settings = config.storage_settings()?;
+    let key_provider = storage_settings.key_provider()?;
 
     // Setup basic auth and sso
     let auth_manager = AuthManager::new(
@@ -24,19 +29,31 @@ pub async fn create_app_with_state() -> Result<(Router, Arc<AppState>)> {
     )
     .await?;
 
-    // Build agent store
-    let agent_store = Arc::new(AgentStore::new(&config.agent_settings).await?);
+    let sql_client = Arc::new(sql_client);
+
+    // Build agent store, persisting async jobs alongside the registries
+    let agent_store = Arc::new(
+        AgentStore::new(&config.agent_settings)
+            .await?
+            .with_job_store(Arc::clone(&sql_client)),
+    );
+
+    // Jobs still pending or running belonged to a previous process and will never complete
+    if let Err(e) = agent_store.recover_interrupted_jobs().await {
+        warn!("Failed to recover interrupted agent jobs: {e}");
+    }
+    agent_store.spawn_job_reaper();
 
     // Create shared state for the application (storage client, auth manager, config)
-    let sql_client = Arc::new(sql_client);
     let app_state = Arc::new(AppState {
         storage_client: Arc::new(storage_client),
         sql_client: Arc::clone(&sql_client),
         auth_manager,
         config,
         storage_settings,
+        key_provider,
         scouter_client,
-        event_bus: EventBus::new(100),
+        event_bus: EventBus::new(100).with_outbox(Arc::clone(&sql_client)),
         agent_store,
     });
 
@@ -47,6 +64,31 @@ pub async fn create_app_with_state() -> Result<(Router, Arc<AppState>)> {
     let event_handler = AuditEventHandler::new(app_state.clone());
     event_handler.start().await;
 
+    // Relay outbox events to webhooks and other sinks
+    if app_state.config.webhook_settings.enabled {
+        EventDispatcher::new(
+            Arc::clone(&app_state.sql_client),
+            &app_state.config.webhook_settings,
+        )?
+        .start();
+    }
+
+    // Apply space retention policies in the background
+    if app_state.config.retention_settings.enabled {
+        spawn_retention_task(app_state.clone());
+    }
+
+    // Count storage written before usage was tracked
+    spawn_usage_backfill_task(app_state.clone());
+
+    // Index READMEs written before card search existed
+    spawn_readme_index_backfill_task(app_state.clone());
+
+    // Scan for orphaned artifacts on a schedule
+    if app_state.config.storage_gc_settings.enabled {
+        spawn_storage_gc_task(app_state.clone());
+    }
+
     // Initialize default user if none exists
     if let Err(e) = initialize_default_user(&app_state.sql_client, &app_state.scouter_client).await
     {
@@ -41,6 +41,8 @@ impl AuditEventHandler {
                             error!("Failed to log space name event: {e}");
                         }
                     }
+                    // already persisted to the outbox and relayed by the event dispatcher
+                    Event::Registry(_) => {}
                 }
             }
         });
@@ -1,3 +1,4 @@
 pub mod handler;
+pub mod route;
 pub mod schema;
 pub use handler::AuditEventHandler;
@@ -0,0 +1,238 @@
+use crate::core::auth::util::require_admin;
+use crate::core::error::{OpsmlServerError, internal_server_error};
+use crate::core::state::AppState;
+use anyhow::{Context, Result};
+use axum::{
+    Extension, Json, Router,
+    body::{Body, Bytes},
+    extract::{Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use futures::stream;
+use opsml_auth::permission::UserPermissions;
+use opsml_events::AuditContext;
+use opsml_sql::enums::client::SqlClientEnum;
+use opsml_sql::error::SqlError;
+use opsml_sql::traits::AuditLogicTrait;
+use opsml_types::contracts::{
+    AuditEventRecord, AuditExportFormat, AuditPageResponse, AuditQueryArgs, Operation, ResourceType,
+};
+use serde::Deserialize;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::Arc;
+use tracing::{error, instrument};
+
+const DEFAULT_AUDIT_PAGE_SIZE: i32 = 100;
+const MAX_AUDIT_PAGE_SIZE: i32 = 1000;
+
+#[derive(Debug, Deserialize, Default)]
+pub struct AuditExportQuery {
+    #[serde(default)]
+    pub format: AuditExportFormat,
+}
+
+fn clamp_limit(limit: Option<i32>) -> i32 {
+    limit
+        .unwrap_or(DEFAULT_AUDIT_PAGE_SIZE)
+        .clamp(1, MAX_AUDIT_PAGE_SIZE)
+}
+
+fn audit_context(resource_id: &str, args: &AuditQueryArgs) -> AuditContext {
+    AuditContext {
+        resource_id: resource_id.to_string(),
+        resource_type: ResourceType::Database,
+        metadata: serde_json::to_string(args)
+            .unwrap_or_else(|e| format!("Failed to serialize AuditQueryArgs: {e}")),
+        registry_type: None,
+        operation: Operation::Read,
+        access_location: None,
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/opsml/api/audit",
+    params(
+        ("username" = Option<String>, Query, description = "Filter by username"),
+        ("operation" = Option<String>, Query, description = "Filter by operation (e.g. Read, Delete)"),
+        ("resource_type" = Option<String>, Query, description = "Filter by resource type (e.g. File, Database)"),
+        ("resource_id" = Option<String>, Query, description = "Filter by resource id"),
+        ("registry_type" = Option<String>, Query, description = "Filter by registry type"),
+        ("status" = Option<String>, Query, description = "Filter by status (Success, Failed, Denied)"),
+        ("start_time" = Option<String>, Query, description = "Only events at or after this RFC 3339 timestamp"),
+        ("end_time" = Option<String>, Query, description = "Only events at or before this RFC 3339 timestamp"),
+        ("cursor" = Option<i64>, Query, description = "Cursor returned by the previous page"),
+        ("limit" = Option<i32>, Query, description = "Page size (max 1000)"),
+    ),
+    responses(
+        (status = 200, description = "Page of audit events, newest first", body = AuditPageResponse),
+        (status = 403, description = "Forbidden — requires admin", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "audit"
+)]
+/// Query audit events
+///
+/// Requires admin permissions
+#[instrument(skip_all)]
+pub async fn query_audit_events(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(mut args): Query<AuditQueryArgs>,
+) -> Result<Response, (StatusCode, Json<OpsmlServerError>)> {
+    require_admin(&perms)?;
+
+    let limit = clamp_limit(args.limit);
+    args.limit = Some(limit);
+
+    let events = state
+        .sql_client
+        .query_audit_events(&args)
+        .await
+        .map_err(|e| {
+            error!("Failed to query audit events: {e}");
+            internal_server_error(e, "Failed to query audit events", None)
+        })?;
+
+    let next_cursor = if events.len() as i32 == limit {
+        events.last().map(|e| e.id)
+    } else {
+        None
+    };
+
+    let mut response = Json(AuditPageResponse {
+        events,
+        next_cursor,
+    })
+    .into_response();
+
+    response
+        .extensions_mut()
+        .insert(audit_context("audit_query", &args));
+
+    Ok(response)
+}
+
+/// Paginates through every event matching `args` and yields them as encoded chunks
+fn export_stream(
+    sql_client: Arc<SqlClientEnum>,
+    mut args: AuditQueryArgs,
+    format: AuditExportFormat,
+) -> impl futures::Stream<Item = Result<Bytes, SqlError>> {
+    args.limit = Some(MAX_AUDIT_PAGE_SIZE);
+
+    // (args, header_written, exhausted)
+    stream::try_unfold(
+        (args, false, false),
+        move |(mut args, header_written, exhausted)| {
+            let sql_client = sql_client.clone();
+            let format = format.clone();
+            async move {
+                if exhausted {
+                    return Ok(None);
+                }
+
+                let events = sql_client.query_audit_events(&args).await?;
+                let exhausted = (events.len() as i32) < MAX_AUDIT_PAGE_SIZE;
+                args.cursor = events.last().map(|e| e.id);
+
+                let mut chunk = String::new();
+                if format == AuditExportFormat::Csv && !header_written {
+                    chunk.push_str(AuditEventRecord::CSV_HEADER);
+                    chunk.push('\n');
+                }
+
+                for event in &events {
+                    match format {
+                        AuditExportFormat::Ndjson => {
+                            chunk.push_str(&serde_json::to_string(event)?);
+                        }
+                        AuditExportFormat::Csv => chunk.push_str(&event.to_csv_row()),
+                    }
+                    chunk.push('\n');
+                }
+
+                Ok(Some((Bytes::from(chunk), (args, true, exhausted))))
+            }
+        },
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/opsml/api/audit/export",
+    params(
+        ("format" = Option<AuditExportFormat>, Query, description = "Export format: ndjson (default) or csv"),
+        ("username" = Option<String>, Query, description = "Filter by username"),
+        ("operation" = Option<String>, Query, description = "Filter by operation (e.g. Read, Delete)"),
+        ("resource_type" = Option<String>, Query, description = "Filter by resource type (e.g. File, Database)"),
+        ("resource_id" = Option<String>, Query, description = "Filter by resource id"),
+        ("registry_type" = Option<String>, Query, description = "Filter by registry type"),
+        ("status" = Option<String>, Query, description = "Filter by status (Success, Failed, Denied)"),
+        ("start_time" = Option<String>, Query, description = "Only events at or after this RFC 3339 timestamp"),
+        ("end_time" = Option<String>, Query, description = "Only events at or before this RFC 3339 timestamp"),
+    ),
+    responses(
+        (status = 200, description = "Streamed export of all matching audit events", content_type = "application/x-ndjson"),
+        (status = 403, description = "Forbidden — requires admin", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "audit"
+)]
+/// Export all matching audit events as NDJSON or CSV
+///
+/// Requires admin permissions
+#[instrument(skip_all)]
+pub async fn export_audit_events(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(args): Query<AuditQueryArgs>,
+    Query(export): Query<AuditExportQuery>,
+) -> Result<Response, (StatusCode, Json<OpsmlServerError>)> {
+    require_admin(&perms)?;
+
+    let (content_type, filename) = match export.format {
+        AuditExportFormat::Ndjson => ("application/x-ndjson", "audit_events.ndjson"),
+        AuditExportFormat::Csv => ("text/csv", "audit_events.csv"),
+    };
+
+    let context = audit_context("audit_export", &args);
+    let body = Body::from_stream(export_stream(state.sql_client.clone(), args, export.format));
+
+    let mut response = (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        body,
+    )
+        .into_response();
+
+    response.extensions_mut().insert(context);
+
+    Ok(response)
+}
+
+pub async fn get_audit_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        Router::new()
+            .route(&format!("{prefix}/audit"), get(query_audit_events))
+            .route(&format!("{prefix}/audit/export"), get(export_audit_events))
+    }));
+
+    match result {
+        Ok(router) => Ok(router),
+        Err(_) => {
+            error!("Failed to create audit router");
+            Err(anyhow::anyhow!("Failed to create audit router"))
+                .context("Panic occurred while creating the router")
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use crate::core::auth::middleware::header::HeaderValue;
 use crate::core::auth::schema::AuthError;
 use crate::core::state::AppState;
-use crate::core::user::utils::get_user;
+use crate::core::user::utils::{get_group_grants, get_user};
 use axum::http::{StatusCode, header};
 use axum::response::IntoResponse;
 use axum::{
@@ -11,6 +11,7 @@ use axum::{
 };
 use axum_extra::extract::cookie::CookieJar;
 use opsml_auth::permission::UserPermissions;
+use opsml_auth::token::{is_api_token, parse_api_token, verify_api_token};
 use opsml_sql::traits::UserLogicTrait;
 use serde::Serialize;
 use std::sync::Arc;
@@ -22,6 +23,73 @@ pub struct ErrorResponse {
     pub message: String,
 }
 
+fn unauthorized(message: &str) -> (StatusCode, Json<AuthError>) {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(AuthError {
+            error: "Unauthorized".to_string(),
+            message: message.to_string(),
+        }),
+    )
+}
+
+/// Resolves a personal API token to the permissions it was scoped to.
+/// Token permissions are intersected with the owner's current permissions so
+/// that downgrading a user also downgrades their tokens
+async fn authenticate_api_token(
+    state: &Arc<AppState>,
+    token: &str,
+) -> Result<UserPermissions, (StatusCode, Json<AuthError>)> {
+    let (token_id, _) = parse_api_token(token).map_err(|_| unauthorized("Invalid API token"))?;
+
+    let api_token = state
+        .sql_client
+        .get_api_token(token_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch api token: {e}");
+            unauthorized("Invalid API token")
+        })?
+        .ok_or_else(|| unauthorized("Invalid API token"))?;
+
+    verify_api_token(token, &api_token.token_hash)
+        .map_err(|_| unauthorized("Invalid API token"))?;
+
+    if api_token.revoked {
+        return Err(unauthorized("API token has been revoked"));
+    }
+
+    if api_token.is_expired() {
+        return Err(unauthorized("API token has expired"));
+    }
+
+    let user = get_user(&state.sql_client, &api_token.username, None)
+        .await
+        .map_err(|_| unauthorized("User not found"))?;
+
+    if !user.active {
+        return Err(unauthorized("User is inactive"));
+    }
+
+    let sql_client = state.sql_client.clone();
+    let token_id = api_token.token_id.clone();
+    tokio::spawn(async move {
+        if let Err(e) = sql_client.update_api_token_last_used(&token_id).await {
+            error!("Failed to update api token last used: {e}");
+        }
+    });
+
+    let grants = get_group_grants(&state.sql_client, &user.username)
+        .await
+        .map_err(|e| {
+            error!("Failed to resolve group permissions: {e}");
+            unauthorized("Failed to resolve permissions")
+        })?;
+    let owner = UserPermissions::resolve(&user, &grants);
+
+    Ok(owner.scope_to_token(&api_token.permissions))
+}
+
 pub async fn auth_api_middleware(
     cookie_jar: CookieJar,
     State(state): State<Arc<AppState>>,
@@ -56,61 +124,83 @@ pub async fn auth_api_middleware(
         )
     })?;
 
+    // personal API tokens are opaque and validated against the database
+    if is_api_token(&access_token) {
+        let permissions = authenticate_api_token(&state, &access_token).await?;
+        req.extensions_mut().insert(permissions);
+        return Ok(next.run(req).await.into_response());
+    }
+
     // validate the access token (this will also check if the token is expired)
-    let auth_middleware = match state.auth_manager.validate_jwt(&access_token) {
-        Ok(claims) => {
-            let permissions = claims.permissions.clone();
-            let group_permissions = claims.group_permissions.clone();
-            UserPermissions {
-                username: claims.sub,
-                permissions,
-                group_permissions,
+    let auth_middleware =
+        match state.auth_manager.validate_jwt(&access_token) {
+            Ok(claims) => {
+                let permissions = claims.permissions.clone();
+                let group_permissions = claims.group_permissions.clone();
+                UserPermissions {
+                    username: claims.sub,
+                    permissions,
+                    group_permissions,
+                }
             }
-        }
-        Err(_) => {
-            info!("Access token expired, attempting refresh");
-
-            let expired_claims = state
-                .auth_manager
-                .decode_jwt_without_validation(&access_token)
-                .map_err(|_| {
-                    (
-                        StatusCode::UNAUTHORIZED,
-                        Json(AuthError {
-                            error: "Unauthorized".to_string(),
-                            message: "Invalid token format".to_string(),
-                        }),
-                    )
-                })?;
-
-            let mut user = get_user(&state.sql_client, &expired_claims.sub, None)
-                .await
-                .map_err(|_| {
-                    (
-                        StatusCode::UNAUTHORIZED,
-                        Json(AuthError {
-                            error: "Unauthorized".to_string(),
-                            message: "User not found".to_string(),
-                        }),
-                    )
-                })?;
-
-            // Validate stored refresh token
-            if let Some(stored_refresh) = user.refresh_token.as_ref()
-                && state.auth_manager.validate_jwt(stored_refresh).is_ok()
-            {
-                // Generate new tokens
-                let new_access_token = state.auth_manager.generate_jwt(&user).map_err(|_| {
-                    (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        Json(AuthError {
-                            error: "Server Error".to_string(),
-                            message: "Failed to generate access token".to_string(),
-                        }),
-                    )
-                })?;
-                let new_refresh_token =
-                    state
+            Err(_) => {
+                info!("Access token expired, attempting refresh");
+
+                let expired_claims = state
+                    .auth_manager
+                    .decode_jwt_without_validation(&access_token)
+                    .map_err(|_| {
+                        (
+                            StatusCode::UNAUTHORIZED,
+                            Json(AuthError {
+                                error: "Unauthorized".to_string(),
+                                message: "Invalid token format".to_string(),
+                            }),
+                        )
+                    })?;
+
+                let mut user = get_user(&state.sql_client, &expired_claims.sub, None)
+                    .await
+                    .map_err(|_| {
+                        (
+                            StatusCode::UNAUTHORIZED,
+                            Json(AuthError {
+                                error: "Unauthorized".to_string(),
+                                message: "User not found".to_string(),
+                            }),
+                        )
+                    })?;
+
+                // Validate stored refresh token
+                if let Some(stored_refresh) = user.refresh_token.as_ref()
+                    && state.auth_manager.validate_jwt(stored_refresh).is_ok()
+                {
+                    let grants = get_group_grants(&state.sql_client, &user.username)
+                        .await
+                        .map_err(|_| {
+                            (
+                                StatusCode::INTERNAL_SERVER_ERROR,
+                                Json(AuthError {
+                                    error: "Server Error".to_string(),
+                                    message: "Failed to resolve group permissions".to_string(),
+                                }),
+                            )
+                        })?;
+
+                    // Generate new tokens
+                    let new_access_token = state
+                        .auth_manager
+                        .generate_jwt(&user, &grants)
+                        .map_err(|_| {
+                            (
+                                StatusCode::INTERNAL_SERVER_ERROR,
+                                Json(AuthError {
+                                    error: "Server Error".to_string(),
+                                    message: "Failed to generate access token".to_string(),
+                                }),
+                            )
+                        })?;
+                    let new_refresh_token = state
                         .auth_manager
                         .generate_refresh_token(&user)
                         .map_err(|_| {
@@ -123,54 +213,50 @@ pub async fn auth_api_middleware(
                             )
                         })?;
 
-                // Update refresh token in database
-                user.refresh_token = Some(new_refresh_token.clone());
-
-                if (state.sql_client.update_user(&user).await).is_err() {
-                    return Err((
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        Json(AuthError {
-                            error: "Server Error".to_string(),
-                            message: "Failed to update refresh token".to_string(),
-                        }),
-                    ));
+                    // Update refresh token in database
+                    user.refresh_token = Some(new_refresh_token.clone());
+
+                    if (state.sql_client.update_user(&user).await).is_err() {
+                        return Err((
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            Json(AuthError {
+                                error: "Server Error".to_string(),
+                                message: "Failed to update refresh token".to_string(),
+                            }),
+                        ));
+                    }
+
+                    let auth_middleware = UserPermissions::resolve(&user, &grants);
+                    req.extensions_mut().insert(auth_middleware);
+
+                    // Add new token to request headers for downstream handlers
+                    req.headers_mut().insert(
+                        header::AUTHORIZATION,
+                        HeaderValue::from_str(&format!("Bearer {new_access_token}")).unwrap(),
+                    );
+
+                    // Run the request and modify the response
+                    let response = next.run(req).await;
+                    let mut response = response.into_response();
+
+                    // Add new token to response headers
+                    response.headers_mut().insert(
+                        header::AUTHORIZATION,
+                        HeaderValue::from_str(&format!("Bearer {new_access_token}")).unwrap(),
+                    );
+
+                    return Ok(response);
                 }
 
-                let auth_middleware = UserPermissions {
-                    username: user.username,
-                    permissions: user.permissions,
-                    group_permissions: user.group_permissions,
-                };
-                req.extensions_mut().insert(auth_middleware);
-
-                // Add new token to request headers for downstream handlers
-                req.headers_mut().insert(
-                    header::AUTHORIZATION,
-                    HeaderValue::from_str(&format!("Bearer {new_access_token}")).unwrap(),
-                );
-
-                // Run the request and modify the response
-                let response = next.run(req).await;
-                let mut response = response.into_response();
-
-                // Add new token to response headers
-                response.headers_mut().insert(
-                    header::AUTHORIZATION,
-                    HeaderValue::from_str(&format!("Bearer {new_access_token}")).unwrap(),
-                );
-
-                return Ok(response);
+                return Err((
+                    StatusCode::UNAUTHORIZED,
+                    Json(AuthError {
+                        error: "Unauthorized".to_string(),
+                        message: "No refresh token found".to_string(),
+                    }),
+                ));
             }
-
-            return Err((
-                StatusCode::UNAUTHORIZED,
-                Json(AuthError {
-                    error: "Unauthorized".to_string(),
-                    message: "No refresh token found".to_string(),
-                }),
-            ));
-        }
-    };
+        };
 
     // add the auth middleware to the request extensions
     req.extensions_mut().insert(auth_middleware);
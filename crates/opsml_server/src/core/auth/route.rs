@@ -8,8 +8,8 @@ use crate::core::user::route::create_user;
 use crate::core::user::schema::RecoveryResetRequest;
 use crate::core::user::schema::ResetPasswordResponse;
 use crate::core::user::schema::{CreateUserRequest, CreateUserUiResponse, UserResponse};
-use crate::core::user::utils::get_user;
 use crate::core::user::utils::get_user as get_user_from_db;
+use crate::core::user::utils::{get_group_grants, get_user};
 use anyhow::{Context, Result};
 use axum::extract::Query;
 /// Route for debugging information
@@ -141,10 +141,20 @@ pub async fn api_login_handler(
     };
 
     // generate JWT token
-    let jwt_token = state.auth_manager.generate_jwt(&user).map_err(|e| {
-        error!("Failed to generate JWT token: {e}");
-        internal_server_error(e, "Failed to generate JWT token", None)
-    })?;
+    let grants = get_group_grants(&state.sql_client, &user.username)
+        .await
+        .map_err(|e| {
+            error!("Failed to resolve group permissions: {e}");
+            internal_server_error(e, "Failed to resolve group permissions", None)
+        })?;
+
+    let jwt_token = state
+        .auth_manager
+        .generate_jwt(&user, &grants)
+        .map_err(|e| {
+            error!("Failed to generate JWT token: {e}");
+            internal_server_error(e, "Failed to generate JWT token", None)
+        })?;
 
     // check if refresh token is already set.
     // if it is, check if its valid and return it
@@ -299,10 +309,20 @@ pub(crate) async fn ui_login_handler(
     }
 
     // generate JWT token
-    let jwt_token = state.auth_manager.generate_jwt(&user).map_err(|e| {
-        error!("Failed to generate JWT token: {e}");
-        internal_server_error(e, "Failed to generate JWT token", None)
-    })?;
+    let grants = get_group_grants(&state.sql_client, &user.username)
+        .await
+        .map_err(|e| {
+            error!("Failed to resolve group permissions: {e}");
+            internal_server_error(e, "Failed to resolve group permissions", None)
+        })?;
+
+    let jwt_token = state
+        .auth_manager
+        .generate_jwt(&user, &grants)
+        .map_err(|e| {
+            error!("Failed to generate JWT token: {e}");
+            internal_server_error(e, "Failed to generate JWT token", None)
+        })?;
 
     let refresh_token = state
         .auth_manager
@@ -407,12 +427,22 @@ pub async fn api_refresh_token_handler(
             })?;
 
         // generate new JWT token
-        let jwt_token = state.auth_manager.generate_jwt(&user).map_err(|_| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(OpsmlServerError::failed_token_generation()),
-            )
-        })?;
+        let grants = get_group_grants(&state.sql_client, &user.username)
+            .await
+            .map_err(|e| {
+                error!("Failed to resolve group permissions: {e}");
+                internal_server_error(e, "Failed to resolve group permissions", None)
+            })?;
+
+        let jwt_token = state
+            .auth_manager
+            .generate_jwt(&user, &grants)
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(OpsmlServerError::failed_token_generation()),
+                )
+            })?;
 
         // generate new refresh token
         info!("Generating new refresh token for user: {}", user.username);
@@ -567,10 +597,20 @@ pub(crate) async fn exchange_callback_token(
     let mut user = authenticate_user_with_sso_callback(&state, &code, &code_verifier).await?;
 
     // generate JWT token
-    let jwt_token = state.auth_manager.generate_jwt(&user).map_err(|e| {
-        error!("Failed to generate JWT token: {e}");
-        internal_server_error(e, "Failed to generate JWT token", None)
-    })?;
+    let grants = get_group_grants(&state.sql_client, &user.username)
+        .await
+        .map_err(|e| {
+            error!("Failed to resolve group permissions: {e}");
+            internal_server_error(e, "Failed to resolve group permissions", None)
+        })?;
+
+    let jwt_token = state
+        .auth_manager
+        .generate_jwt(&user, &grants)
+        .map_err(|e| {
+            error!("Failed to generate JWT token: {e}");
+            internal_server_error(e, "Failed to generate JWT token", None)
+        })?;
 
     let refresh_token = state
         .auth_manager
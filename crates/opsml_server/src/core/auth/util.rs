@@ -4,6 +4,8 @@ use crate::core::state::AppState;
 use anyhow::Result;
 /// Route for debugging information
 use axum::{Json, http::StatusCode};
+use opsml_auth::group::{GroupGrants, map_sso_groups};
+use opsml_auth::permission::UserPermissions;
 use opsml_auth::sso::types::UserInfo;
 use opsml_sql::schemas::User;
 use opsml_sql::traits::*;
@@ -95,7 +97,7 @@ async fn create_user(
     if state.scouter_client.is_enabled() {
         match state
             .auth_manager
-            .exchange_token_for_scouter(&new_user)
+            .exchange_token_for_scouter(&new_user, &GroupGrants::default())
             .await
         {
             Ok(exchange_token) => {
@@ -138,7 +140,7 @@ async fn validate_user_with_opsml(
 ) -> Result<User, (StatusCode, Json<OpsmlServerError>)> {
     // get user from database
     // check if user exists in db
-    match state
+    let opsml_user = match state
         .sql_client
         .get_user(&user.username, Some("sso"))
         .await
@@ -147,9 +149,8 @@ async fn validate_user_with_opsml(
             internal_server_error(e, "Failed to get user from database", None)
         })? {
         Some(opsml_user) => {
-            // user exists, return it
             info!("User {} found in database", user.username);
-            Ok(opsml_user)
+            opsml_user
         }
         None => {
             // user does not exist, create it
@@ -157,9 +158,52 @@ async fn validate_user_with_opsml(
                 "User {} not found in database, creating new user",
                 user.username
             );
-            create_user(state, user).await
+            create_user(state, user).await?
         }
+    };
+
+    sync_sso_groups(state, &opsml_user.username, &user.groups).await?;
+
+    Ok(opsml_user)
+}
+
+/// Brings a user's membership of SSO-mapped opsml groups in line with the
+/// groups claimed by the identity provider at login
+async fn sync_sso_groups(
+    state: &Arc<AppState>,
+    username: &str,
+    idp_groups: &[String],
+) -> Result<(), (StatusCode, Json<OpsmlServerError>)> {
+    let groups = state.sql_client.get_groups().await.map_err(|e| {
+        error!("Failed to get groups: {e}");
+        internal_server_error(e, "Failed to get groups", None)
+    })?;
+
+    let sync = map_sso_groups(&groups, idp_groups);
+
+    for group in &sync.join {
+        state
+            .sql_client
+            .add_group_member(group, username)
+            .await
+            .map_err(|e| {
+                error!("Failed to add user to group {group}: {e}");
+                internal_server_error(e, "Failed to sync SSO groups", None)
+            })?;
+    }
+
+    for group in &sync.leave {
+        state
+            .sql_client
+            .remove_group_member(group, username)
+            .await
+            .map_err(|e| {
+                error!("Failed to remove user from group {group}: {e}");
+                internal_server_error(e, "Failed to sync SSO groups", None)
+            })?;
     }
+
+    Ok(())
 }
 
 pub async fn authenticate_user_with_sso(
@@ -190,3 +234,11 @@ pub async fn authenticate_user_with_sso_callback(
 
     Ok(user)
 }
+
+/// Reject callers that are not in the admin group
+pub fn require_admin(perms: &UserPermissions) -> Result<(), (StatusCode, Json<OpsmlServerError>)> {
+    if !perms.is_admin() {
+        return OpsmlServerError::need_admin_permission().into_response(StatusCode::FORBIDDEN);
+    }
+    Ok(())
+}
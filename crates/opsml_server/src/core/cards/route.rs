@@ -4,10 +4,11 @@ use crate::core::cards::schema::PageInfo;
 use crate::core::cards::schema::{
     CreateReadeMe, QueryPageResponse, ReadeMe, RegistryStatsResponse, VersionPageResponse,
 };
-use crate::core::cards::utils::{cleanup_artifacts, insert_card_into_db, run_skill_scan};
-use crate::core::error::{OpsmlServerError, internal_server_error};
+use crate::core::cards::utils::{index_card, insert_card_into_db, run_skill_scan};
+use crate::core::error::{OpsmlServerError, ServerError, internal_server_error};
 use crate::core::files::utils::{
     create_and_store_encrypted_file, create_artifact_key, download_artifact, get_artifact_key,
+    record_written_file, stored_bytes,
 };
 use crate::core::state::AppState;
 use anyhow::{Context, Result};
@@ -22,16 +23,19 @@ use axum::{
 use opsml_auth::permission::UserPermissions;
 use opsml_crypt::decrypt_directory;
 use opsml_events::AuditContext;
-use opsml_sql::enums::utils::get_next_version;
+use opsml_sql::enums::utils::{get_next_version, search_readable_cards, trash_card};
+use opsml_sql::error::SqlError;
 use opsml_sql::schemas::*;
 use opsml_sql::traits::*;
 use opsml_types::contracts::{CompareHashRequest, CompareHashResponse};
 use opsml_types::{SaveName, Suffix};
 use opsml_types::{cards::*, contracts::*};
+use opsml_utils::utils::get_utc_datetime;
 use serde_qs;
 
 use serde::de::DeserializeOwned;
 use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::path::Path;
 use std::sync::Arc;
 use tempfile::tempdir;
 use tracing::{debug, error, info, instrument, warn};
@@ -158,11 +162,26 @@ pub async fn get_registry_tags(
 pub async fn get_all_space_stats(
     State(state): State<Arc<AppState>>,
 ) -> Result<Json<SpaceStatsResponse>, (StatusCode, Json<OpsmlServerError>)> {
-    let stats = state.sql_client.get_all_space_stats().await.map_err(|e| {
+    let mut stats = state.sql_client.get_all_space_stats().await.map_err(|e| {
         error!("Failed to get all space stats: {e}");
         internal_server_error(e, "Failed to get all space stats", None)
     })?;
 
+    let usage = state.sql_client.get_space_usage().await.map_err(|e| {
+        error!("Failed to get space usage: {e}");
+        internal_server_error(e, "Failed to get space usage", None)
+    })?;
+
+    for record in usage {
+        if let Some(space_stats) = stats.iter_mut().find(|stats| stats.space == record.space) {
+            space_stats.storage_bytes += record.bytes;
+            space_stats.usage.push(RegistryUsage {
+                registry_type: record.registry_type,
+                bytes: record.bytes,
+            });
+        }
+    }
+
     Ok(Json(SpaceStatsResponse { stats }))
 }
 
@@ -215,6 +234,7 @@ pub async fn create_space_record(
     let record = SpaceRecord {
         space: space_request.space,
         description: space_request.description.unwrap_or_default(),
+        ..Default::default()
     };
     state
         .sql_client
@@ -246,6 +266,7 @@ pub async fn update_space_record(
     let record = SpaceRecord {
         space: space_request.space,
         description: space_request.description.unwrap_or_default(),
+        ..Default::default()
     };
     state
         .sql_client
@@ -661,6 +682,8 @@ pub async fn create_card(
             .sql_client
             .get_artifact_key(&existing.uid, &card_request.registry_type.to_string())
             .await
+            .map_err(ServerError::from)
+            .and_then(|key| state.open_artifact_key(&key))
             .map_err(|e| internal_server_error(e, "Failed to get artifact key", None))?;
 
         info!(
@@ -716,7 +739,8 @@ pub async fn create_card(
         .await
         .map_err(|e| {
             error!("Failed to get next version: {e}");
-            internal_server_error(e, "Failed to get next version", None)
+            let status = matches!(e, SqlError::VersionInTrash(_)).then_some(StatusCode::CONFLICT);
+            internal_server_error(e, "Failed to get next version", status)
         })?;
 
         info!(
@@ -733,6 +757,7 @@ pub async fn create_card(
             card_request.card.clone(),
             version.clone(),
             &table,
+            &state.config.app_env,
         )
         .await
         {
@@ -794,7 +819,9 @@ pub async fn create_card(
                         }
                     }
                     let key = match key_opt {
-                        Some(k) => k,
+                        Some(k) => state.open_artifact_key(&k).map_err(|e| {
+                            internal_server_error(e, "Failed to get artifact key", None)
+                        })?,
                         None => {
                             return Err(internal_server_error(
                                 "artifact key not available after 3 retries",
@@ -855,6 +882,7 @@ pub async fn create_card(
     let key = create_artifact_key(
         &state.sql_client,
         &state.storage_settings.encryption_key,
+        &*state.key_provider,
         &uid,
         &space,
         &registry_type,
@@ -866,6 +894,11 @@ pub async fn create_card(
         internal_server_error(e, "Failed to create artifact key", None)
     })?;
 
+    let mut document =
+        CardSearchDocument::from_record(&card_request.card, &uid, &version.to_string());
+    document.registry_type = card_request.registry_type.clone();
+    index_card(&state.sql_client, &document).await;
+
     debug!("Card created successfully");
 
     let mut response = Json(CreateCardResponse {
@@ -897,10 +930,24 @@ pub async fn create_card(
         registry_type: card_request.registry_type.clone(),
     };
 
+    let registry_event = RegistryEvent::new(
+        RegistryEventType::CardCreated,
+        card_request.card.space(),
+        Some(card_request.registry_type.clone()),
+        &uid,
+        serde_json::json!({
+            "uid": uid,
+            "name": card_request.card.name(),
+            "version": version.to_string(),
+        }),
+        &perms.username,
+    );
+
     {
         let extensions = response.extensions_mut();
         extensions.insert(audit_context);
         extensions.insert(space_name);
+        extensions.insert(registry_event);
     }
 
     Ok(response)
@@ -921,6 +968,7 @@ pub async fn create_card(
 #[instrument(skip_all)]
 pub async fn update_card(
     State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
     Json(card_request): Json<UpdateCardRequest>,
 ) -> Result<Response, (StatusCode, Json<OpsmlServerError>)> {
     info!(
@@ -946,6 +994,14 @@ pub async fn update_card(
             internal_server_error(e, "Failed to update card", None)
         })?;
 
+    let mut document = CardSearchDocument::from_record(
+        &card_request.card,
+        card.uid(),
+        &card_request.card.version(),
+    );
+    document.registry_type = card_request.registry_type.clone();
+    index_card(&state.sql_client, &document).await;
+
     debug!("Card updated successfully");
     let mut response = Json(UpdateCardResponse { updated: true }).into_response();
 
@@ -958,7 +1014,24 @@ pub async fn update_card(
         access_location: None,
     };
 
-    response.extensions_mut().insert(audit_context);
+    let registry_event = RegistryEvent::new(
+        RegistryEventType::CardUpdated,
+        card_request.card.space(),
+        Some(card_request.registry_type.clone()),
+        card.uid(),
+        serde_json::json!({
+            "uid": card.uid(),
+            "name": card_request.card.name(),
+            "version": card_request.card.version(),
+        }),
+        &perms.username,
+    );
+
+    {
+        let extensions = response.extensions_mut();
+        extensions.insert(audit_context);
+        extensions.insert(registry_event);
+    }
 
     Ok(response)
 }
@@ -972,8 +1045,9 @@ pub async fn update_card(
         ("registry_type" = String, Query, description = "Registry type"),
     ),
     responses(
-        (status = 200, description = "Card deleted", body = UidResponse),
+        (status = 200, description = "Card moved to the trash", body = UidResponse),
         (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 404, description = "Card not found", body = OpsmlServerError),
         (status = 500, description = "Internal error", body = OpsmlServerError),
     ),
     security(("bearer_token" = [])),
@@ -993,29 +1067,26 @@ pub async fn delete_card(
 
     let table = CardTable::from_registry_type(&params.registry_type);
 
-    // delete the artifact key and the artifact itself
-    cleanup_artifacts(
-        &state.storage_client,
+    // move the card to the trash. Artifacts are kept until the card is purged
+    let trashed = trash_card(
         &state.sql_client,
-        params.uid.clone(),
-        params.registry_type.clone(),
-        &table,
+        &params.registry_type,
+        &params.uid,
+        TrashReason::Deleted,
+        &perms.username,
     )
     .await
-    .map_err(|e| {
-        error!("Failed to cleanup artifacts: {e}");
-        internal_server_error(e, "Failed to cleanup artifacts", None)
-    })?;
-
-    // delete card
-    let (space, name) = state
-        .sql_client
-        .delete_card(&table, &params.uid)
-        .await
-        .map_err(|e| {
+    .map_err(|e| match e {
+        SqlError::SqlxError(sqlx::Error::RowNotFound) => (
+            StatusCode::NOT_FOUND,
+            Json(OpsmlServerError::not_found("Card")),
+        ),
+        e => {
             error!("Failed to delete card: {e}");
             internal_server_error(e, "Failed to delete card", None)
-        })?;
+        }
+    })?;
+    let (space, name) = (trashed.space, trashed.name);
 
     let mut response = Json(UidResponse { exists: false }).into_response();
 
@@ -1028,7 +1099,23 @@ pub async fn delete_card(
         access_location: None,
     };
 
-    response.extensions_mut().insert(audit_context);
+    let registry_event = RegistryEvent::new(
+        RegistryEventType::CardDeleted,
+        &space,
+        Some(params.registry_type.clone()),
+        &params.uid,
+        serde_json::json!({
+            "uid": params.uid,
+            "name": name,
+        }),
+        &perms.username,
+    );
+
+    {
+        let extensions = response.extensions_mut();
+        extensions.insert(audit_context);
+        extensions.insert(registry_event);
+    }
 
     // Get count of remaining cards in the space
     let query_params = CardQueryArgs {
@@ -1098,6 +1185,8 @@ pub async fn load_card(
         .sql_client
         .get_card_key_for_loading(&table, &params)
         .await
+        .map_err(ServerError::from)
+        .and_then(|key| state.open_artifact_key(&key))
         .map_err(|e| {
             error!("Failed to get card key for loading: {e}");
             internal_server_error(e, "Failed to get card key for loading", None)
@@ -1137,6 +1226,8 @@ pub async fn get_card(
         .sql_client
         .get_card_key_for_loading(&table, &params)
         .await
+        .map_err(ServerError::from)
+        .and_then(|key| state.open_artifact_key(&key))
         .map_err(|e| {
             error!("Failed to get card key for loading: {e}");
             internal_server_error(e, "Failed to get card key for loading", None)
@@ -1254,6 +1345,7 @@ pub async fn get_readme(
     match download_artifact(
         state.storage_client.clone(),
         state.sql_client.clone(),
+        &*state.key_provider,
         &lpath,
         &rpath,
         &params.registry_type.to_string(),
@@ -1312,6 +1404,7 @@ pub async fn create_readme(
     let key = get_artifact_key(
         &state.sql_client,
         &state.storage_settings.encryption_key,
+        &*state.key_provider,
         &req.registry_type.to_string(),
         &req.space,
         &readme_path,
@@ -1322,6 +1415,10 @@ pub async fn create_readme(
         internal_server_error(e, "Failed to get artifact key", None)
     })?;
 
+    let previous = stored_bytes(&state.storage_client, Path::new(&readme_path))
+        .await
+        .unwrap_or(0);
+
     let lpath = format!("{}.{}", SaveName::ReadMe, Suffix::Md);
     let result = create_and_store_encrypted_file(
         state.storage_client.clone(),
@@ -1333,7 +1430,25 @@ pub async fn create_readme(
     .await;
 
     match result {
-        Ok(uploaded) => Ok(Json(uploaded)),
+        Ok(uploaded) => {
+            record_written_file(
+                &state.storage_client,
+                &state.sql_client,
+                &readme_path,
+                previous,
+            )
+            .await;
+
+            if uploaded.uploaded
+                && let Err(e) = state
+                    .sql_client
+                    .update_search_readme(&req.registry_type, &req.space, &req.name, &req.readme)
+                    .await
+            {
+                error!("Failed to index readme for search: {e}");
+            }
+            Ok(Json(uploaded))
+        }
         Err(e) => Ok(Json(UploadResponse {
             uploaded: false,
             message: format!("Failed to upload readme: {e}"),
@@ -1377,6 +1492,372 @@ pub async fn compare_content_hash(
     Ok(Json(CompareHashResponse { card }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/opsml/api/card/alias",
+    params(
+        ("space" = String, Query, description = "Card space"),
+        ("name" = String, Query, description = "Card name"),
+        ("registry_type" = String, Query, description = "Registry type"),
+    ),
+    responses(
+        (status = 200, description = "Aliases defined for the card", body = CardAliasListResponse),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "cards"
+)]
+#[instrument(skip_all)]
+pub async fn list_card_aliases(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(params): Query<CardAliasQuery>,
+) -> Result<Json<CardAliasListResponse>, (StatusCode, Json<OpsmlServerError>)> {
+    if !perms.has_read_permission(&params.space) {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let aliases = state
+        .sql_client
+        .get_card_aliases(&params.space, &params.name, &params.registry_type)
+        .await
+        .map_err(|e| {
+            error!("Failed to get card aliases: {e}");
+            internal_server_error(e, "Failed to get card aliases", None)
+        })?;
+
+    Ok(Json(CardAliasListResponse { aliases }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/opsml/api/card/alias",
+    request_body(content = SetCardAliasRequest, description = "Alias to create or move"),
+    responses(
+        (status = 200, description = "Alias set", body = CardAliasResponse),
+        (status = 400, description = "Invalid alias or version", body = OpsmlServerError),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 404, description = "Card version not found", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "cards"
+)]
+#[instrument(skip_all)]
+pub async fn set_card_alias(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(req): Json<SetCardAliasRequest>,
+) -> Result<Response, (StatusCode, Json<OpsmlServerError>)> {
+    if !perms.has_write_permission(&req.space) {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    if !is_valid_alias_name(&req.alias) {
+        return OpsmlServerError::bad_request(
+            "Alias must be lowercase alphanumeric (with '-' or '_') and contain a letter",
+        )
+        .into_response(StatusCode::BAD_REQUEST);
+    }
+
+    // aliases always point at a single concrete version, never a range or another alias
+    if semver::Version::parse(&req.version).is_err() {
+        return OpsmlServerError::bad_request("Alias target must be a concrete version")
+            .into_response(StatusCode::BAD_REQUEST);
+    }
+
+    let table = CardTable::from_registry_type(&req.registry_type);
+    let query_args = CardQueryArgs {
+        space: Some(req.space.clone()),
+        name: Some(req.name.clone()),
+        version: Some(req.version.clone()),
+        registry_type: req.registry_type.clone(),
+        ..Default::default()
+    };
+
+    let cards = state
+        .sql_client
+        .query_cards(&table, &query_args)
+        .await
+        .map_err(|e| {
+            error!("Failed to query cards: {e}");
+            internal_server_error(e, "Failed to query cards", None)
+        })?;
+
+    let Some(uid) = cards
+        .uid_versions()
+        .into_iter()
+        .find(|(_, version)| *version == req.version)
+        .map(|(uid, _)| uid.to_string())
+    else {
+        return OpsmlServerError::not_found("Card version").into_response(StatusCode::NOT_FOUND);
+    };
+
+    let previous_version = state
+        .sql_client
+        .get_card_alias(&req.space, &req.name, &req.registry_type, &req.alias)
+        .await
+        .map_err(|e| {
+            error!("Failed to get card alias: {e}");
+            internal_server_error(e, "Failed to get card alias", None)
+        })?
+        .map(|alias| alias.version);
+
+    let alias = CardAlias {
+        space: req.space.clone(),
+        name: req.name.clone(),
+        registry_type: req.registry_type.clone(),
+        alias: req.alias.clone(),
+        uid,
+        version: req.version.clone(),
+        updated_by: perms.username.clone(),
+        updated_at: get_utc_datetime(),
+    };
+
+    state
+        .sql_client
+        .upsert_card_alias(&alias)
+        .await
+        .map_err(|e| {
+            error!("Failed to set card alias: {e}");
+            internal_server_error(e, "Failed to set card alias", None)
+        })?;
+
+    info!(
+        "Moved alias {}/{}@{} from {:?} to {}",
+        alias.space, alias.name, alias.alias, previous_version, alias.version
+    );
+
+    let audit_context = AuditContext {
+        resource_id: format!("{}/{}@{}", alias.space, alias.name, alias.alias),
+        resource_type: ResourceType::Card,
+        metadata: serde_json::json!({
+            "alias": alias.alias,
+            "uid": alias.uid,
+            "from_version": previous_version,
+            "to_version": alias.version,
+        })
+        .to_string(),
+        registry_type: Some(alias.registry_type.clone()),
+        operation: Operation::Update,
+        access_location: None,
+    };
+
+    let registry_event = RegistryEvent::new(
+        RegistryEventType::AliasMoved,
+        &alias.space,
+        Some(alias.registry_type.clone()),
+        &audit_context.resource_id,
+        serde_json::json!({
+            "name": alias.name,
+            "alias": alias.alias,
+            "uid": alias.uid,
+            "from_version": previous_version,
+            "to_version": alias.version,
+        }),
+        &perms.username,
+    );
+
+    let mut response = Json(CardAliasResponse {
+        alias,
+        previous_version,
+    })
+    .into_response();
+    {
+        let extensions = response.extensions_mut();
+        extensions.insert(audit_context);
+        extensions.insert(registry_event);
+    }
+
+    Ok(response)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/opsml/api/card/alias",
+    params(
+        ("space" = String, Query, description = "Card space"),
+        ("name" = String, Query, description = "Card name"),
+        ("registry_type" = String, Query, description = "Registry type"),
+        ("alias" = String, Query, description = "Alias to remove"),
+    ),
+    responses(
+        (status = 200, description = "Alias removed", body = CardAliasResponse),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 404, description = "Alias not found", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "cards"
+)]
+#[instrument(skip_all)]
+pub async fn delete_card_alias(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(params): Query<DeleteCardAliasRequest>,
+) -> Result<Response, (StatusCode, Json<OpsmlServerError>)> {
+    if !perms.has_write_permission(&params.space) {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let existing = state
+        .sql_client
+        .get_card_alias(
+            &params.space,
+            &params.name,
+            &params.registry_type,
+            &params.alias,
+        )
+        .await
+        .map_err(|e| {
+            error!("Failed to get card alias: {e}");
+            internal_server_error(e, "Failed to get card alias", None)
+        })?;
+
+    let Some(alias) = existing else {
+        return OpsmlServerError::not_found("Alias").into_response(StatusCode::NOT_FOUND);
+    };
+
+    state
+        .sql_client
+        .delete_card_alias(
+            &params.space,
+            &params.name,
+            &params.registry_type,
+            &params.alias,
+        )
+        .await
+        .map_err(|e| {
+            error!("Failed to delete card alias: {e}");
+            internal_server_error(e, "Failed to delete card alias", None)
+        })?;
+
+    let audit_context = AuditContext {
+        resource_id: format!("{}/{}@{}", alias.space, alias.name, alias.alias),
+        resource_type: ResourceType::Card,
+        metadata: serde_json::json!({
+            "alias": alias.alias,
+            "uid": alias.uid,
+            "from_version": alias.version,
+            "to_version": null,
+        })
+        .to_string(),
+        registry_type: Some(alias.registry_type.clone()),
+        operation: Operation::Delete,
+        access_location: None,
+    };
+
+    let registry_event = RegistryEvent::new(
+        RegistryEventType::AliasMoved,
+        &alias.space,
+        Some(alias.registry_type.clone()),
+        &audit_context.resource_id,
+        serde_json::json!({
+            "name": alias.name,
+            "alias": alias.alias,
+            "uid": alias.uid,
+            "from_version": alias.version,
+            "to_version": null,
+        }),
+        &perms.username,
+    );
+
+    let previous_version = Some(alias.version.clone());
+    let mut response = Json(CardAliasResponse {
+        alias,
+        previous_version,
+    })
+    .into_response();
+    {
+        let extensions = response.extensions_mut();
+        extensions.insert(audit_context);
+        extensions.insert(registry_event);
+    }
+
+    Ok(response)
+}
+
+#[utoipa::path(
+    get,
+    path = "/opsml/api/card/lineage",
+    params(
+        ("uid" = String, Query, description = "Uid of the card to start from"),
+        ("registry_type" = Option<String>, Query, description = "Registry of the root card. Searched across registries when omitted"),
+        ("depth" = Option<usize>, Query, description = "Number of hops to walk (default 2, max 10)"),
+        ("direction" = Option<LineageDirection>, Query, description = "upstream, downstream or both"),
+    ),
+    responses(
+        (status = 200, description = "Lineage graph around the card", body = LineageGraph),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 404, description = "Card not found", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "cards"
+)]
+#[instrument(skip_all)]
+pub async fn get_card_lineage(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(params): Query<LineageQuery>,
+) -> Result<Json<LineageGraph>, (StatusCode, Json<OpsmlServerError>)> {
+    let mut graph = match state.sql_client.get_lineage_graph(&params).await {
+        Ok(graph) => graph,
+        Err(opsml_sql::error::SqlError::LineageRootNotFound(uid)) => {
+            return OpsmlServerError::not_found(&format!("card {uid}"))
+                .into_response(StatusCode::NOT_FOUND);
+        }
+        Err(e) => {
+            error!("Failed to get card lineage: {e}");
+            return Err(internal_server_error(e, "Failed to get card lineage", None));
+        }
+    };
+
+    // the root is always the first node
+    if !perms.has_read_permission(&graph.nodes[0].space) {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    // lineage crosses spaces, so cards the caller can't read are left out of the graph
+    graph.retain_nodes(|node| perms.has_read_permission(&node.space));
+
+    Ok(Json(graph))
+}
+
+#[utoipa::path(
+    get,
+    path = "/opsml/api/card/search",
+    params(
+        ("query" = String, Query, description = "Free-text query matched against names, spaces, tags, READMEs and descriptions"),
+        ("registry_type" = Option<String>, Query, description = "Restrict results to a registry"),
+        ("space" = Option<String>, Query, description = "Restrict results to a space"),
+        ("limit" = Option<usize>, Query, description = "Maximum number of cards (default 20, max 100)"),
+    ),
+    responses(
+        (status = 200, description = "Ranked cards with highlighted matches", body = CardSearchResponse),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "cards"
+)]
+#[instrument(skip_all)]
+pub async fn search_cards(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(params): Query<CardSearchQuery>,
+) -> Result<Json<CardSearchResponse>, (StatusCode, Json<OpsmlServerError>)> {
+    let readable_spaces = perms.readable_spaces();
+    let response = search_readable_cards(&state.sql_client, &params, readable_spaces.as_deref())
+        .await
+        .map_err(|e| {
+            error!("Failed to search cards: {e}");
+            internal_server_error(e, "Failed to search cards", None)
+        })?;
+
+    Ok(Json(response))
+}
+
 pub async fn get_card_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
     let result = catch_unwind(AssertUnwindSafe(|| {
         Router::new()
@@ -1413,6 +1894,11 @@ pub async fn get_card_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
             .route(&format!("{prefix}/card/load"), get(load_card))
             .route(&format!("{prefix}/card/update"), post(update_card))
             .route(&format!("{prefix}/card/delete"), delete(delete_card))
+            .route(&format!("{prefix}/card/alias"), get(list_card_aliases))
+            .route(&format!("{prefix}/card/alias"), put(set_card_alias))
+            .route(&format!("{prefix}/card/alias"), delete(delete_card_alias))
+            .route(&format!("{prefix}/card/lineage"), get(get_card_lineage))
+            .route(&format!("{prefix}/card/search"), get(search_cards))
             .route(
                 &format!("{prefix}/card/compare_hash"),
                 post(compare_content_hash),
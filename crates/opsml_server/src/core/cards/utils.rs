@@ -4,10 +4,9 @@ use crate::core::state::AppState;
 use opsml_sql::enums::client::SqlClientEnum;
 use opsml_sql::schemas::*;
 use opsml_sql::traits::*;
-use opsml_storage::StorageClientEnum;
 use opsml_types::cards::CardTable;
+use opsml_types::contracts::*;
 use opsml_types::contracts::{SkillCardClientRecord, SkillScanResult};
-use opsml_types::{RegistryType, contracts::*};
 use semver::Version;
 use std::sync::Arc;
 use tracing::{error, instrument};
@@ -17,6 +16,8 @@ use tracing::{error, instrument};
 /// * `sql_client` - The sql client
 /// * `card` - The card to insert
 /// * `version` - The version of the card
+/// * `table` - The table to insert the card into
+/// * `app_env` - The environment the server runs in, recorded on the card
 ///
 /// # Returns
 ///
@@ -27,6 +28,7 @@ pub async fn insert_card_into_db(
     card: CardRecord,
     version: Version,
     table: &CardTable,
+    app_env: &str,
 ) -> Result<InsertCardResponse, ServerError> {
     // match on registry type
     let card = match card {
@@ -42,6 +44,7 @@ pub async fn insert_card_into_db(
                 client_card.interface_type,
                 client_card.opsml_version,
                 client_card.username,
+                app_env.to_string(),
             );
             ServerCard::Data(server_card)
         }
@@ -60,6 +63,7 @@ pub async fn insert_card_into_db(
                 client_card.task_type,
                 client_card.opsml_version,
                 client_card.username,
+                app_env.to_string(),
             );
             ServerCard::Model(server_card)
         }
@@ -78,6 +82,7 @@ pub async fn insert_card_into_db(
                 client_card.opsml_version,
                 client_card.username,
                 client_card.status,
+                app_env.to_string(),
             );
             ServerCard::Experiment(server_card)
         }
@@ -94,6 +99,7 @@ pub async fn insert_card_into_db(
                 client_card.experimentcard_uids,
                 client_card.opsml_version,
                 client_card.username,
+                app_env.to_string(),
             );
             ServerCard::Audit(server_card)
         }
@@ -108,6 +114,7 @@ pub async fn insert_card_into_db(
                 client_card.opsml_version,
                 client_card.username,
                 client_card.content_hash,
+                app_env.to_string(),
             );
             ServerCard::Prompt(server_card)
         }
@@ -126,6 +133,7 @@ pub async fn insert_card_into_db(
                 client_card.content_hash,
                 client_card.username,
                 client_card.tags,
+                app_env.to_string(),
             );
             ServerCard::Service(Box::new(server_card))
         }
@@ -143,6 +151,7 @@ pub async fn insert_card_into_db(
                 client_card.username,
                 client_card.content_hash,
                 client_card.input_schema,
+                app_env.to_string(),
             );
             ServerCard::Skill(server_card)
         }
@@ -157,6 +166,7 @@ pub async fn insert_card_into_db(
                 client_card.opsml_version,
                 client_card.username,
                 client_card.content_hash,
+                app_env.to_string(),
             );
             ServerCard::SubAgent(server_card)
         }
@@ -172,6 +182,7 @@ pub async fn insert_card_into_db(
                 client_card.opsml_version,
                 client_card.username,
                 client_card.content_hash,
+                app_env.to_string(),
             );
             ServerCard::Tool(server_card)
         }
@@ -249,42 +260,12 @@ pub async fn run_skill_scan(
     Ok(())
 }
 
-#[instrument(skip_all)]
-pub async fn cleanup_artifacts(
-    storage_client: &Arc<StorageClientEnum>,
-    sql_client: &Arc<SqlClientEnum>,
-    uid: String,
-    registry_type: RegistryType,
-    table: &CardTable,
-) -> Result<(), ServerError> {
-    // get artifact key
-    let key = sql_client
-        .get_card_key_for_loading(
-            table,
-            &CardQueryArgs {
-                uid: Some(uid.clone()),
-                registry_type: registry_type.clone(),
-                ..Default::default()
-            },
-        )
-        .await
-        .inspect_err(|e| {
-            error!("Failed to get artifact key: {e}");
-        })?;
-
-    storage_client
-        .rm(&key.storage_path(), true)
-        .await
-        .inspect_err(|e| {
-            error!("Failed to remove artifact: {e}");
-        })?;
-
-    sql_client
-        .delete_artifact_key(&uid, &registry_type.to_string())
-        .await
-        .inspect_err(|e| {
-            error!("Failed to delete artifact key: {e}");
-        })?;
-
-    Ok(())
+/// Index a card version for full-text search.
+///
+/// The search index is derived from the registry tables, so a failure here is logged instead of
+/// failing the registration it follows.
+pub async fn index_card(sql_client: &SqlClientEnum, document: &CardSearchDocument) {
+    if let Err(e) = sql_client.upsert_search_document(document).await {
+        error!("Failed to index card {} for search: {e}", document.uid);
+    }
 }
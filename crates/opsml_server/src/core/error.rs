@@ -202,6 +202,20 @@ impl OpsmlServerError {
         }
     }
 
+    pub fn storage_quota_exceeded(space: &str, used: i64, quota: i64, incoming: i64) -> Self {
+        error!("Upload rejected, space {space} is over its storage quota");
+        OpsmlServerError {
+            error: format!(
+                "Storage quota exceeded for space '{space}': {used} of {quota} bytes used, upload of {incoming} bytes rejected"
+            ),
+            code: Some("STORAGE_QUOTA_EXCEEDED"),
+            suggested_action: Some(
+                "Delete unused cards and purge the trash, or ask an admin to raise the space's hard quota",
+            ),
+            retry: Some(false),
+        }
+    }
+
     pub fn no_drift_profile_found() -> Self {
         error!("No drift profile found");
         OpsmlServerError {
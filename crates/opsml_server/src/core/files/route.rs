@@ -1,6 +1,9 @@
 use crate::core::error::OpsmlServerError;
+use crate::core::error::ServerError;
 use crate::core::error::internal_server_error;
-use crate::core::files::utils::get_content_for_files;
+use crate::core::files::utils::{
+    get_content_for_files, record_storage_usage, storage_path_owner, stored_bytes,
+};
 use crate::core::state::AppState;
 use anyhow::{Context, Result};
 use axum::extract::DefaultBodyLimit;
@@ -9,32 +12,231 @@ use axum::{
     Extension, Json, Router,
     body::Body,
     extract::{Query, State},
-    http::StatusCode,
+    http::{StatusCode, header},
     response::{IntoResponse, Response},
-    routing::{delete, get, post},
+    routing::{delete, get, post, put},
 };
 use headers::HeaderMap;
 use opsml_auth::permission::UserPermissions;
 use opsml_events::AuditContext;
 use opsml_sql::enums::utils::get_next_version;
 use opsml_sql::schemas::ArtifactSqlRecord;
-use opsml_sql::traits::ArtifactLogicTrait;
+use opsml_sql::traits::{ArtifactLogicTrait, SpaceLogicTrait};
+use opsml_storage::file_version;
 use opsml_types::{MAX_FILE_SIZE, StorageType, contracts::*};
 use opsml_types::{RegistryType, cards::CardTable};
 
 use tokio::fs::File;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
 
 /// Route for debugging information
 use serde_json::json;
 use std::collections::VecDeque;
+use std::io::SeekFrom;
 use std::panic::{AssertUnwindSafe, catch_unwind};
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio_util::io::ReaderStream;
 use tracing::debug;
-use tracing::{error, info, instrument};
+use tracing::{error, info, instrument, warn};
+
+/// Quotas of `space`, or `None` if it has no quota set
+async fn space_quotas(
+    state: &AppState,
+    space: &str,
+) -> Result<Option<SpaceRecord>, (StatusCode, Json<OpsmlServerError>)> {
+    let record = state
+        .sql_client
+        .get_space_record(space)
+        .await
+        .map_err(|e| {
+            error!("Failed to get space record: {e}");
+            internal_server_error(e, "Failed to get space record", None)
+        })?;
+
+    Ok(record
+        .filter(|record| record.soft_quota_bytes.is_some() || record.hard_quota_bytes.is_some()))
+}
+
+async fn space_bytes(
+    state: &AppState,
+    space: &str,
+) -> Result<i64, (StatusCode, Json<OpsmlServerError>)> {
+    state.sql_client.get_space_bytes(space).await.map_err(|e| {
+        error!("Failed to get space usage: {e}");
+        internal_server_error(e, "Failed to get space usage", None)
+    })
+}
+
+fn quota_exceeded(
+    space: &str,
+    used: i64,
+    quota: i64,
+    incoming: i64,
+) -> (StatusCode, Json<OpsmlServerError>) {
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        Json(OpsmlServerError::storage_quota_exceeded(
+            space, used, quota, incoming,
+        )),
+    )
+}
+
+fn soft_quota_warning(space: &str, total: i64, soft_quota: Option<i64>) -> Option<String> {
+    soft_quota.filter(|quota| total > *quota).map(|quota| {
+        let message = format!(
+            "Space '{space}' is over its soft storage quota: {total} of {quota} bytes used"
+        );
+        warn!("{message}");
+        message
+    })
+}
+
+/// Reject an upload once `space` is at its hard quota, or when `incoming` bytes would take it
+/// past the quota. Returns a warning when the upload takes the space past its soft quota.
+/// Nothing is reserved, so this only fails early before the upload is stored
+async fn check_space_quota(
+    state: &AppState,
+    space: &str,
+    incoming: i64,
+) -> Result<Option<String>, (StatusCode, Json<OpsmlServerError>)> {
+    let Some(record) = space_quotas(state, space).await? else {
+        return Ok(None);
+    };
+
+    let used = space_bytes(state, space).await?;
+    let total = used.saturating_add(incoming);
+
+    if let Some(quota) = record.hard_quota_bytes
+        && (used >= quota || total > quota)
+    {
+        return Err(quota_exceeded(space, used, quota, incoming));
+    }
+
+    Ok(soft_quota_warning(space, total, record.soft_quota_bytes))
+}
+
+/// Add `incoming` bytes to the usage of the space owning `path`. Growth that would take the
+/// space past its hard quota is rejected, and the check and update happen atomically so
+/// concurrent uploads cannot together exceed the quota. Returns a warning when the space ends
+/// up past its soft quota. Release the bytes with `record_storage_usage` if the upload fails
+async fn reserve_space_quota(
+    state: &AppState,
+    path: &str,
+    incoming: i64,
+) -> Result<Option<String>, (StatusCode, Json<OpsmlServerError>)> {
+    let Some((space, registry_type)) = storage_path_owner(path) else {
+        return Ok(None);
+    };
+
+    let record = match space_quotas(state, &space).await? {
+        Some(record) if incoming > 0 => record,
+        _ => {
+            record_storage_usage(&state.sql_client, path, incoming).await;
+            return Ok(None);
+        }
+    };
+
+    match record.hard_quota_bytes {
+        Some(quota) => {
+            let reserved = state
+                .sql_client
+                .reserve_space_usage(&space, &registry_type, incoming, quota)
+                .await
+                .map_err(|e| {
+                    error!("Failed to reserve space usage: {e}");
+                    internal_server_error(e, "Failed to reserve space usage", None)
+                })?;
+
+            if !reserved {
+                let used = space_bytes(state, &space).await?;
+                return Err(quota_exceeded(&space, used, quota, incoming));
+            }
+        }
+        None => record_storage_usage(&state.sql_client, path, incoming).await,
+    }
+
+    let used = space_bytes(state, &space).await?;
+    Ok(soft_quota_warning(&space, used, record.soft_quota_bytes))
+}
+
+/// Reserve `bytes` for an upload that goes straight to object storage. The bytes count toward
+/// the space's usage right away, so concurrent uploads cannot together pass the hard quota,
+/// and are settled by `settle_upload` once the upload is completed or cancelled
+async fn reserve_upload(
+    state: &AppState,
+    path: &str,
+    bytes: i64,
+) -> Result<(), (StatusCode, Json<OpsmlServerError>)> {
+    let Some((space, registry_type)) = storage_path_owner(path) else {
+        return Ok(());
+    };
+
+    reserve_space_quota(state, path, bytes).await?;
+
+    if let Err(e) = state
+        .sql_client
+        .insert_upload_reservation(path, &space, &registry_type, bytes)
+        .await
+    {
+        error!("Failed to reserve storage for upload {path}: {e}");
+        record_storage_usage(&state.sql_client, path, -bytes).await;
+        return Err(internal_server_error(
+            e,
+            "Failed to reserve storage for upload",
+            None,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Replace the bytes reserved for an upload with what was actually stored, or release them when
+/// the upload was cancelled. Growth past the reservation has to fit the hard quota, otherwise the
+/// upload is removed again. Returns a warning when the space ends up past its soft quota
+async fn settle_upload(
+    state: &AppState,
+    path: &str,
+    cancel: bool,
+) -> Result<Option<String>, (StatusCode, Json<OpsmlServerError>)> {
+    let reserved = match state.sql_client.take_upload_reservation(path).await {
+        Ok(reserved) => reserved.unwrap_or(0),
+        Err(e) => {
+            // the reserved bytes stay counted until usage is recounted
+            error!("Failed to take storage reservation of upload {path}: {e}");
+            return Ok(None);
+        }
+    };
+
+    if cancel {
+        record_storage_usage(&state.sql_client, path, -reserved).await;
+        return Ok(None);
+    }
+
+    let stored = match stored_bytes(&state.storage_client, Path::new(path)).await {
+        Ok(stored) => stored,
+        Err(e) => {
+            error!("Failed to get size of uploaded file {path}: {e}");
+            return Ok(None);
+        }
+    };
+
+    match reserve_space_quota(state, path, stored - reserved).await {
+        Ok(warning) => Ok(warning),
+        Err(e) => {
+            if e.0 == StatusCode::PAYLOAD_TOO_LARGE {
+                match state.storage_client.rm(Path::new(path), false).await {
+                    Ok(_) => record_storage_usage(&state.sql_client, path, -reserved).await,
+                    Err(rm_err) => {
+                        error!("Failed to remove upload {path} over the storage quota: {rm_err}")
+                    }
+                }
+            }
+            Err(e)
+        }
+    }
+}
 
 /// Create a multipart upload session (write)
 ///
@@ -51,11 +253,13 @@ use tracing::{error, info, instrument};
     path = "/opsml/api/files/multipart",
     params(
         ("path" = String, Query, description = "Storage path for the file to upload"),
+        ("file_size" = Option<i64>, Query, description = "Size of the file in bytes, checked against the space's storage quota"),
     ),
     responses(
         (status = 200, description = "Multipart upload session created", body = MultiPartSession),
         (status = 400, description = "Invalid path", body = OpsmlServerError),
         (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 413, description = "Storage quota exceeded", body = OpsmlServerError),
         (status = 500, description = "Internal error", body = OpsmlServerError),
     ),
     security(("bearer_token" = [])),
@@ -91,6 +295,16 @@ pub async fn create_multipart_upload(
         ));
     }
 
+    // local uploads are counted as their parts arrive. Other backends upload straight to object
+    // storage, so the bytes are reserved now and settled once the upload is completed
+    let file_size = params.file_size.unwrap_or(0);
+    let reserve = state.storage_client.storage_type() != StorageType::Local;
+    if reserve {
+        reserve_upload(&state, &params.path, file_size).await?;
+    } else if let Some((space, _)) = storage_path_owner(&params.path) {
+        check_space_quota(&state, &space, file_size).await?;
+    }
+
     let path = Path::new(&params.path);
     debug!("Creating multipart upload for path: {}", path.display());
 
@@ -102,6 +316,9 @@ pub async fn create_multipart_upload(
         Ok(session_url) => session_url,
         Err(e) => {
             error!("Failed to create multipart upload: {e}");
+            if reserve {
+                settle_upload(&state, &params.path, true).await?;
+            }
             return Err(internal_server_error(
                 e,
                 "Failed to create multipart upload",
@@ -236,6 +453,7 @@ pub async fn generate_presigned_url(
     responses(
         (status = 200, description = "Multipart upload completed", body = UploadResponse),
         (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 413, description = "Storage quota exceeded, the upload was removed", body = OpsmlServerError),
         (status = 500, description = "Internal error", body = OpsmlServerError),
     ),
     security(("bearer_token" = [])),
@@ -247,13 +465,21 @@ pub async fn complete_multipart_upload(
     Extension(perms): Extension<UserPermissions>,
 
     Json(req): Json<CompleteMultipartUpload>,
-) -> Result<Json<UploadResponse>, (StatusCode, Json<OpsmlServerError>)> {
+) -> Result<Response, (StatusCode, Json<OpsmlServerError>)> {
     // check for write access
 
     if !perms.has_write_permission("") {
         return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
     }
 
+    let path = req.path.clone();
+    let cancel = req.cancel;
+    let registry_event = if cancel {
+        None
+    } else {
+        file_uploaded_event(&path, &perms.username)
+    };
+
     state
         .storage_client
         .complete_multipart_upload(req)
@@ -263,10 +489,62 @@ pub async fn complete_multipart_upload(
             internal_server_error(e, "Failed to complete multipart upload", None)
         })?;
 
-    Ok(Json(UploadResponse {
+    // local uploads were already counted as their parts arrived
+    let quota_warning = match state.storage_client.storage_type() {
+        StorageType::Local => None,
+        _ => settle_upload(&state, &path, cancel).await?,
+    };
+
+    let mut response = Json(UploadResponse {
         uploaded: true,
-        message: "".to_string(),
-    }))
+        message: quota_warning.unwrap_or_default(),
+    })
+    .into_response();
+
+    if let Some(registry_event) = registry_event {
+        response.extensions_mut().insert(registry_event);
+    }
+
+    Ok(response)
+}
+
+/// Storage paths are laid out as `{registry}/{space}/{name}/v{version}/...`, so the space
+/// is the second path component. Paths outside of a space do not produce an event
+fn file_uploaded_event(path: &str, username: &str) -> Option<RegistryEvent> {
+    let space = Path::new(path).iter().nth(1)?.to_str()?;
+
+    Some(RegistryEvent::new(
+        RegistryEventType::FileUploaded,
+        space,
+        None,
+        path,
+        json!({ "path": path }),
+        username,
+    ))
+}
+
+/// Write an uploaded file to local storage, creating its directory if needed
+async fn write_upload(
+    rpath: &Path,
+    data: &[u8],
+) -> Result<(), (StatusCode, Json<OpsmlServerError>)> {
+    if let Some(parent) = rpath.parent() {
+        tokio::fs::create_dir_all(parent).await.map_err(|e| {
+            error!("Failed to create directory: {e}");
+            internal_server_error(e, "Failed to create directory", None)
+        })?;
+    }
+
+    let mut file = File::create(rpath).await.map_err(|e| {
+        error!("Failed to create file: {e}");
+        internal_server_error(e, "Failed to create file", None)
+    })?;
+    file.write_all(data).await.map_err(|e| {
+        error!("Failed to write file: {e}");
+        internal_server_error(e, "Failed to write file", None)
+    })?;
+
+    Ok(())
 }
 
 // this is for local storage only
@@ -276,6 +554,7 @@ pub async fn complete_multipart_upload(
     request_body(content = inline(serde_json::Value), content_type = "multipart/form-data", description = "File part data"),
     responses(
         (status = 200, description = "File part uploaded", body = UploadResponse),
+        (status = 413, description = "Storage quota exceeded", body = OpsmlServerError),
         (status = 500, description = "Internal error", body = OpsmlServerError),
     ),
     security(("bearer_token" = [])),
@@ -284,8 +563,13 @@ pub async fn complete_multipart_upload(
 #[instrument(skip_all)]
 pub async fn upload_multipart(
     State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
     mut multipart: Multipart,
-) -> Result<Json<UploadResponse>, (StatusCode, Json<OpsmlServerError>)> {
+) -> Result<Response, (StatusCode, Json<OpsmlServerError>)> {
+    // clients send one file per request
+    let mut registry_event = None;
+    let mut quota_warning = None;
+
     while let Some(field) = multipart.next_field().await.unwrap() {
         let file_name = field.file_name().unwrap().to_string();
         let data = field.bytes().await.map_err(|e| {
@@ -297,28 +581,34 @@ pub async fn upload_multipart(
         // join the bucket and the file name
         let rpath = Path::new(&bucket).join(&file_name);
 
-        // create the directory if it doesn't exist
-        if let Some(parent) = rpath.parent() {
-            tokio::fs::create_dir_all(parent).await.map_err(|e| {
-                error!("Failed to create directory: {e}");
-                internal_server_error(e, "Failed to create directory", None)
-            })?;
+        // overwriting a file only adds the difference in size
+        let previous = tokio::fs::metadata(&rpath)
+            .await
+            .map(|metadata| metadata.len() as i64)
+            .unwrap_or(0);
+        let added = data.len() as i64 - previous;
+
+        quota_warning = reserve_space_quota(&state, &file_name, added).await?;
+
+        if let Err(e) = write_upload(&rpath, &data).await {
+            record_storage_usage(&state.sql_client, &file_name, -added).await;
+            return Err(e);
         }
 
-        let mut file = File::create(&rpath).await.map_err(|e| {
-            error!("Failed to create file: {e}");
-            internal_server_error(e, "Failed to create file", None)
-        })?;
-        file.write_all(&data).await.map_err(|e| {
-            error!("Failed to write file: {e}");
-            internal_server_error(e, "Failed to write file", None)
-        })?;
+        registry_event = file_uploaded_event(&file_name, &perms.username);
     }
 
-    Ok(Json(UploadResponse {
+    let mut response = Json(UploadResponse {
         uploaded: true,
-        message: "".to_string(),
-    }))
+        message: quota_warning.unwrap_or_default(),
+    })
+    .into_response();
+
+    if let Some(registry_event) = registry_event {
+        response.extensions_mut().insert(registry_event);
+    }
+
+    Ok(response)
 }
 
 #[utoipa::path(
@@ -534,6 +824,7 @@ pub async fn get_file_for_ui(
     let mut files = get_content_for_files(
         &state.storage_client,
         &state.sql_client,
+        &state.key_provider,
         &file_path,
         &req.uid,
         &req.registry_type.to_string(),
@@ -601,6 +892,7 @@ pub async fn get_files_for_ui(
     let files = get_content_for_files(
         &state.storage_client,
         &state.sql_client,
+        &state.key_provider,
         &file_path,
         &req.uid,
         &req.registry_type.to_string(),
@@ -655,6 +947,13 @@ pub async fn delete_file(
 
     info!("Deleting path: {}", path.display());
 
+    let released = stored_bytes(&state.storage_client, path)
+        .await
+        .unwrap_or_else(|e| {
+            error!("Failed to get size of {}: {e}", path.display());
+            0
+        });
+
     let files = state.storage_client.rm(path, recursive).await;
 
     //
@@ -664,6 +963,7 @@ pub async fn delete_file(
             internal_server_error(e, "Failed to delete files", None)
         });
     }
+    record_storage_usage(&state.sql_client, &params.path, -released).await;
 
     // check if file exists
     let exists = state.storage_client.exists(path).await;
@@ -697,6 +997,8 @@ pub async fn delete_file(
     ),
     responses(
         (status = 200, description = "File bytes", content_type = "application/octet-stream"),
+        (status = 206, description = "File bytes from the offset in the `Range: bytes=<start>-` header, unless an `If-Range` header names a different version of the file", content_type = "application/octet-stream"),
+        (status = 416, description = "Range start is past the end of the file"),
         (status = 400, description = "Not supported for non-local storage", body = OpsmlServerError),
         (status = 500, description = "Internal error", body = OpsmlServerError),
     ),
@@ -706,6 +1008,7 @@ pub async fn delete_file(
 pub async fn download_file(
     State(state): State<Arc<AppState>>,
     Query(params): Query<DownloadFileQuery>,
+    headers: HeaderMap,
 ) -> Response<Body> {
     // check if storage client is local (fails if not)
     if state.storage_client.storage_type() != StorageType::Local {
@@ -722,7 +1025,7 @@ pub async fn download_file(
     let bucket = state.config.opsml_storage_uri.clone();
     let rpath = Path::new(&bucket).join(path);
 
-    let file = match File::open(&rpath).await {
+    let mut file = match File::open(&rpath).await {
         Ok(file) => file,
         Err(e) => {
             error!("Failed to open file: {e}");
@@ -730,10 +1033,84 @@ pub async fn download_file(
         }
     };
 
+    let metadata = match file.metadata().await {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            error!("Failed to read file metadata: {e}");
+            return internal_server_error(e, "Failed to read file metadata", None).into_response();
+        }
+    };
+    let len = metadata.len();
+    let etag = format!("\"{}\"", file_version(&metadata));
+
+    // a range is only served if the file is still the version the client resumes from
+    let start = range_start(&headers).filter(|_| if_range_matches(&headers, &etag));
+    let Some(start) = start else {
+        let stream = ReaderStream::new(file);
+        let body = Body::from_stream(stream);
+
+        return (
+            StatusCode::OK,
+            [
+                (header::ACCEPT_RANGES, "bytes".to_string()),
+                (header::ETAG, etag),
+            ],
+            body,
+        )
+            .into_response();
+    };
+
+    // resumed download - only send the bytes after `start`
+    if start >= len {
+        return (
+            StatusCode::RANGE_NOT_SATISFIABLE,
+            [(header::CONTENT_RANGE, format!("bytes */{len}"))],
+        )
+            .into_response();
+    }
+
+    if let Err(e) = file.seek(SeekFrom::Start(start)).await {
+        error!("Failed to seek file: {e}");
+        return internal_server_error(e, "Failed to seek file", None).into_response();
+    }
+
     let stream = ReaderStream::new(file);
     let body = Body::from_stream(stream);
 
-    (StatusCode::OK, body).into_response()
+    (
+        StatusCode::PARTIAL_CONTENT,
+        [
+            (header::ACCEPT_RANGES, "bytes".to_string()),
+            (
+                header::CONTENT_RANGE,
+                format!("bytes {start}-{}/{len}", len - 1),
+            ),
+            (header::CONTENT_LENGTH, (len - start).to_string()),
+            (header::ETAG, etag),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+/// Start offset of an open-ended `Range: bytes=<start>-` header. Other range forms are not
+/// used by opsml clients and are answered with the full file
+fn range_start(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(header::RANGE)?
+        .to_str()
+        .ok()?
+        .strip_prefix("bytes=")?
+        .strip_suffix('-')?
+        .parse()
+        .ok()
+}
+
+/// Whether an `If-Range` header, if any, names the current version of the file
+fn if_range_matches(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get(header::IF_RANGE)
+        .is_none_or(|value| value.to_str().is_ok_and(|value| value == etag))
 }
 
 #[utoipa::path(
@@ -760,6 +1137,8 @@ pub async fn get_artifact_key(
         .sql_client
         .get_artifact_key(&req.uid, &req.registry_type.to_string())
         .await
+        .map_err(ServerError::from)
+        .and_then(|key| state.open_artifact_key(&key))
         .map_err(|e| {
             error!("Failed to get artifact key: {e}");
             internal_server_error(e, "Failed to get artifact key", None)
@@ -768,6 +1147,57 @@ pub async fn get_artifact_key(
     Ok(Json(key))
 }
 
+/// Record the digest of the manifest uploaded with a card's artifacts so that downloads can
+/// detect a missing or replaced manifest
+#[utoipa::path(
+    put,
+    path = "/opsml/api/files/key/manifest",
+    request_body = ArtifactManifestRequest,
+    responses(
+        (status = 200, description = "Manifest digest recorded"),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "files"
+)]
+#[instrument(skip_all)]
+pub async fn set_artifact_manifest(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(req): Json<ArtifactManifestRequest>,
+) -> Result<StatusCode, (StatusCode, Json<OpsmlServerError>)> {
+    debug!("Setting artifact manifest for: {}", req.uid);
+    let registry_type = req.registry_type.to_string();
+
+    let key = state
+        .sql_client
+        .get_artifact_key(&req.uid, &registry_type)
+        .await
+        .map_err(|e| {
+            error!("Failed to get artifact key: {e}");
+            internal_server_error(e, "Failed to get artifact key", None)
+        })?;
+
+    if !perms.has_write_permission(&key.space) {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(OpsmlServerError::permission_denied()),
+        ));
+    }
+
+    state
+        .sql_client
+        .set_artifact_manifest(&req.uid, &registry_type, &req.manifest_sha256)
+        .await
+        .map_err(|e| {
+            error!("Failed to set artifact manifest: {e}");
+            internal_server_error(e, "Failed to set artifact manifest", None)
+        })?;
+
+    Ok(StatusCode::OK)
+}
+
 /// Create artifact record
 #[utoipa::path(
     post,
@@ -776,6 +1206,7 @@ pub async fn get_artifact_key(
     responses(
         (status = 200, description = "Artifact record created", body = CreateArtifactResponse),
         (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 413, description = "Storage quota exceeded", body = OpsmlServerError),
         (status = 500, description = "Internal error", body = OpsmlServerError),
     ),
     security(("bearer_token" = [])),
@@ -794,6 +1225,9 @@ pub async fn create_artifact_record(
         return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
     }
 
+    // artifacts are uploaded after the record is created, so only a full space is rejected here
+    check_space_quota(&state, &req.space, 0).await?;
+
     // version request is always major for artifacts
     let version_request = CardVersionRequest {
         space: req.space.clone(),
@@ -817,6 +1251,7 @@ pub async fn create_artifact_record(
         version,
         req.media_type,
         req.artifact_type.to_string(),
+        state.config.app_env.clone(),
     );
 
     state
@@ -933,6 +1368,10 @@ pub async fn get_file_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
             .route(&format!("{prefix}/files/list/info"), get(list_file_info))
             .route(&format!("{prefix}/files/delete"), delete(delete_file))
             .route(&format!("{prefix}/files/key"), get(get_artifact_key))
+            .route(
+                &format!("{prefix}/files/key/manifest"),
+                put(set_artifact_manifest),
+            )
             .route(&format!("{prefix}/files/content"), post(get_file_for_ui))
             .route(
                 &format!("{prefix}/files/content/batch"),
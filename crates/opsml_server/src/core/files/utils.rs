@@ -3,17 +3,16 @@ use anyhow::Result;
 use base64::prelude::*;
 use mime_guess::mime;
 use opsml_crypt::{
-    decrypt_directory, decrypt_file, encrypt_directory,
-    key::{derive_encryption_key, encrypted_key, generate_salt},
+    KeyProvider, decrypt_directory, decrypt_file, encrypt_directory,
+    key::{derive_encryption_key, generate_salt},
 };
 use opsml_sql::enums::client::SqlClientEnum;
-use opsml_sql::traits::ArtifactLogicTrait;
+use opsml_sql::traits::{ArtifactLogicTrait, SpaceLogicTrait};
 use opsml_storage::StorageClientEnum;
 use opsml_types::RegistryType;
 use opsml_types::contracts::FileInfo;
 use opsml_types::contracts::RawFile;
 use opsml_types::contracts::{ArtifactKey, DownloadResponse, UploadResponse};
-use opsml_utils::uid_to_byte_key;
 use std::collections::VecDeque;
 use std::path::{Path, PathBuf};
 use std::result;
@@ -30,6 +29,7 @@ use uuid::Uuid;
 pub async fn create_artifact_key(
     sql_client: &SqlClientEnum,
     encryption_key: &[u8],
+    key_provider: &dyn KeyProvider,
     uid: &str,
     space: &str,
     registry_type: &str,
@@ -43,12 +43,9 @@ pub async fn create_artifact_key(
 
     // create derived key
     let derived_key = derive_encryption_key(encryption_key, &salt, registry_type.as_bytes())?;
-    let uid_key = uid_to_byte_key(uid)?;
 
-    // encrypt key before sending
-    let encrypted_key = encrypted_key(&uid_key, &derived_key)?;
-
-    // spawn a task to insert the key into the database
+    // the stored key is wrapped with the KEK, the returned key with the uid
+    let (encrypted_key, key_version) = key_provider.wrap_key(&derived_key)?;
 
     let artifact_key = ArtifactKey {
         uid: uid.to_string(),
@@ -56,9 +53,10 @@ pub async fn create_artifact_key(
         registry_type: RegistryType::from_string(registry_type)?,
         encrypted_key,
         storage_key: storage_key.to_string(),
+        key_version,
+        manifest_sha256: None,
     };
 
-    // Spawn task and add to managed set
     sql_client
         .insert_artifact_key(&artifact_key)
         .await
@@ -66,7 +64,7 @@ pub async fn create_artifact_key(
             error!("Failed to insert artifact key: {e}");
         })?;
 
-    Ok(artifact_key)
+    Ok(artifact_key.open(key_provider)?)
 }
 
 pub async fn create_and_store_encrypted_file(
@@ -117,6 +115,7 @@ pub async fn create_and_store_encrypted_file(
 pub async fn download_artifact(
     storage_client: Arc<StorageClientEnum>,
     sql_client: Arc<SqlClientEnum>,
+    key_provider: &dyn KeyProvider,
     lpath: &Path,
     rpath: &str,
     registry_type: &str,
@@ -144,7 +143,7 @@ pub async fn download_artifact(
         return Err(ServerError::ArtifactKeyNotFound);
     }
 
-    let key = key.unwrap();
+    let key = key.unwrap().open(key_provider)?;
     let rpath = PathBuf::from(rpath);
 
     // Check if file exists in storage
@@ -183,6 +182,7 @@ pub async fn download_artifact(
 pub async fn download_artifacts(
     storage_client: Arc<StorageClientEnum>,
     sql_client: Arc<SqlClientEnum>,
+    key_provider: &dyn KeyProvider,
     lpath: &Path,
     rpath: &Path,
     registry_type: &str,
@@ -193,7 +193,8 @@ pub async fn download_artifacts(
         .await
         .inspect_err(|e| {
             error!("Failed to get artifact key: {e}");
-        })?;
+        })?
+        .open(key_provider)?;
 
     let rpath = key.storage_path().join(rpath);
 
@@ -229,6 +230,7 @@ pub async fn download_artifacts(
 pub async fn get_artifact_key(
     sql_client: &SqlClientEnum,
     encryption_key: &[u8],
+    key_provider: &dyn KeyProvider,
     registry_type: &str,
     space: &str,
     storage_key: &str,
@@ -239,12 +241,13 @@ pub async fn get_artifact_key(
         .inspect_err(|e| {
             error!("Failed to get artifact key: {e}");
         })? {
-        Some(key) => Ok(key),
+        Some(key) => Ok(key.open(key_provider)?),
         None => {
             let uid = Uuid::new_v4().to_string();
             create_artifact_key(
                 sql_client,
                 encryption_key,
+                key_provider,
                 &uid,
                 space,
                 registry_type,
@@ -255,6 +258,62 @@ pub async fn get_artifact_key(
     }
 }
 
+/// Space and registry that own a `{table}/{space}/...` storage path
+pub fn storage_path_owner(path: &str) -> Option<(String, RegistryType)> {
+    let mut segments = Path::new(path.trim_start_matches('/')).iter();
+    let table = segments.next()?.to_str()?;
+    let space = segments.next()?.to_str()?;
+
+    let registry = table.strip_prefix("opsml_")?.strip_suffix("_registry")?;
+    let registry_type = RegistryType::from_string(registry).ok()?;
+
+    Some((space.to_string(), registry_type))
+}
+
+/// Total size of the objects stored under `path`, or 0 if nothing is stored there
+pub async fn stored_bytes(
+    storage_client: &StorageClientEnum,
+    path: &Path,
+) -> Result<i64, ServerError> {
+    if !storage_client.exists(path).await? {
+        return Ok(0);
+    }
+    let files = storage_client.find_info(path).await?;
+    Ok(files.iter().map(|file| file.size.max(0)).sum())
+}
+
+/// Add `bytes` (negative when objects are removed) to the usage of the space owning `path`.
+/// The storage operation has already happened, so failures are logged rather than returned
+pub async fn record_storage_usage(sql_client: &SqlClientEnum, path: &str, bytes: i64) {
+    if bytes == 0 {
+        return;
+    }
+    let Some((space, registry_type)) = storage_path_owner(path) else {
+        return;
+    };
+
+    if let Err(e) = sql_client
+        .add_space_usage(&space, &registry_type, bytes)
+        .await
+    {
+        error!("Failed to record storage usage for space {space}: {e}");
+    }
+}
+
+/// Count a file the server wrote itself toward the usage of the space owning `path`. `previous`
+/// is what was stored at `path` before the write, so an overwrite only adds the difference
+pub async fn record_written_file(
+    storage_client: &StorageClientEnum,
+    sql_client: &SqlClientEnum,
+    path: &str,
+    previous: i64,
+) {
+    match stored_bytes(storage_client, Path::new(path)).await {
+        Ok(stored) => record_storage_usage(sql_client, path, stored - previous).await,
+        Err(e) => error!("Failed to get size of {path}: {e}"),
+    }
+}
+
 /// Get the content of a file
 /// # Arguments
 /// * `file` - The file information
@@ -284,12 +343,14 @@ async fn get_file_content(file: &FileInfo, lpath: &Path) -> Result<RawFile, Serv
 /// # Arguments
 /// * `storage_client` - Storage client for download objects
 /// * `sql_client` - SQL client used to retrieve artifact keys
+/// * `key_provider` - Provider used to unwrap the stored artifact keys
 /// * `file_path` - File path that points to directory containing files to download
 /// * `uid` - UID associated with the files (experiment uid, model uid, etc.)
 /// * `registry_type` - Registry type for the files
 pub async fn get_content_for_files(
     storage_client: &Arc<StorageClientEnum>,
     sql_client: &Arc<SqlClientEnum>,
+    key_provider: &Arc<dyn KeyProvider>,
     file_path: &Path,
     uid: &str,
     registry_type: &str,
@@ -316,6 +377,7 @@ pub async fn get_content_for_files(
         // Spawn a new asynchronous task for each item
         let task_storage_client = storage_client.clone();
         let task_sql_client = sql_client.clone();
+        let task_key_provider = key_provider.clone();
         let task_uid = uid.to_string();
         let task_registry_type = registry_type.to_string();
         let lpath = tmp_path.join(file.name.clone());
@@ -324,6 +386,7 @@ pub async fn get_content_for_files(
             download_artifact(
                 task_storage_client,
                 task_sql_client,
+                &*task_key_provider,
                 &lpath,
                 &file.name,
                 &task_registry_type,
@@ -0,0 +1,436 @@
+use crate::core::auth::util::require_admin;
+use crate::core::error::{OpsmlServerError, internal_server_error};
+use crate::core::state::AppState;
+use anyhow::{Context, Result};
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, get, post, put},
+};
+use opsml_auth::permission::UserPermissions;
+use opsml_sql::traits::{GroupLogicTrait, UserLogicTrait};
+use opsml_types::contracts::{
+    AddGroupMemberRequest, CreateGroupRequest, GroupListResponse, GroupRecord, GroupResponse,
+    GroupRole, SetGroupRoleRequest,
+};
+use opsml_utils::utils::get_utc_datetime;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::Arc;
+use tracing::{error, info, instrument};
+
+type GroupResult<T> = Result<T, (StatusCode, Json<OpsmlServerError>)>;
+
+async fn get_group_or_404(state: &AppState, name: &str) -> GroupResult<GroupRecord> {
+    state
+        .sql_client
+        .get_group(name)
+        .await
+        .map_err(|e| {
+            error!("Failed to get group: {e}");
+            internal_server_error(e, "Failed to get group", None)
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(OpsmlServerError::not_found("Group")),
+            )
+        })
+}
+
+async fn get_group_response(state: &AppState, name: &str) -> GroupResult<GroupResponse> {
+    let group = get_group_or_404(state, name).await?;
+
+    let roles = state.sql_client.get_group_roles(name).await.map_err(|e| {
+        error!("Failed to get group roles: {e}");
+        internal_server_error(e, "Failed to get group roles", None)
+    })?;
+
+    let members = state
+        .sql_client
+        .get_group_members(name)
+        .await
+        .map_err(|e| {
+            error!("Failed to get group members: {e}");
+            internal_server_error(e, "Failed to get group members", None)
+        })?;
+
+    Ok(GroupResponse {
+        group,
+        roles,
+        members,
+    })
+}
+
+#[utoipa::path(
+    post,
+    path = "/opsml/api/group",
+    request_body = CreateGroupRequest,
+    responses(
+        (status = 200, description = "Group created", body = GroupRecord),
+        (status = 403, description = "Forbidden — requires admin", body = OpsmlServerError),
+        (status = 409, description = "Group already exists", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "group"
+)]
+/// Create a group
+///
+/// Requires admin permissions
+#[instrument(skip_all)]
+pub(crate) async fn create_group(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(req): Json<CreateGroupRequest>,
+) -> GroupResult<Json<GroupRecord>> {
+    require_admin(&perms)?;
+
+    if req.name.trim().is_empty() {
+        return OpsmlServerError::bad_request("Group name must not be empty")
+            .into_response(StatusCode::BAD_REQUEST);
+    }
+
+    let existing = state.sql_client.get_group(&req.name).await.map_err(|e| {
+        error!("Failed to get group: {e}");
+        internal_server_error(e, "Failed to get group", None)
+    })?;
+
+    if existing.is_some() {
+        return OpsmlServerError::new(format!("Group {} already exists", req.name))
+            .into_response(StatusCode::CONFLICT);
+    }
+
+    let group = GroupRecord {
+        name: req.name,
+        description: req.description.unwrap_or_default(),
+        sso_groups: req.sso_groups.unwrap_or_default(),
+        created_at: get_utc_datetime(),
+    };
+
+    if let Err(e) = state.sql_client.insert_group(&group).await {
+        error!("Failed to create group: {e}");
+        return Err(internal_server_error(e, "Failed to create group", None));
+    }
+
+    info!("Group {} created", group.name);
+
+    Ok(Json(group))
+}
+
+#[utoipa::path(
+    get,
+    path = "/opsml/api/group",
+    responses(
+        (status = 200, description = "All groups", body = GroupListResponse),
+        (status = 403, description = "Forbidden — requires admin", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "group"
+)]
+/// List groups
+///
+/// Requires admin permissions
+#[instrument(skip_all)]
+pub(crate) async fn list_groups(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+) -> GroupResult<Json<GroupListResponse>> {
+    require_admin(&perms)?;
+
+    let groups = state.sql_client.get_groups().await.map_err(|e| {
+        error!("Failed to list groups: {e}");
+        internal_server_error(e, "Failed to list groups", None)
+    })?;
+
+    Ok(Json(GroupListResponse { groups }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/opsml/api/group/{name}",
+    params(
+        ("name" = String, Path, description = "Group name"),
+    ),
+    responses(
+        (status = 200, description = "Group with its roles and members", body = GroupResponse),
+        (status = 403, description = "Forbidden — requires admin", body = OpsmlServerError),
+        (status = 404, description = "Group not found", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "group"
+)]
+/// Get a group, its space roles and members
+///
+/// Requires admin permissions
+#[instrument(skip_all)]
+pub(crate) async fn get_group(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Path(name): Path<String>,
+) -> GroupResult<Json<GroupResponse>> {
+    require_admin(&perms)?;
+
+    Ok(Json(get_group_response(&state, &name).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/opsml/api/group/{name}",
+    params(
+        ("name" = String, Path, description = "Group name"),
+    ),
+    responses(
+        (status = 200, description = "Group deleted", body = inline(serde_json::Value)),
+        (status = 403, description = "Forbidden — requires admin", body = OpsmlServerError),
+        (status = 404, description = "Group not found", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "group"
+)]
+/// Delete a group along with its roles and memberships
+///
+/// Requires admin permissions
+#[instrument(skip_all)]
+pub(crate) async fn delete_group(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Path(name): Path<String>,
+) -> GroupResult<Json<serde_json::Value>> {
+    require_admin(&perms)?;
+    get_group_or_404(&state, &name).await?;
+
+    if let Err(e) = state.sql_client.delete_group(&name).await {
+        error!("Failed to delete group: {e}");
+        return Err(internal_server_error(e, "Failed to delete group", None));
+    }
+
+    info!("Group {} deleted", name);
+
+    Ok(Json(serde_json::json!({"success": true})))
+}
+
+#[utoipa::path(
+    put,
+    path = "/opsml/api/group/{name}/role",
+    params(
+        ("name" = String, Path, description = "Group name"),
+    ),
+    request_body = SetGroupRoleRequest,
+    responses(
+        (status = 200, description = "Updated group", body = GroupResponse),
+        (status = 403, description = "Forbidden — requires admin", body = OpsmlServerError),
+        (status = 404, description = "Group not found", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "group"
+)]
+/// Grant a space role to a group, replacing any existing role on that space.
+/// Use the space `all` to grant the role on every space
+///
+/// Requires admin permissions
+#[instrument(skip_all)]
+pub(crate) async fn set_group_role(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Path(name): Path<String>,
+    Json(req): Json<SetGroupRoleRequest>,
+) -> GroupResult<Json<GroupResponse>> {
+    require_admin(&perms)?;
+    get_group_or_404(&state, &name).await?;
+
+    let role = GroupRole {
+        group_name: name.clone(),
+        space: req.space,
+        role: req.role,
+    };
+
+    if let Err(e) = state.sql_client.upsert_group_role(&role).await {
+        error!("Failed to set group role: {e}");
+        return Err(internal_server_error(e, "Failed to set group role", None));
+    }
+
+    info!(
+        "Group {} granted {} on space {}",
+        name, role.role, role.space
+    );
+
+    Ok(Json(get_group_response(&state, &name).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/opsml/api/group/{name}/role/{space}",
+    params(
+        ("name" = String, Path, description = "Group name"),
+        ("space" = String, Path, description = "Space to remove the role from"),
+    ),
+    responses(
+        (status = 200, description = "Updated group", body = GroupResponse),
+        (status = 403, description = "Forbidden — requires admin", body = OpsmlServerError),
+        (status = 404, description = "Group not found", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "group"
+)]
+/// Remove a group's role on a space
+///
+/// Requires admin permissions
+#[instrument(skip_all)]
+pub(crate) async fn delete_group_role(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Path((name, space)): Path<(String, String)>,
+) -> GroupResult<Json<GroupResponse>> {
+    require_admin(&perms)?;
+    get_group_or_404(&state, &name).await?;
+
+    if let Err(e) = state.sql_client.delete_group_role(&name, &space).await {
+        error!("Failed to delete group role: {e}");
+        return Err(internal_server_error(
+            e,
+            "Failed to delete group role",
+            None,
+        ));
+    }
+
+    Ok(Json(get_group_response(&state, &name).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/opsml/api/group/{name}/member",
+    params(
+        ("name" = String, Path, description = "Group name"),
+    ),
+    request_body = AddGroupMemberRequest,
+    responses(
+        (status = 200, description = "Updated group", body = GroupResponse),
+        (status = 403, description = "Forbidden — requires admin", body = OpsmlServerError),
+        (status = 404, description = "Group or user not found", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "group"
+)]
+/// Add a user to a group
+///
+/// Requires admin permissions
+#[instrument(skip_all)]
+pub(crate) async fn add_group_member(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Path(name): Path<String>,
+    Json(req): Json<AddGroupMemberRequest>,
+) -> GroupResult<Json<GroupResponse>> {
+    require_admin(&perms)?;
+    get_group_or_404(&state, &name).await?;
+
+    let user = state
+        .sql_client
+        .get_user(&req.username, None)
+        .await
+        .map_err(|e| {
+            error!("Failed to get user: {e}");
+            internal_server_error(e, "Failed to get user", None)
+        })?;
+
+    if user.is_none() {
+        return OpsmlServerError::user_not_found().into_response(StatusCode::NOT_FOUND);
+    }
+
+    if let Err(e) = state
+        .sql_client
+        .add_group_member(&name, &req.username)
+        .await
+    {
+        error!("Failed to add group member: {e}");
+        return Err(internal_server_error(e, "Failed to add group member", None));
+    }
+
+    info!("User {} added to group {}", req.username, name);
+
+    Ok(Json(get_group_response(&state, &name).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/opsml/api/group/{name}/member/{username}",
+    params(
+        ("name" = String, Path, description = "Group name"),
+        ("username" = String, Path, description = "User to remove"),
+    ),
+    responses(
+        (status = 200, description = "Updated group", body = GroupResponse),
+        (status = 403, description = "Forbidden — requires admin", body = OpsmlServerError),
+        (status = 404, description = "Group not found", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "group"
+)]
+/// Remove a user from a group
+///
+/// Requires admin permissions
+#[instrument(skip_all)]
+pub(crate) async fn remove_group_member(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Path((name, username)): Path<(String, String)>,
+) -> GroupResult<Json<GroupResponse>> {
+    require_admin(&perms)?;
+    get_group_or_404(&state, &name).await?;
+
+    if let Err(e) = state.sql_client.remove_group_member(&name, &username).await {
+        error!("Failed to remove group member: {e}");
+        return Err(internal_server_error(
+            e,
+            "Failed to remove group member",
+            None,
+        ));
+    }
+
+    info!("User {} removed from group {}", username, name);
+
+    Ok(Json(get_group_response(&state, &name).await?))
+}
+
+pub async fn get_group_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        Router::new()
+            .route(&format!("{prefix}/group"), post(create_group))
+            .route(&format!("{prefix}/group"), get(list_groups))
+            .route(&format!("{prefix}/group/{{name}}"), get(get_group))
+            .route(&format!("{prefix}/group/{{name}}"), delete(delete_group))
+            .route(
+                &format!("{prefix}/group/{{name}}/role"),
+                put(set_group_role),
+            )
+            .route(
+                &format!("{prefix}/group/{{name}}/role/{{space}}"),
+                delete(delete_group_role),
+            )
+            .route(
+                &format!("{prefix}/group/{{name}}/member"),
+                post(add_group_member),
+            )
+            .route(
+                &format!("{prefix}/group/{{name}}/member/{{username}}"),
+                delete(remove_group_member),
+            )
+    }));
+
+    match result {
+        Ok(router) => Ok(router),
+        Err(_) => {
+            error!("Failed to create group router");
+            Err(anyhow::anyhow!("Failed to create group router"))
+                .context("Panic occurred while creating the router")
+        }
+    }
+}
@@ -14,9 +14,10 @@ use axum_extra::TypedHeader;
 use headers::UserAgent;
 use opsml_events::create_audit_event;
 use opsml_events::{AuditContext, Event};
-use opsml_types::contracts::SpaceNameEvent;
+use opsml_types::contracts::{RegistryEvent, SpaceNameEvent};
 use std::net::SocketAddr;
 use std::sync::Arc;
+use tracing::error;
 
 pub async fn event_middleware(
     State(state): State<Arc<AppState>>,
@@ -46,5 +47,21 @@ pub async fn event_middleware(
         response.extensions_mut().remove::<SpaceNameEvent>();
     }
 
+    // Handle registry events. These are awaited so they are in the outbox before the client sees the response,
+    // and the request fails if they cannot be recorded so a change is never silently missing from webhooks
+    if let Some(event) = response.extensions_mut().remove::<RegistryEvent>()
+        && let Err(e) = state.event_bus.publish_registry_event(event).await
+    {
+        error!("Failed to publish registry event: {e}");
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(AuditError {
+                error: e.to_string(),
+                message: "Change was saved but its registry event could not be recorded"
+                    .to_string(),
+            }),
+        ));
+    }
+
     Ok(response)
 }
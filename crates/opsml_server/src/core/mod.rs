@@ -1,3 +1,4 @@
+pub mod admin;
 pub mod agent;
 pub mod agentic;
 pub mod app;
@@ -10,6 +11,7 @@ pub mod docs;
 pub mod error;
 pub mod experiment;
 pub mod files;
+pub mod group;
 pub mod health;
 pub mod middleware;
 pub mod openapi;
@@ -19,4 +21,6 @@ pub mod settings;
 pub mod setup;
 pub mod shutdown;
 pub mod state;
+pub mod trash;
 pub mod user;
+pub mod webhook;
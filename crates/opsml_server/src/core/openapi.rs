@@ -16,6 +16,7 @@ use crate::core::docs::route::{
 use crate::core::error::OpsmlServerError;
 use crate::core::experiment::types::GroupedMetric;
 use crate::core::user::schema::{
+    ApiTokenListResponse, ApiTokenResponse, CreateApiTokenRequest, CreateApiTokenResponse,
     CreateUserRequest, CreateUserResponse, CreateUserUiResponse, RecoveryResetRequest,
     ResetPasswordResponse, UpdateUserRequest, UserListResponse, UserResponse,
 };
@@ -24,21 +25,36 @@ use opsml_types::{
     api::types::{Alive, JwtToken},
     cards::{CPUMetrics, HardwareMetrics, MemoryMetrics, Metric, NetworkRates, Parameter},
     contracts::{
-        ArtifactKey, ArtifactKeyRequest, ArtifactQueryArgs, ArtifactRecord, CardCursor,
-        CardQueryArgs, CardSpaceResponse, CardTagsResponse, CompareHashRequest,
-        CompareHashResponse, CompleteMultipartUpload, CreateArtifactRequest,
-        CreateArtifactResponse, CreateCardResponse, CrudSpaceRequest, CrudSpaceResponse,
-        DashboardStats, DeleteCardRequest, DeleteFileQuery, DeleteFileResponse, DownloadFileQuery,
-        FileInfo, FileTreeNode, FileTreeResponse, GetHardwareMetricRequest, GetMetricNamesRequest,
-        GetMetricNamesResponse, GetMetricRequest, GetParameterRequest, HardwareMetricRequest,
-        HardwareMetricResponse, InvokeMetadata, InvokeRequest, InvokeResponse, JobStatus,
-        ListFileInfoResponse, ListFileQuery, ListFileResponse, McpServers, MetricRequest,
-        MetricResponse, MultiPartQuery, MultiPartSession, ParameterRequest, ParameterResponse,
-        PresignedQuery, PresignedUrl, QueryPageRequest, RawFile, RawFileRequest,
-        RegistrySpaceRequest, RegistryStatsRequest, ServiceQueryArgs, ServiceType, SpaceRecord,
-        SpaceRecordResponse, SpaceStats, SpaceStatsResponse, StorageSettings, UiSettings,
-        UidRequest, UidResponse, UpdateCardResponse, UploadResponse, VersionCursor,
-        VersionPageRequest, skill::MarketplaceStats,
+        AddGroupMemberRequest, ArtifactKey, ArtifactKeyRequest, ArtifactManifestRequest,
+        ArtifactQueryArgs, ArtifactRecord, AuditEventRecord, AuditExportFormat, AuditPageResponse,
+        AuditQueryArgs, CardAlias, CardAliasListResponse, CardAliasQuery, CardAliasResponse,
+        CardCursor, CardQueryArgs, CardSearchHit, CardSearchQuery, CardSearchResponse,
+        CardSpaceResponse, CardTagsResponse, CompareHashRequest, CompareHashResponse,
+        CompleteMultipartUpload, ConsistencyIssue, ConsistencyIssueKind, ConsistencyReport,
+        ConsistencyScanRequest, CreateArtifactRequest, CreateArtifactResponse, CreateCardResponse,
+        CreateGroupRequest, CreateWebhookRequest, CreateWebhookResponse, CrudSpaceRequest,
+        CrudSpaceResponse, DashboardStats, DeleteCardAliasRequest, DeleteCardRequest,
+        DeleteFileQuery, DeleteFileResponse, DeleteRetentionPolicyRequest, DeliveryStatus,
+        DownloadFileQuery, FileInfo, FileTreeNode, FileTreeResponse, GetHardwareMetricRequest,
+        GetMetricNamesRequest, GetMetricNamesResponse, GetMetricRequest, GetParameterRequest,
+        GroupListResponse, GroupRecord, GroupResponse, GroupRole, HardwareMetricRequest,
+        HardwareMetricResponse, InvocationMode, InvokeMetadata, InvokeRequest, InvokeResponse,
+        JobStatus, LineageDirection, LineageEdge, LineageGraph, LineageNode, LineageQuery,
+        LineageRelation, ListFileInfoResponse, ListFileQuery, ListFileResponse, McpServers,
+        MetricRequest, MetricResponse, MultiPartQuery, MultiPartSession, ParameterRequest,
+        ParameterResponse, PresignedQuery, PresignedUrl, PurgeTrashRequest, PurgeTrashResponse,
+        QueryPageRequest, RawFile, RawFileRequest, RegistryEvent, RegistryEventType,
+        RegistrySpaceRequest, RegistryStatsRequest, RegistryUsage, RestoreCardRequest,
+        RestoreCardResponse, RetentionPolicy, RetentionPolicyListResponse, RetentionRunReport,
+        RotateArtifactKeysRequest, RotateArtifactKeysResponse, ServiceQueryArgs, ServiceType,
+        SetCardAliasRequest, SetGroupRoleRequest, SpaceQuotaRequest, SpaceRecord,
+        SpaceRecordResponse, SpaceRegistryUsage, SpaceRole, SpaceStats, SpaceStatsResponse,
+        SpaceStorageUsage, StepStatus, StepTrace, StorageSettings, TrashListResponse, TrashQuery,
+        TrashReason, TrashedCard, UiSettings, UidRequest, UidResponse, UpdateCardResponse,
+        UploadResponse, UsageRecountRequest, UsageRecountResponse, VersionCursor,
+        VersionPageRequest, Webhook, WebhookDelivery, WebhookDeliveryListResponse,
+        WebhookDeliveryQuery, WebhookListResponse, WebhookQuery, WorkflowRun, WorkflowRunRequest,
+        WorkflowRunStatus, skill::MarketplaceStats,
     },
 };
 use utoipa::OpenApi;
@@ -79,9 +95,44 @@ use utoipa::OpenApi;
         crate::core::user::route::get_user,
         crate::core::user::route::update_user,
         crate::core::user::route::delete_user,
+        crate::core::user::route::create_api_token,
+        crate::core::user::route::list_api_tokens,
+        crate::core::user::route::revoke_api_token,
+        // group
+        crate::core::group::route::create_group,
+        crate::core::group::route::list_groups,
+        crate::core::group::route::get_group,
+        crate::core::group::route::delete_group,
+        crate::core::group::route::set_group_role,
+        crate::core::group::route::delete_group_role,
+        crate::core::group::route::add_group_member,
+        crate::core::group::route::remove_group_member,
+        // webhook
+        crate::core::webhook::route::create_webhook,
+        crate::core::webhook::route::list_webhooks,
+        crate::core::webhook::route::delete_webhook,
+        crate::core::webhook::route::list_webhook_deliveries,
+        // admin
+        crate::core::admin::route::rotate_artifact_keys,
+        crate::core::admin::route::scan_storage,
+        crate::core::admin::route::recount_storage_usage,
+        crate::core::admin::route::set_space_quota,
+        // trash
+        crate::core::trash::route::list_trash,
+        crate::core::trash::route::restore_trashed_card,
+        crate::core::trash::route::purge_trash,
+        crate::core::trash::route::list_retention_policies,
+        crate::core::trash::route::upsert_retention_policy,
+        crate::core::trash::route::delete_retention_policy,
+        crate::core::trash::route::run_retention_policies,
+        // audit
+        crate::core::audit::route::query_audit_events,
+        crate::core::audit::route::export_audit_events,
         // agentic
         crate::core::agentic::agent_route::invoke_agent,
         crate::core::agentic::agent_route::get_agent_job,
+        crate::core::agentic::agent_route::cancel_agent_job,
+        crate::core::agentic::workflow_route::run_workflow,
         crate::core::agentic::route::get_skill_latest,
         crate::core::agentic::route::get_skill_pinned,
         crate::core::agentic::route::get_skill_map,
@@ -114,6 +165,11 @@ use utoipa::OpenApi;
         crate::core::cards::route::get_readme,
         crate::core::cards::route::create_readme,
         crate::core::cards::route::compare_content_hash,
+        crate::core::cards::route::list_card_aliases,
+        crate::core::cards::route::set_card_alias,
+        crate::core::cards::route::delete_card_alias,
+        crate::core::cards::route::get_card_lineage,
+        crate::core::cards::route::search_cards,
         // experiment
         crate::core::experiment::route::insert_metrics,
         crate::core::experiment::route::get_metrics,
@@ -136,6 +192,7 @@ use utoipa::OpenApi;
         crate::core::files::route::delete_file,
         crate::core::files::route::download_file,
         crate::core::files::route::get_artifact_key,
+        crate::core::files::route::set_artifact_manifest,
         crate::core::files::route::create_artifact_record,
         crate::core::files::route::query_artifact_records,
         // scouter
@@ -211,14 +268,74 @@ use utoipa::OpenApi;
             UserListResponse,
             RecoveryResetRequest,
             ResetPasswordResponse,
+            CreateApiTokenRequest,
+            CreateApiTokenResponse,
+            ApiTokenResponse,
+            ApiTokenListResponse,
+            // group
+            GroupRecord,
+            GroupRole,
+            SpaceRole,
+            CreateGroupRequest,
+            SetGroupRoleRequest,
+            AddGroupMemberRequest,
+            GroupResponse,
+            GroupListResponse,
+            // webhook
+            RegistryEventType,
+            RegistryEvent,
+            Webhook,
+            CreateWebhookRequest,
+            CreateWebhookResponse,
+            WebhookQuery,
+            WebhookListResponse,
+            DeliveryStatus,
+            WebhookDelivery,
+            WebhookDeliveryQuery,
+            WebhookDeliveryListResponse,
+            // admin
+            RotateArtifactKeysRequest,
+            RotateArtifactKeysResponse,
+            ConsistencyScanRequest,
+            ConsistencyIssueKind,
+            ConsistencyIssue,
+            SpaceStorageUsage,
+            ConsistencyReport,
+            UsageRecountRequest,
+            SpaceRegistryUsage,
+            UsageRecountResponse,
+            // trash
+            TrashReason,
+            TrashedCard,
+            TrashQuery,
+            TrashListResponse,
+            RestoreCardRequest,
+            RestoreCardResponse,
+            PurgeTrashRequest,
+            PurgeTrashResponse,
+            RetentionPolicy,
+            RetentionPolicyListResponse,
+            DeleteRetentionPolicyRequest,
+            RetentionRunReport,
+            // audit
+            AuditQueryArgs,
+            AuditEventRecord,
+            AuditPageResponse,
+            AuditExportFormat,
             // agentic
             ArtifactMeta,
             MapResponse,
             InvokeRequest,
+            InvocationMode,
             InvokeResponse,
             InvokeMetadata,
             JobStatus,
             MarketplaceStats,
+            WorkflowRunRequest,
+            WorkflowRun,
+            WorkflowRunStatus,
+            StepTrace,
+            StepStatus,
             // genai / mcp
             McpServers,
             ServiceQueryArgs,
@@ -230,6 +347,8 @@ use utoipa::OpenApi;
             CardSpaceResponse,
             CardTagsResponse,
             SpaceStats,
+            RegistryUsage,
+            SpaceQuotaRequest,
             SpaceStatsResponse,
             SpaceRecord,
             SpaceRecordResponse,
@@ -244,6 +363,21 @@ use utoipa::OpenApi;
             CreateCardResponse,
             UpdateCardResponse,
             DeleteCardRequest,
+            CardAlias,
+            CardAliasQuery,
+            SetCardAliasRequest,
+            DeleteCardAliasRequest,
+            CardAliasResponse,
+            CardAliasListResponse,
+            LineageQuery,
+            LineageDirection,
+            LineageRelation,
+            LineageNode,
+            LineageEdge,
+            LineageGraph,
+            CardSearchQuery,
+            CardSearchHit,
+            CardSearchResponse,
             CompareHashRequest,
             CompareHashResponse,
             DashboardStats,
@@ -291,6 +425,7 @@ use utoipa::OpenApi;
             CompleteMultipartUpload,
             UploadResponse,
             ArtifactKeyRequest,
+            ArtifactManifestRequest,
             ArtifactKey,
             ArtifactQueryArgs,
             ArtifactRecord,
@@ -308,6 +443,11 @@ use utoipa::OpenApi;
         (name = "settings", description = "Storage and UI feature flag settings — no auth required"),
         (name = "auth", description = "Authentication — login, logout, token refresh, SSO, and password recovery"),
         (name = "user", description = "User management — create, read, update, delete users"),
+        (name = "group", description = "Groups — space-scoped roles (viewer/editor/owner) and membership (admin only)"),
+        (name = "webhook", description = "Webhooks — per-space subscriptions to registry events with signed, retried deliveries"),
+        (name = "admin", description = "Server administration — artifact key rotation, storage consistency scans and space quotas (admin only)"),
+        (name = "trash", description = "Trash — restore deleted cards, purge them and manage per-space retention (purge and retention are admin only)"),
+        (name = "audit", description = "Audit log — query and export recorded audit events (admin only)"),
         (name = "agentic", description = "Agentic primitives — invoke agents, browse skills, subagents, tools, and marketplace"),
         (name = "genai", description = "GenAI service registry — MCP server discovery"),
         (name = "cards", description = "Card registry — create, list, load, update, delete versioned artifact cards"),
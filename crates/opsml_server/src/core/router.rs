@@ -1,5 +1,7 @@
+use crate::core::admin::route::get_admin_router;
 use crate::core::agent::route::get_agent_router;
 use crate::core::agentic::route::get_agentic_router;
+use crate::core::audit::route::get_audit_router;
 use crate::core::auth::middleware::auth_api_middleware;
 use crate::core::auth::route::get_auth_router;
 use crate::core::capabilities::route::get_capabilities_router;
@@ -8,6 +10,7 @@ use crate::core::debug::route::get_debug_router;
 use crate::core::docs::route::get_docs_router;
 use crate::core::experiment::route::get_experiment_router;
 use crate::core::files::route::get_file_router;
+use crate::core::group::route::get_group_router;
 use crate::core::health::route::get_health_router;
 use crate::core::middleware::event::event_middleware;
 use crate::core::middleware::metrics::track_metrics;
@@ -15,7 +18,9 @@ use crate::core::openapi::ApiDoc;
 use crate::core::scouter::route::get_scouter_router;
 use crate::core::settings::route::get_settings_router;
 use crate::core::state::AppState;
+use crate::core::trash::route::get_trash_router;
 use crate::core::user::route::get_user_router;
+use crate::core::webhook::route::get_webhook_router;
 use anyhow::Result;
 use axum::http::{
     HeaderName, HeaderValue, Method,
@@ -64,9 +69,14 @@ pub async fn create_router(app_state: Arc<AppState>) -> Result<Router> {
     let run_routes = get_experiment_router(ROUTE_PREFIX).await?;
     let auth_routes = get_auth_router(ROUTE_PREFIX).await?;
     let user_routes = get_user_router(ROUTE_PREFIX).await?;
+    let group_routes = get_group_router(ROUTE_PREFIX).await?;
+    let audit_routes = get_audit_router(ROUTE_PREFIX).await?;
     let scouter_routes = get_scouter_router(ROUTE_PREFIX).await?;
     let agent_routes = get_agent_router(ROUTE_PREFIX).await?;
     let agentic_routes = get_agentic_router(ROUTE_PREFIX).await?;
+    let webhook_routes = get_webhook_router(ROUTE_PREFIX).await?;
+    let admin_routes = get_admin_router(ROUTE_PREFIX).await?;
+    let trash_routes = get_trash_router(ROUTE_PREFIX).await?;
     let docs_routes = get_docs_router(V1_PREFIX).await?;
     let capabilities_routes = get_capabilities_router(V1_PREFIX).await?;
 
@@ -77,9 +87,14 @@ pub async fn create_router(app_state: Arc<AppState>) -> Result<Router> {
         .merge(card_routes)
         .merge(run_routes)
         .merge(user_routes)
+        .merge(group_routes)
+        .merge(audit_routes)
         .merge(scouter_routes)
         .merge(agent_routes)
         .merge(agentic_routes)
+        .merge(webhook_routes)
+        .merge(admin_routes)
+        .merge(trash_routes)
         .route_layer(middleware::from_fn_with_state(
             app_state.clone(),
             event_middleware,
@@ -1,5 +1,5 @@
-use crate::core::error::{OpsmlServerError, internal_server_error};
-use crate::core::files::utils::download_artifacts;
+use crate::core::error::{OpsmlServerError, ServerError, internal_server_error};
+use crate::core::files::utils::{download_artifacts, record_written_file, stored_bytes};
 use crate::core::scouter;
 use crate::core::scouter::types::DriftProfileResult;
 use crate::core::scouter::utils::load_drift_profiles;
@@ -162,6 +162,8 @@ pub async fn update_drift_profile(
         .sql_client
         .get_artifact_key(&req.uid, &req.registry_type.to_string())
         .await
+        .map_err(ServerError::from)
+        .and_then(|key| state.open_artifact_key(&key))
         .map_err(|e| {
             error!("Failed to get artifact key: {e}");
             internal_server_error(e, "Failed to get artifact key", None)
@@ -198,6 +200,10 @@ pub async fn update_drift_profile(
         internal_server_error(e, "Failed to get encryption key", None)
     })?;
 
+    let previous = stored_bytes(&state.storage_client, &drift_path)
+        .await
+        .unwrap_or(0);
+
     save_encrypted_profile(
         &req.request.profile,
         filename,
@@ -207,6 +213,14 @@ pub async fn update_drift_profile(
     )
     .await?;
 
+    record_written_file(
+        &state.storage_client,
+        &state.sql_client,
+        &drift_path.to_string_lossy(),
+        previous,
+    )
+    .await;
+
     // 2. Scouter task
     let exchange_token = state.exchange_token_from_perms(&perms).await.map_err(|e| {
         error!("Failed to exchange token for scouter: {e}");
@@ -599,6 +613,8 @@ pub async fn get_drift_profiles_for_ui(
         .sql_client
         .get_artifact_key(&req.uid, &registry)
         .await
+        .map_err(ServerError::from)
+        .and_then(|key| state.open_artifact_key(&key))
         .map_err(|e| {
             error!("Failed to get artifact key: {e}");
             internal_server_error(e, "Failed to get artifact key", None)
@@ -642,6 +658,7 @@ pub async fn get_drift_profiles_for_ui(
     download_artifacts(
         state.storage_client.clone(),
         state.sql_client.clone(),
+        &*state.key_provider,
         &dest_path,
         source_path,
         &registry,
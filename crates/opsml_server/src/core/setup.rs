@@ -149,7 +149,10 @@ pub async fn setup_components() -> AnyhowResult<(
     ScouterApiClient,
 )> {
     // setup config
-    let config = OpsmlConfig::default();
+    let config = OpsmlConfig::load().context(Colorize::purple("❌ Failed to load config"))?;
+    config
+        .validate()
+        .context(Colorize::purple("❌ Invalid server config"))?;
 
     // start logging
     let logging = setup_logging(&config.logging_config);
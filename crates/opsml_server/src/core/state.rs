@@ -1,13 +1,16 @@
 use crate::core::error::ServerError;
 use crate::core::scouter::client::ScouterApiClient;
+use crate::core::user::utils::get_group_grants;
 use opsml_agent::AgentStore;
 use opsml_auth::auth::AuthManager;
 use opsml_auth::permission::UserPermissions;
+use opsml_crypt::KeyProvider;
 use opsml_events::EventBus;
 use opsml_settings::config::{OpsmlConfig, OpsmlStorageSettings};
 use opsml_sql::enums::client::SqlClientEnum;
 use opsml_sql::traits::UserLogicTrait;
 use opsml_storage::storage::enums::client::StorageClientEnum;
+use opsml_types::contracts::ArtifactKey;
 use std::sync::Arc;
 use tracing::error;
 
@@ -17,12 +20,19 @@ pub struct AppState {
     pub auth_manager: AuthManager,
     pub config: OpsmlConfig,
     pub storage_settings: OpsmlStorageSettings,
+    pub key_provider: Arc<dyn KeyProvider>,
     pub scouter_client: ScouterApiClient,
     pub event_bus: EventBus,
     pub agent_store: Arc<AgentStore>,
 }
 
 impl AppState {
+    /// Artifact keys are stored wrapped with a KEK. Returns the uid-wrapped form that is used for
+    /// encryption and handed to clients
+    pub fn open_artifact_key(&self, key: &ArtifactKey) -> Result<ArtifactKey, ServerError> {
+        Ok(key.open(&*self.key_provider)?)
+    }
+
     pub async fn exchange_token_from_perms(
         &self,
         perms: &UserPermissions,
@@ -36,8 +46,10 @@ impl AppState {
                 ServerError::UserNotFoundError
             })?;
 
+        let grants = get_group_grants(&self.sql_client, &user.username).await?;
+
         self.auth_manager
-            .exchange_token_for_scouter(&user)
+            .exchange_token_for_scouter(&user, &grants)
             .await
             .map_err(|e| {
                 error!("Failed to exchange token from permissions: {e}");
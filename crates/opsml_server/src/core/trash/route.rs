@@ -0,0 +1,449 @@
+use crate::core::auth::util::require_admin;
+use crate::core::error::{OpsmlServerError, internal_server_error};
+use crate::core::state::AppState;
+use crate::core::trash::utils::{purge_trashed_cards, run_retention};
+use anyhow::{Context, Result};
+use axum::{
+    Extension, Json, Router,
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{delete, get, post, put},
+};
+use chrono::Duration;
+use opsml_auth::permission::UserPermissions;
+use opsml_events::AuditContext;
+use opsml_sql::enums::utils::restore_card;
+use opsml_sql::error::SqlError;
+use opsml_sql::schemas::schema::TrashSqlRecord;
+use opsml_sql::traits::TrashLogicTrait;
+use opsml_types::RegistryType;
+use opsml_types::contracts::{
+    DeleteRetentionPolicyRequest, Operation, PurgeTrashRequest, PurgeTrashResponse, RegistryEvent,
+    RegistryEventType, ResourceType, RestoreCardRequest, RestoreCardResponse, RetentionPolicy,
+    RetentionPolicyListResponse, RetentionRunReport, SpaceNameEvent, TrashListResponse, TrashQuery,
+    TrashedCard,
+};
+use opsml_utils::utils::get_utc_datetime;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::Arc;
+use tracing::{error, info, instrument};
+
+type TrashResult<T> = Result<T, (StatusCode, Json<OpsmlServerError>)>;
+
+#[utoipa::path(
+    get,
+    path = "/opsml/api/card/trash",
+    params(
+        ("space" = Option<String>, Query, description = "Filter by space"),
+        ("registry_type" = Option<String>, Query, description = "Filter by registry type"),
+        ("deleted_before" = Option<String>, Query, description = "Only cards trashed before this RFC 3339 timestamp"),
+    ),
+    responses(
+        (status = 200, description = "Trashed cards the caller can read, most recently trashed first", body = TrashListResponse),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "trash"
+)]
+/// List trashed cards
+#[instrument(skip_all)]
+pub(crate) async fn list_trash(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(query): Query<TrashQuery>,
+) -> TrashResult<Json<TrashListResponse>> {
+    if let Some(space) = &query.space
+        && !perms.has_read_permission(space)
+    {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let records = state.sql_client.list_trash(&query).await.map_err(|e| {
+        error!("Failed to list trash: {e}");
+        internal_server_error(e, "Failed to list trash", None)
+    })?;
+
+    let cards = records
+        .into_iter()
+        .filter(|record| perms.has_read_permission(&record.space))
+        .map(TrashedCard::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| {
+            error!("Failed to read trash record: {e}");
+            internal_server_error(e, "Failed to list trash", None)
+        })?;
+
+    Ok(Json(TrashListResponse { cards }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/opsml/api/card/trash/restore",
+    request_body = RestoreCardRequest,
+    responses(
+        (status = 200, description = "Card restored to its registry", body = RestoreCardResponse),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 404, description = "Card not found in the trash", body = OpsmlServerError),
+        (status = 409, description = "The same version has been registered again", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "trash"
+)]
+/// Restore a trashed card
+///
+/// Requires write permission on the card's space
+#[instrument(skip_all)]
+pub(crate) async fn restore_trashed_card(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(req): Json<RestoreCardRequest>,
+) -> TrashResult<Response> {
+    let record = state
+        .sql_client
+        .get_trash_record(&req.uid)
+        .await
+        .map_err(|e| {
+            error!("Failed to get trash record: {e}");
+            internal_server_error(e, "Failed to get trash record", None)
+        })?;
+
+    let Some(record) = record else {
+        return OpsmlServerError::not_found("Trashed card").into_response(StatusCode::NOT_FOUND);
+    };
+
+    if !perms.has_write_permission(&record.space) {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let record = restore_card(&state.sql_client, &record.uid)
+        .await
+        .map_err(|e| match e {
+            SqlError::RestoreConflict(_) => (
+                StatusCode::CONFLICT,
+                Json(OpsmlServerError::new(e.to_string())),
+            ),
+            SqlError::TrashRecordNotFound(_) => (
+                StatusCode::NOT_FOUND,
+                Json(OpsmlServerError::not_found("Trashed card")),
+            ),
+            e => {
+                error!("Failed to restore card: {e}");
+                internal_server_error(e, "Failed to restore card", None)
+            }
+        })?;
+
+    let registry_type = RegistryType::from_string(&record.registry_type).map_err(|e| {
+        error!("Failed to read trash record: {e}");
+        internal_server_error(e, "Failed to restore card", None)
+    })?;
+
+    info!(
+        "{} restored {} {}/{} v{}",
+        perms.username, registry_type, record.space, record.name, record.version
+    );
+
+    let mut response = Json(RestoreCardResponse {
+        uid: record.uid.clone(),
+        registry_type: registry_type.clone(),
+        space: record.space.clone(),
+        name: record.name.clone(),
+        version: record.version.clone(),
+    })
+    .into_response();
+
+    let audit_context = AuditContext {
+        resource_id: record.uid.clone(),
+        resource_type: ResourceType::Database,
+        metadata: serde_json::to_string(&req).unwrap_or_default(),
+        registry_type: Some(registry_type.clone()),
+        operation: Operation::Update,
+        access_location: None,
+    };
+
+    // the space name record is removed when the last version of a card is deleted
+    let space_name = SpaceNameEvent {
+        space: record.space.clone(),
+        name: record.name.clone(),
+        registry_type: registry_type.clone(),
+    };
+
+    let registry_event = RegistryEvent::new(
+        RegistryEventType::CardRestored,
+        &record.space,
+        Some(registry_type),
+        &record.uid,
+        serde_json::json!({
+            "uid": record.uid,
+            "name": record.name,
+            "version": record.version,
+        }),
+        &perms.username,
+    );
+
+    {
+        let extensions = response.extensions_mut();
+        extensions.insert(audit_context);
+        extensions.insert(space_name);
+        extensions.insert(registry_event);
+    }
+
+    Ok(response)
+}
+
+#[utoipa::path(
+    post,
+    path = "/opsml/api/admin/trash/purge",
+    request_body = PurgeTrashRequest,
+    responses(
+        (status = 200, description = "Trashed cards purged", body = PurgeTrashResponse),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "trash"
+)]
+/// Permanently delete trashed cards, their artifacts and artifact keys
+///
+/// Cards a stage alias still points to are skipped. Requires admin permission
+#[instrument(skip_all)]
+pub(crate) async fn purge_trash(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(req): Json<PurgeTrashRequest>,
+) -> TrashResult<Json<PurgeTrashResponse>> {
+    require_admin(&perms)?;
+
+    let query = TrashQuery {
+        space: req.space.clone(),
+        registry_type: None,
+        deleted_before: req
+            .older_than_days
+            .map(|days| get_utc_datetime() - Duration::days(days as i64)),
+    };
+
+    let records: Vec<TrashSqlRecord> = state
+        .sql_client
+        .list_trash(&query)
+        .await
+        .map_err(|e| {
+            error!("Failed to list trash: {e}");
+            internal_server_error(e, "Failed to list trash", None)
+        })?
+        .into_iter()
+        .filter(|record| {
+            req.uids
+                .as_ref()
+                .is_none_or(|uids| uids.contains(&record.uid))
+        })
+        .collect();
+
+    let response = purge_trashed_cards(&state, records, true, &perms.username)
+        .await
+        .map_err(|e| {
+            error!("Failed to purge trash: {e}");
+            internal_server_error(e, "Failed to purge trash", None)
+        })?;
+
+    info!(
+        "{} purged {} trashed card(s) ({} skipped)",
+        perms.username,
+        response.purged.len(),
+        response.skipped.len()
+    );
+
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    get,
+    path = "/opsml/api/admin/retention",
+    responses(
+        (status = 200, description = "Retention policies", body = RetentionPolicyListResponse),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "trash"
+)]
+/// List space retention policies
+///
+/// Requires admin permission
+#[instrument(skip_all)]
+pub(crate) async fn list_retention_policies(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+) -> TrashResult<Json<RetentionPolicyListResponse>> {
+    require_admin(&perms)?;
+
+    let policies = state
+        .sql_client
+        .get_retention_policies()
+        .await
+        .map_err(|e| {
+            error!("Failed to get retention policies: {e}");
+            internal_server_error(e, "Failed to get retention policies", None)
+        })?;
+
+    Ok(Json(RetentionPolicyListResponse { policies }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/opsml/api/admin/retention",
+    request_body = RetentionPolicy,
+    responses(
+        (status = 200, description = "Retention policy saved", body = RetentionPolicy),
+        (status = 400, description = "Invalid policy", body = OpsmlServerError),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "trash"
+)]
+/// Create or replace the retention policy of a space
+///
+/// Requires admin permission
+#[instrument(skip_all)]
+pub(crate) async fn upsert_retention_policy(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(mut policy): Json<RetentionPolicy>,
+) -> TrashResult<Json<RetentionPolicy>> {
+    require_admin(&perms)?;
+
+    if policy.keep_last_versions == Some(0) {
+        return OpsmlServerError::bad_request("keep_last_versions must be at least 1")
+            .into_response(StatusCode::BAD_REQUEST);
+    }
+
+    policy.updated_by = perms.username.clone();
+    policy.updated_at = get_utc_datetime();
+
+    state
+        .sql_client
+        .upsert_retention_policy(&policy)
+        .await
+        .map_err(|e| {
+            error!("Failed to save retention policy: {e}");
+            internal_server_error(e, "Failed to save retention policy", None)
+        })?;
+
+    info!(
+        "{} updated the retention policy of space {}",
+        perms.username, policy.space
+    );
+
+    Ok(Json(policy))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/opsml/api/admin/retention",
+    params(
+        ("space" = String, Query, description = "Space to remove the retention policy from"),
+    ),
+    responses(
+        (status = 200, description = "Retention policy removed", body = bool),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 404, description = "No retention policy for the space", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "trash"
+)]
+/// Remove the retention policy of a space
+///
+/// Requires admin permission
+#[instrument(skip_all)]
+pub(crate) async fn delete_retention_policy(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(req): Query<DeleteRetentionPolicyRequest>,
+) -> TrashResult<Json<bool>> {
+    require_admin(&perms)?;
+
+    let deleted = state
+        .sql_client
+        .delete_retention_policy(&req.space)
+        .await
+        .map_err(|e| {
+            error!("Failed to delete retention policy: {e}");
+            internal_server_error(e, "Failed to delete retention policy", None)
+        })?;
+
+    if !deleted {
+        return OpsmlServerError::not_found("Retention policy")
+            .into_response(StatusCode::NOT_FOUND);
+    }
+
+    Ok(Json(true))
+}
+
+#[utoipa::path(
+    post,
+    path = "/opsml/api/admin/retention/run",
+    responses(
+        (status = 200, description = "Retention applied", body = RetentionRunReport),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "trash"
+)]
+/// Apply every retention policy now instead of waiting for the background task
+///
+/// Requires admin permission
+#[instrument(skip_all)]
+pub(crate) async fn run_retention_policies(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+) -> TrashResult<Json<RetentionRunReport>> {
+    require_admin(&perms)?;
+
+    let report = run_retention(&state).await.map_err(|e| {
+        error!("Failed to apply retention policies: {e}");
+        internal_server_error(e, "Failed to apply retention policies", None)
+    })?;
+
+    Ok(Json(report))
+}
+
+pub async fn get_trash_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        Router::new()
+            .route(&format!("{prefix}/card/trash"), get(list_trash))
+            .route(
+                &format!("{prefix}/card/trash/restore"),
+                post(restore_trashed_card),
+            )
+            .route(&format!("{prefix}/admin/trash/purge"), post(purge_trash))
+            .route(
+                &format!("{prefix}/admin/retention"),
+                get(list_retention_policies),
+            )
+            .route(
+                &format!("{prefix}/admin/retention"),
+                put(upsert_retention_policy),
+            )
+            .route(
+                &format!("{prefix}/admin/retention"),
+                delete(delete_retention_policy),
+            )
+            .route(
+                &format!("{prefix}/admin/retention/run"),
+                post(run_retention_policies),
+            )
+    }));
+
+    match result {
+        Ok(router) => Ok(router),
+        Err(_) => {
+            error!("Failed to create trash router");
+            Err(anyhow::anyhow!("Failed to create trash router"))
+                .context("Panic occurred while creating the router")
+        }
+    }
+}
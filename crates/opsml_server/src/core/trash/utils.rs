@@ -0,0 +1,178 @@
+use crate::core::error::ServerError;
+use crate::core::files::utils::{record_storage_usage, stored_bytes};
+use crate::core::state::AppState;
+use chrono::Duration;
+use opsml_sql::enums::utils::{get_retention_candidates, trash_card};
+use opsml_sql::schemas::schema::TrashSqlRecord;
+use opsml_sql::traits::{AliasLogicTrait, ArtifactLogicTrait, SearchLogicTrait, TrashLogicTrait};
+use opsml_types::RegistryType;
+use opsml_types::contracts::{
+    PurgeTrashResponse, RegistryEvent, RegistryEventType, RetentionRunReport, TrashQuery,
+    TrashReason,
+};
+use opsml_utils::utils::get_utc_datetime;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tracing::{error, info, instrument, warn};
+
+/// User recorded on cards trashed or purged by the retention task
+pub const RETENTION_USER: &str = "retention";
+
+/// Whether a stage alias still points to a trashed card
+async fn is_aliased(
+    state: &AppState,
+    record: &TrashSqlRecord,
+    registry_type: &RegistryType,
+) -> Result<bool, ServerError> {
+    let aliases = state
+        .sql_client
+        .get_card_aliases(&record.space, &record.name, registry_type)
+        .await?;
+    Ok(aliases.iter().any(|alias| alias.uid == record.uid))
+}
+
+/// Permanently remove a trashed card: its storage prefix, artifact key, search document and
+/// trash record
+#[instrument(skip_all)]
+async fn purge_card(
+    state: &AppState,
+    record: &TrashSqlRecord,
+    registry_type: &RegistryType,
+) -> Result<(), ServerError> {
+    let registry_type = registry_type.to_string();
+
+    match state
+        .sql_client
+        .get_artifact_key(&record.uid, &registry_type)
+        .await
+    {
+        Ok(key) => {
+            let released = stored_bytes(&state.storage_client, &key.storage_path()).await?;
+            state.storage_client.rm(&key.storage_path(), true).await?;
+            record_storage_usage(&state.sql_client, &key.storage_key, -released).await;
+            state
+                .sql_client
+                .delete_artifact_key(&record.uid, &registry_type)
+                .await?;
+        }
+        Err(opsml_sql::error::SqlError::SqlxError(sqlx::Error::RowNotFound)) => {
+            warn!("No artifact key found for trashed card {}", record.uid);
+        }
+        Err(e) => return Err(e.into()),
+    }
+
+    state.sql_client.delete_search_document(&record.uid).await?;
+    state.sql_client.delete_trash_record(&record.uid).await?;
+
+    Ok(())
+}
+
+/// Purge trashed cards, skipping those a stage alias points to when `protect_aliased` is set.
+///
+/// A card that fails to purge is logged and left in the trash so a later purge can retry it
+#[instrument(skip_all)]
+pub async fn purge_trashed_cards(
+    state: &AppState,
+    records: Vec<TrashSqlRecord>,
+    protect_aliased: bool,
+    purged_by: &str,
+) -> Result<PurgeTrashResponse, ServerError> {
+    let mut response = PurgeTrashResponse::default();
+
+    for record in records {
+        let registry_type = RegistryType::from_string(&record.registry_type)?;
+
+        if protect_aliased && is_aliased(state, &record, &registry_type).await? {
+            response.skipped.push(record.uid);
+            continue;
+        }
+
+        if let Err(e) = purge_card(state, &record, &registry_type).await {
+            error!("Failed to purge card {}: {e}", record.uid);
+            continue;
+        }
+
+        let event = RegistryEvent::new(
+            RegistryEventType::CardPurged,
+            &record.space,
+            Some(registry_type),
+            &record.uid,
+            serde_json::json!({
+                "uid": record.uid,
+                "name": record.name,
+                "version": record.version,
+            }),
+            purged_by,
+        );
+        if let Err(e) = state.event_bus.publish_registry_event(event).await {
+            error!("Failed to publish registry event: {e}");
+        }
+
+        response.purged.push(record.uid);
+    }
+
+    Ok(response)
+}
+
+/// Apply every space retention policy once.
+///
+/// Versions beyond `keep_last_versions` are moved to the trash, then cards that have been in the
+/// trash longer than `purge_trashed_after_days` are purged
+#[instrument(skip_all)]
+pub async fn run_retention(state: &AppState) -> Result<RetentionRunReport, ServerError> {
+    let mut report = RetentionRunReport::default();
+
+    for policy in state.sql_client.get_retention_policies().await? {
+        for (registry_type, uid) in get_retention_candidates(&state.sql_client, &policy).await? {
+            match trash_card(
+                &state.sql_client,
+                &registry_type,
+                &uid,
+                TrashReason::Retention,
+                RETENTION_USER,
+            )
+            .await
+            {
+                Ok(_) => report.trashed.push(uid),
+                Err(e) => error!("Failed to trash card {uid}: {e}"),
+            }
+        }
+
+        if let Some(days) = policy.purge_trashed_after_days {
+            let query = TrashQuery {
+                space: Some(policy.space.clone()),
+                registry_type: None,
+                deleted_before: Some(get_utc_datetime() - Duration::days(days as i64)),
+            };
+            let records = state.sql_client.list_trash(&query).await?;
+            let purged =
+                purge_trashed_cards(state, records, policy.protect_aliased, RETENTION_USER).await?;
+            report.purged.extend(purged.purged);
+        }
+    }
+
+    Ok(report)
+}
+
+/// Periodically apply space retention policies
+pub fn spawn_retention_task(state: Arc<AppState>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let interval = state.config.retention_settings.interval_secs;
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            ticker.tick().await;
+
+            match run_retention(&state).await {
+                Ok(report) if !report.trashed.is_empty() || !report.purged.is_empty() => info!(
+                    "Retention trashed {} and purged {} card(s)",
+                    report.trashed.len(),
+                    report.purged.len()
+                ),
+                Ok(_) => {}
+                Err(e) => error!("Failed to apply retention policies: {e}"),
+            }
+        }
+    })
+}
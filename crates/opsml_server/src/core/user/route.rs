@@ -2,9 +2,10 @@ use crate::core::error::{OpsmlServerError, internal_server_error};
 use crate::core::scouter;
 use crate::core::state::AppState;
 use crate::core::user::schema::{
+    ApiTokenListResponse, ApiTokenResponse, CreateApiTokenRequest, CreateApiTokenResponse,
     CreateUserRequest, CreateUserResponse, UpdateUserRequest, UserListResponse, UserResponse,
 };
-use crate::core::user::utils::get_user as get_user_from_db;
+use crate::core::user::utils::{get_group_grants, get_user as get_user_from_db};
 use anyhow::{Context, Result};
 use axum::extract::Path;
 use axum::{
@@ -15,8 +16,9 @@ use axum::{
 };
 
 use opsml_auth::permission::UserPermissions;
+use opsml_auth::token::generate_api_token;
 use opsml_auth::util::generate_recovery_codes_with_hashes;
-use opsml_sql::schemas::schema::User;
+use opsml_sql::schemas::schema::{ApiToken, User};
 use opsml_sql::traits::UserLogicTrait;
 use opsml_types::RequestType;
 use password_auth::generate_hash;
@@ -24,6 +26,8 @@ use std::panic::{AssertUnwindSafe, catch_unwind};
 use std::sync::Arc;
 use tracing::{error, info, instrument};
 
+const DEFAULT_API_TOKEN_EXPIRY_DAYS: i64 = 90;
+
 #[utoipa::path(
     post,
     path = "/opsml/api/user",
@@ -390,6 +394,181 @@ pub(crate) async fn delete_user(
     Ok(Json(serde_json::json!({"success": true})))
 }
 
+#[utoipa::path(
+    post,
+    path = "/opsml/api/user/{username}/token",
+    params(
+        ("username" = String, Path, description = "User the token is issued for"),
+    ),
+    request_body = CreateApiTokenRequest,
+    responses(
+        (status = 200, description = "Token created. The plaintext token is only returned once", body = CreateApiTokenResponse),
+        (status = 400, description = "Invalid permissions requested", body = OpsmlServerError),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 404, description = "User not found", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "user"
+)]
+/// Create a personal API token
+///
+/// Users can create tokens for themselves; admins can create tokens for any user
+#[instrument(skip_all)]
+pub(crate) async fn create_api_token(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Path(username): Path<String>,
+    Json(create_req): Json<CreateApiTokenRequest>,
+) -> Result<Json<CreateApiTokenResponse>, (StatusCode, Json<OpsmlServerError>)> {
+    let is_admin = perms.group_permissions.contains(&"admin".to_string());
+    let is_self = perms.username == username;
+
+    if !is_admin && !is_self {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let user = get_user_from_db(&state.sql_client, &username, None).await?;
+    let grants = get_group_grants(&state.sql_client, &user.username)
+        .await
+        .map_err(|e| {
+            error!("Failed to resolve group permissions: {e}");
+            internal_server_error(e, "Failed to resolve group permissions", None)
+        })?;
+    let owner = UserPermissions::resolve(&user, &grants);
+
+    // a token can never grant more than its owner has, nor more than the caller has
+    // (prevents a token-authenticated caller from minting a broader token)
+    if let Some(permission) = create_req
+        .permissions
+        .iter()
+        .find(|p| !owner.covers(p) || !perms.covers(p))
+    {
+        return OpsmlServerError::bad_request(&format!(
+            "Permission {permission} cannot be granted to this token"
+        ))
+        .into_response(StatusCode::BAD_REQUEST);
+    }
+
+    let expires_at = match create_req
+        .expires_in_days
+        .unwrap_or(DEFAULT_API_TOKEN_EXPIRY_DAYS)
+    {
+        days if days < 0 => {
+            return OpsmlServerError::bad_request("expires_in_days must not be negative")
+                .into_response(StatusCode::BAD_REQUEST);
+        }
+        0 => None,
+        days => Some(chrono::Utc::now() + chrono::Duration::days(days)),
+    };
+
+    let generated = generate_api_token();
+    let token = ApiToken::new(
+        generated.token_id,
+        user.username,
+        create_req.name,
+        generated.token_hash,
+        create_req.permissions,
+        expires_at,
+    );
+
+    if let Err(e) = state.sql_client.insert_api_token(&token).await {
+        error!("Failed to create api token: {e}");
+        return Err(internal_server_error(e, "Failed to create api token", None));
+    }
+
+    info!("API token {} created for user {}", token.token_id, username);
+
+    Ok(Json(CreateApiTokenResponse {
+        token: generated.token,
+        details: ApiTokenResponse::from(token),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/opsml/api/user/{username}/token",
+    params(
+        ("username" = String, Path, description = "User whose tokens are listed"),
+    ),
+    responses(
+        (status = 200, description = "Tokens for the user (secrets are never returned)", body = ApiTokenListResponse),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "user"
+)]
+/// List a user's API tokens
+#[instrument(skip_all)]
+pub(crate) async fn list_api_tokens(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Path(username): Path<String>,
+) -> Result<Json<ApiTokenListResponse>, (StatusCode, Json<OpsmlServerError>)> {
+    let is_admin = perms.group_permissions.contains(&"admin".to_string());
+    if !is_admin && perms.username != username {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let tokens = match state.sql_client.get_api_tokens(&username).await {
+        Ok(tokens) => tokens,
+        Err(e) => {
+            error!("Failed to list api tokens: {e}");
+            return Err(internal_server_error(e, "Failed to list api tokens", None));
+        }
+    };
+
+    Ok(Json(ApiTokenListResponse {
+        tokens: tokens.into_iter().map(ApiTokenResponse::from).collect(),
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/opsml/api/user/{username}/token/{token_id}",
+    params(
+        ("username" = String, Path, description = "User who owns the token"),
+        ("token_id" = String, Path, description = "Token to revoke"),
+    ),
+    responses(
+        (status = 200, description = "Token revoked", body = inline(serde_json::Value)),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 404, description = "Token not found", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "user"
+)]
+/// Revoke an API token
+#[instrument(skip_all)]
+pub(crate) async fn revoke_api_token(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Path((username, token_id)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<OpsmlServerError>)> {
+    let is_admin = perms.group_permissions.contains(&"admin".to_string());
+    if !is_admin && perms.username != username {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    match state
+        .sql_client
+        .revoke_api_token(&username, &token_id)
+        .await
+    {
+        Ok(true) => {
+            info!("API token {} revoked for user {}", token_id, username);
+            Ok(Json(serde_json::json!({"success": true})))
+        }
+        Ok(false) => OpsmlServerError::not_found("API token").into_response(StatusCode::NOT_FOUND),
+        Err(e) => {
+            error!("Failed to revoke api token: {e}");
+            Err(internal_server_error(e, "Failed to revoke api token", None))
+        }
+    }
+}
+
 pub async fn get_user_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
     let result = catch_unwind(AssertUnwindSafe(|| {
         Router::new()
@@ -398,6 +577,18 @@ pub async fn get_user_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
             .route(&format!("{prefix}/user/{{username}}"), get(get_user))
             .route(&format!("{prefix}/user/{{username}}"), put(update_user))
             .route(&format!("{prefix}/user/{{username}}"), delete(delete_user))
+            .route(
+                &format!("{prefix}/user/{{username}}/token"),
+                post(create_api_token),
+            )
+            .route(
+                &format!("{prefix}/user/{{username}}/token"),
+                get(list_api_tokens),
+            )
+            .route(
+                &format!("{prefix}/user/{{username}}/token/{{token_id}}"),
+                delete(revoke_api_token),
+            )
     }));
 
     match result {
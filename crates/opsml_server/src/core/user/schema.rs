@@ -1,4 +1,5 @@
-use opsml_sql::schemas::schema::User;
+use chrono::{DateTime, Utc};
+use opsml_sql::schemas::schema::{ApiToken, User};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, utoipa::ToSchema)]
@@ -91,3 +92,50 @@ impl From<User> for UserResponse {
         }
     }
 }
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CreateApiTokenRequest {
+    /// Human-readable label, e.g. "ci-pipeline"
+    pub name: String,
+    /// Permissions granted to the token. Must be a subset of the owning user's permissions
+    pub permissions: Vec<String>,
+    /// Days until the token expires. Defaults to 90; 0 creates a token that never expires
+    pub expires_in_days: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ApiTokenResponse {
+    pub token_id: String,
+    pub name: String,
+    pub permissions: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked: bool,
+}
+
+impl From<ApiToken> for ApiTokenResponse {
+    fn from(token: ApiToken) -> Self {
+        ApiTokenResponse {
+            token_id: token.token_id,
+            name: token.name,
+            permissions: token.permissions,
+            created_at: token.created_at,
+            expires_at: token.expires_at,
+            last_used_at: token.last_used_at,
+            revoked: token.revoked,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CreateApiTokenResponse {
+    /// Plaintext token. This is only returned once and cannot be recovered
+    pub token: String,
+    pub details: ApiTokenResponse,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ApiTokenListResponse {
+    pub tokens: Vec<ApiTokenResponse>,
+}
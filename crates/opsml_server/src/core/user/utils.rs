@@ -2,9 +2,11 @@ use crate::core::error::{OpsmlServerError, internal_server_error};
 use anyhow::Result;
 /// Route for debugging information
 use axum::{Json, http::StatusCode};
+use opsml_auth::group::GroupGrants;
 use opsml_sql::enums::client::SqlClientEnum;
+use opsml_sql::error::SqlError;
 use opsml_sql::schemas::User;
-use opsml_sql::traits::UserLogicTrait;
+use opsml_sql::traits::{GroupLogicTrait, UserLogicTrait};
 use tracing::error;
 
 /// Resuable function to get a user from the database
@@ -44,3 +46,15 @@ pub async fn get_user(
             )
         })
 }
+
+/// Loads the groups a user belongs to and the space roles those groups hold.
+/// Used to resolve effective permissions whenever a token is issued
+pub async fn get_group_grants(
+    sql_client: &SqlClientEnum,
+    username: &str,
+) -> Result<GroupGrants, SqlError> {
+    let groups = sql_client.get_user_groups(username).await?;
+    let roles = sql_client.get_user_group_roles(username).await?;
+
+    Ok(GroupGrants::new(groups, roles))
+}
@@ -0,0 +1,258 @@
+use crate::core::error::{OpsmlServerError, internal_server_error};
+use crate::core::state::AppState;
+use anyhow::{Context, Result};
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{delete, get, post},
+};
+use opsml_auth::permission::UserPermissions;
+use opsml_events::webhook::{generate_webhook_secret, validate_webhook_url};
+use opsml_sql::schemas::schema::WebhookSqlRecord;
+use opsml_sql::traits::EventLogicTrait;
+use opsml_types::contracts::{
+    CreateWebhookRequest, CreateWebhookResponse, Webhook, WebhookDeliveryListResponse,
+    WebhookDeliveryQuery, WebhookListResponse, WebhookQuery,
+};
+use opsml_utils::{create_uuid7, utils::get_utc_datetime};
+use sqlx::types::Json as SqlxJson;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::Arc;
+use tracing::{error, info, instrument};
+
+type WebhookResult<T> = Result<T, (StatusCode, Json<OpsmlServerError>)>;
+
+/// Default number of deliveries returned for a webhook
+const DEFAULT_DELIVERY_LIMIT: i64 = 50;
+
+async fn get_webhook_or_404(state: &AppState, id: &str) -> WebhookResult<WebhookSqlRecord> {
+    state
+        .sql_client
+        .get_webhook(id)
+        .await
+        .map_err(|e| {
+            error!("Failed to get webhook: {e}");
+            internal_server_error(e, "Failed to get webhook", None)
+        })?
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(OpsmlServerError::not_found("Webhook")),
+            )
+        })
+}
+
+#[utoipa::path(
+    post,
+    path = "/opsml/api/webhook",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 200, description = "Webhook registered", body = CreateWebhookResponse),
+        (status = 400, description = "Invalid webhook url", body = OpsmlServerError),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "webhook"
+)]
+/// Register a webhook
+///
+/// Requires write permission on the space. The url must resolve to a public address
+/// unless `OPSML_WEBHOOK_ALLOW_PRIVATE_TARGETS` is set. The returned secret signs every
+/// delivery and is not shown again
+#[instrument(skip_all)]
+pub(crate) async fn create_webhook(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Json(req): Json<CreateWebhookRequest>,
+) -> WebhookResult<Json<CreateWebhookResponse>> {
+    if !perms.has_write_permission(&req.space) {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let allow_private = state.config.webhook_settings.allow_private_targets;
+    if let Err(e) = validate_webhook_url(&req.url, allow_private).await {
+        return OpsmlServerError::bad_request(&e.to_string())
+            .into_response(StatusCode::BAD_REQUEST);
+    }
+
+    let record = WebhookSqlRecord {
+        id: create_uuid7(),
+        space: req.space,
+        url: req.url,
+        event_types: SqlxJson(req.event_types),
+        secret: generate_webhook_secret(),
+        active: true,
+        created_by: perms.username.clone(),
+        created_at: get_utc_datetime(),
+    };
+
+    state
+        .sql_client
+        .insert_webhook(&record)
+        .await
+        .map_err(|e| {
+            error!("Failed to create webhook: {e}");
+            internal_server_error(e, "Failed to create webhook", None)
+        })?;
+
+    info!(
+        "Webhook {} registered for space {}",
+        record.id, record.space
+    );
+
+    let secret = record.secret.clone();
+    Ok(Json(CreateWebhookResponse {
+        webhook: Webhook::from(record),
+        secret,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/opsml/api/webhook",
+    params(
+        ("space" = String, Query, description = "Space to list webhooks for"),
+    ),
+    responses(
+        (status = 200, description = "Webhooks registered for the space", body = WebhookListResponse),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "webhook"
+)]
+/// List webhooks
+///
+/// Requires read permission on the space
+#[instrument(skip_all)]
+pub(crate) async fn list_webhooks(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Query(params): Query<WebhookQuery>,
+) -> WebhookResult<Json<WebhookListResponse>> {
+    if !perms.has_read_permission(&params.space) {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let webhooks = state
+        .sql_client
+        .list_webhooks(&params.space)
+        .await
+        .map_err(|e| {
+            error!("Failed to list webhooks: {e}");
+            internal_server_error(e, "Failed to list webhooks", None)
+        })?;
+
+    Ok(Json(WebhookListResponse {
+        webhooks: webhooks.into_iter().map(Webhook::from).collect(),
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/opsml/api/webhook/{id}",
+    params(
+        ("id" = String, Path, description = "Webhook id"),
+    ),
+    responses(
+        (status = 200, description = "Webhook removed", body = Webhook),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 404, description = "Webhook not found", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "webhook"
+)]
+/// Remove a webhook along with its pending deliveries
+///
+/// Requires write permission on the webhook's space
+#[instrument(skip_all)]
+pub(crate) async fn delete_webhook(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Path(id): Path<String>,
+) -> WebhookResult<Json<Webhook>> {
+    let record = get_webhook_or_404(&state, &id).await?;
+
+    if !perms.has_write_permission(&record.space) {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    state.sql_client.delete_webhook(&id).await.map_err(|e| {
+        error!("Failed to delete webhook: {e}");
+        internal_server_error(e, "Failed to delete webhook", None)
+    })?;
+
+    info!("Webhook {} removed from space {}", record.id, record.space);
+
+    Ok(Json(Webhook::from(record)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/opsml/api/webhook/{id}/deliveries",
+    params(
+        ("id" = String, Path, description = "Webhook id"),
+        ("limit" = Option<i64>, Query, description = "Maximum number of deliveries to return"),
+    ),
+    responses(
+        (status = 200, description = "Most recent deliveries, newest first", body = WebhookDeliveryListResponse),
+        (status = 403, description = "Forbidden", body = OpsmlServerError),
+        (status = 404, description = "Webhook not found", body = OpsmlServerError),
+        (status = 500, description = "Internal error", body = OpsmlServerError),
+    ),
+    security(("bearer_token" = [])),
+    tag = "webhook"
+)]
+/// List recent deliveries for a webhook, including attempts and the last error
+///
+/// Requires read permission on the webhook's space
+#[instrument(skip_all)]
+pub(crate) async fn list_webhook_deliveries(
+    State(state): State<Arc<AppState>>,
+    Extension(perms): Extension<UserPermissions>,
+    Path(id): Path<String>,
+    Query(params): Query<WebhookDeliveryQuery>,
+) -> WebhookResult<Json<WebhookDeliveryListResponse>> {
+    let record = get_webhook_or_404(&state, &id).await?;
+
+    if !perms.has_read_permission(&record.space) {
+        return OpsmlServerError::permission_denied().into_response(StatusCode::FORBIDDEN);
+    }
+
+    let limit = params.limit.unwrap_or(DEFAULT_DELIVERY_LIMIT);
+    let deliveries = state
+        .sql_client
+        .list_webhook_deliveries(&id, limit)
+        .await
+        .map_err(|e| {
+            error!("Failed to list webhook deliveries: {e}");
+            internal_server_error(e, "Failed to list webhook deliveries", None)
+        })?;
+
+    Ok(Json(WebhookDeliveryListResponse { deliveries }))
+}
+
+pub async fn get_webhook_router(prefix: &str) -> Result<Router<Arc<AppState>>> {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        Router::new()
+            .route(&format!("{prefix}/webhook"), post(create_webhook))
+            .route(&format!("{prefix}/webhook"), get(list_webhooks))
+            .route(&format!("{prefix}/webhook/{{id}}"), delete(delete_webhook))
+            .route(
+                &format!("{prefix}/webhook/{{id}}/deliveries"),
+                get(list_webhook_deliveries),
+            )
+    }));
+
+    match result {
+        Ok(router) => Ok(router),
+        Err(_) => {
+            error!("Failed to create webhook router");
+            Err(anyhow::anyhow!("Failed to create webhook router"))
+                .context("Panic occurred while creating the router")
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use crate::common::TestHelper;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use http_body_util::BodyExt;
+use opsml_crypt::encrypted_key;
+use opsml_server::core::cards::schema::CreateReadeMe;
+use opsml_sql::traits::ArtifactLogicTrait;
+use opsml_types::RegistryType;
+use opsml_types::contracts::{
+    ArtifactKey, ArtifactKeyRequest, ConsistencyIssueKind, ConsistencyReport,
+    ConsistencyScanRequest, RotateArtifactKeysRequest, RotateArtifactKeysResponse,
+};
+use opsml_utils::uid_to_byte_key;
+
+#[tokio::test]
+async fn test_opsml_server_rotate_artifact_keys() {
+    let helper = TestHelper::new(None).await;
+
+    // 1. A key stored by an older server is still wrapped with the card uid
+    let uid = "0194b6a1-2f3c-7d4e-8a9b-0c1d2e3f4a5b".to_string();
+    let data_key = [7u8; 32];
+    let legacy = ArtifactKey {
+        uid: uid.clone(),
+        space: helper.space.clone(),
+        registry_type: RegistryType::Data,
+        encrypted_key: encrypted_key(&uid_to_byte_key(&uid).unwrap(), &data_key).unwrap(),
+        storage_key: "opsml_data_registry/space/legacy/v1.0.0".to_string(),
+        key_version: 0,
+        manifest_sha256: None,
+    };
+    helper
+        .app_state
+        .sql_client
+        .insert_artifact_key(&legacy)
+        .await
+        .unwrap();
+
+    // 2. Rotation re-wraps it with the current KEK
+    let request = Request::builder()
+        .uri("/opsml/api/admin/keys/rotate")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            serde_json::to_string(&RotateArtifactKeysRequest {
+                batch_size: Some(1),
+            })
+            .unwrap(),
+        ))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let rotated: RotateArtifactKeysResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(rotated.key_version, 1);
+    assert!(rotated.rewrapped >= 1);
+    assert_eq!(rotated.skipped, 0);
+
+    let stored = helper
+        .app_state
+        .sql_client
+        .get_artifact_key(&uid, &RegistryType::Data.to_string())
+        .await
+        .unwrap();
+    assert_eq!(stored.key_version, 1);
+    assert_ne!(stored.encrypted_key, legacy.encrypted_key);
+
+    // 3. Clients still receive the uid-wrapped key and the data key is unchanged
+    let query = serde_qs::to_string(&ArtifactKeyRequest {
+        uid: uid.clone(),
+        registry_type: RegistryType::Data,
+    })
+    .unwrap();
+    let request = Request::builder()
+        .uri(format!("/opsml/api/files/key?{query}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let key: ArtifactKey = serde_json::from_slice(&body).unwrap();
+    assert_eq!(key.key_version, 0);
+    assert_eq!(key.get_crypt_key().unwrap(), data_key);
+
+    // 4. A second rotation has nothing left to do
+    let request = Request::builder()
+        .uri("/opsml/api/admin/keys/rotate")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from("{}"))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let rotated: RotateArtifactKeysResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(rotated.rewrapped, 0);
+
+    helper.cleanup();
+}
+
+async fn scan_storage(helper: &TestHelper, delete: bool) -> ConsistencyReport {
+    let request = Request::builder()
+        .uri("/opsml/api/admin/storage/scan")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            serde_json::to_string(&ConsistencyScanRequest {
+                delete,
+                space: Some(helper.space.clone()),
+            })
+            .unwrap(),
+        ))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn test_opsml_server_storage_scan() {
+    let helper = TestHelper::new(None).await;
+
+    // 1. Objects left behind by a card that was never registered
+    let prefix = format!("opsml_model_registry/{}/ghost/v0.0.1", helper.space);
+    let dir = std::env::current_dir()
+        .unwrap()
+        .join("opsml_registries")
+        .join(&prefix);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("file.txt"), "orphaned").unwrap();
+
+    // 2. A dry run reports them without touching storage
+    let report = scan_storage(&helper, false).await;
+    assert!(report.dry_run);
+    let issue = report
+        .issues
+        .iter()
+        .find(|issue| issue.kind == ConsistencyIssueKind::OrphanedObjects)
+        .unwrap();
+    assert_eq!(issue.storage_key, prefix);
+    assert_eq!(issue.objects, 1);
+    assert_eq!(issue.bytes, 8);
+    assert!(!issue.deleted);
+    assert_eq!(report.orphaned_bytes(), 8);
+    assert!(dir.join("file.txt").exists());
+
+    // 3. Delete mode removes them
+    let report = scan_storage(&helper, true).await;
+    assert!(!report.dry_run);
+    assert!(
+        report
+            .issues
+            .iter()
+            .any(|issue| issue.kind == ConsistencyIssueKind::OrphanedObjects && issue.deleted)
+    );
+    assert!(!dir.join("file.txt").exists());
+
+    // 4. Nothing is left to clean up
+    let report = scan_storage(&helper, false).await;
+    assert_eq!(report.count(ConsistencyIssueKind::OrphanedObjects), 0);
+
+    helper.cleanup();
+}
+
+async fn create_readme(helper: &TestHelper, name: &str) {
+    let create_readme = CreateReadeMe {
+        space: helper.space.clone(),
+        name: name.to_string(),
+        registry_type: RegistryType::Model,
+        readme: "# Readme".to_string(),
+    };
+    let request = Request::builder()
+        .uri("/opsml/api/card/readme")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&create_readme).unwrap()))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_opsml_server_storage_scan_keeps_readmes() {
+    let mut helper = TestHelper::new(None).await;
+    helper.create_modelcard().await;
+
+    // 1. A README for a registered card and one for a card that was never registered
+    create_readme(&helper, &helper.name).await;
+    create_readme(&helper, "ghost").await;
+
+    let registry = std::env::current_dir()
+        .unwrap()
+        .join("opsml_registries")
+        .join("opsml_model_registry")
+        .join(&helper.space);
+    let readme = registry.join(&helper.name).join("README.md");
+    let ghost_readme = registry.join("ghost").join("README.md");
+    assert!(readme.exists());
+    assert!(ghost_readme.exists());
+
+    // 2. Delete mode keeps the README of the registered card and removes the other
+    let report = scan_storage(&helper, true).await;
+    assert_eq!(report.count(ConsistencyIssueKind::OrphanedObjects), 0);
+
+    let orphaned_keys: Vec<_> = report
+        .issues
+        .iter()
+        .filter(|issue| issue.kind == ConsistencyIssueKind::OrphanedKey)
+        .collect();
+    assert_eq!(orphaned_keys.len(), 1);
+    assert!(orphaned_keys[0].storage_key.ends_with("/ghost/README.md"));
+    assert!(orphaned_keys[0].deleted);
+
+    assert!(readme.exists());
+    assert!(!ghost_readme.exists());
+
+    helper.cleanup();
+}
+
+#[tokio::test]
+async fn test_opsml_server_storage_scan_reports_unkeyed_cards() {
+    let mut helper = TestHelper::new(None).await;
+    helper.create_modelcard().await;
+
+    // 1. A registered card whose key insert never happened
+    helper
+        .app_state
+        .sql_client
+        .delete_artifact_key(&helper.key.uid, &RegistryType::Model.to_string())
+        .await
+        .unwrap();
+
+    // 2. The scan reports it, and delete mode leaves the card alone
+    let report = scan_storage(&helper, true).await;
+    let issue = report
+        .issues
+        .iter()
+        .find(|issue| issue.kind == ConsistencyIssueKind::MissingKey)
+        .unwrap();
+    assert_eq!(issue.uid.as_deref(), Some(helper.key.uid.as_str()));
+    assert_eq!(issue.registry_type, Some(RegistryType::Model));
+    assert!(!issue.deleted);
+
+    let report = scan_storage(&helper, false).await;
+    assert_eq!(report.count(ConsistencyIssueKind::MissingKey), 1);
+
+    helper.cleanup();
+}
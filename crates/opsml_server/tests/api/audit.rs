@@ -0,0 +1,97 @@
+use crate::common::TestHelper;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use http_body_util::BodyExt;
+use opsml_sql::traits::AuditLogicTrait;
+use opsml_types::contracts::{AuditEvent, AuditPageResponse, AuditStatus, Operation};
+
+#[tokio::test]
+async fn test_opsml_server_audit_query_and_export() {
+    let helper = TestHelper::new(None).await;
+
+    for operation in [Operation::Read, Operation::Read, Operation::Delete] {
+        let event = AuditEvent {
+            username: "compliance_user".to_string(),
+            operation,
+            status: AuditStatus::Success,
+            resource_id: "model-uid".to_string(),
+            ..Default::default()
+        };
+        helper
+            .app_state
+            .sql_client
+            .insert_audit_event(event)
+            .await
+            .unwrap();
+    }
+
+    // 1. First page
+    let request = Request::builder()
+        .uri("/opsml/api/audit?username=compliance_user&operation=Read&limit=1")
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let page: AuditPageResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(page.events.len(), 1);
+    let cursor = page.next_cursor.unwrap();
+
+    // 2. Second page
+    let request = Request::builder()
+        .uri(format!(
+            "/opsml/api/audit?username=compliance_user&operation=Read&limit=1&cursor={cursor}"
+        ))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let page: AuditPageResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(page.events.len(), 1);
+    assert!(page.events[0].id < cursor);
+
+    // 3. Export as ndjson
+    let request = Request::builder()
+        .uri("/opsml/api/audit/export?username=compliance_user")
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get(header::CONTENT_TYPE).unwrap(),
+        "application/x-ndjson"
+    );
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert_eq!(body.lines().count(), 3);
+
+    // 4. Export as csv
+    let request = Request::builder()
+        .uri("/opsml/api/audit/export?username=compliance_user&format=csv")
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    let mut lines = body.lines();
+    assert!(lines.next().unwrap().starts_with("id,created_at,username"));
+    assert_eq!(lines.count(), 3);
+
+    helper.cleanup();
+}
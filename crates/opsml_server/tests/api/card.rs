@@ -6,10 +6,12 @@ use axum::{
 use http_body_util::BodyExt; // for `collect`
 use opsml_crypt::encrypt_file;
 use opsml_semver::VersionType;
+use opsml_server::core::admin::utils::backfill_readme_index;
 use opsml_server::core::cards::schema::{
     CreateReadeMe, DashboardStatsResponse, QueryPageResponse, ReadeMe, RegistryStatsResponse,
     VersionPageResponse,
 };
+use opsml_sql::traits::SearchLogicTrait;
 use opsml_types::contracts::DeploymentConfig;
 use opsml_types::contracts::*;
 use opsml_types::*;
@@ -2270,3 +2272,271 @@ async fn test_opsml_server_card_service_card_agents() {
         helper.cleanup();
     });
 }
+
+#[tokio::test]
+async fn test_opsml_server_card_aliases() {
+    let helper = TestHelper::new(None).await;
+
+    let set_alias = |alias: &str, version: &str| {
+        let body = serde_json::to_string(&SetCardAliasRequest {
+            space: "repo1".to_string(),
+            name: "Model1".to_string(),
+            registry_type: RegistryType::Model,
+            alias: alias.to_string(),
+            version: version.to_string(),
+        })
+        .unwrap();
+
+        Request::builder()
+            .uri("/opsml/api/card/alias")
+            .method("PUT")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    };
+
+    // invalid alias names, version ranges and unknown versions are rejected
+    let response = helper.send_oneshot(set_alias("Production", "1.0.0")).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = helper.send_oneshot(set_alias("production", "^1.0")).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = helper.send_oneshot(set_alias("production", "9.9.9")).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = helper.send_oneshot(set_alias("production", "1.0.0")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let set_response: CardAliasResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(set_response.alias.version, "1.0.0");
+    assert_eq!(
+        set_response.alias.uid,
+        "550e8400-e29b-41d4-a716-446655440000"
+    );
+    assert!(set_response.previous_version.is_none());
+
+    // the alias resolves wherever a version is accepted
+    let args = CardQueryArgs {
+        space: Some("repo1".to_string()),
+        name: Some("Model1".to_string()),
+        version: Some("@production".to_string()),
+        registry_type: RegistryType::Model,
+        ..Default::default()
+    };
+    let query_string = serde_qs::to_string(&args).unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card/list?{query_string}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let card_results: Vec<CardRecord> = serde_json::from_slice(&body).unwrap();
+    assert_eq!(card_results.len(), 1);
+    assert_eq!(card_results[0].uid(), set_response.alias.uid);
+
+    // list aliases
+    let query_string = serde_qs::to_string(&CardAliasQuery {
+        space: "repo1".to_string(),
+        name: "Model1".to_string(),
+        registry_type: RegistryType::Model,
+    })
+    .unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card/alias?{query_string}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let aliases: CardAliasListResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(aliases.aliases.len(), 1);
+    assert_eq!(aliases.aliases[0].alias, "production");
+
+    // unset the alias
+    let query_string = serde_qs::to_string(&DeleteCardAliasRequest {
+        space: "repo1".to_string(),
+        name: "Model1".to_string(),
+        registry_type: RegistryType::Model,
+        alias: "production".to_string(),
+    })
+    .unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card/alias?{query_string}"))
+        .method("DELETE")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card/alias?{query_string}"))
+        .method("DELETE")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    helper.cleanup();
+}
+
+#[tokio::test]
+async fn test_opsml_server_card_lineage() {
+    let helper = TestHelper::new(None).await;
+
+    let mut query = LineageQuery::new("550e8400-e29b-41d4-a716-446655440000");
+    query.registry_type = Some(RegistryType::Model);
+    let query_string = serde_qs::to_string(&query).unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card/lineage?{query_string}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let graph: LineageGraph = serde_json::from_slice(&body).unwrap();
+
+    // the fixture model only references cards that don't exist, so the graph is just the root
+    assert_eq!(graph.nodes.len(), 1);
+    assert_eq!(graph.nodes[0].name, "Model1");
+    assert_eq!(graph.nodes[0].registry_type, RegistryType::Model);
+    assert!(graph.edges.is_empty());
+
+    let request = Request::builder()
+        .uri("/opsml/api/card/lineage?uid=not-a-card")
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    helper.cleanup();
+}
+
+async fn search_hits(helper: &TestHelper, query_string: &str) -> Vec<CardSearchHit> {
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card/search?{query_string}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice::<CardSearchResponse>(&body)
+        .unwrap()
+        .hits
+}
+
+#[tokio::test]
+async fn test_opsml_server_card_search() {
+    let mut helper = TestHelper::new(None).await;
+
+    helper.create_modelcard().await;
+
+    let create_readme = CreateReadeMe {
+        space: helper.space.clone(),
+        name: helper.name.clone(),
+        registry_type: RegistryType::Model,
+        readme: "Predicts customer churn from weekly usage".to_string(),
+    };
+
+    let request = Request::builder()
+        .uri("/opsml/api/card/readme")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&create_readme).unwrap()))
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let query = CardSearchQuery {
+        query: "churn".to_string(),
+        registry_type: Some(RegistryType::Model),
+        ..Default::default()
+    };
+    let query_string = serde_qs::to_string(&query).unwrap();
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card/search?{query_string}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let results: CardSearchResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(results.hits.len(), 1);
+    assert_eq!(results.hits[0].name, helper.name);
+    assert!(
+        results.hits[0]
+            .highlight
+            .as_ref()
+            .unwrap()
+            .contains("<mark>churn</mark>")
+    );
+
+    // a README written before the card was indexed is picked up by the backfill
+    let sql_client = &helper.app_state.sql_client;
+    sql_client
+        .delete_search_document(&helper.key.uid)
+        .await
+        .unwrap();
+    sql_client
+        .upsert_search_document(&CardSearchDocument {
+            uid: helper.key.uid.clone(),
+            registry_type: RegistryType::Model,
+            space: helper.space.clone(),
+            name: helper.name.clone(),
+            version: helper.version.clone(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(
+        search_hits(&helper, &query_string).await.is_empty(),
+        "README should not be indexed yet"
+    );
+
+    let indexed = backfill_readme_index(&helper.app_state).await.unwrap();
+    assert_eq!(indexed, 1);
+    assert_eq!(search_hits(&helper, &query_string).await.len(), 1);
+
+    // a query without searchable terms matches nothing
+    let request = Request::builder()
+        .uri("/opsml/api/card/search?query=%22*")
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let results: CardSearchResponse = serde_json::from_slice(&body).unwrap();
+    assert!(results.hits.is_empty());
+
+    helper.cleanup();
+}
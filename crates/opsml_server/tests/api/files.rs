@@ -4,6 +4,7 @@ use axum::{
     http::{Request, StatusCode, header},
 };
 use http_body_util::BodyExt; // for `collect`
+use opsml_sql::traits::SpaceLogicTrait;
 use opsml_types::{RegistryType, contracts::*};
 use test_utils::retry_flaky_test;
 
@@ -196,3 +197,301 @@ async fn test_opsml_server_log_artifact() {
     let records: Vec<ArtifactRecord> = serde_json::from_slice(&body_bytes).unwrap();
     assert!(records.len() == 1);
 }
+
+/// Upload a file to local storage the way `LocalMultipartUpload` does
+async fn upload_file(helper: &TestHelper, path: &str, content: &str) -> axum::response::Response {
+    let boundary = "opsml-test-boundary";
+    let body = format!(
+        "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{path}\"\r\nContent-Type: application/octet-stream\r\n\r\n{content}\r\n--{boundary}--\r\n"
+    );
+
+    let request = Request::builder()
+        .uri("/opsml/api/files/multipart")
+        .method("POST")
+        .header(
+            header::CONTENT_TYPE,
+            format!("multipart/form-data; boundary={boundary}"),
+        )
+        .body(Body::from(body))
+        .unwrap();
+
+    helper.send_oneshot(request).await
+}
+
+#[tokio::test]
+async fn test_opsml_server_space_quota() {
+    let mut helper = TestHelper::new(None).await;
+    helper.create_modelcard().await;
+    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+    let sql_client = helper.app_state.sql_client.clone();
+    let base_path = format!(
+        "opsml_model_registry/{}/{}/v{}",
+        helper.space, helper.name, helper.version
+    );
+
+    // 1. The soft quota cannot exceed the hard quota
+    let request = Request::builder()
+        .uri("/opsml/api/admin/space/quota")
+        .method("PUT")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            serde_json::to_string(&SpaceQuotaRequest {
+                space: helper.space.clone(),
+                soft_quota_bytes: Some(30),
+                hard_quota_bytes: Some(20),
+            })
+            .unwrap(),
+        ))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let request = Request::builder()
+        .uri("/opsml/api/admin/space/quota")
+        .method("PUT")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            serde_json::to_string(&SpaceQuotaRequest {
+                space: helper.space.clone(),
+                soft_quota_bytes: Some(10),
+                hard_quota_bytes: Some(20),
+            })
+            .unwrap(),
+        ))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let record: SpaceRecord = serde_json::from_slice(&body).unwrap();
+    assert_eq!(record.soft_quota_bytes, Some(10));
+    assert_eq!(record.hard_quota_bytes, Some(20));
+
+    // 2. Uploads past the soft quota succeed with a warning
+    let response = upload_file(&helper, &format!("{base_path}/a.txt"), "fifteen bytes!!").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let uploaded: UploadResponse = serde_json::from_slice(&body).unwrap();
+    assert!(uploaded.uploaded);
+    assert!(uploaded.message.contains("soft storage quota"));
+    assert_eq!(sql_client.get_space_bytes(&helper.space).await.unwrap(), 15);
+
+    // 3. Overwriting a file only counts the difference
+    let response = upload_file(&helper, &format!("{base_path}/a.txt"), "fifteen bytes??").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(sql_client.get_space_bytes(&helper.space).await.unwrap(), 15);
+
+    // 4. Uploads past the hard quota are rejected
+    let response = upload_file(&helper, &format!("{base_path}/b.txt"), "ten bytes!").await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let error: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error["code"], "STORAGE_QUOTA_EXCEEDED");
+    assert!(
+        error["error"]
+            .as_str()
+            .unwrap()
+            .contains(&format!("'{}'", helper.space))
+    );
+    assert_eq!(sql_client.get_space_bytes(&helper.space).await.unwrap(), 15);
+
+    // 5. Usage and quotas are reported with the space stats
+    let request = Request::builder()
+        .uri("/opsml/api/card/space/stats")
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let stats: SpaceStatsResponse = serde_json::from_slice(&body).unwrap();
+    let stats = stats
+        .stats
+        .iter()
+        .find(|stats| stats.space == helper.space)
+        .unwrap();
+    assert_eq!(stats.storage_bytes, 15);
+    assert_eq!(
+        stats.usage,
+        vec![RegistryUsage {
+            registry_type: RegistryType::Model,
+            bytes: 15,
+        }]
+    );
+    assert_eq!(stats.soft_quota_bytes, Some(10));
+    assert_eq!(stats.hard_quota_bytes, Some(20));
+
+    // 6. Deleting files releases their usage
+    let query = serde_qs::to_string(&DeleteFileQuery {
+        path: format!("{base_path}/a.txt"),
+        recursive: false,
+    })
+    .unwrap();
+    let request = Request::builder()
+        .uri(format!("/opsml/api/files/delete?{query}"))
+        .method("DELETE")
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(sql_client.get_space_bytes(&helper.space).await.unwrap(), 0);
+
+    // 7. Concurrent uploads that only fit one at a time cannot together pass the hard quota
+    let (first, second) = tokio::join!(
+        upload_file(&helper, &format!("{base_path}/c.txt"), "twelve bytes"),
+        upload_file(&helper, &format!("{base_path}/d.txt"), "twelve bytes"),
+    );
+    let mut statuses = [first.status(), second.status()];
+    statuses.sort();
+    assert_eq!(statuses, [StatusCode::OK, StatusCode::PAYLOAD_TOO_LARGE]);
+    assert_eq!(sql_client.get_space_bytes(&helper.space).await.unwrap(), 12);
+
+    // 8. A recount replaces usage that drifted from storage
+    sql_client
+        .add_space_usage(&helper.space, &RegistryType::Model, -50)
+        .await
+        .unwrap();
+    assert_eq!(
+        sql_client.get_space_bytes(&helper.space).await.unwrap(),
+        -38
+    );
+
+    let request = Request::builder()
+        .uri("/opsml/api/admin/storage/recount")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            serde_json::to_string(&UsageRecountRequest {
+                space: Some(helper.space.clone()),
+            })
+            .unwrap(),
+        ))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let recount: UsageRecountResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        recount.usage,
+        vec![SpaceRegistryUsage {
+            space: helper.space.clone(),
+            registry_type: RegistryType::Model,
+            bytes: 12,
+        }]
+    );
+    assert_eq!(sql_client.get_space_bytes(&helper.space).await.unwrap(), 12);
+
+    helper.cleanup();
+}
+
+#[tokio::test]
+async fn test_opsml_server_download_file_range() {
+    let mut helper = TestHelper::new(None).await;
+    helper.create_modelcard().await;
+
+    let path = format!(
+        "opsml_model_registry/{}/{}/v{}/weights.bin",
+        helper.space, helper.name, helper.version
+    );
+    let response = upload_file(&helper, &path, "0123456789").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let query = serde_qs::to_string(&DownloadFileQuery { path }).unwrap();
+    let download = |range: Option<&str>, if_range: Option<&str>| {
+        let mut request = Request::builder()
+            .uri(format!("/opsml/api/files?{query}"))
+            .method("GET");
+        if let Some(range) = range {
+            request = request.header(header::RANGE, range);
+        }
+        if let Some(if_range) = if_range {
+            request = request.header(header::IF_RANGE, if_range);
+        }
+        request.body(Body::empty()).unwrap()
+    };
+
+    // full download
+    let response = helper.send_oneshot(download(None, None)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = response.headers()[header::ETAG]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"0123456789");
+
+    // resume from byte 4 of the same version
+    let response = helper
+        .send_oneshot(download(Some("bytes=4-"), Some(&etag)))
+        .await;
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(
+        response.headers().get(header::CONTENT_RANGE).unwrap(),
+        "bytes 4-9/10"
+    );
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"456789");
+
+    // a different version gets the whole file
+    let response = helper
+        .send_oneshot(download(Some("bytes=4-"), Some("\"stale\"")))
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"0123456789");
+
+    // nothing left to send
+    let response = helper.send_oneshot(download(Some("bytes=10-"), None)).await;
+    assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+
+    helper.cleanup();
+}
+
+#[tokio::test]
+async fn test_opsml_server_artifact_manifest_digest() {
+    let mut helper = TestHelper::new(None).await;
+    helper.create_modelcard().await;
+    assert!(helper.key.manifest_sha256.is_none());
+
+    let manifest_request = ArtifactManifestRequest {
+        uid: helper.key.uid.clone(),
+        registry_type: RegistryType::Model,
+        manifest_sha256: "abc123".to_string(),
+    };
+
+    let request = Request::builder()
+        .uri("/opsml/api/files/key/manifest")
+        .method("PUT")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            serde_json::to_string(&manifest_request).unwrap(),
+        ))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // the digest is returned with the artifact key
+    let query = serde_qs::to_string(&ArtifactKeyRequest {
+        uid: helper.key.uid.clone(),
+        registry_type: RegistryType::Model,
+    })
+    .unwrap();
+    let request = Request::builder()
+        .uri(format!("/opsml/api/files/key?{query}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let key: ArtifactKey = serde_json::from_slice(&body).unwrap();
+    assert_eq!(key.manifest_sha256.as_deref(), Some("abc123"));
+
+    helper.cleanup();
+}
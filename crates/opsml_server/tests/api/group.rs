@@ -0,0 +1,146 @@
+use crate::common::TestHelper;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use http_body_util::BodyExt;
+use opsml_server::core::user::schema::CreateUserRequest;
+use opsml_types::JwtToken;
+use opsml_types::contracts::{
+    AddGroupMemberRequest, CreateGroupRequest, GroupResponse, SetGroupRoleRequest, SpaceRole,
+};
+
+fn json_request(uri: &str, method: &str, body: String) -> Request<Body> {
+    Request::builder()
+        .uri(uri)
+        .method(method)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_opsml_server_group_roles_resolve_into_jwt() {
+    let helper = TestHelper::new(None).await;
+
+    // 1. Create a user with no direct permissions
+    let create_user = CreateUserRequest {
+        username: "analyst".to_string(),
+        password: "analyst_password".to_string(),
+        email: "analyst@example.com".to_string(),
+        permissions: Some(vec![]),
+        group_permissions: Some(vec![]),
+        role: Some("user".to_string()),
+        active: Some(true),
+    };
+    let request = json_request(
+        "/opsml/api/user",
+        "POST",
+        serde_json::to_string(&create_user).unwrap(),
+    );
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 2. Create a group, grant it editor on a space and add the user
+    let create_group = CreateGroupRequest {
+        name: "analysts".to_string(),
+        description: Some("Data analysts".to_string()),
+        sso_groups: None,
+    };
+    let request = json_request(
+        "/opsml/api/group",
+        "POST",
+        serde_json::to_string(&create_group).unwrap(),
+    );
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // duplicate group names are rejected
+    let request = json_request(
+        "/opsml/api/group",
+        "POST",
+        serde_json::to_string(&create_group).unwrap(),
+    );
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let set_role = SetGroupRoleRequest {
+        space: "team-a".to_string(),
+        role: SpaceRole::Editor,
+    };
+    let request = json_request(
+        "/opsml/api/group/analysts/role",
+        "PUT",
+        serde_json::to_string(&set_role).unwrap(),
+    );
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let add_member = AddGroupMemberRequest {
+        username: "analyst".to_string(),
+    };
+    let request = json_request(
+        "/opsml/api/group/analysts/member",
+        "POST",
+        serde_json::to_string(&add_member).unwrap(),
+    );
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let group: GroupResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(group.members, vec!["analyst".to_string()]);
+    assert_eq!(group.roles.len(), 1);
+
+    // 3. Login as the user; the issued JWT carries the group's effective permissions
+    let request = Request::builder()
+        .uri("/opsml/api/auth/login")
+        .header("Username", "analyst")
+        .header("Password", "analyst_password")
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_no_auth(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let token: JwtToken = serde_json::from_slice(&body).unwrap();
+
+    let claims = helper
+        .app_state
+        .auth_manager
+        .validate_jwt(&token.token)
+        .unwrap();
+    assert!(claims.permissions.contains(&"read:team-a".to_string()));
+    assert!(claims.permissions.contains(&"write:team-a".to_string()));
+    assert!(!claims.permissions.contains(&"delete:team-a".to_string()));
+    assert!(claims.group_permissions.contains(&"analysts".to_string()));
+
+    // 4. Group management requires admin
+    let request = Request::builder()
+        .uri("/opsml/api/group")
+        .method("GET")
+        .header(header::AUTHORIZATION, format!("Bearer {}", token.token))
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_no_auth(request).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // 5. Deleting the group removes the grant
+    let request = Request::builder()
+        .uri("/opsml/api/group/analysts")
+        .method("DELETE")
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = Request::builder()
+        .uri("/opsml/api/group/analysts")
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    helper.cleanup();
+}
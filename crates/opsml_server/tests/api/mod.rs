@@ -1,11 +1,17 @@
+pub mod admin;
+pub mod audit;
 pub mod card;
 pub mod experiment;
 pub mod files;
+pub mod group;
 pub mod login;
 pub mod scouter;
 pub mod skill_cli;
 pub mod skill_scan;
 pub mod subagent_cli;
 pub mod tool_cli;
+pub mod trash;
 pub mod user;
 pub mod v1;
+pub mod webhook;
+pub mod workflow;
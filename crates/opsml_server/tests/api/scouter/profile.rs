@@ -5,6 +5,7 @@ use axum::{
     body::Body,
     http::{Request, StatusCode},
 };
+use opsml_auth::group::GroupGrants;
 use opsml_crypt::encrypt_file;
 use opsml_sql::schemas::User;
 use opsml_types::SaveName;
@@ -164,7 +165,11 @@ fn restricted_token(helper: &TestHelper) -> String {
         group_permissions: vec![],
         ..Default::default()
     };
-    helper.app_state.auth_manager.generate_jwt(&user).unwrap()
+    helper
+        .app_state
+        .auth_manager
+        .generate_jwt(&user, &GroupGrants::default())
+        .unwrap()
 }
 
 #[tokio::test]
@@ -8,8 +8,8 @@ use opsml_semver::VersionType;
 use opsml_types::{
     RegistryType,
     contracts::{
-        CardRecord, CardVersionRequest, CreateCardRequest, CreateCardResponse, InvokeRequest,
-        InvokeResponse, JobStatus, SkillCardClientRecord,
+        CardRecord, CardVersionRequest, CreateCardRequest, CreateCardResponse, InvocationMode,
+        InvokeRequest, InvokeResponse, JobStatus, SkillCardClientRecord,
     },
 };
 use test_utils::retry_flaky_test;
@@ -188,6 +188,8 @@ async fn test_agent_invoke_sync() {
 
         let invoke_request = InvokeRequest {
             input: serde_json::json!("Classify this skill content"),
+            mode: InvocationMode::Sync,
+            session_id: None,
         };
 
         let request = Request::builder()
@@ -209,6 +211,160 @@ async fn test_agent_invoke_sync() {
     });
 }
 
+/// Agent invoke endpoint — async call returns a pending job that can be polled and cancelled.
+#[tokio::test]
+async fn test_agent_invoke_async() {
+    retry_flaky_test!({
+        let helper = TestHelper::new(None).await;
+
+        helper.app_state.agent_store.set_mock_response(
+            "skill-scan",
+            serde_json::json!({
+                "classification": "Clean",
+                "reason": "No violations",
+                "findings": []
+            }),
+        );
+
+        let invoke_request = InvokeRequest {
+            input: serde_json::json!("Classify this skill content"),
+            mode: InvocationMode::Async,
+            session_id: None,
+        };
+
+        let request = Request::builder()
+            .uri("/opsml/api/v1/agent/skill-scan/invoke")
+            .method("POST")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(serde_json::to_string(&invoke_request).unwrap()))
+            .unwrap();
+
+        let response = helper.send_oneshot(request).await;
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let invoke_response: InvokeResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(invoke_response.status, JobStatus::Pending);
+        assert_eq!(invoke_response.metadata.invocation, "async");
+
+        let job_uri = format!(
+            "/opsml/api/v1/agent/skill-scan/jobs/{}",
+            invoke_response.job_id
+        );
+
+        let mut job = invoke_response;
+        for _ in 0..50 {
+            let request = Request::builder()
+                .uri(&job_uri)
+                .method("GET")
+                .body(Body::empty())
+                .unwrap();
+
+            let response = helper.send_oneshot(request).await;
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            job = serde_json::from_slice(&body).unwrap();
+            if job.status == JobStatus::Done {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        assert_eq!(job.status, JobStatus::Done);
+        assert_eq!(job.result.unwrap()["classification"], "Clean");
+
+        // jobs are only reachable under the agent that ran them
+        let request = Request::builder()
+            .uri(format!(
+                "/opsml/api/v1/agent/custom-agent/jobs/{}",
+                job.job_id
+            ))
+            .method("GET")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = helper.send_oneshot(request).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        // cancelling a finished job leaves it untouched
+        let request = Request::builder()
+            .uri(&job_uri)
+            .method("DELETE")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = helper.send_oneshot(request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let job: InvokeResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(job.status, JobStatus::Done);
+
+        helper.cleanup();
+    });
+}
+
+/// Agent invoke endpoint — an agent that needs input is resumed in the same session.
+#[tokio::test]
+async fn test_agent_invoke_session() {
+    retry_flaky_test!({
+        let helper = TestHelper::new(None).await;
+
+        helper
+            .app_state
+            .agent_store
+            .set_mock_response("custom-agent", serde_json::json!({"result": "ok"}));
+        helper
+            .app_state
+            .agent_store
+            .set_mock_prompt("custom-agent", "Which environment?");
+
+        let invoke = |input: &str, session_id: Option<String>| {
+            let invoke_request = InvokeRequest {
+                input: serde_json::json!(input),
+                mode: InvocationMode::Sync,
+                session_id,
+            };
+
+            Request::builder()
+                .uri("/opsml/api/v1/agent/custom-agent/invoke")
+                .method("POST")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(serde_json::to_string(&invoke_request).unwrap()))
+                .unwrap()
+        };
+
+        let response = helper.send_oneshot(invoke("deploy", None)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let invoke_response: InvokeResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(invoke_response.status, JobStatus::NeedsInput);
+        assert_eq!(
+            invoke_response.prompt.as_deref(),
+            Some("Which environment?")
+        );
+
+        let response = helper
+            .send_oneshot(invoke("staging", invoke_response.session_id))
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let invoke_response: InvokeResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(invoke_response.status, JobStatus::Done);
+        assert_eq!(invoke_response.result.unwrap()["result"], "ok");
+
+        // unknown sessions are rejected
+        let response = helper
+            .send_oneshot(invoke("staging", Some("missing".to_string())))
+            .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        helper.cleanup();
+    });
+}
+
 /// Agent invoke endpoint — unknown agent returns 404.
 #[tokio::test]
 async fn test_agent_invoke_missing() {
@@ -217,6 +373,8 @@ async fn test_agent_invoke_missing() {
 
         let invoke_request = InvokeRequest {
             input: serde_json::json!("hello"),
+            mode: InvocationMode::Sync,
+            session_id: None,
         };
 
         let request = Request::builder()
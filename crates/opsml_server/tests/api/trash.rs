@@ -0,0 +1,250 @@
+use crate::common::TestHelper;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use http_body_util::BodyExt;
+use opsml_sql::traits::ArtifactLogicTrait;
+use opsml_types::RegistryType;
+use opsml_types::contracts::{
+    DeleteCardRequest, PurgeTrashRequest, PurgeTrashResponse, RestoreCardRequest,
+    RestoreCardResponse, RetentionPolicy, RetentionPolicyListResponse, TrashListResponse,
+    TrashQuery, TrashReason, UidRequest, UidResponse,
+};
+
+async fn delete_card(helper: &TestHelper, uid: &str) {
+    let query = serde_qs::to_string(&DeleteCardRequest {
+        uid: uid.to_string(),
+        space: helper.space.clone(),
+        registry_type: RegistryType::Model,
+    })
+    .unwrap();
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card/delete?{query}"))
+        .method("DELETE")
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+async fn card_exists(helper: &TestHelper, uid: &str) -> bool {
+    let query = serde_qs::to_string(&UidRequest {
+        uid: uid.to_string(),
+        registry_type: RegistryType::Model,
+    })
+    .unwrap();
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card?{query}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let uid_response: UidResponse = serde_json::from_slice(&body).unwrap();
+    uid_response.exists
+}
+
+async fn list_trash(helper: &TestHelper) -> TrashListResponse {
+    let query = serde_qs::to_string(&TrashQuery {
+        space: Some(helper.space.clone()),
+        ..Default::default()
+    })
+    .unwrap();
+    let request = Request::builder()
+        .uri(format!("/opsml/api/card/trash?{query}"))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn test_opsml_server_trash_restore_purge() {
+    let mut helper = TestHelper::new(None).await;
+    helper.create_modelcard().await;
+    let uid = helper.key.uid.clone();
+
+    // 1. Deleting a card moves it to the trash
+    delete_card(&helper, &uid).await;
+    assert!(!card_exists(&helper, &uid).await);
+
+    let trash = list_trash(&helper).await;
+    assert_eq!(trash.cards.len(), 1);
+    assert_eq!(trash.cards[0].uid, uid);
+    assert_eq!(trash.cards[0].reason, TrashReason::Deleted);
+
+    // 2. Restoring puts it back in the registry
+    let request = Request::builder()
+        .uri("/opsml/api/card/trash/restore")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            serde_json::to_string(&RestoreCardRequest { uid: uid.clone() }).unwrap(),
+        ))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let restored: RestoreCardResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(restored.name, helper.name);
+    assert!(card_exists(&helper, &uid).await);
+    assert!(list_trash(&helper).await.cards.is_empty());
+
+    // 3. Purging removes the trashed card and its artifact key for good
+    delete_card(&helper, &uid).await;
+
+    let request = Request::builder()
+        .uri("/opsml/api/admin/trash/purge")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            serde_json::to_string(&PurgeTrashRequest {
+                uids: Some(vec![uid.clone()]),
+                ..Default::default()
+            })
+            .unwrap(),
+        ))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let purged: PurgeTrashResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(purged.purged, vec![uid.clone()]);
+    assert!(list_trash(&helper).await.cards.is_empty());
+    assert!(
+        helper
+            .app_state
+            .sql_client
+            .get_artifact_key(&uid, &RegistryType::Model.to_string())
+            .await
+            .is_err()
+    );
+
+    // 4. A purged card can no longer be restored
+    let request = Request::builder()
+        .uri("/opsml/api/card/trash/restore")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            serde_json::to_string(&RestoreCardRequest { uid }).unwrap(),
+        ))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    helper.cleanup();
+}
+
+#[tokio::test]
+async fn test_opsml_server_trash_reserves_version() {
+    let mut helper = TestHelper::new(None).await;
+    helper.create_modelcard().await;
+    let trashed = helper.key.clone();
+
+    // 1. Registering the same version after trashing it gets a new version and storage prefix
+    delete_card(&helper, &trashed.uid).await;
+    helper.create_modelcard().await;
+    let live = helper.key.clone();
+    assert_ne!(live.storage_key, trashed.storage_key);
+    assert!(live.storage_key.ends_with("/v1.1.0"));
+
+    let artifact = std::env::current_dir()
+        .unwrap()
+        .join("opsml_registries")
+        .join(&live.storage_key)
+        .join("file.txt");
+    std::fs::create_dir_all(artifact.parent().unwrap()).unwrap();
+    std::fs::write(&artifact, "live").unwrap();
+
+    // 2. Purging the trashed card leaves the live card's artifacts alone
+    let request = Request::builder()
+        .uri("/opsml/api/admin/trash/purge")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(
+            serde_json::to_string(&PurgeTrashRequest {
+                uids: Some(vec![trashed.uid.clone()]),
+                ..Default::default()
+            })
+            .unwrap(),
+        ))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    assert!(artifact.exists());
+    assert!(card_exists(&helper, &live.uid).await);
+    assert!(
+        helper
+            .app_state
+            .sql_client
+            .get_artifact_key(&live.uid, &RegistryType::Model.to_string())
+            .await
+            .is_ok()
+    );
+
+    helper.cleanup();
+}
+
+#[tokio::test]
+async fn test_opsml_server_retention_policy() {
+    let helper = TestHelper::new(None).await;
+
+    let mut policy = RetentionPolicy::new(&helper.space);
+    policy.keep_last_versions = Some(0);
+
+    // at least one version must be kept
+    let request = Request::builder()
+        .uri("/opsml/api/admin/retention")
+        .method("PUT")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&policy).unwrap()))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    policy.keep_last_versions = Some(5);
+    policy.purge_trashed_after_days = Some(30);
+    let request = Request::builder()
+        .uri("/opsml/api/admin/retention")
+        .method("PUT")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&policy).unwrap()))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = Request::builder()
+        .uri("/opsml/api/admin/retention")
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let policies: RetentionPolicyListResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(policies.policies.len(), 1);
+    assert_eq!(policies.policies[0].keep_last_versions, Some(5));
+    assert_eq!(policies.policies[0].purge_trashed_after_days, Some(30));
+    assert_eq!(policies.policies[0].updated_by, "admin");
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/admin/retention?space={}", helper.space))
+        .method("DELETE")
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    helper.cleanup();
+}
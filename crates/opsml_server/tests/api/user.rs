@@ -281,3 +281,110 @@ async fn test_opsml_login_logout() {
 
     assert!(logout_response.logged_out);
 }
+
+#[tokio::test]
+async fn test_opsml_server_user_api_tokens() {
+    let helper = TestHelper::new(None).await;
+
+    let create_req = CreateUserRequest {
+        username: "service_account".to_string(),
+        password: "service_password".to_string(),
+        email: "service@example.com".to_string(),
+        permissions: Some(vec!["read:all".to_string()]),
+        group_permissions: Some(vec!["user".to_string()]),
+        role: Some("user".to_string()),
+        active: Some(true),
+    };
+
+    let request = Request::builder()
+        .uri("/opsml/api/user")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&create_req).unwrap()))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 1. Permissions the user does not have cannot be granted
+    let token_req = CreateApiTokenRequest {
+        name: "ci".to_string(),
+        permissions: vec!["write:all".to_string()],
+        expires_in_days: None,
+    };
+    let request = Request::builder()
+        .uri("/opsml/api/user/service_account/token")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&token_req).unwrap()))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // 2. Create a scoped token
+    let token_req = CreateApiTokenRequest {
+        name: "ci".to_string(),
+        permissions: vec!["read:space".to_string()],
+        expires_in_days: Some(30),
+    };
+    let request = Request::builder()
+        .uri("/opsml/api/user/service_account/token")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&token_req).unwrap()))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let created: CreateApiTokenResponse = serde_json::from_slice(&body).unwrap();
+    assert!(created.token.starts_with("opsml_pat_"));
+    assert!(created.details.expires_at.is_some());
+
+    // 3. The token authenticates as the service account
+    let request = Request::builder()
+        .uri("/opsml/api/user/service_account/token")
+        .method("GET")
+        .header(header::AUTHORIZATION, format!("Bearer {}", created.token))
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_no_auth(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let listed: ApiTokenListResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(listed.tokens.len(), 1);
+    assert_eq!(listed.tokens[0].permissions, vec!["read:space".to_string()]);
+
+    // 4. Tokens never carry admin rights
+    let request = Request::builder()
+        .uri("/opsml/api/user")
+        .method("GET")
+        .header(header::AUTHORIZATION, format!("Bearer {}", created.token))
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_no_auth(request).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // 5. Revoke the token
+    let request = Request::builder()
+        .uri(format!(
+            "/opsml/api/user/service_account/token/{}",
+            created.details.token_id
+        ))
+        .method("DELETE")
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = Request::builder()
+        .uri("/opsml/api/user/service_account/token")
+        .method("GET")
+        .header(header::AUTHORIZATION, format!("Bearer {}", created.token))
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_no_auth(request).await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    helper.cleanup();
+}
@@ -0,0 +1,140 @@
+use crate::common::TestHelper;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use http_body_util::BodyExt;
+use mockito::Matcher;
+use opsml_events::EventDispatcher;
+use opsml_types::contracts::{
+    CreateWebhookRequest, CreateWebhookResponse, DeliveryStatus, RegistryEventType,
+    WebhookDeliveryListResponse, WebhookListResponse,
+};
+
+#[tokio::test]
+async fn test_opsml_server_webhook_delivery() {
+    let mut helper = TestHelper::new(None).await;
+    let mut receiver = mockito::Server::new_async().await;
+
+    let hook = receiver
+        .mock("POST", "/hook")
+        .match_header("x-opsml-event", "card_created")
+        .match_header(
+            "x-opsml-signature",
+            Matcher::Regex("^sha256=[0-9a-f]{64}$".to_string()),
+        )
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "event_type": "card_created",
+            "space": helper.space,
+        })))
+        .with_status(200)
+        .expect_at_least(1)
+        .create_async()
+        .await;
+
+    // 1. Register a webhook for card creation in the helper's space
+    let create = CreateWebhookRequest {
+        space: helper.space.clone(),
+        url: format!("{}/hook", receiver.url()),
+        event_types: vec![RegistryEventType::CardCreated],
+    };
+    let request = Request::builder()
+        .uri("/opsml/api/webhook")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&create).unwrap()))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let created: CreateWebhookResponse = serde_json::from_slice(&body).unwrap();
+    assert!(created.secret.starts_with("whsec_"));
+
+    // non-http urls are rejected
+    let invalid = CreateWebhookRequest {
+        url: "file:///etc/passwd".to_string(),
+        ..create
+    };
+    let request = Request::builder()
+        .uri("/opsml/api/webhook")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&invalid).unwrap()))
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/webhook?space={}", helper.space))
+        .method("GET")
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let listed: WebhookListResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(listed.webhooks.len(), 1);
+
+    // 2. Creating a card writes a card_created event to the outbox
+    helper.create_modelcard().await;
+
+    // 3. Relay the outbox and deliver. The background dispatcher may get there first
+    let dispatcher = EventDispatcher::new(
+        helper.app_state.sql_client.clone(),
+        &helper.app_state.config.webhook_settings,
+    )
+    .unwrap();
+
+    let mut deliveries = vec![];
+    for _ in 0..20 {
+        dispatcher.dispatch_once().await.unwrap();
+
+        let request = Request::builder()
+            .uri(format!(
+                "/opsml/api/webhook/{}/deliveries",
+                created.webhook.id
+            ))
+            .method("GET")
+            .body(Body::empty())
+            .unwrap();
+        let response = helper.send_oneshot(request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let listed: WebhookDeliveryListResponse = serde_json::from_slice(&body).unwrap();
+        deliveries = listed.deliveries;
+
+        if deliveries
+            .iter()
+            .any(|delivery| delivery.status == DeliveryStatus::Delivered)
+        {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0].status, DeliveryStatus::Delivered);
+    hook.assert_async().await;
+
+    // 4. Removing the webhook removes its deliveries
+    let request = Request::builder()
+        .uri(format!("/opsml/api/webhook/{}", created.webhook.id))
+        .method("DELETE")
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = Request::builder()
+        .uri(format!("/opsml/api/webhook/{}", created.webhook.id))
+        .method("DELETE")
+        .body(Body::empty())
+        .unwrap();
+    let response = helper.send_oneshot(request).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    helper.cleanup();
+}
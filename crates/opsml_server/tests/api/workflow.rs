@@ -0,0 +1,102 @@
+use crate::common::TestHelper;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use http_body_util::BodyExt;
+use opsml_types::contracts::{
+    CardRef, StepStatus, WorkflowRun, WorkflowRunRequest, WorkflowRunStatus, WorkflowSpec,
+    WorkflowStep,
+};
+use serde_json::json;
+
+fn run_request(spec: WorkflowSpec, inputs: serde_json::Value) -> Request<Body> {
+    let body = WorkflowRunRequest {
+        space: "repo1".to_string(),
+        name: "inline".to_string(),
+        spec: Some(spec),
+        inputs: inputs.as_object().cloned().unwrap_or_default(),
+        ..Default::default()
+    };
+
+    Request::builder()
+        .uri("/opsml/api/v1/workflow/run")
+        .method("POST")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(serde_json::to_string(&body).unwrap()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_opsml_server_run_inline_workflow() {
+    let helper = TestHelper::new(None).await;
+
+    let spec: WorkflowSpec = serde_json::from_value(json!({
+        "inputs": {"topic": {"type": "string", "required": true}},
+        "steps": [
+            {"name": "draft", "prompt": "Write about {{ inputs.topic }}"},
+            {
+                "name": "review",
+                "dependsOn": ["draft"],
+                "prompt": "Review: {{ draft.text }}",
+                "condition": "{{ inputs.topic }} == 'skip'"
+            }
+        ],
+        "outputs": {"draft": "{{ draft.text }}"}
+    }))
+    .unwrap();
+
+    let response = helper
+        .send_oneshot(run_request(spec.clone(), json!({"topic": "owls"})))
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let run: WorkflowRun = serde_json::from_slice(&body).unwrap();
+    assert_eq!(run.status, WorkflowRunStatus::Succeeded);
+    assert_eq!(run.steps[0].status, StepStatus::Succeeded);
+    assert_eq!(run.steps[1].status, StepStatus::Skipped);
+    assert_eq!(run.outputs["draft"], json!("Write about owls"));
+
+    // missing required input is rejected before anything runs
+    let response = helper.send_oneshot(run_request(spec, json!({}))).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_opsml_server_run_workflow_unknown_card() {
+    let helper = TestHelper::new(None).await;
+
+    let spec = WorkflowSpec {
+        steps: vec![WorkflowStep {
+            name: "summarize".to_string(),
+            agent: Some(CardRef {
+                space: "repo1".to_string(),
+                name: "does-not-exist".to_string(),
+                version: None,
+            }),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let response = helper.send_oneshot(run_request(spec, json!({}))).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_opsml_server_run_workflow_unsupported_step() {
+    let helper = TestHelper::new(None).await;
+
+    let spec: WorkflowSpec = serde_json::from_value(json!({
+        "steps": [
+            {"name": "draft", "prompt": "Write a haiku"},
+            {"name": "lint", "dependsOn": ["draft"], "tool": {"name": "lint"}}
+        ]
+    }))
+    .unwrap();
+
+    // the tool step is rejected before the prompt step runs
+    let response = helper.send_oneshot(run_request(spec, json!({}))).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -449,10 +449,13 @@ GrrNOufvPsvmCRO9m4ESRrk=
 fn set_common_env_vars(scouter_url: &str) {
     unsafe {
         env::set_var("SCOUTER_SERVER_URI", scouter_url);
+        env::set_var("APP_ENV", "development");
         env::set_var("RUST_LOG", "debug");
         env::set_var("LOG_LEVEL", "debug");
         env::set_var("LOG_JSON", "false");
         env::set_var("OPSML_AUTH", "true");
+        // webhook receivers in the tests listen on loopback
+        env::set_var("OPSML_WEBHOOK_ALLOW_PRIVATE_TARGETS", "true");
     }
 }
 
@@ -551,6 +554,8 @@ impl TestHelper {
                 registry_type: RegistryType::Data,
                 encrypted_key: vec![],
                 storage_key: "".to_string(),
+                key_version: 0,
+                manifest_sha256: None,
             },
             server: scouter_server,
             sso_server: mock_sso_server,
@@ -1,6 +1,11 @@
 use crate::error::SettingsError;
+use crate::profile::ProfileStore;
+use crate::source::ConfigSource;
 use base64::prelude::*;
+use opsml_crypt::error::CryptError;
+use opsml_crypt::{KeyProvider, LocalKeyProvider};
 use opsml_types::{SqlType, StorageType};
+use opsml_utils::create_uuid7;
 use rusty_logging::LogLevel;
 use rusty_logging::logger::{LoggingConfig, WriteLevel};
 use serde::Serialize;
@@ -8,6 +13,7 @@ use std::default::Default;
 use std::env;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::Arc;
 use tracing::warn;
 
 #[derive(Debug, Clone, Serialize, PartialEq)]
@@ -24,6 +30,13 @@ pub struct ApiSettings {
     pub password: String,
     pub prod_token: Option<String>,
     pub use_sso: bool,
+    /// Personal API token sent instead of logging in with username and password
+    pub token: Option<String>,
+    /// File caching the JWT of the active client profile between invocations
+    pub token_cache: Option<PathBuf>,
+    /// PEM bundle of additional CA certificates to trust, shared with the S3 settings so
+    /// presigned storage urls resolve against a private CA in client mode
+    pub ca_bundle: Option<PathBuf>,
 }
 
 /// StorageSettings for used with all storage clients
@@ -33,6 +46,50 @@ pub struct OpsmlStorageSettings {
     pub api_settings: ApiSettings,
     pub storage_type: StorageType,
     pub encryption_key: Vec<u8>,
+    /// Provider holding the key-encryption-keys (`OPSML_KEK_PROVIDER`)
+    pub kek_provider: String,
+    /// Optional keyfile with additional key-encryption-keys (`OPSML_KEK_PATH`)
+    pub kek_path: Option<PathBuf>,
+    pub s3_settings: S3Settings,
+    /// Maximum number of files transferred at once by recursive get/put (`OPSML_TRANSFER_CONCURRENCY`)
+    pub transfer_concurrency: usize,
+}
+
+/// Overrides for S3-compatible object stores (MinIO, Ceph, R2). Buckets are still addressed with
+/// `s3://` storage uris; credentials come from the usual AWS environment variables and profiles
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct S3Settings {
+    /// Custom endpoint, e.g. `http://localhost:9000` for a local MinIO
+    pub endpoint_url: Option<String>,
+    /// Region to sign requests for. Defaults to the AWS environment/profile, or `us-east-1` when
+    /// a custom endpoint is set
+    pub region: Option<String>,
+    /// Address buckets as `{endpoint}/{bucket}/{key}` instead of `{bucket}.{endpoint}/{key}`
+    pub force_path_style: bool,
+    /// PEM bundle of additional CA certificates to trust for the endpoint
+    pub ca_bundle: Option<PathBuf>,
+}
+
+impl S3Settings {
+    pub fn from_source(source: &ConfigSource) -> Self {
+        let non_empty = |name: &str| source.var(name).filter(|v| !v.trim().is_empty());
+
+        Self {
+            endpoint_url: non_empty("OPSML_S3_ENDPOINT_URL"),
+            region: non_empty("OPSML_S3_REGION"),
+            force_path_style: source
+                .var("OPSML_S3_FORCE_PATH_STYLE")
+                .unwrap_or_else(|| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            ca_bundle: non_empty("OPSML_S3_CA_BUNDLE").map(PathBuf::from),
+        }
+    }
+
+    /// Whether the bucket lives on an S3-compatible store rather than AWS
+    pub fn is_custom_endpoint(&self) -> bool {
+        self.endpoint_url.is_some()
+    }
 }
 
 impl OpsmlStorageSettings {
@@ -44,6 +101,10 @@ impl OpsmlStorageSettings {
     pub fn new(storage_uri: &str) -> Self {
         OpsmlStorageSettings {
             encryption_key: vec![],
+            kek_provider: DEFAULT_KEK_PROVIDER.to_string(),
+            kek_path: None,
+            s3_settings: S3Settings::default(),
+            transfer_concurrency: DEFAULT_TRANSFER_CONCURRENCY,
             storage_uri: storage_uri.to_string(),
             api_settings: ApiSettings {
                 base_url: "".to_string(),
@@ -52,10 +113,29 @@ impl OpsmlStorageSettings {
                 password: "guest".to_string(),
                 use_sso: false,
                 prod_token: None,
+                token: None,
+                token_cache: None,
+                ca_bundle: None,
             },
             storage_type: StorageType::Local,
         }
     }
+
+    /// Key provider wrapping artifact keys, selected by `kek_provider`. The `local` provider
+    /// holds a KEK derived from the master encryption key plus any KEKs in the configured keyfile
+    pub fn key_provider(&self) -> Result<Arc<dyn KeyProvider>, CryptError> {
+        match self.kek_provider.as_str() {
+            "local" => {
+                let provider = LocalKeyProvider::from_master_key(&self.encryption_key)?;
+                let provider = match &self.kek_path {
+                    Some(path) => provider.with_keyfile(path)?,
+                    None => provider,
+                };
+                Ok(Arc::new(provider))
+            }
+            other => Err(CryptError::UnknownKeyProvider(other.to_string())),
+        }
+    }
 }
 
 /// DatabaseSettings for used with all database clients
@@ -75,20 +155,236 @@ pub struct AuthSettings {
     pub prod_token: Option<String>,
     pub scouter_secret: String,
     pub use_sso: bool,
+    pub api_token: Option<String>,
 }
 
-#[derive(Debug, Clone, Default, Serialize)]
+/// `APP_ENV` values in which the server may start with the built-in default secrets
+pub const DEVELOPMENT_ENVS: [&str; 3] = ["development", "dev", "local"];
+
+/// Environment used when `APP_ENV` is not set
+pub const DEFAULT_APP_ENV: &str = "development";
+
+/// Key provider used when `OPSML_KEK_PROVIDER` is not set
+pub const DEFAULT_KEK_PROVIDER: &str = "local";
+
+/// Default number of files moved in parallel by recursive storage get/put
+pub const DEFAULT_TRANSFER_CONCURRENCY: usize = 8;
+
+/// Default number of agent jobs that may run concurrently
+pub const DEFAULT_AGENT_MAX_CONCURRENT_JOBS: usize = 4;
+
+/// Default time finished agent jobs are retained before cleanup (24 hours)
+pub const DEFAULT_AGENT_JOB_TTL_SECS: u64 = 86400;
+
+/// Default time an idle agent session is kept before it expires (1 hour)
+pub const DEFAULT_AGENT_SESSION_TTL_SECS: u64 = 3600;
+
+#[derive(Debug, Clone, Serialize)]
 pub struct AgentSettings {
     pub agents_dir: Option<String>,
     pub skill_scan_enabled: bool,
+    pub max_concurrent_jobs: usize,
+    pub job_ttl_secs: u64,
+    pub session_ttl_secs: u64,
+    /// Identifies this server among replicas sharing a database. Jobs record the instance that
+    /// runs them so a restart recovers its own right away. Jobs of an instance that does not
+    /// come back are failed once their heartbeat goes stale
+    pub instance_id: String,
+}
+
+impl Default for AgentSettings {
+    fn default() -> Self {
+        Self {
+            agents_dir: None,
+            skill_scan_enabled: false,
+            max_concurrent_jobs: DEFAULT_AGENT_MAX_CONCURRENT_JOBS,
+            job_ttl_secs: DEFAULT_AGENT_JOB_TTL_SECS,
+            session_ttl_secs: DEFAULT_AGENT_SESSION_TTL_SECS,
+            instance_id: default_instance_id(),
+        }
+    }
+}
+
+/// Falls back to the host name, and to a random id when that is unavailable
+fn default_instance_id() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .filter(|hostname| !hostname.is_empty())
+        .unwrap_or_else(create_uuid7)
 }
 
 impl AgentSettings {
-    pub fn new() -> Self {
+    pub fn from_source(source: &ConfigSource) -> Self {
+        Self {
+            agents_dir: source.var("OPSML_AGENTS_DIR"),
+            skill_scan_enabled: source
+                .var("OPSML_SKILL_SCAN_ENABLED")
+                .unwrap_or_else(|| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            max_concurrent_jobs: source
+                .var("OPSML_AGENT_MAX_CONCURRENT_JOBS")
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(DEFAULT_AGENT_MAX_CONCURRENT_JOBS),
+            job_ttl_secs: source
+                .var("OPSML_AGENT_JOB_TTL_SECS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_AGENT_JOB_TTL_SECS),
+            session_ttl_secs: source
+                .var("OPSML_AGENT_SESSION_TTL_SECS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_AGENT_SESSION_TTL_SECS),
+            instance_id: source
+                .var("OPSML_INSTANCE_ID")
+                .unwrap_or_else(default_instance_id),
+        }
+    }
+}
+
+/// Default interval between polls of the event outbox and pending webhook deliveries
+pub const DEFAULT_WEBHOOK_POLL_INTERVAL_MS: u64 = 1000;
+
+/// Default number of attempts before a webhook delivery is marked as failed
+pub const DEFAULT_WEBHOOK_MAX_ATTEMPTS: i32 = 8;
+
+/// Default timeout for a single webhook request
+pub const DEFAULT_WEBHOOK_TIMEOUT_SECS: u64 = 10;
+
+/// Default delay before the first retry. Doubles with every failed attempt
+pub const DEFAULT_WEBHOOK_BACKOFF_SECS: u64 = 5;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookSettings {
+    pub enabled: bool,
+    pub poll_interval_ms: u64,
+    pub max_attempts: i32,
+    pub timeout_secs: u64,
+    pub backoff_secs: u64,
+    /// Allows webhooks that target loopback, private or link-local addresses
+    pub allow_private_targets: bool,
+}
+
+impl Default for WebhookSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            poll_interval_ms: DEFAULT_WEBHOOK_POLL_INTERVAL_MS,
+            max_attempts: DEFAULT_WEBHOOK_MAX_ATTEMPTS,
+            timeout_secs: DEFAULT_WEBHOOK_TIMEOUT_SECS,
+            backoff_secs: DEFAULT_WEBHOOK_BACKOFF_SECS,
+            allow_private_targets: false,
+        }
+    }
+}
+
+impl WebhookSettings {
+    pub fn from_source(source: &ConfigSource) -> Self {
+        Self {
+            enabled: source
+                .var("OPSML_WEBHOOKS_ENABLED")
+                .unwrap_or_else(|| "true".to_string())
+                .parse()
+                .unwrap_or(true),
+            poll_interval_ms: source
+                .var("OPSML_WEBHOOK_POLL_INTERVAL_MS")
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(DEFAULT_WEBHOOK_POLL_INTERVAL_MS),
+            max_attempts: source
+                .var("OPSML_WEBHOOK_MAX_ATTEMPTS")
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(DEFAULT_WEBHOOK_MAX_ATTEMPTS),
+            timeout_secs: source
+                .var("OPSML_WEBHOOK_TIMEOUT_SECS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_WEBHOOK_TIMEOUT_SECS),
+            backoff_secs: source
+                .var("OPSML_WEBHOOK_BACKOFF_SECS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_WEBHOOK_BACKOFF_SECS),
+            allow_private_targets: source
+                .var("OPSML_WEBHOOK_ALLOW_PRIVATE_TARGETS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// Default interval between retention passes over spaces with a retention policy
+pub const DEFAULT_RETENTION_INTERVAL_SECS: u64 = 3600;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RetentionSettings {
+    pub enabled: bool,
+    pub interval_secs: u64,
+}
+
+impl Default for RetentionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: DEFAULT_RETENTION_INTERVAL_SECS,
+        }
+    }
+}
+
+impl RetentionSettings {
+    pub fn from_source(source: &ConfigSource) -> Self {
+        Self {
+            enabled: source
+                .var("OPSML_RETENTION_ENABLED")
+                .unwrap_or_else(|| "true".to_string())
+                .parse()
+                .unwrap_or(true),
+            interval_secs: source
+                .var("OPSML_RETENTION_INTERVAL_SECS")
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(DEFAULT_RETENTION_INTERVAL_SECS),
+        }
+    }
+}
+
+/// Default interval between storage consistency scans
+pub const DEFAULT_STORAGE_GC_INTERVAL_SECS: u64 = 86400;
+
+/// Scheduled scan for orphaned artifacts. Disabled by default, and only reports orphans unless
+/// `delete` is set
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageGcSettings {
+    pub enabled: bool,
+    pub interval_secs: u64,
+    pub delete: bool,
+}
+
+impl Default for StorageGcSettings {
+    fn default() -> Self {
         Self {
-            agents_dir: std::env::var("OPSML_AGENTS_DIR").ok(),
-            skill_scan_enabled: std::env::var("OPSML_SKILL_SCAN_ENABLED")
-                .unwrap_or_else(|_| "false".to_string())
+            enabled: false,
+            interval_secs: DEFAULT_STORAGE_GC_INTERVAL_SECS,
+            delete: false,
+        }
+    }
+}
+
+impl StorageGcSettings {
+    pub fn from_source(source: &ConfigSource) -> Self {
+        Self {
+            enabled: source
+                .var("OPSML_STORAGE_GC_ENABLED")
+                .unwrap_or_else(|| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            interval_secs: source
+                .var("OPSML_STORAGE_GC_INTERVAL_SECS")
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(DEFAULT_STORAGE_GC_INTERVAL_SECS),
+            delete: source
+                .var("OPSML_STORAGE_GC_DELETE")
+                .unwrap_or_else(|| "false".to_string())
                 .parse()
                 .unwrap_or(false),
         }
@@ -116,6 +412,10 @@ impl ScouterSettings {
 pub struct OpsmlConfig {
     pub app_name: String,
     pub app_env: String,
+    /// Whether `APP_ENV` was set rather than defaulted. The server only skips the default
+    /// secrets check when a development environment is chosen explicitly
+    #[serde(skip)]
+    pub app_env_explicit: bool,
     pub app_version: String,
     pub opsml_storage_uri: String,
     pub opsml_tracking_uri: String,
@@ -128,14 +428,51 @@ pub struct OpsmlConfig {
     pub mode: OpsmlMode,
     pub base_path: PathBuf,
     pub agent_settings: AgentSettings,
+    pub webhook_settings: WebhookSettings,
+    pub retention_settings: RetentionSettings,
+    pub storage_gc_settings: StorageGcSettings,
+    pub s3_settings: S3Settings,
+    pub transfer_concurrency: usize,
+    pub kek_provider: String,
+    pub kek_path: Option<PathBuf>,
+    /// Space used by CLI commands when `--space` is not given
+    pub default_space: Option<String>,
+    /// Name of the client profile the config was built from
+    pub client_profile: Option<String>,
+    pub token_cache: Option<PathBuf>,
 }
 
 impl Default for OpsmlConfig {
+    /// Builds the config from the environment, the `opsml.toml`/`opsml.yaml` config file and the
+    /// selected client profile. Clients fall back to the environment alone if the config file is
+    /// invalid; the server uses `OpsmlConfig::load` and refuses to start instead
     fn default() -> Self {
-        let opsml_storage_uri =
-            env::var("OPSML_STORAGE_URI").unwrap_or_else(|_| "./opsml_registries".to_string());
+        let source = ConfigSource::load()
+            .and_then(|mut source| {
+                ProfileStore::load()?.apply(&mut source)?;
+                Ok(source)
+            })
+            .unwrap_or_else(|e| {
+                warn!("Ignoring opsml config: {e}");
+                ConfigSource::from_env(env::vars().collect()).unwrap_or_default()
+            });
+
+        OpsmlConfig::from_source(&source)
+    }
+}
 
-        let opsml_tracking_uri = env::var("OPSML_TRACKING_URI").unwrap_or_else(|_| {
+impl OpsmlConfig {
+    /// Load the config file and environment, failing on an invalid config file or secret reference
+    pub fn load() -> Result<Self, SettingsError> {
+        Ok(OpsmlConfig::from_source(&ConfigSource::load()?))
+    }
+
+    pub fn from_source(source: &ConfigSource) -> Self {
+        let opsml_storage_uri = source
+            .var("OPSML_STORAGE_URI")
+            .unwrap_or_else(|| "./opsml_registries".to_string());
+
+        let opsml_tracking_uri = source.var("OPSML_TRACKING_URI").unwrap_or_else(|| {
             let mut current_dir = env::current_dir().expect("Failed to get current directory");
             current_dir.push("opsml.db");
             format!(
@@ -150,14 +487,15 @@ impl Default for OpsmlConfig {
 
         // set scouter settings
         let scouter_settings = ScouterSettings {
-            server_uri: env::var("SCOUTER_SERVER_URI").unwrap_or("".to_string()),
-            bootstrap_token: env::var("SCOUTER_BOOTSTRAP_TOKEN")
-                .unwrap_or(generate_default_secret()),
+            server_uri: source.var("SCOUTER_SERVER_URI").unwrap_or_default(),
+            bootstrap_token: source
+                .var("SCOUTER_BOOTSTRAP_TOKEN")
+                .unwrap_or_else(generate_default_secret),
         };
 
         // set auth settings
         let auth_settings = AuthSettings {
-            jwt_secret: env::var("OPSML_ENCRYPT_SECRET").unwrap_or_else(|_| {
+            jwt_secret: source.var("OPSML_ENCRYPT_SECRET").unwrap_or_else(|| {
                 if mode == OpsmlMode::Server {
                     warn!(
                         "Using default secret for encryption 
@@ -166,7 +504,7 @@ impl Default for OpsmlConfig {
                 }
                 generate_default_secret()
             }),
-            refresh_secret: env::var("OPSML_REFRESH_SECRET").unwrap_or_else(|_| {
+            refresh_secret: source.var("OPSML_REFRESH_SECRET").unwrap_or_else(|| {
                 if mode == OpsmlMode::Server {
                     warn!(
                         "Using default secret for refreshing. 
@@ -175,7 +513,7 @@ impl Default for OpsmlConfig {
                 }
                 generate_default_secret()
             }),
-            scouter_secret: env::var("SCOUTER_AUTH_SECRET").unwrap_or_else(|_| {
+            scouter_secret: source.var("SCOUTER_AUTH_SECRET").unwrap_or_else(|| {
                 if mode == OpsmlMode::Server {
                     warn!(
                         "Using default secret for scouter. 
@@ -185,31 +523,42 @@ impl Default for OpsmlConfig {
                 generate_default_secret()
             }),
 
-            username: env::var("OPSML_USERNAME").unwrap_or("guest".to_string()),
-            password: env::var("OPSML_PASSWORD").unwrap_or("guest".to_string()),
-            use_sso: env::var("OPSML_USE_SSO")
-                .unwrap_or_else(|_| "false".to_string())
+            username: source
+                .var("OPSML_USERNAME")
+                .unwrap_or_else(|| "guest".to_string()),
+            password: source
+                .var("OPSML_PASSWORD")
+                .unwrap_or_else(|| "guest".to_string()),
+            use_sso: source
+                .var("OPSML_USE_SSO")
+                .unwrap_or_else(|| "false".to_string())
                 .parse()
                 .unwrap_or(false),
-            prod_token: env::var("OPSML_PROD_TOKEN").ok(),
+            prod_token: source.var("OPSML_PROD_TOKEN"),
+            api_token: source.var("OPSML_API_TOKEN"),
         };
 
         // set database settings
         let database_settings = DatabaseSettings {
             connection_uri: opsml_tracking_uri.clone(),
-            max_connections: env::var("OPSML_MAX_POOL_CONNECTIONS")
-                .unwrap_or_else(|_| "30".to_string())
+            max_connections: source
+                .var("OPSML_MAX_POOL_CONNECTIONS")
+                .unwrap_or_else(|| "30".to_string())
                 .parse()
                 .unwrap_or(30),
             sql_type: OpsmlConfig::get_sql_type(&opsml_tracking_uri),
         };
 
-        let log_level =
-            LogLevel::from_str(&env::var("LOG_LEVEL").unwrap_or_else(|_| "info".to_string()))
-                .unwrap_or(LogLevel::Info);
+        let log_level = LogLevel::from_str(
+            &source
+                .var("LOG_LEVEL")
+                .unwrap_or_else(|| "info".to_string()),
+        )
+        .unwrap_or(LogLevel::Info);
 
-        let log_json = env::var("LOG_JSON")
-            .unwrap_or_else(|_| "false".to_string())
+        let log_json = source
+            .var("LOG_JSON")
+            .unwrap_or_else(|| "false".to_string())
             .parse()
             .unwrap_or(false);
 
@@ -217,8 +566,8 @@ impl Default for OpsmlConfig {
             LoggingConfig::rust_new(false, log_level, WriteLevel::Stdout, log_json);
 
         // check OPSML_BASE_PATH or use current directory
-        let base_path = match env::var("OPSML_BASE_PATH") {
-            Ok(path) => {
+        let base_path = match source.var("OPSML_BASE_PATH") {
+            Some(path) => {
                 let path = PathBuf::from(path);
                 if path.exists() {
                     path
@@ -227,19 +576,23 @@ impl Default for OpsmlConfig {
                     std::env::current_dir().expect("Failed to get current directory")
                 }
             }
-            Err(_) => std::env::current_dir().expect("Failed to get current directory"),
+            None => std::env::current_dir().expect("Failed to get current directory"),
         };
 
         OpsmlConfig {
             app_name: "opsml".to_string(),
-            app_env: env::var("APP_ENV").unwrap_or_else(|_| "development".to_string()),
+            app_env: source
+                .var("APP_ENV")
+                .unwrap_or_else(|| DEFAULT_APP_ENV.to_string()),
+            app_env_explicit: source.var("APP_ENV").is_some(),
             app_version: opsml_version::version(),
             opsml_storage_uri: OpsmlConfig::set_opsml_storage_uri(opsml_storage_uri, &mode),
             opsml_tracking_uri,
 
             opsml_proxy_root: "opsml-root:/".to_string(),
-            opsml_registry_path: env::var("OPSML_REGISTRY_PATH")
-                .unwrap_or_else(|_| "opsml_registry".to_string()),
+            opsml_registry_path: source
+                .var("OPSML_REGISTRY_PATH")
+                .unwrap_or_else(|| "opsml_registry".to_string()),
 
             database_settings,
             scouter_settings,
@@ -247,7 +600,23 @@ impl Default for OpsmlConfig {
             mode,
             logging_config,
             base_path,
-            agent_settings: AgentSettings::new(),
+            agent_settings: AgentSettings::from_source(source),
+            webhook_settings: WebhookSettings::from_source(source),
+            retention_settings: RetentionSettings::from_source(source),
+            storage_gc_settings: StorageGcSettings::from_source(source),
+            s3_settings: S3Settings::from_source(source),
+            transfer_concurrency: source
+                .var("OPSML_TRANSFER_CONCURRENCY")
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(DEFAULT_TRANSFER_CONCURRENCY),
+            kek_provider: source
+                .var("OPSML_KEK_PROVIDER")
+                .unwrap_or_else(|| DEFAULT_KEK_PROVIDER.to_string()),
+            kek_path: source.var("OPSML_KEK_PATH").map(PathBuf::from),
+            default_space: source.var("OPSML_DEFAULT_SPACE"),
+            client_profile: source.client_profile.clone(),
+            token_cache: source.token_cache.clone(),
         }
     }
 }
@@ -342,6 +711,10 @@ impl OpsmlConfig {
             encryption_key: BASE64_STANDARD
                 .decode(self.auth_settings.jwt_secret.clone())
                 .map_err(SettingsError::Base64DecodeError)?,
+            kek_provider: self.kek_provider.clone(),
+            kek_path: self.kek_path.clone(),
+            s3_settings: self.s3_settings.clone(),
+            transfer_concurrency: self.transfer_concurrency,
             storage_uri: self.opsml_storage_uri.clone(),
             storage_type: self.get_storage_type(),
             api_settings: ApiSettings {
@@ -351,9 +724,197 @@ impl OpsmlConfig {
                 password: self.auth_settings.password.clone(),
                 use_sso: self.auth_settings.use_sso,
                 prod_token: self.auth_settings.prod_token.clone(),
+                token: self.auth_settings.api_token.clone(),
+                token_cache: self.token_cache.clone(),
+                ca_bundle: self.s3_settings.ca_bundle.clone(),
             },
         })
     }
+
+    pub fn is_development(&self) -> bool {
+        DEVELOPMENT_ENVS.contains(&self.app_env.to_lowercase().as_str())
+    }
+
+    /// Refuse to run a server with the built-in default secrets unless `APP_ENV` is explicitly
+    /// set to a development environment
+    pub fn validate(&self) -> Result<(), SettingsError> {
+        if self.mode != OpsmlMode::Server || (self.app_env_explicit && self.is_development()) {
+            return Ok(());
+        }
+
+        let default_secret = generate_default_secret();
+        let mut defaults = vec![];
+        if self.auth_settings.jwt_secret == default_secret {
+            defaults.push("OPSML_ENCRYPT_SECRET");
+        }
+        if self.auth_settings.refresh_secret == default_secret {
+            defaults.push("OPSML_REFRESH_SECRET");
+        }
+        if self.scouter_settings.enabled() {
+            if self.auth_settings.scouter_secret == default_secret {
+                defaults.push("SCOUTER_AUTH_SECRET");
+            }
+            if self.scouter_settings.bootstrap_token == default_secret {
+                defaults.push("SCOUTER_BOOTSTRAP_TOKEN");
+            }
+        }
+
+        if defaults.is_empty() {
+            Ok(())
+        } else {
+            let app_env = if self.app_env_explicit {
+                self.app_env.clone()
+            } else {
+                "<unset>".to_string()
+            };
+            Err(SettingsError::DefaultSecretsError(
+                app_env,
+                defaults.join(", "),
+            ))
+        }
+    }
+
+    /// Effective value of every setting in `CONFIG_KEYS`, including defaults
+    pub fn effective_settings(&self, source: &ConfigSource) -> Vec<(&'static str, String)> {
+        let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+        let path = |value: &Option<PathBuf>| {
+            value
+                .as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default()
+        };
+
+        vec![
+            ("APP_ENV", self.app_env.clone()),
+            (
+                "LOG_LEVEL",
+                source
+                    .var("LOG_LEVEL")
+                    .unwrap_or_else(|| "info".to_string()),
+            ),
+            (
+                "LOG_JSON",
+                source
+                    .var("LOG_JSON")
+                    .unwrap_or_else(|| "false".to_string()),
+            ),
+            ("OPSML_STORAGE_URI", self.opsml_storage_uri.clone()),
+            ("OPSML_TRACKING_URI", self.opsml_tracking_uri.clone()),
+            ("OPSML_REGISTRY_PATH", self.opsml_registry_path.clone()),
+            ("OPSML_BASE_PATH", self.base_path.display().to_string()),
+            (
+                "OPSML_ENCRYPT_SECRET",
+                self.auth_settings.jwt_secret.clone(),
+            ),
+            (
+                "OPSML_REFRESH_SECRET",
+                self.auth_settings.refresh_secret.clone(),
+            ),
+            ("OPSML_USERNAME", self.auth_settings.username.clone()),
+            ("OPSML_PASSWORD", self.auth_settings.password.clone()),
+            ("OPSML_USE_SSO", self.auth_settings.use_sso.to_string()),
+            ("OPSML_PROD_TOKEN", optional(&self.auth_settings.prod_token)),
+            ("OPSML_API_TOKEN", optional(&self.auth_settings.api_token)),
+            ("OPSML_DEFAULT_SPACE", optional(&self.default_space)),
+            (
+                "OPSML_MAX_POOL_CONNECTIONS",
+                self.database_settings.max_connections.to_string(),
+            ),
+            ("OPSML_KEK_PROVIDER", self.kek_provider.clone()),
+            ("OPSML_KEK_PATH", path(&self.kek_path)),
+            (
+                "OPSML_TRANSFER_CONCURRENCY",
+                self.transfer_concurrency.to_string(),
+            ),
+            (
+                "OPSML_AGENTS_DIR",
+                optional(&self.agent_settings.agents_dir),
+            ),
+            (
+                "OPSML_SKILL_SCAN_ENABLED",
+                self.agent_settings.skill_scan_enabled.to_string(),
+            ),
+            (
+                "OPSML_AGENT_MAX_CONCURRENT_JOBS",
+                self.agent_settings.max_concurrent_jobs.to_string(),
+            ),
+            (
+                "OPSML_AGENT_JOB_TTL_SECS",
+                self.agent_settings.job_ttl_secs.to_string(),
+            ),
+            (
+                "OPSML_AGENT_SESSION_TTL_SECS",
+                self.agent_settings.session_ttl_secs.to_string(),
+            ),
+            ("OPSML_INSTANCE_ID", self.agent_settings.instance_id.clone()),
+            (
+                "OPSML_WEBHOOKS_ENABLED",
+                self.webhook_settings.enabled.to_string(),
+            ),
+            (
+                "OPSML_WEBHOOK_POLL_INTERVAL_MS",
+                self.webhook_settings.poll_interval_ms.to_string(),
+            ),
+            (
+                "OPSML_WEBHOOK_MAX_ATTEMPTS",
+                self.webhook_settings.max_attempts.to_string(),
+            ),
+            (
+                "OPSML_WEBHOOK_TIMEOUT_SECS",
+                self.webhook_settings.timeout_secs.to_string(),
+            ),
+            (
+                "OPSML_WEBHOOK_BACKOFF_SECS",
+                self.webhook_settings.backoff_secs.to_string(),
+            ),
+            (
+                "OPSML_WEBHOOK_ALLOW_PRIVATE_TARGETS",
+                self.webhook_settings.allow_private_targets.to_string(),
+            ),
+            (
+                "OPSML_RETENTION_ENABLED",
+                self.retention_settings.enabled.to_string(),
+            ),
+            (
+                "OPSML_RETENTION_INTERVAL_SECS",
+                self.retention_settings.interval_secs.to_string(),
+            ),
+            (
+                "OPSML_STORAGE_GC_ENABLED",
+                self.storage_gc_settings.enabled.to_string(),
+            ),
+            (
+                "OPSML_STORAGE_GC_INTERVAL_SECS",
+                self.storage_gc_settings.interval_secs.to_string(),
+            ),
+            (
+                "OPSML_STORAGE_GC_DELETE",
+                self.storage_gc_settings.delete.to_string(),
+            ),
+            (
+                "OPSML_S3_ENDPOINT_URL",
+                optional(&self.s3_settings.endpoint_url),
+            ),
+            ("OPSML_S3_REGION", optional(&self.s3_settings.region)),
+            (
+                "OPSML_S3_FORCE_PATH_STYLE",
+                self.s3_settings.force_path_style.to_string(),
+            ),
+            ("OPSML_S3_CA_BUNDLE", path(&self.s3_settings.ca_bundle)),
+            (
+                "SCOUTER_SERVER_URI",
+                self.scouter_settings.server_uri.clone(),
+            ),
+            (
+                "SCOUTER_AUTH_SECRET",
+                self.auth_settings.scouter_secret.clone(),
+            ),
+            (
+                "SCOUTER_BOOTSTRAP_TOKEN",
+                self.scouter_settings.bootstrap_token.clone(),
+            ),
+        ]
+    }
 }
 
 impl OpsmlConfig {
@@ -432,6 +993,25 @@ mod tests {
         cleanup();
     }
 
+    #[test]
+    fn test_key_provider_selection() {
+        let mut settings = OpsmlStorageSettings::new("./test-bucket");
+        settings.encryption_key = vec![7u8; 32];
+
+        let provider = settings.key_provider().unwrap();
+        let (wrapped, version) = provider.wrap_key(&[1u8; 32]).unwrap();
+        assert_eq!(
+            provider.unwrap_key(&wrapped, version).unwrap(),
+            vec![1u8; 32]
+        );
+
+        settings.kek_provider = "vault".to_string();
+        assert!(matches!(
+            settings.key_provider(),
+            Err(CryptError::UnknownKeyProvider(name)) if name == "vault"
+        ));
+    }
+
     #[test]
     fn test_storage_root() {
         let opsml_config = OpsmlConfig {
@@ -480,6 +1060,81 @@ mod tests {
         cleanup();
     }
 
+    #[test]
+    fn test_s3_settings() {
+        let opsml_config = OpsmlConfig {
+            opsml_storage_uri: "s3://test-bucket".to_string(),
+            s3_settings: S3Settings {
+                endpoint_url: Some("http://localhost:9000".to_string()),
+                force_path_style: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let storage_settings = opsml_config.storage_settings().unwrap();
+        assert_eq!(storage_settings.storage_type, StorageType::Aws);
+        assert!(storage_settings.s3_settings.is_custom_endpoint());
+        assert!(storage_settings.s3_settings.force_path_style);
+        assert_eq!(storage_settings.s3_settings.region, None);
+
+        cleanup();
+    }
+
+    #[test]
+    fn test_validate_default_secrets() {
+        let source = ConfigSource::from_env(
+            [
+                ("OPSML_TRACKING_URI", "sqlite:///opsml.db"),
+                ("APP_ENV", "production"),
+            ]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+        )
+        .unwrap();
+
+        let opsml_config = OpsmlConfig::from_source(&source);
+        assert_eq!(opsml_config.mode, OpsmlMode::Server);
+        let err = opsml_config.validate().unwrap_err().to_string();
+        assert!(err.contains("OPSML_ENCRYPT_SECRET, OPSML_REFRESH_SECRET"));
+
+        let opsml_config = OpsmlConfig {
+            app_env: "development".to_string(),
+            ..opsml_config
+        };
+        assert!(opsml_config.validate().is_ok());
+
+        // an unset APP_ENV defaults to development but does not allow default secrets
+        let unset = ConfigSource::from_env(
+            [("OPSML_TRACKING_URI", "sqlite:///opsml.db")]
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+        .unwrap();
+        let unset_config = OpsmlConfig::from_source(&unset);
+        assert_eq!(unset_config.app_env, "development");
+        let err = unset_config.validate().unwrap_err().to_string();
+        assert!(err.contains("APP_ENV=<unset>"));
+
+        let opsml_config = OpsmlConfig {
+            app_env: "production".to_string(),
+            auth_settings: AuthSettings {
+                jwt_secret: BASE64_STANDARD.encode([7u8; 32]),
+                refresh_secret: BASE64_STANDARD.encode([8u8; 32]),
+                ..opsml_config.auth_settings.clone()
+            },
+            ..opsml_config
+        };
+        assert!(opsml_config.validate().is_ok());
+
+        let effective = opsml_config.effective_settings(&source);
+        assert_eq!(effective.len(), crate::source::CONFIG_KEYS.len());
+
+        cleanup();
+    }
+
     #[test]
     fn test_default() {
         let opsml_config = OpsmlConfig::default();
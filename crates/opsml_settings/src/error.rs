@@ -1,8 +1,59 @@
 use base64::DecodeError;
+use std::path::PathBuf;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum SettingsError {
     #[error("Failed to decode base64: {0}")]
     Base64DecodeError(#[from] DecodeError),
+
+    #[error(transparent)]
+    IoError(std::io::Error),
+
+    #[error("Config file {0} does not exist")]
+    ConfigFileNotFound(PathBuf),
+
+    #[error("Failed to read config file {0}: {1}")]
+    ReadConfigError(PathBuf, #[source] std::io::Error),
+
+    #[error("Unsupported config file {0}. Expected a .toml, .yaml or .yml file")]
+    UnsupportedConfigFormat(PathBuf),
+
+    #[error("Failed to parse config file: {0}")]
+    ParseConfigError(String),
+
+    #[error("Unknown config setting {0}")]
+    UnknownConfigKey(String),
+
+    #[error("Invalid value for {0}: {1}")]
+    InvalidConfigValue(String, String),
+
+    #[error("Config profile {0} is not defined in the config file")]
+    UnknownProfile(String),
+
+    #[error("Failed to read secret {0} from {1}: {2}")]
+    ReadSecretError(String, PathBuf, #[source] std::io::Error),
+
+    #[error(
+        "Refusing to start the server with APP_ENV={0} and default secrets for {1}. Set them in the environment or config file, or set APP_ENV=development"
+    )]
+    DefaultSecretsError(String, String),
+
+    #[error("Client profile {0} does not exist. Create it with `opsml login --profile {0}`")]
+    ProfileNotFound(String),
+
+    #[error("Invalid profile name {0}. Use letters, digits, '-' and '_'")]
+    InvalidProfileName(String),
+
+    #[error("Could not determine the user config directory. Set OPSML_PROFILES_DIR")]
+    MissingConfigDir,
+
+    #[error("Failed to read profiles from {0}: {1}")]
+    ReadProfilesError(PathBuf, #[source] std::io::Error),
+
+    #[error("Failed to write profiles to {0}: {1}")]
+    WriteProfilesError(PathBuf, #[source] std::io::Error),
+
+    #[error("Invalid profiles file {0}: {1}")]
+    ParseProfilesError(PathBuf, String),
 }
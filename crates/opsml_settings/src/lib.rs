@@ -1,3 +1,5 @@
 pub mod config;
 pub mod error;
+pub mod profile;
+pub mod source;
 pub use config::*;
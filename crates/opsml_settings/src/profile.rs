@@ -0,0 +1,347 @@
+use crate::error::SettingsError;
+use crate::source::{ConfigSource, ValueOrigin};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Name of the client profile to use, overriding the active profile and the environment
+pub const PROFILE_ENV: &str = "OPSML_PROFILE";
+
+/// Directory holding `profiles.toml` and cached tokens. Defaults to `{config_dir}/opsml`
+pub const PROFILES_DIR_ENV: &str = "OPSML_PROFILES_DIR";
+
+pub const PROFILES_FILE: &str = "profiles.toml";
+
+const TOKENS_DIR: &str = "tokens";
+
+/// Settings that decide which user a client authenticates as
+const CREDENTIAL_KEYS: [&str; 4] = [
+    "OPSML_USERNAME",
+    "OPSML_PASSWORD",
+    "OPSML_API_TOKEN",
+    "OPSML_USE_SSO",
+];
+
+static SELECTED_PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Select a client profile for the rest of the process, as done by the CLI `--profile` flag.
+/// Must be called before the opsml config is first loaded
+pub fn select_profile(name: &str) {
+    if SELECTED_PROFILE.set(name.to_string()).is_err() {
+        tracing::warn!("A client profile was already selected, ignoring {name}");
+    }
+}
+
+/// Profile requested with `--profile` or `OPSML_PROFILE`
+pub fn requested_profile() -> Option<String> {
+    SELECTED_PROFILE.get().cloned().or_else(|| {
+        env::var(PROFILE_ENV)
+            .ok()
+            .filter(|name| !name.trim().is_empty())
+    })
+}
+
+/// Connection settings for one OpsML server
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClientProfile {
+    pub tracking_uri: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+
+    /// Personal API token, used instead of username and password
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_space: Option<String>,
+
+    #[serde(default)]
+    pub use_sso: bool,
+}
+
+impl ClientProfile {
+    /// Settings provided by the profile, keyed by their environment variable names
+    fn settings(&self) -> Vec<(&'static str, String)> {
+        let mut settings = vec![
+            ("OPSML_TRACKING_URI", self.tracking_uri.clone()),
+            ("OPSML_USE_SSO", self.use_sso.to_string()),
+        ];
+        let optional = [
+            ("OPSML_USERNAME", &self.username),
+            ("OPSML_PASSWORD", &self.password),
+            ("OPSML_API_TOKEN", &self.token),
+            ("OPSML_DEFAULT_SPACE", &self.default_space),
+        ];
+        for (key, value) in optional {
+            if let Some(value) = value {
+                settings.push((key, value.clone()));
+            }
+        }
+        settings
+    }
+}
+
+/// Named client profiles stored in the user config dir
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfileStore {
+    #[serde(skip)]
+    dir: PathBuf,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active: Option<String>,
+
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ClientProfile>,
+}
+
+impl ProfileStore {
+    pub fn dir() -> Result<PathBuf, SettingsError> {
+        match env::var(PROFILES_DIR_ENV)
+            .ok()
+            .filter(|d| !d.trim().is_empty())
+        {
+            Some(dir) => Ok(PathBuf::from(dir)),
+            None => dirs::config_dir()
+                .map(|dir| dir.join("opsml"))
+                .ok_or(SettingsError::MissingConfigDir),
+        }
+    }
+
+    pub fn load() -> Result<Self, SettingsError> {
+        Self::load_from(&Self::dir()?)
+    }
+
+    /// Load the profiles in `dir`. A missing file is an empty store
+    pub fn load_from(dir: &Path) -> Result<Self, SettingsError> {
+        let path = dir.join(PROFILES_FILE);
+
+        let mut store = if path.exists() {
+            let contents = fs::read_to_string(&path)
+                .map_err(|e| SettingsError::ReadProfilesError(path.clone(), e))?;
+            toml::from_str::<ProfileStore>(&contents)
+                .map_err(|e| SettingsError::ParseProfilesError(path.clone(), e.to_string()))?
+        } else {
+            ProfileStore::default()
+        };
+
+        store.dir = dir.to_path_buf();
+        Ok(store)
+    }
+
+    pub fn save(&self) -> Result<(), SettingsError> {
+        let path = self.dir.join(PROFILES_FILE);
+        let contents = toml::to_string_pretty(self)
+            .map_err(|e| SettingsError::ParseProfilesError(path.clone(), e.to_string()))?;
+        write_private(&path, contents.as_bytes())
+            .map_err(|e| SettingsError::WriteProfilesError(path, e))
+    }
+
+    pub fn get(&self, name: &str) -> Result<&ClientProfile, SettingsError> {
+        self.profiles
+            .get(name)
+            .ok_or_else(|| SettingsError::ProfileNotFound(name.to_string()))
+    }
+
+    /// Add or replace a profile. The first profile saved becomes the active one
+    pub fn upsert(&mut self, name: &str, profile: ClientProfile) -> Result<(), SettingsError> {
+        validate_profile_name(name)?;
+        self.profiles.insert(name.to_string(), profile);
+        if self.active.is_none() {
+            self.active = Some(name.to_string());
+        }
+        Ok(())
+    }
+
+    pub fn set_active(&mut self, name: &str) -> Result<(), SettingsError> {
+        self.get(name)?;
+        self.active = Some(name.to_string());
+        Ok(())
+    }
+
+    /// File caching the JWT of a profile between invocations
+    pub fn token_cache_path(&self, name: &str) -> PathBuf {
+        self.dir.join(TOKENS_DIR).join(format!("{name}.jwt"))
+    }
+
+    /// Apply the requested or active profile to `source`. A profile requested with `--profile`
+    /// or `OPSML_PROFILE` overrides the environment. The active profile only applies when no
+    /// tracking uri is set, and never overrides environment variables
+    pub fn apply(&self, source: &mut ConfigSource) -> Result<(), SettingsError> {
+        let requested = requested_profile();
+
+        let name = match (&requested, &self.active) {
+            (Some(name), _) => name.clone(),
+            (None, Some(active)) if source.var("OPSML_TRACKING_URI").is_none() => active.clone(),
+            _ => return Ok(()),
+        };
+        let profile = self.get(&name)?;
+
+        for (key, value) in profile.settings() {
+            if requested.is_some() || source.origin(key) != Some(ValueOrigin::Environment) {
+                source.set(key, value, ValueOrigin::ClientProfile);
+            }
+        }
+
+        // the cached token belongs to the profile's user, so it is not used when the
+        // environment supplies any credentials
+        let profile_credentials = CREDENTIAL_KEYS
+            .iter()
+            .all(|key| source.origin(key) != Some(ValueOrigin::Environment));
+        if profile_credentials {
+            source.token_cache = Some(self.token_cache_path(&name));
+        }
+        source.client_profile = Some(name);
+        Ok(())
+    }
+}
+
+fn validate_profile_name(name: &str) -> Result<(), SettingsError> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(SettingsError::InvalidProfileName(name.to_string()))
+    }
+}
+
+/// Write a file readable only by the current user, creating parent directories as needed
+pub fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(parent, fs::Permissions::from_mode(0o700))?;
+        }
+    }
+
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, contents)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&tmp_path, fs::Permissions::from_mode(0o600))?;
+    }
+    fs::rename(&tmp_path, path).inspect_err(|_| {
+        let _ = fs::remove_file(&tmp_path);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_profile_store_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut store = ProfileStore::load_from(dir.path()).unwrap();
+        assert!(store.profiles.is_empty());
+
+        store
+            .upsert(
+                "staging",
+                ClientProfile {
+                    tracking_uri: "https://opsml.staging".to_string(),
+                    username: Some("admin".to_string()),
+                    password: Some("admin".to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        store
+            .upsert(
+                "prod",
+                ClientProfile {
+                    tracking_uri: "https://opsml.prod".to_string(),
+                    token: Some("opsml_pat_abc_123".to_string()),
+                    default_space: Some("models".to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert!(store.upsert("../prod", ClientProfile::default()).is_err());
+        assert!(store.set_active("dev").is_err());
+        store.save().unwrap();
+
+        let store = ProfileStore::load_from(dir.path()).unwrap();
+        assert_eq!(store.active.as_deref(), Some("staging"));
+        assert_eq!(store.profiles.len(), 2);
+        assert_eq!(
+            store.get("prod").unwrap().token.as_deref(),
+            Some("opsml_pat_abc_123")
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(dir.path().join(PROFILES_FILE))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn test_active_profile_does_not_override_environment() {
+        let mut store = ProfileStore::default();
+        store
+            .upsert(
+                "staging",
+                ClientProfile {
+                    tracking_uri: "https://opsml.staging".to_string(),
+                    username: Some("admin".to_string()),
+                    default_space: Some("models".to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        let env_vars: HashMap<String, String> =
+            [("OPSML_USERNAME".to_string(), "me".to_string())].into();
+        let mut source = ConfigSource::from_env(env_vars).unwrap();
+        store.apply(&mut source).unwrap();
+
+        assert_eq!(source.client_profile.as_deref(), Some("staging"));
+        assert_eq!(
+            source.var("OPSML_TRACKING_URI").unwrap(),
+            "https://opsml.staging"
+        );
+        assert_eq!(source.var("OPSML_USERNAME").unwrap(), "me");
+        assert_eq!(source.var("OPSML_DEFAULT_SPACE").unwrap(), "models");
+        // the profile's cached token is not sent for the environment's user
+        assert_eq!(source.token_cache, None);
+
+        let mut source = ConfigSource::from_env(HashMap::new()).unwrap();
+        store.apply(&mut source).unwrap();
+        assert_eq!(source.var("OPSML_USERNAME").unwrap(), "admin");
+        assert_eq!(source.token_cache, Some(store.token_cache_path("staging")));
+
+        // an exported tracking uri means the active profile is not used
+        let env_vars: HashMap<String, String> = [(
+            "OPSML_TRACKING_URI".to_string(),
+            "http://localhost:8080".to_string(),
+        )]
+        .into();
+        let mut source = ConfigSource::from_env(env_vars).unwrap();
+        store.apply(&mut source).unwrap();
+        assert_eq!(source.client_profile, None);
+        assert_eq!(
+            source.var("OPSML_TRACKING_URI").unwrap(),
+            "http://localhost:8080"
+        );
+    }
+}
@@ -1,25 +1,31 @@
 use crate::error::SqlError;
 use crate::mysql::client::MySqlClient;
 use crate::postgres::client::PostgresClient;
-use crate::schemas::VersionSummary;
 use crate::schemas::schema::{
-    ArtifactSqlRecord, CardResults, CardSummary, HardwareMetricsRecord, MetricRecord,
-    ParameterRecord, QueryStats, ServerCard, ServiceCardRecord, SkillCardRecord,
-    SubAgentCardRecord, ToolCardRecord, User,
+    ApiToken, ArtifactSqlRecord, CardResults, CardSummary, HardwareMetricsRecord,
+    LineageNodeSqlRecord, MetricRecord, ParameterRecord, QueryStats, ServerCard, ServiceCardRecord,
+    SkillCardRecord, SubAgentCardRecord, ToolCardRecord, TrashSqlRecord, User, WebhookSqlRecord,
 };
+use crate::schemas::{UnkeyedCard, VersionSummary};
 use crate::sqlite::client::SqliteClient;
 use crate::traits::{
-    ArtifactLogicTrait, AuditLogicTrait, CardLogicTrait, ExperimentLogicTrait, SkillLogicTrait,
-    SpaceLogicTrait, SubAgentLogicTrait, ToolLogicTrait, UserLogicTrait,
+    AgentJobLogicTrait, AliasLogicTrait, ArtifactLogicTrait, AuditLogicTrait, CardLogicTrait,
+    EventLogicTrait, ExperimentLogicTrait, GroupLogicTrait, LineageLogicTrait, SearchLogicTrait,
+    SkillLogicTrait, SpaceLogicTrait, SubAgentLogicTrait, ToolLogicTrait, TrashLogicTrait,
+    UserLogicTrait,
 };
 use anyhow::Context;
 use anyhow::Result as AnyhowResult;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use opsml_settings::config::DatabaseSettings;
 use opsml_types::contracts::skill::MarketplaceStats;
 use opsml_types::contracts::{
-    ArtifactQueryArgs, ArtifactRecord, AuditEvent, DashboardStats, SpaceNameEvent, SpaceRecord,
-    SpaceStats,
+    AgentJobRecord, ArtifactQueryArgs, ArtifactRecord, AuditEvent, AuditEventRecord,
+    AuditQueryArgs, CardAlias, CardSearchDocument, CardSearchHit, CardSearchQuery, DashboardStats,
+    GroupRecord, GroupRole, LineageDirection, LineageEdge, LineageGraph, LineageNode, LineageQuery,
+    RegistryEvent, RetentionPolicy, SpaceNameEvent, SpaceRecord, SpaceRegistryUsage, SpaceStats,
+    TrashQuery, WebhookDelivery,
 };
 use opsml_types::contracts::{CardArgs, VersionCursor};
 use opsml_types::{
@@ -27,6 +33,24 @@ use opsml_types::{
     cards::CardTable,
     contracts::{ArtifactKey, CardQueryArgs, ServiceQueryArgs},
 };
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+/// Card registries that can take part in lineage, in the order they are probed
+/// when the registry of a root uid is not known
+const LINEAGE_REGISTRIES: [RegistryType; 11] = [
+    RegistryType::Data,
+    RegistryType::Model,
+    RegistryType::Experiment,
+    RegistryType::Audit,
+    RegistryType::Prompt,
+    RegistryType::Service,
+    RegistryType::Mcp,
+    RegistryType::Agent,
+    RegistryType::Skill,
+    RegistryType::SubAgent,
+    RegistryType::Tool,
+];
 
 #[derive(Debug, Clone)]
 pub enum SqlClientEnum {
@@ -99,6 +123,9 @@ impl CardLogicTrait for SqlClientEnum {
         table: &CardTable,
         query_args: &CardQueryArgs,
     ) -> Result<CardResults, SqlError> {
+        let query_args = self.resolve_version_alias(query_args).await?;
+        let query_args = query_args.as_ref();
+
         match self {
             SqlClientEnum::Postgres(client) => client.card.query_cards(table, query_args).await,
             SqlClientEnum::Sqlite(client) => client.card.query_cards(table, query_args).await,
@@ -236,6 +263,9 @@ impl CardLogicTrait for SqlClientEnum {
         table: &CardTable,
         query_args: &CardQueryArgs,
     ) -> Result<ArtifactKey, SqlError> {
+        let query_args = self.resolve_version_alias(query_args).await?;
+        let query_args = query_args.as_ref();
+
         match self {
             SqlClientEnum::Postgres(client) => {
                 client
@@ -341,6 +371,135 @@ impl ArtifactLogicTrait for SqlClientEnum {
         }
     }
 
+    async fn get_artifact_keys(
+        &self,
+        after_uid: &str,
+        limit: i64,
+    ) -> Result<Vec<ArtifactKey>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.artifact.get_artifact_keys(after_uid, limit).await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client.artifact.get_artifact_keys(after_uid, limit).await
+            }
+            SqlClientEnum::MySql(client) => {
+                client.artifact.get_artifact_keys(after_uid, limit).await
+            }
+        }
+    }
+
+    async fn get_cards_without_key(
+        &self,
+        table: &CardTable,
+        after_uid: &str,
+        limit: i64,
+    ) -> Result<Vec<UnkeyedCard>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .artifact
+                    .get_cards_without_key(table, after_uid, limit)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .artifact
+                    .get_cards_without_key(table, after_uid, limit)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .artifact
+                    .get_cards_without_key(table, after_uid, limit)
+                    .await
+            }
+        }
+    }
+
+    async fn get_artifact_keys_to_rewrap(
+        &self,
+        key_version: u32,
+        after_uid: &str,
+        limit: i64,
+    ) -> Result<Vec<ArtifactKey>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .artifact
+                    .get_artifact_keys_to_rewrap(key_version, after_uid, limit)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .artifact
+                    .get_artifact_keys_to_rewrap(key_version, after_uid, limit)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .artifact
+                    .get_artifact_keys_to_rewrap(key_version, after_uid, limit)
+                    .await
+            }
+        }
+    }
+
+    async fn rewrap_artifact_key(
+        &self,
+        key: &ArtifactKey,
+        previous_version: u32,
+    ) -> Result<bool, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .artifact
+                    .rewrap_artifact_key(key, previous_version)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .artifact
+                    .rewrap_artifact_key(key, previous_version)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .artifact
+                    .rewrap_artifact_key(key, previous_version)
+                    .await
+            }
+        }
+    }
+
+    async fn set_artifact_manifest(
+        &self,
+        uid: &str,
+        registry_type: &str,
+        manifest_sha256: &str,
+    ) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .artifact
+                    .set_artifact_manifest(uid, registry_type, manifest_sha256)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .artifact
+                    .set_artifact_manifest(uid, registry_type, manifest_sha256)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .artifact
+                    .set_artifact_manifest(uid, registry_type, manifest_sha256)
+                    .await
+            }
+        }
+    }
+
     async fn delete_artifact_key(&self, uid: &str, registry_type: &str) -> Result<(), SqlError> {
         match self {
             SqlClientEnum::Postgres(client) => {
@@ -524,6 +683,164 @@ impl UserLogicTrait for SqlClientEnum {
             SqlClientEnum::MySql(client) => client.user.update_user(user).await,
         }
     }
+    async fn insert_api_token(&self, token: &ApiToken) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.user.insert_api_token(token).await,
+            SqlClientEnum::Sqlite(client) => client.user.insert_api_token(token).await,
+            SqlClientEnum::MySql(client) => client.user.insert_api_token(token).await,
+        }
+    }
+
+    async fn get_api_token(&self, token_id: &str) -> Result<Option<ApiToken>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.user.get_api_token(token_id).await,
+            SqlClientEnum::Sqlite(client) => client.user.get_api_token(token_id).await,
+            SqlClientEnum::MySql(client) => client.user.get_api_token(token_id).await,
+        }
+    }
+
+    async fn get_api_tokens(&self, username: &str) -> Result<Vec<ApiToken>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.user.get_api_tokens(username).await,
+            SqlClientEnum::Sqlite(client) => client.user.get_api_tokens(username).await,
+            SqlClientEnum::MySql(client) => client.user.get_api_tokens(username).await,
+        }
+    }
+
+    async fn revoke_api_token(&self, username: &str, token_id: &str) -> Result<bool, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.user.revoke_api_token(username, token_id).await
+            }
+            SqlClientEnum::Sqlite(client) => client.user.revoke_api_token(username, token_id).await,
+            SqlClientEnum::MySql(client) => client.user.revoke_api_token(username, token_id).await,
+        }
+    }
+
+    async fn update_api_token_last_used(&self, token_id: &str) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.user.update_api_token_last_used(token_id).await
+            }
+            SqlClientEnum::Sqlite(client) => client.user.update_api_token_last_used(token_id).await,
+            SqlClientEnum::MySql(client) => client.user.update_api_token_last_used(token_id).await,
+        }
+    }
+}
+
+#[async_trait]
+impl GroupLogicTrait for SqlClientEnum {
+    async fn insert_group(&self, group: &GroupRecord) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.group.insert_group(group).await,
+            SqlClientEnum::Sqlite(client) => client.group.insert_group(group).await,
+            SqlClientEnum::MySql(client) => client.group.insert_group(group).await,
+        }
+    }
+
+    async fn get_group(&self, name: &str) -> Result<Option<GroupRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.group.get_group(name).await,
+            SqlClientEnum::Sqlite(client) => client.group.get_group(name).await,
+            SqlClientEnum::MySql(client) => client.group.get_group(name).await,
+        }
+    }
+
+    async fn get_groups(&self) -> Result<Vec<GroupRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.group.get_groups().await,
+            SqlClientEnum::Sqlite(client) => client.group.get_groups().await,
+            SqlClientEnum::MySql(client) => client.group.get_groups().await,
+        }
+    }
+
+    async fn delete_group(&self, name: &str) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.group.delete_group(name).await,
+            SqlClientEnum::Sqlite(client) => client.group.delete_group(name).await,
+            SqlClientEnum::MySql(client) => client.group.delete_group(name).await,
+        }
+    }
+
+    async fn upsert_group_role(&self, role: &GroupRole) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.group.upsert_group_role(role).await,
+            SqlClientEnum::Sqlite(client) => client.group.upsert_group_role(role).await,
+            SqlClientEnum::MySql(client) => client.group.upsert_group_role(role).await,
+        }
+    }
+
+    async fn delete_group_role(&self, group_name: &str, space: &str) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.group.delete_group_role(group_name, space).await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client.group.delete_group_role(group_name, space).await
+            }
+            SqlClientEnum::MySql(client) => client.group.delete_group_role(group_name, space).await,
+        }
+    }
+
+    async fn get_group_roles(&self, group_name: &str) -> Result<Vec<GroupRole>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.group.get_group_roles(group_name).await,
+            SqlClientEnum::Sqlite(client) => client.group.get_group_roles(group_name).await,
+            SqlClientEnum::MySql(client) => client.group.get_group_roles(group_name).await,
+        }
+    }
+
+    async fn add_group_member(&self, group_name: &str, username: &str) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.group.add_group_member(group_name, username).await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client.group.add_group_member(group_name, username).await
+            }
+            SqlClientEnum::MySql(client) => {
+                client.group.add_group_member(group_name, username).await
+            }
+        }
+    }
+
+    async fn remove_group_member(&self, group_name: &str, username: &str) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.group.remove_group_member(group_name, username).await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client.group.remove_group_member(group_name, username).await
+            }
+            SqlClientEnum::MySql(client) => {
+                client.group.remove_group_member(group_name, username).await
+            }
+        }
+    }
+
+    async fn get_group_members(&self, group_name: &str) -> Result<Vec<String>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.group.get_group_members(group_name).await,
+            SqlClientEnum::Sqlite(client) => client.group.get_group_members(group_name).await,
+            SqlClientEnum::MySql(client) => client.group.get_group_members(group_name).await,
+        }
+    }
+
+    async fn get_user_groups(&self, username: &str) -> Result<Vec<String>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.group.get_user_groups(username).await,
+            SqlClientEnum::Sqlite(client) => client.group.get_user_groups(username).await,
+            SqlClientEnum::MySql(client) => client.group.get_user_groups(username).await,
+        }
+    }
+
+    async fn get_user_group_roles(&self, username: &str) -> Result<Vec<GroupRole>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.group.get_user_group_roles(username).await,
+            SqlClientEnum::Sqlite(client) => client.group.get_user_group_roles(username).await,
+            SqlClientEnum::MySql(client) => client.group.get_user_group_roles(username).await,
+        }
+    }
 }
 
 #[async_trait]
@@ -603,63 +920,538 @@ impl SpaceLogicTrait for SqlClientEnum {
             }
         }
     }
-}
 
-#[async_trait]
-impl AuditLogicTrait for SqlClientEnum {
-    async fn insert_audit_event(&self, event: AuditEvent) -> Result<(), SqlError> {
+    async fn set_space_quota(
+        &self,
+        space: &str,
+        soft_quota_bytes: Option<i64>,
+        hard_quota_bytes: Option<i64>,
+    ) -> Result<(), SqlError> {
         match self {
-            SqlClientEnum::Postgres(client) => client.audit.insert_audit_event(event).await,
-            SqlClientEnum::Sqlite(client) => client.audit.insert_audit_event(event).await,
-            SqlClientEnum::MySql(client) => client.audit.insert_audit_event(event).await,
-        }
-    }
-}
-
-impl SqlClientEnum {
-    pub async fn new(settings: &DatabaseSettings) -> Result<Self, SqlError> {
-        match settings.sql_type {
-            SqlType::Postgres => {
-                let client = PostgresClient::new(settings).await?;
-                Ok(SqlClientEnum::Postgres(client))
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .space
+                    .set_space_quota(space, soft_quota_bytes, hard_quota_bytes)
+                    .await
             }
-            SqlType::Sqlite => {
-                let client = SqliteClient::new(settings).await?;
-                Ok(SqlClientEnum::Sqlite(client))
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .space
+                    .set_space_quota(space, soft_quota_bytes, hard_quota_bytes)
+                    .await
             }
-            SqlType::MySql => {
-                let client = MySqlClient::new(settings).await?;
-                Ok(SqlClientEnum::MySql(client))
+            SqlClientEnum::MySql(client) => {
+                client
+                    .space
+                    .set_space_quota(space, soft_quota_bytes, hard_quota_bytes)
+                    .await
             }
         }
     }
 
-    pub async fn query(&self, sql: &str) {
+    async fn add_space_usage(
+        &self,
+        space: &str,
+        registry_type: &RegistryType,
+        bytes: i64,
+    ) -> Result<(), SqlError> {
         match self {
             SqlClientEnum::Postgres(client) => {
-                sqlx::query(sql).execute(&client.pool).await.unwrap();
+                client
+                    .space
+                    .add_space_usage(space, registry_type, bytes)
+                    .await
             }
             SqlClientEnum::Sqlite(client) => {
-                sqlx::query(sql).execute(&client.pool).await.unwrap();
+                client
+                    .space
+                    .add_space_usage(space, registry_type, bytes)
+                    .await
             }
             SqlClientEnum::MySql(client) => {
-                sqlx::query(sql).execute(&client.pool).await.unwrap();
+                client
+                    .space
+                    .add_space_usage(space, registry_type, bytes)
+                    .await
             }
         }
     }
 
-    pub fn name(&self) -> String {
+    async fn get_space_usage(&self) -> Result<Vec<SpaceRegistryUsage>, SqlError> {
         match self {
-            SqlClientEnum::Postgres(_) => "Postgres".to_string(),
-            SqlClientEnum::Sqlite(_) => "Sqlite".to_string(),
-            SqlClientEnum::MySql(_) => "MySql".to_string(),
+            SqlClientEnum::Postgres(client) => client.space.get_space_usage().await,
+            SqlClientEnum::Sqlite(client) => client.space.get_space_usage().await,
+            SqlClientEnum::MySql(client) => client.space.get_space_usage().await,
         }
     }
-}
 
-#[async_trait]
-impl SkillLogicTrait for SqlClientEnum {
-    async fn get_skill_card_by_name(
+    async fn get_space_bytes(&self, space: &str) -> Result<i64, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.space.get_space_bytes(space).await,
+            SqlClientEnum::Sqlite(client) => client.space.get_space_bytes(space).await,
+            SqlClientEnum::MySql(client) => client.space.get_space_bytes(space).await,
+        }
+    }
+
+    async fn reserve_space_usage(
+        &self,
+        space: &str,
+        registry_type: &RegistryType,
+        bytes: i64,
+        hard_quota_bytes: i64,
+    ) -> Result<bool, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .space
+                    .reserve_space_usage(space, registry_type, bytes, hard_quota_bytes)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .space
+                    .reserve_space_usage(space, registry_type, bytes, hard_quota_bytes)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .space
+                    .reserve_space_usage(space, registry_type, bytes, hard_quota_bytes)
+                    .await
+            }
+        }
+    }
+
+    async fn set_space_usage(
+        &self,
+        space: &str,
+        registry_type: &RegistryType,
+        bytes: i64,
+    ) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .space
+                    .set_space_usage(space, registry_type, bytes)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .space
+                    .set_space_usage(space, registry_type, bytes)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .space
+                    .set_space_usage(space, registry_type, bytes)
+                    .await
+            }
+        }
+    }
+
+    async fn insert_upload_reservation(
+        &self,
+        path: &str,
+        space: &str,
+        registry_type: &RegistryType,
+        bytes: i64,
+    ) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .space
+                    .insert_upload_reservation(path, space, registry_type, bytes)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .space
+                    .insert_upload_reservation(path, space, registry_type, bytes)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .space
+                    .insert_upload_reservation(path, space, registry_type, bytes)
+                    .await
+            }
+        }
+    }
+
+    async fn take_upload_reservation(&self, path: &str) -> Result<Option<i64>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.space.take_upload_reservation(path).await,
+            SqlClientEnum::Sqlite(client) => client.space.take_upload_reservation(path).await,
+            SqlClientEnum::MySql(client) => client.space.take_upload_reservation(path).await,
+        }
+    }
+
+    async fn get_upload_reservations(&self) -> Result<Vec<SpaceRegistryUsage>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.space.get_upload_reservations().await,
+            SqlClientEnum::Sqlite(client) => client.space.get_upload_reservations().await,
+            SqlClientEnum::MySql(client) => client.space.get_upload_reservations().await,
+        }
+    }
+
+    async fn delete_stale_upload_reservations(
+        &self,
+        created_before: &DateTime<Utc>,
+    ) -> Result<u64, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .space
+                    .delete_stale_upload_reservations(created_before)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .space
+                    .delete_stale_upload_reservations(created_before)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .space
+                    .delete_stale_upload_reservations(created_before)
+                    .await
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl AuditLogicTrait for SqlClientEnum {
+    async fn insert_audit_event(&self, event: AuditEvent) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.audit.insert_audit_event(event).await,
+            SqlClientEnum::Sqlite(client) => client.audit.insert_audit_event(event).await,
+            SqlClientEnum::MySql(client) => client.audit.insert_audit_event(event).await,
+        }
+    }
+
+    async fn query_audit_events(
+        &self,
+        query_args: &AuditQueryArgs,
+    ) -> Result<Vec<AuditEventRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.audit.query_audit_events(query_args).await,
+            SqlClientEnum::Sqlite(client) => client.audit.query_audit_events(query_args).await,
+            SqlClientEnum::MySql(client) => client.audit.query_audit_events(query_args).await,
+        }
+    }
+}
+
+impl SqlClientEnum {
+    pub async fn new(settings: &DatabaseSettings) -> Result<Self, SqlError> {
+        match settings.sql_type {
+            SqlType::Postgres => {
+                let client = PostgresClient::new(settings).await?;
+                Ok(SqlClientEnum::Postgres(client))
+            }
+            SqlType::Sqlite => {
+                let client = SqliteClient::new(settings).await?;
+                Ok(SqlClientEnum::Sqlite(client))
+            }
+            SqlType::MySql => {
+                let client = MySqlClient::new(settings).await?;
+                Ok(SqlClientEnum::MySql(client))
+            }
+        }
+    }
+
+    pub async fn query(&self, sql: &str) {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                sqlx::query(sql).execute(&client.pool).await.unwrap();
+            }
+            SqlClientEnum::Sqlite(client) => {
+                sqlx::query(sql).execute(&client.pool).await.unwrap();
+            }
+            SqlClientEnum::MySql(client) => {
+                sqlx::query(sql).execute(&client.pool).await.unwrap();
+            }
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            SqlClientEnum::Postgres(_) => "Postgres".to_string(),
+            SqlClientEnum::Sqlite(_) => "Sqlite".to_string(),
+            SqlClientEnum::MySql(_) => "MySql".to_string(),
+        }
+    }
+
+    /// Swaps an alias version such as `@production` for the uid the alias currently points to.
+    /// Query args without an alias are passed through untouched
+    pub async fn resolve_version_alias<'a>(
+        &self,
+        query_args: &'a CardQueryArgs,
+    ) -> Result<Cow<'a, CardQueryArgs>, SqlError> {
+        let Some(alias) = query_args.version_alias() else {
+            return Ok(Cow::Borrowed(query_args));
+        };
+
+        let (Some(space), Some(name)) = (query_args.space.as_deref(), query_args.name.as_deref())
+        else {
+            return Err(SqlError::MissingField(
+                "space and name are required to resolve a version alias".to_string(),
+            ));
+        };
+
+        let resolved = self
+            .get_card_alias(space, name, &query_args.registry_type, alias)
+            .await?
+            .ok_or_else(|| SqlError::VersionAliasNotFound(format!("{space}/{name}@{alias}")))?;
+
+        let mut query_args = query_args.clone();
+        query_args.uid = Some(resolved.uid);
+        query_args.version = None;
+
+        Ok(Cow::Owned(query_args))
+    }
+
+    async fn lineage_node(
+        &self,
+        uid: &str,
+        registry_type: &RegistryType,
+        depth: usize,
+    ) -> Result<Option<LineageNode>, SqlError> {
+        let table = CardTable::from_registry_type(registry_type);
+        let node = self
+            .get_lineage_node(&table, uid)
+            .await?
+            .map(|record: LineageNodeSqlRecord| LineageNode {
+                uid: record.uid,
+                registry_type: registry_type.clone(),
+                space: record.space,
+                name: record.name,
+                version: record.version,
+                depth,
+            });
+
+        Ok(node)
+    }
+
+    /// Walks lineage edges breadth-first from the root card. Cards reached upstream keep
+    /// walking upstream and cards reached downstream keep walking downstream, so siblings
+    /// (e.g. other models trained on the same data) are not pulled into the graph.
+    /// References to cards that no longer exist are dropped
+    pub async fn get_lineage_graph(&self, query: &LineageQuery) -> Result<LineageGraph, SqlError> {
+        let root = match &query.registry_type {
+            Some(registry_type) => self.lineage_node(&query.uid, registry_type, 0).await?,
+            None => {
+                let mut found = None;
+                for registry_type in &LINEAGE_REGISTRIES {
+                    found = self.lineage_node(&query.uid, registry_type, 0).await?;
+                    if found.is_some() {
+                        break;
+                    }
+                }
+                found
+            }
+        }
+        .ok_or_else(|| SqlError::LineageRootNotFound(query.uid.clone()))?;
+
+        let mut visited = HashSet::from([(root.uid.clone(), root.registry_type.clone())]);
+        let mut missing = HashSet::new();
+        let mut seen_edges = HashSet::new();
+        let mut edges = Vec::new();
+        let mut frontier = vec![(
+            root.uid.clone(),
+            root.registry_type.clone(),
+            query.direction,
+        )];
+        let mut nodes = vec![root];
+
+        for depth in 1..=query.effective_depth() {
+            let mut next = Vec::new();
+
+            for (uid, registry_type, direction) in frontier {
+                for edge in self.get_lineage_edges(&uid).await? {
+                    let is_target = edge.target_uid == uid && edge.target_type == registry_type;
+                    let is_source = edge.source_uid == uid && edge.source_type == registry_type;
+
+                    let neighbor = if is_target && direction.includes_upstream() {
+                        (
+                            edge.source_uid.clone(),
+                            edge.source_type.clone(),
+                            LineageDirection::Upstream,
+                        )
+                    } else if is_source && direction.includes_downstream() {
+                        (
+                            edge.target_uid.clone(),
+                            edge.target_type.clone(),
+                            LineageDirection::Downstream,
+                        )
+                    } else {
+                        continue;
+                    };
+
+                    let key = (neighbor.0.clone(), neighbor.1.clone());
+                    if (key.0 == uid && key.1 == registry_type) || missing.contains(&key) {
+                        continue;
+                    }
+
+                    if !visited.contains(&key) {
+                        match self.lineage_node(&key.0, &key.1, depth).await? {
+                            Some(node) => {
+                                nodes.push(node);
+                                visited.insert(key);
+                                next.push(neighbor);
+                            }
+                            None => {
+                                missing.insert(key);
+                                continue;
+                            }
+                        }
+                    }
+
+                    if seen_edges.insert(edge.clone()) {
+                        edges.push(edge);
+                    }
+                }
+            }
+
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+
+        Ok(LineageGraph {
+            root: query.uid.clone(),
+            nodes,
+            edges,
+        })
+    }
+}
+
+#[async_trait]
+impl LineageLogicTrait for SqlClientEnum {
+    async fn get_lineage_edges(&self, uid: &str) -> Result<Vec<LineageEdge>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.lineage.get_lineage_edges(uid).await,
+            SqlClientEnum::Sqlite(client) => client.lineage.get_lineage_edges(uid).await,
+            SqlClientEnum::MySql(client) => client.lineage.get_lineage_edges(uid).await,
+        }
+    }
+
+    async fn get_lineage_node(
+        &self,
+        table: &CardTable,
+        uid: &str,
+    ) -> Result<Option<LineageNodeSqlRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.lineage.get_lineage_node(table, uid).await,
+            SqlClientEnum::Sqlite(client) => client.lineage.get_lineage_node(table, uid).await,
+            SqlClientEnum::MySql(client) => client.lineage.get_lineage_node(table, uid).await,
+        }
+    }
+}
+
+#[async_trait]
+impl AliasLogicTrait for SqlClientEnum {
+    async fn upsert_card_alias(&self, alias: &CardAlias) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.alias.upsert_card_alias(alias).await,
+            SqlClientEnum::Sqlite(client) => client.alias.upsert_card_alias(alias).await,
+            SqlClientEnum::MySql(client) => client.alias.upsert_card_alias(alias).await,
+        }
+    }
+
+    async fn get_card_alias(
+        &self,
+        space: &str,
+        name: &str,
+        registry_type: &RegistryType,
+        alias: &str,
+    ) -> Result<Option<CardAlias>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .alias
+                    .get_card_alias(space, name, registry_type, alias)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .alias
+                    .get_card_alias(space, name, registry_type, alias)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .alias
+                    .get_card_alias(space, name, registry_type, alias)
+                    .await
+            }
+        }
+    }
+
+    async fn get_card_aliases(
+        &self,
+        space: &str,
+        name: &str,
+        registry_type: &RegistryType,
+    ) -> Result<Vec<CardAlias>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .alias
+                    .get_card_aliases(space, name, registry_type)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .alias
+                    .get_card_aliases(space, name, registry_type)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .alias
+                    .get_card_aliases(space, name, registry_type)
+                    .await
+            }
+        }
+    }
+
+    async fn delete_card_alias(
+        &self,
+        space: &str,
+        name: &str,
+        registry_type: &RegistryType,
+        alias: &str,
+    ) -> Result<bool, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .alias
+                    .delete_card_alias(space, name, registry_type, alias)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .alias
+                    .delete_card_alias(space, name, registry_type, alias)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .alias
+                    .delete_card_alias(space, name, registry_type, alias)
+                    .await
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl SkillLogicTrait for SqlClientEnum {
+    async fn get_skill_card_by_name(
         &self,
         space: &str,
         name: &str,
@@ -668,291 +1460,709 @@ impl SkillLogicTrait for SqlClientEnum {
             SqlClientEnum::Postgres(client) => {
                 client.card.get_skill_card_by_name(space, name).await
             }
-            SqlClientEnum::Sqlite(client) => client.card.get_skill_card_by_name(space, name).await,
-            SqlClientEnum::MySql(client) => client.card.get_skill_card_by_name(space, name).await,
+            SqlClientEnum::Sqlite(client) => client.card.get_skill_card_by_name(space, name).await,
+            SqlClientEnum::MySql(client) => client.card.get_skill_card_by_name(space, name).await,
+        }
+    }
+
+    async fn get_skill_card_by_version(
+        &self,
+        space: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<SkillCardRecord, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .card
+                    .get_skill_card_by_version(space, name, version)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .card
+                    .get_skill_card_by_version(space, name, version)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .card
+                    .get_skill_card_by_version(space, name, version)
+                    .await
+            }
+        }
+    }
+
+    async fn increment_skill_download_count(&self, uid: &str) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.card.increment_skill_download_count(uid).await
+            }
+            SqlClientEnum::Sqlite(client) => client.card.increment_skill_download_count(uid).await,
+            SqlClientEnum::MySql(client) => client.card.increment_skill_download_count(uid).await,
+        }
+    }
+
+    async fn list_skill_cards_by_space(
+        &self,
+        space: &str,
+    ) -> Result<Vec<SkillCardRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.card.list_skill_cards_by_space(space).await,
+            SqlClientEnum::Sqlite(client) => client.card.list_skill_cards_by_space(space).await,
+            SqlClientEnum::MySql(client) => client.card.list_skill_cards_by_space(space).await,
+        }
+    }
+
+    async fn get_featured_skills(&self, limit: i64) -> Result<Vec<SkillCardRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.card.get_featured_skills(limit).await,
+            SqlClientEnum::Sqlite(client) => client.card.get_featured_skills(limit).await,
+            SqlClientEnum::MySql(client) => client.card.get_featured_skills(limit).await,
+        }
+    }
+
+    async fn get_all_skill_tags(&self) -> Result<Vec<String>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.card.get_all_skill_tags().await,
+            SqlClientEnum::Sqlite(client) => client.card.get_all_skill_tags().await,
+            SqlClientEnum::MySql(client) => client.card.get_all_skill_tags().await,
+        }
+    }
+
+    async fn get_marketplace_stats(&self) -> Result<MarketplaceStats, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.card.get_marketplace_stats().await,
+            SqlClientEnum::Sqlite(client) => client.card.get_marketplace_stats().await,
+            SqlClientEnum::MySql(client) => client.card.get_marketplace_stats().await,
+        }
+    }
+}
+
+#[async_trait]
+impl SubAgentLogicTrait for SqlClientEnum {
+    async fn get_subagent_card_by_name(
+        &self,
+        space: &str,
+        name: &str,
+    ) -> Result<SubAgentCardRecord, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.card.get_subagent_card_by_name(space, name).await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client.card.get_subagent_card_by_name(space, name).await
+            }
+            SqlClientEnum::MySql(client) => {
+                client.card.get_subagent_card_by_name(space, name).await
+            }
+        }
+    }
+
+    async fn get_subagent_card_by_version(
+        &self,
+        space: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<SubAgentCardRecord, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .card
+                    .get_subagent_card_by_version(space, name, version)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .card
+                    .get_subagent_card_by_version(space, name, version)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .card
+                    .get_subagent_card_by_version(space, name, version)
+                    .await
+            }
+        }
+    }
+
+    async fn increment_subagent_download_count(&self, uid: &str) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.card.increment_subagent_download_count(uid).await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client.card.increment_subagent_download_count(uid).await
+            }
+            SqlClientEnum::MySql(client) => {
+                client.card.increment_subagent_download_count(uid).await
+            }
+        }
+    }
+
+    async fn list_subagent_cards_by_space(
+        &self,
+        space: &str,
+    ) -> Result<Vec<SubAgentCardRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.card.list_subagent_cards_by_space(space).await
+            }
+            SqlClientEnum::Sqlite(client) => client.card.list_subagent_cards_by_space(space).await,
+            SqlClientEnum::MySql(client) => client.card.list_subagent_cards_by_space(space).await,
+        }
+    }
+
+    async fn get_featured_subagents(
+        &self,
+        space: &str,
+        limit: i64,
+    ) -> Result<Vec<SubAgentCardRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.card.get_featured_subagents(space, limit).await
+            }
+            SqlClientEnum::Sqlite(client) => client.card.get_featured_subagents(space, limit).await,
+            SqlClientEnum::MySql(client) => client.card.get_featured_subagents(space, limit).await,
+        }
+    }
+
+    async fn get_all_subagent_tags(&self, space: &str) -> Result<Vec<String>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.card.get_all_subagent_tags(space).await,
+            SqlClientEnum::Sqlite(client) => client.card.get_all_subagent_tags(space).await,
+            SqlClientEnum::MySql(client) => client.card.get_all_subagent_tags(space).await,
+        }
+    }
+
+    async fn get_subagent_marketplace_stats(
+        &self,
+        space: &str,
+    ) -> Result<MarketplaceStats, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.card.get_subagent_marketplace_stats(space).await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client.card.get_subagent_marketplace_stats(space).await
+            }
+            SqlClientEnum::MySql(client) => client.card.get_subagent_marketplace_stats(space).await,
+        }
+    }
+}
+
+#[async_trait]
+impl ToolLogicTrait for SqlClientEnum {
+    async fn get_tool_card_by_name(
+        &self,
+        space: &str,
+        name: &str,
+    ) -> Result<ToolCardRecord, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.card.get_tool_card_by_name(space, name).await,
+            SqlClientEnum::Sqlite(client) => client.card.get_tool_card_by_name(space, name).await,
+            SqlClientEnum::MySql(client) => client.card.get_tool_card_by_name(space, name).await,
+        }
+    }
+
+    async fn get_tool_card_by_version(
+        &self,
+        space: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<ToolCardRecord, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client
+                    .card
+                    .get_tool_card_by_version(space, name, version)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .card
+                    .get_tool_card_by_version(space, name, version)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .card
+                    .get_tool_card_by_version(space, name, version)
+                    .await
+            }
+        }
+    }
+
+    async fn increment_tool_download_count(&self, uid: &str) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.card.increment_tool_download_count(uid).await,
+            SqlClientEnum::Sqlite(client) => client.card.increment_tool_download_count(uid).await,
+            SqlClientEnum::MySql(client) => client.card.increment_tool_download_count(uid).await,
+        }
+    }
+
+    async fn list_tool_cards_by_space(
+        &self,
+        space: &str,
+        tool_type: Option<&str>,
+    ) -> Result<Vec<ToolCardRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => {
+                client.card.list_tool_cards_by_space(space, tool_type).await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client.card.list_tool_cards_by_space(space, tool_type).await
+            }
+            SqlClientEnum::MySql(client) => {
+                client.card.list_tool_cards_by_space(space, tool_type).await
+            }
         }
     }
 
-    async fn get_skill_card_by_version(
+    async fn get_featured_tools(
         &self,
         space: &str,
-        name: &str,
-        version: &str,
-    ) -> Result<SkillCardRecord, SqlError> {
+        limit: i64,
+    ) -> Result<Vec<ToolCardRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.card.get_featured_tools(space, limit).await,
+            SqlClientEnum::Sqlite(client) => client.card.get_featured_tools(space, limit).await,
+            SqlClientEnum::MySql(client) => client.card.get_featured_tools(space, limit).await,
+        }
+    }
+
+    async fn get_all_tool_tags(&self, space: &str) -> Result<Vec<String>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.card.get_all_tool_tags(space).await,
+            SqlClientEnum::Sqlite(client) => client.card.get_all_tool_tags(space).await,
+            SqlClientEnum::MySql(client) => client.card.get_all_tool_tags(space).await,
+        }
+    }
+
+    async fn get_tool_marketplace_stats(&self, space: &str) -> Result<MarketplaceStats, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.card.get_tool_marketplace_stats(space).await,
+            SqlClientEnum::Sqlite(client) => client.card.get_tool_marketplace_stats(space).await,
+            SqlClientEnum::MySql(client) => client.card.get_tool_marketplace_stats(space).await,
+        }
+    }
+}
+
+#[async_trait]
+impl AgentJobLogicTrait for SqlClientEnum {
+    async fn upsert_agent_job(&self, job: &AgentJobRecord) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.agent_job.upsert_agent_job(job).await,
+            SqlClientEnum::Sqlite(client) => client.agent_job.upsert_agent_job(job).await,
+            SqlClientEnum::MySql(client) => client.agent_job.upsert_agent_job(job).await,
+        }
+    }
+
+    async fn get_agent_job(&self, job_id: &str) -> Result<Option<AgentJobRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.agent_job.get_agent_job(job_id).await,
+            SqlClientEnum::Sqlite(client) => client.agent_job.get_agent_job(job_id).await,
+            SqlClientEnum::MySql(client) => client.agent_job.get_agent_job(job_id).await,
+        }
+    }
+
+    async fn fail_incomplete_agent_jobs(
+        &self,
+        error: &str,
+        instance_id: Option<&str>,
+        stale_before: &DateTime<Utc>,
+    ) -> Result<u64, SqlError> {
         match self {
             SqlClientEnum::Postgres(client) => {
                 client
-                    .card
-                    .get_skill_card_by_version(space, name, version)
+                    .agent_job
+                    .fail_incomplete_agent_jobs(error, instance_id, stale_before)
                     .await
             }
             SqlClientEnum::Sqlite(client) => {
                 client
-                    .card
-                    .get_skill_card_by_version(space, name, version)
+                    .agent_job
+                    .fail_incomplete_agent_jobs(error, instance_id, stale_before)
                     .await
             }
             SqlClientEnum::MySql(client) => {
                 client
-                    .card
-                    .get_skill_card_by_version(space, name, version)
+                    .agent_job
+                    .fail_incomplete_agent_jobs(error, instance_id, stale_before)
                     .await
             }
         }
     }
 
-    async fn increment_skill_download_count(&self, uid: &str) -> Result<(), SqlError> {
+    async fn heartbeat_agent_jobs(&self, instance_id: &str) -> Result<u64, SqlError> {
         match self {
             SqlClientEnum::Postgres(client) => {
-                client.card.increment_skill_download_count(uid).await
+                client.agent_job.heartbeat_agent_jobs(instance_id).await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client.agent_job.heartbeat_agent_jobs(instance_id).await
+            }
+            SqlClientEnum::MySql(client) => {
+                client.agent_job.heartbeat_agent_jobs(instance_id).await
             }
-            SqlClientEnum::Sqlite(client) => client.card.increment_skill_download_count(uid).await,
-            SqlClientEnum::MySql(client) => client.card.increment_skill_download_count(uid).await,
-        }
-    }
-
-    async fn list_skill_cards_by_space(
-        &self,
-        space: &str,
-    ) -> Result<Vec<SkillCardRecord>, SqlError> {
-        match self {
-            SqlClientEnum::Postgres(client) => client.card.list_skill_cards_by_space(space).await,
-            SqlClientEnum::Sqlite(client) => client.card.list_skill_cards_by_space(space).await,
-            SqlClientEnum::MySql(client) => client.card.list_skill_cards_by_space(space).await,
         }
     }
 
-    async fn get_featured_skills(&self, limit: i64) -> Result<Vec<SkillCardRecord>, SqlError> {
+    async fn delete_expired_agent_jobs(&self, cutoff: &DateTime<Utc>) -> Result<u64, SqlError> {
         match self {
-            SqlClientEnum::Postgres(client) => client.card.get_featured_skills(limit).await,
-            SqlClientEnum::Sqlite(client) => client.card.get_featured_skills(limit).await,
-            SqlClientEnum::MySql(client) => client.card.get_featured_skills(limit).await,
+            SqlClientEnum::Postgres(client) => {
+                client.agent_job.delete_expired_agent_jobs(cutoff).await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client.agent_job.delete_expired_agent_jobs(cutoff).await
+            }
+            SqlClientEnum::MySql(client) => {
+                client.agent_job.delete_expired_agent_jobs(cutoff).await
+            }
         }
     }
+}
 
-    async fn get_all_skill_tags(&self) -> Result<Vec<String>, SqlError> {
+#[async_trait]
+impl EventLogicTrait for SqlClientEnum {
+    async fn insert_registry_event(&self, event: &RegistryEvent) -> Result<(), SqlError> {
         match self {
-            SqlClientEnum::Postgres(client) => client.card.get_all_skill_tags().await,
-            SqlClientEnum::Sqlite(client) => client.card.get_all_skill_tags().await,
-            SqlClientEnum::MySql(client) => client.card.get_all_skill_tags().await,
+            SqlClientEnum::Postgres(client) => client.event.insert_registry_event(event).await,
+            SqlClientEnum::Sqlite(client) => client.event.insert_registry_event(event).await,
+            SqlClientEnum::MySql(client) => client.event.insert_registry_event(event).await,
         }
     }
 
-    async fn get_marketplace_stats(&self) -> Result<MarketplaceStats, SqlError> {
+    async fn get_registry_event(&self, event_id: &str) -> Result<Option<RegistryEvent>, SqlError> {
         match self {
-            SqlClientEnum::Postgres(client) => client.card.get_marketplace_stats().await,
-            SqlClientEnum::Sqlite(client) => client.card.get_marketplace_stats().await,
-            SqlClientEnum::MySql(client) => client.card.get_marketplace_stats().await,
+            SqlClientEnum::Postgres(client) => client.event.get_registry_event(event_id).await,
+            SqlClientEnum::Sqlite(client) => client.event.get_registry_event(event_id).await,
+            SqlClientEnum::MySql(client) => client.event.get_registry_event(event_id).await,
         }
     }
-}
 
-#[async_trait]
-impl SubAgentLogicTrait for SqlClientEnum {
-    async fn get_subagent_card_by_name(
+    async fn claim_undispatched_events(
         &self,
-        space: &str,
-        name: &str,
-    ) -> Result<SubAgentCardRecord, SqlError> {
+        claim_id: &str,
+        now: &DateTime<Utc>,
+        lease_until: &DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<RegistryEvent>, SqlError> {
         match self {
             SqlClientEnum::Postgres(client) => {
-                client.card.get_subagent_card_by_name(space, name).await
+                client
+                    .event
+                    .claim_undispatched_events(claim_id, now, lease_until, limit)
+                    .await
             }
             SqlClientEnum::Sqlite(client) => {
-                client.card.get_subagent_card_by_name(space, name).await
+                client
+                    .event
+                    .claim_undispatched_events(claim_id, now, lease_until, limit)
+                    .await
             }
             SqlClientEnum::MySql(client) => {
-                client.card.get_subagent_card_by_name(space, name).await
+                client
+                    .event
+                    .claim_undispatched_events(claim_id, now, lease_until, limit)
+                    .await
             }
         }
     }
 
-    async fn get_subagent_card_by_version(
+    async fn release_event_claim(&self, claim_id: &str) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.event.release_event_claim(claim_id).await,
+            SqlClientEnum::Sqlite(client) => client.event.release_event_claim(claim_id).await,
+            SqlClientEnum::MySql(client) => client.event.release_event_claim(claim_id).await,
+        }
+    }
+
+    async fn mark_event_dispatched(&self, event_id: &str) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.event.mark_event_dispatched(event_id).await,
+            SqlClientEnum::Sqlite(client) => client.event.mark_event_dispatched(event_id).await,
+            SqlClientEnum::MySql(client) => client.event.mark_event_dispatched(event_id).await,
+        }
+    }
+
+    async fn insert_webhook(&self, webhook: &WebhookSqlRecord) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.event.insert_webhook(webhook).await,
+            SqlClientEnum::Sqlite(client) => client.event.insert_webhook(webhook).await,
+            SqlClientEnum::MySql(client) => client.event.insert_webhook(webhook).await,
+        }
+    }
+
+    async fn get_webhook(&self, id: &str) -> Result<Option<WebhookSqlRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.event.get_webhook(id).await,
+            SqlClientEnum::Sqlite(client) => client.event.get_webhook(id).await,
+            SqlClientEnum::MySql(client) => client.event.get_webhook(id).await,
+        }
+    }
+
+    async fn list_webhooks(&self, space: &str) -> Result<Vec<WebhookSqlRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.event.list_webhooks(space).await,
+            SqlClientEnum::Sqlite(client) => client.event.list_webhooks(space).await,
+            SqlClientEnum::MySql(client) => client.event.list_webhooks(space).await,
+        }
+    }
+
+    async fn delete_webhook(&self, id: &str) -> Result<bool, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.event.delete_webhook(id).await,
+            SqlClientEnum::Sqlite(client) => client.event.delete_webhook(id).await,
+            SqlClientEnum::MySql(client) => client.event.delete_webhook(id).await,
+        }
+    }
+
+    async fn insert_webhook_delivery(
         &self,
-        space: &str,
-        name: &str,
-        version: &str,
-    ) -> Result<SubAgentCardRecord, SqlError> {
+        webhook_id: &str,
+        event_id: &str,
+        next_attempt_at: &DateTime<Utc>,
+    ) -> Result<(), SqlError> {
         match self {
             SqlClientEnum::Postgres(client) => {
                 client
-                    .card
-                    .get_subagent_card_by_version(space, name, version)
+                    .event
+                    .insert_webhook_delivery(webhook_id, event_id, next_attempt_at)
                     .await
             }
             SqlClientEnum::Sqlite(client) => {
                 client
-                    .card
-                    .get_subagent_card_by_version(space, name, version)
+                    .event
+                    .insert_webhook_delivery(webhook_id, event_id, next_attempt_at)
                     .await
             }
             SqlClientEnum::MySql(client) => {
                 client
-                    .card
-                    .get_subagent_card_by_version(space, name, version)
+                    .event
+                    .insert_webhook_delivery(webhook_id, event_id, next_attempt_at)
                     .await
             }
         }
     }
 
-    async fn increment_subagent_download_count(&self, uid: &str) -> Result<(), SqlError> {
+    async fn claim_due_webhook_deliveries(
+        &self,
+        claim_id: &str,
+        now: &DateTime<Utc>,
+        lease_until: &DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>, SqlError> {
         match self {
             SqlClientEnum::Postgres(client) => {
-                client.card.increment_subagent_download_count(uid).await
+                client
+                    .event
+                    .claim_due_webhook_deliveries(claim_id, now, lease_until, limit)
+                    .await
             }
             SqlClientEnum::Sqlite(client) => {
-                client.card.increment_subagent_download_count(uid).await
+                client
+                    .event
+                    .claim_due_webhook_deliveries(claim_id, now, lease_until, limit)
+                    .await
             }
             SqlClientEnum::MySql(client) => {
-                client.card.increment_subagent_download_count(uid).await
+                client
+                    .event
+                    .claim_due_webhook_deliveries(claim_id, now, lease_until, limit)
+                    .await
             }
         }
     }
 
-    async fn list_subagent_cards_by_space(
-        &self,
-        space: &str,
-    ) -> Result<Vec<SubAgentCardRecord>, SqlError> {
+    async fn update_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<(), SqlError> {
         match self {
-            SqlClientEnum::Postgres(client) => {
-                client.card.list_subagent_cards_by_space(space).await
-            }
-            SqlClientEnum::Sqlite(client) => client.card.list_subagent_cards_by_space(space).await,
-            SqlClientEnum::MySql(client) => client.card.list_subagent_cards_by_space(space).await,
+            SqlClientEnum::Postgres(client) => client.event.update_webhook_delivery(delivery).await,
+            SqlClientEnum::Sqlite(client) => client.event.update_webhook_delivery(delivery).await,
+            SqlClientEnum::MySql(client) => client.event.update_webhook_delivery(delivery).await,
         }
     }
 
-    async fn get_featured_subagents(
+    async fn list_webhook_deliveries(
         &self,
-        space: &str,
+        webhook_id: &str,
         limit: i64,
-    ) -> Result<Vec<SubAgentCardRecord>, SqlError> {
+    ) -> Result<Vec<WebhookDelivery>, SqlError> {
         match self {
             SqlClientEnum::Postgres(client) => {
-                client.card.get_featured_subagents(space, limit).await
+                client
+                    .event
+                    .list_webhook_deliveries(webhook_id, limit)
+                    .await
+            }
+            SqlClientEnum::Sqlite(client) => {
+                client
+                    .event
+                    .list_webhook_deliveries(webhook_id, limit)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .event
+                    .list_webhook_deliveries(webhook_id, limit)
+                    .await
             }
-            SqlClientEnum::Sqlite(client) => client.card.get_featured_subagents(space, limit).await,
-            SqlClientEnum::MySql(client) => client.card.get_featured_subagents(space, limit).await,
         }
     }
+}
 
-    async fn get_all_subagent_tags(&self, space: &str) -> Result<Vec<String>, SqlError> {
+#[async_trait]
+impl SearchLogicTrait for SqlClientEnum {
+    async fn upsert_search_document(&self, document: &CardSearchDocument) -> Result<(), SqlError> {
         match self {
-            SqlClientEnum::Postgres(client) => client.card.get_all_subagent_tags(space).await,
-            SqlClientEnum::Sqlite(client) => client.card.get_all_subagent_tags(space).await,
-            SqlClientEnum::MySql(client) => client.card.get_all_subagent_tags(space).await,
+            SqlClientEnum::Postgres(client) => client.search.upsert_search_document(document).await,
+            SqlClientEnum::Sqlite(client) => client.search.upsert_search_document(document).await,
+            SqlClientEnum::MySql(client) => client.search.upsert_search_document(document).await,
         }
     }
 
-    async fn get_subagent_marketplace_stats(
+    async fn get_search_readme(
         &self,
+        registry_type: &RegistryType,
         space: &str,
-    ) -> Result<MarketplaceStats, SqlError> {
+        name: &str,
+    ) -> Result<Option<String>, SqlError> {
         match self {
             SqlClientEnum::Postgres(client) => {
-                client.card.get_subagent_marketplace_stats(space).await
+                client
+                    .search
+                    .get_search_readme(registry_type, space, name)
+                    .await
             }
             SqlClientEnum::Sqlite(client) => {
-                client.card.get_subagent_marketplace_stats(space).await
+                client
+                    .search
+                    .get_search_readme(registry_type, space, name)
+                    .await
+            }
+            SqlClientEnum::MySql(client) => {
+                client
+                    .search
+                    .get_search_readme(registry_type, space, name)
+                    .await
             }
-            SqlClientEnum::MySql(client) => client.card.get_subagent_marketplace_stats(space).await,
-        }
-    }
-}
-
-#[async_trait]
-impl ToolLogicTrait for SqlClientEnum {
-    async fn get_tool_card_by_name(
-        &self,
-        space: &str,
-        name: &str,
-    ) -> Result<ToolCardRecord, SqlError> {
-        match self {
-            SqlClientEnum::Postgres(client) => client.card.get_tool_card_by_name(space, name).await,
-            SqlClientEnum::Sqlite(client) => client.card.get_tool_card_by_name(space, name).await,
-            SqlClientEnum::MySql(client) => client.card.get_tool_card_by_name(space, name).await,
         }
     }
 
-    async fn get_tool_card_by_version(
+    async fn update_search_readme(
         &self,
+        registry_type: &RegistryType,
         space: &str,
         name: &str,
-        version: &str,
-    ) -> Result<ToolCardRecord, SqlError> {
+        readme: &str,
+    ) -> Result<(), SqlError> {
         match self {
             SqlClientEnum::Postgres(client) => {
                 client
-                    .card
-                    .get_tool_card_by_version(space, name, version)
+                    .search
+                    .update_search_readme(registry_type, space, name, readme)
                     .await
             }
             SqlClientEnum::Sqlite(client) => {
                 client
-                    .card
-                    .get_tool_card_by_version(space, name, version)
+                    .search
+                    .update_search_readme(registry_type, space, name, readme)
                     .await
             }
             SqlClientEnum::MySql(client) => {
                 client
-                    .card
-                    .get_tool_card_by_version(space, name, version)
+                    .search
+                    .update_search_readme(registry_type, space, name, readme)
                     .await
             }
         }
     }
 
-    async fn increment_tool_download_count(&self, uid: &str) -> Result<(), SqlError> {
+    async fn delete_search_document(&self, uid: &str) -> Result<(), SqlError> {
         match self {
-            SqlClientEnum::Postgres(client) => client.card.increment_tool_download_count(uid).await,
-            SqlClientEnum::Sqlite(client) => client.card.increment_tool_download_count(uid).await,
-            SqlClientEnum::MySql(client) => client.card.increment_tool_download_count(uid).await,
+            SqlClientEnum::Postgres(client) => client.search.delete_search_document(uid).await,
+            SqlClientEnum::Sqlite(client) => client.search.delete_search_document(uid).await,
+            SqlClientEnum::MySql(client) => client.search.delete_search_document(uid).await,
         }
     }
 
-    async fn list_tool_cards_by_space(
+    async fn search_cards(
         &self,
-        space: &str,
-        tool_type: Option<&str>,
-    ) -> Result<Vec<ToolCardRecord>, SqlError> {
+        query: &CardSearchQuery,
+        spaces: Option<&[String]>,
+    ) -> Result<Vec<CardSearchHit>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.search.search_cards(query, spaces).await,
+            SqlClientEnum::Sqlite(client) => client.search.search_cards(query, spaces).await,
+            SqlClientEnum::MySql(client) => client.search.search_cards(query, spaces).await,
+        }
+    }
+}
+
+#[async_trait]
+impl TrashLogicTrait for SqlClientEnum {
+    async fn insert_trash_record(&self, record: &TrashSqlRecord) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.trash.insert_trash_record(record).await,
+            SqlClientEnum::Sqlite(client) => client.trash.insert_trash_record(record).await,
+            SqlClientEnum::MySql(client) => client.trash.insert_trash_record(record).await,
+        }
+    }
+
+    async fn get_trash_record(&self, uid: &str) -> Result<Option<TrashSqlRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.trash.get_trash_record(uid).await,
+            SqlClientEnum::Sqlite(client) => client.trash.get_trash_record(uid).await,
+            SqlClientEnum::MySql(client) => client.trash.get_trash_record(uid).await,
+        }
+    }
+
+    async fn list_trash(&self, query: &TrashQuery) -> Result<Vec<TrashSqlRecord>, SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.trash.list_trash(query).await,
+            SqlClientEnum::Sqlite(client) => client.trash.list_trash(query).await,
+            SqlClientEnum::MySql(client) => client.trash.list_trash(query).await,
+        }
+    }
+
+    async fn delete_trash_record(&self, uid: &str) -> Result<(), SqlError> {
+        match self {
+            SqlClientEnum::Postgres(client) => client.trash.delete_trash_record(uid).await,
+            SqlClientEnum::Sqlite(client) => client.trash.delete_trash_record(uid).await,
+            SqlClientEnum::MySql(client) => client.trash.delete_trash_record(uid).await,
+        }
+    }
+
+    async fn upsert_retention_policy(&self, policy: &RetentionPolicy) -> Result<(), SqlError> {
         match self {
-            SqlClientEnum::Postgres(client) => {
-                client.card.list_tool_cards_by_space(space, tool_type).await
-            }
-            SqlClientEnum::Sqlite(client) => {
-                client.card.list_tool_cards_by_space(space, tool_type).await
-            }
-            SqlClientEnum::MySql(client) => {
-                client.card.list_tool_cards_by_space(space, tool_type).await
-            }
+            SqlClientEnum::Postgres(client) => client.trash.upsert_retention_policy(policy).await,
+            SqlClientEnum::Sqlite(client) => client.trash.upsert_retention_policy(policy).await,
+            SqlClientEnum::MySql(client) => client.trash.upsert_retention_policy(policy).await,
         }
     }
 
-    async fn get_featured_tools(
-        &self,
-        space: &str,
-        limit: i64,
-    ) -> Result<Vec<ToolCardRecord>, SqlError> {
+    async fn get_retention_policy(&self, space: &str) -> Result<Option<RetentionPolicy>, SqlError> {
         match self {
-            SqlClientEnum::Postgres(client) => client.card.get_featured_tools(space, limit).await,
-            SqlClientEnum::Sqlite(client) => client.card.get_featured_tools(space, limit).await,
-            SqlClientEnum::MySql(client) => client.card.get_featured_tools(space, limit).await,
+            SqlClientEnum::Postgres(client) => client.trash.get_retention_policy(space).await,
+            SqlClientEnum::Sqlite(client) => client.trash.get_retention_policy(space).await,
+            SqlClientEnum::MySql(client) => client.trash.get_retention_policy(space).await,
         }
     }
 
-    async fn get_all_tool_tags(&self, space: &str) -> Result<Vec<String>, SqlError> {
+    async fn get_retention_policies(&self) -> Result<Vec<RetentionPolicy>, SqlError> {
         match self {
-            SqlClientEnum::Postgres(client) => client.card.get_all_tool_tags(space).await,
-            SqlClientEnum::Sqlite(client) => client.card.get_all_tool_tags(space).await,
-            SqlClientEnum::MySql(client) => client.card.get_all_tool_tags(space).await,
+            SqlClientEnum::Postgres(client) => client.trash.get_retention_policies().await,
+            SqlClientEnum::Sqlite(client) => client.trash.get_retention_policies().await,
+            SqlClientEnum::MySql(client) => client.trash.get_retention_policies().await,
         }
     }
 
-    async fn get_tool_marketplace_stats(&self, space: &str) -> Result<MarketplaceStats, SqlError> {
+    async fn delete_retention_policy(&self, space: &str) -> Result<bool, SqlError> {
         match self {
-            SqlClientEnum::Postgres(client) => client.card.get_tool_marketplace_stats(space).await,
-            SqlClientEnum::Sqlite(client) => client.card.get_tool_marketplace_stats(space).await,
-            SqlClientEnum::MySql(client) => client.card.get_tool_marketplace_stats(space).await,
+            SqlClientEnum::Postgres(client) => client.trash.delete_retention_policy(space).await,
+            SqlClientEnum::Sqlite(client) => client.trash.delete_retention_policy(space).await,
+            SqlClientEnum::MySql(client) => client.trash.delete_retention_policy(space).await,
         }
     }
 }
@@ -970,11 +2180,14 @@ pub async fn get_sql_client(db_settings: &DatabaseSettings) -> AnyhowResult<SqlC
 mod tests {
 
     use super::*;
+    use crate::enums::utils::{get_retention_candidates, restore_card, trash_card};
     use crate::schemas::schema::{
-        AuditCardRecord, DataCardRecord, ExperimentCardRecord, ModelCardRecord,
+        AuditCardRecord, DataCardRecord, ExperimentCardRecord, ModelCardRecord, ServiceCardRecord,
     };
+    use opsml_types::contracts::{CardEntry, LineageRelation, TrashReason};
     use opsml_types::{CommonKwargs, RegistryType};
     use opsml_utils::utils::get_utc_datetime;
+    use sqlx::types::Json;
 
     use std::env;
 
@@ -1092,6 +2305,52 @@ mod tests {
             .unwrap();
         assert_eq!(versions.len(), 2);
 
+        // compound range expressions
+        let versions = client
+            .get_versions(
+                &CardTable::Data,
+                "repo1",
+                "Data1",
+                Some(">=1.1.0, <3.0.0".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 6);
+
+        let versions = client
+            .get_versions(
+                &CardTable::Data,
+                "repo1",
+                "Data1",
+                Some(">=2.0.0, <3.0.0, !=2.0.1".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 3);
+
+        let versions = client
+            .get_versions(
+                &CardTable::Data,
+                "repo1",
+                "Data1",
+                Some("<1.1 || >=3.0.0".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(versions.len(), 4);
+
+        let card_args = CardQueryArgs {
+            name: Some("Data1".to_string()),
+            space: Some("repo1".to_string()),
+            version: Some(">1.1, <=2.0".to_string()),
+            ..Default::default()
+        };
+        let results = client
+            .query_cards(&CardTable::Data, &card_args)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+
         cleanup();
     }
 
@@ -1740,6 +2999,7 @@ mod tests {
         let space_record = SpaceRecord {
             space: CommonKwargs::Undefined.to_string(),
             description: "Space description".to_string(),
+            ..Default::default()
         };
 
         client.insert_space_record(&space_record).await.unwrap();
@@ -1802,6 +3062,7 @@ mod tests {
         let updated_space_record = SpaceRecord {
             space: model_card2.space.clone(),
             description: "Updated Space description".to_string(),
+            ..Default::default()
         };
         client
             .update_space_record(&updated_space_record)
@@ -1836,4 +3097,445 @@ mod tests {
 
         cleanup();
     }
+
+    #[tokio::test]
+    async fn test_enum_space_usage_quota() {
+        let client = get_client().await;
+
+        // quotas can be set on a space without a record
+        client
+            .set_space_quota("space1", Some(100), Some(200))
+            .await
+            .unwrap();
+        let record = client.get_space_record("space1").await.unwrap().unwrap();
+        assert_eq!(record.soft_quota_bytes, Some(100));
+        assert_eq!(record.hard_quota_bytes, Some(200));
+
+        client
+            .add_space_usage("space1", &RegistryType::Model, 150)
+            .await
+            .unwrap();
+        client
+            .add_space_usage("space1", &RegistryType::Data, 25)
+            .await
+            .unwrap();
+        client
+            .add_space_usage("space1", &RegistryType::Model, -50)
+            .await
+            .unwrap();
+        assert_eq!(client.get_space_bytes("space1").await.unwrap(), 125);
+        assert_eq!(client.get_space_bytes("space2").await.unwrap(), 0);
+
+        // releasing more than was recorded shows up as drift until usage is recounted
+        client
+            .add_space_usage("space1", &RegistryType::Data, -100)
+            .await
+            .unwrap();
+        assert_eq!(client.get_space_bytes("space1").await.unwrap(), 25);
+        client
+            .set_space_usage("space1", &RegistryType::Data, 0)
+            .await
+            .unwrap();
+
+        let usage = client.get_space_usage().await.unwrap();
+        assert_eq!(
+            usage,
+            vec![
+                SpaceRegistryUsage {
+                    space: "space1".to_string(),
+                    registry_type: RegistryType::Data,
+                    bytes: 0,
+                },
+                SpaceRegistryUsage {
+                    space: "space1".to_string(),
+                    registry_type: RegistryType::Model,
+                    bytes: 100,
+                },
+            ]
+        );
+
+        // reservations only succeed while the space stays within the hard quota
+        assert!(
+            client
+                .reserve_space_usage("space1", &RegistryType::Data, 100, 200)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !client
+                .reserve_space_usage("space1", &RegistryType::Model, 1, 200)
+                .await
+                .unwrap()
+        );
+        assert_eq!(client.get_space_bytes("space1").await.unwrap(), 200);
+        client
+            .add_space_usage("space1", &RegistryType::Data, -100)
+            .await
+            .unwrap();
+
+        // upload reservations add up per path until they are taken
+        let data_path = "opsml_data_registry/space1/data/v1.0.0/data.parquet";
+        for bytes in [10, 5] {
+            client
+                .insert_upload_reservation(data_path, "space1", &RegistryType::Data, bytes)
+                .await
+                .unwrap();
+        }
+        client
+            .insert_upload_reservation(
+                "opsml_model_registry/space1/model/v1.0.0/model.onnx",
+                "space1",
+                &RegistryType::Model,
+                7,
+            )
+            .await
+            .unwrap();
+
+        let reservations = client.get_upload_reservations().await.unwrap();
+        assert_eq!(reservations.len(), 2);
+        assert_eq!(reservations[0].registry_type, RegistryType::Data);
+        assert_eq!(reservations[0].bytes, 15);
+
+        assert_eq!(
+            client.take_upload_reservation(data_path).await.unwrap(),
+            Some(15)
+        );
+        assert_eq!(
+            client.take_upload_reservation(data_path).await.unwrap(),
+            None
+        );
+
+        // reservations of uploads that were never completed can be purged
+        let purged = client
+            .delete_stale_upload_reservations(&(Utc::now() + chrono::Duration::hours(1)))
+            .await
+            .unwrap();
+        assert_eq!(purged, 1);
+        assert!(client.get_upload_reservations().await.unwrap().is_empty());
+
+        // clearing the hard quota keeps the soft quota
+        client
+            .set_space_quota("space1", Some(100), None)
+            .await
+            .unwrap();
+        let record = client.get_space_record("space1").await.unwrap().unwrap();
+        assert_eq!(record.soft_quota_bytes, Some(100));
+        assert_eq!(record.hard_quota_bytes, None);
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_enum_version_alias() {
+        let client = get_client().await;
+
+        let card_args = CardQueryArgs {
+            name: Some("Data1".to_string()),
+            space: Some("repo1".to_string()),
+            registry_type: RegistryType::Data,
+            ..Default::default()
+        };
+
+        let target = match client
+            .query_cards(&CardTable::Data, &card_args)
+            .await
+            .unwrap()
+        {
+            CardResults::Data(cards) => cards[0].clone(),
+            _ => panic!("Expected data cards"),
+        };
+
+        let alias_args = CardQueryArgs {
+            version: Some("@production".to_string()),
+            ..card_args.clone()
+        };
+
+        // unknown alias
+        let err = client
+            .query_cards(&CardTable::Data, &alias_args)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SqlError::VersionAliasNotFound(_)));
+
+        let alias = CardAlias {
+            space: "repo1".to_string(),
+            name: "Data1".to_string(),
+            registry_type: RegistryType::Data,
+            alias: "production".to_string(),
+            uid: target.uid.clone(),
+            version: target.version.clone(),
+            updated_by: "guest".to_string(),
+            updated_at: get_utc_datetime(),
+        };
+        client.upsert_card_alias(&alias).await.unwrap();
+
+        let results = client
+            .query_cards(&CardTable::Data, &alias_args)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        match results {
+            CardResults::Data(cards) => assert_eq!(cards[0].uid, target.uid),
+            _ => panic!("Expected data cards"),
+        }
+
+        let aliases = client
+            .get_card_aliases("repo1", "Data1", &RegistryType::Data)
+            .await
+            .unwrap();
+        assert_eq!(aliases.len(), 1);
+        assert_eq!(aliases[0].version, alias.version);
+
+        assert!(
+            client
+                .delete_card_alias("repo1", "Data1", &RegistryType::Data, "production")
+                .await
+                .unwrap()
+        );
+        assert!(
+            !client
+                .delete_card_alias("repo1", "Data1", &RegistryType::Data, "production")
+                .await
+                .unwrap()
+        );
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_enum_trash_restore() {
+        let client = get_client().await;
+
+        let card_args = CardQueryArgs {
+            name: Some("Data1".to_string()),
+            space: Some("repo1".to_string()),
+            registry_type: RegistryType::Data,
+            ..Default::default()
+        };
+        let (uid, version) = match client
+            .query_cards(&CardTable::Data, &card_args)
+            .await
+            .unwrap()
+        {
+            CardResults::Data(cards) => (cards[0].uid.clone(), cards[0].version.clone()),
+            _ => panic!("Expected data cards"),
+        };
+
+        let record = trash_card(
+            &client,
+            &RegistryType::Data,
+            &uid,
+            TrashReason::Deleted,
+            "guest",
+        )
+        .await
+        .unwrap();
+        assert_eq!(record.version, version);
+
+        let uid_args = CardQueryArgs {
+            uid: Some(uid.clone()),
+            registry_type: RegistryType::Data,
+            ..Default::default()
+        };
+        let results = client
+            .query_cards(&CardTable::Data, &uid_args)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 0);
+
+        let trashed = client
+            .list_trash(&TrashQuery {
+                space: Some("repo1".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].uid, uid);
+
+        restore_card(&client, &uid).await.unwrap();
+        let results = client
+            .query_cards(&CardTable::Data, &uid_args)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(client.get_trash_record(&uid).await.unwrap().is_none());
+
+        let err = restore_card(&client, &uid).await.unwrap_err();
+        assert!(matches!(err, SqlError::TrashRecordNotFound(_)));
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_enum_retention_candidates() {
+        let client = get_client().await;
+
+        let card_args = CardQueryArgs {
+            name: Some("Data1".to_string()),
+            space: Some("repo1".to_string()),
+            limit: Some(100),
+            registry_type: RegistryType::Data,
+            ..Default::default()
+        };
+        // newest version first
+        let uids: Vec<String> = client
+            .query_cards(&CardTable::Data, &card_args)
+            .await
+            .unwrap()
+            .uid_versions()
+            .iter()
+            .map(|(uid, _)| uid.to_string())
+            .collect();
+        assert_eq!(uids.len(), 10);
+
+        // pin an old version behind an alias
+        let pinned = uids[5].clone();
+        let alias = CardAlias {
+            space: "repo1".to_string(),
+            name: "Data1".to_string(),
+            registry_type: RegistryType::Data,
+            alias: "production".to_string(),
+            uid: pinned.clone(),
+            version: String::new(),
+            updated_by: "guest".to_string(),
+            updated_at: get_utc_datetime(),
+        };
+        client.upsert_card_alias(&alias).await.unwrap();
+
+        let mut policy = RetentionPolicy::new("repo1");
+        policy.keep_last_versions = Some(3);
+        client.upsert_retention_policy(&policy).await.unwrap();
+        let stored = client.get_retention_policy("repo1").await.unwrap().unwrap();
+        assert_eq!(stored.keep_last_versions, Some(3));
+        assert!(stored.protect_aliased);
+
+        let candidates: Vec<String> = get_retention_candidates(&client, &stored)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|(registry_type, _)| *registry_type == RegistryType::Data)
+            .map(|(_, uid)| uid)
+            .collect();
+        assert_eq!(candidates.len(), 6);
+        assert!(!candidates.contains(&pinned));
+        assert!(uids[..3].iter().all(|uid| !candidates.contains(uid)));
+
+        assert!(client.delete_retention_policy("repo1").await.unwrap());
+        assert!(client.get_retention_policies().await.unwrap().is_empty());
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_enum_lineage_graph() {
+        let client = get_client().await;
+
+        let experiment = ExperimentCardRecord::default();
+        let data = DataCardRecord {
+            experimentcard_uid: Some(experiment.uid.clone()),
+            ..Default::default()
+        };
+        let model = ModelCardRecord {
+            datacard_uid: Some(data.uid.clone()),
+            // dangling references are dropped from the graph
+            auditcard_uid: Some("missing-audit".to_string()),
+            ..Default::default()
+        };
+        let service = ServiceCardRecord {
+            cards: Json(vec![CardEntry {
+                registry_type: RegistryType::Model,
+                uid: Some(model.uid.clone()),
+                version: Some(model.version.clone()),
+                alias: "model".to_string(),
+            }]),
+            ..Default::default()
+        };
+
+        client
+            .insert_card(
+                &CardTable::Experiment,
+                &ServerCard::Experiment(experiment.clone()),
+            )
+            .await
+            .unwrap();
+        client
+            .insert_card(&CardTable::Data, &ServerCard::Data(data.clone()))
+            .await
+            .unwrap();
+        client
+            .insert_card(&CardTable::Model, &ServerCard::Model(model.clone()))
+            .await
+            .unwrap();
+        client
+            .insert_card(
+                &CardTable::Service,
+                &ServerCard::Service(Box::new(service.clone())),
+            )
+            .await
+            .unwrap();
+
+        // which services deploy a model trained on this data
+        let graph = client
+            .get_lineage_graph(&LineageQuery {
+                direction: LineageDirection::Downstream,
+                ..LineageQuery::new(&data.uid)
+            })
+            .await
+            .unwrap();
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(graph.edges.len(), 2);
+        let deployed = graph
+            .edges
+            .iter()
+            .find(|edge| edge.relation == LineageRelation::DeployedIn)
+            .unwrap();
+        assert_eq!(deployed.source_uid, model.uid);
+        assert_eq!(deployed.target_uid, service.uid);
+        assert_eq!(deployed.target_type, RegistryType::Service);
+
+        // one hop in both directions picks up the experiment but not the service
+        let graph = client
+            .get_lineage_graph(&LineageQuery {
+                registry_type: Some(RegistryType::Data),
+                depth: 1,
+                ..LineageQuery::new(&data.uid)
+            })
+            .await
+            .unwrap();
+        let uids = graph
+            .nodes
+            .iter()
+            .map(|node| node.uid.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            uids,
+            vec![
+                data.uid.as_str(),
+                experiment.uid.as_str(),
+                model.uid.as_str()
+            ]
+        );
+
+        // upstream from the service walks back to the experiment
+        let graph = client
+            .get_lineage_graph(&LineageQuery {
+                direction: LineageDirection::Upstream,
+                depth: 5,
+                ..LineageQuery::new(&service.uid)
+            })
+            .await
+            .unwrap();
+        assert_eq!(graph.nodes.len(), 4);
+        assert_eq!(graph.nodes[3].registry_type, RegistryType::Experiment);
+        assert_eq!(graph.nodes[3].depth, 3);
+
+        let err = client
+            .get_lineage_graph(&LineageQuery::new("not-a-card"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SqlError::LineageRootNotFound(_)));
+
+        cleanup();
+    }
 }
@@ -1,12 +1,60 @@
 use crate::enums::client::SqlClientEnum;
 use crate::error::SqlError;
-use crate::traits::CardLogicTrait;
+use crate::schemas::schema::{ServerCard, TrashSqlRecord};
+use crate::traits::{
+    AliasLogicTrait, ArtifactLogicTrait, CardLogicTrait, SearchLogicTrait, TrashLogicTrait,
+};
+use opsml_crypt::KeyProvider;
 use opsml_semver::{VersionArgs, VersionValidator};
+use opsml_types::RegistryType;
 use opsml_types::cards::CardTable;
 use opsml_types::contracts::*;
 use semver::Version;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use tracing::{error, instrument};
+use tracing::{error, instrument, warn};
+
+/// Card registries covered by retention policies
+const RETENTION_REGISTRIES: [RegistryType; 11] = [
+    RegistryType::Data,
+    RegistryType::Model,
+    RegistryType::Experiment,
+    RegistryType::Audit,
+    RegistryType::Prompt,
+    RegistryType::Service,
+    RegistryType::Mcp,
+    RegistryType::Agent,
+    RegistryType::Skill,
+    RegistryType::SubAgent,
+    RegistryType::Tool,
+];
+
+/// Versions of a card that sit in the trash. They keep their storage prefix until they are purged
+/// or restored, so they are never handed out again
+async fn trashed_versions(
+    sql_client: &SqlClientEnum,
+    table: &CardTable,
+    space: &str,
+    name: &str,
+) -> Result<HashSet<Version>, SqlError> {
+    let query = TrashQuery {
+        space: Some(space.to_string()),
+        ..Default::default()
+    };
+
+    let mut versions = HashSet::new();
+    for record in sql_client.list_trash(&query).await? {
+        if record.name != name
+            || CardTable::from_registry_type(&RegistryType::from_string(&record.registry_type)?)
+                != *table
+        {
+            continue;
+        }
+        versions.insert(VersionValidator::clean_version(&record.version)?);
+    }
+
+    Ok(versions)
+}
 
 #[instrument(skip_all)]
 pub async fn get_next_version(
@@ -23,28 +71,288 @@ pub async fn get_next_version(
         )
         .await?;
 
-    if versions.is_empty() {
-        return match &request.version {
+    let bump = |version: String| {
+        let args = VersionArgs {
+            version,
+            version_type: request.version_type.clone(),
+            pre: request.pre_tag.clone(),
+            build: request.build_tag.clone(),
+        };
+
+        VersionValidator::bump_version(&args).map_err(|e| {
+            error!("Failed to bump version: {e}");
+            SqlError::from(e)
+        })
+    };
+
+    let mut version = match versions.first() {
+        // Get the latest version as base for bumping
+        Some(latest) => bump(latest.to_string())?,
+        None => match &request.version {
             Some(version_str) => VersionValidator::clean_version(version_str).map_err(|e| {
                 error!("Invalid version format: {e}");
-                e.into()
-            }),
-            None => Ok(Version::new(0, 1, 0)),
-        };
+                SqlError::from(e)
+            })?,
+            None => Version::new(0, 1, 0),
+        },
+    };
+
+    // registering a trashed version again would share its storage prefix, which purging the
+    // trashed card later deletes
+    let reserved = trashed_versions(&sql_client, table, &request.space, &request.name).await?;
+    while reserved.contains(&version) {
+        let next = bump(version.to_string())?;
+        if next == version {
+            return Err(SqlError::VersionInTrash(format!(
+                "{}/{} v{}",
+                request.space, request.name, version
+            )));
+        }
+        version = next;
     }
 
-    // Get the latest version as base for bumping
-    let base_version = versions.first().unwrap().to_string();
+    Ok(version)
+}
 
-    let args = VersionArgs {
-        version: base_version,
-        version_type: request.version_type,
-        pre: request.pre_tag,
-        build: request.build_tag,
+/// Search cards, keeping the best matching version of each card. `readable_spaces` limits the
+/// search to the spaces the caller can read; `None` searches every space.
+///
+/// Every version of a card is indexed, so ranked versions are fetched a page at a time until
+/// `limit` distinct cards are collected or the matches run out
+#[instrument(skip_all)]
+pub async fn search_readable_cards(
+    sql_client: &SqlClientEnum,
+    query: &CardSearchQuery,
+    readable_spaces: Option<&[String]>,
+) -> Result<CardSearchResponse, SqlError> {
+    let limit = query.limit();
+    if readable_spaces.is_some_and(|spaces| spaces.is_empty()) {
+        return Ok(CardSearchResponse::from_hits(Vec::new(), limit));
+    }
+
+    let mut page = CardSearchQuery {
+        limit: Some(MAX_SEARCH_LIMIT),
+        offset: Some(0),
+        ..query.clone()
     };
+    let mut hits = Vec::new();
+    let mut cards = HashSet::new();
+
+    loop {
+        let batch = sql_client.search_cards(&page, readable_spaces).await?;
+        let exhausted = batch.len() < MAX_SEARCH_LIMIT;
+
+        for hit in batch {
+            cards.insert((
+                hit.registry_type.clone(),
+                hit.space.clone(),
+                hit.name.clone(),
+            ));
+            hits.push(hit);
+        }
+
+        if exhausted || cards.len() >= limit {
+            return Ok(CardSearchResponse::from_hits(hits, limit));
+        }
+        page.offset = page.offset.map(|offset| offset + MAX_SEARCH_LIMIT);
+    }
+}
+
+/// Default number of artifact keys re-wrapped per batch
+const DEFAULT_REWRAP_BATCH_SIZE: i64 = 500;
+
+/// Re-wrap every stored artifact key that is not wrapped with the provider's current KEK.
+///
+/// Only the wrapped data keys change, so artifacts do not need to be re-encrypted. Keys that are
+/// modified concurrently are skipped and picked up by the next rotation
+#[instrument(skip_all)]
+pub async fn rewrap_artifact_keys(
+    sql_client: &SqlClientEnum,
+    key_provider: &dyn KeyProvider,
+    batch_size: Option<i64>,
+) -> Result<RotateArtifactKeysResponse, SqlError> {
+    let batch_size = batch_size
+        .filter(|size| *size > 0)
+        .unwrap_or(DEFAULT_REWRAP_BATCH_SIZE);
+    let key_version = key_provider.current_version();
+    let mut rewrapped = 0u64;
+    let mut skipped = 0u64;
+    let mut after_uid = String::new();
+
+    loop {
+        let keys = sql_client
+            .get_artifact_keys_to_rewrap(key_version, &after_uid, batch_size)
+            .await?;
+
+        let Some(last) = keys.last() else {
+            break;
+        };
+        after_uid = last.uid.clone();
+
+        for key in keys {
+            let sealed = key.seal(key_provider)?;
 
-    VersionValidator::bump_version(&args).map_err(|e| {
-        error!("Failed to bump version: {e}");
-        e.into()
+            if sql_client
+                .rewrap_artifact_key(&sealed, key.key_version)
+                .await?
+            {
+                rewrapped += 1;
+            } else {
+                warn!("Artifact key {} changed during rotation, skipping", key.uid);
+                skipped += 1;
+            }
+        }
+    }
+
+    Ok(RotateArtifactKeysResponse {
+        key_version,
+        rewrapped,
+        skipped,
     })
 }
+
+/// Move a card to the trash.
+///
+/// The registry row is copied into the trash and then removed from its table. Artifacts, the
+/// artifact key and the search document are left in place so the card can be restored until it
+/// is purged
+#[instrument(skip_all)]
+pub async fn trash_card(
+    sql_client: &SqlClientEnum,
+    registry_type: &RegistryType,
+    uid: &str,
+    reason: TrashReason,
+    deleted_by: &str,
+) -> Result<TrashSqlRecord, SqlError> {
+    let table = CardTable::from_registry_type(registry_type);
+    let args = CardQueryArgs {
+        uid: Some(uid.to_string()),
+        registry_type: registry_type.clone(),
+        ..Default::default()
+    };
+    let card = sql_client
+        .query_cards(&table, &args)
+        .await?
+        .into_server_cards()
+        .into_iter()
+        .next()
+        .ok_or(SqlError::SqlxError(sqlx::Error::RowNotFound))?;
+
+    let record = TrashSqlRecord::new(&card, registry_type, reason, deleted_by)?;
+    sql_client.insert_trash_record(&record).await?;
+
+    if let Err(e) = sql_client.delete_card(&table, uid).await {
+        // keep the card in its registry rather than listing it in both places
+        sql_client.delete_trash_record(uid).await?;
+        return Err(e);
+    }
+
+    Ok(record)
+}
+
+/// Put a trashed card back into its registry.
+///
+/// Fails with `RestoreConflict` if the same version has been registered again since the card was
+/// trashed
+#[instrument(skip_all)]
+pub async fn restore_card(
+    sql_client: &SqlClientEnum,
+    uid: &str,
+) -> Result<TrashSqlRecord, SqlError> {
+    let record = sql_client
+        .get_trash_record(uid)
+        .await?
+        .ok_or_else(|| SqlError::TrashRecordNotFound(uid.to_string()))?;
+    let registry_type = RegistryType::from_string(&record.registry_type)?;
+    let table = CardTable::from_registry_type(&registry_type);
+
+    let args = CardQueryArgs {
+        space: Some(record.space.clone()),
+        name: Some(record.name.clone()),
+        version: Some(record.version.clone()),
+        registry_type: registry_type.clone(),
+        ..Default::default()
+    };
+    let existing = sql_client.query_cards(&table, &args).await?;
+    if existing
+        .uid_versions()
+        .iter()
+        .any(|(_, version)| *version == record.version)
+    {
+        return Err(SqlError::RestoreConflict(format!(
+            "{}/{} v{}",
+            record.space, record.name, record.version
+        )));
+    }
+
+    let card = ServerCard::from_json(&table, record.record.0.clone())?;
+    sql_client.insert_card(&table, &card).await?;
+    sql_client.delete_trash_record(uid).await?;
+
+    Ok(record)
+}
+
+/// Uids of card versions a `keep_last_versions` policy would move to the trash.
+///
+/// Versions are ranked per card by semver, newest first. Versions a stage alias points to are
+/// kept when the policy protects aliases, and do not count towards the versions kept
+#[instrument(skip_all)]
+pub async fn get_retention_candidates(
+    sql_client: &SqlClientEnum,
+    policy: &RetentionPolicy,
+) -> Result<Vec<(RegistryType, String)>, SqlError> {
+    let Some(keep) = policy.keep_last_versions else {
+        return Ok(Vec::new());
+    };
+    let keep = keep as usize;
+    let mut candidates = Vec::new();
+
+    for registry_type in RETENTION_REGISTRIES.iter() {
+        let table = CardTable::from_registry_type(registry_type);
+        let args = CardQueryArgs {
+            space: Some(policy.space.clone()),
+            limit: Some(i32::MAX),
+            registry_type: registry_type.clone(),
+            ..Default::default()
+        };
+
+        // query results are sorted newest version first
+        let mut versions_by_name: HashMap<String, Vec<String>> = HashMap::new();
+        for card in sql_client
+            .query_cards(&table, &args)
+            .await?
+            .into_server_cards()
+        {
+            versions_by_name
+                .entry(card.name())
+                .or_default()
+                .push(card.uid().to_string());
+        }
+
+        for (name, uids) in versions_by_name {
+            if uids.len() <= keep {
+                continue;
+            }
+
+            let aliased: HashSet<String> = if policy.protect_aliased {
+                sql_client
+                    .get_card_aliases(&policy.space, &name, registry_type)
+                    .await?
+                    .into_iter()
+                    .map(|alias| alias.uid)
+                    .collect()
+            } else {
+                HashSet::new()
+            };
+
+            candidates.extend(
+                uids.into_iter()
+                    .filter(|uid| !aliased.contains(uid))
+                    .skip(keep)
+                    .map(|uid| (registry_type.clone(), uid)),
+            );
+        }
+    }
+
+    Ok(candidates)
+}
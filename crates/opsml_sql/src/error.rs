@@ -40,6 +40,36 @@ pub enum SqlError {
 
     #[error("Missing required field: {0}")]
     MissingField(String),
+
+    #[error("Version alias not found: {0}")]
+    VersionAliasNotFound(String),
+
+    #[error("Invalid lineage relation: {0}")]
+    InvalidLineageRelation(String),
+
+    #[error("Card not found for lineage: {0}")]
+    LineageRootNotFound(String),
+
+    #[error("Invalid agent job status: {0}")]
+    InvalidJobStatus(String),
+
+    #[error("Invalid registry event type: {0}")]
+    InvalidEventType(String),
+
+    #[error("Invalid webhook delivery status: {0}")]
+    InvalidDeliveryStatus(String),
+
+    #[error("Invalid trash reason: {0}")]
+    InvalidTrashReason(String),
+
+    #[error("Card not found in trash: {0}")]
+    TrashRecordNotFound(String),
+
+    #[error("Cannot restore {0}: the version has been registered again")]
+    RestoreConflict(String),
+
+    #[error("Version {0} is in the trash. Restore or purge it before registering it again")]
+    VersionInTrash(String),
 }
 
 impl SqlError {
@@ -1,9 +1,12 @@
 use crate::{
     error::SqlError,
     mysql::sql::{
+        agent_job::AgentJobLogicMySqlClient, alias::AliasLogicMySqlClient,
         artifact::ArtifactLogicMySqlClient, audit::AuditLogicMySqlClient,
         card::CardLogicMySqlClient, evaluation::EvaluationLogicMySqlClient,
-        experiment::ExperimentLogicMySqlClient, space::SpaceLogicMySqlClient,
+        event::EventLogicMySqlClient, experiment::ExperimentLogicMySqlClient,
+        group::GroupLogicMySqlClient, lineage::LineageLogicMySqlClient,
+        search::SearchLogicMySqlClient, space::SpaceLogicMySqlClient, trash::TrashLogicMySqlClient,
         user::UserLogicMySqlClient,
     },
 };
@@ -26,6 +29,13 @@ pub struct MySqlClient {
     pub space: SpaceLogicMySqlClient,
     pub audit: AuditLogicMySqlClient,
     pub eval: EvaluationLogicMySqlClient,
+    pub group: GroupLogicMySqlClient,
+    pub alias: AliasLogicMySqlClient,
+    pub lineage: LineageLogicMySqlClient,
+    pub agent_job: AgentJobLogicMySqlClient,
+    pub event: EventLogicMySqlClient,
+    pub search: SearchLogicMySqlClient,
+    pub trash: TrashLogicMySqlClient,
 }
 
 impl MySqlClient {
@@ -44,6 +54,13 @@ impl MySqlClient {
             space: SpaceLogicMySqlClient::new(&pool),
             audit: AuditLogicMySqlClient::new(&pool),
             eval: EvaluationLogicMySqlClient::new(&pool),
+            group: GroupLogicMySqlClient::new(&pool),
+            alias: AliasLogicMySqlClient::new(&pool),
+            lineage: LineageLogicMySqlClient::new(&pool),
+            agent_job: AgentJobLogicMySqlClient::new(&pool),
+            event: EventLogicMySqlClient::new(&pool),
+            search: SearchLogicMySqlClient::new(&pool),
+            trash: TrashLogicMySqlClient::new(&pool),
             pool,
         };
 
@@ -70,13 +87,15 @@ mod tests {
 
     use crate::schemas::EvaluationSqlRecord;
     use crate::schemas::schema::{
-        ArtifactSqlRecord, AuditCardRecord, CardResults, DataCardRecord, ExperimentCardRecord,
-        HardwareMetricsRecord, MetricRecord, ModelCardRecord, ParameterRecord, PromptCardRecord,
-        ServerCard, ServiceCardRecord, SkillCardRecord, SubAgentCardRecord, ToolCardRecord, User,
+        ApiToken, ArtifactSqlRecord, AuditCardRecord, CardResults, DataCardRecord,
+        ExperimentCardRecord, HardwareMetricsRecord, MetricRecord, ModelCardRecord,
+        ParameterRecord, PromptCardRecord, ServerCard, ServiceCardRecord, SkillCardRecord,
+        SubAgentCardRecord, ToolCardRecord, User, WebhookSqlRecord,
     };
     use crate::traits::EvaluationLogicTrait;
     use crate::traits::{
-        ArtifactLogicTrait, AuditLogicTrait, CardLogicTrait, ExperimentLogicTrait, SpaceLogicTrait,
+        AgentJobLogicTrait, AliasLogicTrait, ArtifactLogicTrait, AuditLogicTrait, CardLogicTrait,
+        EventLogicTrait, ExperimentLogicTrait, GroupLogicTrait, LineageLogicTrait, SpaceLogicTrait,
         UserLogicTrait,
     };
     use opsml_settings::config::DatabaseSettings;
@@ -87,7 +106,11 @@ mod tests {
         SecurityRequirement,
     };
     use opsml_types::contracts::evaluation::{EvaluationProvider, EvaluationType};
-    use opsml_types::contracts::{ArtifactKey, ArtifactQueryArgs, AuditEvent, SpaceNameEvent};
+    use opsml_types::contracts::{
+        AgentJobRecord, ArtifactKey, ArtifactQueryArgs, AuditEvent, AuditQueryArgs, AuditStatus,
+        CardAlias, CardEntry, DeliveryStatus, GroupRecord, GroupRole, JobStatus, LineageRelation,
+        Operation, RegistryEvent, RegistryEventType, SpaceNameEvent, SpaceRole,
+    };
     use opsml_types::contracts::{ProtocolBinding, VersionCursor};
     use opsml_types::{
         RegistryType,
@@ -97,6 +120,7 @@ mod tests {
             Resources, ServiceConfig, ServiceQueryArgs, ServiceType, SpaceRecord,
         },
     };
+    use opsml_utils::create_uuid7;
     use opsml_utils::utils::get_utc_datetime;
     use semver::Version;
     use sqlx::types::Json;
@@ -173,6 +197,36 @@ mod tests {
             DELETE
             FROM opsml_experiment_parameter;
 
+            DELETE
+            FROM opsml_group_member;
+
+            DELETE
+            FROM opsml_group_role;
+
+            DELETE
+            FROM opsml_group;
+
+            DELETE
+            FROM opsml_card_alias;
+
+            DELETE
+            FROM opsml_card_search;
+
+            DELETE
+            FROM opsml_card_trash;
+
+            DELETE
+            FROM opsml_retention_policy;
+
+            DELETE
+            FROM opsml_space_usage;
+
+            DELETE
+            FROM opsml_upload_reservation;
+
+            DELETE
+            FROM opsml_user_api_token;
+
             DELETE
             FROM opsml_user;
 
@@ -202,6 +256,14 @@ mod tests {
             DELETE FROM opsml_skill_registry;
 
             DELETE FROM opsml_subagent_registry;
+
+            DELETE FROM opsml_agent_job;
+
+            DELETE FROM opsml_event_outbox;
+
+            DELETE FROM opsml_webhook;
+
+            DELETE FROM opsml_webhook_delivery;
             "#,
         )
         .fetch_all(pool)
@@ -1624,6 +1686,649 @@ mod tests {
         client.user.delete_user("user").await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_mysql_api_tokens() {
+        let client = db_client().await;
+
+        let user = User::new(
+            "service".to_string(),
+            "pass".to_string(),
+            "email".to_string(),
+            vec![],
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        client.user.insert_user(&user).await.unwrap();
+
+        let token = ApiToken::new(
+            "token_id".to_string(),
+            "service".to_string(),
+            "ci".to_string(),
+            "hash".to_string(),
+            vec!["read:space".to_string()],
+            Some(chrono::Utc::now() + chrono::Duration::days(1)),
+        );
+        client.user.insert_api_token(&token).await.unwrap();
+
+        let fetched = client
+            .user
+            .get_api_token("token_id")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.username, "service");
+        assert_eq!(fetched.token_hash, "hash");
+        assert_eq!(fetched.permissions, vec!["read:space"]);
+        assert!(fetched.last_used_at.is_none());
+        assert!(!fetched.revoked);
+        assert!(!fetched.is_expired());
+
+        client
+            .user
+            .update_api_token_last_used("token_id")
+            .await
+            .unwrap();
+
+        let tokens = client.user.get_api_tokens("service").await.unwrap();
+        assert_eq!(tokens.len(), 1);
+        assert!(tokens[0].last_used_at.is_some());
+
+        // revoking another user's token is a no-op
+        let revoked = client
+            .user
+            .revoke_api_token("other", "token_id")
+            .await
+            .unwrap();
+        assert!(!revoked);
+
+        let revoked = client
+            .user
+            .revoke_api_token("service", "token_id")
+            .await
+            .unwrap();
+        assert!(revoked);
+
+        let fetched = client
+            .user
+            .get_api_token("token_id")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(fetched.revoked);
+
+        // tokens are removed with their user
+        client.user.delete_user("service").await.unwrap();
+        assert!(
+            client
+                .user
+                .get_api_token("token_id")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mysql_groups() {
+        let client = db_client().await;
+
+        let user = User::new(
+            "member".to_string(),
+            "pass".to_string(),
+            "email".to_string(),
+            vec![],
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        client.user.insert_user(&user).await.unwrap();
+
+        let group = GroupRecord {
+            name: "ml-team".to_string(),
+            description: "ML engineers".to_string(),
+            sso_groups: vec!["okta-ml".to_string()],
+            created_at: chrono::Utc::now(),
+        };
+        client.group.insert_group(&group).await.unwrap();
+
+        let fetched = client.group.get_group("ml-team").await.unwrap().unwrap();
+        assert_eq!(fetched.sso_groups, vec!["okta-ml"]);
+        assert_eq!(client.group.get_groups().await.unwrap().len(), 1);
+
+        let mut role = GroupRole {
+            group_name: "ml-team".to_string(),
+            space: "team-a".to_string(),
+            role: SpaceRole::Viewer,
+        };
+        client.group.upsert_group_role(&role).await.unwrap();
+
+        // upserting the same space replaces the role
+        role.role = SpaceRole::Owner;
+        client.group.upsert_group_role(&role).await.unwrap();
+
+        let roles = client.group.get_group_roles("ml-team").await.unwrap();
+        assert_eq!(roles.len(), 1);
+        assert_eq!(roles[0].role, SpaceRole::Owner);
+
+        // adding a member twice is a no-op
+        client
+            .group
+            .add_group_member("ml-team", "member")
+            .await
+            .unwrap();
+        client
+            .group
+            .add_group_member("ml-team", "member")
+            .await
+            .unwrap();
+        assert_eq!(
+            client.group.get_group_members("ml-team").await.unwrap(),
+            vec!["member"]
+        );
+        assert_eq!(
+            client.group.get_user_groups("member").await.unwrap(),
+            vec!["ml-team"]
+        );
+
+        let user_roles = client.group.get_user_group_roles("member").await.unwrap();
+        assert_eq!(user_roles, vec![role]);
+
+        client
+            .group
+            .delete_group_role("ml-team", "team-a")
+            .await
+            .unwrap();
+        assert!(
+            client
+                .group
+                .get_user_group_roles("member")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        client
+            .group
+            .remove_group_member("ml-team", "member")
+            .await
+            .unwrap();
+        assert!(
+            client
+                .group
+                .get_user_groups("member")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        client.group.delete_group("ml-team").await.unwrap();
+        assert!(client.group.get_group("ml-team").await.unwrap().is_none());
+
+        client.user.delete_user("member").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_mysql_card_aliases() {
+        let client = db_client().await;
+
+        let mut alias = CardAlias {
+            space: "repo1".to_string(),
+            name: "model".to_string(),
+            registry_type: RegistryType::Model,
+            alias: "production".to_string(),
+            uid: create_uuid7(),
+            version: "1.0.0".to_string(),
+            updated_by: "guest".to_string(),
+            updated_at: get_utc_datetime(),
+        };
+        client.alias.upsert_card_alias(&alias).await.unwrap();
+
+        // moving the alias replaces the target version
+        alias.uid = create_uuid7();
+        alias.version = "1.1.0".to_string();
+        client.alias.upsert_card_alias(&alias).await.unwrap();
+
+        let fetched = client
+            .alias
+            .get_card_alias("repo1", "model", &RegistryType::Model, "production")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.version, "1.1.0");
+        assert_eq!(fetched.uid, alias.uid);
+
+        let aliases = client
+            .alias
+            .get_card_aliases("repo1", "model", &RegistryType::Model)
+            .await
+            .unwrap();
+        assert_eq!(aliases.len(), 1);
+
+        assert!(
+            client
+                .alias
+                .delete_card_alias("repo1", "model", &RegistryType::Model, "production")
+                .await
+                .unwrap()
+        );
+        assert!(
+            client
+                .alias
+                .get_card_alias("repo1", "model", &RegistryType::Model, "production")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mysql_agent_jobs() {
+        let client = db_client().await;
+
+        let mut job = AgentJobRecord {
+            job_id: create_uuid7(),
+            agent_id: "skill-scan".to_string(),
+            owner: "guest".to_string(),
+            status: JobStatus::Pending,
+            result: None,
+            error: None,
+            duration_ms: None,
+            session_id: Some(create_uuid7()),
+            prompt: None,
+            instance_id: "opsml-0".to_string(),
+            created_at: get_utc_datetime(),
+            updated_at: get_utc_datetime(),
+        };
+        client.agent_job.upsert_agent_job(&job).await.unwrap();
+
+        job.status = JobStatus::NeedsInput;
+        job.prompt = Some("Which environment?".to_string());
+        client.agent_job.upsert_agent_job(&job).await.unwrap();
+
+        let fetched = client
+            .agent_job
+            .get_agent_job(&job.job_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.status, JobStatus::NeedsInput);
+        assert_eq!(fetched.prompt, job.prompt);
+        assert_eq!(fetched.session_id, job.session_id);
+
+        job.status = JobStatus::Done;
+        job.result = Some(serde_json::json!({"verdict": "safe"}));
+        job.prompt = None;
+        job.duration_ms = Some(42);
+        client.agent_job.upsert_agent_job(&job).await.unwrap();
+
+        let fetched = client
+            .agent_job
+            .get_agent_job(&job.job_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.status, JobStatus::Done);
+        assert_eq!(fetched.result, job.result);
+        assert_eq!(fetched.duration_ms, Some(42));
+
+        // this instance's in-flight jobs fail on restart, fresh jobs of other instances are kept
+        let running = AgentJobRecord {
+            job_id: create_uuid7(),
+            status: JobStatus::Running,
+            ..job.clone()
+        };
+        client.agent_job.upsert_agent_job(&running).await.unwrap();
+        let other_instance = AgentJobRecord {
+            job_id: create_uuid7(),
+            status: JobStatus::Running,
+            instance_id: "opsml-1".to_string(),
+            ..job.clone()
+        };
+        client
+            .agent_job
+            .upsert_agent_job(&other_instance)
+            .await
+            .unwrap();
+        let failed = client
+            .agent_job
+            .fail_incomplete_agent_jobs(
+                "interrupted",
+                Some("opsml-0"),
+                &(get_utc_datetime() - chrono::Duration::seconds(60)),
+            )
+            .await
+            .unwrap();
+        assert_eq!(failed, 1);
+
+        // live instances keep their jobs fresh
+        let touched = client
+            .agent_job
+            .heartbeat_agent_jobs("opsml-1")
+            .await
+            .unwrap();
+        assert_eq!(touched, 1);
+
+        let fetched = client
+            .agent_job
+            .get_agent_job(&running.job_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.status, JobStatus::Failed);
+        assert_eq!(fetched.error.as_deref(), Some("interrupted"));
+
+        let fetched = client
+            .agent_job
+            .get_agent_job(&other_instance.job_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.status, JobStatus::Running);
+        assert_eq!(fetched.instance_id, "opsml-1");
+
+        // jobs whose heartbeat is stale are failed whatever instance ran them
+        let failed = client
+            .agent_job
+            .fail_incomplete_agent_jobs(
+                "stale",
+                None,
+                &(get_utc_datetime() + chrono::Duration::seconds(60)),
+            )
+            .await
+            .unwrap();
+        assert_eq!(failed, 1);
+
+        let fetched = client
+            .agent_job
+            .get_agent_job(&other_instance.job_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.status, JobStatus::Failed);
+        assert_eq!(fetched.error.as_deref(), Some("stale"));
+
+        let cutoff = get_utc_datetime() + chrono::Duration::seconds(60);
+        let deleted = client
+            .agent_job
+            .delete_expired_agent_jobs(&cutoff)
+            .await
+            .unwrap();
+        assert_eq!(deleted, 3);
+        assert!(
+            client
+                .agent_job
+                .get_agent_job(&job.job_id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mysql_events() {
+        let client = db_client().await;
+
+        let event = RegistryEvent::new(
+            RegistryEventType::CardCreated,
+            "repo1",
+            Some(RegistryType::Model),
+            &create_uuid7(),
+            serde_json::json!({"name": "model1", "version": "1.0.0"}),
+            "guest",
+        );
+        client.event.insert_registry_event(&event).await.unwrap();
+
+        let fetched = client
+            .event
+            .get_registry_event(&event.event_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.event_type, RegistryEventType::CardCreated);
+        assert_eq!(fetched.registry_type, Some(RegistryType::Model));
+        assert_eq!(fetched.payload, event.payload);
+
+        let claimed_at = get_utc_datetime();
+        let lease_until = claimed_at + chrono::Duration::seconds(60);
+        let pending = client
+            .event
+            .claim_undispatched_events("claim-1", &claimed_at, &lease_until, 10)
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 1);
+
+        // another instance skips the event while the claim is held
+        assert!(
+            client
+                .event
+                .claim_undispatched_events("claim-2", &claimed_at, &lease_until, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // a released claim is picked up by the next poll
+        client.event.release_event_claim("claim-1").await.unwrap();
+        let pending = client
+            .event
+            .claim_undispatched_events("claim-2", &claimed_at, &lease_until, 10)
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 1);
+
+        client
+            .event
+            .mark_event_dispatched(&event.event_id)
+            .await
+            .unwrap();
+
+        // dispatched events are not claimed again once the lease expires
+        let expired = lease_until + chrono::Duration::seconds(1);
+        assert!(
+            client
+                .event
+                .claim_undispatched_events("claim-3", &expired, &expired, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let webhook = WebhookSqlRecord {
+            id: create_uuid7(),
+            space: "repo1".to_string(),
+            url: "http://localhost:8080/hook".to_string(),
+            event_types: Json(vec![RegistryEventType::CardCreated]),
+            secret: "secret".to_string(),
+            active: true,
+            created_by: "guest".to_string(),
+            created_at: get_utc_datetime(),
+        };
+        client.event.insert_webhook(&webhook).await.unwrap();
+
+        let webhooks = client.event.list_webhooks("repo1").await.unwrap();
+        assert_eq!(webhooks.len(), 1);
+        assert_eq!(
+            webhooks[0].event_types.0,
+            vec![RegistryEventType::CardCreated]
+        );
+        assert_eq!(webhooks[0].secret, "secret");
+
+        // queuing the same event twice keeps a single delivery
+        let now = get_utc_datetime();
+        for _ in 0..2 {
+            client
+                .event
+                .insert_webhook_delivery(&webhook.id, &event.event_id, &now)
+                .await
+                .unwrap();
+        }
+
+        let later = now + chrono::Duration::seconds(60);
+        let lease_until = later + chrono::Duration::seconds(600);
+        let mut due = client
+            .event
+            .claim_due_webhook_deliveries("claim-1", &later, &lease_until, 10)
+            .await
+            .unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].status, DeliveryStatus::Pending);
+
+        // a claimed delivery is not handed to another instance
+        assert!(
+            client
+                .event
+                .claim_due_webhook_deliveries("claim-2", &later, &lease_until, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // a failed attempt is retried later, so it is not due yet
+        let mut delivery = due.remove(0);
+        delivery.attempts = 1;
+        delivery.next_attempt_at = later + chrono::Duration::seconds(60);
+        delivery.last_error = Some("connection refused".to_string());
+        client
+            .event
+            .update_webhook_delivery(&delivery)
+            .await
+            .unwrap();
+        assert!(
+            client
+                .event
+                .claim_due_webhook_deliveries("claim-3", &later, &lease_until, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let deliveries = client
+            .event
+            .list_webhook_deliveries(&webhook.id, 10)
+            .await
+            .unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].attempts, 1);
+        assert_eq!(deliveries[0].last_error, delivery.last_error);
+
+        assert!(client.event.delete_webhook(&webhook.id).await.unwrap());
+        assert!(!client.event.delete_webhook(&webhook.id).await.unwrap());
+        assert!(
+            client
+                .event
+                .get_webhook(&webhook.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            client
+                .event
+                .list_webhook_deliveries(&webhook.id, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mysql_lineage() {
+        let client = db_client().await;
+
+        let data = DataCardRecord::default();
+        let model = ModelCardRecord {
+            datacard_uid: Some(data.uid.clone()),
+            ..Default::default()
+        };
+        let experiment = ExperimentCardRecord {
+            datacard_uids: Json(vec![data.uid.clone()]),
+            ..Default::default()
+        };
+        let service = ServiceCardRecord {
+            cards: Json(vec![CardEntry {
+                registry_type: RegistryType::Model,
+                uid: Some(model.uid.clone()),
+                version: Some(model.version.clone()),
+                alias: "model".to_string(),
+            }]),
+            ..Default::default()
+        };
+
+        client
+            .card
+            .insert_card(&CardTable::Data, &ServerCard::Data(data.clone()))
+            .await
+            .unwrap();
+        client
+            .card
+            .insert_card(&CardTable::Model, &ServerCard::Model(model.clone()))
+            .await
+            .unwrap();
+        client
+            .card
+            .insert_card(
+                &CardTable::Experiment,
+                &ServerCard::Experiment(experiment.clone()),
+            )
+            .await
+            .unwrap();
+        client
+            .card
+            .insert_card(
+                &CardTable::Service,
+                &ServerCard::Service(Box::new(service.clone())),
+            )
+            .await
+            .unwrap();
+
+        let edges = client.lineage.get_lineage_edges(&data.uid).await.unwrap();
+        assert_eq!(edges.len(), 2);
+        assert!(
+            edges
+                .iter()
+                .any(|edge| edge.relation == LineageRelation::TrainedOn
+                    && edge.source_uid == data.uid
+                    && edge.target_uid == model.uid)
+        );
+        assert!(
+            edges
+                .iter()
+                .any(|edge| edge.relation == LineageRelation::ProducedBy
+                    && edge.source_uid == experiment.uid
+                    && edge.target_type == RegistryType::Data)
+        );
+
+        let edges = client
+            .lineage
+            .get_lineage_edges(&service.uid)
+            .await
+            .unwrap();
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].relation, LineageRelation::DeployedIn);
+        assert_eq!(edges[0].source_type, RegistryType::Model);
+
+        let node = client
+            .lineage
+            .get_lineage_node(&CardTable::Model, &model.uid)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(node.version, model.version);
+        assert!(
+            client
+                .lineage
+                .get_lineage_node(&CardTable::Data, &model.uid)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
     #[tokio::test]
     async fn test_mysql_artifact_keys() {
         let client = db_client().await;
@@ -1636,6 +2341,8 @@ mod tests {
             registry_type: RegistryType::Data,
             encrypted_key: encrypted_key.clone(),
             storage_key: "opsml_registry".to_string(),
+            key_version: 0,
+            manifest_sha256: None,
         };
 
         client.artifact.insert_artifact_key(&key).await.unwrap();
@@ -1656,6 +2363,8 @@ mod tests {
             registry_type: RegistryType::Data,
             encrypted_key: encrypted_key.clone(),
             storage_key: "opsml_registry".to_string(),
+            key_version: 0,
+            manifest_sha256: None,
         };
 
         client.artifact.update_artifact_key(&key).await.unwrap();
@@ -1689,6 +2398,74 @@ mod tests {
         assert_eq!(result, "guest");
     }
 
+    #[tokio::test]
+    async fn test_mysql_query_audit_events() {
+        let client = db_client().await;
+
+        let events = vec![
+            ("audit_alice", Operation::Read, AuditStatus::Success),
+            ("audit_alice", Operation::Read, AuditStatus::Success),
+            ("audit_bob", Operation::Delete, AuditStatus::Denied),
+        ];
+
+        for (username, operation, status) in events {
+            let event = AuditEvent {
+                username: username.to_string(),
+                operation,
+                status,
+                ..Default::default()
+            };
+            client.audit.insert_audit_event(event).await.unwrap();
+        }
+
+        // filter by username
+        let args = AuditQueryArgs {
+            username: Some("audit_alice".to_string()),
+            ..Default::default()
+        };
+        let records = client.audit.query_audit_events(&args).await.unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(records[0].id > records[1].id);
+
+        // filter by operation and status
+        let args = AuditQueryArgs {
+            operation: Some("Delete".to_string()),
+            status: Some("Denied".to_string()),
+            registry_type: Some(RegistryType::Model),
+            ..Default::default()
+        };
+        let records = client.audit.query_audit_events(&args).await.unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].username, "audit_bob");
+
+        // paginate with cursor
+        let mut args = AuditQueryArgs {
+            username: Some("audit_alice".to_string()),
+            limit: Some(1),
+            ..Default::default()
+        };
+        let first = client.audit.query_audit_events(&args).await.unwrap();
+        assert_eq!(first.len(), 1);
+
+        args.cursor = Some(first[0].id);
+        let second = client.audit.query_audit_events(&args).await.unwrap();
+        assert_eq!(second.len(), 1);
+        assert!(second[0].id < first[0].id);
+
+        args.cursor = Some(second[0].id);
+        let third = client.audit.query_audit_events(&args).await.unwrap();
+        assert!(third.is_empty());
+
+        // time range in the future returns nothing
+        let args = AuditQueryArgs {
+            username: Some("audit_alice".to_string()),
+            start_time: Some(get_utc_datetime() + chrono::Duration::days(1)),
+            ..Default::default()
+        };
+        let records = client.audit.query_audit_events(&args).await.unwrap();
+        assert!(records.is_empty());
+    }
+
     #[tokio::test]
     async fn test_mysql_get_load_card_key() {
         let client = db_client().await;
@@ -1707,6 +2484,8 @@ mod tests {
             registry_type: RegistryType::Data,
             encrypted_key: encrypted_key.clone(),
             storage_key: "opsml_registry".to_string(),
+            key_version: 0,
+            manifest_sha256: None,
         };
 
         client.artifact.insert_artifact_key(&key).await.unwrap();
@@ -1776,6 +2555,8 @@ mod tests {
             registry_type: RegistryType::Service,
             encrypted_key: encrypted_key.clone(),
             storage_key: "opsml_registry".to_string(),
+            key_version: 0,
+            manifest_sha256: None,
         };
 
         client.artifact.insert_artifact_key(&key).await.unwrap();
@@ -1835,6 +2616,7 @@ mod tests {
         let space_record = SpaceRecord {
             space: CommonKwargs::Undefined.to_string(),
             description: "Space description".to_string(),
+            ..Default::default()
         };
 
         client
@@ -1927,6 +2709,7 @@ mod tests {
         let updated_space_record = SpaceRecord {
             space: model_card2.space.clone(),
             description: "Updated Space description".to_string(),
+            ..Default::default()
         };
         client
             .space
@@ -1978,6 +2761,7 @@ mod tests {
             Version::new(0, 0, 0),
             "png".to_string(),
             ArtifactType::Generic.to_string(),
+            "development".to_string(),
         );
         client
             .artifact
@@ -1991,6 +2775,7 @@ mod tests {
             Version::new(0, 0, 0),
             "png".to_string(),
             ArtifactType::Figure.to_string(),
+            "development".to_string(),
         );
 
         client
@@ -2022,6 +2807,7 @@ mod tests {
             "test".to_string(),
             EvaluationType::Agent,
             EvaluationProvider::Opsml,
+            "development".to_string(),
         );
         let uid = eval_record.uid.clone();
         client
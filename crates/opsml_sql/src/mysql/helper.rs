@@ -15,6 +15,81 @@ const GET_USERS_SQL: &str = include_str!("sql/user/get_users.sql");
 const UPDATE_USER_SQL: &str = include_str!("sql/user/update_user.sql");
 const DELETE_USER_SQL: &str = include_str!("sql/user/delete_user.sql");
 const LAST_ADMIN_SQL: &str = include_str!("sql/user/last_admin.sql");
+const INSERT_API_TOKEN_SQL: &str = include_str!("sql/user/insert_api_token.sql");
+const GET_API_TOKEN_SQL: &str = include_str!("sql/user/get_api_token.sql");
+const LIST_API_TOKENS_SQL: &str = include_str!("sql/user/list_api_tokens.sql");
+const REVOKE_API_TOKEN_SQL: &str = include_str!("sql/user/revoke_api_token.sql");
+const UPDATE_API_TOKEN_LAST_USED_SQL: &str =
+    include_str!("sql/user/update_api_token_last_used.sql");
+
+// groups
+const INSERT_GROUP_SQL: &str = include_str!("sql/group/insert_group.sql");
+const GET_GROUP_SQL: &str = include_str!("sql/group/get_group.sql");
+const GET_GROUPS_SQL: &str = include_str!("sql/group/get_groups.sql");
+const DELETE_GROUP_SQL: &str = include_str!("sql/group/delete_group.sql");
+const UPSERT_GROUP_ROLE_SQL: &str = include_str!("sql/group/upsert_group_role.sql");
+const DELETE_GROUP_ROLE_SQL: &str = include_str!("sql/group/delete_group_role.sql");
+const GET_GROUP_ROLES_SQL: &str = include_str!("sql/group/get_group_roles.sql");
+const INSERT_GROUP_MEMBER_SQL: &str = include_str!("sql/group/insert_group_member.sql");
+const DELETE_GROUP_MEMBER_SQL: &str = include_str!("sql/group/delete_group_member.sql");
+const GET_GROUP_MEMBERS_SQL: &str = include_str!("sql/group/get_group_members.sql");
+const GET_USER_GROUPS_SQL: &str = include_str!("sql/group/get_user_groups.sql");
+const GET_USER_GROUP_ROLES_SQL: &str = include_str!("sql/group/get_user_group_roles.sql");
+
+// card aliases
+const UPSERT_CARD_ALIAS_SQL: &str = include_str!("sql/alias/upsert_card_alias.sql");
+const GET_CARD_ALIAS_SQL: &str = include_str!("sql/alias/get_card_alias.sql");
+const GET_CARD_ALIASES_SQL: &str = include_str!("sql/alias/get_card_aliases.sql");
+const DELETE_CARD_ALIAS_SQL: &str = include_str!("sql/alias/delete_card_alias.sql");
+
+// card search
+const UPSERT_SEARCH_DOCUMENT_SQL: &str = include_str!("sql/search/upsert_search_document.sql");
+const GET_SEARCH_README_SQL: &str = include_str!("sql/search/get_search_readme.sql");
+const UPDATE_SEARCH_README_SQL: &str = include_str!("sql/search/update_search_readme.sql");
+const DELETE_SEARCH_DOCUMENT_SQL: &str = include_str!("sql/search/delete_search_document.sql");
+const SEARCH_CARDS_SQL: &str = include_str!("sql/search/search_cards.sql");
+
+// trash and retention
+const INSERT_TRASH_RECORD_SQL: &str = include_str!("sql/trash/insert_trash_record.sql");
+const GET_TRASH_RECORD_SQL: &str = include_str!("sql/trash/get_trash_record.sql");
+const LIST_TRASH_SQL: &str = include_str!("sql/trash/list_trash.sql");
+const DELETE_TRASH_RECORD_SQL: &str = include_str!("sql/trash/delete_trash_record.sql");
+const UPSERT_RETENTION_POLICY_SQL: &str = include_str!("sql/trash/upsert_retention_policy.sql");
+const GET_RETENTION_POLICY_SQL: &str = include_str!("sql/trash/get_retention_policy.sql");
+const GET_RETENTION_POLICIES_SQL: &str = include_str!("sql/trash/get_retention_policies.sql");
+const DELETE_RETENTION_POLICY_SQL: &str = include_str!("sql/trash/delete_retention_policy.sql");
+
+// agent jobs
+const UPSERT_AGENT_JOB_SQL: &str = include_str!("sql/agent_job/upsert_agent_job.sql");
+const GET_AGENT_JOB_SQL: &str = include_str!("sql/agent_job/get_agent_job.sql");
+const FAIL_INCOMPLETE_AGENT_JOBS_SQL: &str =
+    include_str!("sql/agent_job/fail_incomplete_agent_jobs.sql");
+const DELETE_EXPIRED_AGENT_JOBS_SQL: &str =
+    include_str!("sql/agent_job/delete_expired_agent_jobs.sql");
+const HEARTBEAT_AGENT_JOBS_SQL: &str = include_str!("sql/agent_job/heartbeat_agent_jobs.sql");
+
+// event outbox and webhooks
+const INSERT_REGISTRY_EVENT_SQL: &str = include_str!("sql/event/insert_registry_event.sql");
+const GET_REGISTRY_EVENT_SQL: &str = include_str!("sql/event/get_registry_event.sql");
+const CLAIM_UNDISPATCHED_EVENTS_SQL: &str = include_str!("sql/event/claim_undispatched_events.sql");
+const GET_CLAIMED_EVENTS_SQL: &str = include_str!("sql/event/get_claimed_events.sql");
+const RELEASE_EVENT_CLAIM_SQL: &str = include_str!("sql/event/release_event_claim.sql");
+const MARK_EVENT_DISPATCHED_SQL: &str = include_str!("sql/event/mark_event_dispatched.sql");
+const INSERT_WEBHOOK_SQL: &str = include_str!("sql/event/insert_webhook.sql");
+const GET_WEBHOOK_SQL: &str = include_str!("sql/event/get_webhook.sql");
+const LIST_WEBHOOKS_SQL: &str = include_str!("sql/event/list_webhooks.sql");
+const DELETE_WEBHOOK_SQL: &str = include_str!("sql/event/delete_webhook.sql");
+const DELETE_WEBHOOK_DELIVERIES_SQL: &str = include_str!("sql/event/delete_webhook_deliveries.sql");
+const INSERT_WEBHOOK_DELIVERY_SQL: &str = include_str!("sql/event/insert_webhook_delivery.sql");
+const CLAIM_DUE_WEBHOOK_DELIVERIES_SQL: &str =
+    include_str!("sql/event/claim_due_webhook_deliveries.sql");
+const GET_CLAIMED_WEBHOOK_DELIVERIES_SQL: &str =
+    include_str!("sql/event/get_claimed_webhook_deliveries.sql");
+const UPDATE_WEBHOOK_DELIVERY_SQL: &str = include_str!("sql/event/update_webhook_delivery.sql");
+const LIST_WEBHOOK_DELIVERIES_SQL: &str = include_str!("sql/event/list_webhook_deliveries.sql");
+
+// lineage
+const GET_LINEAGE_EDGES_SQL: &str = include_str!("sql/lineage/get_lineage_edges.sql");
 
 // space stats
 const INSERT_SPACE_RECORD_SQL: &str = include_str!("sql/space/insert_space_record.sql");
@@ -26,6 +101,18 @@ const GET_SPACE_RECORD_SQL: &str = include_str!("sql/space/get_space_record.sql"
 const UPDATE_SPACE_RECORD_SQL: &str = include_str!("sql/space/update_space_record.sql");
 const DELETE_SPACE_RECORD_SQL: &str = include_str!("sql/space/delete_space_record.sql");
 const DELETE_SPACE_NAME_RECORD_SQL: &str = include_str!("sql/space/delete_space_name_record.sql");
+const SET_SPACE_QUOTA_SQL: &str = include_str!("sql/space/set_space_quota.sql");
+const ADD_SPACE_USAGE_SQL: &str = include_str!("sql/space/add_space_usage.sql");
+const GET_SPACE_USAGE_SQL: &str = include_str!("sql/space/get_space_usage.sql");
+const GET_SPACE_BYTES_SQL: &str = include_str!("sql/space/get_space_bytes.sql");
+const LOCK_SPACE_RECORD_SQL: &str = include_str!("sql/space/lock_space_record.sql");
+const SET_SPACE_USAGE_SQL: &str = include_str!("sql/space/set_space_usage.sql");
+const INSERT_UPLOAD_RESERVATION_SQL: &str = include_str!("sql/space/insert_upload_reservation.sql");
+const GET_UPLOAD_RESERVATION_SQL: &str = include_str!("sql/space/get_upload_reservation.sql");
+const DELETE_UPLOAD_RESERVATION_SQL: &str = include_str!("sql/space/delete_upload_reservation.sql");
+const GET_UPLOAD_RESERVATIONS_SQL: &str = include_str!("sql/space/get_upload_reservations.sql");
+const DELETE_STALE_UPLOAD_RESERVATIONS_SQL: &str =
+    include_str!("sql/space/delete_stale_upload_reservations.sql");
 
 // experiment
 const GET_HARDWARE_METRIC_SQL: &str = include_str!("sql/experiment/get_hardware_metric.sql");
@@ -102,6 +189,11 @@ const UPDATE_ARTIFACT_KEY_SQL: &str = include_str!("sql/artifact/update_artifact
 const GET_ARTIFACT_KEY_FROM_STORAGE_PATH_SQL: &str =
     include_str!("sql/artifact/get_artifact_key_from_storage_path.sql");
 const DELETE_ARTIFACT_KEY_SQL: &str = include_str!("sql/artifact/delete_artifact_key.sql");
+const GET_ARTIFACT_KEYS_SQL: &str = include_str!("sql/artifact/get_artifact_keys.sql");
+const GET_ARTIFACT_KEYS_TO_REWRAP_SQL: &str =
+    include_str!("sql/artifact/get_artifact_keys_to_rewrap.sql");
+const REWRAP_ARTIFACT_KEY_SQL: &str = include_str!("sql/artifact/rewrap_artifact_key.sql");
+const SET_ARTIFACT_MANIFEST_SQL: &str = include_str!("sql/artifact/set_artifact_manifest.sql");
 
 // evaluation
 const INSERT_EVALUATION_RECORD_SQL: &str = include_str!("sql/evaluation/insert_evaluation.sql");
@@ -109,6 +201,7 @@ const GET_EVALUATION_RECORD_SQL: &str = include_str!("sql/evaluation/get_evaluat
 
 // audit events
 const INSERT_AUDIT_EVENT_SQL: &str = include_str!("sql/audit/insert_audit_event.sql");
+const QUERY_AUDIT_EVENTS_SQL: &str = include_str!("sql/audit/query_audit_events.sql");
 
 pub struct MySqlQueryHelper;
 
@@ -117,6 +210,16 @@ impl MySqlQueryHelper {
         format!("SELECT uid FROM {table} WHERE uid = ?")
     }
 
+    pub fn get_cards_without_key_query(table: &CardTable) -> String {
+        format!(
+            "SELECT uid, space, name, version FROM {table} c
+            WHERE uid > ?
+            AND NOT EXISTS (SELECT 1 FROM opsml_artifact_key k WHERE k.uid = c.uid)
+            ORDER BY uid
+            LIMIT ?"
+        )
+    }
+
     pub fn get_user_insert_query() -> &'static str {
         INSERT_USER_SQL
     }
@@ -137,6 +240,243 @@ impl MySqlQueryHelper {
         LAST_ADMIN_SQL
     }
 
+    pub fn get_api_token_insert_query() -> &'static str {
+        INSERT_API_TOKEN_SQL
+    }
+
+    pub fn get_api_token_query() -> &'static str {
+        GET_API_TOKEN_SQL
+    }
+
+    pub fn get_api_tokens_query() -> &'static str {
+        LIST_API_TOKENS_SQL
+    }
+
+    pub fn get_api_token_revoke_query() -> &'static str {
+        REVOKE_API_TOKEN_SQL
+    }
+
+    pub fn get_api_token_last_used_update_query() -> &'static str {
+        UPDATE_API_TOKEN_LAST_USED_SQL
+    }
+
+    pub fn get_group_insert_query() -> &'static str {
+        INSERT_GROUP_SQL
+    }
+
+    pub fn get_group_query() -> &'static str {
+        GET_GROUP_SQL
+    }
+
+    pub fn get_groups_query() -> &'static str {
+        GET_GROUPS_SQL
+    }
+
+    pub fn get_group_delete_query() -> &'static str {
+        DELETE_GROUP_SQL
+    }
+
+    pub fn get_group_role_upsert_query() -> &'static str {
+        UPSERT_GROUP_ROLE_SQL
+    }
+
+    pub fn get_group_role_delete_query() -> &'static str {
+        DELETE_GROUP_ROLE_SQL
+    }
+
+    pub fn get_group_roles_query() -> &'static str {
+        GET_GROUP_ROLES_SQL
+    }
+
+    pub fn get_group_member_insert_query() -> &'static str {
+        INSERT_GROUP_MEMBER_SQL
+    }
+
+    pub fn get_group_member_delete_query() -> &'static str {
+        DELETE_GROUP_MEMBER_SQL
+    }
+
+    pub fn get_group_members_query() -> &'static str {
+        GET_GROUP_MEMBERS_SQL
+    }
+
+    pub fn get_user_groups_query() -> &'static str {
+        GET_USER_GROUPS_SQL
+    }
+
+    pub fn get_user_group_roles_query() -> &'static str {
+        GET_USER_GROUP_ROLES_SQL
+    }
+
+    pub fn get_search_document_upsert_query() -> &'static str {
+        UPSERT_SEARCH_DOCUMENT_SQL
+    }
+
+    pub fn get_search_readme_query() -> &'static str {
+        GET_SEARCH_README_SQL
+    }
+
+    pub fn get_search_readme_update_query() -> &'static str {
+        UPDATE_SEARCH_README_SQL
+    }
+
+    pub fn get_search_document_delete_query() -> &'static str {
+        DELETE_SEARCH_DOCUMENT_SQL
+    }
+
+    pub fn get_search_cards_query() -> &'static str {
+        SEARCH_CARDS_SQL
+    }
+
+    /// Boolean-mode query requiring every term, matched as a prefix
+    pub fn get_search_match(terms: &[String]) -> String {
+        terms
+            .iter()
+            .map(|term| format!("+{term}*"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    pub fn get_trash_record_insert_query() -> &'static str {
+        INSERT_TRASH_RECORD_SQL
+    }
+
+    pub fn get_trash_record_query() -> &'static str {
+        GET_TRASH_RECORD_SQL
+    }
+
+    pub fn get_list_trash_query() -> &'static str {
+        LIST_TRASH_SQL
+    }
+
+    pub fn get_trash_record_delete_query() -> &'static str {
+        DELETE_TRASH_RECORD_SQL
+    }
+
+    pub fn get_retention_policy_upsert_query() -> &'static str {
+        UPSERT_RETENTION_POLICY_SQL
+    }
+
+    pub fn get_retention_policy_query() -> &'static str {
+        GET_RETENTION_POLICY_SQL
+    }
+
+    pub fn get_retention_policies_query() -> &'static str {
+        GET_RETENTION_POLICIES_SQL
+    }
+
+    pub fn get_retention_policy_delete_query() -> &'static str {
+        DELETE_RETENTION_POLICY_SQL
+    }
+
+    pub fn get_card_alias_upsert_query() -> &'static str {
+        UPSERT_CARD_ALIAS_SQL
+    }
+
+    pub fn get_card_alias_query() -> &'static str {
+        GET_CARD_ALIAS_SQL
+    }
+
+    pub fn get_card_aliases_query() -> &'static str {
+        GET_CARD_ALIASES_SQL
+    }
+
+    pub fn get_card_alias_delete_query() -> &'static str {
+        DELETE_CARD_ALIAS_SQL
+    }
+
+    pub fn get_agent_job_upsert_query() -> &'static str {
+        UPSERT_AGENT_JOB_SQL
+    }
+
+    pub fn get_agent_job_query() -> &'static str {
+        GET_AGENT_JOB_SQL
+    }
+
+    pub fn get_fail_incomplete_agent_jobs_query() -> &'static str {
+        FAIL_INCOMPLETE_AGENT_JOBS_SQL
+    }
+
+    pub fn get_delete_expired_agent_jobs_query() -> &'static str {
+        DELETE_EXPIRED_AGENT_JOBS_SQL
+    }
+
+    pub fn get_heartbeat_agent_jobs_query() -> &'static str {
+        HEARTBEAT_AGENT_JOBS_SQL
+    }
+
+    pub fn get_insert_registry_event_query() -> &'static str {
+        INSERT_REGISTRY_EVENT_SQL
+    }
+
+    pub fn get_get_registry_event_query() -> &'static str {
+        GET_REGISTRY_EVENT_SQL
+    }
+
+    pub fn get_claim_undispatched_events_query() -> &'static str {
+        CLAIM_UNDISPATCHED_EVENTS_SQL
+    }
+
+    pub fn get_get_claimed_events_query() -> &'static str {
+        GET_CLAIMED_EVENTS_SQL
+    }
+
+    pub fn get_release_event_claim_query() -> &'static str {
+        RELEASE_EVENT_CLAIM_SQL
+    }
+
+    pub fn get_mark_event_dispatched_query() -> &'static str {
+        MARK_EVENT_DISPATCHED_SQL
+    }
+
+    pub fn get_insert_webhook_query() -> &'static str {
+        INSERT_WEBHOOK_SQL
+    }
+
+    pub fn get_get_webhook_query() -> &'static str {
+        GET_WEBHOOK_SQL
+    }
+
+    pub fn get_list_webhooks_query() -> &'static str {
+        LIST_WEBHOOKS_SQL
+    }
+
+    pub fn get_delete_webhook_query() -> &'static str {
+        DELETE_WEBHOOK_SQL
+    }
+
+    pub fn get_delete_webhook_deliveries_query() -> &'static str {
+        DELETE_WEBHOOK_DELIVERIES_SQL
+    }
+
+    pub fn get_insert_webhook_delivery_query() -> &'static str {
+        INSERT_WEBHOOK_DELIVERY_SQL
+    }
+
+    pub fn get_claim_due_webhook_deliveries_query() -> &'static str {
+        CLAIM_DUE_WEBHOOK_DELIVERIES_SQL
+    }
+
+    pub fn get_get_claimed_webhook_deliveries_query() -> &'static str {
+        GET_CLAIMED_WEBHOOK_DELIVERIES_SQL
+    }
+
+    pub fn get_update_webhook_delivery_query() -> &'static str {
+        UPDATE_WEBHOOK_DELIVERY_SQL
+    }
+
+    pub fn get_list_webhook_deliveries_query() -> &'static str {
+        LIST_WEBHOOK_DELIVERIES_SQL
+    }
+
+    pub fn get_lineage_edges_query() -> &'static str {
+        GET_LINEAGE_EDGES_SQL
+    }
+
+    pub fn get_lineage_node_query(table: &CardTable) -> String {
+        format!("SELECT uid, space, name, version FROM {table} WHERE uid = ?")
+    }
+
     pub fn get_user_delete_query() -> &'static str {
         DELETE_USER_SQL
     }
@@ -695,6 +1035,10 @@ impl MySqlQueryHelper {
         INSERT_AUDIT_EVENT_SQL
     }
 
+    pub fn get_audit_events_query() -> &'static str {
+        QUERY_AUDIT_EVENTS_SQL
+    }
+
     pub fn get_load_card_query(
         table: &CardTable,
         query_args: &CardQueryArgs,
@@ -707,7 +1051,7 @@ impl MySqlQueryHelper {
             "WITH query_cards AS (
                 {}
             )
-            SELECT a.uid, a.space, a.registry_type, a.encrypted_key, a.storage_key
+            SELECT a.uid, a.space, a.registry_type, a.encrypted_key, a.storage_key, a.key_version, a.manifest_sha256
             FROM {} as a
             INNER JOIN query_cards as b 
                 ON a.uid = b.uid;",
@@ -722,6 +1066,22 @@ impl MySqlQueryHelper {
         DELETE_ARTIFACT_KEY_SQL
     }
 
+    pub fn get_artifact_keys_query() -> &'static str {
+        GET_ARTIFACT_KEYS_SQL
+    }
+
+    pub fn get_artifact_keys_to_rewrap_query() -> &'static str {
+        GET_ARTIFACT_KEYS_TO_REWRAP_SQL
+    }
+
+    pub fn get_rewrap_artifact_key_query() -> &'static str {
+        REWRAP_ARTIFACT_KEY_SQL
+    }
+
+    pub fn get_set_artifact_manifest_query() -> &'static str {
+        SET_ARTIFACT_MANIFEST_SQL
+    }
+
     pub fn get_all_space_stats_query() -> &'static str {
         GET_ALL_SPACE_STATS_SQL
     }
@@ -750,6 +1110,50 @@ impl MySqlQueryHelper {
         DELETE_SPACE_NAME_RECORD_SQL
     }
 
+    pub fn get_set_space_quota_query() -> &'static str {
+        SET_SPACE_QUOTA_SQL
+    }
+
+    pub fn get_add_space_usage_query() -> &'static str {
+        ADD_SPACE_USAGE_SQL
+    }
+
+    pub fn get_space_usage_query() -> &'static str {
+        GET_SPACE_USAGE_SQL
+    }
+
+    pub fn get_space_bytes_query() -> &'static str {
+        GET_SPACE_BYTES_SQL
+    }
+
+    pub fn get_lock_space_record_query() -> &'static str {
+        LOCK_SPACE_RECORD_SQL
+    }
+
+    pub fn get_set_space_usage_query() -> &'static str {
+        SET_SPACE_USAGE_SQL
+    }
+
+    pub fn get_insert_upload_reservation_query() -> &'static str {
+        INSERT_UPLOAD_RESERVATION_SQL
+    }
+
+    pub fn get_upload_reservation_query() -> &'static str {
+        GET_UPLOAD_RESERVATION_SQL
+    }
+
+    pub fn get_delete_upload_reservation_query() -> &'static str {
+        DELETE_UPLOAD_RESERVATION_SQL
+    }
+
+    pub fn get_upload_reservations_query() -> &'static str {
+        GET_UPLOAD_RESERVATIONS_SQL
+    }
+
+    pub fn get_delete_stale_upload_reservations_query() -> &'static str {
+        DELETE_STALE_UPLOAD_RESERVATIONS_SQL
+    }
+
     pub fn get_evaluation_record_insert_query() -> &'static str {
         INSERT_EVALUATION_RECORD_SQL
     }
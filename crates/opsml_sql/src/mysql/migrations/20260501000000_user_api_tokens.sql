@@ -0,0 +1,14 @@
+-- Long-lived, revocable API tokens for service accounts
+CREATE TABLE IF NOT EXISTS opsml_user_api_token (
+    token_id VARCHAR(64) NOT NULL PRIMARY KEY,
+    username VARCHAR(255) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    token_hash VARCHAR(255) NOT NULL,
+    permissions JSON NOT NULL DEFAULT ('[]'),
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    expires_at DATETIME,
+    last_used_at DATETIME,
+    revoked BOOLEAN NOT NULL DEFAULT FALSE,
+    INDEX idx_user_api_token_username (username),
+    FOREIGN KEY (username) REFERENCES opsml_user (username) ON DELETE CASCADE
+);
@@ -0,0 +1,26 @@
+-- Groups with space-scoped roles, replacing flat per-user permission strings
+CREATE TABLE IF NOT EXISTS opsml_group (
+    name VARCHAR(255) NOT NULL PRIMARY KEY,
+    description TEXT NOT NULL,
+    sso_groups JSON NOT NULL DEFAULT ('[]'),
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS opsml_group_role (
+    group_name VARCHAR(255) NOT NULL,
+    space VARCHAR(255) NOT NULL,
+    role VARCHAR(32) NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (group_name, space),
+    FOREIGN KEY (group_name) REFERENCES opsml_group (name) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS opsml_group_member (
+    group_name VARCHAR(255) NOT NULL,
+    username VARCHAR(255) NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (group_name, username),
+    INDEX idx_group_member_username (username),
+    FOREIGN KEY (group_name) REFERENCES opsml_group (name) ON DELETE CASCADE,
+    FOREIGN KEY (username) REFERENCES opsml_user (username) ON DELETE CASCADE
+);
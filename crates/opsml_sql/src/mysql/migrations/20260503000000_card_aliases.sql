@@ -0,0 +1,12 @@
+-- Movable stage aliases (e.g. production, staging) pointing at a concrete card version
+CREATE TABLE IF NOT EXISTS opsml_card_alias (
+    space VARCHAR(255) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    registry_type VARCHAR(64) NOT NULL,
+    alias VARCHAR(64) NOT NULL,
+    uid VARCHAR(64) NOT NULL,
+    version VARCHAR(255) NOT NULL,
+    updated_by VARCHAR(255) NOT NULL DEFAULT 'guest',
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (space, name, registry_type, alias)
+);
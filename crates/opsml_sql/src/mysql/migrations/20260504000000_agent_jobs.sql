@@ -0,0 +1,14 @@
+-- Agent invocations dispatched asynchronously, kept so job status survives restarts
+CREATE TABLE IF NOT EXISTS opsml_agent_job (
+    job_id VARCHAR(64) PRIMARY KEY,
+    agent_id VARCHAR(255) NOT NULL,
+    owner VARCHAR(255) NOT NULL,
+    status VARCHAR(32) NOT NULL,
+    result JSON,
+    error TEXT,
+    duration_ms BIGINT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    INDEX idx_opsml_agent_job_status (status),
+    INDEX idx_opsml_agent_job_updated_at (updated_at)
+);
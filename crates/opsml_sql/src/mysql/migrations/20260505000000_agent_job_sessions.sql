@@ -0,0 +1,3 @@
+-- Track the session an agent job ran in and the prompt it is waiting on
+ALTER TABLE opsml_agent_job ADD COLUMN session_id VARCHAR(64);
+ALTER TABLE opsml_agent_job ADD COLUMN prompt TEXT;
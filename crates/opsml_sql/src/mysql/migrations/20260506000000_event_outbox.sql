@@ -0,0 +1,40 @@
+-- Durable outbox of registry events and the webhooks they are delivered to
+CREATE TABLE IF NOT EXISTS opsml_event_outbox (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    event_id VARCHAR(64) NOT NULL UNIQUE,
+    event_type VARCHAR(32) NOT NULL,
+    space VARCHAR(255) NOT NULL,
+    registry_type VARCHAR(32),
+    resource_id VARCHAR(1024) NOT NULL,
+    payload JSON NOT NULL,
+    username VARCHAR(255) NOT NULL,
+    dispatched BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    INDEX idx_opsml_event_outbox_dispatched (dispatched)
+);
+
+CREATE TABLE IF NOT EXISTS opsml_webhook (
+    id VARCHAR(64) PRIMARY KEY,
+    space VARCHAR(255) NOT NULL,
+    url VARCHAR(2048) NOT NULL,
+    event_types JSON NOT NULL,
+    secret VARCHAR(255) NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by VARCHAR(255) NOT NULL,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    INDEX idx_opsml_webhook_space (space)
+);
+
+CREATE TABLE IF NOT EXISTS opsml_webhook_delivery (
+    id BIGINT AUTO_INCREMENT PRIMARY KEY,
+    webhook_id VARCHAR(64) NOT NULL,
+    event_id VARCHAR(64) NOT NULL,
+    status VARCHAR(32) NOT NULL DEFAULT 'pending',
+    attempts INT NOT NULL DEFAULT 0,
+    next_attempt_at DATETIME NOT NULL,
+    last_error TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE KEY uq_opsml_webhook_delivery (webhook_id, event_id),
+    INDEX idx_opsml_webhook_delivery_status (status, next_attempt_at)
+);
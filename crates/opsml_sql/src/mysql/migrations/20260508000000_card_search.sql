@@ -0,0 +1,39 @@
+-- Full-text search index over cards. One document per card version
+CREATE TABLE IF NOT EXISTS opsml_card_search (
+    uid VARCHAR(64) PRIMARY KEY,
+    registry_type VARCHAR(64) NOT NULL,
+    space VARCHAR(255) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    version VARCHAR(255) NOT NULL,
+    tags TEXT NOT NULL,
+    description TEXT,
+    readme MEDIUMTEXT,
+    body MEDIUMTEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    INDEX idx_opsml_card_search_card (registry_type, space, name),
+    FULLTEXT INDEX idx_opsml_card_search_fulltext (name, space, tags, description, readme, body)
+);
+
+-- Index existing cards. READMEs and skill bodies are picked up the next time they are written
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'data', space, name, version, COALESCE(CAST(tags AS CHAR), ''), NULL, created_at FROM opsml_data_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'model', space, name, version, COALESCE(CAST(tags AS CHAR), ''), NULL, created_at FROM opsml_model_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'experiment', space, name, version, COALESCE(CAST(tags AS CHAR), ''), NULL, created_at FROM opsml_experiment_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'audit', space, name, version, COALESCE(CAST(tags AS CHAR), ''), NULL, created_at FROM opsml_audit_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'prompt', space, name, version, COALESCE(CAST(tags AS CHAR), ''), NULL, created_at FROM opsml_prompt_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'service', space, name, version, COALESCE(CAST(tags AS CHAR), ''), NULL, created_at FROM opsml_service_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'mcp', space, name, version, COALESCE(CAST(tags AS CHAR), ''), NULL, created_at FROM opsml_mcp_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'agent', space, name, version, COALESCE(CAST(tags AS CHAR), ''), NULL, created_at FROM opsml_agent_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'skill', space, name, version, COALESCE(CAST(tags AS CHAR), ''), description, created_at FROM opsml_skill_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'subagent', space, name, version, COALESCE(CAST(tags AS CHAR), ''), description, created_at FROM opsml_subagent_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'tool', space, name, version, COALESCE(CAST(tags AS CHAR), ''), description, created_at FROM opsml_tool_registry;
@@ -0,0 +1,24 @@
+-- Cards moved to the trash by a delete or a retention policy. `record` holds the deleted row so it
+-- can be restored; artifacts and the artifact key are kept until the card is purged
+CREATE TABLE IF NOT EXISTS opsml_card_trash (
+    uid VARCHAR(64) PRIMARY KEY,
+    registry_type VARCHAR(64) NOT NULL,
+    space VARCHAR(255) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    version VARCHAR(255) NOT NULL,
+    record JSON NOT NULL,
+    reason VARCHAR(32) NOT NULL DEFAULT 'deleted',
+    deleted_by VARCHAR(255) NOT NULL DEFAULT 'guest',
+    deleted_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    INDEX idx_opsml_card_trash_space (space, deleted_at)
+);
+
+-- Per-space retention rules applied by the server's retention task
+CREATE TABLE IF NOT EXISTS opsml_retention_policy (
+    space VARCHAR(255) PRIMARY KEY,
+    keep_last_versions INT,
+    purge_trashed_after_days INT,
+    protect_aliased BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_by VARCHAR(255) NOT NULL DEFAULT 'guest',
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,12 @@
+-- Storage quotas set by admins. NULL means no limit
+ALTER TABLE opsml_space ADD COLUMN soft_quota_bytes BIGINT;
+ALTER TABLE opsml_space ADD COLUMN hard_quota_bytes BIGINT;
+
+-- Artifact bytes uploaded per space and registry, kept up to date by the files router
+CREATE TABLE IF NOT EXISTS opsml_space_usage (
+    space VARCHAR(255) NOT NULL,
+    registry_type VARCHAR(64) NOT NULL,
+    bytes BIGINT NOT NULL DEFAULT 0,
+    updated_at DATETIME DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+    PRIMARY KEY (space, registry_type)
+);
@@ -0,0 +1,8 @@
+-- Bytes reserved for uploads that went straight to object storage and have not been completed yet
+CREATE TABLE IF NOT EXISTS opsml_upload_reservation (
+    path VARCHAR(512) PRIMARY KEY,
+    space VARCHAR(255) NOT NULL,
+    registry_type VARCHAR(64) NOT NULL,
+    bytes BIGINT NOT NULL DEFAULT 0,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
\ No newline at end of file
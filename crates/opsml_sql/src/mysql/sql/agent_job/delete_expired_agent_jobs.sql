@@ -0,0 +1 @@
+DELETE FROM opsml_agent_job WHERE status NOT IN ('pending', 'running') AND updated_at < ?;
\ No newline at end of file
@@ -0,0 +1 @@
+UPDATE opsml_agent_job SET updated_at = CURRENT_TIMESTAMP WHERE status IN ('pending', 'running') AND instance_id = ?;
\ No newline at end of file
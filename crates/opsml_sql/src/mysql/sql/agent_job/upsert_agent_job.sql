@@ -0,0 +1,2 @@
+INSERT INTO opsml_agent_job (job_id, agent_id, owner, status, result, error, duration_ms, session_id, prompt, instance_id, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+ON DUPLICATE KEY UPDATE status = VALUES(status), result = VALUES(result), error = VALUES(error), duration_ms = VALUES(duration_ms), session_id = VALUES(session_id), prompt = VALUES(prompt), updated_at = CURRENT_TIMESTAMP;
\ No newline at end of file
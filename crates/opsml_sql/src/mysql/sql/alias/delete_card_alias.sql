@@ -0,0 +1 @@
+DELETE FROM opsml_card_alias WHERE space = ? AND name = ? AND registry_type = ? AND alias = ?;
\ No newline at end of file
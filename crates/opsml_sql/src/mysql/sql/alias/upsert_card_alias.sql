@@ -0,0 +1,2 @@
+INSERT INTO opsml_card_alias (space, name, registry_type, alias, uid, version, updated_by) VALUES (?, ?, ?, ?, ?, ?, ?)
+ON DUPLICATE KEY UPDATE uid = VALUES(uid), version = VALUES(version), updated_by = VALUES(updated_by), updated_at = CURRENT_TIMESTAMP;
\ No newline at end of file
@@ -1 +1 @@
-INSERT INTO opsml_artifact_key (uid, space, registry_type, encrypted_key, storage_key) VALUES (?, ?, ?, ?, ?);
\ No newline at end of file
+INSERT INTO opsml_artifact_key (uid, space, registry_type, encrypted_key, storage_key, key_version) VALUES (?, ?, ?, ?, ?, ?);
\ No newline at end of file
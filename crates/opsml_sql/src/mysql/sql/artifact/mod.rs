@@ -2,10 +2,11 @@ use crate::{mysql::helper::MySqlQueryHelper, traits::ArtifactLogicTrait};
 
 use crate::error::SqlError;
 
-use crate::schemas::ArtifactSqlRecord;
+use crate::schemas::{ArtifactSqlRecord, UnkeyedCard};
 use async_trait::async_trait;
 use opsml_types::{
     RegistryType,
+    cards::CardTable,
     contracts::{ArtifactKey, ArtifactQueryArgs, ArtifactRecord},
 };
 use sqlx::{MySql, Pool};
@@ -54,6 +55,7 @@ impl ArtifactLogicTrait for ArtifactLogicMySqlClient {
             .bind(key.registry_type.to_string())
             .bind(key.encrypted_key.clone())
             .bind(&key.storage_key)
+            .bind(key.key_version as i32)
             .execute(&self.pool)
             .await?;
 
@@ -67,11 +69,12 @@ impl ArtifactLogicTrait for ArtifactLogicMySqlClient {
     ) -> Result<ArtifactKey, SqlError> {
         let query = MySqlQueryHelper::get_artifact_key_select_query();
 
-        let key: (String, String, String, Vec<u8>, String) = sqlx::query_as(query)
-            .bind(uid)
-            .bind(registry_type)
-            .fetch_one(&self.pool)
-            .await?;
+        let key: (String, String, String, Vec<u8>, String, i32, Option<String>) =
+            sqlx::query_as(query)
+                .bind(uid)
+                .bind(registry_type)
+                .fetch_one(&self.pool)
+                .await?;
 
         Ok(ArtifactKey {
             uid: key.0,
@@ -79,6 +82,8 @@ impl ArtifactLogicTrait for ArtifactLogicMySqlClient {
             registry_type: RegistryType::from_string(&key.2)?,
             encrypted_key: key.3,
             storage_key: key.4,
+            key_version: key.5 as u32,
+            manifest_sha256: key.6,
         })
     }
 
@@ -89,11 +94,12 @@ impl ArtifactLogicTrait for ArtifactLogicMySqlClient {
     ) -> Result<Option<ArtifactKey>, SqlError> {
         let query = MySqlQueryHelper::get_artifact_key_from_storage_path_query();
 
-        let key: Option<(String, String, String, Vec<u8>, String)> = sqlx::query_as(query)
-            .bind(storage_path)
-            .bind(registry_type)
-            .fetch_optional(&self.pool)
-            .await?;
+        let key: Option<(String, String, String, Vec<u8>, String, i32, Option<String>)> =
+            sqlx::query_as(query)
+                .bind(storage_path)
+                .bind(registry_type)
+                .fetch_optional(&self.pool)
+                .await?;
 
         return match key {
             Some(k) => Ok(Some(ArtifactKey {
@@ -102,6 +108,8 @@ impl ArtifactLogicTrait for ArtifactLogicMySqlClient {
                 registry_type: RegistryType::from_string(&k.2)?,
                 encrypted_key: k.3,
                 storage_key: k.4,
+                key_version: k.5 as u32,
+                manifest_sha256: k.6,
             })),
             None => Ok(None),
         };
@@ -111,6 +119,7 @@ impl ArtifactLogicTrait for ArtifactLogicMySqlClient {
         let query = MySqlQueryHelper::get_artifact_key_update_query();
         sqlx::query(query)
             .bind(key.encrypted_key.clone())
+            .bind(key.key_version as i32)
             .bind(&key.uid)
             .bind(key.registry_type.to_string())
             .execute(&self.pool)
@@ -119,6 +128,118 @@ impl ArtifactLogicTrait for ArtifactLogicMySqlClient {
         Ok(())
     }
 
+    async fn get_artifact_keys(
+        &self,
+        after_uid: &str,
+        limit: i64,
+    ) -> Result<Vec<ArtifactKey>, SqlError> {
+        let query = MySqlQueryHelper::get_artifact_keys_query();
+
+        let keys: Vec<(String, String, String, Vec<u8>, String, i32, Option<String>)> =
+            sqlx::query_as(query)
+                .bind(after_uid)
+                .bind(limit)
+                .fetch_all(&self.pool)
+                .await?;
+
+        keys.into_iter()
+            .map(|k| {
+                Ok(ArtifactKey {
+                    uid: k.0,
+                    space: k.1,
+                    registry_type: RegistryType::from_string(&k.2)?,
+                    encrypted_key: k.3,
+                    storage_key: k.4,
+                    key_version: k.5 as u32,
+                    manifest_sha256: k.6,
+                })
+            })
+            .collect()
+    }
+
+    async fn get_cards_without_key(
+        &self,
+        table: &CardTable,
+        after_uid: &str,
+        limit: i64,
+    ) -> Result<Vec<UnkeyedCard>, SqlError> {
+        let query = MySqlQueryHelper::get_cards_without_key_query(table);
+
+        let cards = sqlx::query_as(&query)
+            .bind(after_uid)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(cards)
+    }
+
+    async fn get_artifact_keys_to_rewrap(
+        &self,
+        key_version: u32,
+        after_uid: &str,
+        limit: i64,
+    ) -> Result<Vec<ArtifactKey>, SqlError> {
+        let query = MySqlQueryHelper::get_artifact_keys_to_rewrap_query();
+
+        let keys: Vec<(String, String, String, Vec<u8>, String, i32, Option<String>)> =
+            sqlx::query_as(query)
+                .bind(key_version as i32)
+                .bind(after_uid)
+                .bind(limit)
+                .fetch_all(&self.pool)
+                .await?;
+
+        keys.into_iter()
+            .map(|k| {
+                Ok(ArtifactKey {
+                    uid: k.0,
+                    space: k.1,
+                    registry_type: RegistryType::from_string(&k.2)?,
+                    encrypted_key: k.3,
+                    storage_key: k.4,
+                    key_version: k.5 as u32,
+                    manifest_sha256: k.6,
+                })
+            })
+            .collect()
+    }
+
+    async fn rewrap_artifact_key(
+        &self,
+        key: &ArtifactKey,
+        previous_version: u32,
+    ) -> Result<bool, SqlError> {
+        let query = MySqlQueryHelper::get_rewrap_artifact_key_query();
+        let result = sqlx::query(query)
+            .bind(key.encrypted_key.clone())
+            .bind(key.key_version as i32)
+            .bind(&key.uid)
+            .bind(key.registry_type.to_string())
+            .bind(previous_version as i32)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn set_artifact_manifest(
+        &self,
+        uid: &str,
+        registry_type: &str,
+        manifest_sha256: &str,
+    ) -> Result<(), SqlError> {
+        let query = MySqlQueryHelper::get_set_artifact_manifest_query();
+        sqlx::query(query)
+            .bind(manifest_sha256)
+            .bind(uid)
+            .bind(registry_type)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
     async fn delete_artifact_key(&self, uid: &str, registry_type: &str) -> Result<(), SqlError> {
         let query = MySqlQueryHelper::get_artifact_key_delete_query();
         sqlx::query(query)
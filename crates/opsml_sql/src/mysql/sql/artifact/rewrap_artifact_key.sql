@@ -0,0 +1 @@
+UPDATE opsml_artifact_key SET encrypted_key = ?, key_version = ? WHERE uid = ? AND registry_type = ? AND key_version = ?;
\ No newline at end of file
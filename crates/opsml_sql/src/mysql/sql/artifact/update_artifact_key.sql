@@ -1 +1 @@
-UPDATE opsml_artifact_key SET encrypted_key = ?, created_at = CURRENT_TIMESTAMP WHERE uid = ? AND registry_type = ?;
\ No newline at end of file
+UPDATE opsml_artifact_key SET encrypted_key = ?, key_version = ?, created_at = CURRENT_TIMESTAMP WHERE uid = ? AND registry_type = ?;
\ No newline at end of file
@@ -0,0 +1,28 @@
+SELECT
+    id,
+    created_at,
+    username,
+    client_ip,
+    user_agent,
+    operation,
+    resource_type,
+    resource_id,
+    access_location,
+    status,
+    error_message,
+    metadata,
+    registry_type,
+    route
+FROM opsml_audit_event
+WHERE 1=1
+    AND (? IS NULL OR username = ?)
+    AND (? IS NULL OR operation = ?)
+    AND (? IS NULL OR resource_type = ?)
+    AND (? IS NULL OR resource_id = ?)
+    AND (? IS NULL OR registry_type = ?)
+    AND (? IS NULL OR status = ?)
+    AND (? IS NULL OR created_at >= ?)
+    AND (? IS NULL OR created_at <= ?)
+    AND (? IS NULL OR id < ?)
+ORDER BY id DESC
+LIMIT ?;
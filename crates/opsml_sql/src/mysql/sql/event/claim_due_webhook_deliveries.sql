@@ -0,0 +1 @@
+UPDATE opsml_webhook_delivery SET claim_id = ?, next_attempt_at = ? WHERE status = 'pending' AND next_attempt_at <= ? ORDER BY next_attempt_at LIMIT ?;
\ No newline at end of file
@@ -0,0 +1 @@
+UPDATE opsml_event_outbox SET claim_id = ?, claimed_until = ? WHERE dispatched = FALSE AND (claimed_until IS NULL OR claimed_until <= ?) ORDER BY id LIMIT ?;
\ No newline at end of file
@@ -0,0 +1 @@
+DELETE FROM opsml_webhook_delivery WHERE webhook_id = ?;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT id, space, url, event_types, secret, active, created_by, created_at FROM opsml_webhook WHERE id = ?;
\ No newline at end of file
@@ -0,0 +1 @@
+INSERT INTO opsml_event_outbox (event_id, event_type, space, registry_type, resource_id, payload, username, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?);
\ No newline at end of file
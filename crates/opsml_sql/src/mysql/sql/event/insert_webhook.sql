@@ -0,0 +1 @@
+INSERT INTO opsml_webhook (id, space, url, event_types, secret, active, created_by, created_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?);
\ No newline at end of file
@@ -0,0 +1 @@
+INSERT IGNORE INTO opsml_webhook_delivery (webhook_id, event_id, next_attempt_at) VALUES (?, ?, ?);
\ No newline at end of file
@@ -0,0 +1 @@
+UPDATE opsml_event_outbox SET claim_id = NULL, claimed_until = NULL WHERE claim_id = ? AND dispatched = FALSE;
\ No newline at end of file
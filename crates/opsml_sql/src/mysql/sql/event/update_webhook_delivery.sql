@@ -0,0 +1 @@
+UPDATE opsml_webhook_delivery SET status = ?, attempts = ?, next_attempt_at = ?, last_error = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?;
\ No newline at end of file
@@ -0,0 +1 @@
+DELETE FROM opsml_group_member WHERE group_name = ? AND username = ?;
\ No newline at end of file
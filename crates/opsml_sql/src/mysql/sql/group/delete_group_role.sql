@@ -0,0 +1 @@
+DELETE FROM opsml_group_role WHERE group_name = ? AND space = ?;
\ No newline at end of file
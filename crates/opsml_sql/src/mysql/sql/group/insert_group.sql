@@ -0,0 +1 @@
+INSERT INTO opsml_group (name, description, sso_groups) VALUES (?, ?, ?);
\ No newline at end of file
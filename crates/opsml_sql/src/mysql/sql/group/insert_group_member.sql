@@ -0,0 +1 @@
+INSERT IGNORE INTO opsml_group_member (group_name, username) VALUES (?, ?);
\ No newline at end of file
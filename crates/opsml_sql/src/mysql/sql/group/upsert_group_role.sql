@@ -0,0 +1,2 @@
+INSERT INTO opsml_group_role (group_name, space, role) VALUES (?, ?, ?)
+ON DUPLICATE KEY UPDATE role = VALUES(role);
\ No newline at end of file
@@ -0,0 +1,82 @@
+SELECT CAST(r.experimentcard_uid AS CHAR(64)) AS source_uid, 'experiment' AS source_type, CAST(r.uid AS CHAR(64)) AS target_uid, 'data' AS target_type, 'produced_by' AS relation
+FROM opsml_data_registry AS r
+WHERE r.experimentcard_uid IS NOT NULL AND (r.uid = ? OR r.experimentcard_uid = ?)
+UNION ALL
+SELECT CAST(r.uid AS CHAR(64)) AS source_uid, 'data' AS source_type, CAST(r.auditcard_uid AS CHAR(64)) AS target_uid, 'audit' AS target_type, 'audited_by' AS relation
+FROM opsml_data_registry AS r
+WHERE r.auditcard_uid IS NOT NULL AND (r.uid = ? OR r.auditcard_uid = ?)
+UNION ALL
+SELECT CAST(r.datacard_uid AS CHAR(64)) AS source_uid, 'data' AS source_type, CAST(r.uid AS CHAR(64)) AS target_uid, 'model' AS target_type, 'trained_on' AS relation
+FROM opsml_model_registry AS r
+WHERE r.datacard_uid IS NOT NULL AND (r.uid = ? OR r.datacard_uid = ?)
+UNION ALL
+SELECT CAST(r.experimentcard_uid AS CHAR(64)) AS source_uid, 'experiment' AS source_type, CAST(r.uid AS CHAR(64)) AS target_uid, 'model' AS target_type, 'produced_by' AS relation
+FROM opsml_model_registry AS r
+WHERE r.experimentcard_uid IS NOT NULL AND (r.uid = ? OR r.experimentcard_uid = ?)
+UNION ALL
+SELECT CAST(r.uid AS CHAR(64)) AS source_uid, 'model' AS source_type, CAST(r.auditcard_uid AS CHAR(64)) AS target_uid, 'audit' AS target_type, 'audited_by' AS relation
+FROM opsml_model_registry AS r
+WHERE r.auditcard_uid IS NOT NULL AND (r.uid = ? OR r.auditcard_uid = ?)
+UNION ALL
+SELECT CAST(r.experimentcard_uid AS CHAR(64)) AS source_uid, 'experiment' AS source_type, CAST(r.uid AS CHAR(64)) AS target_uid, 'prompt' AS target_type, 'produced_by' AS relation
+FROM opsml_prompt_registry AS r
+WHERE r.experimentcard_uid IS NOT NULL AND (r.uid = ? OR r.experimentcard_uid = ?)
+UNION ALL
+SELECT CAST(r.uid AS CHAR(64)) AS source_uid, 'prompt' AS source_type, CAST(r.auditcard_uid AS CHAR(64)) AS target_uid, 'audit' AS target_type, 'audited_by' AS relation
+FROM opsml_prompt_registry AS r
+WHERE r.auditcard_uid IS NOT NULL AND (r.uid = ? OR r.auditcard_uid = ?)
+UNION ALL
+SELECT CAST(r.uid AS CHAR(64)) AS source_uid, 'experiment' AS source_type, CAST(j.card_uid AS CHAR(64)) AS target_uid, 'data' AS target_type, 'produced_by' AS relation
+FROM opsml_experiment_registry AS r
+CROSS JOIN JSON_TABLE(r.datacard_uids, '$[*]' COLUMNS(card_uid VARCHAR(64) PATH '$')) AS j
+WHERE r.uid = ? OR j.card_uid = ?
+UNION ALL
+SELECT CAST(r.uid AS CHAR(64)) AS source_uid, 'experiment' AS source_type, CAST(j.card_uid AS CHAR(64)) AS target_uid, 'model' AS target_type, 'produced_by' AS relation
+FROM opsml_experiment_registry AS r
+CROSS JOIN JSON_TABLE(r.modelcard_uids, '$[*]' COLUMNS(card_uid VARCHAR(64) PATH '$')) AS j
+WHERE r.uid = ? OR j.card_uid = ?
+UNION ALL
+SELECT CAST(r.uid AS CHAR(64)) AS source_uid, 'experiment' AS source_type, CAST(j.card_uid AS CHAR(64)) AS target_uid, 'prompt' AS target_type, 'produced_by' AS relation
+FROM opsml_experiment_registry AS r
+CROSS JOIN JSON_TABLE(r.promptcard_uids, '$[*]' COLUMNS(card_uid VARCHAR(64) PATH '$')) AS j
+WHERE r.uid = ? OR j.card_uid = ?
+UNION ALL
+SELECT CAST(r.uid AS CHAR(64)) AS source_uid, 'experiment' AS source_type, CAST(j.card_uid AS CHAR(64)) AS target_uid, 'service' AS target_type, 'produced_by' AS relation
+FROM opsml_experiment_registry AS r
+CROSS JOIN JSON_TABLE(r.service_card_uids, '$[*]' COLUMNS(card_uid VARCHAR(64) PATH '$')) AS j
+WHERE r.uid = ? OR j.card_uid = ?
+UNION ALL
+SELECT CAST(r.uid AS CHAR(64)) AS source_uid, 'experiment' AS source_type, CAST(j.card_uid AS CHAR(64)) AS target_uid, 'experiment' AS target_type, 'linked' AS relation
+FROM opsml_experiment_registry AS r
+CROSS JOIN JSON_TABLE(r.experimentcard_uids, '$[*]' COLUMNS(card_uid VARCHAR(64) PATH '$')) AS j
+WHERE r.uid = ? OR j.card_uid = ?
+UNION ALL
+SELECT CAST(j.card_uid AS CHAR(64)) AS source_uid, 'data' AS source_type, CAST(r.uid AS CHAR(64)) AS target_uid, 'audit' AS target_type, 'audited_by' AS relation
+FROM opsml_audit_registry AS r
+CROSS JOIN JSON_TABLE(r.datacard_uids, '$[*]' COLUMNS(card_uid VARCHAR(64) PATH '$')) AS j
+WHERE r.uid = ? OR j.card_uid = ?
+UNION ALL
+SELECT CAST(j.card_uid AS CHAR(64)) AS source_uid, 'model' AS source_type, CAST(r.uid AS CHAR(64)) AS target_uid, 'audit' AS target_type, 'audited_by' AS relation
+FROM opsml_audit_registry AS r
+CROSS JOIN JSON_TABLE(r.modelcard_uids, '$[*]' COLUMNS(card_uid VARCHAR(64) PATH '$')) AS j
+WHERE r.uid = ? OR j.card_uid = ?
+UNION ALL
+SELECT CAST(j.card_uid AS CHAR(64)) AS source_uid, 'experiment' AS source_type, CAST(r.uid AS CHAR(64)) AS target_uid, 'audit' AS target_type, 'audited_by' AS relation
+FROM opsml_audit_registry AS r
+CROSS JOIN JSON_TABLE(r.experimentcard_uids, '$[*]' COLUMNS(card_uid VARCHAR(64) PATH '$')) AS j
+WHERE r.uid = ? OR j.card_uid = ?
+UNION ALL
+SELECT CAST(j.card_uid AS CHAR(64)) AS source_uid, CAST(LOWER(j.card_type) AS CHAR(32)) AS source_type, CAST(r.uid AS CHAR(64)) AS target_uid, 'service' AS target_type, 'deployed_in' AS relation
+FROM opsml_service_registry AS r
+CROSS JOIN JSON_TABLE(r.cards, '$[*]' COLUMNS(card_uid VARCHAR(64) PATH '$.uid', card_type VARCHAR(32) PATH '$.registry_type')) AS j
+WHERE j.card_uid IS NOT NULL AND (r.uid = ? OR j.card_uid = ?)
+UNION ALL
+SELECT CAST(j.card_uid AS CHAR(64)) AS source_uid, CAST(LOWER(j.card_type) AS CHAR(32)) AS source_type, CAST(r.uid AS CHAR(64)) AS target_uid, 'mcp' AS target_type, 'deployed_in' AS relation
+FROM opsml_mcp_registry AS r
+CROSS JOIN JSON_TABLE(r.cards, '$[*]' COLUMNS(card_uid VARCHAR(64) PATH '$.uid', card_type VARCHAR(32) PATH '$.registry_type')) AS j
+WHERE j.card_uid IS NOT NULL AND (r.uid = ? OR j.card_uid = ?)
+UNION ALL
+SELECT CAST(j.card_uid AS CHAR(64)) AS source_uid, CAST(LOWER(j.card_type) AS CHAR(32)) AS source_type, CAST(r.uid AS CHAR(64)) AS target_uid, 'agent' AS target_type, 'deployed_in' AS relation
+FROM opsml_agent_registry AS r
+CROSS JOIN JSON_TABLE(r.cards, '$[*]' COLUMNS(card_uid VARCHAR(64) PATH '$.uid', card_type VARCHAR(32) PATH '$.registry_type')) AS j
+WHERE j.card_uid IS NOT NULL AND (r.uid = ? OR j.card_uid = ?)
\ No newline at end of file
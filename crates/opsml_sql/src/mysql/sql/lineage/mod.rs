@@ -0,0 +1,47 @@
+use crate::{mysql::helper::MySqlQueryHelper, traits::LineageLogicTrait};
+
+use crate::error::SqlError;
+use crate::schemas::schema::{LineageEdgeSqlRecord, LineageNodeSqlRecord};
+use async_trait::async_trait;
+use opsml_types::cards::CardTable;
+use opsml_types::contracts::LineageEdge;
+use sqlx::{MySql, Pool};
+
+#[derive(Debug, Clone)]
+pub struct LineageLogicMySqlClient {
+    pool: sqlx::Pool<MySql>,
+}
+impl LineageLogicMySqlClient {
+    pub fn new(pool: &Pool<MySql>) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
+
+#[async_trait]
+impl LineageLogicTrait for LineageLogicMySqlClient {
+    async fn get_lineage_edges(&self, uid: &str) -> Result<Vec<LineageEdge>, SqlError> {
+        let query = MySqlQueryHelper::get_lineage_edges_query();
+        // mysql has no numbered placeholders, so the uid is bound once per placeholder
+        let mut sql_query = sqlx::query_as::<_, LineageEdgeSqlRecord>(query);
+        for _ in 0..query.matches('?').count() {
+            sql_query = sql_query.bind(uid);
+        }
+        let records = sql_query.fetch_all(&self.pool).await?;
+
+        records.into_iter().map(LineageEdge::try_from).collect()
+    }
+
+    async fn get_lineage_node(
+        &self,
+        table: &CardTable,
+        uid: &str,
+    ) -> Result<Option<LineageNodeSqlRecord>, SqlError> {
+        let query = MySqlQueryHelper::get_lineage_node_query(table);
+        let record: Option<LineageNodeSqlRecord> = sqlx::query_as(&query)
+            .bind(uid)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(record)
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{mysql::helper::MySqlQueryHelper, traits::SearchLogicTrait};
+
+use crate::error::SqlError;
+use crate::schemas::schema::CardSearchSqlRecord;
+use crate::utils::highlight_terms;
+use async_trait::async_trait;
+use opsml_types::RegistryType;
+use opsml_types::contracts::{CardSearchDocument, CardSearchHit, CardSearchQuery, search_terms};
+use sqlx::{MySql, Pool};
+
+/// Number of words in a search highlight excerpt
+const HIGHLIGHT_WORDS: usize = 32;
+
+#[derive(Debug, Clone)]
+pub struct SearchLogicMySqlClient {
+    pool: sqlx::Pool<MySql>,
+}
+impl SearchLogicMySqlClient {
+    pub fn new(pool: &Pool<MySql>) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
+
+#[async_trait]
+impl SearchLogicTrait for SearchLogicMySqlClient {
+    async fn upsert_search_document(&self, document: &CardSearchDocument) -> Result<(), SqlError> {
+        let registry_type = document.registry_type.to_string();
+
+        // New versions inherit the readme, which is stored per card rather than per version
+        let readme = match &document.readme {
+            Some(readme) => Some(readme.clone()),
+            None => {
+                self.get_search_readme(&document.registry_type, &document.space, &document.name)
+                    .await?
+            }
+        };
+
+        sqlx::query(MySqlQueryHelper::get_search_document_upsert_query())
+            .bind(&document.uid)
+            .bind(&registry_type)
+            .bind(&document.space)
+            .bind(&document.name)
+            .bind(&document.version)
+            .bind(document.tags.join(" "))
+            .bind(&document.description)
+            .bind(readme)
+            .bind(&document.body)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_search_readme(
+        &self,
+        registry_type: &RegistryType,
+        space: &str,
+        name: &str,
+    ) -> Result<Option<String>, SqlError> {
+        Ok(
+            sqlx::query_scalar::<_, Option<String>>(MySqlQueryHelper::get_search_readme_query())
+                .bind(registry_type.to_string())
+                .bind(space)
+                .bind(name)
+                .fetch_optional(&self.pool)
+                .await?
+                .flatten(),
+        )
+    }
+
+    async fn update_search_readme(
+        &self,
+        registry_type: &RegistryType,
+        space: &str,
+        name: &str,
+        readme: &str,
+    ) -> Result<(), SqlError> {
+        sqlx::query(MySqlQueryHelper::get_search_readme_update_query())
+            .bind(readme)
+            .bind(registry_type.to_string())
+            .bind(space)
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_search_document(&self, uid: &str) -> Result<(), SqlError> {
+        sqlx::query(MySqlQueryHelper::get_search_document_delete_query())
+            .bind(uid)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn search_cards(
+        &self,
+        query: &CardSearchQuery,
+        spaces: Option<&[String]>,
+    ) -> Result<Vec<CardSearchHit>, SqlError> {
+        let terms = search_terms(&query.query);
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+        let registry_type = query.registry_type.as_ref().map(|r| r.to_string());
+
+        let match_expr = MySqlQueryHelper::get_search_match(&terms);
+        let spaces = spaces.map(serde_json::to_string).transpose()?;
+        let records: Vec<CardSearchSqlRecord> =
+            sqlx::query_as(MySqlQueryHelper::get_search_cards_query())
+                .bind(&match_expr)
+                .bind(&match_expr)
+                .bind(&registry_type)
+                .bind(&registry_type)
+                .bind(&query.space)
+                .bind(&query.space)
+                .bind(&spaces)
+                .bind(&spaces)
+                .bind(query.limit() as i64)
+                .bind(query.offset.unwrap_or(0) as i64)
+                .fetch_all(&self.pool)
+                .await?;
+
+        // MySQL has no highlight function; the query returns the indexed text instead
+        records
+            .into_iter()
+            .map(|mut record| {
+                record.highlight = record
+                    .highlight
+                    .as_deref()
+                    .and_then(|text| highlight_terms(text, &terms, HIGHLIGHT_WORDS));
+                CardSearchHit::try_from(record)
+            })
+            .collect()
+    }
+}
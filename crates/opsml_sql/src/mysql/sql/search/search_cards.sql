@@ -0,0 +1,16 @@
+SELECT
+    uid,
+    registry_type,
+    space,
+    name,
+    version,
+    CAST(MATCH (name, space, tags, description, readme, body) AGAINST (? IN BOOLEAN MODE) AS DOUBLE) AS score,
+    CONCAT_WS(' ', name, tags, description, readme, body) AS highlight
+FROM opsml_card_search
+WHERE MATCH (name, space, tags, description, readme, body) AGAINST (? IN BOOLEAN MODE)
+    AND (? IS NULL OR registry_type = ?)
+    AND (? IS NULL OR space = ?)
+    AND (? IS NULL OR JSON_CONTAINS(?, JSON_QUOTE(space)))
+    AND uid NOT IN (SELECT uid FROM opsml_card_trash)
+ORDER BY score DESC
+LIMIT ? OFFSET ?;
@@ -0,0 +1 @@
+UPDATE opsml_card_search SET readme = ? WHERE registry_type = ? AND space = ? AND name = ?;
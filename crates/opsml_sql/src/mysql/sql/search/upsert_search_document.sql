@@ -0,0 +1,2 @@
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, readme, body) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+ON DUPLICATE KEY UPDATE registry_type = VALUES(registry_type), space = VALUES(space), name = VALUES(name), version = VALUES(version), tags = VALUES(tags), description = VALUES(description), readme = COALESCE(VALUES(readme), readme), body = COALESCE(VALUES(body), body);
@@ -0,0 +1 @@
+DELETE FROM opsml_upload_reservation WHERE created_at < ?;
\ No newline at end of file
@@ -1,8 +1,11 @@
 SELECT 
-    space,
-    CAST(SUM(CASE WHEN registry_type = 'model' THEN 1 ELSE 0 END) AS SIGNED) as model_count,
-    CAST(SUM(CASE WHEN registry_type = 'data' THEN 1 ELSE 0 END) AS SIGNED) as data_count,
-    CAST(SUM(CASE WHEN registry_type = 'prompt' THEN 1 ELSE 0 END) AS SIGNED) as prompt_count,
-    CAST(SUM(CASE WHEN registry_type = 'experiment' THEN 1 ELSE 0 END) AS SIGNED) as experiment_count
-FROM opsml_space_name
-GROUP BY space;
\ No newline at end of file
+    n.space,
+    CAST(SUM(CASE WHEN n.registry_type = 'model' THEN 1 ELSE 0 END) AS SIGNED) as model_count,
+    CAST(SUM(CASE WHEN n.registry_type = 'data' THEN 1 ELSE 0 END) AS SIGNED) as data_count,
+    CAST(SUM(CASE WHEN n.registry_type = 'prompt' THEN 1 ELSE 0 END) AS SIGNED) as prompt_count,
+    CAST(SUM(CASE WHEN n.registry_type = 'experiment' THEN 1 ELSE 0 END) AS SIGNED) as experiment_count,
+    MAX(s.soft_quota_bytes) as soft_quota_bytes,
+    MAX(s.hard_quota_bytes) as hard_quota_bytes
+FROM opsml_space_name n
+LEFT JOIN opsml_space s ON s.space = n.space
+GROUP BY n.space;
@@ -0,0 +1,3 @@
+SELECT CAST(COALESCE(SUM(bytes), 0) AS SIGNED)
+FROM opsml_space_usage
+WHERE space = ?;
\ No newline at end of file
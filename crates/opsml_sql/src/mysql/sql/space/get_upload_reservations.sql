@@ -0,0 +1,7 @@
+SELECT
+    space,
+    registry_type,
+    CAST(COALESCE(SUM(bytes), 0) AS SIGNED)
+FROM opsml_upload_reservation
+GROUP BY space, registry_type
+ORDER BY space, registry_type;
\ No newline at end of file
@@ -0,0 +1,5 @@
+INSERT INTO opsml_upload_reservation
+(path, space, registry_type, bytes)
+VALUES (?, ?, ?, ?)
+ON DUPLICATE KEY UPDATE
+    bytes = bytes + VALUES(bytes);
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT space FROM opsml_space WHERE space = ? FOR UPDATE;
\ No newline at end of file
@@ -0,0 +1,6 @@
+INSERT INTO opsml_space
+(space, description, soft_quota_bytes, hard_quota_bytes)
+VALUES (?, '', ?, ?)
+ON DUPLICATE KEY UPDATE
+    soft_quota_bytes = VALUES(soft_quota_bytes),
+    hard_quota_bytes = VALUES(hard_quota_bytes);
\ No newline at end of file
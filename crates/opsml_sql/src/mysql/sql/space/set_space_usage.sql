@@ -0,0 +1,5 @@
+INSERT INTO opsml_space_usage
+(space, registry_type, bytes)
+VALUES (?, ?, ?)
+ON DUPLICATE KEY UPDATE
+    bytes = VALUES(bytes);
\ No newline at end of file
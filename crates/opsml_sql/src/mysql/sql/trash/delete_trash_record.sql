@@ -0,0 +1 @@
+DELETE FROM opsml_card_trash WHERE uid = ?;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT space, keep_last_versions, purge_trashed_after_days, protect_aliased, updated_by, updated_at FROM opsml_retention_policy ORDER BY space;
\ No newline at end of file
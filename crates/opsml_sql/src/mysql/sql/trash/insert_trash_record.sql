@@ -0,0 +1 @@
+INSERT INTO opsml_card_trash (uid, registry_type, space, name, version, record, reason, deleted_by) VALUES (?, ?, ?, ?, ?, ?, ?, ?);
\ No newline at end of file
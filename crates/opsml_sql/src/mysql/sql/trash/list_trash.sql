@@ -0,0 +1,6 @@
+SELECT uid, registry_type, space, name, version, record, reason, deleted_by, deleted_at
+FROM opsml_card_trash
+WHERE (? IS NULL OR space = ?)
+    AND (? IS NULL OR registry_type = ?)
+    AND (? IS NULL OR deleted_at <= ?)
+ORDER BY deleted_at DESC;
\ No newline at end of file
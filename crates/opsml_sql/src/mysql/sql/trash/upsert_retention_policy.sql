@@ -0,0 +1,2 @@
+INSERT INTO opsml_retention_policy (space, keep_last_versions, purge_trashed_after_days, protect_aliased, updated_by) VALUES (?, ?, ?, ?, ?)
+ON DUPLICATE KEY UPDATE keep_last_versions = VALUES(keep_last_versions), purge_trashed_after_days = VALUES(purge_trashed_after_days), protect_aliased = VALUES(protect_aliased), updated_by = VALUES(updated_by), updated_at = CURRENT_TIMESTAMP;
\ No newline at end of file
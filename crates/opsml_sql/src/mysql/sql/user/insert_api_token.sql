@@ -0,0 +1 @@
+INSERT INTO opsml_user_api_token (token_id, username, name, token_hash, permissions, created_at, expires_at) VALUES (?, ?, ?, ?, ?, ?, ?);
\ No newline at end of file
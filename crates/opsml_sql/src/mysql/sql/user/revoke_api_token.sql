@@ -0,0 +1 @@
+UPDATE opsml_user_api_token SET revoked = TRUE WHERE username = ? AND token_id = ?;
\ No newline at end of file
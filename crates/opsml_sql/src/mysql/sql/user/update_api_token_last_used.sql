@@ -0,0 +1 @@
+UPDATE opsml_user_api_token SET last_used_at = ? WHERE token_id = ?;
\ No newline at end of file
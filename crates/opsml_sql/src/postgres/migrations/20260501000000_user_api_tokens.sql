@@ -0,0 +1,13 @@
+-- Long-lived, revocable API tokens for service accounts
+CREATE TABLE IF NOT EXISTS opsml_user_api_token (
+    token_id TEXT NOT NULL PRIMARY KEY,
+    username TEXT NOT NULL REFERENCES opsml_user (username) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    token_hash TEXT NOT NULL,
+    permissions JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    expires_at TIMESTAMPTZ,
+    last_used_at TIMESTAMPTZ,
+    revoked BOOLEAN NOT NULL DEFAULT FALSE
+);
+CREATE INDEX IF NOT EXISTS idx_user_api_token_username ON opsml_user_api_token (username);
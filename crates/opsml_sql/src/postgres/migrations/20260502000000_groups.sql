@@ -0,0 +1,23 @@
+-- Groups with space-scoped roles, replacing flat per-user permission strings
+CREATE TABLE IF NOT EXISTS opsml_group (
+    name TEXT NOT NULL PRIMARY KEY,
+    description TEXT NOT NULL DEFAULT '',
+    sso_groups JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS opsml_group_role (
+    group_name TEXT NOT NULL REFERENCES opsml_group (name) ON DELETE CASCADE,
+    space TEXT NOT NULL,
+    role TEXT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (group_name, space)
+);
+
+CREATE TABLE IF NOT EXISTS opsml_group_member (
+    group_name TEXT NOT NULL REFERENCES opsml_group (name) ON DELETE CASCADE,
+    username TEXT NOT NULL REFERENCES opsml_user (username) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (group_name, username)
+);
+CREATE INDEX IF NOT EXISTS idx_group_member_username ON opsml_group_member (username);
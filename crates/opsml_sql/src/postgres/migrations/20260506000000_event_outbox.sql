@@ -0,0 +1,43 @@
+-- Durable outbox of registry events and the webhooks they are delivered to
+CREATE TABLE IF NOT EXISTS opsml_event_outbox (
+    id BIGSERIAL PRIMARY KEY,
+    event_id TEXT NOT NULL UNIQUE,
+    event_type TEXT NOT NULL,
+    space TEXT NOT NULL,
+    registry_type TEXT,
+    resource_id TEXT NOT NULL,
+    payload JSONB NOT NULL DEFAULT '{}',
+    username TEXT NOT NULL,
+    dispatched BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_event_outbox_dispatched ON opsml_event_outbox (dispatched);
+
+CREATE TABLE IF NOT EXISTS opsml_webhook (
+    id TEXT PRIMARY KEY,
+    space TEXT NOT NULL,
+    url TEXT NOT NULL,
+    event_types JSONB NOT NULL DEFAULT '[]',
+    secret TEXT NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by TEXT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_webhook_space ON opsml_webhook (space);
+
+CREATE TABLE IF NOT EXISTS opsml_webhook_delivery (
+    id BIGSERIAL PRIMARY KEY,
+    webhook_id TEXT NOT NULL,
+    event_id TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL,
+    last_error TEXT,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    UNIQUE (webhook_id, event_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_webhook_delivery_status ON opsml_webhook_delivery (status, next_attempt_at);
@@ -0,0 +1,2 @@
+-- Version of the key-encryption-key each artifact key is wrapped with. 0 marks keys wrapped with the card uid
+ALTER TABLE opsml_artifact_key ADD COLUMN key_version INTEGER NOT NULL DEFAULT 0;
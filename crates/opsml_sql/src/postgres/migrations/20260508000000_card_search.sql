@@ -0,0 +1,47 @@
+-- Full-text search index over cards. One document per card version
+CREATE TABLE IF NOT EXISTS opsml_card_search (
+    uid TEXT PRIMARY KEY,
+    registry_type TEXT NOT NULL,
+    space TEXT NOT NULL,
+    name TEXT NOT NULL,
+    version TEXT NOT NULL,
+    tags TEXT NOT NULL DEFAULT '',
+    description TEXT,
+    readme TEXT,
+    body TEXT,
+    created_at TIMESTAMPTZ DEFAULT NOW(),
+    document TSVECTOR GENERATED ALWAYS AS (
+        setweight(to_tsvector('english', coalesce(name, '')), 'A') ||
+        setweight(to_tsvector('english', coalesce(tags, '')), 'B') ||
+        setweight(to_tsvector('english', coalesce(description, '')), 'B') ||
+        setweight(to_tsvector('english', coalesce(space, '')), 'C') ||
+        setweight(to_tsvector('english', coalesce(readme, '') || ' ' || coalesce(body, '')), 'D')
+    ) STORED
+);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_card_search_card ON opsml_card_search (registry_type, space, name);
+CREATE INDEX IF NOT EXISTS idx_opsml_card_search_document ON opsml_card_search USING GIN (document);
+
+-- Index existing cards. READMEs and skill bodies are picked up the next time they are written
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'data', space, name, version, COALESCE(tags::TEXT, ''), NULL, created_at FROM opsml_data_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'model', space, name, version, COALESCE(tags::TEXT, ''), NULL, created_at FROM opsml_model_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'experiment', space, name, version, COALESCE(tags::TEXT, ''), NULL, created_at FROM opsml_experiment_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'audit', space, name, version, COALESCE(tags::TEXT, ''), NULL, created_at FROM opsml_audit_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'prompt', space, name, version, COALESCE(tags::TEXT, ''), NULL, created_at FROM opsml_prompt_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'service', space, name, version, COALESCE(tags::TEXT, ''), NULL, created_at FROM opsml_service_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'mcp', space, name, version, COALESCE(tags::TEXT, ''), NULL, created_at FROM opsml_mcp_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'agent', space, name, version, COALESCE(tags::TEXT, ''), NULL, created_at FROM opsml_agent_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'skill', space, name, version, COALESCE(tags::TEXT, ''), description, created_at FROM opsml_skill_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'subagent', space, name, version, COALESCE(tags::TEXT, ''), description, created_at FROM opsml_subagent_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'tool', space, name, version, COALESCE(tags::TEXT, ''), description, created_at FROM opsml_tool_registry;
@@ -0,0 +1,12 @@
+-- Storage quotas set by admins. NULL means no limit
+ALTER TABLE opsml_space ADD COLUMN IF NOT EXISTS soft_quota_bytes BIGINT;
+ALTER TABLE opsml_space ADD COLUMN IF NOT EXISTS hard_quota_bytes BIGINT;
+
+-- Artifact bytes uploaded per space and registry, kept up to date by the files router
+CREATE TABLE IF NOT EXISTS opsml_space_usage (
+    space TEXT NOT NULL,
+    registry_type TEXT NOT NULL,
+    bytes BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ DEFAULT NOW(),
+    PRIMARY KEY (space, registry_type)
+);
@@ -0,0 +1,2 @@
+-- Track which server instance runs an agent job so restarts only recover their own jobs
+ALTER TABLE opsml_agent_job ADD COLUMN instance_id TEXT;
@@ -0,0 +1,8 @@
+-- Bytes reserved for uploads that went straight to object storage and have not been completed yet
+CREATE TABLE IF NOT EXISTS opsml_upload_reservation (
+    path TEXT PRIMARY KEY,
+    space TEXT NOT NULL,
+    registry_type TEXT NOT NULL,
+    bytes BIGINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ DEFAULT NOW()
+);
\ No newline at end of file
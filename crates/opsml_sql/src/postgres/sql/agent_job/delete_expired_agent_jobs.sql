@@ -0,0 +1 @@
+DELETE FROM opsml_agent_job WHERE status NOT IN ('pending', 'running') AND updated_at < $1;
\ No newline at end of file
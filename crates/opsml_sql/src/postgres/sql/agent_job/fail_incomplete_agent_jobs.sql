@@ -0,0 +1 @@
+UPDATE opsml_agent_job SET status = 'failed', error = $1, updated_at = NOW() WHERE status IN ('pending', 'running') AND (instance_id = $2 OR updated_at < $3);
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT job_id, agent_id, owner, status, result, error, duration_ms, session_id, prompt, instance_id, created_at, updated_at FROM opsml_agent_job WHERE job_id = $1;
\ No newline at end of file
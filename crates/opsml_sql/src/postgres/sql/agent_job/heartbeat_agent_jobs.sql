@@ -0,0 +1 @@
+UPDATE opsml_agent_job SET updated_at = NOW() WHERE status IN ('pending', 'running') AND instance_id = $1;
\ No newline at end of file
@@ -0,0 +1,2 @@
+INSERT INTO opsml_agent_job (job_id, agent_id, owner, status, result, error, duration_ms, session_id, prompt, instance_id, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+ON CONFLICT (job_id) DO UPDATE SET status = excluded.status, result = excluded.result, error = excluded.error, duration_ms = excluded.duration_ms, session_id = excluded.session_id, prompt = excluded.prompt, updated_at = NOW();
\ No newline at end of file
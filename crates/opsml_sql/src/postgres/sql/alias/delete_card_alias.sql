@@ -0,0 +1 @@
+DELETE FROM opsml_card_alias WHERE space = $1 AND name = $2 AND registry_type = $3 AND alias = $4;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT space, name, registry_type, alias, uid, version, updated_by, updated_at FROM opsml_card_alias WHERE space = $1 AND name = $2 AND registry_type = $3 ORDER BY alias;
\ No newline at end of file
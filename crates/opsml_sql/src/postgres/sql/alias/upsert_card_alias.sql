@@ -0,0 +1,2 @@
+INSERT INTO opsml_card_alias (space, name, registry_type, alias, uid, version, updated_by) VALUES ($1, $2, $3, $4, $5, $6, $7)
+ON CONFLICT (space, name, registry_type, alias) DO UPDATE SET uid = excluded.uid, version = excluded.version, updated_by = excluded.updated_by, updated_at = NOW();
\ No newline at end of file
@@ -0,0 +1 @@
+UPDATE opsml_artifact_key SET encrypted_key = $1, key_version = $2 WHERE uid = $3 AND registry_type = $4 AND key_version = $5;
\ No newline at end of file
@@ -0,0 +1 @@
+UPDATE opsml_artifact_key SET manifest_sha256 = $1 WHERE uid = $2 AND registry_type = $3;
\ No newline at end of file
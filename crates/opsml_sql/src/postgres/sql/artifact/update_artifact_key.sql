@@ -1 +1 @@
-UPDATE opsml_artifact_key SET encrypted_key = $1, created_at = CURRENT_TIMESTAMP WHERE uid = $2 AND registry_type = $3;
\ No newline at end of file
+UPDATE opsml_artifact_key SET encrypted_key = $1, key_version = $2, created_at = CURRENT_TIMESTAMP WHERE uid = $3 AND registry_type = $4;
\ No newline at end of file
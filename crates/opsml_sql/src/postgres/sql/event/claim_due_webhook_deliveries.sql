@@ -0,0 +1 @@
+UPDATE opsml_webhook_delivery SET claim_id = $1, next_attempt_at = $2 WHERE id IN (SELECT id FROM opsml_webhook_delivery WHERE status = 'pending' AND next_attempt_at <= $3 ORDER BY next_attempt_at LIMIT $4 FOR UPDATE SKIP LOCKED);
\ No newline at end of file
@@ -0,0 +1 @@
+UPDATE opsml_event_outbox SET claim_id = $1, claimed_until = $2 WHERE id IN (SELECT id FROM opsml_event_outbox WHERE dispatched = FALSE AND (claimed_until IS NULL OR claimed_until <= $3) ORDER BY id LIMIT $4 FOR UPDATE SKIP LOCKED);
\ No newline at end of file
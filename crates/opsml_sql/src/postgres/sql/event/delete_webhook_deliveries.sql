@@ -0,0 +1 @@
+DELETE FROM opsml_webhook_delivery WHERE webhook_id = $1;
\ No newline at end of file
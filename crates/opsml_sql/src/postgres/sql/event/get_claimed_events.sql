@@ -0,0 +1 @@
+SELECT event_id, event_type, space, registry_type, resource_id, payload, username, created_at FROM opsml_event_outbox WHERE claim_id = $1 AND dispatched = FALSE ORDER BY id;
\ No newline at end of file
@@ -0,0 +1 @@
+INSERT INTO opsml_event_outbox (event_id, event_type, space, registry_type, resource_id, payload, username, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8);
\ No newline at end of file
@@ -0,0 +1 @@
+INSERT INTO opsml_webhook (id, space, url, event_types, secret, active, created_by, created_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8);
\ No newline at end of file
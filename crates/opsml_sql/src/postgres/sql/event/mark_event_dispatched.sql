@@ -0,0 +1 @@
+UPDATE opsml_event_outbox SET dispatched = TRUE WHERE event_id = $1;
\ No newline at end of file
@@ -0,0 +1,224 @@
+use crate::{postgres::helper::PostgresQueryHelper, traits::EventLogicTrait};
+
+use crate::error::SqlError;
+use crate::schemas::schema::{RegistryEventSqlRecord, WebhookDeliverySqlRecord, WebhookSqlRecord};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use opsml_types::contracts::{RegistryEvent, WebhookDelivery};
+use sqlx::{Pool, Postgres, types::Json};
+
+#[derive(Debug, Clone)]
+pub struct EventLogicPostgresClient {
+    pool: sqlx::Pool<Postgres>,
+}
+impl EventLogicPostgresClient {
+    pub fn new(pool: &Pool<Postgres>) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
+
+#[async_trait]
+impl EventLogicTrait for EventLogicPostgresClient {
+    async fn insert_registry_event(&self, event: &RegistryEvent) -> Result<(), SqlError> {
+        let query = PostgresQueryHelper::get_insert_registry_event_query();
+        sqlx::query(query)
+            .bind(&event.event_id)
+            .bind(event.event_type.to_string())
+            .bind(&event.space)
+            .bind(
+                event
+                    .registry_type
+                    .as_ref()
+                    .map(|registry_type| registry_type.to_string()),
+            )
+            .bind(&event.resource_id)
+            .bind(Json(&event.payload))
+            .bind(&event.username)
+            .bind(event.created_at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_registry_event(&self, event_id: &str) -> Result<Option<RegistryEvent>, SqlError> {
+        let query = PostgresQueryHelper::get_get_registry_event_query();
+        let record: Option<RegistryEventSqlRecord> = sqlx::query_as(query)
+            .bind(event_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        record.map(RegistryEvent::try_from).transpose()
+    }
+
+    async fn claim_undispatched_events(
+        &self,
+        claim_id: &str,
+        now: &DateTime<Utc>,
+        lease_until: &DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<RegistryEvent>, SqlError> {
+        let query = PostgresQueryHelper::get_claim_undispatched_events_query();
+        sqlx::query(query)
+            .bind(claim_id)
+            .bind(lease_until)
+            .bind(now)
+            .bind(limit)
+            .execute(&self.pool)
+            .await?;
+
+        let query = PostgresQueryHelper::get_get_claimed_events_query();
+        let records: Vec<RegistryEventSqlRecord> = sqlx::query_as(query)
+            .bind(claim_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        records.into_iter().map(RegistryEvent::try_from).collect()
+    }
+
+    async fn release_event_claim(&self, claim_id: &str) -> Result<(), SqlError> {
+        let query = PostgresQueryHelper::get_release_event_claim_query();
+        sqlx::query(query)
+            .bind(claim_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn mark_event_dispatched(&self, event_id: &str) -> Result<(), SqlError> {
+        let query = PostgresQueryHelper::get_mark_event_dispatched_query();
+        sqlx::query(query)
+            .bind(event_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn insert_webhook(&self, webhook: &WebhookSqlRecord) -> Result<(), SqlError> {
+        let query = PostgresQueryHelper::get_insert_webhook_query();
+        sqlx::query(query)
+            .bind(&webhook.id)
+            .bind(&webhook.space)
+            .bind(&webhook.url)
+            .bind(&webhook.event_types)
+            .bind(&webhook.secret)
+            .bind(webhook.active)
+            .bind(&webhook.created_by)
+            .bind(webhook.created_at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_webhook(&self, id: &str) -> Result<Option<WebhookSqlRecord>, SqlError> {
+        let query = PostgresQueryHelper::get_get_webhook_query();
+        let record: Option<WebhookSqlRecord> = sqlx::query_as(query)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(record)
+    }
+
+    async fn list_webhooks(&self, space: &str) -> Result<Vec<WebhookSqlRecord>, SqlError> {
+        let query = PostgresQueryHelper::get_list_webhooks_query();
+        let records: Vec<WebhookSqlRecord> = sqlx::query_as(query)
+            .bind(space)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(records)
+    }
+
+    async fn delete_webhook(&self, id: &str) -> Result<bool, SqlError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(PostgresQueryHelper::get_delete_webhook_deliveries_query())
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        let result = sqlx::query(PostgresQueryHelper::get_delete_webhook_query())
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn insert_webhook_delivery(
+        &self,
+        webhook_id: &str,
+        event_id: &str,
+        next_attempt_at: &DateTime<Utc>,
+    ) -> Result<(), SqlError> {
+        let query = PostgresQueryHelper::get_insert_webhook_delivery_query();
+        sqlx::query(query)
+            .bind(webhook_id)
+            .bind(event_id)
+            .bind(next_attempt_at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn claim_due_webhook_deliveries(
+        &self,
+        claim_id: &str,
+        now: &DateTime<Utc>,
+        lease_until: &DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>, SqlError> {
+        let query = PostgresQueryHelper::get_claim_due_webhook_deliveries_query();
+        sqlx::query(query)
+            .bind(claim_id)
+            .bind(lease_until)
+            .bind(now)
+            .bind(limit)
+            .execute(&self.pool)
+            .await?;
+
+        let query = PostgresQueryHelper::get_get_claimed_webhook_deliveries_query();
+        let records: Vec<WebhookDeliverySqlRecord> = sqlx::query_as(query)
+            .bind(claim_id)
+            .fetch_all(&self.pool)
+            .await?;
+
+        records.into_iter().map(WebhookDelivery::try_from).collect()
+    }
+
+    async fn update_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<(), SqlError> {
+        let query = PostgresQueryHelper::get_update_webhook_delivery_query();
+        sqlx::query(query)
+            .bind(delivery.status.to_string())
+            .bind(delivery.attempts)
+            .bind(delivery.next_attempt_at)
+            .bind(&delivery.last_error)
+            .bind(delivery.id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn list_webhook_deliveries(
+        &self,
+        webhook_id: &str,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>, SqlError> {
+        let query = PostgresQueryHelper::get_list_webhook_deliveries_query();
+        let records: Vec<WebhookDeliverySqlRecord> = sqlx::query_as(query)
+            .bind(webhook_id)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        records.into_iter().map(WebhookDelivery::try_from).collect()
+    }
+}
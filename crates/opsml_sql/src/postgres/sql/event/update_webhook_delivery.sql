@@ -0,0 +1 @@
+UPDATE opsml_webhook_delivery SET status = $1, attempts = $2, next_attempt_at = $3, last_error = $4, updated_at = NOW() WHERE id = $5;
\ No newline at end of file
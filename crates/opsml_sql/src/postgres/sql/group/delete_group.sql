@@ -0,0 +1 @@
+DELETE FROM opsml_group WHERE name = $1;
\ No newline at end of file
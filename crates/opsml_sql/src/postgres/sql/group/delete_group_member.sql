@@ -0,0 +1 @@
+DELETE FROM opsml_group_member WHERE group_name = $1 AND username = $2;
\ No newline at end of file
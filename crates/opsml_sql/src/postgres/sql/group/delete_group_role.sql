@@ -0,0 +1 @@
+DELETE FROM opsml_group_role WHERE group_name = $1 AND space = $2;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT name, description, sso_groups, created_at FROM opsml_group WHERE name = $1;
\ No newline at end of file
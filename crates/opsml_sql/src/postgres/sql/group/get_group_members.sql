@@ -0,0 +1 @@
+SELECT username FROM opsml_group_member WHERE group_name = $1 ORDER BY username;
\ No newline at end of file
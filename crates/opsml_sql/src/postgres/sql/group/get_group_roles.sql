@@ -0,0 +1 @@
+SELECT group_name, space, role FROM opsml_group_role WHERE group_name = $1 ORDER BY space;
\ No newline at end of file
@@ -0,0 +1,9 @@
+SELECT
+    role.group_name,
+    role.space,
+    role.role
+FROM opsml_group_role AS role
+INNER JOIN opsml_group_member AS member
+    ON role.group_name = member.group_name
+WHERE member.username = $1
+ORDER BY role.group_name, role.space;
\ No newline at end of file
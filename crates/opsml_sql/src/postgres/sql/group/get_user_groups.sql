@@ -0,0 +1 @@
+SELECT group_name FROM opsml_group_member WHERE username = $1 ORDER BY group_name;
\ No newline at end of file
@@ -0,0 +1 @@
+INSERT INTO opsml_group (name, description, sso_groups) VALUES ($1, $2, $3);
\ No newline at end of file
@@ -0,0 +1,2 @@
+INSERT INTO opsml_group_member (group_name, username) VALUES ($1, $2)
+ON CONFLICT (group_name, username) DO NOTHING;
\ No newline at end of file
@@ -0,0 +1,143 @@
+use crate::{postgres::helper::PostgresQueryHelper, traits::GroupLogicTrait};
+
+use crate::error::SqlError;
+use crate::schemas::schema::{GroupRoleSqlRecord, GroupSqlRecord};
+use async_trait::async_trait;
+use opsml_types::contracts::{GroupRecord, GroupRole};
+use sqlx::{Pool, Postgres};
+
+#[derive(Debug, Clone)]
+pub struct GroupLogicPostgresClient {
+    pool: sqlx::Pool<Postgres>,
+}
+impl GroupLogicPostgresClient {
+    pub fn new(pool: &Pool<Postgres>) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
+
+#[async_trait]
+impl GroupLogicTrait for GroupLogicPostgresClient {
+    async fn insert_group(&self, group: &GroupRecord) -> Result<(), SqlError> {
+        let query = PostgresQueryHelper::get_group_insert_query();
+        let sso_groups = serde_json::to_value(&group.sso_groups)?;
+
+        sqlx::query(query)
+            .bind(&group.name)
+            .bind(&group.description)
+            .bind(&sso_groups)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_group(&self, name: &str) -> Result<Option<GroupRecord>, SqlError> {
+        let query = PostgresQueryHelper::get_group_query();
+        let record: Option<GroupSqlRecord> = sqlx::query_as(query)
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(record.map(GroupRecord::from))
+    }
+
+    async fn get_groups(&self) -> Result<Vec<GroupRecord>, SqlError> {
+        let query = PostgresQueryHelper::get_groups_query();
+        let records: Vec<GroupSqlRecord> = sqlx::query_as(query).fetch_all(&self.pool).await?;
+
+        Ok(records.into_iter().map(GroupRecord::from).collect())
+    }
+
+    async fn delete_group(&self, name: &str) -> Result<(), SqlError> {
+        let query = PostgresQueryHelper::get_group_delete_query();
+        sqlx::query(query).bind(name).execute(&self.pool).await?;
+
+        Ok(())
+    }
+
+    async fn upsert_group_role(&self, role: &GroupRole) -> Result<(), SqlError> {
+        let query = PostgresQueryHelper::get_group_role_upsert_query();
+        sqlx::query(query)
+            .bind(&role.group_name)
+            .bind(&role.space)
+            .bind(role.role.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_group_role(&self, group_name: &str, space: &str) -> Result<(), SqlError> {
+        let query = PostgresQueryHelper::get_group_role_delete_query();
+        sqlx::query(query)
+            .bind(group_name)
+            .bind(space)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_group_roles(&self, group_name: &str) -> Result<Vec<GroupRole>, SqlError> {
+        let query = PostgresQueryHelper::get_group_roles_query();
+        let records: Vec<GroupRoleSqlRecord> = sqlx::query_as(query)
+            .bind(group_name)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(records.into_iter().map(GroupRole::from).collect())
+    }
+
+    async fn add_group_member(&self, group_name: &str, username: &str) -> Result<(), SqlError> {
+        let query = PostgresQueryHelper::get_group_member_insert_query();
+        sqlx::query(query)
+            .bind(group_name)
+            .bind(username)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn remove_group_member(&self, group_name: &str, username: &str) -> Result<(), SqlError> {
+        let query = PostgresQueryHelper::get_group_member_delete_query();
+        sqlx::query(query)
+            .bind(group_name)
+            .bind(username)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_group_members(&self, group_name: &str) -> Result<Vec<String>, SqlError> {
+        let query = PostgresQueryHelper::get_group_members_query();
+        let members: Vec<String> = sqlx::query_scalar(query)
+            .bind(group_name)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(members)
+    }
+
+    async fn get_user_groups(&self, username: &str) -> Result<Vec<String>, SqlError> {
+        let query = PostgresQueryHelper::get_user_groups_query();
+        let groups: Vec<String> = sqlx::query_scalar(query)
+            .bind(username)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(groups)
+    }
+
+    async fn get_user_group_roles(&self, username: &str) -> Result<Vec<GroupRole>, SqlError> {
+        let query = PostgresQueryHelper::get_user_group_roles_query();
+        let records: Vec<GroupRoleSqlRecord> = sqlx::query_as(query)
+            .bind(username)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(records.into_iter().map(GroupRole::from).collect())
+    }
+}
@@ -0,0 +1,82 @@
+SELECT r.experimentcard_uid AS source_uid, 'experiment' AS source_type, r.uid AS target_uid, 'data' AS target_type, 'produced_by' AS relation
+FROM opsml_data_registry AS r
+WHERE r.experimentcard_uid IS NOT NULL AND (r.uid = $1 OR r.experimentcard_uid = $1)
+UNION ALL
+SELECT r.uid AS source_uid, 'data' AS source_type, r.auditcard_uid AS target_uid, 'audit' AS target_type, 'audited_by' AS relation
+FROM opsml_data_registry AS r
+WHERE r.auditcard_uid IS NOT NULL AND (r.uid = $1 OR r.auditcard_uid = $1)
+UNION ALL
+SELECT r.datacard_uid AS source_uid, 'data' AS source_type, r.uid AS target_uid, 'model' AS target_type, 'trained_on' AS relation
+FROM opsml_model_registry AS r
+WHERE r.datacard_uid IS NOT NULL AND (r.uid = $1 OR r.datacard_uid = $1)
+UNION ALL
+SELECT r.experimentcard_uid AS source_uid, 'experiment' AS source_type, r.uid AS target_uid, 'model' AS target_type, 'produced_by' AS relation
+FROM opsml_model_registry AS r
+WHERE r.experimentcard_uid IS NOT NULL AND (r.uid = $1 OR r.experimentcard_uid = $1)
+UNION ALL
+SELECT r.uid AS source_uid, 'model' AS source_type, r.auditcard_uid AS target_uid, 'audit' AS target_type, 'audited_by' AS relation
+FROM opsml_model_registry AS r
+WHERE r.auditcard_uid IS NOT NULL AND (r.uid = $1 OR r.auditcard_uid = $1)
+UNION ALL
+SELECT r.experimentcard_uid AS source_uid, 'experiment' AS source_type, r.uid AS target_uid, 'prompt' AS target_type, 'produced_by' AS relation
+FROM opsml_prompt_registry AS r
+WHERE r.experimentcard_uid IS NOT NULL AND (r.uid = $1 OR r.experimentcard_uid = $1)
+UNION ALL
+SELECT r.uid AS source_uid, 'prompt' AS source_type, r.auditcard_uid AS target_uid, 'audit' AS target_type, 'audited_by' AS relation
+FROM opsml_prompt_registry AS r
+WHERE r.auditcard_uid IS NOT NULL AND (r.uid = $1 OR r.auditcard_uid = $1)
+UNION ALL
+SELECT r.uid AS source_uid, 'experiment' AS source_type, j.value AS target_uid, 'data' AS target_type, 'produced_by' AS relation
+FROM opsml_experiment_registry AS r
+CROSS JOIN LATERAL jsonb_array_elements_text(CASE WHEN jsonb_typeof(r.datacard_uids) = 'array' THEN r.datacard_uids ELSE '[]'::jsonb END) AS j(value)
+WHERE r.uid = $1 OR j.value = $1
+UNION ALL
+SELECT r.uid AS source_uid, 'experiment' AS source_type, j.value AS target_uid, 'model' AS target_type, 'produced_by' AS relation
+FROM opsml_experiment_registry AS r
+CROSS JOIN LATERAL jsonb_array_elements_text(CASE WHEN jsonb_typeof(r.modelcard_uids) = 'array' THEN r.modelcard_uids ELSE '[]'::jsonb END) AS j(value)
+WHERE r.uid = $1 OR j.value = $1
+UNION ALL
+SELECT r.uid AS source_uid, 'experiment' AS source_type, j.value AS target_uid, 'prompt' AS target_type, 'produced_by' AS relation
+FROM opsml_experiment_registry AS r
+CROSS JOIN LATERAL jsonb_array_elements_text(CASE WHEN jsonb_typeof(r.promptcard_uids) = 'array' THEN r.promptcard_uids ELSE '[]'::jsonb END) AS j(value)
+WHERE r.uid = $1 OR j.value = $1
+UNION ALL
+SELECT r.uid AS source_uid, 'experiment' AS source_type, j.value AS target_uid, 'service' AS target_type, 'produced_by' AS relation
+FROM opsml_experiment_registry AS r
+CROSS JOIN LATERAL jsonb_array_elements_text(CASE WHEN jsonb_typeof(r.service_card_uids) = 'array' THEN r.service_card_uids ELSE '[]'::jsonb END) AS j(value)
+WHERE r.uid = $1 OR j.value = $1
+UNION ALL
+SELECT r.uid AS source_uid, 'experiment' AS source_type, j.value AS target_uid, 'experiment' AS target_type, 'linked' AS relation
+FROM opsml_experiment_registry AS r
+CROSS JOIN LATERAL jsonb_array_elements_text(CASE WHEN jsonb_typeof(r.experimentcard_uids) = 'array' THEN r.experimentcard_uids ELSE '[]'::jsonb END) AS j(value)
+WHERE r.uid = $1 OR j.value = $1
+UNION ALL
+SELECT j.value AS source_uid, 'data' AS source_type, r.uid AS target_uid, 'audit' AS target_type, 'audited_by' AS relation
+FROM opsml_audit_registry AS r
+CROSS JOIN LATERAL jsonb_array_elements_text(CASE WHEN jsonb_typeof(r.datacard_uids) = 'array' THEN r.datacard_uids ELSE '[]'::jsonb END) AS j(value)
+WHERE r.uid = $1 OR j.value = $1
+UNION ALL
+SELECT j.value AS source_uid, 'model' AS source_type, r.uid AS target_uid, 'audit' AS target_type, 'audited_by' AS relation
+FROM opsml_audit_registry AS r
+CROSS JOIN LATERAL jsonb_array_elements_text(CASE WHEN jsonb_typeof(r.modelcard_uids) = 'array' THEN r.modelcard_uids ELSE '[]'::jsonb END) AS j(value)
+WHERE r.uid = $1 OR j.value = $1
+UNION ALL
+SELECT j.value AS source_uid, 'experiment' AS source_type, r.uid AS target_uid, 'audit' AS target_type, 'audited_by' AS relation
+FROM opsml_audit_registry AS r
+CROSS JOIN LATERAL jsonb_array_elements_text(CASE WHEN jsonb_typeof(r.experimentcard_uids) = 'array' THEN r.experimentcard_uids ELSE '[]'::jsonb END) AS j(value)
+WHERE r.uid = $1 OR j.value = $1
+UNION ALL
+SELECT j.value->>'uid' AS source_uid, LOWER(j.value->>'registry_type') AS source_type, r.uid AS target_uid, 'service' AS target_type, 'deployed_in' AS relation
+FROM opsml_service_registry AS r
+CROSS JOIN LATERAL jsonb_array_elements(CASE WHEN jsonb_typeof(r.cards) = 'array' THEN r.cards ELSE '[]'::jsonb END) AS j(value)
+WHERE j.value->>'uid' IS NOT NULL AND (r.uid = $1 OR j.value->>'uid' = $1)
+UNION ALL
+SELECT j.value->>'uid' AS source_uid, LOWER(j.value->>'registry_type') AS source_type, r.uid AS target_uid, 'mcp' AS target_type, 'deployed_in' AS relation
+FROM opsml_mcp_registry AS r
+CROSS JOIN LATERAL jsonb_array_elements(CASE WHEN jsonb_typeof(r.cards) = 'array' THEN r.cards ELSE '[]'::jsonb END) AS j(value)
+WHERE j.value->>'uid' IS NOT NULL AND (r.uid = $1 OR j.value->>'uid' = $1)
+UNION ALL
+SELECT j.value->>'uid' AS source_uid, LOWER(j.value->>'registry_type') AS source_type, r.uid AS target_uid, 'agent' AS target_type, 'deployed_in' AS relation
+FROM opsml_agent_registry AS r
+CROSS JOIN LATERAL jsonb_array_elements(CASE WHEN jsonb_typeof(r.cards) = 'array' THEN r.cards ELSE '[]'::jsonb END) AS j(value)
+WHERE j.value->>'uid' IS NOT NULL AND (r.uid = $1 OR j.value->>'uid' = $1)
\ No newline at end of file
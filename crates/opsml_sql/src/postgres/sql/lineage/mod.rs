@@ -0,0 +1,45 @@
+use crate::{postgres::helper::PostgresQueryHelper, traits::LineageLogicTrait};
+
+use crate::error::SqlError;
+use crate::schemas::schema::{LineageEdgeSqlRecord, LineageNodeSqlRecord};
+use async_trait::async_trait;
+use opsml_types::cards::CardTable;
+use opsml_types::contracts::LineageEdge;
+use sqlx::{Pool, Postgres};
+
+#[derive(Debug, Clone)]
+pub struct LineageLogicPostgresClient {
+    pool: sqlx::Pool<Postgres>,
+}
+impl LineageLogicPostgresClient {
+    pub fn new(pool: &Pool<Postgres>) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
+
+#[async_trait]
+impl LineageLogicTrait for LineageLogicPostgresClient {
+    async fn get_lineage_edges(&self, uid: &str) -> Result<Vec<LineageEdge>, SqlError> {
+        let query = PostgresQueryHelper::get_lineage_edges_query();
+        let records: Vec<LineageEdgeSqlRecord> = sqlx::query_as(query)
+            .bind(uid)
+            .fetch_all(&self.pool)
+            .await?;
+
+        records.into_iter().map(LineageEdge::try_from).collect()
+    }
+
+    async fn get_lineage_node(
+        &self,
+        table: &CardTable,
+        uid: &str,
+    ) -> Result<Option<LineageNodeSqlRecord>, SqlError> {
+        let query = PostgresQueryHelper::get_lineage_node_query(table);
+        let record: Option<LineageNodeSqlRecord> = sqlx::query_as(&query)
+            .bind(uid)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(record)
+    }
+}
@@ -0,0 +1 @@
+SELECT readme FROM opsml_card_search WHERE registry_type = $1 AND space = $2 AND name = $3 AND readme IS NOT NULL LIMIT 1;
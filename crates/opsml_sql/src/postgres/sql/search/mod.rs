@@ -0,0 +1,118 @@
+use crate::{postgres::helper::PostgresQueryHelper, traits::SearchLogicTrait};
+
+use crate::error::SqlError;
+use crate::schemas::schema::CardSearchSqlRecord;
+use async_trait::async_trait;
+use opsml_types::RegistryType;
+use opsml_types::contracts::{CardSearchDocument, CardSearchHit, CardSearchQuery, search_terms};
+use sqlx::{Pool, Postgres};
+
+#[derive(Debug, Clone)]
+pub struct SearchLogicPostgresClient {
+    pool: sqlx::Pool<Postgres>,
+}
+impl SearchLogicPostgresClient {
+    pub fn new(pool: &Pool<Postgres>) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
+
+#[async_trait]
+impl SearchLogicTrait for SearchLogicPostgresClient {
+    async fn upsert_search_document(&self, document: &CardSearchDocument) -> Result<(), SqlError> {
+        let registry_type = document.registry_type.to_string();
+
+        // New versions inherit the readme, which is stored per card rather than per version
+        let readme = match &document.readme {
+            Some(readme) => Some(readme.clone()),
+            None => {
+                self.get_search_readme(&document.registry_type, &document.space, &document.name)
+                    .await?
+            }
+        };
+
+        sqlx::query(PostgresQueryHelper::get_search_document_upsert_query())
+            .bind(&document.uid)
+            .bind(&registry_type)
+            .bind(&document.space)
+            .bind(&document.name)
+            .bind(&document.version)
+            .bind(document.tags.join(" "))
+            .bind(&document.description)
+            .bind(readme)
+            .bind(&document.body)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_search_readme(
+        &self,
+        registry_type: &RegistryType,
+        space: &str,
+        name: &str,
+    ) -> Result<Option<String>, SqlError> {
+        Ok(
+            sqlx::query_scalar::<_, Option<String>>(PostgresQueryHelper::get_search_readme_query())
+                .bind(registry_type.to_string())
+                .bind(space)
+                .bind(name)
+                .fetch_optional(&self.pool)
+                .await?
+                .flatten(),
+        )
+    }
+
+    async fn update_search_readme(
+        &self,
+        registry_type: &RegistryType,
+        space: &str,
+        name: &str,
+        readme: &str,
+    ) -> Result<(), SqlError> {
+        sqlx::query(PostgresQueryHelper::get_search_readme_update_query())
+            .bind(readme)
+            .bind(registry_type.to_string())
+            .bind(space)
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_search_document(&self, uid: &str) -> Result<(), SqlError> {
+        sqlx::query(PostgresQueryHelper::get_search_document_delete_query())
+            .bind(uid)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn search_cards(
+        &self,
+        query: &CardSearchQuery,
+        spaces: Option<&[String]>,
+    ) -> Result<Vec<CardSearchHit>, SqlError> {
+        let terms = search_terms(&query.query);
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+        let registry_type = query.registry_type.as_ref().map(|r| r.to_string());
+
+        let records: Vec<CardSearchSqlRecord> =
+            sqlx::query_as(PostgresQueryHelper::get_search_cards_query())
+                .bind(PostgresQueryHelper::get_search_match(&terms))
+                .bind(registry_type)
+                .bind(&query.space)
+                .bind(query.limit() as i64)
+                .bind(query.offset.unwrap_or(0) as i64)
+                .bind(spaces)
+                .fetch_all(&self.pool)
+                .await?;
+
+        records.into_iter().map(CardSearchHit::try_from).collect()
+    }
+}
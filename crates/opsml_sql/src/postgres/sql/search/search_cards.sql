@@ -0,0 +1,36 @@
+SELECT
+    ranked.uid,
+    ranked.registry_type,
+    ranked.space,
+    ranked.name,
+    ranked.version,
+    ranked.score,
+    ts_headline(
+        'english',
+        concat_ws(' ', ranked.name, ranked.tags, ranked.description, ranked.readme, ranked.body),
+        ranked.query,
+        'StartSel=<mark>, StopSel=</mark>, MaxWords=32, MinWords=8, MaxFragments=1'
+    ) AS highlight
+FROM (
+    SELECT
+        s.uid,
+        s.registry_type,
+        s.space,
+        s.name,
+        s.version,
+        s.tags,
+        s.description,
+        s.readme,
+        s.body,
+        q.query,
+        ts_rank(s.document, q.query)::FLOAT8 AS score
+    FROM opsml_card_search s, to_tsquery('english', $1) AS q(query)
+    WHERE s.document @@ q.query
+        AND ($2::TEXT IS NULL OR s.registry_type = $2)
+        AND ($3::TEXT IS NULL OR s.space = $3)
+        AND ($6::TEXT[] IS NULL OR s.space = ANY($6))
+        AND NOT EXISTS (SELECT 1 FROM opsml_card_trash t WHERE t.uid = s.uid)
+    ORDER BY score DESC
+    LIMIT $4 OFFSET $5
+) ranked
+ORDER BY ranked.score DESC;
@@ -0,0 +1,2 @@
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, readme, body) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+ON CONFLICT (uid) DO UPDATE SET registry_type = excluded.registry_type, space = excluded.space, name = excluded.name, version = excluded.version, tags = excluded.tags, description = excluded.description, readme = COALESCE(excluded.readme, opsml_card_search.readme), body = COALESCE(excluded.body, opsml_card_search.body);
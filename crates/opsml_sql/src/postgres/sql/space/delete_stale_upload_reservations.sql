@@ -0,0 +1 @@
+DELETE FROM opsml_upload_reservation WHERE created_at < $1;
\ No newline at end of file
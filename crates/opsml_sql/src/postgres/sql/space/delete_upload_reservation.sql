@@ -0,0 +1 @@
+DELETE FROM opsml_upload_reservation WHERE path = $1;
\ No newline at end of file
@@ -0,0 +1,3 @@
+SELECT CAST(COALESCE(SUM(bytes), 0) AS BIGINT)
+FROM opsml_space_usage
+WHERE space = $1;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT bytes FROM opsml_upload_reservation WHERE path = $1 FOR UPDATE;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT space FROM opsml_space WHERE space = $1 FOR UPDATE;
\ No newline at end of file
@@ -0,0 +1,8 @@
+INSERT INTO opsml_space
+(space, description, soft_quota_bytes, hard_quota_bytes)
+VALUES ($1, '', $2, $3)
+ON CONFLICT(space)
+DO UPDATE SET
+    soft_quota_bytes = excluded.soft_quota_bytes,
+    hard_quota_bytes = excluded.hard_quota_bytes,
+    updated_at = NOW();
\ No newline at end of file
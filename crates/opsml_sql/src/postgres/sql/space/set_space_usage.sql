@@ -0,0 +1,7 @@
+INSERT INTO opsml_space_usage
+(space, registry_type, bytes)
+VALUES ($1, $2, $3)
+ON CONFLICT(space, registry_type)
+DO UPDATE SET
+    bytes = excluded.bytes,
+    updated_at = NOW();
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT uid, registry_type, space, name, version, record, reason, deleted_by, deleted_at FROM opsml_card_trash WHERE uid = $1;
\ No newline at end of file
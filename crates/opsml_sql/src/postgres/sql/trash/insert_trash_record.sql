@@ -0,0 +1 @@
+INSERT INTO opsml_card_trash (uid, registry_type, space, name, version, record, reason, deleted_by) VALUES ($1, $2, $3, $4, $5, $6, $7, $8);
\ No newline at end of file
@@ -0,0 +1,2 @@
+INSERT INTO opsml_retention_policy (space, keep_last_versions, purge_trashed_after_days, protect_aliased, updated_by) VALUES ($1, $2, $3, $4, $5)
+ON CONFLICT (space) DO UPDATE SET keep_last_versions = excluded.keep_last_versions, purge_trashed_after_days = excluded.purge_trashed_after_days, protect_aliased = excluded.protect_aliased, updated_by = excluded.updated_by, updated_at = NOW();
\ No newline at end of file
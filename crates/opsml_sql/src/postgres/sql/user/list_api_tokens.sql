@@ -0,0 +1 @@
+SELECT * FROM opsml_user_api_token WHERE username = $1 ORDER BY created_at DESC;
\ No newline at end of file
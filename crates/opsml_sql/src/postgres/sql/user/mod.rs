@@ -1,10 +1,11 @@
 use crate::postgres::helper::PostgresQueryHelper;
 
 use crate::error::SqlError;
-use crate::schemas::schema::User;
+use crate::schemas::schema::{ApiToken, User};
 
 use crate::traits::UserLogicTrait;
 use async_trait::async_trait;
+use opsml_utils::utils::get_utc_datetime;
 use sqlx::{FromRow, Pool, Postgres, Row, postgres::PgRow};
 
 impl FromRow<'_, PgRow> for User {
@@ -54,6 +55,25 @@ impl FromRow<'_, PgRow> for User {
         })
     }
 }
+impl FromRow<'_, PgRow> for ApiToken {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let permissions: Vec<String> =
+            serde_json::from_value(row.try_get("permissions")?).unwrap_or_default();
+
+        Ok(ApiToken {
+            token_id: row.try_get("token_id")?,
+            username: row.try_get("username")?,
+            name: row.try_get("name")?,
+            token_hash: row.try_get("token_hash")?,
+            permissions,
+            created_at: row.try_get("created_at")?,
+            expires_at: row.try_get("expires_at")?,
+            last_used_at: row.try_get("last_used_at")?,
+            revoked: row.try_get("revoked")?,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct UserLogicPostgresClient {
     pool: sqlx::Pool<Postgres>,
@@ -178,4 +198,69 @@ impl UserLogicTrait for UserLogicPostgresClient {
 
         Ok(())
     }
+
+    async fn insert_api_token(&self, token: &ApiToken) -> Result<(), SqlError> {
+        let query = PostgresQueryHelper::get_api_token_insert_query();
+
+        let permissions = serde_json::to_value(&token.permissions)?;
+
+        sqlx::query(query)
+            .bind(&token.token_id)
+            .bind(&token.username)
+            .bind(&token.name)
+            .bind(&token.token_hash)
+            .bind(&permissions)
+            .bind(token.created_at)
+            .bind(token.expires_at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_api_token(&self, token_id: &str) -> Result<Option<ApiToken>, SqlError> {
+        let query = PostgresQueryHelper::get_api_token_query();
+
+        let token: Option<ApiToken> = sqlx::query_as(query)
+            .bind(token_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(token)
+    }
+
+    async fn get_api_tokens(&self, username: &str) -> Result<Vec<ApiToken>, SqlError> {
+        let query = PostgresQueryHelper::get_api_tokens_query();
+
+        let tokens = sqlx::query_as::<_, ApiToken>(query)
+            .bind(username)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(tokens)
+    }
+
+    async fn revoke_api_token(&self, username: &str, token_id: &str) -> Result<bool, SqlError> {
+        let query = PostgresQueryHelper::get_api_token_revoke_query();
+
+        let result = sqlx::query(query)
+            .bind(username)
+            .bind(token_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn update_api_token_last_used(&self, token_id: &str) -> Result<(), SqlError> {
+        let query = PostgresQueryHelper::get_api_token_last_used_update_query();
+
+        sqlx::query(query)
+            .bind(get_utc_datetime())
+            .bind(token_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
 }
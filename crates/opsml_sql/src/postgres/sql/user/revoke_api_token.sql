@@ -0,0 +1 @@
+UPDATE opsml_user_api_token SET revoked = TRUE WHERE username = $1 AND token_id = $2;
\ No newline at end of file
@@ -0,0 +1 @@
+UPDATE opsml_user_api_token SET last_used_at = $1 WHERE token_id = $2;
\ No newline at end of file
@@ -1,15 +1,21 @@
 use crate::error::SqlError;
 use chrono::{DateTime, Utc};
 use opsml_semver::error::VersionError;
+use opsml_settings::DEFAULT_APP_ENV;
 use opsml_types::cards::{CardStatus, CardTable, ParameterValue};
 use opsml_types::contracts::evaluation::{EvaluationProvider, EvaluationType};
 use opsml_types::contracts::{
-    ArtifactRecord, AuditCardClientRecord, CardEntry, CardRecord, DataCardClientRecord,
-    ExperimentCardClientRecord, McpServer, ModelCardClientRecord, PromptCardClientRecord,
-    ServiceCardClientRecord, ServiceConfig, SkillCardClientRecord, SkillDependency,
-    SubAgentCardClientRecord, ToolCardClientRecord,
+    AgentJobRecord, ArtifactRecord, AuditCardClientRecord, AuditEventRecord, CardAlias, CardEntry,
+    CardRecord, CardSearchHit, DataCardClientRecord, ExperimentCardClientRecord, GroupRecord,
+    GroupRole, LineageEdge, LineageRelation, McpServer, ModelCardClientRecord,
+    PromptCardClientRecord, ServiceCardClientRecord, ServiceConfig, SkillCardClientRecord,
+    SkillDependency, SubAgentCardClientRecord, ToolCardClientRecord,
+};
+use opsml_types::contracts::{
+    ArtifactType, DeliveryStatus, DeploymentConfig, JobStatus, RegistryEvent, RegistryEventType,
+    RetentionPolicy, ServiceMetadata, ServiceType, TrashReason, TrashedCard, Webhook,
+    WebhookDelivery, escape_highlight,
 };
-use opsml_types::contracts::{ArtifactType, DeploymentConfig, ServiceMetadata, ServiceType};
 use opsml_types::{CommonKwargs, DataType, ModelType, RegistryType};
 use opsml_utils::create_uuid7;
 use opsml_utils::utils::get_utc_datetime;
@@ -19,10 +25,9 @@ use serde_json::Value;
 use serde_json::json;
 use sqlx::{prelude::FromRow, types::Json};
 use std::collections::HashMap;
-use std::env;
 use std::str::FromStr;
 
-pub type SqlSpaceRecord = (String, i64, i64, i64, i64);
+pub type SqlSpaceRecord = (String, i64, i64, i64, i64, Option<i64>, Option<i64>);
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct MetricRecord {
@@ -160,6 +165,15 @@ pub struct VersionSummary {
     pub created_at: DateTime<Utc>,
 }
 
+/// A registered card that has no artifact key
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct UnkeyedCard {
+    pub uid: String,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct DataCardRecord {
     pub uid: String,
@@ -195,9 +209,9 @@ impl DataCardRecord {
         interface_type: String,
         username: String,
         opsml_version: String,
+        app_env: String,
     ) -> Self {
         let created_at = get_utc_datetime();
-        let app_env = env::var("APP_ENV").unwrap_or_else(|_| "development".to_string());
         let uid = create_uuid7();
 
         DataCardRecord {
@@ -274,7 +288,7 @@ impl Default for DataCardRecord {
         DataCardRecord {
             uid: create_uuid7(),
             created_at: get_utc_datetime(),
-            app_env: env::var("APP_ENV").unwrap_or_else(|_| "development".to_string()),
+            app_env: DEFAULT_APP_ENV.to_string(),
             name: CommonKwargs::Undefined.to_string(),
             space: CommonKwargs::Undefined.to_string(),
             major: 1,
@@ -335,9 +349,9 @@ impl ModelCardRecord {
         task_type: String,
         opsml_version: String,
         username: String,
+        app_env: String,
     ) -> Self {
         let created_at = get_utc_datetime();
-        let app_env = env::var("APP_ENV").unwrap_or_else(|_| "development".to_string());
         let uid = create_uuid7();
 
         ModelCardRecord {
@@ -421,7 +435,7 @@ impl Default for ModelCardRecord {
         ModelCardRecord {
             uid: create_uuid7(),
             created_at: get_utc_datetime(),
-            app_env: env::var("APP_ENV").unwrap_or_else(|_| "development".to_string()),
+            app_env: DEFAULT_APP_ENV.to_string(),
             name: CommonKwargs::Undefined.to_string(),
             space: CommonKwargs::Undefined.to_string(),
             major: 1,
@@ -473,7 +487,7 @@ impl Default for ExperimentCardRecord {
         ExperimentCardRecord {
             uid: create_uuid7(),
             created_at: get_utc_datetime(),
-            app_env: env::var("APP_ENV").unwrap_or_else(|_| "development".to_string()),
+            app_env: DEFAULT_APP_ENV.to_string(),
             name: CommonKwargs::Undefined.to_string(),
             space: CommonKwargs::Undefined.to_string(),
             major: 1,
@@ -510,9 +524,9 @@ impl ExperimentCardRecord {
         opsml_version: String,
         username: String,
         status: CardStatus,
+        app_env: String,
     ) -> Self {
         let created_at = get_utc_datetime();
-        let app_env = env::var("APP_ENV").unwrap_or_else(|_| "development".to_string());
         let uid = create_uuid7();
 
         ExperimentCardRecord {
@@ -624,9 +638,9 @@ impl AuditCardRecord {
         experimentcard_uids: Vec<String>,
         opsml_version: String,
         username: String,
+        app_env: String,
     ) -> Self {
         let created_at = get_utc_datetime();
-        let app_env = env::var("APP_ENV").unwrap_or_else(|_| "development".to_string());
         let uid = create_uuid7();
 
         AuditCardRecord {
@@ -703,7 +717,7 @@ impl Default for AuditCardRecord {
         AuditCardRecord {
             uid: create_uuid7(),
             created_at: get_utc_datetime(),
-            app_env: env::var("APP_ENV").unwrap_or_else(|_| "development".to_string()),
+            app_env: DEFAULT_APP_ENV.to_string(),
             name: CommonKwargs::Undefined.to_string(),
             space: CommonKwargs::Undefined.to_string(),
             major: 1,
@@ -756,9 +770,9 @@ impl PromptCardRecord {
         opsml_version: String,
         username: String,
         content_hash: Vec<u8>,
+        app_env: String,
     ) -> Self {
         let created_at = get_utc_datetime();
-        let app_env = env::var("APP_ENV").unwrap_or_else(|_| "development".to_string());
         let uid = create_uuid7();
 
         PromptCardRecord {
@@ -834,7 +848,7 @@ impl Default for PromptCardRecord {
         PromptCardRecord {
             uid: create_uuid7(),
             created_at: get_utc_datetime(),
-            app_env: env::var("APP_ENV").unwrap_or_else(|_| "development".to_string()),
+            app_env: DEFAULT_APP_ENV.to_string(),
             name: CommonKwargs::Undefined.to_string(),
             space: CommonKwargs::Undefined.to_string(),
             major: 1,
@@ -892,9 +906,9 @@ impl ServiceCardRecord {
         content_hash: Vec<u8>,
         username: String,
         tags: Vec<String>,
+        app_env: String,
     ) -> Self {
         let created_at = get_utc_datetime();
-        let app_env = env::var("APP_ENV").unwrap_or_else(|_| "development".to_string());
         let uid = create_uuid7();
 
         ServiceCardRecord {
@@ -971,7 +985,7 @@ impl Default for ServiceCardRecord {
         ServiceCardRecord {
             uid: create_uuid7(),
             created_at: get_utc_datetime(),
-            app_env: env::var("APP_ENV").unwrap_or_else(|_| "development".to_string()),
+            app_env: DEFAULT_APP_ENV.to_string(),
             name: CommonKwargs::Undefined.to_string(),
             space: CommonKwargs::Undefined.to_string(),
             major: 1,
@@ -1055,10 +1069,10 @@ impl ArtifactSqlRecord {
         version: Version,
         media_type: String,
         artifact_type: String,
+        app_env: String,
     ) -> Self {
         let created_at = get_utc_datetime();
         let updated_at = created_at;
-        let app_env = env::var("APP_ENV").unwrap_or_else(|_| "development".to_string());
         let uid = create_uuid7();
 
         ArtifactSqlRecord {
@@ -1122,6 +1136,379 @@ impl ArtifactSqlRecord {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuditEventSqlRecord {
+    pub id: i32,
+    pub created_at: DateTime<Utc>,
+    pub username: String,
+    pub client_ip: String,
+    pub user_agent: Option<String>,
+    pub operation: String,
+    pub resource_type: String,
+    pub resource_id: String,
+    pub access_location: Option<String>,
+    pub status: String,
+    pub error_message: Option<String>,
+    pub metadata: Option<String>,
+    pub registry_type: Option<String>,
+    pub route: Option<String>,
+}
+
+impl From<AuditEventSqlRecord> for AuditEventRecord {
+    fn from(record: AuditEventSqlRecord) -> Self {
+        AuditEventRecord {
+            id: record.id as i64,
+            created_at: record.created_at,
+            username: record.username,
+            client_ip: record.client_ip,
+            user_agent: record.user_agent,
+            operation: record.operation,
+            resource_type: record.resource_type,
+            resource_id: record.resource_id,
+            access_location: record.access_location,
+            status: record.status,
+            error_message: record.error_message,
+            metadata: record.metadata,
+            registry_type: record.registry_type,
+            route: record.route,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct GroupSqlRecord {
+    pub name: String,
+    pub description: String,
+    pub sso_groups: Json<Vec<String>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<GroupSqlRecord> for GroupRecord {
+    fn from(record: GroupSqlRecord) -> Self {
+        GroupRecord {
+            name: record.name,
+            description: record.description,
+            sso_groups: record.sso_groups.0,
+            created_at: record.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct GroupRoleSqlRecord {
+    pub group_name: String,
+    pub space: String,
+    pub role: String,
+}
+
+impl From<GroupRoleSqlRecord> for GroupRole {
+    fn from(record: GroupRoleSqlRecord) -> Self {
+        GroupRole {
+            group_name: record.group_name,
+            space: record.space,
+            // roles are validated on write; fall back to the least privileged role
+            role: record.role.parse().unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CardAliasSqlRecord {
+    pub space: String,
+    pub name: String,
+    pub registry_type: String,
+    pub alias: String,
+    pub uid: String,
+    pub version: String,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<CardAliasSqlRecord> for CardAlias {
+    type Error = SqlError;
+
+    fn try_from(record: CardAliasSqlRecord) -> Result<Self, Self::Error> {
+        Ok(CardAlias {
+            registry_type: RegistryType::from_string(&record.registry_type)?,
+            space: record.space,
+            name: record.name,
+            alias: record.alias,
+            uid: record.uid,
+            version: record.version,
+            updated_by: record.updated_by,
+            updated_at: record.updated_at,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CardSearchSqlRecord {
+    pub uid: String,
+    pub registry_type: String,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+    pub score: f64,
+    pub highlight: Option<String>,
+}
+
+impl TryFrom<CardSearchSqlRecord> for CardSearchHit {
+    type Error = SqlError;
+
+    fn try_from(record: CardSearchSqlRecord) -> Result<Self, Self::Error> {
+        Ok(CardSearchHit {
+            registry_type: RegistryType::from_string(&record.registry_type)?,
+            uid: record.uid,
+            space: record.space,
+            name: record.name,
+            version: record.version,
+            score: record.score,
+            highlight: record
+                .highlight
+                .filter(|h| !h.is_empty())
+                .map(|h| escape_highlight(&h)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TrashSqlRecord {
+    pub uid: String,
+    pub registry_type: String,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+    pub record: Json<Value>,
+    pub reason: String,
+    pub deleted_by: String,
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl TrashSqlRecord {
+    /// Snapshot a registry row before it is removed from its table
+    pub fn new(
+        card: &ServerCard,
+        registry_type: &RegistryType,
+        reason: TrashReason,
+        deleted_by: &str,
+    ) -> Result<Self, SqlError> {
+        Ok(Self {
+            uid: card.uid().to_string(),
+            registry_type: registry_type.to_string(),
+            space: card.space(),
+            name: card.name(),
+            version: card.version(),
+            record: Json(card.to_json()?),
+            reason: reason.to_string(),
+            deleted_by: deleted_by.to_string(),
+            deleted_at: get_utc_datetime(),
+        })
+    }
+}
+
+impl TryFrom<TrashSqlRecord> for TrashedCard {
+    type Error = SqlError;
+
+    fn try_from(record: TrashSqlRecord) -> Result<Self, Self::Error> {
+        Ok(TrashedCard {
+            registry_type: RegistryType::from_string(&record.registry_type)?,
+            reason: TrashReason::from_string(&record.reason)
+                .ok_or_else(|| SqlError::InvalidTrashReason(record.reason.clone()))?,
+            uid: record.uid,
+            space: record.space,
+            name: record.name,
+            version: record.version,
+            deleted_by: record.deleted_by,
+            deleted_at: record.deleted_at,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RetentionPolicySqlRecord {
+    pub space: String,
+    pub keep_last_versions: Option<i32>,
+    pub purge_trashed_after_days: Option<i32>,
+    pub protect_aliased: bool,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<RetentionPolicySqlRecord> for RetentionPolicy {
+    fn from(record: RetentionPolicySqlRecord) -> Self {
+        RetentionPolicy {
+            space: record.space,
+            keep_last_versions: record.keep_last_versions.map(|n| n.max(0) as u32),
+            purge_trashed_after_days: record.purge_trashed_after_days.map(|n| n.max(0) as u32),
+            protect_aliased: record.protect_aliased,
+            updated_by: record.updated_by,
+            updated_at: record.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AgentJobSqlRecord {
+    pub job_id: String,
+    pub agent_id: String,
+    pub owner: String,
+    pub status: String,
+    pub result: Option<Json<Value>>,
+    pub error: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub session_id: Option<String>,
+    pub prompt: Option<String>,
+    pub instance_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<AgentJobSqlRecord> for AgentJobRecord {
+    type Error = SqlError;
+
+    fn try_from(record: AgentJobSqlRecord) -> Result<Self, Self::Error> {
+        Ok(AgentJobRecord {
+            status: JobStatus::from_string(&record.status)
+                .ok_or(SqlError::InvalidJobStatus(record.status))?,
+            job_id: record.job_id,
+            agent_id: record.agent_id,
+            owner: record.owner,
+            result: record.result.map(|result| result.0),
+            error: record.error,
+            duration_ms: record.duration_ms.map(|duration| duration as u64),
+            session_id: record.session_id,
+            prompt: record.prompt,
+            instance_id: record.instance_id.unwrap_or_default(),
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RegistryEventSqlRecord {
+    pub event_id: String,
+    pub event_type: String,
+    pub space: String,
+    pub registry_type: Option<String>,
+    pub resource_id: String,
+    pub payload: Json<Value>,
+    pub username: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<RegistryEventSqlRecord> for RegistryEvent {
+    type Error = SqlError;
+
+    fn try_from(record: RegistryEventSqlRecord) -> Result<Self, Self::Error> {
+        Ok(RegistryEvent {
+            event_type: RegistryEventType::from_string(&record.event_type)
+                .ok_or(SqlError::InvalidEventType(record.event_type))?,
+            registry_type: record
+                .registry_type
+                .as_deref()
+                .map(RegistryType::from_string)
+                .transpose()?,
+            event_id: record.event_id,
+            space: record.space,
+            resource_id: record.resource_id,
+            payload: record.payload.0,
+            username: record.username,
+            created_at: record.created_at,
+        })
+    }
+}
+
+/// Webhook row including its signing secret, which is never part of the api contract
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WebhookSqlRecord {
+    pub id: String,
+    pub space: String,
+    pub url: String,
+    pub event_types: Json<Vec<RegistryEventType>>,
+    pub secret: String,
+    pub active: bool,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<WebhookSqlRecord> for Webhook {
+    fn from(record: WebhookSqlRecord) -> Self {
+        Webhook {
+            id: record.id,
+            space: record.space,
+            url: record.url,
+            event_types: record.event_types.0,
+            active: record.active,
+            created_by: record.created_by,
+            created_at: record.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WebhookDeliverySqlRecord {
+    pub id: i64,
+    pub webhook_id: String,
+    pub event_id: String,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TryFrom<WebhookDeliverySqlRecord> for WebhookDelivery {
+    type Error = SqlError;
+
+    fn try_from(record: WebhookDeliverySqlRecord) -> Result<Self, Self::Error> {
+        Ok(WebhookDelivery {
+            status: DeliveryStatus::from_string(&record.status)
+                .ok_or(SqlError::InvalidDeliveryStatus(record.status))?,
+            id: record.id,
+            webhook_id: record.webhook_id,
+            event_id: record.event_id,
+            attempts: record.attempts,
+            next_attempt_at: record.next_attempt_at,
+            last_error: record.last_error,
+            updated_at: record.updated_at,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LineageEdgeSqlRecord {
+    pub source_uid: String,
+    pub source_type: String,
+    pub target_uid: String,
+    pub target_type: String,
+    pub relation: String,
+}
+
+impl TryFrom<LineageEdgeSqlRecord> for LineageEdge {
+    type Error = SqlError;
+
+    fn try_from(record: LineageEdgeSqlRecord) -> Result<Self, Self::Error> {
+        Ok(LineageEdge {
+            source_type: RegistryType::from_string(&record.source_type)?,
+            target_type: RegistryType::from_string(&record.target_type)?,
+            relation: LineageRelation::from_string(&record.relation)
+                .ok_or(SqlError::InvalidLineageRelation(record.relation))?,
+            source_uid: record.source_uid,
+            target_uid: record.target_uid,
+        })
+    }
+}
+
+/// Identifying fields of a card, used to label lineage graph nodes
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LineageNodeSqlRecord {
+    pub uid: String,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct EvaluationSqlRecord {
     pub uid: String,
@@ -1164,9 +1551,9 @@ impl EvaluationSqlRecord {
         name: String,
         evaluation_type: EvaluationType,
         evaluation_provider: EvaluationProvider,
+        app_env: String,
     ) -> Self {
         let created_at = get_utc_datetime();
-        let app_env = env::var("APP_ENV").unwrap_or_else(|_| "development".to_string());
         let uid = create_uuid7();
 
         EvaluationSqlRecord {
@@ -1220,9 +1607,9 @@ impl SkillCardRecord {
         username: String,
         content_hash: Vec<u8>,
         input_schema: Option<Value>,
+        app_env: String,
     ) -> Self {
         let created_at = get_utc_datetime();
-        let app_env = env::var("APP_ENV").unwrap_or_else(|_| "development".to_string());
         let uid = create_uuid7();
 
         SkillCardRecord {
@@ -1308,7 +1695,7 @@ impl Default for SkillCardRecord {
         SkillCardRecord {
             uid: create_uuid7(),
             created_at: get_utc_datetime(),
-            app_env: env::var("APP_ENV").unwrap_or_else(|_| "development".to_string()),
+            app_env: DEFAULT_APP_ENV.to_string(),
             name: CommonKwargs::Undefined.to_string(),
             space: CommonKwargs::Undefined.to_string(),
             major: 1,
@@ -1365,9 +1752,9 @@ impl SubAgentCardRecord {
         opsml_version: String,
         username: String,
         content_hash: Option<Vec<u8>>,
+        app_env: String,
     ) -> Self {
         let created_at = get_utc_datetime();
-        let app_env = env::var("APP_ENV").unwrap_or_else(|_| "development".to_string());
         let uid = create_uuid7();
 
         SubAgentCardRecord {
@@ -1446,7 +1833,7 @@ impl Default for SubAgentCardRecord {
         SubAgentCardRecord {
             uid: create_uuid7(),
             created_at: get_utc_datetime(),
-            app_env: std::env::var("APP_ENV").unwrap_or_else(|_| "development".to_string()),
+            app_env: DEFAULT_APP_ENV.to_string(),
             name: CommonKwargs::Undefined.to_string(),
             space: CommonKwargs::Undefined.to_string(),
             major: 1,
@@ -1502,9 +1889,9 @@ impl ToolCardRecord {
         opsml_version: String,
         username: String,
         content_hash: Option<Vec<u8>>,
+        app_env: String,
     ) -> Self {
         let created_at = get_utc_datetime();
-        let app_env = env::var("APP_ENV").unwrap_or_else(|_| "development".to_string());
         let uid = create_uuid7();
 
         ToolCardRecord {
@@ -1583,7 +1970,7 @@ impl Default for ToolCardRecord {
         ToolCardRecord {
             uid: create_uuid7(),
             created_at: get_utc_datetime(),
-            app_env: std::env::var("APP_ENV").unwrap_or_else(|_| "development".to_string()),
+            app_env: DEFAULT_APP_ENV.to_string(),
             name: CommonKwargs::Undefined.to_string(),
             space: CommonKwargs::Undefined.to_string(),
             major: 1,
@@ -1646,6 +2033,65 @@ impl CardResults {
             CardResults::Tool(cards) => cards.is_empty(),
         }
     }
+    /// `(uid, version)` pairs for every card in the result set
+    pub fn uid_versions(&self) -> Vec<(&str, &str)> {
+        match self {
+            CardResults::Data(cards) => cards
+                .iter()
+                .map(|c| (c.uid.as_str(), c.version.as_str()))
+                .collect(),
+            CardResults::Model(cards) => cards
+                .iter()
+                .map(|c| (c.uid.as_str(), c.version.as_str()))
+                .collect(),
+            CardResults::Experiment(cards) => cards
+                .iter()
+                .map(|c| (c.uid.as_str(), c.version.as_str()))
+                .collect(),
+            CardResults::Audit(cards) => cards
+                .iter()
+                .map(|c| (c.uid.as_str(), c.version.as_str()))
+                .collect(),
+            CardResults::Prompt(cards) => cards
+                .iter()
+                .map(|c| (c.uid.as_str(), c.version.as_str()))
+                .collect(),
+            CardResults::Service(cards) => cards
+                .iter()
+                .map(|c| (c.uid.as_str(), c.version.as_str()))
+                .collect(),
+            CardResults::Skill(cards) => cards
+                .iter()
+                .map(|c| (c.uid.as_str(), c.version.as_str()))
+                .collect(),
+            CardResults::SubAgent(cards) => cards
+                .iter()
+                .map(|c| (c.uid.as_str(), c.version.as_str()))
+                .collect(),
+            CardResults::Tool(cards) => cards
+                .iter()
+                .map(|c| (c.uid.as_str(), c.version.as_str()))
+                .collect(),
+        }
+    }
+    pub fn into_server_cards(self) -> Vec<ServerCard> {
+        match self {
+            CardResults::Data(cards) => cards.into_iter().map(ServerCard::Data).collect(),
+            CardResults::Model(cards) => cards.into_iter().map(ServerCard::Model).collect(),
+            CardResults::Experiment(cards) => {
+                cards.into_iter().map(ServerCard::Experiment).collect()
+            }
+            CardResults::Audit(cards) => cards.into_iter().map(ServerCard::Audit).collect(),
+            CardResults::Prompt(cards) => cards.into_iter().map(ServerCard::Prompt).collect(),
+            CardResults::Service(cards) => cards
+                .into_iter()
+                .map(|card| ServerCard::Service(Box::new(card)))
+                .collect(),
+            CardResults::Skill(cards) => cards.into_iter().map(ServerCard::Skill).collect(),
+            CardResults::SubAgent(cards) => cards.into_iter().map(ServerCard::SubAgent).collect(),
+            CardResults::Tool(cards) => cards.into_iter().map(ServerCard::Tool).collect(),
+        }
+    }
     pub fn to_json(&self) -> Vec<String> {
         match self {
             CardResults::Data(cards) => cards
@@ -1818,6 +2264,40 @@ impl ServerCard {
         }
     }
 
+    /// The full registry row as JSON, as kept in the trash
+    pub fn to_json(&self) -> Result<Value, SqlError> {
+        let value = match self {
+            ServerCard::Data(card) => serde_json::to_value(card)?,
+            ServerCard::Model(card) => serde_json::to_value(card)?,
+            ServerCard::Experiment(card) => serde_json::to_value(card)?,
+            ServerCard::Audit(card) => serde_json::to_value(card)?,
+            ServerCard::Prompt(card) => serde_json::to_value(card)?,
+            ServerCard::Service(card) => serde_json::to_value(card)?,
+            ServerCard::Skill(card) => serde_json::to_value(card)?,
+            ServerCard::SubAgent(card) => serde_json::to_value(card)?,
+            ServerCard::Tool(card) => serde_json::to_value(card)?,
+        };
+        Ok(value)
+    }
+
+    /// Rebuild a registry row from the JSON written by `to_json`
+    pub fn from_json(table: &CardTable, value: Value) -> Result<Self, SqlError> {
+        match table {
+            CardTable::Data => Ok(ServerCard::Data(serde_json::from_value(value)?)),
+            CardTable::Model => Ok(ServerCard::Model(serde_json::from_value(value)?)),
+            CardTable::Experiment => Ok(ServerCard::Experiment(serde_json::from_value(value)?)),
+            CardTable::Audit => Ok(ServerCard::Audit(serde_json::from_value(value)?)),
+            CardTable::Prompt => Ok(ServerCard::Prompt(serde_json::from_value(value)?)),
+            CardTable::Service | CardTable::Mcp | CardTable::Agent => Ok(ServerCard::Service(
+                Box::new(serde_json::from_value(value)?),
+            )),
+            CardTable::Skill => Ok(ServerCard::Skill(serde_json::from_value(value)?)),
+            CardTable::SubAgent => Ok(ServerCard::SubAgent(serde_json::from_value(value)?)),
+            CardTable::Tool => Ok(ServerCard::Tool(serde_json::from_value(value)?)),
+            _ => Err(SqlError::InvalidTableName),
+        }
+    }
+
     /// Convert a `Card` enum to a `ServerCard` enum.
     ///
     /// # Arguments
@@ -2016,3 +2496,61 @@ impl std::fmt::Debug for User {
             .finish()
     }
 }
+
+/// Long-lived API token issued to a user. Only the hash of the token secret is stored
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub token_id: String,
+    pub username: String,
+    pub name: String,
+    pub token_hash: String,
+    pub permissions: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked: bool,
+}
+
+impl ApiToken {
+    pub fn new(
+        token_id: String,
+        username: String,
+        name: String,
+        token_hash: String,
+        permissions: Vec<String>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        ApiToken {
+            token_id,
+            username,
+            name,
+            token_hash,
+            permissions,
+            created_at: get_utc_datetime(),
+            expires_at,
+            last_used_at: None,
+            revoked: false,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= get_utc_datetime())
+    }
+}
+
+impl std::fmt::Debug for ApiToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiToken")
+            .field("token_id", &self.token_id)
+            .field("username", &self.username)
+            .field("name", &self.name)
+            .field("token_hash", &"[redacted]")
+            .field("permissions", &self.permissions)
+            .field("created_at", &self.created_at)
+            .field("expires_at", &self.expires_at)
+            .field("last_used_at", &self.last_used_at)
+            .field("revoked", &self.revoked)
+            .finish()
+    }
+}
@@ -1,10 +1,13 @@
 use crate::{
     error::SqlError,
     sqlite::sql::{
+        agent_job::AgentJobLogicSqliteClient, alias::AliasLogicSqliteClient,
         artifact::ArtifactLogicSqliteClient, audit::AuditLogicSqliteClient,
         card::CardLogicSqliteClient, evaluation::EvaluationLogicSqliteClient,
-        experiment::ExperimentLogicSqliteClient, space::SpaceLogicSqliteClient,
-        user::UserLogicSqliteClient,
+        event::EventLogicSqliteClient, experiment::ExperimentLogicSqliteClient,
+        group::GroupLogicSqliteClient, lineage::LineageLogicSqliteClient,
+        search::SearchLogicSqliteClient, space::SpaceLogicSqliteClient,
+        trash::TrashLogicSqliteClient, user::UserLogicSqliteClient,
     },
 };
 use opsml_settings::config::DatabaseSettings;
@@ -25,6 +28,13 @@ pub struct SqliteClient {
     pub space: SpaceLogicSqliteClient,
     pub audit: AuditLogicSqliteClient,
     pub eval: EvaluationLogicSqliteClient,
+    pub group: GroupLogicSqliteClient,
+    pub alias: AliasLogicSqliteClient,
+    pub lineage: LineageLogicSqliteClient,
+    pub agent_job: AgentJobLogicSqliteClient,
+    pub event: EventLogicSqliteClient,
+    pub search: SearchLogicSqliteClient,
+    pub trash: TrashLogicSqliteClient,
 }
 
 impl SqliteClient {
@@ -66,6 +76,13 @@ impl SqliteClient {
             space: SpaceLogicSqliteClient::new(&pool),
             audit: AuditLogicSqliteClient::new(&pool),
             eval: EvaluationLogicSqliteClient::new(&pool),
+            group: GroupLogicSqliteClient::new(&pool),
+            alias: AliasLogicSqliteClient::new(&pool),
+            lineage: LineageLogicSqliteClient::new(&pool),
+            agent_job: AgentJobLogicSqliteClient::new(&pool),
+            event: EventLogicSqliteClient::new(&pool),
+            search: SearchLogicSqliteClient::new(&pool),
+            trash: TrashLogicSqliteClient::new(&pool),
             pool,
         };
 
@@ -98,13 +115,15 @@ mod tests {
 
     use crate::schemas::EvaluationSqlRecord;
     use crate::schemas::schema::{
-        ArtifactSqlRecord, AuditCardRecord, CardResults, DataCardRecord, ExperimentCardRecord,
-        HardwareMetricsRecord, MetricRecord, ModelCardRecord, ParameterRecord, PromptCardRecord,
-        ServerCard, ServiceCardRecord, SkillCardRecord, SubAgentCardRecord, ToolCardRecord, User,
+        ApiToken, ArtifactSqlRecord, AuditCardRecord, CardResults, DataCardRecord,
+        ExperimentCardRecord, HardwareMetricsRecord, MetricRecord, ModelCardRecord,
+        ParameterRecord, PromptCardRecord, ServerCard, ServiceCardRecord, SkillCardRecord,
+        SubAgentCardRecord, ToolCardRecord, User, WebhookSqlRecord,
     };
     use crate::traits::{
-        ArtifactLogicTrait, AuditLogicTrait, CardLogicTrait, EvaluationLogicTrait,
-        ExperimentLogicTrait, SpaceLogicTrait, ToolLogicTrait, UserLogicTrait,
+        AgentJobLogicTrait, AliasLogicTrait, ArtifactLogicTrait, AuditLogicTrait, CardLogicTrait,
+        EvaluationLogicTrait, EventLogicTrait, ExperimentLogicTrait, GroupLogicTrait,
+        LineageLogicTrait, SearchLogicTrait, SpaceLogicTrait, ToolLogicTrait, UserLogicTrait,
     };
     use opsml_settings::config::DatabaseSettings;
     use opsml_types::SqlType;
@@ -116,8 +135,11 @@ mod tests {
     };
     use opsml_types::contracts::evaluation::{EvaluationProvider, EvaluationType};
     use opsml_types::contracts::{
-        ArtifactKey, ArtifactQueryArgs, AuditEvent, DeploymentConfig, McpCapability, McpConfig,
-        McpTransport, Resources, ServiceConfig, ServiceQueryArgs, ServiceType, SpaceNameEvent,
+        AgentJobRecord, ArtifactKey, ArtifactQueryArgs, AuditEvent, AuditQueryArgs, AuditStatus,
+        CardAlias, CardEntry, CardSearchDocument, CardSearchQuery, DeliveryStatus,
+        DeploymentConfig, GroupRecord, GroupRole, JobStatus, LineageRelation, McpCapability,
+        McpConfig, McpTransport, Operation, RegistryEvent, RegistryEventType, Resources,
+        ServiceConfig, ServiceQueryArgs, ServiceType, SpaceNameEvent, SpaceRole,
     };
     use opsml_types::{
         RegistryType,
@@ -125,6 +147,7 @@ mod tests {
         contracts::{ArtifactType, CardQueryArgs},
     };
 
+    use opsml_utils::create_uuid7;
     use opsml_utils::utils::get_utc_datetime;
     use semver::Version;
     use sqlx::types::Json;
@@ -1583,6 +1606,823 @@ mod tests {
         cleanup();
     }
 
+    #[tokio::test]
+    async fn test_sqlite_api_tokens() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        let user = User::new(
+            "service".to_string(),
+            "pass".to_string(),
+            "email".to_string(),
+            vec![],
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        client.user.insert_user(&user).await.unwrap();
+
+        let token = ApiToken::new(
+            "token_id".to_string(),
+            "service".to_string(),
+            "ci".to_string(),
+            "hash".to_string(),
+            vec!["read:space".to_string()],
+            Some(chrono::Utc::now() + chrono::Duration::days(1)),
+        );
+        client.user.insert_api_token(&token).await.unwrap();
+
+        let fetched = client
+            .user
+            .get_api_token("token_id")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.username, "service");
+        assert_eq!(fetched.token_hash, "hash");
+        assert_eq!(fetched.permissions, vec!["read:space"]);
+        assert!(fetched.last_used_at.is_none());
+        assert!(!fetched.revoked);
+        assert!(!fetched.is_expired());
+
+        client
+            .user
+            .update_api_token_last_used("token_id")
+            .await
+            .unwrap();
+
+        let tokens = client.user.get_api_tokens("service").await.unwrap();
+        assert_eq!(tokens.len(), 1);
+        assert!(tokens[0].last_used_at.is_some());
+
+        // revoking another user's token is a no-op
+        let revoked = client
+            .user
+            .revoke_api_token("other", "token_id")
+            .await
+            .unwrap();
+        assert!(!revoked);
+
+        let revoked = client
+            .user
+            .revoke_api_token("service", "token_id")
+            .await
+            .unwrap();
+        assert!(revoked);
+
+        let fetched = client
+            .user
+            .get_api_token("token_id")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(fetched.revoked);
+
+        // tokens are removed with their user
+        client.user.delete_user("service").await.unwrap();
+        assert!(
+            client
+                .user
+                .get_api_token("token_id")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_groups() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        let user = User::new(
+            "member".to_string(),
+            "pass".to_string(),
+            "email".to_string(),
+            vec![],
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        client.user.insert_user(&user).await.unwrap();
+
+        let group = GroupRecord {
+            name: "ml-team".to_string(),
+            description: "ML engineers".to_string(),
+            sso_groups: vec!["okta-ml".to_string()],
+            created_at: chrono::Utc::now(),
+        };
+        client.group.insert_group(&group).await.unwrap();
+
+        let fetched = client.group.get_group("ml-team").await.unwrap().unwrap();
+        assert_eq!(fetched.sso_groups, vec!["okta-ml"]);
+        assert_eq!(client.group.get_groups().await.unwrap().len(), 1);
+
+        let mut role = GroupRole {
+            group_name: "ml-team".to_string(),
+            space: "team-a".to_string(),
+            role: SpaceRole::Viewer,
+        };
+        client.group.upsert_group_role(&role).await.unwrap();
+
+        // upserting the same space replaces the role
+        role.role = SpaceRole::Owner;
+        client.group.upsert_group_role(&role).await.unwrap();
+
+        let roles = client.group.get_group_roles("ml-team").await.unwrap();
+        assert_eq!(roles.len(), 1);
+        assert_eq!(roles[0].role, SpaceRole::Owner);
+
+        // adding a member twice is a no-op
+        client
+            .group
+            .add_group_member("ml-team", "member")
+            .await
+            .unwrap();
+        client
+            .group
+            .add_group_member("ml-team", "member")
+            .await
+            .unwrap();
+        assert_eq!(
+            client.group.get_group_members("ml-team").await.unwrap(),
+            vec!["member"]
+        );
+        assert_eq!(
+            client.group.get_user_groups("member").await.unwrap(),
+            vec!["ml-team"]
+        );
+
+        let user_roles = client.group.get_user_group_roles("member").await.unwrap();
+        assert_eq!(user_roles, vec![role]);
+
+        client
+            .group
+            .delete_group_role("ml-team", "team-a")
+            .await
+            .unwrap();
+        assert!(
+            client
+                .group
+                .get_user_group_roles("member")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        client
+            .group
+            .remove_group_member("ml-team", "member")
+            .await
+            .unwrap();
+        assert!(
+            client
+                .group
+                .get_user_groups("member")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        client.group.delete_group("ml-team").await.unwrap();
+        assert!(client.group.get_group("ml-team").await.unwrap().is_none());
+
+        client.user.delete_user("member").await.unwrap();
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_card_aliases() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        let mut alias = CardAlias {
+            space: "repo1".to_string(),
+            name: "model".to_string(),
+            registry_type: RegistryType::Model,
+            alias: "production".to_string(),
+            uid: create_uuid7(),
+            version: "1.0.0".to_string(),
+            updated_by: "guest".to_string(),
+            updated_at: get_utc_datetime(),
+        };
+        client.alias.upsert_card_alias(&alias).await.unwrap();
+
+        // moving the alias replaces the target version
+        alias.uid = create_uuid7();
+        alias.version = "1.1.0".to_string();
+        client.alias.upsert_card_alias(&alias).await.unwrap();
+
+        let fetched = client
+            .alias
+            .get_card_alias("repo1", "model", &RegistryType::Model, "production")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.version, "1.1.0");
+        assert_eq!(fetched.uid, alias.uid);
+
+        let aliases = client
+            .alias
+            .get_card_aliases("repo1", "model", &RegistryType::Model)
+            .await
+            .unwrap();
+        assert_eq!(aliases.len(), 1);
+
+        assert!(
+            client
+                .alias
+                .delete_card_alias("repo1", "model", &RegistryType::Model, "production")
+                .await
+                .unwrap()
+        );
+        assert!(
+            client
+                .alias
+                .get_card_alias("repo1", "model", &RegistryType::Model, "production")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_card_search() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        let model = CardSearchDocument {
+            uid: create_uuid7(),
+            registry_type: RegistryType::Model,
+            space: "retention".to_string(),
+            name: "xgb-classifier".to_string(),
+            version: "1.0.0".to_string(),
+            tags: vec!["tabular".to_string()],
+            ..Default::default()
+        };
+        let skill = CardSearchDocument {
+            uid: create_uuid7(),
+            registry_type: RegistryType::Skill,
+            space: "agents".to_string(),
+            name: "summarize".to_string(),
+            version: "0.1.0".to_string(),
+            description: Some("Summarize documents".to_string()),
+            body: Some("Explain why customers churn before summarizing".to_string()),
+            ..Default::default()
+        };
+        client.search.upsert_search_document(&model).await.unwrap();
+        client.search.upsert_search_document(&skill).await.unwrap();
+
+        // skill body is searchable, the model is not yet
+        let query = CardSearchQuery {
+            query: "churn".to_string(),
+            ..Default::default()
+        };
+        let hits = client.search.search_cards(&query, None).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].uid, skill.uid);
+        assert!(hits[0].highlight.as_ref().unwrap().contains("<mark>"));
+
+        // readme applies to the card and is inherited by new versions
+        client
+            .search
+            .update_search_readme(
+                &RegistryType::Model,
+                "retention",
+                "xgb-classifier",
+                "Predicts customer churn from usage",
+            )
+            .await
+            .unwrap();
+        let next = CardSearchDocument {
+            uid: create_uuid7(),
+            version: "1.1.0".to_string(),
+            ..model.clone()
+        };
+        client.search.upsert_search_document(&next).await.unwrap();
+
+        let hits = client.search.search_cards(&query, None).await.unwrap();
+        assert_eq!(hits.len(), 3);
+
+        // only the readable spaces are searched
+        let spaces = vec!["agents".to_string()];
+        let hits = client
+            .search
+            .search_cards(&query, Some(&spaces))
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].uid, skill.uid);
+
+        let query = CardSearchQuery {
+            query: "churn".to_string(),
+            registry_type: Some(RegistryType::Model),
+            limit: Some(1),
+            ..Default::default()
+        };
+        let hits = client.search.search_cards(&query, None).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].registry_type, RegistryType::Model);
+
+        let next_page = CardSearchQuery {
+            offset: Some(1),
+            ..query.clone()
+        };
+        let page = client.search.search_cards(&next_page, None).await.unwrap();
+        assert_eq!(page.len(), 1);
+        assert_ne!(page[0].uid, hits[0].uid);
+
+        client
+            .search
+            .delete_search_document(&skill.uid)
+            .await
+            .unwrap();
+        let query = CardSearchQuery {
+            query: "summarize".to_string(),
+            ..Default::default()
+        };
+        assert!(
+            client
+                .search
+                .search_cards(&query, None)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_agent_jobs() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        let mut job = AgentJobRecord {
+            job_id: create_uuid7(),
+            agent_id: "skill-scan".to_string(),
+            owner: "guest".to_string(),
+            status: JobStatus::Pending,
+            result: None,
+            error: None,
+            duration_ms: None,
+            session_id: Some(create_uuid7()),
+            prompt: None,
+            instance_id: "opsml-0".to_string(),
+            created_at: get_utc_datetime(),
+            updated_at: get_utc_datetime(),
+        };
+        client.agent_job.upsert_agent_job(&job).await.unwrap();
+
+        job.status = JobStatus::NeedsInput;
+        job.prompt = Some("Which environment?".to_string());
+        client.agent_job.upsert_agent_job(&job).await.unwrap();
+
+        let fetched = client
+            .agent_job
+            .get_agent_job(&job.job_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.status, JobStatus::NeedsInput);
+        assert_eq!(fetched.prompt, job.prompt);
+        assert_eq!(fetched.session_id, job.session_id);
+
+        job.status = JobStatus::Done;
+        job.result = Some(serde_json::json!({"verdict": "safe"}));
+        job.prompt = None;
+        job.duration_ms = Some(42);
+        client.agent_job.upsert_agent_job(&job).await.unwrap();
+
+        let fetched = client
+            .agent_job
+            .get_agent_job(&job.job_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.status, JobStatus::Done);
+        assert_eq!(fetched.result, job.result);
+        assert_eq!(fetched.duration_ms, Some(42));
+
+        // this instance's in-flight jobs fail on restart, fresh jobs of other instances are kept
+        let running = AgentJobRecord {
+            job_id: create_uuid7(),
+            status: JobStatus::Running,
+            ..job.clone()
+        };
+        client.agent_job.upsert_agent_job(&running).await.unwrap();
+        let other_instance = AgentJobRecord {
+            job_id: create_uuid7(),
+            status: JobStatus::Running,
+            instance_id: "opsml-1".to_string(),
+            ..job.clone()
+        };
+        client
+            .agent_job
+            .upsert_agent_job(&other_instance)
+            .await
+            .unwrap();
+        let failed = client
+            .agent_job
+            .fail_incomplete_agent_jobs(
+                "interrupted",
+                Some("opsml-0"),
+                &(get_utc_datetime() - chrono::Duration::seconds(60)),
+            )
+            .await
+            .unwrap();
+        assert_eq!(failed, 1);
+
+        // live instances keep their jobs fresh
+        let touched = client
+            .agent_job
+            .heartbeat_agent_jobs("opsml-1")
+            .await
+            .unwrap();
+        assert_eq!(touched, 1);
+
+        let fetched = client
+            .agent_job
+            .get_agent_job(&running.job_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.status, JobStatus::Failed);
+        assert_eq!(fetched.error.as_deref(), Some("interrupted"));
+
+        let fetched = client
+            .agent_job
+            .get_agent_job(&other_instance.job_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.status, JobStatus::Running);
+        assert_eq!(fetched.instance_id, "opsml-1");
+
+        // jobs whose heartbeat is stale are failed whatever instance ran them
+        let failed = client
+            .agent_job
+            .fail_incomplete_agent_jobs(
+                "stale",
+                None,
+                &(get_utc_datetime() + chrono::Duration::seconds(60)),
+            )
+            .await
+            .unwrap();
+        assert_eq!(failed, 1);
+
+        let fetched = client
+            .agent_job
+            .get_agent_job(&other_instance.job_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.status, JobStatus::Failed);
+        assert_eq!(fetched.error.as_deref(), Some("stale"));
+
+        let cutoff = get_utc_datetime() + chrono::Duration::seconds(60);
+        let deleted = client
+            .agent_job
+            .delete_expired_agent_jobs(&cutoff)
+            .await
+            .unwrap();
+        assert_eq!(deleted, 3);
+        assert!(
+            client
+                .agent_job
+                .get_agent_job(&job.job_id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_events() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        let event = RegistryEvent::new(
+            RegistryEventType::CardCreated,
+            "repo1",
+            Some(RegistryType::Model),
+            &create_uuid7(),
+            serde_json::json!({"name": "model1", "version": "1.0.0"}),
+            "guest",
+        );
+        client.event.insert_registry_event(&event).await.unwrap();
+
+        let fetched = client
+            .event
+            .get_registry_event(&event.event_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.event_type, RegistryEventType::CardCreated);
+        assert_eq!(fetched.registry_type, Some(RegistryType::Model));
+        assert_eq!(fetched.payload, event.payload);
+
+        let claimed_at = get_utc_datetime();
+        let lease_until = claimed_at + chrono::Duration::seconds(60);
+        let pending = client
+            .event
+            .claim_undispatched_events("claim-1", &claimed_at, &lease_until, 10)
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 1);
+
+        // another instance skips the event while the claim is held
+        assert!(
+            client
+                .event
+                .claim_undispatched_events("claim-2", &claimed_at, &lease_until, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // a released claim is picked up by the next poll
+        client.event.release_event_claim("claim-1").await.unwrap();
+        let pending = client
+            .event
+            .claim_undispatched_events("claim-2", &claimed_at, &lease_until, 10)
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 1);
+
+        client
+            .event
+            .mark_event_dispatched(&event.event_id)
+            .await
+            .unwrap();
+
+        // dispatched events are not claimed again once the lease expires
+        let expired = lease_until + chrono::Duration::seconds(1);
+        assert!(
+            client
+                .event
+                .claim_undispatched_events("claim-3", &expired, &expired, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let webhook = WebhookSqlRecord {
+            id: create_uuid7(),
+            space: "repo1".to_string(),
+            url: "http://localhost:8080/hook".to_string(),
+            event_types: Json(vec![RegistryEventType::CardCreated]),
+            secret: "secret".to_string(),
+            active: true,
+            created_by: "guest".to_string(),
+            created_at: get_utc_datetime(),
+        };
+        client.event.insert_webhook(&webhook).await.unwrap();
+
+        let webhooks = client.event.list_webhooks("repo1").await.unwrap();
+        assert_eq!(webhooks.len(), 1);
+        assert_eq!(
+            webhooks[0].event_types.0,
+            vec![RegistryEventType::CardCreated]
+        );
+        assert_eq!(webhooks[0].secret, "secret");
+
+        // queuing the same event twice keeps a single delivery
+        let now = get_utc_datetime();
+        for _ in 0..2 {
+            client
+                .event
+                .insert_webhook_delivery(&webhook.id, &event.event_id, &now)
+                .await
+                .unwrap();
+        }
+
+        let later = now + chrono::Duration::seconds(60);
+        let lease_until = later + chrono::Duration::seconds(600);
+        let mut due = client
+            .event
+            .claim_due_webhook_deliveries("claim-1", &later, &lease_until, 10)
+            .await
+            .unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].status, DeliveryStatus::Pending);
+
+        // a claimed delivery is not handed to another instance
+        assert!(
+            client
+                .event
+                .claim_due_webhook_deliveries("claim-2", &later, &lease_until, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // a failed attempt is retried later, so it is not due yet
+        let mut delivery = due.remove(0);
+        delivery.attempts = 1;
+        delivery.next_attempt_at = later + chrono::Duration::seconds(60);
+        delivery.last_error = Some("connection refused".to_string());
+        client
+            .event
+            .update_webhook_delivery(&delivery)
+            .await
+            .unwrap();
+        assert!(
+            client
+                .event
+                .claim_due_webhook_deliveries("claim-3", &later, &lease_until, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        let deliveries = client
+            .event
+            .list_webhook_deliveries(&webhook.id, 10)
+            .await
+            .unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].attempts, 1);
+        assert_eq!(deliveries[0].last_error, delivery.last_error);
+
+        assert!(client.event.delete_webhook(&webhook.id).await.unwrap());
+        assert!(!client.event.delete_webhook(&webhook.id).await.unwrap());
+        assert!(
+            client
+                .event
+                .get_webhook(&webhook.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            client
+                .event
+                .list_webhook_deliveries(&webhook.id, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        cleanup();
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_lineage() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        let data = DataCardRecord::default();
+        let model = ModelCardRecord {
+            datacard_uid: Some(data.uid.clone()),
+            ..Default::default()
+        };
+        let experiment = ExperimentCardRecord {
+            datacard_uids: Json(vec![data.uid.clone()]),
+            ..Default::default()
+        };
+        let service = ServiceCardRecord {
+            cards: Json(vec![CardEntry {
+                registry_type: RegistryType::Model,
+                uid: Some(model.uid.clone()),
+                version: Some(model.version.clone()),
+                alias: "model".to_string(),
+            }]),
+            ..Default::default()
+        };
+
+        client
+            .card
+            .insert_card(&CardTable::Data, &ServerCard::Data(data.clone()))
+            .await
+            .unwrap();
+        client
+            .card
+            .insert_card(&CardTable::Model, &ServerCard::Model(model.clone()))
+            .await
+            .unwrap();
+        client
+            .card
+            .insert_card(
+                &CardTable::Experiment,
+                &ServerCard::Experiment(experiment.clone()),
+            )
+            .await
+            .unwrap();
+        client
+            .card
+            .insert_card(
+                &CardTable::Service,
+                &ServerCard::Service(Box::new(service.clone())),
+            )
+            .await
+            .unwrap();
+
+        let edges = client.lineage.get_lineage_edges(&data.uid).await.unwrap();
+        assert_eq!(edges.len(), 2);
+        assert!(
+            edges
+                .iter()
+                .any(|edge| edge.relation == LineageRelation::TrainedOn
+                    && edge.source_uid == data.uid
+                    && edge.target_uid == model.uid)
+        );
+        assert!(
+            edges
+                .iter()
+                .any(|edge| edge.relation == LineageRelation::ProducedBy
+                    && edge.source_uid == experiment.uid
+                    && edge.target_type == RegistryType::Data)
+        );
+
+        let edges = client
+            .lineage
+            .get_lineage_edges(&service.uid)
+            .await
+            .unwrap();
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].relation, LineageRelation::DeployedIn);
+        assert_eq!(edges[0].source_type, RegistryType::Model);
+
+        let node = client
+            .lineage
+            .get_lineage_node(&CardTable::Model, &model.uid)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(node.version, model.version);
+        assert!(
+            client
+                .lineage
+                .get_lineage_node(&CardTable::Data, &model.uid)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        cleanup();
+    }
+
     #[tokio::test]
     async fn test_sqlite_artifact_keys() {
         cleanup();
@@ -1603,6 +2443,8 @@ mod tests {
             registry_type: RegistryType::Data,
             encrypted_key: encrypted_key.clone(),
             storage_key: "opsml_registry".to_string(),
+            key_version: 0,
+            manifest_sha256: None,
         };
 
         client.artifact.insert_artifact_key(&key).await.unwrap();
@@ -1623,6 +2465,8 @@ mod tests {
             registry_type: RegistryType::Data,
             encrypted_key: encrypted_key.clone(),
             storage_key: "opsml_registry".to_string(),
+            key_version: 0,
+            manifest_sha256: None,
         };
 
         client.artifact.update_artifact_key(&key).await.unwrap();
@@ -1634,6 +2478,65 @@ mod tests {
             .unwrap();
 
         assert_eq!(key.encrypted_key, encrypted_key);
+
+        // rewrap with a new key-encryption-key version
+        let stale = client
+            .artifact
+            .get_artifact_keys_to_rewrap(2, "", 10)
+            .await
+            .unwrap();
+        assert_eq!(stale.len(), 1);
+
+        let rewrapped = ArtifactKey {
+            encrypted_key: (64..96).collect(),
+            key_version: 2,
+            ..key.clone()
+        };
+        assert!(
+            client
+                .artifact
+                .rewrap_artifact_key(&rewrapped, 0)
+                .await
+                .unwrap()
+        );
+        // a second rewrap from the old version is a no-op
+        assert!(
+            !client
+                .artifact
+                .rewrap_artifact_key(&rewrapped, 0)
+                .await
+                .unwrap()
+        );
+
+        let key = client
+            .artifact
+            .get_artifact_key(&key.uid, &key.registry_type.to_string())
+            .await
+            .unwrap();
+        assert_eq!(key.key_version, 2);
+        assert!(
+            client
+                .artifact
+                .get_artifact_keys_to_rewrap(2, "", 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // record the manifest digest
+        assert!(key.manifest_sha256.is_none());
+        client
+            .artifact
+            .set_artifact_manifest(&key.uid, &key.registry_type.to_string(), "abc123")
+            .await
+            .unwrap();
+
+        let key = client
+            .artifact
+            .get_artifact_key(&key.uid, &key.registry_type.to_string())
+            .await
+            .unwrap();
+        assert_eq!(key.manifest_sha256.as_deref(), Some("abc123"));
     }
 
     #[tokio::test]
@@ -1664,6 +2567,82 @@ mod tests {
         assert_eq!(result, "guest");
     }
 
+    #[tokio::test]
+    async fn test_sqlite_query_audit_events() {
+        cleanup();
+
+        let config = DatabaseSettings {
+            connection_uri: get_connection_uri(),
+            max_connections: 1,
+            sql_type: SqlType::Sqlite,
+        };
+
+        let client = SqliteClient::new(&config).await.unwrap();
+
+        let events = vec![
+            ("audit_alice", Operation::Read, AuditStatus::Success),
+            ("audit_alice", Operation::Read, AuditStatus::Success),
+            ("audit_bob", Operation::Delete, AuditStatus::Denied),
+        ];
+
+        for (username, operation, status) in events {
+            let event = AuditEvent {
+                username: username.to_string(),
+                operation,
+                status,
+                ..Default::default()
+            };
+            client.audit.insert_audit_event(event).await.unwrap();
+        }
+
+        // filter by username
+        let args = AuditQueryArgs {
+            username: Some("audit_alice".to_string()),
+            ..Default::default()
+        };
+        let records = client.audit.query_audit_events(&args).await.unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(records[0].id > records[1].id);
+
+        // filter by operation and status
+        let args = AuditQueryArgs {
+            operation: Some("Delete".to_string()),
+            status: Some("Denied".to_string()),
+            registry_type: Some(RegistryType::Model),
+            ..Default::default()
+        };
+        let records = client.audit.query_audit_events(&args).await.unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].username, "audit_bob");
+
+        // paginate with cursor
+        let mut args = AuditQueryArgs {
+            username: Some("audit_alice".to_string()),
+            limit: Some(1),
+            ..Default::default()
+        };
+        let first = client.audit.query_audit_events(&args).await.unwrap();
+        assert_eq!(first.len(), 1);
+
+        args.cursor = Some(first[0].id);
+        let second = client.audit.query_audit_events(&args).await.unwrap();
+        assert_eq!(second.len(), 1);
+        assert!(second[0].id < first[0].id);
+
+        args.cursor = Some(second[0].id);
+        let third = client.audit.query_audit_events(&args).await.unwrap();
+        assert!(third.is_empty());
+
+        // time range in the future returns nothing
+        let args = AuditQueryArgs {
+            username: Some("audit_alice".to_string()),
+            start_time: Some(get_utc_datetime() + chrono::Duration::days(1)),
+            ..Default::default()
+        };
+        let records = client.audit.query_audit_events(&args).await.unwrap();
+        assert!(records.is_empty());
+    }
+
     #[tokio::test]
     async fn test_sqlite_get_load_card_key() {
         cleanup();
@@ -1690,6 +2669,8 @@ mod tests {
             registry_type: RegistryType::Data,
             encrypted_key: encrypted_key.clone(),
             storage_key: "opsml_registry".to_string(),
+            key_version: 0,
+            manifest_sha256: None,
         };
 
         client.artifact.insert_artifact_key(&key).await.unwrap();
@@ -1767,6 +2748,8 @@ mod tests {
             registry_type: RegistryType::Service,
             encrypted_key: encrypted_key.clone(),
             storage_key: "opsml_registry".to_string(),
+            key_version: 0,
+            manifest_sha256: None,
         };
 
         client.artifact.insert_artifact_key(&key).await.unwrap();
@@ -1936,6 +2919,7 @@ mod tests {
             Version::new(0, 0, 0),
             "png".to_string(),
             ArtifactType::Generic.to_string(),
+            "development".to_string(),
         );
         client
             .artifact
@@ -1949,6 +2933,7 @@ mod tests {
             Version::new(0, 0, 0),
             "png".to_string(),
             ArtifactType::Figure.to_string(),
+            "development".to_string(),
         );
 
         client
@@ -1987,6 +2972,7 @@ mod tests {
             "test".to_string(),
             EvaluationType::Agent,
             EvaluationProvider::Opsml,
+            "development".to_string(),
         );
         let uid = eval_record.uid.clone();
         client
@@ -2289,6 +3275,7 @@ mod tests {
             "0.0.0".to_string(),
             "admin".to_string(),
             None,
+            "development".to_string(),
         );
 
         let uid = record.uid.clone();
@@ -0,0 +1,14 @@
+-- Long-lived, revocable API tokens for service accounts
+CREATE TABLE IF NOT EXISTS opsml_user_api_token (
+    token_id TEXT NOT NULL PRIMARY KEY,
+    username TEXT NOT NULL,
+    name TEXT NOT NULL,
+    token_hash TEXT NOT NULL,
+    permissions TEXT NOT NULL DEFAULT '[]',
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP,
+    last_used_at TIMESTAMP,
+    revoked BOOLEAN NOT NULL DEFAULT FALSE,
+    FOREIGN KEY (username) REFERENCES opsml_user (username) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_user_api_token_username ON opsml_user_api_token (username);
@@ -0,0 +1,26 @@
+-- Groups with space-scoped roles, replacing flat per-user permission strings
+CREATE TABLE IF NOT EXISTS opsml_group (
+    name TEXT NOT NULL PRIMARY KEY,
+    description TEXT NOT NULL DEFAULT '',
+    sso_groups TEXT NOT NULL DEFAULT '[]',
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS opsml_group_role (
+    group_name TEXT NOT NULL,
+    space TEXT NOT NULL,
+    role TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (group_name, space),
+    FOREIGN KEY (group_name) REFERENCES opsml_group (name) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS opsml_group_member (
+    group_name TEXT NOT NULL,
+    username TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (group_name, username),
+    FOREIGN KEY (group_name) REFERENCES opsml_group (name) ON DELETE CASCADE,
+    FOREIGN KEY (username) REFERENCES opsml_user (username) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS idx_group_member_username ON opsml_group_member (username);
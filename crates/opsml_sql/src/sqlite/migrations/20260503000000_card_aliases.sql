@@ -0,0 +1,12 @@
+-- Movable stage aliases (e.g. production, staging) pointing at a concrete card version
+CREATE TABLE IF NOT EXISTS opsml_card_alias (
+    space TEXT NOT NULL,
+    name TEXT NOT NULL,
+    registry_type TEXT NOT NULL,
+    alias TEXT NOT NULL,
+    uid TEXT NOT NULL,
+    version TEXT NOT NULL,
+    updated_by TEXT NOT NULL DEFAULT 'guest',
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (space, name, registry_type, alias)
+);
@@ -0,0 +1,15 @@
+-- Agent invocations dispatched asynchronously, kept so job status survives restarts
+CREATE TABLE IF NOT EXISTS opsml_agent_job (
+    job_id TEXT PRIMARY KEY,
+    agent_id TEXT NOT NULL,
+    owner TEXT NOT NULL,
+    status TEXT NOT NULL,
+    result TEXT,
+    error TEXT,
+    duration_ms INTEGER,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_agent_job_status ON opsml_agent_job (status);
+CREATE INDEX IF NOT EXISTS idx_opsml_agent_job_updated_at ON opsml_agent_job (updated_at);
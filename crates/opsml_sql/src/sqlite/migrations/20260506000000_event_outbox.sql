@@ -0,0 +1,43 @@
+-- Durable outbox of registry events and the webhooks they are delivered to
+CREATE TABLE IF NOT EXISTS opsml_event_outbox (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_id TEXT NOT NULL UNIQUE,
+    event_type TEXT NOT NULL,
+    space TEXT NOT NULL,
+    registry_type TEXT,
+    resource_id TEXT NOT NULL,
+    payload TEXT NOT NULL DEFAULT '{}',
+    username TEXT NOT NULL,
+    dispatched BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_event_outbox_dispatched ON opsml_event_outbox (dispatched);
+
+CREATE TABLE IF NOT EXISTS opsml_webhook (
+    id TEXT PRIMARY KEY,
+    space TEXT NOT NULL,
+    url TEXT NOT NULL,
+    event_types TEXT NOT NULL DEFAULT '[]',
+    secret TEXT NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by TEXT NOT NULL,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_webhook_space ON opsml_webhook (space);
+
+CREATE TABLE IF NOT EXISTS opsml_webhook_delivery (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    webhook_id TEXT NOT NULL,
+    event_id TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMP NOT NULL,
+    last_error TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (webhook_id, event_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_webhook_delivery_status ON opsml_webhook_delivery (status, next_attempt_at);
@@ -0,0 +1,65 @@
+-- Full-text search index over cards. One document per card version
+CREATE TABLE IF NOT EXISTS opsml_card_search (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    uid TEXT NOT NULL UNIQUE,
+    registry_type TEXT NOT NULL,
+    space TEXT NOT NULL,
+    name TEXT NOT NULL,
+    version TEXT NOT NULL,
+    tags TEXT NOT NULL DEFAULT '',
+    description TEXT,
+    readme TEXT,
+    body TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_card_search_card ON opsml_card_search (registry_type, space, name);
+
+-- External content FTS5 table; kept in sync with opsml_card_search by the triggers below
+CREATE VIRTUAL TABLE IF NOT EXISTS opsml_card_search_fts USING fts5(
+    name, space, tags, description, readme, body,
+    content = 'opsml_card_search',
+    content_rowid = 'id',
+    tokenize = 'porter unicode61'
+);
+
+CREATE TRIGGER IF NOT EXISTS opsml_card_search_ai AFTER INSERT ON opsml_card_search BEGIN
+    INSERT INTO opsml_card_search_fts (rowid, name, space, tags, description, readme, body)
+    VALUES (new.id, new.name, new.space, new.tags, new.description, new.readme, new.body);
+END;
+
+CREATE TRIGGER IF NOT EXISTS opsml_card_search_ad AFTER DELETE ON opsml_card_search BEGIN
+    INSERT INTO opsml_card_search_fts (opsml_card_search_fts, rowid, name, space, tags, description, readme, body)
+    VALUES ('delete', old.id, old.name, old.space, old.tags, old.description, old.readme, old.body);
+END;
+
+CREATE TRIGGER IF NOT EXISTS opsml_card_search_au AFTER UPDATE ON opsml_card_search BEGIN
+    INSERT INTO opsml_card_search_fts (opsml_card_search_fts, rowid, name, space, tags, description, readme, body)
+    VALUES ('delete', old.id, old.name, old.space, old.tags, old.description, old.readme, old.body);
+    INSERT INTO opsml_card_search_fts (rowid, name, space, tags, description, readme, body)
+    VALUES (new.id, new.name, new.space, new.tags, new.description, new.readme, new.body);
+END;
+
+-- Index existing cards. READMEs and skill bodies are picked up the next time they are written
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'data', space, name, version, COALESCE(tags, ''), NULL, created_at FROM opsml_data_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'model', space, name, version, COALESCE(tags, ''), NULL, created_at FROM opsml_model_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'experiment', space, name, version, COALESCE(tags, ''), NULL, created_at FROM opsml_experiment_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'audit', space, name, version, COALESCE(tags, ''), NULL, created_at FROM opsml_audit_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'prompt', space, name, version, COALESCE(tags, ''), NULL, created_at FROM opsml_prompt_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'service', space, name, version, COALESCE(tags, ''), NULL, created_at FROM opsml_service_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'mcp', space, name, version, COALESCE(tags, ''), NULL, created_at FROM opsml_mcp_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'agent', space, name, version, COALESCE(tags, ''), NULL, created_at FROM opsml_agent_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'skill', space, name, version, COALESCE(tags, ''), description, created_at FROM opsml_skill_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'subagent', space, name, version, COALESCE(tags, ''), description, created_at FROM opsml_subagent_registry;
+INSERT INTO opsml_card_search (uid, registry_type, space, name, version, tags, description, created_at)
+SELECT uid, 'tool', space, name, version, COALESCE(tags, ''), description, created_at FROM opsml_tool_registry;
@@ -0,0 +1,25 @@
+-- Cards moved to the trash by a delete or a retention policy. `record` holds the deleted row so it
+-- can be restored; artifacts and the artifact key are kept until the card is purged
+CREATE TABLE IF NOT EXISTS opsml_card_trash (
+    uid TEXT PRIMARY KEY,
+    registry_type TEXT NOT NULL,
+    space TEXT NOT NULL,
+    name TEXT NOT NULL,
+    version TEXT NOT NULL,
+    record JSON NOT NULL,
+    reason TEXT NOT NULL DEFAULT 'deleted',
+    deleted_by TEXT NOT NULL DEFAULT 'guest',
+    deleted_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_opsml_card_trash_space ON opsml_card_trash (space, deleted_at);
+
+-- Per-space retention rules applied by the server's retention task
+CREATE TABLE IF NOT EXISTS opsml_retention_policy (
+    space TEXT PRIMARY KEY,
+    keep_last_versions INTEGER,
+    purge_trashed_after_days INTEGER,
+    protect_aliased BOOLEAN NOT NULL DEFAULT 1,
+    updated_by TEXT NOT NULL DEFAULT 'guest',
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+);
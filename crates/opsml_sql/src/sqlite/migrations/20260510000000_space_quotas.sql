@@ -0,0 +1,12 @@
+-- Storage quotas set by admins. NULL means no limit
+ALTER TABLE opsml_space ADD COLUMN soft_quota_bytes INTEGER;
+ALTER TABLE opsml_space ADD COLUMN hard_quota_bytes INTEGER;
+
+-- Artifact bytes uploaded per space and registry, kept up to date by the files router
+CREATE TABLE IF NOT EXISTS opsml_space_usage (
+    space TEXT NOT NULL,
+    registry_type TEXT NOT NULL,
+    bytes INTEGER NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (space, registry_type)
+);
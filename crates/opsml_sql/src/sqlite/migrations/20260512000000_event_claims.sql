@@ -0,0 +1,7 @@
+-- Claims let several server instances share the outbox and delivery queue without handling a row twice
+ALTER TABLE opsml_event_outbox ADD COLUMN claim_id TEXT;
+ALTER TABLE opsml_event_outbox ADD COLUMN claimed_until TIMESTAMP;
+ALTER TABLE opsml_webhook_delivery ADD COLUMN claim_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_opsml_event_outbox_claim ON opsml_event_outbox (claim_id);
+CREATE INDEX IF NOT EXISTS idx_opsml_webhook_delivery_claim ON opsml_webhook_delivery (claim_id);
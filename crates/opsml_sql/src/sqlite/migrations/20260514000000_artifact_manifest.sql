@@ -0,0 +1,2 @@
+-- SHA-256 of the card's plaintext manifest.json, recorded after its artifacts are uploaded. NULL for cards uploaded before manifests were tracked
+ALTER TABLE opsml_artifact_key ADD COLUMN manifest_sha256 TEXT;
\ No newline at end of file
@@ -0,0 +1 @@
+UPDATE opsml_agent_job SET status = 'failed', error = ?, updated_at = CURRENT_TIMESTAMP WHERE status IN ('pending', 'running') AND (instance_id = ? OR updated_at < DATETIME(?));
\ No newline at end of file
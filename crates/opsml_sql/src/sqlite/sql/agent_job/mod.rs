@@ -0,0 +1,87 @@
+use crate::{sqlite::helper::SqliteQueryHelper, traits::AgentJobLogicTrait};
+
+use crate::error::SqlError;
+use crate::schemas::schema::AgentJobSqlRecord;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use opsml_types::contracts::AgentJobRecord;
+use sqlx::{Pool, Sqlite};
+
+#[derive(Debug, Clone)]
+pub struct AgentJobLogicSqliteClient {
+    pool: sqlx::Pool<Sqlite>,
+}
+impl AgentJobLogicSqliteClient {
+    pub fn new(pool: &Pool<Sqlite>) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
+
+#[async_trait]
+impl AgentJobLogicTrait for AgentJobLogicSqliteClient {
+    async fn upsert_agent_job(&self, job: &AgentJobRecord) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_agent_job_upsert_query();
+        let result = job.result.as_ref().map(serde_json::to_string).transpose()?;
+
+        sqlx::query(query)
+            .bind(&job.job_id)
+            .bind(&job.agent_id)
+            .bind(&job.owner)
+            .bind(job.status.to_string())
+            .bind(result)
+            .bind(&job.error)
+            .bind(job.duration_ms.map(|duration| duration as i64))
+            .bind(&job.session_id)
+            .bind(&job.prompt)
+            .bind(&job.instance_id)
+            .bind(job.created_at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_agent_job(&self, job_id: &str) -> Result<Option<AgentJobRecord>, SqlError> {
+        let query = SqliteQueryHelper::get_agent_job_query();
+        let record: Option<AgentJobSqlRecord> = sqlx::query_as(query)
+            .bind(job_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        record.map(AgentJobRecord::try_from).transpose()
+    }
+
+    async fn fail_incomplete_agent_jobs(
+        &self,
+        error: &str,
+        instance_id: Option<&str>,
+        stale_before: &DateTime<Utc>,
+    ) -> Result<u64, SqlError> {
+        let query = SqliteQueryHelper::get_fail_incomplete_agent_jobs_query();
+        let result = sqlx::query(query)
+            .bind(error)
+            .bind(instance_id)
+            .bind(stale_before)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn heartbeat_agent_jobs(&self, instance_id: &str) -> Result<u64, SqlError> {
+        let query = SqliteQueryHelper::get_heartbeat_agent_jobs_query();
+        let result = sqlx::query(query)
+            .bind(instance_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_expired_agent_jobs(&self, cutoff: &DateTime<Utc>) -> Result<u64, SqlError> {
+        let query = SqliteQueryHelper::get_delete_expired_agent_jobs_query();
+        let result = sqlx::query(query).bind(cutoff).execute(&self.pool).await?;
+
+        Ok(result.rows_affected())
+    }
+}
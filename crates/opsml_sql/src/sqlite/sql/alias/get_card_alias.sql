@@ -0,0 +1 @@
+SELECT space, name, registry_type, alias, uid, version, updated_by, updated_at FROM opsml_card_alias WHERE space = ? AND name = ? AND registry_type = ? AND alias = ?;
\ No newline at end of file
@@ -0,0 +1,93 @@
+use crate::{sqlite::helper::SqliteQueryHelper, traits::AliasLogicTrait};
+
+use crate::error::SqlError;
+use crate::schemas::schema::CardAliasSqlRecord;
+use async_trait::async_trait;
+use opsml_types::RegistryType;
+use opsml_types::contracts::CardAlias;
+use sqlx::{Pool, Sqlite};
+
+#[derive(Debug, Clone)]
+pub struct AliasLogicSqliteClient {
+    pool: sqlx::Pool<Sqlite>,
+}
+impl AliasLogicSqliteClient {
+    pub fn new(pool: &Pool<Sqlite>) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
+
+#[async_trait]
+impl AliasLogicTrait for AliasLogicSqliteClient {
+    async fn upsert_card_alias(&self, alias: &CardAlias) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_card_alias_upsert_query();
+
+        sqlx::query(query)
+            .bind(&alias.space)
+            .bind(&alias.name)
+            .bind(alias.registry_type.to_string())
+            .bind(&alias.alias)
+            .bind(&alias.uid)
+            .bind(&alias.version)
+            .bind(&alias.updated_by)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_card_alias(
+        &self,
+        space: &str,
+        name: &str,
+        registry_type: &RegistryType,
+        alias: &str,
+    ) -> Result<Option<CardAlias>, SqlError> {
+        let query = SqliteQueryHelper::get_card_alias_query();
+        let record: Option<CardAliasSqlRecord> = sqlx::query_as(query)
+            .bind(space)
+            .bind(name)
+            .bind(registry_type.to_string())
+            .bind(alias)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        record.map(CardAlias::try_from).transpose()
+    }
+
+    async fn get_card_aliases(
+        &self,
+        space: &str,
+        name: &str,
+        registry_type: &RegistryType,
+    ) -> Result<Vec<CardAlias>, SqlError> {
+        let query = SqliteQueryHelper::get_card_aliases_query();
+        let records: Vec<CardAliasSqlRecord> = sqlx::query_as(query)
+            .bind(space)
+            .bind(name)
+            .bind(registry_type.to_string())
+            .fetch_all(&self.pool)
+            .await?;
+
+        records.into_iter().map(CardAlias::try_from).collect()
+    }
+
+    async fn delete_card_alias(
+        &self,
+        space: &str,
+        name: &str,
+        registry_type: &RegistryType,
+        alias: &str,
+    ) -> Result<bool, SqlError> {
+        let query = SqliteQueryHelper::get_card_alias_delete_query();
+        let result = sqlx::query(query)
+            .bind(space)
+            .bind(name)
+            .bind(registry_type.to_string())
+            .bind(alias)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
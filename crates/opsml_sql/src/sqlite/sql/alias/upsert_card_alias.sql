@@ -0,0 +1,2 @@
+INSERT INTO opsml_card_alias (space, name, registry_type, alias, uid, version, updated_by) VALUES (?, ?, ?, ?, ?, ?, ?)
+ON CONFLICT (space, name, registry_type, alias) DO UPDATE SET uid = excluded.uid, version = excluded.version, updated_by = excluded.updated_by, updated_at = CURRENT_TIMESTAMP;
\ No newline at end of file
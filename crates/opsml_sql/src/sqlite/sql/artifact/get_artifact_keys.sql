@@ -0,0 +1,5 @@
+SELECT uid, space, registry_type, encrypted_key, storage_key, key_version, manifest_sha256
+FROM opsml_artifact_key
+WHERE uid > ?
+ORDER BY uid
+LIMIT ?;
\ No newline at end of file
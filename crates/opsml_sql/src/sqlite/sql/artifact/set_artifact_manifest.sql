@@ -0,0 +1 @@
+UPDATE opsml_artifact_key SET manifest_sha256 = ? WHERE uid = ? AND registry_type = ?;
\ No newline at end of file
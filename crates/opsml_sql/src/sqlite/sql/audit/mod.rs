@@ -1,8 +1,9 @@
 use crate::{sqlite::helper::SqliteQueryHelper, traits::AuditLogicTrait};
 
 use crate::error::SqlError;
+use crate::schemas::AuditEventSqlRecord;
 use async_trait::async_trait;
-use opsml_types::contracts::AuditEvent;
+use opsml_types::contracts::{AuditEvent, AuditEventRecord, AuditQueryArgs};
 use sqlx::{Pool, Sqlite};
 
 #[derive(Debug, Clone)]
@@ -37,4 +38,27 @@ impl AuditLogicTrait for AuditLogicSqliteClient {
 
         Ok(())
     }
+
+    async fn query_audit_events(
+        &self,
+        query_args: &AuditQueryArgs,
+    ) -> Result<Vec<AuditEventRecord>, SqlError> {
+        let query = SqliteQueryHelper::get_audit_events_query();
+        let registry_type = query_args.registry_type.as_ref().map(|r| r.to_string());
+        let rows: Vec<AuditEventSqlRecord> = sqlx::query_as(query)
+            .bind(query_args.username.as_ref())
+            .bind(query_args.operation.as_ref())
+            .bind(query_args.resource_type.as_ref())
+            .bind(query_args.resource_id.as_ref())
+            .bind(registry_type)
+            .bind(query_args.status.as_ref())
+            .bind(query_args.start_time)
+            .bind(query_args.end_time)
+            .bind(query_args.cursor)
+            .bind(query_args.limit.unwrap_or(100))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.into_iter().map(AuditEventRecord::from).collect())
+    }
 }
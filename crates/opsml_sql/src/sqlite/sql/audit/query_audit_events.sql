@@ -0,0 +1,28 @@
+SELECT
+    id,
+    created_at,
+    username,
+    client_ip,
+    user_agent,
+    operation,
+    resource_type,
+    resource_id,
+    access_location,
+    status,
+    error_message,
+    metadata,
+    registry_type,
+    route
+FROM opsml_audit_event
+WHERE 1=1
+    AND (?1 IS NULL OR username = ?1)
+    AND (?2 IS NULL OR operation = ?2)
+    AND (?3 IS NULL OR resource_type = ?3)
+    AND (?4 IS NULL OR resource_id = ?4)
+    AND (?5 IS NULL OR registry_type = ?5)
+    AND (?6 IS NULL OR status = ?6)
+    AND (?7 IS NULL OR created_at >= DATETIME(?7))
+    AND (?8 IS NULL OR created_at <= DATETIME(?8))
+    AND (?9 IS NULL OR id < ?9)
+ORDER BY id DESC
+LIMIT ?10;
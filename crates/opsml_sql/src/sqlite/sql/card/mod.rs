@@ -931,14 +931,15 @@ impl CardLogicTrait for CardLogicSqliteClient {
     ) -> Result<ArtifactKey, SqlError> {
         let query = SqliteQueryHelper::get_load_card_query(table, query_args)?;
 
-        let key: (String, String, String, Vec<u8>, String) = sqlx::query_as(&query)
-            .bind(query_args.uid.as_ref())
-            .bind(query_args.space.as_ref())
-            .bind(query_args.name.as_ref())
-            .bind(query_args.max_date.as_ref())
-            .bind(query_args.limit.unwrap_or(1))
-            .fetch_one(&self.pool)
-            .await?;
+        let key: (String, String, String, Vec<u8>, String, i32, Option<String>) =
+            sqlx::query_as(&query)
+                .bind(query_args.uid.as_ref())
+                .bind(query_args.space.as_ref())
+                .bind(query_args.name.as_ref())
+                .bind(query_args.max_date.as_ref())
+                .bind(query_args.limit.unwrap_or(1))
+                .fetch_one(&self.pool)
+                .await?;
 
         Ok(ArtifactKey {
             uid: key.0,
@@ -946,6 +947,8 @@ impl CardLogicTrait for CardLogicSqliteClient {
             registry_type: RegistryType::from_string(&key.2)?,
             encrypted_key: key.3,
             storage_key: key.4,
+            key_version: key.5 as u32,
+            manifest_sha256: key.6,
         })
     }
 
@@ -0,0 +1 @@
+UPDATE opsml_webhook_delivery SET claim_id = ?, next_attempt_at = ? WHERE id IN (SELECT id FROM opsml_webhook_delivery WHERE status = 'pending' AND DATETIME(next_attempt_at) <= DATETIME(?) ORDER BY next_attempt_at LIMIT ?);
\ No newline at end of file
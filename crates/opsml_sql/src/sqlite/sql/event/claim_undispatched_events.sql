@@ -0,0 +1 @@
+UPDATE opsml_event_outbox SET claim_id = ?, claimed_until = ? WHERE id IN (SELECT id FROM opsml_event_outbox WHERE dispatched = FALSE AND (claimed_until IS NULL OR DATETIME(claimed_until) <= DATETIME(?)) ORDER BY id LIMIT ?);
\ No newline at end of file
@@ -0,0 +1 @@
+DELETE FROM opsml_webhook WHERE id = ?;
\ No newline at end of file
@@ -0,0 +1,2 @@
+INSERT INTO opsml_webhook_delivery (webhook_id, event_id, next_attempt_at) VALUES (?, ?, ?)
+ON CONFLICT (webhook_id, event_id) DO NOTHING;
\ No newline at end of file
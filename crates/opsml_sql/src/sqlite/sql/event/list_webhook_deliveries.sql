@@ -0,0 +1 @@
+SELECT id, webhook_id, event_id, status, attempts, next_attempt_at, last_error, updated_at FROM opsml_webhook_delivery WHERE webhook_id = ? ORDER BY id DESC LIMIT ?;
\ No newline at end of file
@@ -0,0 +1 @@
+DELETE FROM opsml_group WHERE name = ?;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT name, description, sso_groups, created_at FROM opsml_group WHERE name = ?;
\ No newline at end of file
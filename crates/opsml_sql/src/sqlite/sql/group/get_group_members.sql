@@ -0,0 +1 @@
+SELECT username FROM opsml_group_member WHERE group_name = ? ORDER BY username;
\ No newline at end of file
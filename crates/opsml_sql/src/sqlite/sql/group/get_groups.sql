@@ -0,0 +1 @@
+SELECT name, description, sso_groups, created_at FROM opsml_group ORDER BY name;
\ No newline at end of file
@@ -0,0 +1 @@
+SELECT group_name FROM opsml_group_member WHERE username = ? ORDER BY group_name;
\ No newline at end of file
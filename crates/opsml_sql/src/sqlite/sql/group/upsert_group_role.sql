@@ -0,0 +1,2 @@
+INSERT INTO opsml_group_role (group_name, space, role) VALUES (?, ?, ?)
+ON CONFLICT (group_name, space) DO UPDATE SET role = excluded.role;
\ No newline at end of file
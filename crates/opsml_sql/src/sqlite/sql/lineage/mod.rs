@@ -0,0 +1,45 @@
+use crate::{sqlite::helper::SqliteQueryHelper, traits::LineageLogicTrait};
+
+use crate::error::SqlError;
+use crate::schemas::schema::{LineageEdgeSqlRecord, LineageNodeSqlRecord};
+use async_trait::async_trait;
+use opsml_types::cards::CardTable;
+use opsml_types::contracts::LineageEdge;
+use sqlx::{Pool, Sqlite};
+
+#[derive(Debug, Clone)]
+pub struct LineageLogicSqliteClient {
+    pool: sqlx::Pool<Sqlite>,
+}
+impl LineageLogicSqliteClient {
+    pub fn new(pool: &Pool<Sqlite>) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
+
+#[async_trait]
+impl LineageLogicTrait for LineageLogicSqliteClient {
+    async fn get_lineage_edges(&self, uid: &str) -> Result<Vec<LineageEdge>, SqlError> {
+        let query = SqliteQueryHelper::get_lineage_edges_query();
+        let records: Vec<LineageEdgeSqlRecord> = sqlx::query_as(query)
+            .bind(uid)
+            .fetch_all(&self.pool)
+            .await?;
+
+        records.into_iter().map(LineageEdge::try_from).collect()
+    }
+
+    async fn get_lineage_node(
+        &self,
+        table: &CardTable,
+        uid: &str,
+    ) -> Result<Option<LineageNodeSqlRecord>, SqlError> {
+        let query = SqliteQueryHelper::get_lineage_node_query(table);
+        let record: Option<LineageNodeSqlRecord> = sqlx::query_as(&query)
+            .bind(uid)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(record)
+    }
+}
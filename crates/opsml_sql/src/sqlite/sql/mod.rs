@@ -1,7 +1,14 @@
+pub mod agent_job;
+pub mod alias;
 pub mod artifact;
 pub mod audit;
 pub mod card;
 pub mod evaluation;
+pub mod event;
 pub mod experiment;
+pub mod group;
+pub mod lineage;
+pub mod search;
 pub mod space;
+pub mod trash;
 pub mod user;
@@ -0,0 +1 @@
+SELECT readme FROM opsml_card_search WHERE registry_type = ? AND space = ? AND name = ? AND readme IS NOT NULL LIMIT 1;
@@ -0,0 +1,17 @@
+SELECT
+    s.uid,
+    s.registry_type,
+    s.space,
+    s.name,
+    s.version,
+    -bm25(opsml_card_search_fts, 10.0, 2.0, 5.0, 4.0, 1.0, 1.0) AS score,
+    snippet(opsml_card_search_fts, -1, '<mark>', '</mark>', '...', 24) AS highlight
+FROM opsml_card_search_fts
+JOIN opsml_card_search s ON s.id = opsml_card_search_fts.rowid
+WHERE opsml_card_search_fts MATCH ?1
+    AND (?2 IS NULL OR s.registry_type = ?2)
+    AND (?3 IS NULL OR s.space = ?3)
+    AND (?6 IS NULL OR s.space IN (SELECT value FROM json_each(?6)))
+    AND NOT EXISTS (SELECT 1 FROM opsml_card_trash t WHERE t.uid = s.uid)
+ORDER BY score DESC
+LIMIT ?4 OFFSET ?5;
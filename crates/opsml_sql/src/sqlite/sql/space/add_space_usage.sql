@@ -0,0 +1,7 @@
+INSERT INTO opsml_space_usage
+(space, registry_type, bytes)
+VALUES (?, ?, ?)
+ON CONFLICT(space, registry_type)
+DO UPDATE SET
+    bytes = opsml_space_usage.bytes + ?,
+    updated_at = CURRENT_TIMESTAMP;
\ No newline at end of file
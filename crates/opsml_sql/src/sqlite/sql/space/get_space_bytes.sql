@@ -0,0 +1,3 @@
+SELECT COALESCE(SUM(bytes), 0)
+FROM opsml_space_usage
+WHERE space = ?;
\ No newline at end of file
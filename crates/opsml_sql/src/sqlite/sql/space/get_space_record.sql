@@ -1,5 +1,7 @@
 SELECT 
     space,
-    description
+    description,
+    soft_quota_bytes,
+    hard_quota_bytes
 FROM opsml_space
 WHERE space = ?;
\ No newline at end of file
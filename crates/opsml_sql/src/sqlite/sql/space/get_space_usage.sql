@@ -0,0 +1,6 @@
+SELECT
+    space,
+    registry_type,
+    bytes
+FROM opsml_space_usage
+ORDER BY space, registry_type;
\ No newline at end of file
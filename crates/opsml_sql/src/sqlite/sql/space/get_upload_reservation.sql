@@ -0,0 +1 @@
+SELECT bytes FROM opsml_upload_reservation WHERE path = ?;
\ No newline at end of file
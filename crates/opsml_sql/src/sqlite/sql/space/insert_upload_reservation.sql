@@ -0,0 +1,6 @@
+INSERT INTO opsml_upload_reservation
+(path, space, registry_type, bytes)
+VALUES (?, ?, ?, ?)
+ON CONFLICT(path)
+DO UPDATE SET
+    bytes = opsml_upload_reservation.bytes + excluded.bytes;
\ No newline at end of file
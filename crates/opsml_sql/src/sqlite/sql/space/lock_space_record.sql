@@ -0,0 +1 @@
+UPDATE opsml_space SET updated_at = updated_at WHERE space = ?;
\ No newline at end of file
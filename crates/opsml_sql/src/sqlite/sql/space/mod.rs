@@ -4,9 +4,10 @@ use crate::error::SqlError;
 
 use crate::schemas::SqlSpaceRecord;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use opsml_types::{
     RegistryType,
-    contracts::{SpaceNameEvent, SpaceRecord, SpaceStats},
+    contracts::{SpaceNameEvent, SpaceRecord, SpaceRegistryUsage, SpaceStats},
 };
 use sqlx::{Pool, Sqlite};
 
@@ -56,13 +57,16 @@ impl SpaceLogicTrait for SpaceLogicSqliteClient {
                 data_count: s.2,
                 prompt_count: s.3,
                 experiment_count: s.4,
+                soft_quota_bytes: s.5,
+                hard_quota_bytes: s.6,
+                ..Default::default()
             })
             .collect())
     }
 
     async fn get_space_record(&self, space: &str) -> Result<Option<SpaceRecord>, SqlError> {
         let query = SqliteQueryHelper::get_space_record_query();
-        let record: Option<(String, String)> = sqlx::query_as(query)
+        let record: Option<(String, String, Option<i64>, Option<i64>)> = sqlx::query_as(query)
             .bind(space)
             .fetch_optional(&self.pool)
             .await?;
@@ -70,6 +74,8 @@ impl SpaceLogicTrait for SpaceLogicSqliteClient {
         Ok(record.map(|r| SpaceRecord {
             space: r.0,
             description: r.1,
+            soft_quota_bytes: r.2,
+            hard_quota_bytes: r.3,
         }))
     }
 
@@ -107,4 +113,184 @@ impl SpaceLogicTrait for SpaceLogicSqliteClient {
 
         Ok(())
     }
+
+    async fn set_space_quota(
+        &self,
+        space: &str,
+        soft_quota_bytes: Option<i64>,
+        hard_quota_bytes: Option<i64>,
+    ) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_set_space_quota_query();
+        sqlx::query(query)
+            .bind(space)
+            .bind(soft_quota_bytes)
+            .bind(hard_quota_bytes)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn add_space_usage(
+        &self,
+        space: &str,
+        registry_type: &RegistryType,
+        bytes: i64,
+    ) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_add_space_usage_query();
+        sqlx::query(query)
+            .bind(space)
+            .bind(registry_type.to_string())
+            .bind(bytes)
+            .bind(bytes)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_space_usage(&self) -> Result<Vec<SpaceRegistryUsage>, SqlError> {
+        let query = SqliteQueryHelper::get_space_usage_query();
+        let rows: Vec<(String, String, i64)> = sqlx::query_as(query).fetch_all(&self.pool).await?;
+
+        rows.into_iter()
+            .map(|(space, registry_type, bytes)| {
+                Ok(SpaceRegistryUsage {
+                    space,
+                    registry_type: RegistryType::from_string(&registry_type)?,
+                    bytes,
+                })
+            })
+            .collect()
+    }
+
+    async fn get_space_bytes(&self, space: &str) -> Result<i64, SqlError> {
+        let query = SqliteQueryHelper::get_space_bytes_query();
+        let bytes: i64 = sqlx::query_scalar(query)
+            .bind(space)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(bytes)
+    }
+
+    async fn reserve_space_usage(
+        &self,
+        space: &str,
+        registry_type: &RegistryType,
+        bytes: i64,
+        hard_quota_bytes: i64,
+    ) -> Result<bool, SqlError> {
+        let mut tx = self.pool.begin().await?;
+
+        // lock the space so concurrent reservations see each other's usage
+        sqlx::query(SqliteQueryHelper::get_lock_space_record_query())
+            .bind(space)
+            .execute(&mut *tx)
+            .await?;
+
+        let used: i64 = sqlx::query_scalar(SqliteQueryHelper::get_space_bytes_query())
+            .bind(space)
+            .fetch_one(&mut *tx)
+            .await?;
+        if used.saturating_add(bytes) > hard_quota_bytes {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        sqlx::query(SqliteQueryHelper::get_add_space_usage_query())
+            .bind(space)
+            .bind(registry_type.to_string())
+            .bind(bytes)
+            .bind(bytes)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    async fn set_space_usage(
+        &self,
+        space: &str,
+        registry_type: &RegistryType,
+        bytes: i64,
+    ) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_set_space_usage_query();
+        sqlx::query(query)
+            .bind(space)
+            .bind(registry_type.to_string())
+            .bind(bytes)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn insert_upload_reservation(
+        &self,
+        path: &str,
+        space: &str,
+        registry_type: &RegistryType,
+        bytes: i64,
+    ) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_insert_upload_reservation_query();
+        sqlx::query(query)
+            .bind(path)
+            .bind(space)
+            .bind(registry_type.to_string())
+            .bind(bytes)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn take_upload_reservation(&self, path: &str) -> Result<Option<i64>, SqlError> {
+        let mut tx = self.pool.begin().await?;
+
+        let bytes: Option<i64> =
+            sqlx::query_scalar(SqliteQueryHelper::get_upload_reservation_query())
+                .bind(path)
+                .fetch_optional(&mut *tx)
+                .await?;
+
+        if bytes.is_some() {
+            sqlx::query(SqliteQueryHelper::get_delete_upload_reservation_query())
+                .bind(path)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(bytes)
+    }
+
+    async fn get_upload_reservations(&self) -> Result<Vec<SpaceRegistryUsage>, SqlError> {
+        let query = SqliteQueryHelper::get_upload_reservations_query();
+        let rows: Vec<(String, String, i64)> = sqlx::query_as(query).fetch_all(&self.pool).await?;
+
+        rows.into_iter()
+            .map(|(space, registry_type, bytes)| {
+                Ok(SpaceRegistryUsage {
+                    space,
+                    registry_type: RegistryType::from_string(&registry_type)?,
+                    bytes,
+                })
+            })
+            .collect()
+    }
+
+    async fn delete_stale_upload_reservations(
+        &self,
+        created_before: &DateTime<Utc>,
+    ) -> Result<u64, SqlError> {
+        let query = SqliteQueryHelper::get_delete_stale_upload_reservations_query();
+        let result = sqlx::query(query)
+            .bind(created_before)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
 }
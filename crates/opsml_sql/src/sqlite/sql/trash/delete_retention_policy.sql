@@ -0,0 +1 @@
+DELETE FROM opsml_retention_policy WHERE space = ?;
\ No newline at end of file
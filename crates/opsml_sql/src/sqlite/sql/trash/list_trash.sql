@@ -0,0 +1,6 @@
+SELECT uid, registry_type, space, name, version, record, reason, deleted_by, deleted_at
+FROM opsml_card_trash
+WHERE (?1 IS NULL OR space = ?1)
+    AND (?2 IS NULL OR registry_type = ?2)
+    AND (?3 IS NULL OR deleted_at <= DATETIME(?3))
+ORDER BY deleted_at DESC;
\ No newline at end of file
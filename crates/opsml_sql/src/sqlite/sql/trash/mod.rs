@@ -0,0 +1,108 @@
+use crate::{sqlite::helper::SqliteQueryHelper, traits::TrashLogicTrait};
+
+use crate::error::SqlError;
+use crate::schemas::schema::{RetentionPolicySqlRecord, TrashSqlRecord};
+use async_trait::async_trait;
+use opsml_types::contracts::{RetentionPolicy, TrashQuery};
+use sqlx::{Pool, Sqlite};
+
+#[derive(Debug, Clone)]
+pub struct TrashLogicSqliteClient {
+    pool: sqlx::Pool<Sqlite>,
+}
+impl TrashLogicSqliteClient {
+    pub fn new(pool: &Pool<Sqlite>) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
+
+#[async_trait]
+impl TrashLogicTrait for TrashLogicSqliteClient {
+    async fn insert_trash_record(&self, record: &TrashSqlRecord) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_trash_record_insert_query();
+
+        sqlx::query(query)
+            .bind(&record.uid)
+            .bind(&record.registry_type)
+            .bind(&record.space)
+            .bind(&record.name)
+            .bind(&record.version)
+            .bind(&record.record)
+            .bind(&record.reason)
+            .bind(&record.deleted_by)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_trash_record(&self, uid: &str) -> Result<Option<TrashSqlRecord>, SqlError> {
+        let query = SqliteQueryHelper::get_trash_record_query();
+        let record: Option<TrashSqlRecord> = sqlx::query_as(query)
+            .bind(uid)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(record)
+    }
+
+    async fn list_trash(&self, query: &TrashQuery) -> Result<Vec<TrashSqlRecord>, SqlError> {
+        let registry_type = query.registry_type.as_ref().map(|r| r.to_string());
+        let records: Vec<TrashSqlRecord> =
+            sqlx::query_as(SqliteQueryHelper::get_list_trash_query())
+                .bind(&query.space)
+                .bind(registry_type)
+                .bind(query.deleted_before)
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(records)
+    }
+
+    async fn delete_trash_record(&self, uid: &str) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_trash_record_delete_query();
+        sqlx::query(query).bind(uid).execute(&self.pool).await?;
+
+        Ok(())
+    }
+
+    async fn upsert_retention_policy(&self, policy: &RetentionPolicy) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_retention_policy_upsert_query();
+
+        sqlx::query(query)
+            .bind(&policy.space)
+            .bind(policy.keep_last_versions.map(|n| n as i32))
+            .bind(policy.purge_trashed_after_days.map(|n| n as i32))
+            .bind(policy.protect_aliased)
+            .bind(&policy.updated_by)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_retention_policy(&self, space: &str) -> Result<Option<RetentionPolicy>, SqlError> {
+        let query = SqliteQueryHelper::get_retention_policy_query();
+        let record: Option<RetentionPolicySqlRecord> = sqlx::query_as(query)
+            .bind(space)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(record.map(RetentionPolicy::from))
+    }
+
+    async fn get_retention_policies(&self) -> Result<Vec<RetentionPolicy>, SqlError> {
+        let query = SqliteQueryHelper::get_retention_policies_query();
+        let records: Vec<RetentionPolicySqlRecord> =
+            sqlx::query_as(query).fetch_all(&self.pool).await?;
+
+        Ok(records.into_iter().map(RetentionPolicy::from).collect())
+    }
+
+    async fn delete_retention_policy(&self, space: &str) -> Result<bool, SqlError> {
+        let query = SqliteQueryHelper::get_retention_policy_delete_query();
+        let result = sqlx::query(query).bind(space).execute(&self.pool).await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1 @@
+SELECT * FROM opsml_user_api_token WHERE token_id = ?;
\ No newline at end of file
@@ -1,10 +1,11 @@
 use crate::sqlite::helper::SqliteQueryHelper;
 
 use crate::error::SqlError;
-use crate::schemas::schema::User;
+use crate::schemas::schema::{ApiToken, User};
 
 use crate::traits::UserLogicTrait;
 use async_trait::async_trait;
+use opsml_utils::utils::get_utc_datetime;
 use sqlx::{FromRow, Pool, Row, Sqlite, sqlite::SqliteRow};
 
 impl FromRow<'_, SqliteRow> for User {
@@ -55,6 +56,25 @@ impl FromRow<'_, SqliteRow> for User {
     }
 }
 
+impl FromRow<'_, SqliteRow> for ApiToken {
+    fn from_row(row: &SqliteRow) -> Result<Self, sqlx::Error> {
+        let permissions: Vec<String> =
+            serde_json::from_value(row.try_get("permissions")?).unwrap_or_default();
+
+        Ok(ApiToken {
+            token_id: row.try_get("token_id")?,
+            username: row.try_get("username")?,
+            name: row.try_get("name")?,
+            token_hash: row.try_get("token_hash")?,
+            permissions,
+            created_at: row.try_get("created_at")?,
+            expires_at: row.try_get("expires_at")?,
+            last_used_at: row.try_get("last_used_at")?,
+            revoked: row.try_get("revoked")?,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct UserLogicSqliteClient {
     pool: sqlx::Pool<Sqlite>,
@@ -179,4 +199,69 @@ impl UserLogicTrait for UserLogicSqliteClient {
 
         Ok(())
     }
+
+    async fn insert_api_token(&self, token: &ApiToken) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_api_token_insert_query();
+
+        let permissions = serde_json::to_string(&token.permissions)?;
+
+        sqlx::query(query)
+            .bind(&token.token_id)
+            .bind(&token.username)
+            .bind(&token.name)
+            .bind(&token.token_hash)
+            .bind(&permissions)
+            .bind(token.created_at)
+            .bind(token.expires_at)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_api_token(&self, token_id: &str) -> Result<Option<ApiToken>, SqlError> {
+        let query = SqliteQueryHelper::get_api_token_query();
+
+        let token: Option<ApiToken> = sqlx::query_as(query)
+            .bind(token_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(token)
+    }
+
+    async fn get_api_tokens(&self, username: &str) -> Result<Vec<ApiToken>, SqlError> {
+        let query = SqliteQueryHelper::get_api_tokens_query();
+
+        let tokens = sqlx::query_as::<_, ApiToken>(query)
+            .bind(username)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(tokens)
+    }
+
+    async fn revoke_api_token(&self, username: &str, token_id: &str) -> Result<bool, SqlError> {
+        let query = SqliteQueryHelper::get_api_token_revoke_query();
+
+        let result = sqlx::query(query)
+            .bind(username)
+            .bind(token_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn update_api_token_last_used(&self, token_id: &str) -> Result<(), SqlError> {
+        let query = SqliteQueryHelper::get_api_token_last_used_update_query();
+
+        sqlx::query(query)
+            .bind(get_utc_datetime())
+            .bind(token_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
 }
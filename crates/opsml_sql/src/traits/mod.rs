@@ -1,19 +1,24 @@
 use crate::error::SqlError;
 use crate::schemas::schema::{
-    ArtifactSqlRecord, CardResults, CardSummary, HardwareMetricsRecord, MetricRecord,
-    ParameterRecord, QueryStats, ServerCard, SkillCardRecord, SubAgentCardRecord, ToolCardRecord,
-    User, VersionSummary,
+    ApiToken, ArtifactSqlRecord, CardResults, CardSummary, HardwareMetricsRecord,
+    LineageNodeSqlRecord, MetricRecord, ParameterRecord, QueryStats, ServerCard, SkillCardRecord,
+    SubAgentCardRecord, ToolCardRecord, TrashSqlRecord, UnkeyedCard, User, VersionSummary,
+    WebhookSqlRecord,
 };
 use crate::schemas::{EvaluationSqlRecord, ServiceCardRecord};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use opsml_types::cards::CardTable;
 use opsml_types::contracts::CardQueryArgs;
 use opsml_types::contracts::skill::MarketplaceStats;
 use opsml_types::{
     RegistryType,
     contracts::{
-        ArtifactKey, ArtifactQueryArgs, ArtifactRecord, AuditEvent, CardArgs, DashboardStats,
-        ServiceQueryArgs, SpaceNameEvent, SpaceRecord, SpaceStats, VersionCursor,
+        AgentJobRecord, ArtifactKey, ArtifactQueryArgs, ArtifactRecord, AuditEvent,
+        AuditEventRecord, AuditQueryArgs, CardAlias, CardArgs, CardSearchDocument, CardSearchHit,
+        CardSearchQuery, DashboardStats, GroupRecord, GroupRole, LineageEdge, RegistryEvent,
+        RetentionPolicy, ServiceQueryArgs, SpaceNameEvent, SpaceRecord, SpaceRegistryUsage,
+        SpaceStats, TrashQuery, VersionCursor, WebhookDelivery,
     },
 };
 
@@ -123,6 +128,12 @@ pub trait UserLogicTrait {
     async fn is_last_admin(&self, username: &str) -> Result<bool, SqlError>;
     async fn delete_user(&self, username: &str) -> Result<(), SqlError>;
     async fn update_user(&self, user: &User) -> Result<(), SqlError>;
+    async fn insert_api_token(&self, token: &ApiToken) -> Result<(), SqlError>;
+    async fn get_api_token(&self, token_id: &str) -> Result<Option<ApiToken>, SqlError>;
+    async fn get_api_tokens(&self, username: &str) -> Result<Vec<ApiToken>, SqlError>;
+    /// Marks the token as revoked. Returns false if no token matched `username` and `token_id`
+    async fn revoke_api_token(&self, username: &str, token_id: &str) -> Result<bool, SqlError>;
+    async fn update_api_token_last_used(&self, token_id: &str) -> Result<(), SqlError>;
 }
 
 #[async_trait]
@@ -141,12 +152,216 @@ pub trait ArtifactLogicTrait {
         registry_type: &str,
     ) -> Result<Option<ArtifactKey>, SqlError>;
     async fn delete_artifact_key(&self, uid: &str, registry_type: &str) -> Result<(), SqlError>;
+    /// All keys ordered by uid, starting after `after_uid`
+    async fn get_artifact_keys(
+        &self,
+        after_uid: &str,
+        limit: i64,
+    ) -> Result<Vec<ArtifactKey>, SqlError>;
+    /// Cards in `table` without an artifact key, ordered by uid and starting after `after_uid`
+    async fn get_cards_without_key(
+        &self,
+        table: &CardTable,
+        after_uid: &str,
+        limit: i64,
+    ) -> Result<Vec<UnkeyedCard>, SqlError>;
+    /// Keys not wrapped with `key_version`, ordered by uid and starting after `after_uid`
+    async fn get_artifact_keys_to_rewrap(
+        &self,
+        key_version: u32,
+        after_uid: &str,
+        limit: i64,
+    ) -> Result<Vec<ArtifactKey>, SqlError>;
+    /// Replace a key's wrapping if it is still wrapped with `previous_version`.
+    /// Returns false if the key changed or was deleted in the meantime
+    async fn rewrap_artifact_key(
+        &self,
+        key: &ArtifactKey,
+        previous_version: u32,
+    ) -> Result<bool, SqlError>;
+    /// Record the digest of the manifest uploaded with a card's artifacts
+    async fn set_artifact_manifest(
+        &self,
+        uid: &str,
+        registry_type: &str,
+        manifest_sha256: &str,
+    ) -> Result<(), SqlError>;
     async fn query_artifacts(
         &self,
         query_args: &ArtifactQueryArgs,
     ) -> Result<Vec<ArtifactRecord>, SqlError>;
 }
 
+#[async_trait]
+pub trait GroupLogicTrait {
+    async fn insert_group(&self, group: &GroupRecord) -> Result<(), SqlError>;
+    async fn get_group(&self, name: &str) -> Result<Option<GroupRecord>, SqlError>;
+    async fn get_groups(&self) -> Result<Vec<GroupRecord>, SqlError>;
+    async fn delete_group(&self, name: &str) -> Result<(), SqlError>;
+    /// Grants `role` on a space to a group, replacing any existing role for that space
+    async fn upsert_group_role(&self, role: &GroupRole) -> Result<(), SqlError>;
+    async fn delete_group_role(&self, group_name: &str, space: &str) -> Result<(), SqlError>;
+    async fn get_group_roles(&self, group_name: &str) -> Result<Vec<GroupRole>, SqlError>;
+    /// Adds a user to a group. Adding an existing member is a no-op
+    async fn add_group_member(&self, group_name: &str, username: &str) -> Result<(), SqlError>;
+    async fn remove_group_member(&self, group_name: &str, username: &str) -> Result<(), SqlError>;
+    async fn get_group_members(&self, group_name: &str) -> Result<Vec<String>, SqlError>;
+    async fn get_user_groups(&self, username: &str) -> Result<Vec<String>, SqlError>;
+    /// All space roles held by the groups a user belongs to
+    async fn get_user_group_roles(&self, username: &str) -> Result<Vec<GroupRole>, SqlError>;
+}
+
+#[async_trait]
+pub trait AliasLogicTrait {
+    /// Points an alias at a card version, moving it if the alias already exists
+    async fn upsert_card_alias(&self, alias: &CardAlias) -> Result<(), SqlError>;
+    async fn get_card_alias(
+        &self,
+        space: &str,
+        name: &str,
+        registry_type: &RegistryType,
+        alias: &str,
+    ) -> Result<Option<CardAlias>, SqlError>;
+    async fn get_card_aliases(
+        &self,
+        space: &str,
+        name: &str,
+        registry_type: &RegistryType,
+    ) -> Result<Vec<CardAlias>, SqlError>;
+    /// Returns false if the alias did not exist
+    async fn delete_card_alias(
+        &self,
+        space: &str,
+        name: &str,
+        registry_type: &RegistryType,
+        alias: &str,
+    ) -> Result<bool, SqlError>;
+}
+
+#[async_trait]
+pub trait SearchLogicTrait {
+    /// Indexes a card version, replacing any existing document for its uid. A document without a
+    /// readme keeps the readme indexed for other versions of the same card
+    async fn upsert_search_document(&self, document: &CardSearchDocument) -> Result<(), SqlError>;
+    /// The readme indexed for a card, if any version of it has one
+    async fn get_search_readme(
+        &self,
+        registry_type: &RegistryType,
+        space: &str,
+        name: &str,
+    ) -> Result<Option<String>, SqlError>;
+    /// Sets the indexed readme of every version of a card
+    async fn update_search_readme(
+        &self,
+        registry_type: &RegistryType,
+        space: &str,
+        name: &str,
+        readme: &str,
+    ) -> Result<(), SqlError>;
+    async fn delete_search_document(&self, uid: &str) -> Result<(), SqlError>;
+    /// Ranked full-text search, best match first. Returns no hits for a query without terms.
+    /// `spaces` restricts the hits to those spaces; `None` searches every space
+    async fn search_cards(
+        &self,
+        query: &CardSearchQuery,
+        spaces: Option<&[String]>,
+    ) -> Result<Vec<CardSearchHit>, SqlError>;
+}
+
+#[async_trait]
+pub trait TrashLogicTrait {
+    async fn insert_trash_record(&self, record: &TrashSqlRecord) -> Result<(), SqlError>;
+    async fn get_trash_record(&self, uid: &str) -> Result<Option<TrashSqlRecord>, SqlError>;
+    /// Trashed cards matching the query, most recently deleted first
+    async fn list_trash(&self, query: &TrashQuery) -> Result<Vec<TrashSqlRecord>, SqlError>;
+    async fn delete_trash_record(&self, uid: &str) -> Result<(), SqlError>;
+    async fn upsert_retention_policy(&self, policy: &RetentionPolicy) -> Result<(), SqlError>;
+    async fn get_retention_policy(&self, space: &str) -> Result<Option<RetentionPolicy>, SqlError>;
+    async fn get_retention_policies(&self) -> Result<Vec<RetentionPolicy>, SqlError>;
+    /// Returns true if a policy was removed
+    async fn delete_retention_policy(&self, space: &str) -> Result<bool, SqlError>;
+}
+
+#[async_trait]
+pub trait AgentJobLogicTrait {
+    /// Inserts a job or updates the status, result, error and duration of an existing one
+    async fn upsert_agent_job(&self, job: &AgentJobRecord) -> Result<(), SqlError>;
+    async fn get_agent_job(&self, job_id: &str) -> Result<Option<AgentJobRecord>, SqlError>;
+    /// Marks pending and running jobs as failed when they are owned by `instance_id`, e.g. after
+    /// a restart interrupted them, or when their last heartbeat is older than `stale_before`
+    /// whatever instance ran them. Returns the number of jobs updated
+    async fn fail_incomplete_agent_jobs(
+        &self,
+        error: &str,
+        instance_id: Option<&str>,
+        stale_before: &DateTime<Utc>,
+    ) -> Result<u64, SqlError>;
+    /// Refreshes `updated_at` on the pending and running jobs of `instance_id` so other
+    /// instances do not consider them stale. Returns the number of jobs touched
+    async fn heartbeat_agent_jobs(&self, instance_id: &str) -> Result<u64, SqlError>;
+    /// Deletes finished jobs last updated before `cutoff`. Returns the number of jobs removed
+    async fn delete_expired_agent_jobs(&self, cutoff: &DateTime<Utc>) -> Result<u64, SqlError>;
+}
+
+#[async_trait]
+pub trait EventLogicTrait {
+    /// Appends a registry event to the outbox
+    async fn insert_registry_event(&self, event: &RegistryEvent) -> Result<(), SqlError>;
+    async fn get_registry_event(&self, event_id: &str) -> Result<Option<RegistryEvent>, SqlError>;
+    /// Claims up to `limit` of the oldest undispatched events until `lease_until` and returns
+    /// them oldest first. Events claimed by another instance are skipped until their lease expires
+    async fn claim_undispatched_events(
+        &self,
+        claim_id: &str,
+        now: &DateTime<Utc>,
+        lease_until: &DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<RegistryEvent>, SqlError>;
+    /// Releases the events of a claim that were not dispatched so they can be claimed again
+    async fn release_event_claim(&self, claim_id: &str) -> Result<(), SqlError>;
+    async fn mark_event_dispatched(&self, event_id: &str) -> Result<(), SqlError>;
+    async fn insert_webhook(&self, webhook: &WebhookSqlRecord) -> Result<(), SqlError>;
+    async fn get_webhook(&self, id: &str) -> Result<Option<WebhookSqlRecord>, SqlError>;
+    async fn list_webhooks(&self, space: &str) -> Result<Vec<WebhookSqlRecord>, SqlError>;
+    /// Deletes a webhook along with its deliveries. Returns false if it did not exist
+    async fn delete_webhook(&self, id: &str) -> Result<bool, SqlError>;
+    /// Queues an event for a webhook. Queuing the same event twice is a no-op
+    async fn insert_webhook_delivery(
+        &self,
+        webhook_id: &str,
+        event_id: &str,
+        next_attempt_at: &DateTime<Utc>,
+    ) -> Result<(), SqlError>;
+    /// Claims up to `limit` pending deliveries due at `now` by moving their next attempt to
+    /// `lease_until`, so other instances skip them until the lease expires, and returns them
+    async fn claim_due_webhook_deliveries(
+        &self,
+        claim_id: &str,
+        now: &DateTime<Utc>,
+        lease_until: &DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>, SqlError>;
+    /// Persists the status, attempt count, retry time and last error of a delivery
+    async fn update_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<(), SqlError>;
+    /// Returns the most recent deliveries for a webhook, newest first
+    async fn list_webhook_deliveries(
+        &self,
+        webhook_id: &str,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>, SqlError>;
+}
+
+#[async_trait]
+pub trait LineageLogicTrait {
+    /// Returns every lineage edge where `uid` is either the upstream or the downstream card
+    async fn get_lineage_edges(&self, uid: &str) -> Result<Vec<LineageEdge>, SqlError>;
+    async fn get_lineage_node(
+        &self,
+        table: &CardTable,
+        uid: &str,
+    ) -> Result<Option<LineageNodeSqlRecord>, SqlError>;
+}
+
 #[async_trait]
 pub trait SpaceLogicTrait {
     async fn insert_space_record(&self, space: &SpaceRecord) -> Result<(), SqlError>;
@@ -161,11 +376,80 @@ pub trait SpaceLogicTrait {
         name: &str,
         registry_type: &RegistryType,
     ) -> Result<(), SqlError>;
+
+    /// Create the space record if needed and set its quotas. `None` removes a limit
+    async fn set_space_quota(
+        &self,
+        space: &str,
+        soft_quota_bytes: Option<i64>,
+        hard_quota_bytes: Option<i64>,
+    ) -> Result<(), SqlError>;
+
+    /// Add `bytes` (negative to release) to a space's usage for a registry. Usage is not
+    /// clamped, so releasing more than was recorded shows up as drift until usage is recounted
+    async fn add_space_usage(
+        &self,
+        space: &str,
+        registry_type: &RegistryType,
+        bytes: i64,
+    ) -> Result<(), SqlError>;
+    async fn get_space_usage(&self) -> Result<Vec<SpaceRegistryUsage>, SqlError>;
+
+    /// Bytes used by a space across all registries
+    async fn get_space_bytes(&self, space: &str) -> Result<i64, SqlError>;
+
+    /// Add `bytes` to a space's usage for a registry unless that takes the space past
+    /// `hard_quota_bytes`. The space is locked while its usage is checked, so concurrent
+    /// reservations cannot together exceed the quota. Returns whether the bytes were reserved
+    async fn reserve_space_usage(
+        &self,
+        space: &str,
+        registry_type: &RegistryType,
+        bytes: i64,
+        hard_quota_bytes: i64,
+    ) -> Result<bool, SqlError>;
+
+    /// Replace a space's usage for a registry, used when usage is recounted from storage
+    async fn set_space_usage(
+        &self,
+        space: &str,
+        registry_type: &RegistryType,
+        bytes: i64,
+    ) -> Result<(), SqlError>;
+
+    /// Record `bytes` reserved for an upload to `path` that has not completed yet. Reservations
+    /// for the same path add up
+    async fn insert_upload_reservation(
+        &self,
+        path: &str,
+        space: &str,
+        registry_type: &RegistryType,
+        bytes: i64,
+    ) -> Result<(), SqlError>;
+
+    /// Remove the reservation of an upload to `path`, returning the bytes it held
+    async fn take_upload_reservation(&self, path: &str) -> Result<Option<i64>, SqlError>;
+
+    /// Bytes held by pending upload reservations per space and registry
+    async fn get_upload_reservations(&self) -> Result<Vec<SpaceRegistryUsage>, SqlError>;
+
+    /// Delete reservations created before `created_before`, left behind by uploads that were
+    /// never completed. Their bytes stay in the space usage until it is recounted
+    async fn delete_stale_upload_reservations(
+        &self,
+        created_before: &DateTime<Utc>,
+    ) -> Result<u64, SqlError>;
 }
 
 #[async_trait]
 pub trait AuditLogicTrait {
     async fn insert_audit_event(&self, event: AuditEvent) -> Result<(), SqlError>;
+
+    /// Query audit events newest first, using keyset pagination on the event id
+    async fn query_audit_events(
+        &self,
+        query_args: &AuditQueryArgs,
+    ) -> Result<Vec<AuditEventRecord>, SqlError>;
 }
 
 #[async_trait]
@@ -1,7 +1,56 @@
 use crate::error::SqlError;
-use opsml_semver::VersionParser;
+use opsml_semver::{Comparator, VersionParser, VersionPredicate, VersionRange};
+use opsml_types::contracts::{HIGHLIGHT_END, HIGHLIGHT_START};
+
+/// Render a predicate as a lexicographic comparison on the (major, minor, patch) columns.
+/// Values come from parsed integers so they are safe to inline
+fn predicate_to_sql(predicate: &VersionPredicate) -> String {
+    let (major, minor, patch) = predicate.key();
+
+    let compare = |major_op: &str, patch_op: &str| {
+        format!(
+            "(major {major_op} {major} OR (major = {major} AND (minor {major_op} {minor} OR (minor = {minor} AND patch {patch_op} {patch}))))"
+        )
+    };
+
+    match predicate.op {
+        Comparator::Greater => compare(">", ">"),
+        Comparator::GreaterEq => compare(">", ">="),
+        Comparator::Less => compare("<", "<"),
+        Comparator::LessEq => compare("<", "<="),
+        Comparator::Exact => format!("(major = {major} AND minor = {minor} AND patch = {patch})"),
+        Comparator::NotEqual => {
+            format!("NOT (major = {major} AND minor = {minor} AND patch = {patch})")
+        }
+    }
+}
+
+/// Append a range expression (e.g. `>=1.2.0, <2.0.0 || ^3.0`) as a single AND clause
+fn add_version_range(builder: &mut String, range: &VersionRange) {
+    let alternatives = range
+        .alternatives
+        .iter()
+        .map(|predicates| {
+            let clause = predicates
+                .iter()
+                .map(predicate_to_sql)
+                .collect::<Vec<_>>()
+                .join(" AND ");
+            format!("({clause})")
+        })
+        .collect::<Vec<_>>()
+        .join(" OR ");
+
+    builder.push_str(&format!(" AND ({alternatives})"));
+}
 
 pub fn add_version_bounds(builder: &mut String, version: &str) -> Result<(), SqlError> {
+    if VersionRange::is_range_expression(version) {
+        let range = VersionRange::parse(version)?;
+        add_version_range(builder, &range);
+        return Ok(());
+    }
+
     let version_bounds = VersionParser::get_version_to_search(version)?;
 
     // construct lower bound (already validated)
@@ -45,3 +94,65 @@ pub fn add_version_bounds(builder: &mut String, version: &str) -> Result<(), Sql
     }
     Ok(())
 }
+
+/// Build a search highlight for backends without a native one.
+///
+/// Returns an excerpt of about `max_words` words around the first word matching (or starting
+/// with) one of `terms`, with matching words wrapped in highlight markers
+pub fn highlight_terms(text: &str, terms: &[String], max_words: usize) -> Option<String> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let is_match = |word: &str| {
+        let word = word
+            .trim_matches(|c: char| !c.is_alphanumeric() && c != '_')
+            .to_lowercase();
+        !word.is_empty() && terms.iter().any(|term| word.starts_with(term.as_str()))
+    };
+
+    let first = words.iter().position(|word| is_match(word))?;
+    let start = first.saturating_sub(max_words / 4);
+    let end = (start + max_words).min(words.len());
+
+    let excerpt = words[start..end]
+        .iter()
+        .map(|word| match is_match(word) {
+            true => format!("{HIGHLIGHT_START}{word}{HIGHLIGHT_END}"),
+            false => word.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let prefix = if start > 0 { "..." } else { "" };
+    let suffix = if end < words.len() { "..." } else { "" };
+    Some(format!("{prefix}{excerpt}{suffix}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_version_bounds_range() {
+        let mut query = String::new();
+        add_version_bounds(&mut query, ">=1.2.0, !=1.3.1 || <0.5").unwrap();
+
+        assert_eq!(
+            query,
+            " AND (((major > 1 OR (major = 1 AND (minor > 2 OR (minor = 2 AND patch >= 0)))) AND NOT (major = 1 AND minor = 3 AND patch = 1)) OR ((major < 0 OR (major = 0 AND (minor < 5 OR (minor = 5 AND patch < 0))))))"
+        );
+
+        assert!(add_version_bounds(&mut query, ">=1.2.0,").is_ok());
+        assert!(add_version_bounds(&mut query, ">=1.x").is_err());
+    }
+
+    #[test]
+    fn test_highlight_terms() {
+        let terms = vec!["churn".to_string()];
+        let text = "one two three four five six seven eight Churn-model predicts churning users";
+
+        assert_eq!(
+            highlight_terms(text, &terms, 6).unwrap(),
+            "...eight <mark>Churn-model</mark> predicts <mark>churning</mark> users"
+        );
+        assert!(highlight_terms("nothing relevant", &terms, 6).is_none());
+    }
+}
@@ -4,8 +4,12 @@ pub mod storage;
 pub use storage::enums::client::StorageClientEnum;
 
 pub use crate::storage::error::StorageError;
-pub use crate::storage::local::client::copy_objects;
+pub use crate::storage::local::client::{copy_objects, file_version};
 pub use storage::filesystem::{
     FileSystemStorage, async_storage_client, reset_storage_client, storage_client,
 };
 pub use storage::http::client::HttpFSStorageClient;
+pub use storage::manifest::{ArtifactManifest, ManifestIssue, verify_manifest, write_manifest};
+pub use storage::transfer::{
+    ProgressCallback, ProgressTracker, TransferProgress, console_progress,
+};
@@ -2,23 +2,27 @@ use crate::storage::aws::error::AwsError;
 use crate::storage::base::{PathExt, StorageClient, get_files};
 use crate::storage::error::StorageError;
 use crate::storage::filesystem::FileSystem;
+use crate::storage::transfer::{
+    ObjectStream, ProgressTracker, download_objects, run_concurrent, upload_sizes,
+};
 use crate::storage::utils::get_chunk_parts;
 use async_trait::async_trait;
 use aws_config::BehaviorVersion;
 use aws_config::SdkConfig;
 use aws_sdk_s3::Client;
+use aws_sdk_s3::config::{Region, RequestChecksumCalculation, ResponseChecksumValidation};
 use aws_sdk_s3::operation::get_object::GetObjectOutput;
 use aws_sdk_s3::presigning::PresigningConfig;
 use aws_sdk_s3::primitives::ByteStream;
 use aws_sdk_s3::primitives::Length;
 use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
-use opsml_settings::config::OpsmlStorageSettings;
+use aws_smithy_http_client::tls::{self, TlsContext, TrustStore, rustls_provider::CryptoMode};
+use futures::StreamExt;
+use opsml_settings::config::{OpsmlStorageSettings, S3Settings};
 use opsml_types::StorageType;
 use opsml_types::contracts::{CompleteMultipartUpload, FileInfo, MultipartCompleteParts};
 use opsml_utils::ChunkParts;
 use reqwest::Client as HttpClient;
-use std::fs::File;
-use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -29,16 +33,82 @@ use tracing::{error, instrument};
 // Thus, some structs and functions will need to spawn a new runtime to run async functions from a sync context.
 // This is handled at the 3rd-party abstraction level, so the user does not need to worry about it.
 
+// S3-compatible stores generally ignore the region, but requests still need to be signed for one
+const DEFAULT_S3_COMPATIBLE_REGION: &str = "us-east-1";
+
 pub struct AWSCreds {
     pub config: SdkConfig,
 }
 
 impl AWSCreds {
-    pub async fn new() -> Result<Self, AwsError> {
-        let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    pub async fn new(settings: &S3Settings) -> Result<Self, AwsError> {
+        let mut loader = aws_config::defaults(BehaviorVersion::latest());
+
+        if let Some(region) = &settings.region {
+            loader = loader.region(Region::new(region.clone()));
+        }
+
+        if let Some(ca_bundle) = &settings.ca_bundle {
+            let pem = Self::read_ca_bundle(ca_bundle)?;
+            let tls_context = TlsContext::builder()
+                .with_trust_store(TrustStore::default().with_pem_certificate(pem.as_slice()))
+                .build()
+                .map_err(|e| AwsError::CaBundleError(e.to_string()))?;
+
+            loader = loader.http_client(
+                aws_smithy_http_client::Builder::new()
+                    .tls_provider(tls::Provider::Rustls(CryptoMode::AwsLc))
+                    .tls_context(tls_context)
+                    .build_https(),
+            );
+        }
+
+        let mut config = loader.load().await;
+
+        if settings.is_custom_endpoint() && config.region().is_none() {
+            config = config
+                .into_builder()
+                .region(Region::new(DEFAULT_S3_COMPATIBLE_REGION))
+                .build();
+        }
 
         Ok(Self { config })
     }
+
+    /// Build an S3 client, applying any endpoint and addressing overrides
+    pub fn client(&self, settings: &S3Settings) -> Client {
+        let mut builder = aws_sdk_s3::config::Builder::from(&self.config)
+            .force_path_style(settings.force_path_style);
+
+        if let Some(endpoint_url) = &settings.endpoint_url {
+            // most S3-compatible stores reject the flexible checksums the sdk sends by default
+            builder = builder
+                .endpoint_url(endpoint_url)
+                .request_checksum_calculation(RequestChecksumCalculation::WhenRequired)
+                .response_checksum_validation(ResponseChecksumValidation::WhenRequired);
+        }
+
+        Client::from_conf(builder.build())
+    }
+
+    /// Build the http client used to upload parts to presigned urls
+    pub fn http_client(settings: &S3Settings) -> Result<HttpClient, AwsError> {
+        let mut builder = HttpClient::builder();
+
+        if let Some(ca_bundle) = &settings.ca_bundle {
+            for cert in reqwest::Certificate::from_pem_bundle(&Self::read_ca_bundle(ca_bundle)?)? {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+
+        Ok(builder.build()?)
+    }
+
+    fn read_ca_bundle(path: &Path) -> Result<Vec<u8>, AwsError> {
+        std::fs::read(path).map_err(|e| {
+            AwsError::CaBundleError(format!("failed to read {}: {}", path.display(), e))
+        })
+    }
 }
 
 // standalone function for creating a presigned url for a part
@@ -85,9 +155,10 @@ impl AWSMulitPartUpload {
         lpath: &str,
         rpath: &str,
         upload_id: &str,
+        settings: &S3Settings,
     ) -> Result<Self, AwsError> {
-        let creds = AWSCreds::new().await?;
-        let client = Client::new(&creds.config);
+        let creds = AWSCreds::new(settings).await?;
+        let client = creds.client(settings);
         let file_size = Self::get_file_size(lpath)?;
 
         Ok(Self {
@@ -98,7 +169,7 @@ impl AWSMulitPartUpload {
             upload_id: upload_id.to_string(),
             file_size,
 
-            http_client: HttpClient::new(),
+            http_client: AWSCreds::http_client(settings)?,
         })
     }
 
@@ -251,6 +322,7 @@ impl AWSMulitPartUpload {
 pub struct AWSStorageClient {
     pub client: Client,
     pub bucket: String,
+    s3_settings: S3Settings,
 }
 
 #[async_trait]
@@ -263,8 +335,8 @@ impl StorageClient for AWSStorageClient {
     }
     async fn new(settings: &OpsmlStorageSettings) -> Result<Self, StorageError> {
         // read creds from env
-        let creds = AWSCreds::new().await?;
-        let client = Client::new(&creds.config);
+        let creds = AWSCreds::new(&settings.s3_settings).await?;
+        let client = creds.client(&settings.s3_settings);
 
         let bucket = settings
             .storage_uri
@@ -272,40 +344,42 @@ impl StorageClient for AWSStorageClient {
             .unwrap_or(&settings.storage_uri)
             .to_string();
 
-        Ok(Self { client, bucket })
+        Ok(Self {
+            client,
+            bucket,
+            s3_settings: settings.s3_settings.clone(),
+        })
     }
 
-    async fn get_object(&self, lpath: &str, rpath: &str) -> Result<(), StorageError> {
-        // check if lpath and rpath have suffixes
-        let lpath = Path::new(lpath);
-        let rpath = Path::new(rpath);
-
-        // fail if lpath and rpath have no suffixes
-        if lpath.extension().is_none() || rpath.extension().is_none() {
-            return Err(StorageError::LocalAndRemotePathsMustHaveSuffixesError);
-        }
-
-        // create and open lpath file
-        let prefix = Path::new(lpath).parent().unwrap();
-
-        if !prefix.exists() {
-            // create the directory if it does not exist and skip errors
-            std::fs::create_dir_all(prefix)?;
-        }
-
-        // create and open lpath file
-        let mut file = File::create(lpath)?;
+    async fn get_object_version(&self, rpath: &str) -> Result<String, StorageError> {
+        let response = self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(rpath)
+            .send()
+            .await
+            .map_err(|e| AwsError::HeadObjectError(Box::new(e)))?;
 
-        // get stream
-        let mut response = self.get_object_stream(rpath.to_str().unwrap()).await?;
+        Ok(response.e_tag.ok_or(AwsError::MissingEtagError)?)
+    }
 
-        // iterate over the stream and write to the file
-        while let Some(v) = response.body.next().await {
-            let chunk = v.map_err(|e| AwsError::ByteStreamError(e.to_string()))?;
-            file.write_all(&chunk)?;
-        }
+    async fn get_object_range(
+        &self,
+        rpath: &str,
+        offset: u64,
+    ) -> Result<ObjectStream, StorageError> {
+        let response = self.get_object_stream(rpath, offset).await?;
+
+        let stream = futures::stream::try_unfold(response.body, |mut body| async move {
+            match body.try_next().await {
+                Ok(Some(chunk)) => Ok(Some((chunk, body))),
+                Ok(None) => Ok(None),
+                Err(e) => Err(AwsError::ByteStreamError(e.to_string()).into()),
+            }
+        });
 
-        Ok(())
+        Ok(stream.boxed())
     }
 
     /// Generate a presigned url for an object in the storage bucket
@@ -541,12 +615,18 @@ impl AWSStorageClient {
     ///
     /// A Result with the object stream if successful
     ///
-    pub async fn get_object_stream(&self, rpath: &str) -> Result<GetObjectOutput, AwsError> {
+    /// Get an object body, starting at byte `offset`
+    pub async fn get_object_stream(
+        &self,
+        rpath: &str,
+        offset: u64,
+    ) -> Result<GetObjectOutput, AwsError> {
         let response = self
             .client
             .get_object()
             .bucket(&self.bucket)
             .key(rpath)
+            .set_range((offset > 0).then(|| format!("bytes={offset}-")))
             .send()
             .await?;
         Ok(response)
@@ -570,7 +650,7 @@ impl AWSStorageClient {
         rpath: &str,
     ) -> Result<AWSMulitPartUpload, AwsError> {
         let upload_id = self.create_multipart_upload(rpath).await?;
-        AWSMulitPartUpload::new(&self.bucket, lpath, rpath, &upload_id).await
+        AWSMulitPartUpload::new(&self.bucket, lpath, rpath, &upload_id, &self.s3_settings).await
     }
 
     /// Generate a presigned url for a part in the multipart upload
@@ -652,6 +732,7 @@ impl AWSStorageClient {
 #[derive(Clone)]
 pub struct S3FStorageClient {
     client: AWSStorageClient,
+    concurrency: usize,
 }
 
 #[async_trait]
@@ -666,7 +747,10 @@ impl FileSystem for S3FStorageClient {
 
     async fn new(settings: &OpsmlStorageSettings) -> Self {
         let client = AWSStorageClient::new(settings).await.unwrap();
-        Self { client }
+        Self {
+            client,
+            concurrency: settings.transfer_concurrency,
+        }
     }
 
     fn storage_type(&self) -> StorageType {
@@ -683,39 +767,22 @@ impl FileSystem for S3FStorageClient {
         self.client.find_info(stripped_path.to_str().unwrap()).await
     }
 
-    async fn get(&self, lpath: &Path, rpath: &Path, recursive: bool) -> Result<(), StorageError> {
-        // strip the paths
-        let stripped_rpath = rpath.strip_path(self.client.bucket().await);
-        let stripped_lpath = lpath.strip_path(self.client.bucket().await);
-
-        if recursive {
-            // list all objects in the path
-            let objects = self.client.find(stripped_rpath.to_str().unwrap()).await?;
-
-            // iterate over each object and get it
-            for obj in objects {
-                let file_path = Path::new(obj.as_str());
-                let stripped_path = file_path.strip_path(self.client.bucket().await);
-                let relative_path = file_path.relative_path(&stripped_rpath)?;
-                let local_path = stripped_lpath.join(relative_path);
-
-                self.client
-                    .get_object(
-                        local_path.to_str().unwrap(),
-                        stripped_path.to_str().unwrap(),
-                    )
-                    .await?;
-            }
-        } else {
-            self.client
-                .get_object(
-                    stripped_lpath.to_str().unwrap(),
-                    stripped_rpath.to_str().unwrap(),
-                )
-                .await?;
-        }
-
-        Ok(())
+    async fn get_with_progress(
+        &self,
+        lpath: &Path,
+        rpath: &Path,
+        recursive: bool,
+        progress: &ProgressTracker,
+    ) -> Result<(), StorageError> {
+        download_objects(
+            &self.client,
+            lpath,
+            rpath,
+            recursive,
+            self.concurrency,
+            progress,
+        )
+        .await
     }
 
     async fn copy(&self, src: &Path, dest: &Path, recursive: bool) -> Result<(), StorageError> {
@@ -774,49 +841,56 @@ impl FileSystem for S3FStorageClient {
             .await
     }
 
-    async fn put(&self, lpath: &Path, rpath: &Path, recursive: bool) -> Result<(), StorageError> {
+    async fn put_with_progress(
+        &self,
+        lpath: &Path,
+        rpath: &Path,
+        recursive: bool,
+        progress: &ProgressTracker,
+    ) -> Result<(), StorageError> {
         let stripped_lpath = lpath.strip_path(self.client.bucket().await);
         let stripped_rpath = rpath.strip_path(self.client.bucket().await);
 
-        if recursive {
+        let uploads = if recursive {
             if !stripped_lpath.is_dir() {
                 return Err(StorageError::PathMustBeDirectoryError);
             }
 
-            let files: Vec<PathBuf> = get_files(&stripped_lpath)?;
-
-            for file in files {
-                let chunk_parts = get_chunk_parts(&file)?;
-
+            let mut uploads = Vec::new();
+            for file in get_files(&stripped_lpath)? {
                 let stripped_file_path = file.strip_path(self.client.bucket().await);
                 let relative_path = file.relative_path(&stripped_lpath)?;
-                let remote_path = stripped_rpath.join(relative_path);
+                uploads.push((stripped_file_path, stripped_rpath.join(relative_path)));
+            }
+            uploads
+        } else {
+            vec![(stripped_lpath, stripped_rpath)]
+        };
+
+        let sizes = upload_sizes(&uploads)?;
+        progress.add_files(uploads.len() as u64, sizes.iter().sum());
+
+        run_concurrent(
+            uploads.into_iter().zip(sizes).collect(),
+            self.concurrency,
+            |((local_path, remote_path), size)| async move {
+                let chunk_parts = get_chunk_parts(&local_path)?;
 
                 let uploader = self
                     .client
                     .create_multipart_uploader(
-                        stripped_file_path.to_str().unwrap(),
+                        local_path.to_str().unwrap(),
                         remote_path.to_str().unwrap(),
                     )
                     .await?;
 
                 uploader.upload_file_in_chunks(chunk_parts).await?;
-            }
-        } else {
-            let chunk_parts = get_chunk_parts(&stripped_lpath)?;
-
-            let uploader = self
-                .client
-                .create_multipart_uploader(
-                    stripped_lpath.to_str().unwrap(),
-                    stripped_rpath.to_str().unwrap(),
-                )
-                .await?;
-
-            uploader.upload_file_in_chunks(chunk_parts).await?;
-        }
-
-        Ok(())
+                progress.add_bytes(size);
+                progress.file_completed();
+                Ok::<(), StorageError>(())
+            },
+        )
+        .await
     }
 
     async fn complete_multipart_upload(
@@ -855,6 +929,7 @@ impl S3FStorageClient {
             lpath.to_str().unwrap(),
             rpath.to_str().unwrap(),
             &upload_id,
+            &self.client.s3_settings,
         )
         .await
     }
@@ -880,6 +955,8 @@ mod tests {
     use rand::Rng;
     use rand::distr::Alphanumeric;
     use rand::rng;
+    use std::fs::File;
+    use std::io::Write;
     use std::path::Path;
     use tempfile::TempDir;
 
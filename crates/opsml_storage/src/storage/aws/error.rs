@@ -6,6 +6,7 @@ use aws_sdk_s3::operation::create_multipart_upload::CreateMultipartUploadError;
 use aws_sdk_s3::operation::delete_object::DeleteObjectError;
 use aws_sdk_s3::operation::delete_objects::DeleteObjectsError;
 use aws_sdk_s3::operation::get_object::GetObjectError;
+use aws_sdk_s3::operation::head_object::HeadObjectError;
 use aws_sdk_s3::operation::list_objects_v2::ListObjectsV2Error;
 use aws_sdk_s3::operation::upload_part::UploadPartError;
 use aws_sdk_s3::presigning::PresigningConfigError;
@@ -40,6 +41,9 @@ pub enum AwsError {
     #[error(transparent)]
     GetObjectError(#[from] Box<SdkError<GetObjectError>>),
 
+    #[error(transparent)]
+    HeadObjectError(#[from] Box<SdkError<HeadObjectError>>),
+
     #[error(transparent)]
     ListObjectsV2Error(#[from] Box<SdkError<ListObjectsV2Error>>),
 
@@ -76,6 +80,9 @@ pub enum AwsError {
 
     #[error("No eTag is response")]
     MissingEtagError,
+
+    #[error("Invalid S3 CA bundle: {0}")]
+    CaBundleError(String),
 }
 
 impl From<SdkError<CreateMultipartUploadError>> for AwsError {
@@ -106,6 +113,11 @@ impl From<SdkError<GetObjectError>> for AwsError {
         Self::GetObjectError(Box::new(err))
     }
 }
+impl From<SdkError<HeadObjectError>> for AwsError {
+    fn from(err: SdkError<HeadObjectError>) -> Self {
+        Self::HeadObjectError(Box::new(err))
+    }
+}
 impl From<SdkError<ListObjectsV2Error>> for AwsError {
     fn from(err: SdkError<ListObjectsV2Error>) -> Self {
         Self::ListObjectsV2Error(Box::new(err))
@@ -4,6 +4,9 @@ use crate::storage::base::StorageClient;
 use crate::storage::base::get_files;
 use crate::storage::error::StorageError;
 use crate::storage::filesystem::FileSystem;
+use crate::storage::transfer::{
+    ObjectStream, ProgressTracker, download_objects, run_concurrent, upload_sizes,
+};
 use crate::storage::utils::get_chunk_parts;
 use crate::storage::utils::set_download_chunk_size;
 use async_trait::async_trait;
@@ -25,7 +28,6 @@ use std::fs::File;
 use std::io::BufReader;
 use std::io::Read;
 use std::io::Seek;
-use std::io::Write;
 use std::path::{Path, PathBuf};
 use time::{Duration, OffsetDateTime};
 
@@ -217,44 +219,43 @@ impl StorageClient for AzureStorageClient {
         Ok(true)
     }
 
-    async fn get_object(&self, lpath: &str, rpath: &str) -> Result<(), StorageError> {
-        let lpath = Path::new(lpath);
-        let rpath = Path::new(rpath);
-
-        if lpath.extension().is_none() || rpath.extension().is_none() {
-            return Err(StorageError::PathMustBeDirectoryError);
-        }
-
-        // create and open lpath file
-        let prefix = Path::new(lpath).parent().unwrap();
-
-        if !prefix.exists() {
-            // create the directory if it does not exist and skip errors
-            std::fs::create_dir_all(prefix)?;
-        }
+    async fn get_object_version(&self, rpath: &str) -> Result<String, StorageError> {
+        let properties = self
+            .client
+            .container_client(self.bucket.as_str())
+            .blob_client(rpath)
+            .get_properties()
+            .await
+            .map_err(AzureError::CoreError)?;
 
-        // create and open lpath file
-        let mut file = File::create(lpath)?;
-        let file_size = file.metadata()?.len();
+        Ok(properties.blob.properties.etag.to_string())
+    }
 
-        let chunk_size = set_download_chunk_size(file_size, None);
+    async fn get_object_range(
+        &self,
+        rpath: &str,
+        offset: u64,
+    ) -> Result<ObjectStream, StorageError> {
+        // the blob size is not known up front, so start from the smallest download chunk
+        let chunk_size = set_download_chunk_size(0, None);
 
         let container = self.client.container_client(self.bucket.as_str());
-        let blob = container.blob_client(rpath.to_str().unwrap());
+        let blob = container.blob_client(rpath);
 
-        let mut stream = blob.get().chunk_size(chunk_size as u64).into_stream();
+        let mut request = blob.get().chunk_size(chunk_size as u64);
+        if offset > 0 {
+            request = request.range(offset..);
+        }
 
-        // iterate over the stream and write to the file
-        while let Some(value) = stream.next().await {
+        let stream = request.into_stream().then(|value| async move {
             let chunk = value.map_err(AzureError::CoreError)?.data;
 
             // collect into bytes
             let bytes = chunk.collect().await.map_err(AzureError::CoreError)?;
+            Ok::<_, StorageError>(bytes)
+        });
 
-            file.write_all(&bytes)?;
-        }
-
-        Ok(())
+        Ok(stream.boxed())
     }
 
     async fn generate_presigned_url(
@@ -420,6 +421,7 @@ impl AzureStorageClient {
 pub struct AzureFSStorageClient {
     client: AzureStorageClient,
     http_client: HttpClient,
+    concurrency: usize,
 }
 
 #[async_trait]
@@ -442,6 +444,7 @@ impl FileSystem for AzureFSStorageClient {
         Self {
             client,
             http_client,
+            concurrency: settings.transfer_concurrency,
         }
     }
 
@@ -455,39 +458,22 @@ impl FileSystem for AzureFSStorageClient {
         self.client.find_info(stripped_path.to_str().unwrap()).await
     }
 
-    async fn get(&self, lpath: &Path, rpath: &Path, recursive: bool) -> Result<(), StorageError> {
-        // strip the paths
-        let stripped_rpath = rpath.strip_path(self.client.bucket().await);
-        let stripped_lpath = lpath.strip_path(self.client.bucket().await);
-
-        if recursive {
-            // list all objects in the path
-            let objects = self.client.find(stripped_rpath.to_str().unwrap()).await?;
-
-            // iterate over each object and get it
-            for obj in objects {
-                let file_path = Path::new(obj.as_str());
-                let stripped_path = file_path.strip_path(self.client.bucket().await);
-                let relative_path = file_path.relative_path(&stripped_rpath)?;
-                let local_path = stripped_lpath.join(relative_path);
-
-                self.client
-                    .get_object(
-                        local_path.to_str().unwrap(),
-                        stripped_path.to_str().unwrap(),
-                    )
-                    .await?;
-            }
-        } else {
-            self.client
-                .get_object(
-                    stripped_lpath.to_str().unwrap(),
-                    stripped_rpath.to_str().unwrap(),
-                )
-                .await?;
-        }
-
-        Ok(())
+    async fn get_with_progress(
+        &self,
+        lpath: &Path,
+        rpath: &Path,
+        recursive: bool,
+        progress: &ProgressTracker,
+    ) -> Result<(), StorageError> {
+        download_objects(
+            &self.client,
+            lpath,
+            rpath,
+            recursive,
+            self.concurrency,
+            progress,
+        )
+        .await
     }
 
     async fn copy(&self, src: &Path, dest: &Path, recursive: bool) -> Result<(), StorageError> {
@@ -547,41 +533,52 @@ impl FileSystem for AzureFSStorageClient {
             .await
     }
 
-    async fn put(&self, lpath: &Path, rpath: &Path, recursive: bool) -> Result<(), StorageError> {
+    async fn put_with_progress(
+        &self,
+        lpath: &Path,
+        rpath: &Path,
+        recursive: bool,
+        progress: &ProgressTracker,
+    ) -> Result<(), StorageError> {
         let stripped_lpath = lpath.strip_path(self.client.bucket().await);
         let stripped_rpath = rpath.strip_path(self.client.bucket().await);
 
-        if recursive {
+        let uploads = if recursive {
             if !stripped_lpath.is_dir() {
                 return Err(StorageError::PathMustBeDirectoryError);
             }
 
-            let files: Vec<PathBuf> = get_files(&stripped_lpath)?;
-
-            for file in files {
-                let chunk_parts = get_chunk_parts(&file)?;
-
+            let mut uploads = Vec::new();
+            for file in get_files(&stripped_lpath)? {
                 let stripped_file_path = file.strip_path(self.client.bucket().await);
                 let relative_path = file.relative_path(&stripped_lpath)?;
-                let remote_path = stripped_rpath.join(relative_path);
-
-                let uploader = self
-                    .create_multipart_uploader(&stripped_file_path, &remote_path)
-                    .await?;
-
-                uploader.upload_file_in_chunks(chunk_parts).await?;
+                uploads.push((stripped_file_path, stripped_rpath.join(relative_path)));
             }
+            uploads
         } else {
-            let chunk_parts = get_chunk_parts(&stripped_lpath)?;
+            vec![(stripped_lpath, stripped_rpath)]
+        };
 
-            let uploader = self
-                .create_multipart_uploader(&stripped_lpath, &stripped_rpath)
-                .await?;
+        let sizes = upload_sizes(&uploads)?;
+        progress.add_files(uploads.len() as u64, sizes.iter().sum());
 
-            uploader.upload_file_in_chunks(chunk_parts).await?;
-        };
+        run_concurrent(
+            uploads.into_iter().zip(sizes).collect(),
+            self.concurrency,
+            |((local_path, remote_path), size)| async move {
+                let chunk_parts = get_chunk_parts(&local_path)?;
 
-        Ok(())
+                let uploader = self
+                    .create_multipart_uploader(&local_path, &remote_path)
+                    .await?;
+
+                uploader.upload_file_in_chunks(chunk_parts).await?;
+                progress.add_bytes(size);
+                progress.file_completed();
+                Ok::<(), StorageError>(())
+            },
+        )
+        .await
     }
 
     async fn complete_multipart_upload(
@@ -652,6 +649,7 @@ mod tests {
     use rand::Rng;
     use rand::distr::Alphanumeric;
     use rand::rng;
+    use std::io::Write;
     use std::path::Path;
     use tempfile::TempDir;
 
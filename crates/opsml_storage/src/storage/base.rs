@@ -1,5 +1,8 @@
 // create pyo3 async iterator
 use crate::storage::error::StorageError;
+use crate::storage::transfer::{
+    DEFAULT_TRANSFER_CONCURRENCY, ObjectStream, ProgressTracker, download_objects,
+};
 use async_trait::async_trait;
 use opsml_settings::config::OpsmlStorageSettings;
 use opsml_types::{StorageType, contracts::FileInfo};
@@ -56,13 +59,21 @@ pub fn get_files(path: &Path) -> Result<Vec<PathBuf>, StorageError> {
 
 // Define the StorageClient trait with common methods
 #[async_trait]
-pub trait StorageClient: Sized {
+pub trait StorageClient: Sized + Send + Sync {
     fn storage_type(&self) -> StorageType;
     async fn bucket(&self) -> &str;
     async fn new(settings: &OpsmlStorageSettings) -> Result<Self, StorageError>;
     async fn find(&self, path: &str) -> Result<Vec<String>, StorageError>;
     async fn find_info(&self, path: &str) -> Result<Vec<FileInfo>, StorageError>;
-    async fn get_object(&self, local_path: &str, remote_path: &str) -> Result<(), StorageError>;
+    /// Stream an object starting at byte `offset`, used for resumable downloads
+    async fn get_object_range(
+        &self,
+        remote_path: &str,
+        offset: u64,
+    ) -> Result<ObjectStream, StorageError>;
+    /// Identifier that changes whenever the object is rewritten, used to tell whether a partially
+    /// downloaded file still belongs to the object
+    async fn get_object_version(&self, remote_path: &str) -> Result<String, StorageError>;
     async fn copy_objects(&self, src: &str, dest: &str) -> Result<bool, StorageError>;
     async fn copy_object(&self, src: &str, dest: &str) -> Result<bool, StorageError>;
     async fn delete_objects(&self, path: &str) -> Result<bool, StorageError>;
@@ -98,38 +109,15 @@ pub trait FileSystem<T: StorageClient> {
     }
 
     async fn get(&self, lpath: &Path, rpath: &Path, recursive: bool) -> Result<(), StorageError> {
-        // strip the paths
-        let stripped_rpath = rpath.strip_path(self.client().bucket().await);
-        let stripped_lpath = lpath.strip_path(self.client().bucket().await);
-
-        if recursive {
-            // list all objects in the path
-            let objects = self.client().find(stripped_rpath.to_str().unwrap()).await?;
-
-            // iterate over each object and get it
-            for obj in objects {
-                let file_path = Path::new(obj.as_str());
-                let stripped_path = file_path.strip_path(self.client().bucket().await);
-                let relative_path = file_path.relative_path(&stripped_rpath)?;
-                let local_path = stripped_lpath.join(relative_path);
-
-                self.client()
-                    .get_object(
-                        local_path.to_str().unwrap(),
-                        stripped_path.to_str().unwrap(),
-                    )
-                    .await?;
-            }
-        } else {
-            self.client()
-                .get_object(
-                    stripped_lpath.to_str().unwrap(),
-                    stripped_rpath.to_str().unwrap(),
-                )
-                .await?;
-        }
-
-        Ok(())
+        download_objects(
+            self.client(),
+            lpath,
+            rpath,
+            recursive,
+            DEFAULT_TRANSFER_CONCURRENCY,
+            &ProgressTracker::default(),
+        )
+        .await
     }
 
     async fn copy(&self, src: &Path, dest: &Path, recursive: bool) -> Result<(), StorageError> {
@@ -7,6 +7,7 @@ use crate::storage::aws::client::{AWSMulitPartUpload, S3FStorageClient};
 use crate::storage::azure::client::{AzureFSStorageClient, AzureMultipartUpload};
 use crate::storage::error::StorageError;
 use crate::storage::gcs::client::{GCSFSStorageClient, GoogleMultipartUpload};
+use crate::storage::transfer::ProgressTracker;
 use anyhow::{Context, Result as AnyhowResult};
 use opsml_settings::config::{OpsmlConfig, OpsmlStorageSettings};
 use opsml_types::StorageType;
@@ -165,6 +166,70 @@ impl StorageClientEnum {
         }
     }
 
+    #[instrument(skip_all)]
+    pub async fn get_with_progress(
+        &self,
+        lpath: &Path,
+        rpath: &Path,
+        recursive: bool,
+        progress: &ProgressTracker,
+    ) -> Result<(), StorageError> {
+        match self {
+            StorageClientEnum::Google(client) => {
+                client
+                    .get_with_progress(lpath, rpath, recursive, progress)
+                    .await
+            }
+            StorageClientEnum::AWS(client) => {
+                client
+                    .get_with_progress(lpath, rpath, recursive, progress)
+                    .await
+            }
+            StorageClientEnum::Local(client) => {
+                client
+                    .get_with_progress(lpath, rpath, recursive, progress)
+                    .await
+            }
+            StorageClientEnum::Azure(client) => {
+                client
+                    .get_with_progress(lpath, rpath, recursive, progress)
+                    .await
+            }
+        }
+    }
+
+    #[instrument(skip_all)]
+    pub async fn put_with_progress(
+        &self,
+        lpath: &Path,
+        rpath: &Path,
+        recursive: bool,
+        progress: &ProgressTracker,
+    ) -> Result<(), StorageError> {
+        match self {
+            StorageClientEnum::Google(client) => {
+                client
+                    .put_with_progress(lpath, rpath, recursive, progress)
+                    .await
+            }
+            StorageClientEnum::AWS(client) => {
+                client
+                    .put_with_progress(lpath, rpath, recursive, progress)
+                    .await
+            }
+            StorageClientEnum::Local(client) => {
+                client
+                    .put_with_progress(lpath, rpath, recursive, progress)
+                    .await
+            }
+            StorageClientEnum::Azure(client) => {
+                client
+                    .put_with_progress(lpath, rpath, recursive, progress)
+                    .await
+            }
+        }
+    }
+
     #[instrument(skip_all)]
     pub async fn copy(&self, src: &Path, dest: &Path, recursive: bool) -> Result<(), StorageError> {
         match self {
@@ -103,6 +103,25 @@ pub enum StorageError {
 
     #[error(transparent)]
     JoinError(#[from] tokio::task::JoinError),
+
+    #[error("Checksum mismatch for {path}: expected sha256 {expected}, got {actual}")]
+    ChecksumMismatchError {
+        path: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("File listed in artifact manifest is missing: {0}")]
+    ManifestFileMissingError(String),
+
+    #[error("Card was uploaded with an artifact manifest, but {0} is missing")]
+    ManifestMissingError(String),
+
+    #[error(transparent)]
+    ThreadPoolBuildError(#[from] rayon::ThreadPoolBuildError),
+
+    #[error("Transfer failed: {0}")]
+    TransferError(String),
 }
 
 #[cfg(feature = "server")]
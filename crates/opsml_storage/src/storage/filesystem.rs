@@ -6,6 +6,7 @@ use opsml_state::block_on;
 
 use crate::storage::error::StorageError;
 use crate::storage::http::client::{AsyncHttpFSStorageClient, HttpFSStorageClient};
+use crate::storage::transfer::{DEFAULT_TRANSFER_CONCURRENCY, ProgressTracker};
 use async_trait::async_trait;
 use opsml_settings::config::{OpsmlMode, OpsmlStorageSettings};
 use opsml_state::{app_state, get_api_client, get_async_api_client};
@@ -25,8 +26,30 @@ pub trait FileSystem {
     async fn new(settings: &OpsmlStorageSettings) -> Self;
     async fn find(&self, path: &Path) -> Result<Vec<String>, StorageError>;
     async fn find_info(&self, path: &Path) -> Result<Vec<FileInfo>, StorageError>;
-    async fn get(&self, lpath: &Path, rpath: &Path, recursive: bool) -> Result<(), StorageError>;
-    async fn put(&self, lpath: &Path, rpath: &Path, recursive: bool) -> Result<(), StorageError>;
+    async fn get(&self, lpath: &Path, rpath: &Path, recursive: bool) -> Result<(), StorageError> {
+        self.get_with_progress(lpath, rpath, recursive, &ProgressTracker::default())
+            .await
+    }
+    async fn put(&self, lpath: &Path, rpath: &Path, recursive: bool) -> Result<(), StorageError> {
+        self.put_with_progress(lpath, rpath, recursive, &ProgressTracker::default())
+            .await
+    }
+    /// Download `rpath` to `lpath`. Recursive downloads fetch up to `transfer_concurrency` objects
+    /// at once and resume from any partially written local files
+    async fn get_with_progress(
+        &self,
+        lpath: &Path,
+        rpath: &Path,
+        recursive: bool,
+        progress: &ProgressTracker,
+    ) -> Result<(), StorageError>;
+    async fn put_with_progress(
+        &self,
+        lpath: &Path,
+        rpath: &Path,
+        recursive: bool,
+        progress: &ProgressTracker,
+    ) -> Result<(), StorageError>;
     async fn copy(&self, src: &Path, dest: &Path, recursive: bool) -> Result<(), StorageError>;
     async fn rm(&self, path: &Path, recursive: bool) -> Result<(), StorageError>;
     async fn exists(&self, path: &Path) -> Result<bool, StorageError>;
@@ -85,7 +108,10 @@ impl FileSystemStorage {
             OpsmlMode::Client => Ok(Self {
                 #[cfg(feature = "server")]
                 server: None,
-                client: Some(HttpFSStorageClient::new(get_api_client().clone())?),
+                client: Some(HttpFSStorageClient::new(
+                    get_api_client().clone(),
+                    app_state().config()?.transfer_concurrency,
+                )?),
                 active_type: ActiveStorageType::Client,
             }),
         }
@@ -128,30 +154,58 @@ impl FileSystemStorage {
     }
 
     pub fn get(&self, lpath: &Path, rpath: &Path, recursive: bool) -> Result<(), StorageError> {
+        self.get_with_progress(lpath, rpath, recursive, &ProgressTracker::default())
+    }
+
+    pub fn get_with_progress(
+        &self,
+        lpath: &Path,
+        rpath: &Path,
+        recursive: bool,
+        progress: &ProgressTracker,
+    ) -> Result<(), StorageError> {
         match self.active_type {
             #[cfg(feature = "server")]
             ActiveStorageType::Server => block_on(async {
                 self.server
                     .as_ref()
                     .unwrap()
-                    .get(lpath, rpath, recursive)
+                    .get_with_progress(lpath, rpath, recursive, progress)
                     .await
             }),
-            ActiveStorageType::Client => self.client.as_ref().unwrap().get(lpath, rpath, recursive),
+            ActiveStorageType::Client => self
+                .client
+                .as_ref()
+                .unwrap()
+                .get_with_progress(lpath, rpath, recursive, progress),
         }
     }
 
     pub fn put(&self, lpath: &Path, rpath: &Path, recursive: bool) -> Result<(), StorageError> {
+        self.put_with_progress(lpath, rpath, recursive, &ProgressTracker::default())
+    }
+
+    pub fn put_with_progress(
+        &self,
+        lpath: &Path,
+        rpath: &Path,
+        recursive: bool,
+        progress: &ProgressTracker,
+    ) -> Result<(), StorageError> {
         match self.active_type {
             #[cfg(feature = "server")]
             ActiveStorageType::Server => block_on(async {
                 self.server
                     .as_ref()
                     .unwrap()
-                    .put(lpath, rpath, recursive)
+                    .put_with_progress(lpath, rpath, recursive, progress)
                     .await
             }),
-            ActiveStorageType::Client => self.client.as_ref().unwrap().put(lpath, rpath, recursive),
+            ActiveStorageType::Client => self
+                .client
+                .as_ref()
+                .unwrap()
+                .put_with_progress(lpath, rpath, recursive, progress),
         }
     }
 
@@ -260,7 +314,11 @@ async fn build_async_storage_client() -> Arc<AsyncHttpFSStorageClient> {
     let async_api_client = get_async_api_client().await.clone();
 
     // Initialize async API client - need an async block here
-    let api_client = AsyncHttpFSStorageClient::new(async_api_client)
+    let concurrency = app_state()
+        .config()
+        .map(|config| config.transfer_concurrency)
+        .unwrap_or(DEFAULT_TRANSFER_CONCURRENCY);
+    let api_client = AsyncHttpFSStorageClient::new(async_api_client, concurrency)
         .await
         .expect("Failed to create async api client");
 
@@ -2,11 +2,13 @@ use crate::storage::base::{PathExt, StorageClient, get_files};
 use crate::storage::error::StorageError;
 use crate::storage::filesystem::FileSystem;
 use crate::storage::gcs::error::GoogleError;
+use crate::storage::transfer::{
+    ObjectStream, ProgressTracker, download_objects, run_concurrent, upload_sizes,
+};
 use crate::storage::utils::get_chunk_parts;
 use async_trait::async_trait;
 use base64::prelude::*;
 use futures::StreamExt;
-use futures::stream::Stream;
 use gcloud_auth::credentials::CredentialsFile;
 use gcloud_storage::client::{Client, ClientConfig};
 use gcloud_storage::http::objects::Object;
@@ -31,7 +33,6 @@ use std::fs::File;
 use std::io::BufReader;
 use std::io::Read;
 use std::io::Seek;
-use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
 use tracing::error;
@@ -223,26 +224,41 @@ impl StorageClient for GoogleStorageClient {
     /// * `lpath` - The path to the local file
     /// * `rpath` - The path to the remote file
     ///
-    async fn get_object(&self, lpath: &str, rpath: &str) -> Result<(), StorageError> {
-        let mut stream = self.get_object_stream(rpath).await?;
-
-        // create and open lpath file
-        let prefix = Path::new(lpath).parent().unwrap();
-
-        if !prefix.exists() {
-            // create the directory if it does not exist and skip errors
-            std::fs::create_dir_all(prefix)?;
-        }
+    async fn get_object_version(&self, rpath: &str) -> Result<String, StorageError> {
+        let object = self
+            .client
+            .get_object(&GetObjectRequest {
+                bucket: self.bucket.clone(),
+                object: rpath.to_string(),
+                ..Default::default()
+            })
+            .await
+            .map_err(GoogleError::GCloudStorageError)?;
 
-        // create and open lpath file
-        let mut file = File::create(lpath)?;
+        Ok(object.generation.to_string())
+    }
 
-        while let Some(v) = stream.next().await {
-            let chunk = v.map_err(GoogleError::GCloudStorageError)?;
-            file.write_all(&chunk)?;
-        }
+    async fn get_object_range(
+        &self,
+        rpath: &str,
+        offset: u64,
+    ) -> Result<ObjectStream, StorageError> {
+        let stream = self
+            .client
+            .download_streamed_object(
+                &GetObjectRequest {
+                    bucket: self.bucket.clone(),
+                    object: rpath.to_string(),
+                    ..Default::default()
+                },
+                &Range(Some(offset).filter(|o| *o > 0), None),
+            )
+            .await
+            .map_err(GoogleError::GCloudStorageError)?;
 
-        Ok(())
+        Ok(stream
+            .map(|chunk| chunk.map_err(|e| GoogleError::GCloudStorageError(e).into()))
+            .boxed())
     }
 
     /// Generate a presigned url for an object in the storage bucket
@@ -462,26 +478,6 @@ impl GoogleStorageClient {
     /// # Returns
     ///
     /// A stream of bytes
-    pub async fn get_object_stream(
-        &self,
-        rpath: &str,
-    ) -> Result<impl Stream<Item = Result<bytes::Bytes, gcloud_storage::http::Error>>, GoogleError>
-    {
-        // open a bucket and blob and return the stream
-        let result = self
-            .client
-            .download_streamed_object(
-                &GetObjectRequest {
-                    bucket: self.bucket.clone(),
-                    object: rpath.to_string(),
-                    ..Default::default()
-                },
-                &Range::default(),
-            )
-            .await?;
-        Ok(result)
-    }
-
     pub async fn create_multipart_upload(
         &self,
         path: &str,
@@ -532,6 +528,7 @@ impl GoogleStorageClient {
 #[derive(Clone)]
 pub struct GCSFSStorageClient {
     client: GoogleStorageClient,
+    concurrency: usize,
 }
 
 #[async_trait]
@@ -546,7 +543,10 @@ impl FileSystem for GCSFSStorageClient {
 
     async fn new(settings: &OpsmlStorageSettings) -> Self {
         let client = GoogleStorageClient::new(settings).await.unwrap();
-        GCSFSStorageClient { client }
+        GCSFSStorageClient {
+            client,
+            concurrency: settings.transfer_concurrency,
+        }
     }
 
     fn storage_type(&self) -> StorageType {
@@ -563,39 +563,22 @@ impl FileSystem for GCSFSStorageClient {
         self.client.find_info(stripped_path.to_str().unwrap()).await
     }
 
-    async fn get(&self, lpath: &Path, rpath: &Path, recursive: bool) -> Result<(), StorageError> {
-        // strip the paths
-        let stripped_rpath = rpath.strip_path(self.client.bucket().await);
-        let stripped_lpath = lpath.strip_path(self.client.bucket().await);
-
-        if recursive {
-            // list all objects in the path
-            let objects = self.client.find(stripped_rpath.to_str().unwrap()).await?;
-
-            // iterate over each object and get it
-            for obj in objects {
-                let file_path = Path::new(obj.as_str());
-                let stripped_path = file_path.strip_path(self.client.bucket().await);
-                let relative_path = file_path.relative_path(&stripped_rpath)?;
-                let local_path = stripped_lpath.join(relative_path);
-
-                self.client
-                    .get_object(
-                        local_path.to_str().unwrap(),
-                        stripped_path.to_str().unwrap(),
-                    )
-                    .await?;
-            }
-        } else {
-            self.client
-                .get_object(
-                    stripped_lpath.to_str().unwrap(),
-                    stripped_rpath.to_str().unwrap(),
-                )
-                .await?;
-        }
-
-        Ok(())
+    async fn get_with_progress(
+        &self,
+        lpath: &Path,
+        rpath: &Path,
+        recursive: bool,
+        progress: &ProgressTracker,
+    ) -> Result<(), StorageError> {
+        download_objects(
+            &self.client,
+            lpath,
+            rpath,
+            recursive,
+            self.concurrency,
+            progress,
+        )
+        .await
     }
 
     async fn copy(&self, src: &Path, dest: &Path, recursive: bool) -> Result<(), StorageError> {
@@ -654,49 +637,56 @@ impl FileSystem for GCSFSStorageClient {
             .await
     }
 
-    async fn put(&self, lpath: &Path, rpath: &Path, recursive: bool) -> Result<(), StorageError> {
+    async fn put_with_progress(
+        &self,
+        lpath: &Path,
+        rpath: &Path,
+        recursive: bool,
+        progress: &ProgressTracker,
+    ) -> Result<(), StorageError> {
         let stripped_lpath = lpath.strip_path(self.client.bucket().await);
         let stripped_rpath = rpath.strip_path(self.client.bucket().await);
 
-        if recursive {
+        let uploads = if recursive {
             if !stripped_lpath.is_dir() {
                 return Err(StorageError::PathMustBeDirectoryError);
             }
 
-            let files: Vec<PathBuf> = get_files(&stripped_lpath)?;
-
-            for file in files {
-                let chunks_parts = get_chunk_parts(&file)?;
-
+            let mut uploads = Vec::new();
+            for file in get_files(&stripped_lpath)? {
                 let stripped_file_path = file.strip_path(self.client.bucket().await);
                 let relative_path = file.relative_path(&stripped_lpath)?;
-                let remote_path = stripped_rpath.join(relative_path);
+                uploads.push((stripped_file_path, stripped_rpath.join(relative_path)));
+            }
+            uploads
+        } else {
+            vec![(stripped_lpath, stripped_rpath)]
+        };
+
+        let sizes = upload_sizes(&uploads)?;
+        progress.add_files(uploads.len() as u64, sizes.iter().sum());
+
+        run_concurrent(
+            uploads.into_iter().zip(sizes).collect(),
+            self.concurrency,
+            |((local_path, remote_path), size)| async move {
+                let chunks_parts = get_chunk_parts(&local_path)?;
 
                 let uploader = self
                     .client
                     .create_multipart_uploader(
-                        stripped_file_path.to_str().unwrap(),
+                        local_path.to_str().unwrap(),
                         remote_path.to_str().unwrap(),
                     )
                     .await?;
 
                 uploader.upload_file_in_chunks(chunks_parts).await?;
-            }
-        } else {
-            let chunks_parts = get_chunk_parts(&stripped_lpath)?;
-
-            let uploader = self
-                .client
-                .create_multipart_uploader(
-                    stripped_lpath.to_str().unwrap(),
-                    stripped_rpath.to_str().unwrap(),
-                )
-                .await?;
-
-            uploader.upload_file_in_chunks(chunks_parts).await?;
-        };
-
-        Ok(())
+                progress.add_bytes(size);
+                progress.file_completed();
+                Ok::<(), StorageError>(())
+            },
+        )
+        .await
     }
 
     async fn complete_multipart_upload(
@@ -713,7 +703,8 @@ impl FileSystem for GCSFSStorageClient {
                     .send()
                     .await?;
 
-                if !response.status().is_success() {
+                // GCS answers a cancelled session with 499
+                if response.status() != 499 && !response.status().is_success() {
                     // log the error
                     // get the response text
                     let error_text = response.text().await.unwrap_or_default();
@@ -759,6 +750,7 @@ mod tests {
     use opsml_utils::create_uuid7;
     use rand::Rng;
     use rand::distr::Alphanumeric;
+    use std::io::Write;
     use std::path::Path;
     use tempfile::TempDir;
 
@@ -1,15 +1,16 @@
 use crate::storage::error::StorageError;
+use crate::storage::http::base::{range_headers, response_version};
+use crate::storage::transfer::{PartialDownload, ProgressTracker};
 use futures_util::StreamExt;
 use opsml_client::OpsmlApiAsyncClient;
 use opsml_client::error::ApiClientError;
 use opsml_colors::Colorize;
 use opsml_types::api::{RequestType, Routes};
 use opsml_types::{StorageType, contracts::*};
+use reqwest::StatusCode;
 use std::path::Path;
 use std::sync::Arc;
-use tokio::fs::File;
-use tokio::io::AsyncWriteExt;
-use tracing::{error, instrument};
+use tracing::{debug, error, instrument};
 
 #[derive(Clone)]
 pub struct AsyncHttpStorageClient {
@@ -84,24 +85,14 @@ impl AsyncHttpStorageClient {
         Ok(settings.storage_type)
     }
 
-    #[instrument(skip_all)]
-    pub async fn get_object(
+    /// Request an object, starting at byte `offset`. `version` is sent as `If-Range`
+    async fn fetch_object(
         &self,
-        local_path: &str,
         remote_path: &str,
-    ) -> Result<(), StorageError> {
-        // check if local path exists, create it if it doesn't
-        let local_path = Path::new(local_path);
-
-        if !local_path.exists() {
-            std::fs::create_dir_all(local_path.parent().unwrap())?;
-        }
-
-        // create local file
-        let mut file = File::create(local_path).await?;
-
-        // generate presigned url for downloading the object
-        let presigned_url = self.generate_presigned_url(remote_path).await?;
+        offset: u64,
+        version: Option<&str>,
+    ) -> Result<reqwest::Response, StorageError> {
+        let headers = range_headers(offset, version);
 
         // download the object
         let response = if self.storage_type == StorageType::Local {
@@ -117,7 +108,7 @@ impl AsyncHttpStorageClient {
                     RequestType::Get,
                     None,
                     Some(query_string),
-                    None,
+                    Some(headers),
                 )
                 .await
                 .inspect_err(|e| {
@@ -126,28 +117,70 @@ impl AsyncHttpStorageClient {
         } else {
             // if storage clients are gcs, aws and azure, use presigned url to download the object
             // If local storage client, download the object from api route
+            let presigned_url = self.generate_presigned_url(remote_path).await?;
             let url = reqwest::Url::parse(&presigned_url).map_err(|e| {
                 error!("Invalid presigned URL: {e}");
                 StorageError::ParseUrlError(e.to_string())
             })?;
 
-            self.api_client.client.get(url).send().await.unwrap()
+            self.api_client
+                .client
+                .get(url)
+                .headers(headers)
+                .send()
+                .await?
         };
 
-        // create buffer to store downloaded data
+        Ok(response.error_for_status().inspect_err(|e| {
+            error!("Failed to download {remote_path}: {e}");
+        })?)
+    }
+
+    /// Download an object to `local_path`, resuming from a previously interrupted download.
+    /// See [`HttpStorageClient::get_object`](crate::storage::http::base::HttpStorageClient::get_object)
+    #[instrument(skip_all)]
+    pub async fn get_object(
+        &self,
+        local_path: &str,
+        remote_path: &str,
+        file_size: i64,
+        progress: &ProgressTracker,
+    ) -> Result<(), StorageError> {
+        let file_size = file_size.max(0) as u64;
+        let mut part = PartialDownload::open(Path::new(local_path), progress.clone())?;
+
+        // a part file as long as the object cannot be checked against it without a request, and a
+        // range starting at the end of the object is not satisfiable
+        if file_size > 0 && part.offset() >= file_size {
+            part.restart()?;
+        }
+
+        let mut response = self
+            .fetch_object(remote_path, part.offset(), part.version())
+            .await?;
+
+        if part.offset() > 0 && response.status() != StatusCode::PARTIAL_CONTENT {
+            debug!("Range request for {remote_path} was ignored, restarting download");
+            part.restart()?;
+        }
+
+        if !part.validate(response_version(response.headers()).as_deref())? {
+            // the range continues a different version of the object
+            response = self.fetch_object(remote_path, 0, None).await?;
+            part.validate(response_version(response.headers()).as_deref())?;
+        }
+
         let mut stream = response.bytes_stream();
         while let Some(chunk) = stream.next().await {
             let chunk = chunk.inspect_err(|e| {
                 error!("Failed to read chunk from response: {e}");
             })?;
-            file.write_all(&chunk).await.inspect_err(|e| {
+            part.write(&chunk).inspect_err(|e| {
                 error!("Failed to write chunk: {e}");
             })?;
         }
 
-        file.flush().await?;
-
-        Ok(())
+        part.finish()
     }
 
     #[instrument(skip_all)]
@@ -1,15 +1,18 @@
 use crate::storage::error::StorageError;
 use crate::storage::http::multipart::MultiPartUploader;
+use crate::storage::transfer::{PartialDownload, ProgressTracker};
 use crate::storage::utils::set_download_chunk_size;
 use opsml_client::OpsmlApiClient;
 use opsml_client::error::ApiClientError;
 use opsml_colors::Colorize;
 use opsml_types::api::{RequestType, Routes};
 use opsml_types::{StorageType, contracts::*};
-use std::io::{Read, Write};
+use reqwest::StatusCode;
+use reqwest::header::{ETAG, HeaderMap, HeaderValue, IF_RANGE, RANGE};
+use std::io::Read;
 use std::path::Path;
 use std::sync::Arc;
-use tracing::{error, instrument};
+use tracing::{debug, error, instrument};
 
 #[derive(Clone)]
 pub struct HttpStorageClient {
@@ -106,25 +109,15 @@ impl HttpStorageClient {
         Ok(response.files)
     }
 
-    #[instrument(skip_all)]
-    pub fn get_object(
+    /// Request an object, starting at byte `offset`. `version` is sent as `If-Range`, so servers
+    /// that support it answer with the full object if it changed since that version
+    fn fetch_object(
         &self,
-        local_path: &str,
         remote_path: &str,
-        file_size: i64,
-    ) -> Result<(), StorageError> {
-        // check if local path exists, create it if it doesn't
-        let local_path = Path::new(local_path);
-
-        if !local_path.exists() {
-            std::fs::create_dir_all(local_path.parent().unwrap())?;
-        }
-
-        // create local file
-        let mut file = std::fs::File::create(local_path)?;
-
-        // generate presigned url for downloading the object
-        let presigned_url = self.generate_presigned_url(remote_path)?;
+        offset: u64,
+        version: Option<&str>,
+    ) -> Result<reqwest::blocking::Response, StorageError> {
+        let headers = range_headers(offset, version);
 
         // download the object
         let response = if self.storage_type == StorageType::Local {
@@ -140,7 +133,7 @@ impl HttpStorageClient {
                     RequestType::Get,
                     None,
                     Some(query_string),
-                    None,
+                    Some(headers),
                 )
                 .inspect_err(|e| {
                     error!("Failed to get file: {e}");
@@ -148,17 +141,57 @@ impl HttpStorageClient {
         } else {
             // if storage clients are gcs, aws and azure, use presigned url to download the object
             // If local storage client, download the object from api route
+            let presigned_url = self.generate_presigned_url(remote_path)?;
             let url = reqwest::Url::parse(&presigned_url).map_err(|e| {
                 error!("Invalid presigned URL: {e}");
                 StorageError::ParseUrlError(e.to_string())
             })?;
 
-            self.api_client.client.get(url).send().unwrap()
+            self.api_client.client.get(url).headers(headers).send()?
         };
 
+        Ok(response.error_for_status().inspect_err(|e| {
+            error!("Failed to download {remote_path}: {e}");
+        })?)
+    }
+
+    /// Download an object to `local_path`, resuming from a previously interrupted download.
+    ///
+    /// The remaining bytes are requested with a `Range` header. The part file is only kept if the
+    /// object's ETag matches the one recorded when it was written. Servers that ignore the header
+    /// respond with the full object, in which case the partial file is discarded
+    #[instrument(skip_all)]
+    pub fn get_object(
+        &self,
+        local_path: &str,
+        remote_path: &str,
+        file_size: i64,
+        progress: &ProgressTracker,
+    ) -> Result<(), StorageError> {
+        let file_size = file_size.max(0) as u64;
+        let mut part = PartialDownload::open(Path::new(local_path), progress.clone())?;
+
+        // a part file as long as the object cannot be checked against it without a request, and a
+        // range starting at the end of the object is not satisfiable
+        if file_size > 0 && part.offset() >= file_size {
+            part.restart()?;
+        }
+
+        let mut reader = self.fetch_object(remote_path, part.offset(), part.version())?;
+
+        if part.offset() > 0 && reader.status() != StatusCode::PARTIAL_CONTENT {
+            debug!("Range request for {remote_path} was ignored, restarting download");
+            part.restart()?;
+        }
+
+        if !part.validate(response_version(reader.headers()).as_deref())? {
+            // the range continues a different version of the object
+            reader = self.fetch_object(remote_path, 0, None)?;
+            part.validate(response_version(reader.headers()).as_deref())?;
+        }
+
         // create buffer to store downloaded data
-        let mut reader = response;
-        let mut buffer = vec![0; set_download_chunk_size(file_size as u64, None)];
+        let mut buffer = vec![0; set_download_chunk_size(file_size, None)];
 
         loop {
             let bytes_read = reader.read(&mut buffer).inspect_err(|e| {
@@ -169,12 +202,12 @@ impl HttpStorageClient {
                 break;
             }
 
-            file.write_all(&buffer[..bytes_read]).inspect_err(|e| {
+            part.write(&buffer[..bytes_read]).inspect_err(|e| {
                 error!("Failed to write chunk: {e}");
             })?;
         }
 
-        Ok(())
+        part.finish()
     }
 
     #[instrument(skip_all)]
@@ -233,10 +266,15 @@ impl HttpStorageClient {
     }
 
     #[instrument(skip_all)]
-    pub fn create_multipart_upload(&self, path: &str) -> Result<MultiPartSession, StorageError> {
+    pub fn create_multipart_upload(
+        &self,
+        path: &str,
+        file_size: Option<i64>,
+    ) -> Result<MultiPartSession, StorageError> {
         // 1 - create multipart upload request and send to server
         let query = MultiPartQuery {
             path: path.to_string(),
+            file_size,
         };
 
         let query_string = serde_qs::to_string(&query)?;
@@ -254,6 +292,12 @@ impl HttpStorageClient {
                 error!("Failed to create multipart upload: {e}");
             })?;
 
+        // the space is over its storage quota
+        if response.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            let error_resp = response.json::<PermissionDenied>()?;
+            return Err(ApiClientError::ServerError(error_resp.error).into());
+        }
+
         // check if unauthorized
         if response.status().is_client_error() {
             let error_resp = response.json::<PermissionDenied>()?;
@@ -277,8 +321,11 @@ impl HttpStorageClient {
         lpath: &Path,
     ) -> Result<MultiPartUploader, StorageError> {
         // 1 get session url from server
+        let file_size = std::fs::metadata(lpath)
+            .ok()
+            .map(|metadata| metadata.len() as i64);
         let multipart_session = self
-            .create_multipart_upload(rpath.to_str().unwrap())
+            .create_multipart_upload(rpath.to_str().unwrap(), file_size)
             .inspect_err(|e| {
                 error!("Failed to create multipart upload: {e}");
             })?;
@@ -321,3 +368,25 @@ impl HttpStorageClient {
         Ok(response.url)
     }
 }
+
+/// Headers requesting an object from byte `offset`, if the object is still at `version`
+pub fn range_headers(offset: u64, version: Option<&str>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if offset > 0 {
+        let range = format!("bytes={offset}-");
+        headers.insert(RANGE, HeaderValue::from_str(&range).unwrap());
+
+        if let Some(value) = version.and_then(|version| HeaderValue::from_str(version).ok()) {
+            headers.insert(IF_RANGE, value);
+        }
+    }
+    headers
+}
+
+/// Version of a downloaded object, taken from the `ETag` header of the response
+pub fn response_version(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(ETAG)
+        .and_then(|etag| etag.to_str().ok())
+        .map(str::to_string)
+}
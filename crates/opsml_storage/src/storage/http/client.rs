@@ -3,18 +3,23 @@ use crate::storage::base::get_files;
 use crate::storage::error::StorageError;
 use crate::storage::http::async_base::AsyncHttpStorageClient;
 use crate::storage::http::base::HttpStorageClient;
+use crate::storage::transfer::ProgressTracker;
 use crate::storage::utils::get_chunk_parts;
 use opsml_client::OpsmlApiAsyncClient;
 use opsml_client::OpsmlApiClient;
 use opsml_types::StorageType;
 use opsml_types::contracts::FileInfo;
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+use rayon::{ThreadPool, ThreadPoolBuilder};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use tokio::sync::Semaphore;
 use tokio::task::JoinSet;
 use tracing::{debug, error};
 pub struct HttpFSStorageClient {
     pub client: HttpStorageClient,
+    /// Bounded pool used for recursive transfers
+    pool: ThreadPool,
 }
 
 impl HttpFSStorageClient {
@@ -25,9 +30,13 @@ impl HttpFSStorageClient {
         "HttpFSStorageClient"
     }
 
-    pub fn new(api_client: Arc<OpsmlApiClient>) -> Result<Self, StorageError> {
+    pub fn new(api_client: Arc<OpsmlApiClient>, concurrency: usize) -> Result<Self, StorageError> {
         Ok(HttpFSStorageClient {
             client: HttpStorageClient::new(api_client)?,
+            pool: ThreadPoolBuilder::new()
+                .num_threads(concurrency.max(1))
+                .thread_name(|i| format!("opsml-transfer-{i}"))
+                .build()?,
         })
     }
 
@@ -40,28 +49,51 @@ impl HttpFSStorageClient {
     }
 
     pub fn get(&self, lpath: &Path, rpath: &Path, recursive: bool) -> Result<(), StorageError> {
+        self.get_with_progress(lpath, rpath, recursive, &ProgressTracker::default())
+    }
+
+    pub fn get_with_progress(
+        &self,
+        lpath: &Path,
+        rpath: &Path,
+        recursive: bool,
+        progress: &ProgressTracker,
+    ) -> Result<(), StorageError> {
         // list all objects in the path
         let objects = self.client.find_info(rpath.to_str().unwrap())?;
 
         if recursive {
-            objects.into_par_iter().try_for_each(|file_info| {
-                let name = file_info.name;
-                let file_path = PathBuf::from(name);
-                let relative_path = file_path.relative_path(rpath)?;
-                let local_path = lpath.join(relative_path);
-
-                self.client.get_object(
-                    local_path.to_str().unwrap(),
-                    file_path.to_str().unwrap(),
-                    file_info.size,
-                )?;
-
-                Ok::<(), StorageError>(())
+            let total_bytes = objects.iter().map(|f| f.size.max(0) as u64).sum();
+            progress.add_files(objects.len() as u64, total_bytes);
+
+            self.pool.install(|| {
+                objects.into_par_iter().try_for_each(|file_info| {
+                    let name = file_info.name;
+                    let file_path = PathBuf::from(name);
+                    let relative_path = file_path.relative_path(rpath)?;
+                    let local_path = lpath.join(relative_path);
+
+                    self.client.get_object(
+                        local_path.to_str().unwrap(),
+                        file_path.to_str().unwrap(),
+                        file_info.size,
+                        progress,
+                    )?;
+
+                    Ok::<(), StorageError>(())
+                })
             })?;
         } else {
             let file = objects.first().ok_or(StorageError::NoFilesFoundError)?;
+            progress.add_files(1, file.size.max(0) as u64);
+
             self.client
-                .get_object(lpath.to_str().unwrap(), rpath.to_str().unwrap(), file.size)
+                .get_object(
+                    lpath.to_str().unwrap(),
+                    rpath.to_str().unwrap(),
+                    file.size,
+                    progress,
+                )
                 .inspect_err(|e| {
                     error!(
                         "Failed to download file from path {:?} to {:?}: {:?}",
@@ -90,6 +122,16 @@ impl HttpFSStorageClient {
     }
 
     pub fn put(&self, lpath: &Path, rpath: &Path, recursive: bool) -> Result<(), StorageError> {
+        self.put_with_progress(lpath, rpath, recursive, &ProgressTracker::default())
+    }
+
+    pub fn put_with_progress(
+        &self,
+        lpath: &Path,
+        rpath: &Path,
+        recursive: bool,
+        progress: &ProgressTracker,
+    ) -> Result<(), StorageError> {
         let lpath_clone = lpath.to_path_buf();
         let rpath_clone = rpath.to_path_buf();
 
@@ -104,27 +146,44 @@ impl HttpFSStorageClient {
             }
 
             let files: Vec<PathBuf> = get_files(lpath)?;
-
-            files.into_par_iter().try_for_each(|file| {
-                let chunk_parts = get_chunk_parts(&file)?;
-                let relative_path = file.relative_path(&lpath_clone)?;
-                let remote_path = rpath_clone.join(relative_path);
-
-                debug!("remote_path: {:?}, stripped_path: {:?}", remote_path, file);
-
-                // setup multipart upload based on storage provider
-                let mut uploader = self.client.create_multipart_uploader(&remote_path, &file)?;
-
-                debug!("Uploading file: {:?}", file);
-                uploader.upload_file_in_chunks(chunk_parts)?;
-
-                Ok::<(), StorageError>(())
+            let sizes = files
+                .iter()
+                .map(|file| Ok(std::fs::metadata(file)?.len()))
+                .collect::<Result<Vec<u64>, StorageError>>()?;
+            progress.add_files(files.len() as u64, sizes.iter().sum());
+
+            self.pool.install(|| {
+                files
+                    .into_par_iter()
+                    .zip(sizes)
+                    .try_for_each(|(file, size)| {
+                        let chunk_parts = get_chunk_parts(&file)?;
+                        let relative_path = file.relative_path(&lpath_clone)?;
+                        let remote_path = rpath_clone.join(relative_path);
+
+                        debug!("remote_path: {:?}, stripped_path: {:?}", remote_path, file);
+
+                        // setup multipart upload based on storage provider
+                        let mut uploader =
+                            self.client.create_multipart_uploader(&remote_path, &file)?;
+
+                        debug!("Uploading file: {:?}", file);
+                        uploader.upload_file_in_chunks(chunk_parts)?;
+                        progress.add_bytes(size);
+                        progress.file_completed();
+
+                        Ok::<(), StorageError>(())
+                    })
             })?;
         } else {
             let chunk_parts = get_chunk_parts(&lpath_clone)?;
+            let size = std::fs::metadata(lpath)?.len();
+            progress.add_files(1, size);
 
             let mut uploader = self.client.create_multipart_uploader(rpath, lpath)?;
             uploader.upload_file_in_chunks(chunk_parts)?;
+            progress.add_bytes(size);
+            progress.file_completed();
         }
 
         Ok(())
@@ -137,6 +196,8 @@ impl HttpFSStorageClient {
 
 pub struct AsyncHttpFSStorageClient {
     pub client: Arc<AsyncHttpStorageClient>,
+    /// Limits the number of downloads in flight during recursive gets
+    permits: Arc<Semaphore>,
 }
 
 impl AsyncHttpFSStorageClient {
@@ -147,9 +208,13 @@ impl AsyncHttpFSStorageClient {
         "HttpFSStorageClient"
     }
 
-    pub async fn new(api_client: Arc<OpsmlApiAsyncClient>) -> Result<Self, StorageError> {
+    pub async fn new(
+        api_client: Arc<OpsmlApiAsyncClient>,
+        concurrency: usize,
+    ) -> Result<Self, StorageError> {
         Ok(AsyncHttpFSStorageClient {
             client: Arc::new(AsyncHttpStorageClient::new(api_client).await?),
+            permits: Arc::new(Semaphore::new(concurrency.max(1))),
         })
     }
 
@@ -158,24 +223,49 @@ impl AsyncHttpFSStorageClient {
         lpath: &Path,
         rpath: &Path,
         recursive: bool,
+    ) -> Result<(), StorageError> {
+        self.get_with_progress(lpath, rpath, recursive, &ProgressTracker::default())
+            .await
+    }
+
+    pub async fn get_with_progress(
+        &self,
+        lpath: &Path,
+        rpath: &Path,
+        recursive: bool,
+        progress: &ProgressTracker,
     ) -> Result<(), StorageError> {
         // list all objects in the path
         let objects = self.client.find_info(rpath.to_str().unwrap()).await?;
 
         if recursive {
+            let total_bytes = objects.iter().map(|f| f.size.max(0) as u64).sum();
+            progress.add_files(objects.len() as u64, total_bytes);
+
             // iterate over each file and spawn a task to download
             let mut set = JoinSet::new();
             let client = self.client.clone();
             for file_info in objects {
                 let client = client.clone();
-                let name = file_info.name;
-                let file_path = PathBuf::from(name);
+                let permits = self.permits.clone();
+                let progress = progress.clone();
+                let file_path = PathBuf::from(file_info.name);
                 let relative_path = file_path.relative_path(rpath)?;
                 let local_path = lpath.join(relative_path);
 
                 set.spawn(async move {
+                    let _permit = permits
+                        .acquire_owned()
+                        .await
+                        .map_err(|e| StorageError::TransferError(e.to_string()))?;
+
                     client
-                        .get_object(local_path.to_str().unwrap(), file_path.to_str().unwrap())
+                        .get_object(
+                            local_path.to_str().unwrap(),
+                            file_path.to_str().unwrap(),
+                            file_info.size,
+                            &progress,
+                        )
                         .await
                 });
             }
@@ -183,8 +273,16 @@ impl AsyncHttpFSStorageClient {
                 res??;
             }
         } else {
+            let file = objects.first().ok_or(StorageError::NoFilesFoundError)?;
+            progress.add_files(1, file.size.max(0) as u64);
+
             self.client
-                .get_object(lpath.to_str().unwrap(), rpath.to_str().unwrap())
+                .get_object(
+                    lpath.to_str().unwrap(),
+                    rpath.to_str().unwrap(),
+                    file.size,
+                    progress,
+                )
                 .await?;
         }
 
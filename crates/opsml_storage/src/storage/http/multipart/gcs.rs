@@ -3,7 +3,7 @@
 use crate::storage::http::multipart::error::MultiPartError;
 
 use opsml_client::OpsmlApiClient;
-use opsml_types::contracts::UploadPartArgs;
+use opsml_types::contracts::{CompleteMultipartUpload, UploadPartArgs, UploadResponse};
 use reqwest::header::{CONTENT_LENGTH, CONTENT_RANGE};
 use std::fmt;
 use std::fs::File;
@@ -56,6 +56,7 @@ impl fmt::Display for ChunkSize {
 #[derive(Debug)]
 pub struct GcsMultipartUpload {
     session_url: String,
+    rpath: String,
     file_reader: BufReader<File>,
     file_size: u64,
     client: Arc<OpsmlApiClient>,
@@ -64,6 +65,7 @@ pub struct GcsMultipartUpload {
 impl GcsMultipartUpload {
     pub fn new(
         lpath: &Path,
+        rpath: &Path,
         session_url: String,
         client: Arc<OpsmlApiClient>,
     ) -> Result<Self, MultiPartError> {
@@ -77,6 +79,7 @@ impl GcsMultipartUpload {
         Ok(GcsMultipartUpload {
             client,
             session_url,
+            rpath: rpath.to_str().unwrap().to_string(),
             file_reader,
             file_size,
         })
@@ -170,7 +173,7 @@ impl GcsMultipartUpload {
                                 "Error uploading chunk {} after {} retries: {}",
                                 chunk_index, MAX_RETRIES, e
                             );
-                            if let Err(cancel_err) = self.cancel_upload() {
+                            if let Err(cancel_err) = self.complete_upload(true) {
                                 error!("Failed to cancel upload after error: {}", cancel_err);
                             }
                             return Err(e);
@@ -211,9 +214,35 @@ impl GcsMultipartUpload {
             }
         }
 
+        // chunks go straight to GCS, so the server only counts the upload toward the space's
+        // storage usage once it is told the upload is done
+        self.complete_upload(false)?;
+
         Ok(())
     }
 
+    /// Complete or cancel the upload through the server, which settles the storage reserved
+    /// for it. Cancelling also removes the upload session
+    fn complete_upload(&self, cancel: bool) -> Result<UploadResponse, MultiPartError> {
+        let request = CompleteMultipartUpload {
+            path: self.rpath.clone(),
+            session_url: self.session_url.clone(),
+            cancel,
+            ..Default::default()
+        };
+
+        let response = self.client.complete_multipart_upload(request)?;
+        if !response.status().is_success() {
+            error!(
+                "Failed to complete upload with status: {}",
+                response.status()
+            );
+            return Err(MultiPartError::UploadError(response.status()));
+        }
+
+        Ok(response.json::<UploadResponse>()?)
+    }
+
     fn complete_multipart_upload(&self) -> Result<(), MultiPartError> {
         let response = self
             .client
@@ -232,25 +261,4 @@ impl GcsMultipartUpload {
             Ok(())
         }
     }
-
-    fn cancel_upload(&self) -> Result<(), MultiPartError> {
-        let response = self
-            .client
-            .client
-            .delete(&self.session_url)
-            .header(CONTENT_LENGTH, 0)
-            .send()?;
-        if response.status() == 499 {
-            Ok(())
-        } else {
-            let error = match response.error_for_status_ref() {
-                Ok(_) => return Ok(()),
-                Err(error) => error,
-            };
-
-            // trace error and raise
-            error!("Failed to cancel upload: {error}");
-            Err(MultiPartError::CancelUploadError(error.to_string()))
-        }
-    }
 }
@@ -1,10 +1,12 @@
 use crate::storage::http::multipart::error::MultiPartError;
 use opsml_client::OpsmlApiClient;
-use opsml_types::contracts::UploadResponse;
+use opsml_client::error::ApiClientError;
+use opsml_types::contracts::{PermissionDenied, UploadResponse};
+use reqwest::StatusCode;
 use reqwest::blocking::multipart::{Form, Part};
 use std::path::Path;
 use std::sync::Arc;
-use tracing::error;
+use tracing::{error, warn};
 
 #[derive(Debug)]
 pub struct LocalMultipartUpload {
@@ -39,6 +41,12 @@ impl LocalMultipartUpload {
             e
         })?;
 
+        // the space is over its storage quota
+        if response.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            let error_resp = response.json::<PermissionDenied>()?;
+            return Err(ApiClientError::ServerError(error_resp.error).into());
+        }
+
         let response = response.json::<UploadResponse>().map_err(|e| {
             error!("Failed to parse upload response: {e}");
             e
@@ -48,6 +56,11 @@ impl LocalMultipartUpload {
             return Err(MultiPartError::FileUploadError);
         }
 
+        // uploads past a space's soft quota succeed with a warning
+        if !response.message.is_empty() {
+            warn!("{}", response.message);
+        }
+
         Ok(())
     }
 }
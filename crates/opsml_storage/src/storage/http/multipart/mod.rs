@@ -32,10 +32,8 @@ impl MultiPartUploader {
         match *storage_type {
             StorageType::Aws => Ok(S3MultipartUpload::new(lpath, rpath, session_url, client)
                 .map(MultiPartUploader::S3)?),
-            StorageType::Google => {
-                Ok(GcsMultipartUpload::new(lpath, session_url, client)
-                    .map(MultiPartUploader::Gcs)?)
-            }
+            StorageType::Google => Ok(GcsMultipartUpload::new(lpath, rpath, session_url, client)
+                .map(MultiPartUploader::Gcs)?),
             StorageType::Local => {
                 LocalMultipartUpload::new(lpath, rpath, client).map(MultiPartUploader::Local)
             }
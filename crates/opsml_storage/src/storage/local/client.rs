@@ -3,16 +3,35 @@ use crate::storage::base::StorageClient;
 use crate::storage::base::get_files;
 use crate::storage::error::{LocalError, StorageError};
 use crate::storage::filesystem::FileSystem;
+use crate::storage::transfer::{
+    ObjectStream, ProgressTracker, download_objects, run_concurrent, upload_sizes,
+};
 use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt};
 use opsml_settings::config::OpsmlStorageSettings;
 use opsml_types::contracts::CompleteMultipartUpload;
 use opsml_types::{StorageType, contracts::FileInfo};
 use std::fs;
+use std::io::SeekFrom;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
+use tokio::io::AsyncSeekExt;
+use tokio_util::io::ReaderStream;
 use tracing::{debug, error, instrument};
 use walkdir::WalkDir;
 
+/// Version of a local file, derived from its size and modification time. Also sent as the ETag of
+/// files served by the local storage download route
+pub fn file_version(metadata: &fs::Metadata) -> String {
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map(|modified| modified.as_nanos())
+        .unwrap_or_default();
+    format!("{:x}-{:x}", metadata.len(), modified)
+}
+
 pub struct LocalMultiPartUpload {
     pub lpath: PathBuf,
     pub rpath: PathBuf,
@@ -78,22 +97,30 @@ impl StorageClient for LocalStorageClient {
     }
 
     #[instrument(skip_all)]
-    async fn get_object(&self, lpath: &str, rpath: &str) -> Result<(), StorageError> {
+    async fn get_object_range(
+        &self,
+        rpath: &str,
+        offset: u64,
+    ) -> Result<ObjectStream, StorageError> {
         let src_path = self.bucket.join(rpath);
-        let dest_path = Path::new(lpath);
 
         if !src_path.exists() {
             error!("Source path does not exist: {}", src_path.display());
             return Err(LocalError::PathNotExistError(src_path.display().to_string()).into());
         }
 
-        if let Some(parent) = dest_path.parent() {
-            fs::create_dir_all(parent)?;
+        let mut file = tokio::fs::File::open(&src_path).await?;
+        if offset > 0 {
+            file.seek(SeekFrom::Start(offset)).await?;
         }
 
-        fs::copy(&src_path, dest_path)?;
+        Ok(ReaderStream::new(file).map_err(StorageError::from).boxed())
+    }
 
-        Ok(())
+    async fn get_object_version(&self, rpath: &str) -> Result<String, StorageError> {
+        let src_path = self.bucket.join(rpath);
+        let metadata = tokio::fs::metadata(&src_path).await?;
+        Ok(file_version(&metadata))
     }
 
     #[instrument(skip_all)]
@@ -293,6 +320,7 @@ impl LocalStorageClient {
 #[derive(Clone)]
 pub struct LocalFSStorageClient {
     client: LocalStorageClient,
+    concurrency: usize,
 }
 
 #[async_trait]
@@ -305,7 +333,10 @@ impl FileSystem for LocalFSStorageClient {
     }
     async fn new(settings: &OpsmlStorageSettings) -> Self {
         let client = LocalStorageClient::new(settings).await.unwrap();
-        LocalFSStorageClient { client }
+        LocalFSStorageClient {
+            client,
+            concurrency: settings.transfer_concurrency,
+        }
     }
 
     fn storage_type(&self) -> StorageType {
@@ -323,39 +354,22 @@ impl FileSystem for LocalFSStorageClient {
         self.client.find_info(stripped_path.to_str().unwrap()).await
     }
 
-    async fn get(&self, lpath: &Path, rpath: &Path, recursive: bool) -> Result<(), StorageError> {
-        // strip the paths
-        let stripped_rpath = rpath.strip_path(self.client.bucket().await);
-        let stripped_lpath = lpath.strip_path(self.client.bucket().await);
-
-        if recursive {
-            // list all objects in the path
-            let objects = self.client.find(stripped_rpath.to_str().unwrap()).await?;
-
-            // iterate over each object and get it
-            for obj in objects {
-                let file_path = Path::new(obj.as_str());
-                let stripped_path = file_path.strip_path(self.client.bucket().await);
-                let relative_path = file_path.relative_path(&stripped_rpath)?;
-                let local_path = stripped_lpath.join(relative_path);
-
-                self.client
-                    .get_object(
-                        local_path.to_str().unwrap(),
-                        stripped_path.to_str().unwrap(),
-                    )
-                    .await?;
-            }
-        } else {
-            self.client
-                .get_object(
-                    stripped_lpath.to_str().unwrap(),
-                    stripped_rpath.to_str().unwrap(),
-                )
-                .await?;
-        }
-
-        Ok(())
+    async fn get_with_progress(
+        &self,
+        lpath: &Path,
+        rpath: &Path,
+        recursive: bool,
+        progress: &ProgressTracker,
+    ) -> Result<(), StorageError> {
+        download_objects(
+            &self.client,
+            lpath,
+            rpath,
+            recursive,
+            self.concurrency,
+            progress,
+        )
+        .await
     }
 
     async fn copy(&self, src: &Path, dest: &Path, recursive: bool) -> Result<(), StorageError> {
@@ -415,39 +429,50 @@ impl FileSystem for LocalFSStorageClient {
             .await
     }
 
-    async fn put(&self, lpath: &Path, rpath: &Path, recursive: bool) -> Result<(), StorageError> {
+    async fn put_with_progress(
+        &self,
+        lpath: &Path,
+        rpath: &Path,
+        recursive: bool,
+        progress: &ProgressTracker,
+    ) -> Result<(), StorageError> {
         let stripped_lpath = lpath.strip_path(self.client.bucket().await);
         let stripped_rpath = rpath.strip_path(self.client.bucket().await);
 
-        if recursive {
+        let uploads = if recursive {
             if !stripped_lpath.is_dir() {
                 return Err(StorageError::PathMustBeDirectoryError);
             }
 
-            let files: Vec<PathBuf> = get_files(&stripped_lpath)?;
-            for file in files {
-                let stripped_lpath_clone = stripped_lpath.clone();
-                let stripped_rpath_clone = stripped_rpath.clone();
+            let mut uploads = Vec::new();
+            for file in get_files(&stripped_lpath)? {
                 let stripped_file_path = file.strip_path(self.client.bucket().await);
+                let relative_path = file.relative_path(&stripped_lpath)?;
+                uploads.push((stripped_file_path, stripped_rpath.join(relative_path)));
+            }
+            uploads
+        } else {
+            vec![(stripped_lpath, stripped_rpath)]
+        };
 
-                let relative_path = file.relative_path(&stripped_lpath_clone)?;
-                let remote_path = stripped_rpath_clone.join(relative_path);
+        let sizes = upload_sizes(&uploads)?;
+        progress.add_files(uploads.len() as u64, sizes.iter().sum());
 
+        run_concurrent(
+            uploads.into_iter().zip(sizes).collect(),
+            self.concurrency,
+            |((local_path, remote_path), size)| async move {
                 let uploader = self
-                    .create_multipart_uploader(&stripped_file_path, &remote_path)
+                    .create_multipart_uploader(&local_path, &remote_path)
                     .await?;
 
                 uploader.upload_file_in_chunks().await?;
-            }
-        } else {
-            let uploader = self
-                .create_multipart_uploader(&stripped_lpath, &stripped_rpath)
-                .await?;
-
-            uploader.upload_file_in_chunks().await?;
-        };
-
-        Ok(())
+                progress.add_bytes(size);
+                progress.file_completed();
+                Ok::<(), StorageError>(())
+            },
+        )
+        .await
     }
 
     async fn complete_multipart_upload(
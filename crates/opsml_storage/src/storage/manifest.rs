@@ -0,0 +1,314 @@
+use crate::storage::error::StorageError;
+use opsml_types::{SaveName, Suffix};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+use walkdir::WalkDir;
+
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Digest of a single card artifact
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileDigest {
+    pub sha256: String,
+    pub size: u64,
+}
+
+impl FileDigest {
+    pub fn from_file(path: &Path) -> Result<Self, StorageError> {
+        let mut file = File::open(path)?;
+        let mut hasher = Sha256::new();
+        let size = std::io::copy(&mut file, &mut hasher)?;
+
+        Ok(Self {
+            sha256: hex::encode(hasher.finalize()),
+            size,
+        })
+    }
+}
+
+/// A problem found when checking a directory against its manifest
+#[derive(Debug, Clone, PartialEq)]
+pub enum ManifestIssue {
+    Missing(String),
+    Mismatch {
+        path: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for ManifestIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ManifestIssue::Missing(path) => write!(f, "{path}: missing"),
+            ManifestIssue::Mismatch {
+                path,
+                expected,
+                actual,
+            } => write!(f, "{path}: expected sha256 {expected}, got {actual}"),
+        }
+    }
+}
+
+impl From<ManifestIssue> for StorageError {
+    fn from(issue: ManifestIssue) -> Self {
+        match issue {
+            ManifestIssue::Missing(path) => StorageError::ManifestFileMissingError(path),
+            ManifestIssue::Mismatch {
+                path,
+                expected,
+                actual,
+            } => StorageError::ChecksumMismatchError {
+                path,
+                expected,
+                actual,
+            },
+        }
+    }
+}
+
+/// SHA-256 digests of every file saved with a card, keyed by path relative to the card directory.
+///
+/// The manifest is written to `manifest.json` next to the card artifacts before they are
+/// encrypted and uploaded, and checked against the plaintext files after download and decryption.
+/// Files present on disk but absent from the manifest (e.g. experiment artifacts logged
+/// separately) are not checked.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArtifactManifest {
+    pub version: u32,
+    pub files: BTreeMap<String, FileDigest>,
+}
+
+impl Default for ArtifactManifest {
+    fn default() -> Self {
+        Self {
+            version: MANIFEST_VERSION,
+            files: BTreeMap::new(),
+        }
+    }
+}
+
+impl ArtifactManifest {
+    /// Path of the manifest within a card directory
+    pub fn path(dir: &Path) -> PathBuf {
+        dir.join(SaveName::Manifest).with_extension(Suffix::Json)
+    }
+
+    /// Hash every file under `dir`, excluding the manifest itself
+    pub fn from_dir(dir: &Path) -> Result<Self, StorageError> {
+        let manifest_path = Self::path(dir);
+        let mut manifest = Self::default();
+
+        for entry in WalkDir::new(dir) {
+            let entry = entry?;
+            if !entry.file_type().is_file() || entry.path() == manifest_path {
+                continue;
+            }
+
+            let relative = Self::relative_key(entry.path().strip_prefix(dir)?);
+            manifest
+                .files
+                .insert(relative, FileDigest::from_file(entry.path())?);
+        }
+
+        Ok(manifest)
+    }
+
+    /// Load the manifest from a card directory. Returns `None` for cards saved before manifests
+    /// were introduced
+    pub fn load(dir: &Path) -> Result<Option<Self>, StorageError> {
+        let path = Self::path(dir);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let json = std::fs::read_to_string(&path)?;
+        Ok(Some(serde_json::from_str(&json)?))
+    }
+
+    /// SHA-256 of the `manifest.json` in a card directory, or `None` if there is none.
+    /// Recorded with the card's artifact key so a missing or replaced manifest is detected
+    pub fn digest(dir: &Path) -> Result<Option<String>, StorageError> {
+        let path = Self::path(dir);
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        Ok(Some(FileDigest::from_file(&path)?.sha256))
+    }
+
+    /// Check the manifest in `dir` against the digest recorded when the card was uploaded
+    pub fn check_digest(dir: &Path, expected: &str) -> Result<Option<ManifestIssue>, StorageError> {
+        let name = Self::relative_key(&Self::path(Path::new("")));
+
+        Ok(match Self::digest(dir)? {
+            None => Some(ManifestIssue::Missing(name)),
+            Some(actual) if actual != expected => Some(ManifestIssue::Mismatch {
+                path: name,
+                expected: expected.to_string(),
+                actual,
+            }),
+            Some(_) => None,
+        })
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), StorageError> {
+        std::fs::write(Self::path(dir), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Replace the digests of files re-saved under `dir`, keeping entries for everything else.
+    /// Used when only part of a card (e.g. `card.json`) is rewritten
+    pub fn merge_dir(&mut self, dir: &Path) -> Result<(), StorageError> {
+        self.files.extend(Self::from_dir(dir)?.files);
+        Ok(())
+    }
+
+    /// Check every file in the manifest against the files under `dir`
+    pub fn check(&self, dir: &Path) -> Result<Vec<ManifestIssue>, StorageError> {
+        let mut issues = Vec::new();
+
+        for (relative, expected) in &self.files {
+            let path = dir.join(relative);
+            if !path.is_file() {
+                issues.push(ManifestIssue::Missing(relative.clone()));
+                continue;
+            }
+
+            let actual = FileDigest::from_file(&path)?;
+            if actual.sha256 != expected.sha256 {
+                issues.push(ManifestIssue::Mismatch {
+                    path: relative.clone(),
+                    expected: expected.sha256.clone(),
+                    actual: actual.sha256,
+                });
+            }
+        }
+
+        Ok(issues)
+    }
+
+    fn relative_key(path: &Path) -> String {
+        path.components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+/// Write a manifest for every file in a freshly saved card directory
+pub fn write_manifest(dir: &Path) -> Result<ArtifactManifest, StorageError> {
+    let manifest = ArtifactManifest::from_dir(dir)?;
+    manifest.save(dir)?;
+    debug!("Wrote artifact manifest for {} files", manifest.files.len());
+    Ok(manifest)
+}
+
+/// Verify a downloaded and decrypted card directory against its manifest.
+///
+/// `expected` is the manifest digest recorded when the card was uploaded. If set, a missing or
+/// altered manifest is an error. Cards recorded without one are skipped when they have no manifest.
+///
+/// Returns the number of files verified, or `None` when the card has no manifest.
+/// The first missing or corrupted file is returned as an error
+pub fn verify_manifest(dir: &Path, expected: Option<&str>) -> Result<Option<usize>, StorageError> {
+    if let Some(expected) = expected {
+        match ArtifactManifest::check_digest(dir, expected)? {
+            Some(ManifestIssue::Missing(path)) => {
+                return Err(StorageError::ManifestMissingError(path));
+            }
+            Some(issue) => return Err(issue.into()),
+            None => {}
+        }
+    }
+
+    let Some(manifest) = ArtifactManifest::load(dir)? else {
+        debug!(
+            "No artifact manifest found in {:?}, skipping verification",
+            dir
+        );
+        return Ok(None);
+    };
+
+    if let Some(issue) = manifest.check(dir)?.into_iter().next() {
+        return Err(issue.into());
+    }
+
+    Ok(Some(manifest.files.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_manifest_roundtrip_and_verify() {
+        let tmp_dir = TempDir::new().unwrap();
+        let dir = tmp_dir.path();
+
+        std::fs::write(dir.join("card.json"), "{}").unwrap();
+        std::fs::create_dir_all(dir.join("model")).unwrap();
+        std::fs::write(dir.join("model/model.joblib"), b"weights").unwrap();
+
+        let manifest = write_manifest(dir).unwrap();
+        assert_eq!(manifest.files.len(), 2);
+        assert!(manifest.files.contains_key("model/model.joblib"));
+        assert_eq!(verify_manifest(dir, None).unwrap(), Some(2));
+
+        let digest = ArtifactManifest::digest(dir).unwrap().unwrap();
+        assert_eq!(verify_manifest(dir, Some(&digest)).unwrap(), Some(2));
+
+        // corrupt a file
+        std::fs::write(dir.join("model/model.joblib"), b"weighs").unwrap();
+        let err = verify_manifest(dir, Some(&digest)).unwrap_err();
+        assert!(matches!(err, StorageError::ChecksumMismatchError { .. }));
+
+        // remove a file
+        std::fs::remove_file(dir.join("model/model.joblib")).unwrap();
+        let issues = manifest.check(dir).unwrap();
+        assert_eq!(
+            issues,
+            vec![ManifestIssue::Missing("model/model.joblib".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_manifest_missing_is_skipped() {
+        let tmp_dir = TempDir::new().unwrap();
+        std::fs::write(tmp_dir.path().join("card.json"), "{}").unwrap();
+
+        assert_eq!(verify_manifest(tmp_dir.path(), None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_manifest_recorded_digest() {
+        let tmp_dir = TempDir::new().unwrap();
+        let dir = tmp_dir.path();
+
+        std::fs::write(dir.join("card.json"), "{}").unwrap();
+        let digest = {
+            write_manifest(dir).unwrap();
+            ArtifactManifest::digest(dir).unwrap().unwrap()
+        };
+
+        // a manifest replaced along with the files it lists no longer matches
+        std::fs::write(dir.join("card.json"), "{\"uid\": 1}").unwrap();
+        write_manifest(dir).unwrap();
+        let err = verify_manifest(dir, Some(&digest)).unwrap_err();
+        assert!(matches!(
+            err,
+            StorageError::ChecksumMismatchError { ref path, .. } if path == "manifest.json"
+        ));
+
+        // a card recorded with a manifest fails when the manifest is gone
+        std::fs::remove_file(ArtifactManifest::path(dir)).unwrap();
+        let err = verify_manifest(dir, Some(&digest)).unwrap_err();
+        assert!(matches!(err, StorageError::ManifestMissingError(_)));
+    }
+}
@@ -12,4 +12,6 @@ pub mod error;
 pub mod filesystem;
 pub mod http;
 pub mod local;
+pub mod manifest;
+pub mod transfer;
 pub mod utils;
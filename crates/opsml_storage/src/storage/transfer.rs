@@ -0,0 +1,477 @@
+use crate::storage::base::{PathExt, StorageClient};
+use crate::storage::error::StorageError;
+use bytes::Bytes;
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use std::fs::{File, OpenOptions};
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::{debug, warn};
+
+pub use opsml_settings::config::DEFAULT_TRANSFER_CONCURRENCY;
+
+/// Suffix appended to a local file while it is being downloaded
+pub const PARTIAL_DOWNLOAD_SUFFIX: &str = "part";
+
+/// Suffix appended to the part file name for the version of the object it was downloaded from
+const PARTIAL_VERSION_SUFFIX: &str = "version";
+
+/// Minimum number of bytes between two progress reports for the same transfer
+const PROGRESS_REPORT_BYTES: u64 = 1024 * 1024;
+
+/// Stream of object bytes returned by a ranged read
+pub type ObjectStream = BoxStream<'static, Result<Bytes, StorageError>>;
+
+/// Snapshot of a running transfer, passed to progress callbacks.
+///
+/// `total_bytes` is 0 when the backend does not report object sizes up front
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransferProgress {
+    pub bytes_transferred: u64,
+    pub total_bytes: u64,
+    pub files_completed: u64,
+    pub total_files: u64,
+}
+
+pub type ProgressCallback = Arc<dyn Fn(&TransferProgress) + Send + Sync>;
+
+struct ProgressState {
+    callback: ProgressCallback,
+    bytes_transferred: AtomicU64,
+    total_bytes: AtomicU64,
+    files_completed: AtomicU64,
+    total_files: AtomicU64,
+    last_reported: AtomicU64,
+}
+
+/// Shared, cheaply cloneable progress counter for a single get/put call.
+///
+/// The default tracker has no callback and ignores all updates
+#[derive(Clone, Default)]
+pub struct ProgressTracker {
+    state: Option<Arc<ProgressState>>,
+}
+
+impl ProgressTracker {
+    pub fn new(callback: ProgressCallback) -> Self {
+        Self {
+            state: Some(Arc::new(ProgressState {
+                callback,
+                bytes_transferred: AtomicU64::new(0),
+                total_bytes: AtomicU64::new(0),
+                files_completed: AtomicU64::new(0),
+                total_files: AtomicU64::new(0),
+                last_reported: AtomicU64::new(0),
+            })),
+        }
+    }
+
+    /// Register files that will be transferred
+    pub fn add_files(&self, files: u64, bytes: u64) {
+        if let Some(state) = &self.state {
+            state.total_files.fetch_add(files, Ordering::Relaxed);
+            state.total_bytes.fetch_add(bytes, Ordering::Relaxed);
+            self.report();
+        }
+    }
+
+    pub fn add_bytes(&self, bytes: u64) {
+        if let Some(state) = &self.state {
+            let transferred = state.bytes_transferred.fetch_add(bytes, Ordering::Relaxed) + bytes;
+            let last = state.last_reported.load(Ordering::Relaxed);
+
+            if transferred.saturating_sub(last) >= PROGRESS_REPORT_BYTES {
+                state.last_reported.store(transferred, Ordering::Relaxed);
+                self.report();
+            }
+        }
+    }
+
+    /// Discount bytes that have to be transferred again (e.g. a resumed download that restarted)
+    pub fn sub_bytes(&self, bytes: u64) {
+        if let Some(state) = &self.state {
+            let _ =
+                state
+                    .bytes_transferred
+                    .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |b| {
+                        Some(b.saturating_sub(bytes))
+                    });
+            state.last_reported.store(0, Ordering::Relaxed);
+        }
+    }
+
+    pub fn file_completed(&self) {
+        if let Some(state) = &self.state {
+            state.files_completed.fetch_add(1, Ordering::Relaxed);
+            self.report();
+        }
+    }
+
+    pub fn snapshot(&self) -> TransferProgress {
+        match &self.state {
+            Some(state) => TransferProgress {
+                bytes_transferred: state.bytes_transferred.load(Ordering::Relaxed),
+                total_bytes: state.total_bytes.load(Ordering::Relaxed),
+                files_completed: state.files_completed.load(Ordering::Relaxed),
+                total_files: state.total_files.load(Ordering::Relaxed),
+            },
+            None => TransferProgress::default(),
+        }
+    }
+
+    fn report(&self) {
+        if let Some(state) = &self.state {
+            (state.callback)(&self.snapshot());
+        }
+    }
+}
+
+/// Progress callback that redraws a single status line on stderr
+pub fn console_progress(label: &str) -> ProgressCallback {
+    let label = label.to_string();
+    Arc::new(move |progress: &TransferProgress| {
+        let mb = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+        let bytes = if progress.total_bytes > 0 {
+            format!(
+                "{:.1}/{:.1} MB",
+                mb(progress.bytes_transferred),
+                mb(progress.total_bytes)
+            )
+        } else {
+            format!("{:.1} MB", mb(progress.bytes_transferred))
+        };
+
+        let mut stderr = std::io::stderr();
+        let _ = write!(
+            stderr,
+            "\r{label}: {}/{} files, {bytes}",
+            progress.files_completed, progress.total_files
+        );
+
+        if progress.total_files > 0 && progress.files_completed == progress.total_files {
+            let _ = writeln!(stderr);
+        }
+        let _ = stderr.flush();
+    })
+}
+
+/// A local file being downloaded.
+///
+/// Bytes are appended to `<path>.part`, which is only renamed to `path` once the download
+/// completes. The version of the object (ETag, generation or modification time) is kept in
+/// `<path>.part.version`. If a previous attempt was interrupted the existing part file is only
+/// resumed from its length once [`validate`](Self::validate) confirms the object is unchanged
+pub struct PartialDownload {
+    path: PathBuf,
+    part_path: PathBuf,
+    file: File,
+    offset: u64,
+    version: Option<String>,
+    progress: ProgressTracker,
+}
+
+impl PartialDownload {
+    pub fn open(path: &Path, progress: ProgressTracker) -> Result<Self, StorageError> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let part_path = Self::part_path(path);
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&part_path)?;
+        let offset = file.metadata()?.len();
+        let version = std::fs::read_to_string(Self::version_path(path)).ok();
+
+        if offset > 0 {
+            debug!("Resuming download of {:?} at byte {}", path, offset);
+            progress.add_bytes(offset);
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            part_path,
+            file,
+            offset,
+            version,
+            progress,
+        })
+    }
+
+    pub fn part_path(path: &Path) -> PathBuf {
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(".");
+        name.push(PARTIAL_DOWNLOAD_SUFFIX);
+        path.with_file_name(name)
+    }
+
+    pub fn version_path(path: &Path) -> PathBuf {
+        let mut name = Self::part_path(path).into_os_string();
+        name.push(".");
+        name.push(PARTIAL_VERSION_SUFFIX);
+        PathBuf::from(name)
+    }
+
+    /// Version of the object the bytes written so far belong to, if known
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Keep the bytes written so far only if they were downloaded from `version` of the object
+    /// and record `version` for the next attempt. An unknown version never matches, so the part
+    /// file is discarded rather than stitched onto a different object.
+    ///
+    /// Returns false if the download had to start over
+    pub fn validate(&mut self, version: Option<&str>) -> Result<bool, StorageError> {
+        let unchanged = self.offset == 0 || (version.is_some() && self.version() == version);
+        if !unchanged {
+            debug!(
+                "{:?} changed since the part file was written, restarting download",
+                self.path
+            );
+            self.restart()?;
+        }
+
+        if self.version() != version {
+            let version_path = Self::version_path(&self.path);
+            match version {
+                Some(version) => std::fs::write(&version_path, version)?,
+                None if version_path.exists() => std::fs::remove_file(&version_path)?,
+                None => {}
+            }
+            self.version = version.map(str::to_string);
+        }
+
+        Ok(unchanged)
+    }
+
+    /// Number of bytes already written locally
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Discard anything written so far and start again from the first byte
+    pub fn restart(&mut self) -> Result<(), StorageError> {
+        self.file.set_len(0)?;
+        self.progress.sub_bytes(self.offset);
+        self.offset = 0;
+        Ok(())
+    }
+
+    pub fn write(&mut self, chunk: &[u8]) -> Result<(), StorageError> {
+        self.file.write_all(chunk)?;
+        self.offset += chunk.len() as u64;
+        self.progress.add_bytes(chunk.len() as u64);
+        Ok(())
+    }
+
+    pub async fn write_stream(&mut self, mut stream: ObjectStream) -> Result<(), StorageError> {
+        while let Some(chunk) = stream.next().await {
+            self.write(&chunk?)?;
+        }
+        Ok(())
+    }
+
+    /// Move the completed part file into place
+    pub fn finish(self) -> Result<(), StorageError> {
+        self.file.sync_all()?;
+        drop(self.file);
+        std::fs::rename(&self.part_path, &self.path)?;
+        if self.version.is_some() {
+            std::fs::remove_file(Self::version_path(&self.path))?;
+        }
+        self.progress.file_completed();
+        Ok(())
+    }
+}
+
+/// Run `f` over `items` with at most `concurrency` futures in flight, stopping at the first error
+pub async fn run_concurrent<T, F, Fut>(
+    items: Vec<T>,
+    concurrency: usize,
+    f: F,
+) -> Result<(), StorageError>
+where
+    F: Fn(T) -> Fut,
+    Fut: Future<Output = Result<(), StorageError>>,
+{
+    stream::iter(items.into_iter().map(Ok))
+        .try_for_each_concurrent(concurrency.max(1), f)
+        .await
+}
+
+/// Sizes of the local files in a batch of `(local, remote)` uploads, used to seed progress totals
+pub fn upload_sizes(uploads: &[(PathBuf, PathBuf)]) -> Result<Vec<u64>, StorageError> {
+    uploads
+        .iter()
+        .map(|(local_path, _)| Ok(std::fs::metadata(local_path)?.len()))
+        .collect()
+}
+
+/// Download a single object, resuming from a partially written local file if one exists and the
+/// object has not changed since it was written
+pub async fn download_object<C: StorageClient>(
+    client: &C,
+    lpath: &Path,
+    rpath: &str,
+    progress: &ProgressTracker,
+) -> Result<(), StorageError> {
+    let mut part = PartialDownload::open(lpath, progress.clone())?;
+    let version = client.get_object_version(rpath).await?;
+    part.validate(Some(&version))?;
+
+    let stream = match client.get_object_range(rpath, part.offset()).await {
+        Ok(stream) => stream,
+        // the part file may be complete already, so start over
+        Err(e) if part.offset() > 0 => {
+            warn!(
+                "Failed to resume download of {} at byte {}, restarting: {}",
+                rpath,
+                part.offset(),
+                e
+            );
+            part.restart()?;
+            client.get_object_range(rpath, 0).await?
+        }
+        Err(e) => return Err(e),
+    };
+
+    part.write_stream(stream).await?;
+    part.finish()
+}
+
+/// Download one object or, when `recursive`, every object under `rpath` with bounded concurrency
+pub async fn download_objects<C: StorageClient>(
+    client: &C,
+    lpath: &Path,
+    rpath: &Path,
+    recursive: bool,
+    concurrency: usize,
+    progress: &ProgressTracker,
+) -> Result<(), StorageError> {
+    let bucket = client.bucket().await;
+    let stripped_rpath = rpath.strip_path(bucket);
+    let stripped_lpath = lpath.strip_path(bucket);
+
+    if !recursive {
+        progress.add_files(1, 0);
+        return download_object(
+            client,
+            &stripped_lpath,
+            stripped_rpath.to_str().unwrap(),
+            progress,
+        )
+        .await;
+    }
+
+    // list all objects in the path
+    let objects = client.find(stripped_rpath.to_str().unwrap()).await?;
+    progress.add_files(objects.len() as u64, 0);
+
+    let mut downloads = Vec::with_capacity(objects.len());
+    for obj in objects {
+        let file_path = Path::new(obj.as_str());
+        let stripped_path = file_path.strip_path(bucket);
+        let relative_path = file_path.relative_path(&stripped_rpath)?;
+        downloads.push((stripped_lpath.join(relative_path), stripped_path));
+    }
+
+    run_concurrent(
+        downloads,
+        concurrency,
+        |(local_path, remote_path)| async move {
+            download_object(client, &local_path, remote_path.to_str().unwrap(), progress).await
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_partial_download_resumes_and_reports_progress() {
+        let tmp_dir = TempDir::new().unwrap();
+        let path = tmp_dir.path().join("nested/model.bin");
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let captured = reports.clone();
+        let progress = ProgressTracker::new(Arc::new(move |p: &TransferProgress| {
+            captured.lock().unwrap().push(*p);
+        }));
+        progress.add_files(1, 6);
+
+        // first attempt is interrupted after 3 bytes
+        let mut part = PartialDownload::open(&path, progress.clone()).unwrap();
+        assert_eq!(part.offset(), 0);
+        assert!(part.validate(Some("v1")).unwrap());
+        part.write(b"abc").unwrap();
+        drop(part);
+        assert!(!path.exists());
+
+        // second attempt picks up where the first left off
+        let mut part = PartialDownload::open(&path, ProgressTracker::default()).unwrap();
+        assert_eq!(part.offset(), 3);
+        assert_eq!(part.version(), Some("v1"));
+        assert!(part.validate(Some("v1")).unwrap());
+        part.write(b"def").unwrap();
+        part.finish().unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), b"abcdef");
+        assert!(!PartialDownload::part_path(&path).exists());
+        assert!(!PartialDownload::version_path(&path).exists());
+
+        // restarting discards the partial bytes
+        std::fs::write(PartialDownload::part_path(&path), b"stale").unwrap();
+        let mut part = PartialDownload::open(&path, progress.clone()).unwrap();
+        part.restart().unwrap();
+        assert_eq!(part.offset(), 0);
+        part.write(b"fresh").unwrap();
+        part.finish().unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"fresh");
+
+        let last = *reports.lock().unwrap().last().unwrap();
+        assert_eq!(last.files_completed, 1);
+        assert_eq!(last.total_files, 1);
+        assert_eq!(last.bytes_transferred, 8);
+    }
+
+    #[test]
+    fn test_partial_download_restarts_when_object_changed() {
+        let tmp_dir = TempDir::new().unwrap();
+        let path = tmp_dir.path().join("model.bin");
+
+        let mut part = PartialDownload::open(&path, ProgressTracker::default()).unwrap();
+        part.validate(Some("v1")).unwrap();
+        part.write(b"old").unwrap();
+        drop(part);
+
+        // the object was replaced, so the old prefix is discarded
+        let mut part = PartialDownload::open(&path, ProgressTracker::default()).unwrap();
+        assert!(!part.validate(Some("v2")).unwrap());
+        assert_eq!(part.offset(), 0);
+        assert_eq!(
+            std::fs::read_to_string(PartialDownload::version_path(&path)).unwrap(),
+            "v2"
+        );
+        part.write(b"new").unwrap();
+        drop(part);
+
+        // a part file without a known version is never resumed
+        std::fs::remove_file(PartialDownload::version_path(&path)).unwrap();
+        let mut part = PartialDownload::open(&path, ProgressTracker::default()).unwrap();
+        assert!(!part.validate(Some("v2")).unwrap());
+        assert_eq!(part.offset(), 0);
+        part.write(b"fresh").unwrap();
+        part.finish().unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"fresh");
+    }
+}
@@ -0,0 +1,175 @@
+use crate::error::PyProjectTomlError;
+use opsml_types::RegistryType;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const LOCK_VERSION: u32 = 1;
+
+/// A skill, subagent or tool pinned to the exact version and content that was installed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LockedArtifact {
+    pub space: String,
+    pub name: String,
+    pub registry_type: RegistryType,
+    pub version: String,
+    pub uid: String,
+    /// Hex encoded SHA-256 of the artifact content as computed by the card's `calculate_content_hash`
+    pub content_hash: String,
+}
+
+/// Lockfile for agentic artifacts installed by `opsml sync` and the `skill`, `agent` and `tool`
+/// pull commands. Lives next to the skills.yaml it pins (`.opsml-skills.lock` or `~/.opsml/skills.lock`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AgentLockFile {
+    pub version: u32,
+    #[serde(default)]
+    pub artifacts: Vec<LockedArtifact>,
+}
+
+impl Default for AgentLockFile {
+    fn default() -> Self {
+        Self {
+            version: LOCK_VERSION,
+            artifacts: Vec::new(),
+        }
+    }
+}
+
+impl AgentLockFile {
+    /// Lockfile path for a skills.yaml, e.g. `.opsml-skills.yaml` -> `.opsml-skills.lock`
+    pub fn path_for(skills_yaml: &Path) -> std::path::PathBuf {
+        skills_yaml.with_extension("lock")
+    }
+
+    pub fn load(path: &Path) -> Result<Self, PyProjectTomlError> {
+        let content =
+            std::fs::read_to_string(path).map_err(PyProjectTomlError::FailedToReadAgentLock)?;
+        serde_yaml::from_str(&content).map_err(PyProjectTomlError::FailedToParseAgentLock)
+    }
+
+    /// Load the lockfile, or an empty one if it does not exist yet
+    pub fn load_or_default(path: &Path) -> Result<Self, PyProjectTomlError> {
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Write the lockfile atomically via a `.tmp` side-car and rename.
+    /// Artifacts are sorted so the file diffs cleanly
+    pub fn save(&mut self, path: &Path) -> Result<(), PyProjectTomlError> {
+        self.artifacts.sort_by(|a, b| {
+            (a.registry_type.to_string(), &a.space, &a.name).cmp(&(
+                b.registry_type.to_string(),
+                &b.space,
+                &b.name,
+            ))
+        });
+
+        let content =
+            serde_yaml::to_string(&self).map_err(PyProjectTomlError::FailedToSerializeAgentLock)?;
+
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent).map_err(PyProjectTomlError::FailedToWriteAgentLock)?;
+        }
+
+        let tmp = path.with_extension("lock.tmp");
+        if let Err(e) = std::fs::write(&tmp, content).and_then(|_| std::fs::rename(&tmp, path)) {
+            let _ = std::fs::remove_file(&tmp);
+            return Err(PyProjectTomlError::FailedToWriteAgentLock(e));
+        }
+        Ok(())
+    }
+
+    pub fn get(
+        &self,
+        registry_type: &RegistryType,
+        space: &str,
+        name: &str,
+    ) -> Option<&LockedArtifact> {
+        self.artifacts
+            .iter()
+            .find(|a| &a.registry_type == registry_type && a.space == space && a.name == name)
+    }
+
+    /// Insert or replace the entry for the artifact's registry type, space and name.
+    /// Returns true if the lockfile changed
+    pub fn upsert(&mut self, artifact: LockedArtifact) -> bool {
+        match self.artifacts.iter_mut().find(|a| {
+            a.registry_type == artifact.registry_type
+                && a.space == artifact.space
+                && a.name == artifact.name
+        }) {
+            Some(existing) if *existing == artifact => false,
+            Some(existing) => {
+                *existing = artifact;
+                true
+            }
+            None => {
+                self.artifacts.push(artifact);
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn locked(name: &str, registry_type: RegistryType, version: &str) -> LockedArtifact {
+        LockedArtifact {
+            space: "team".to_string(),
+            name: name.to_string(),
+            registry_type,
+            version: version.to_string(),
+            uid: format!("{name}-uid"),
+            content_hash: "ab".repeat(32),
+        }
+    }
+
+    #[test]
+    fn test_agent_lock_roundtrip() {
+        let dir = tempdir().unwrap();
+        let path = AgentLockFile::path_for(&dir.path().join(".opsml-skills.yaml"));
+        assert!(path.ends_with(".opsml-skills.lock"));
+
+        let mut lock = AgentLockFile::load_or_default(&path).unwrap();
+        assert!(lock.artifacts.is_empty());
+
+        assert!(lock.upsert(locked("reviewer", RegistryType::Tool, "1.0.0")));
+        assert!(lock.upsert(locked("reviewer", RegistryType::Skill, "1.0.0")));
+        lock.save(&path).unwrap();
+
+        let loaded = AgentLockFile::load(&path).unwrap();
+        assert_eq!(loaded.version, LOCK_VERSION);
+        assert_eq!(loaded.artifacts.len(), 2);
+        assert_eq!(
+            loaded
+                .get(&RegistryType::Skill, "team", "reviewer")
+                .unwrap()
+                .uid,
+            "reviewer-uid"
+        );
+        assert!(
+            loaded
+                .get(&RegistryType::SubAgent, "team", "reviewer")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_agent_lock_upsert() {
+        let mut lock = AgentLockFile::default();
+        assert!(lock.upsert(locked("reviewer", RegistryType::Skill, "1.0.0")));
+        assert!(!lock.upsert(locked("reviewer", RegistryType::Skill, "1.0.0")));
+        assert!(lock.upsert(locked("reviewer", RegistryType::Skill, "1.1.0")));
+
+        assert_eq!(lock.artifacts.len(), 1);
+        assert_eq!(lock.artifacts[0].version, "1.1.0");
+    }
+}
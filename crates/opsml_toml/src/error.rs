@@ -55,4 +55,16 @@ pub enum PyProjectTomlError {
         "Registry URL is required when creating a new skills.yaml — set OPSML_TRACKING_URI or run `opsml configure` first"
     )]
     RegistryRequired,
+
+    #[error("Failed to read agentic lockfile: {0}")]
+    FailedToReadAgentLock(#[source] std::io::Error),
+
+    #[error("Failed to parse agentic lockfile: {0}")]
+    FailedToParseAgentLock(#[source] serde_yaml::Error),
+
+    #[error("Failed to serialize agentic lockfile: {0}")]
+    FailedToSerializeAgentLock(#[source] serde_yaml::Error),
+
+    #[error("Failed to write agentic lockfile: {0}")]
+    FailedToWriteAgentLock(#[source] std::io::Error),
 }
@@ -1,7 +1,9 @@
+pub mod agent_lock;
 pub mod error;
 pub mod lock;
 pub mod skills_yaml;
 pub mod toml;
+pub use agent_lock::{AgentLockFile, LockedArtifact};
 pub use lock::{LockArtifact, LockFile};
 pub use skills_yaml::{ArtifactRef, OpsmlSkillsYaml};
 pub use toml::{OpsmlTool, OpsmlTools, PyProjectToml};
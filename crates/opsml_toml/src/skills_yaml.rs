@@ -25,6 +25,9 @@ pub struct OpsmlSkillsYaml {
     pub tools: Vec<ArtifactRef>,
     #[serde(default)]
     pub agents: Vec<ArtifactRef>,
+    /// MCP servers required by tracked skills, recorded when their dependencies are resolved
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mcp_servers: Vec<ArtifactRef>,
 }
 
 impl OpsmlSkillsYaml {
@@ -63,6 +66,18 @@ impl OpsmlSkillsYaml {
         path: &Path,
         skill: &ArtifactRef,
         registry: &str,
+    ) -> Result<(), PyProjectTomlError> {
+        Self::append_resolved(path, std::slice::from_ref(skill), &[], registry)
+    }
+
+    /// Append a resolved dependency set: skills (the requested skill first, then its
+    /// dependencies) and the MCP servers they require. Entries already present are left untouched.
+    /// Creates the file if missing, with the same `registry` rules as `append_skill`.
+    pub fn append_resolved(
+        path: &Path,
+        skills: &[ArtifactRef],
+        mcp_servers: &[ArtifactRef],
+        registry: &str,
     ) -> Result<(), PyProjectTomlError> {
         let mut yaml = if path.exists() {
             Self::load(path)?
@@ -76,19 +91,16 @@ impl OpsmlSkillsYaml {
                 skills: Vec::new(),
                 tools: Vec::new(),
                 agents: Vec::new(),
+                mcp_servers: Vec::new(),
             }
         };
 
-        if yaml
-            .skills
-            .iter()
-            .any(|s| s.space == skill.space && s.name == skill.name)
-        {
+        let added_skills = append_missing(&mut yaml.skills, skills);
+        let added_servers = append_missing(&mut yaml.mcp_servers, mcp_servers);
+        if path.exists() && !added_skills && !added_servers {
             return Ok(());
         }
 
-        yaml.skills.push(skill.clone());
-
         let content = serde_yaml::to_string(&yaml)
             .map_err(PyProjectTomlError::FailedToSerializeSkillsYaml)?;
         if let Some(parent) = path.parent() {
@@ -111,6 +123,22 @@ impl OpsmlSkillsYaml {
     }
 }
 
+/// Push every entry whose space + name is not yet tracked. Returns true if anything was added
+fn append_missing(existing: &mut Vec<ArtifactRef>, entries: &[ArtifactRef]) -> bool {
+    let mut added = false;
+    for entry in entries {
+        if existing
+            .iter()
+            .any(|e| e.space == entry.space && e.name == entry.name)
+        {
+            continue;
+        }
+        existing.push(entry.clone());
+        added = true;
+    }
+    added
+}
+
 /// Write `content` to `path` atomically via a `.tmp` side-car and rename.
 /// The `.tmp` file is cleaned up on any error.
 fn write_atomic_yaml(path: &Path, content: &str) -> Result<(), PyProjectTomlError> {
@@ -214,6 +242,38 @@ mod tests {
         assert_eq!(yaml.skills.len(), 1);
     }
 
+    #[test]
+    fn test_append_resolved_records_dependencies() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("skills.yaml");
+        let skill = |name: &str, version: Option<&str>| ArtifactRef {
+            space: "team".into(),
+            name: name.into(),
+            version: version.map(String::from),
+        };
+
+        // the requested skill is already tracked, so only its dependencies are added
+        OpsmlSkillsYaml::append_skill(&path, &skill("my-skill", None), "http://example.com")
+            .unwrap();
+        OpsmlSkillsYaml::append_resolved(
+            &path,
+            &[
+                skill("my-skill", Some("2.0.0")),
+                skill("helper", Some("1.2.0")),
+            ],
+            &[skill("search-mcp", Some("0.3.0"))],
+            "http://example.com",
+        )
+        .unwrap();
+
+        let yaml = OpsmlSkillsYaml::load(&path).unwrap();
+        assert_eq!(yaml.skills.len(), 2);
+        assert_eq!(yaml.skills[0].version, None);
+        assert_eq!(yaml.skills[1].name, "helper");
+        assert_eq!(yaml.mcp_servers.len(), 1);
+        assert_eq!(yaml.mcp_servers[0].version.as_deref(), Some("0.3.0"));
+    }
+
     #[test]
     fn test_remove_skill() {
         let dir = tempdir().unwrap();
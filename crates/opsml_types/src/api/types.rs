@@ -13,6 +13,7 @@ pub enum RequestType {
 #[derive(Debug, Clone)]
 pub enum Routes {
     ArtifactKey,
+    ArtifactManifest,
     ArtifactRecord,
     AuthLogin,
     AuthRefresh,
@@ -28,6 +29,8 @@ pub enum Routes {
     CardVersion,
     CardUpdate,
     CardCompareHash,
+    CardLineage,
+    CardSearch,
 
     CardMetadata,
     CardSpaces,
@@ -65,6 +68,9 @@ pub enum Routes {
     ScouterHealthcheck,
     AgentMcpServers,
     User,
+    AdminRotateKeys,
+    AdminStorageScan,
+    WorkflowRun,
 }
 
 impl Routes {
@@ -79,6 +85,7 @@ impl Routes {
             Routes::List => "files/list",
             Routes::ListInfo => "files/list/info",
             Routes::ArtifactKey => "files/key",
+            Routes::ArtifactManifest => "files/key/manifest",
             Routes::ArtifactRecord => "files/artifact",
             Routes::Healthcheck => "healthcheck",
             Routes::StorageSettings => "storage/settings",
@@ -102,6 +109,8 @@ impl Routes {
             Routes::CardVersion => "card/version",
             Routes::CardUpdate => "card/update",
             Routes::CardCompareHash => "card/compare_hash",
+            Routes::CardLineage => "card/lineage",
+            Routes::CardSearch => "card/search",
             Routes::ExperimentMetrics => "experiment/metrics",
             Routes::ExperimentGroupedMetrics => "experiment/metrics/grouped",
             Routes::ExperimentMetricNames => "experiment/metrics/names",
@@ -134,6 +143,11 @@ impl Routes {
             Routes::AgentMcpServers => "agent/mcp/servers",
 
             Routes::User => "user",
+
+            // Admin Routes
+            Routes::AdminRotateKeys => "admin/keys/rotate",
+            Routes::AdminStorageScan => "admin/storage/scan",
+            Routes::WorkflowRun => "v1/workflow/run",
         }
     }
 }
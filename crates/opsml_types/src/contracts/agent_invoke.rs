@@ -1,9 +1,37 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// How an agent invocation is dispatched
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum InvocationMode {
+    /// Run the agent within the request and return the result
+    #[default]
+    Sync,
+    /// Return a pending job immediately and run the agent in the background
+    Async,
+}
+
+impl Display for InvocationMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvocationMode::Sync => write!(f, "sync"),
+            InvocationMode::Async => write!(f, "async"),
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct InvokeRequest {
     pub input: serde_json::Value,
+    #[serde(default)]
+    pub mode: InvocationMode,
+    /// Continue an existing session. A new session is started when omitted
+    #[serde(default)]
+    pub session_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -13,6 +41,44 @@ pub enum JobStatus {
     Running,
     Done,
     Failed,
+    Cancelled,
+    /// The agent paused to ask a question. Answer it by invoking again in the same session
+    NeedsInput,
+}
+
+impl JobStatus {
+    pub fn from_string(status: &str) -> Option<Self> {
+        match status {
+            "pending" => Some(JobStatus::Pending),
+            "running" => Some(JobStatus::Running),
+            "done" => Some(JobStatus::Done),
+            "failed" => Some(JobStatus::Failed),
+            "cancelled" => Some(JobStatus::Cancelled),
+            "needs_input" => Some(JobStatus::NeedsInput),
+            _ => None,
+        }
+    }
+
+    /// Finished jobs will not change status again
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            JobStatus::Done | JobStatus::Failed | JobStatus::Cancelled | JobStatus::NeedsInput
+        )
+    }
+}
+
+impl Display for JobStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JobStatus::Pending => write!(f, "pending"),
+            JobStatus::Running => write!(f, "running"),
+            JobStatus::Done => write!(f, "done"),
+            JobStatus::Failed => write!(f, "failed"),
+            JobStatus::Cancelled => write!(f, "cancelled"),
+            JobStatus::NeedsInput => write!(f, "needs_input"),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,5 +95,66 @@ pub struct InvokeResponse {
     pub job_id: String,
     pub status: JobStatus,
     pub result: Option<serde_json::Value>,
+    #[serde(default)]
+    pub error: Option<String>,
+    /// Session the invocation ran in. Pass it back to continue the conversation. Unset for
+    /// sync invocations without a session that finished without asking for input
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// Question the agent is waiting on when `status` is `NeedsInput`
+    #[serde(default)]
+    pub prompt: Option<String>,
     pub metadata: InvokeMetadata,
 }
+
+/// Persisted state of an agent invocation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AgentJobRecord {
+    pub job_id: String,
+    pub agent_id: String,
+    pub owner: String,
+    pub status: JobStatus,
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub duration_ms: Option<u64>,
+    pub session_id: Option<String>,
+    /// Question the agent asked when the job ended in `NeedsInput`
+    pub prompt: Option<String>,
+    /// Server instance running the job, so a restart only recovers its own jobs
+    pub instance_id: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_status_round_trip() {
+        for status in [
+            JobStatus::Pending,
+            JobStatus::Running,
+            JobStatus::Done,
+            JobStatus::Failed,
+            JobStatus::Cancelled,
+            JobStatus::NeedsInput,
+        ] {
+            assert_eq!(JobStatus::from_string(&status.to_string()), Some(status));
+        }
+        assert!(!JobStatus::Running.is_finished());
+        assert!(JobStatus::Cancelled.is_finished());
+        assert!(JobStatus::NeedsInput.is_finished());
+    }
+
+    #[test]
+    fn test_invoke_request_defaults_to_sync() {
+        let request: InvokeRequest = serde_json::from_str(r#"{"input": "hello"}"#).unwrap();
+        assert_eq!(request.mode, InvocationMode::Sync);
+        assert!(request.session_id.is_none());
+
+        let request: InvokeRequest =
+            serde_json::from_str(r#"{"input": "hello", "mode": "async"}"#).unwrap();
+        assert_eq!(request.mode, InvocationMode::Async);
+    }
+}
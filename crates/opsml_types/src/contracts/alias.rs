@@ -0,0 +1,103 @@
+use crate::RegistryType;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Prefix that marks a version string as a stage alias, e.g. `@production`
+pub const VERSION_ALIAS_PREFIX: char = '@';
+
+/// Returns the alias name if `version` is an alias reference (`@production` -> `production`)
+pub fn parse_version_alias(version: &str) -> Option<&str> {
+    version
+        .strip_prefix(VERSION_ALIAS_PREFIX)
+        .filter(|alias| !alias.is_empty())
+}
+
+/// Alias names are lowercase alphanumerics plus `-` and `_` so they can never be
+/// confused with a semver string
+pub fn is_valid_alias_name(alias: &str) -> bool {
+    !alias.is_empty()
+        && alias.len() <= 64
+        && alias
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+        && alias.chars().any(|c| c.is_ascii_lowercase())
+}
+
+/// A movable pointer from a named stage (e.g. "production") to a concrete card version
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CardAlias {
+    pub space: String,
+    pub name: String,
+    pub registry_type: RegistryType,
+    pub alias: String,
+    pub uid: String,
+    pub version: String,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SetCardAliasRequest {
+    pub space: String,
+    pub name: String,
+    pub registry_type: RegistryType,
+    pub alias: String,
+    /// Concrete version the alias should point to
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct DeleteCardAliasRequest {
+    pub space: String,
+    pub name: String,
+    pub registry_type: RegistryType,
+    pub alias: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CardAliasQuery {
+    pub space: String,
+    pub name: String,
+    pub registry_type: RegistryType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CardAliasResponse {
+    pub alias: CardAlias,
+    /// Version the alias pointed to before this change, if it already existed
+    pub previous_version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CardAliasListResponse {
+    pub aliases: Vec<CardAlias>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version_alias() {
+        assert_eq!(parse_version_alias("@production"), Some("production"));
+        assert_eq!(parse_version_alias("@"), None);
+        assert_eq!(parse_version_alias("1.0.0"), None);
+        assert_eq!(parse_version_alias("^1.0"), None);
+    }
+
+    #[test]
+    fn test_is_valid_alias_name() {
+        assert!(is_valid_alias_name("production"));
+        assert!(is_valid_alias_name("stage-2"));
+        assert!(!is_valid_alias_name("Production"));
+        assert!(!is_valid_alias_name("1.0.0"));
+        assert!(!is_valid_alias_name("123"));
+        assert!(!is_valid_alias_name(""));
+    }
+}
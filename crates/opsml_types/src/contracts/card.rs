@@ -1,7 +1,7 @@
 use crate::cards::CardStatus;
 use crate::contracts::{
     ArtifactKey, AuditableRequest, DeploymentConfig, ResourceType, ServiceConfig, ServiceMetadata,
-    ServiceType, SkillDependency,
+    ServiceType, SkillDependency, parse_version_alias,
 };
 use crate::error::TypeError;
 use crate::{
@@ -142,6 +142,39 @@ pub struct CrudSpaceResponse {
 pub struct SpaceRecord {
     pub space: String,
     pub description: String,
+    /// Uploads that take the space past this many bytes succeed with a warning
+    #[serde(default)]
+    pub soft_quota_bytes: Option<i64>,
+    /// Uploads that would take the space past this many bytes are rejected
+    #[serde(default)]
+    pub hard_quota_bytes: Option<i64>,
+}
+
+/// Set or clear the storage quotas of a space (admin only)
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SpaceQuotaRequest {
+    pub space: String,
+    #[serde(default)]
+    pub soft_quota_bytes: Option<i64>,
+    #[serde(default)]
+    pub hard_quota_bytes: Option<i64>,
+}
+
+/// Artifact bytes stored by one registry of a space
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RegistryUsage {
+    pub registry_type: RegistryType,
+    pub bytes: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SpaceRegistryUsage {
+    pub space: String,
+    pub registry_type: RegistryType,
+    pub bytes: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -165,6 +198,15 @@ pub struct SpaceStats {
     pub data_count: i64,
     pub prompt_count: i64,
     pub experiment_count: i64,
+    /// Artifact bytes stored across all registries
+    #[serde(default)]
+    pub storage_bytes: i64,
+    #[serde(default)]
+    pub usage: Vec<RegistryUsage>,
+    #[serde(default)]
+    pub soft_quota_bytes: Option<i64>,
+    #[serde(default)]
+    pub hard_quota_bytes: Option<i64>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -476,6 +518,13 @@ pub struct CardQueryArgs {
     pub registry_type: RegistryType,
 }
 
+impl CardQueryArgs {
+    /// Returns the stage alias if `version` is an alias reference such as `@production`
+    pub fn version_alias(&self) -> Option<&str> {
+        self.version.as_deref().and_then(parse_version_alias)
+    }
+}
+
 impl AuditableRequest for CardQueryArgs {
     fn get_resource_id(&self) -> String {
         self.uid.clone().unwrap_or_default()
@@ -0,0 +1,99 @@
+use crate::RegistryType;
+use crate::contracts::SpaceRegistryUsage;
+use serde::{Deserialize, Serialize};
+
+/// Scan registry storage and cross-reference it with artifact keys and card tables
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ConsistencyScanRequest {
+    /// Remove orphaned objects and artifact keys instead of only reporting them
+    #[serde(default)]
+    pub delete: bool,
+    /// Only scan this space
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub space: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ConsistencyIssueKind {
+    /// Objects under a storage prefix that no artifact key points to
+    OrphanedObjects,
+    /// An artifact key whose card is neither registered nor in the trash
+    OrphanedKey,
+    /// An artifact key without a storage prefix
+    EmptyStorageKey,
+    /// A registered card with no objects under its storage prefix
+    MissingArtifacts,
+    /// A registered card without an artifact key
+    MissingKey,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ConsistencyIssue {
+    pub kind: ConsistencyIssueKind,
+    pub space: String,
+    pub storage_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry_type: Option<RegistryType>,
+    pub objects: u64,
+    pub bytes: u64,
+    /// Whether the scan removed the orphaned objects or key
+    #[serde(default)]
+    pub deleted: bool,
+}
+
+/// Storage used by a space. Orphaned bytes are included in `bytes`
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SpaceStorageUsage {
+    pub space: String,
+    pub objects: u64,
+    pub bytes: u64,
+    pub orphaned_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ConsistencyReport {
+    /// True if issues were only reported
+    pub dry_run: bool,
+    pub keys_scanned: u64,
+    pub objects_scanned: u64,
+    pub issues: Vec<ConsistencyIssue>,
+    /// Storage totals per space, sorted by space
+    pub spaces: Vec<SpaceStorageUsage>,
+}
+
+impl ConsistencyReport {
+    pub fn count(&self, kind: ConsistencyIssueKind) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.kind == kind)
+            .count()
+    }
+
+    pub fn orphaned_bytes(&self) -> u64 {
+        self.spaces.iter().map(|usage| usage.orphaned_bytes).sum()
+    }
+}
+
+/// Recount the storage usage tracked for quotas from the objects in registry storage
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct UsageRecountRequest {
+    /// Only recount this space
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub space: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct UsageRecountResponse {
+    /// Usage per space and registry after the recount, sorted by space
+    pub usage: Vec<SpaceRegistryUsage>,
+}
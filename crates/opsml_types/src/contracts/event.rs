@@ -1,5 +1,7 @@
 use crate::RegistryType;
 use crate::contracts::{AuditStatus, Operation, ResourceType};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone)]
 pub struct AuditEvent {
@@ -42,3 +44,141 @@ pub struct SpaceNameEvent {
     pub name: String,
     pub registry_type: RegistryType,
 }
+
+/// Filters used to query persisted audit events
+///
+/// Events are returned newest first. `cursor` is the `id` of the last event from the previous
+/// page; only events with a smaller id are returned.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AuditQueryArgs {
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub operation: Option<String>,
+    #[serde(default)]
+    pub resource_type: Option<String>,
+    #[serde(default)]
+    pub resource_id: Option<String>,
+    #[serde(default)]
+    pub registry_type: Option<RegistryType>,
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub start_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub end_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub cursor: Option<i64>,
+    #[serde(default)]
+    pub limit: Option<i32>,
+}
+
+/// Audit event as read back from the database
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AuditEventRecord {
+    pub id: i64,
+    pub created_at: DateTime<Utc>,
+    pub username: String,
+    pub client_ip: String,
+    pub user_agent: Option<String>,
+    pub operation: String,
+    pub resource_type: String,
+    pub resource_id: String,
+    pub access_location: Option<String>,
+    pub status: String,
+    pub error_message: Option<String>,
+    pub metadata: Option<String>,
+    pub registry_type: Option<String>,
+    pub route: Option<String>,
+}
+
+impl AuditEventRecord {
+    pub const CSV_HEADER: &'static str = "id,created_at,username,client_ip,user_agent,operation,resource_type,resource_id,access_location,status,error_message,metadata,registry_type,route";
+
+    /// Render the record as a single RFC 4180 csv row (without trailing newline)
+    pub fn to_csv_row(&self) -> String {
+        let fields = [
+            self.id.to_string(),
+            self.created_at.to_rfc3339(),
+            self.username.clone(),
+            self.client_ip.clone(),
+            self.user_agent.clone().unwrap_or_default(),
+            self.operation.clone(),
+            self.resource_type.clone(),
+            self.resource_id.clone(),
+            self.access_location.clone().unwrap_or_default(),
+            self.status.clone(),
+            self.error_message.clone().unwrap_or_default(),
+            self.metadata.clone().unwrap_or_default(),
+            self.registry_type.clone().unwrap_or_default(),
+            self.route.clone().unwrap_or_default(),
+        ];
+
+        fields
+            .iter()
+            .map(|f| escape_csv_field(f))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AuditPageResponse {
+    pub events: Vec<AuditEventRecord>,
+    /// Cursor to pass to the next request. `None` when there are no more events
+    pub next_cursor: Option<i64>,
+}
+
+/// Supported formats for bulk audit exports
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum AuditExportFormat {
+    #[default]
+    Ndjson,
+    Csv,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_event_record_csv_row() {
+        let record = AuditEventRecord {
+            id: 1,
+            created_at: DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            username: "admin".to_string(),
+            client_ip: "127.0.0.1".to_string(),
+            user_agent: None,
+            operation: "Read".to_string(),
+            resource_type: "File".to_string(),
+            resource_id: "model/a,b".to_string(),
+            access_location: None,
+            status: "Success".to_string(),
+            error_message: None,
+            metadata: Some("{\"key\": \"value\"}".to_string()),
+            registry_type: Some("model".to_string()),
+            route: Some("/opsml/api/files".to_string()),
+        };
+
+        let row = record.to_csv_row();
+        assert_eq!(
+            row,
+            "1,2025-01-01T00:00:00+00:00,admin,127.0.0.1,,Read,File,\"model/a,b\",,Success,,\"{\"\"key\"\": \"\"value\"\"}\",model,/opsml/api/files"
+        );
+    }
+}
@@ -3,7 +3,7 @@ use crate::StorageType;
 use crate::contracts::ArtifactType;
 use crate::error::TypeError;
 use crate::interfaces::DriftProfileUri;
-use opsml_crypt::decrypt_key;
+use opsml_crypt::{KeyProvider, LEGACY_KEY_VERSION, decrypt_key, encrypted_key};
 #[cfg(feature = "python")]
 use opsml_utils::PyHelperFuncs;
 use opsml_utils::uid_to_byte_key;
@@ -18,6 +18,9 @@ use std::path::PathBuf;
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct MultiPartQuery {
     pub path: String,
+    /// Size of the file to upload, checked against the space's storage quota
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_size: Option<i64>,
 }
 
 #[derive(Serialize, Deserialize, Default)]
@@ -217,6 +220,15 @@ pub struct ArtifactKeyRequest {
     pub registry_type: RegistryType,
 }
 
+/// Digest of the `manifest.json` uploaded with a card's artifacts
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ArtifactManifestRequest {
+    pub uid: String,
+    pub registry_type: RegistryType,
+    pub manifest_sha256: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct ArtifactKey {
@@ -225,16 +237,63 @@ pub struct ArtifactKey {
     pub registry_type: RegistryType,
     pub encrypted_key: Vec<u8>,
     pub storage_key: String,
+    /// Version of the key-encryption-key `encrypted_key` is wrapped with.
+    /// `LEGACY_KEY_VERSION` means the key is wrapped with the card uid
+    #[serde(default)]
+    pub key_version: u32,
+    /// SHA-256 of the plaintext `manifest.json` uploaded with the card's artifacts.
+    /// `None` for cards uploaded before manifests were recorded
+    #[serde(default)]
+    pub manifest_sha256: Option<String>,
 }
 
 impl ArtifactKey {
     pub fn get_crypt_key(&self) -> Result<Vec<u8>, TypeError> {
+        if self.key_version != LEGACY_KEY_VERSION {
+            return Err(TypeError::WrappedArtifactKey(self.key_version));
+        }
+
         // convert uid to byte key (used for card encryption)
         let uid_key = uid_to_byte_key(&self.uid)?;
 
         Ok(decrypt_key(&uid_key, &self.encrypted_key)?)
     }
 
+    /// Wrap the data key with the provider's current KEK for storage
+    pub fn seal(&self, provider: &dyn KeyProvider) -> Result<ArtifactKey, TypeError> {
+        let data_key = self.data_key(provider)?;
+        let (encrypted_key, key_version) = provider.wrap_key(&data_key)?;
+
+        Ok(ArtifactKey {
+            encrypted_key,
+            key_version,
+            ..self.clone()
+        })
+    }
+
+    /// Convert a stored key into the uid-wrapped form used by `get_crypt_key` and sent to clients
+    pub fn open(&self, provider: &dyn KeyProvider) -> Result<ArtifactKey, TypeError> {
+        if self.key_version == LEGACY_KEY_VERSION {
+            return Ok(self.clone());
+        }
+
+        let data_key = self.data_key(provider)?;
+        let uid_key = uid_to_byte_key(&self.uid)?;
+
+        Ok(ArtifactKey {
+            encrypted_key: encrypted_key(&uid_key, &data_key)?,
+            key_version: LEGACY_KEY_VERSION,
+            ..self.clone()
+        })
+    }
+
+    fn data_key(&self, provider: &dyn KeyProvider) -> Result<Vec<u8>, TypeError> {
+        match self.key_version {
+            LEGACY_KEY_VERSION => self.get_crypt_key(),
+            version => Ok(provider.unwrap_key(&self.encrypted_key, version)?),
+        }
+    }
+
     pub fn storage_path(&self) -> PathBuf {
         PathBuf::from(&self.storage_key)
     }
@@ -251,6 +310,24 @@ impl Display for ArtifactKey {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RotateArtifactKeysRequest {
+    /// Number of keys re-wrapped per database round trip
+    #[serde(default)]
+    pub batch_size: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RotateArtifactKeysResponse {
+    /// KEK version every key is now wrapped with
+    pub key_version: u32,
+    pub rewrapped: u64,
+    /// Keys that changed while being re-wrapped and were left for the next rotation
+    pub skipped: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct CreateArtifactRequest {
@@ -0,0 +1,110 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Space-scoped role a group can hold. Roles are cumulative:
+/// editors can also read and owners can also write
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum SpaceRole {
+    #[default]
+    Viewer,
+    Editor,
+    Owner,
+}
+
+impl SpaceRole {
+    pub fn actions(&self) -> &'static [&'static str] {
+        match self {
+            SpaceRole::Viewer => &["read"],
+            SpaceRole::Editor => &["read", "write"],
+            SpaceRole::Owner => &["read", "write", "delete"],
+        }
+    }
+
+    /// Expands the role into `<action>:<space>` permission strings.
+    /// A space of `all` yields the global `read:all`-style permissions
+    pub fn permissions(&self, space: &str) -> Vec<String> {
+        self.actions()
+            .iter()
+            .map(|action| format!("{action}:{space}"))
+            .collect()
+    }
+}
+
+impl Display for SpaceRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpaceRole::Viewer => write!(f, "viewer"),
+            SpaceRole::Editor => write!(f, "editor"),
+            SpaceRole::Owner => write!(f, "owner"),
+        }
+    }
+}
+
+impl std::str::FromStr for SpaceRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "viewer" => Ok(SpaceRole::Viewer),
+            "editor" => Ok(SpaceRole::Editor),
+            "owner" => Ok(SpaceRole::Owner),
+            _ => Err(format!("Unknown SpaceRole: {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct GroupRecord {
+    pub name: String,
+    pub description: String,
+    /// Identity provider group names (e.g. Okta/Keycloak `groups` claim) mapped onto this group
+    pub sso_groups: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct GroupRole {
+    pub group_name: String,
+    pub space: String,
+    pub role: SpaceRole,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateGroupRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub sso_groups: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct SetGroupRoleRequest {
+    pub space: String,
+    pub role: SpaceRole,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AddGroupMemberRequest {
+    pub username: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct GroupResponse {
+    pub group: GroupRecord,
+    pub roles: Vec<GroupRole>,
+    pub members: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct GroupListResponse {
+    pub groups: Vec<GroupRecord>,
+}
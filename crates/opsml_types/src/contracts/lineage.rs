@@ -0,0 +1,235 @@
+use crate::RegistryType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt::Display;
+
+/// Default number of hops walked from the root card
+pub const DEFAULT_LINEAGE_DEPTH: usize = 2;
+
+/// Upper bound on traversal depth to keep lineage queries cheap
+pub const MAX_LINEAGE_DEPTH: usize = 10;
+
+fn default_lineage_depth() -> usize {
+    DEFAULT_LINEAGE_DEPTH
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum LineageDirection {
+    /// Cards the root was built from (e.g. the data a model was trained on)
+    Upstream,
+    /// Cards built from the root (e.g. services deploying a model)
+    Downstream,
+    #[default]
+    Both,
+}
+
+impl LineageDirection {
+    pub fn includes_upstream(&self) -> bool {
+        matches!(self, LineageDirection::Upstream | LineageDirection::Both)
+    }
+
+    pub fn includes_downstream(&self) -> bool {
+        matches!(self, LineageDirection::Downstream | LineageDirection::Both)
+    }
+}
+
+/// How two cards are related. Edges always point from the upstream card to the downstream card
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum LineageRelation {
+    /// data -> model
+    TrainedOn,
+    /// experiment -> card created during the experiment
+    ProducedBy,
+    /// card -> audit
+    AuditedBy,
+    /// card -> service, mcp or agent card that bundles it
+    DeployedIn,
+    /// experiment -> experiment
+    Linked,
+}
+
+impl LineageRelation {
+    pub fn from_string(relation: &str) -> Option<Self> {
+        match relation {
+            "trained_on" => Some(LineageRelation::TrainedOn),
+            "produced_by" => Some(LineageRelation::ProducedBy),
+            "audited_by" => Some(LineageRelation::AuditedBy),
+            "deployed_in" => Some(LineageRelation::DeployedIn),
+            "linked" => Some(LineageRelation::Linked),
+            _ => None,
+        }
+    }
+}
+
+impl Display for LineageRelation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LineageRelation::TrainedOn => write!(f, "trained_on"),
+            LineageRelation::ProducedBy => write!(f, "produced_by"),
+            LineageRelation::AuditedBy => write!(f, "audited_by"),
+            LineageRelation::DeployedIn => write!(f, "deployed_in"),
+            LineageRelation::Linked => write!(f, "linked"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct LineageQuery {
+    pub uid: String,
+    /// Registry of the root card. Looked up across all card registries when omitted
+    #[serde(default)]
+    pub registry_type: Option<RegistryType>,
+    /// Number of hops to walk from the root card. Capped at `MAX_LINEAGE_DEPTH`
+    #[serde(default = "default_lineage_depth")]
+    pub depth: usize,
+    #[serde(default)]
+    pub direction: LineageDirection,
+}
+
+impl LineageQuery {
+    pub fn new(uid: &str) -> Self {
+        Self {
+            uid: uid.to_string(),
+            registry_type: None,
+            depth: DEFAULT_LINEAGE_DEPTH,
+            direction: LineageDirection::default(),
+        }
+    }
+
+    pub fn effective_depth(&self) -> usize {
+        self.depth.min(MAX_LINEAGE_DEPTH)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct LineageNode {
+    pub uid: String,
+    pub registry_type: RegistryType,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+    /// Number of hops from the root card
+    pub depth: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct LineageEdge {
+    pub source_uid: String,
+    pub source_type: RegistryType,
+    pub target_uid: String,
+    pub target_type: RegistryType,
+    pub relation: LineageRelation,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct LineageGraph {
+    pub root: String,
+    pub nodes: Vec<LineageNode>,
+    pub edges: Vec<LineageEdge>,
+}
+
+impl LineageGraph {
+    /// Keeps only the nodes matching `keep`, along with the edges between them
+    pub fn retain_nodes<F>(&mut self, keep: F)
+    where
+        F: Fn(&LineageNode) -> bool,
+    {
+        self.nodes.retain(|node| keep(node));
+
+        let remaining = self
+            .nodes
+            .iter()
+            .map(|node| (node.uid.as_str(), &node.registry_type))
+            .collect::<HashSet<_>>();
+
+        self.edges.retain(|edge| {
+            remaining.contains(&(edge.source_uid.as_str(), &edge.source_type))
+                && remaining.contains(&(edge.target_uid.as_str(), &edge.target_type))
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lineage_query_defaults() {
+        let query: LineageQuery = serde_json::from_str(r#"{"uid": "abc"}"#).unwrap();
+        assert_eq!(query.depth, DEFAULT_LINEAGE_DEPTH);
+        assert_eq!(query.direction, LineageDirection::Both);
+
+        let query: LineageQuery =
+            serde_json::from_str(r#"{"uid": "abc", "depth": 100, "direction": "upstream"}"#)
+                .unwrap();
+        assert_eq!(query.effective_depth(), MAX_LINEAGE_DEPTH);
+        assert!(query.direction.includes_upstream());
+        assert!(!query.direction.includes_downstream());
+    }
+
+    #[test]
+    fn test_lineage_graph_retain_nodes() {
+        let node = |uid: &str, registry_type: RegistryType, space: &str| LineageNode {
+            uid: uid.to_string(),
+            registry_type,
+            space: space.to_string(),
+            name: "name".to_string(),
+            version: "1.0.0".to_string(),
+            depth: 0,
+        };
+
+        let mut graph = LineageGraph {
+            root: "data".to_string(),
+            nodes: vec![
+                node("data", RegistryType::Data, "public"),
+                node("model", RegistryType::Model, "public"),
+                node("service", RegistryType::Service, "private"),
+            ],
+            edges: vec![
+                LineageEdge {
+                    source_uid: "data".to_string(),
+                    source_type: RegistryType::Data,
+                    target_uid: "model".to_string(),
+                    target_type: RegistryType::Model,
+                    relation: LineageRelation::TrainedOn,
+                },
+                LineageEdge {
+                    source_uid: "model".to_string(),
+                    source_type: RegistryType::Model,
+                    target_uid: "service".to_string(),
+                    target_type: RegistryType::Service,
+                    relation: LineageRelation::DeployedIn,
+                },
+            ],
+        };
+
+        graph.retain_nodes(|node| node.space == "public");
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!(graph.edges[0].relation, LineageRelation::TrainedOn);
+    }
+
+    #[test]
+    fn test_lineage_relation_round_trip() {
+        for relation in [
+            LineageRelation::TrainedOn,
+            LineageRelation::ProducedBy,
+            LineageRelation::AuditedBy,
+            LineageRelation::DeployedIn,
+            LineageRelation::Linked,
+        ] {
+            assert_eq!(
+                LineageRelation::from_string(&relation.to_string()),
+                Some(relation)
+            );
+        }
+    }
+}
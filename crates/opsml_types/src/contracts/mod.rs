@@ -1,36 +1,50 @@
 pub mod agent;
 pub mod agent_invoke;
+pub mod alias;
 pub mod artifact;
 pub mod card;
+pub mod consistency;
 pub mod evaluation;
 pub mod event;
 pub mod experiment;
 pub mod file;
+pub mod group;
+pub mod lineage;
 pub mod mcp;
 pub mod potato;
 pub mod scouter;
+pub mod search;
 pub mod security;
 pub mod service;
 pub mod skill;
 pub mod subagent;
 pub mod tool;
 pub mod traits;
+pub mod trash;
+pub mod webhook;
 pub mod workflow;
 
 pub use agent::*;
 pub use agent_invoke::*;
+pub use alias::*;
 pub use artifact::*;
 pub use card::*;
+pub use consistency::*;
 pub use event::*;
 pub use experiment::*;
 pub use file::*;
+pub use group::*;
+pub use lineage::*;
 pub use mcp::*;
 pub use potato::*;
 pub use scouter::*;
+pub use search::*;
 pub use security::*;
 pub use service::*;
 pub use skill::*;
 pub use subagent::*;
 pub use tool::*;
 pub use traits::*;
+pub use trash::*;
+pub use webhook::*;
 pub use workflow::*;
@@ -0,0 +1,198 @@
+use crate::RegistryType;
+use crate::contracts::CardRecord;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+pub const DEFAULT_SEARCH_LIMIT: usize = 20;
+pub const MAX_SEARCH_LIMIT: usize = 100;
+
+/// Markers wrapped around matched terms in search highlights
+pub const HIGHLIGHT_START: &str = "<mark>";
+pub const HIGHLIGHT_END: &str = "</mark>";
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// HTML-escape a highlight while keeping its match markers. Highlights are excerpts of READMEs
+/// and skill bodies, which may contain markup of their own
+pub fn escape_highlight(highlight: &str) -> String {
+    escape_html(highlight)
+        .replace(&escape_html(HIGHLIGHT_START), HIGHLIGHT_START)
+        .replace(&escape_html(HIGHLIGHT_END), HIGHLIGHT_END)
+}
+
+/// Split a free-text query into lowercase alphanumeric terms.
+///
+/// Every backend builds its own full-text query from these terms, so user input never reaches
+/// the FTS5, tsquery or MySQL boolean-mode parsers directly.
+pub fn search_terms(query: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    query
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|term| !term.is_empty())
+        .map(str::to_lowercase)
+        .filter(|term| seen.insert(term.clone()))
+        .collect()
+}
+
+/// Full-text search over card names, spaces, tags, READMEs and skill/subagent/tool descriptions
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CardSearchQuery {
+    pub query: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry_type: Option<RegistryType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub space: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// Number of ranked card versions to skip when paging through matches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+}
+
+impl CardSearchQuery {
+    pub fn limit(&self) -> usize {
+        self.limit
+            .unwrap_or(DEFAULT_SEARCH_LIMIT)
+            .clamp(1, MAX_SEARCH_LIMIT)
+    }
+}
+
+/// The searchable text of a single card version
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CardSearchDocument {
+    pub uid: String,
+    pub registry_type: RegistryType,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+    /// `None` keeps the README already indexed for other versions of the card
+    pub readme: Option<String>,
+    pub body: Option<String>,
+}
+
+impl CardSearchDocument {
+    /// Build the document for a registered card. `uid` and `version` are passed separately
+    /// because they are assigned by the server after the client record is created
+    pub fn from_record(record: &CardRecord, uid: &str, version: &str) -> Self {
+        let (description, body) = match record {
+            CardRecord::Skill(card) => (card.description.clone(), card.body.clone()),
+            CardRecord::SubAgent(card) => (card.description.clone(), None),
+            CardRecord::Tool(card) => (card.description.clone(), None),
+            _ => (None, None),
+        };
+
+        Self {
+            uid: uid.to_string(),
+            registry_type: record.registry_type(),
+            space: record.space().to_string(),
+            name: record.name().to_string(),
+            version: version.to_string(),
+            tags: record.tags().clone(),
+            description,
+            readme: None,
+            body,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CardSearchHit {
+    pub uid: String,
+    pub registry_type: RegistryType,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+    /// Backend specific relevance score. Only comparable between hits of the same search
+    pub score: f64,
+    /// Excerpt around the best match with matched terms wrapped in `<mark>` tags
+    pub highlight: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CardSearchResponse {
+    pub hits: Vec<CardSearchHit>,
+}
+
+impl CardSearchResponse {
+    /// Build a response from ranked hits, keeping only the best matching version of each card
+    /// and at most `limit` cards
+    pub fn from_hits(hits: Vec<CardSearchHit>, limit: usize) -> Self {
+        let mut seen = HashSet::new();
+        let hits = hits
+            .into_iter()
+            .filter(|hit| {
+                seen.insert((
+                    hit.registry_type.clone(),
+                    hit.space.clone(),
+                    hit.name.clone(),
+                ))
+            })
+            .take(limit)
+            .collect();
+        Self { hits }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_terms() {
+        assert_eq!(
+            search_terms("Churn model -- \"v2\" OR churn_rate* churn"),
+            vec!["churn", "model", "v2", "or", "churn_rate"]
+        );
+        assert!(search_terms(" \"*()").is_empty());
+    }
+
+    #[test]
+    fn test_escape_highlight() {
+        assert_eq!(
+            escape_highlight("<img src=x onerror=\"alert(1)\"> predicts <mark>churn</mark> & more"),
+            "&lt;img src=x onerror=&quot;alert(1)&quot;&gt; predicts <mark>churn</mark> &amp; more"
+        );
+    }
+
+    #[test]
+    fn test_search_limit() {
+        let mut query = CardSearchQuery::default();
+        assert_eq!(query.limit(), DEFAULT_SEARCH_LIMIT);
+        query.limit = Some(10_000);
+        assert_eq!(query.limit(), MAX_SEARCH_LIMIT);
+    }
+
+    #[test]
+    fn test_search_response_collapses_versions() {
+        let hit = |name: &str, version: &str, score: f64| CardSearchHit {
+            uid: format!("{name}-{version}"),
+            registry_type: RegistryType::Model,
+            space: "space".to_string(),
+            name: name.to_string(),
+            version: version.to_string(),
+            score,
+            highlight: None,
+        };
+        let hits = vec![
+            hit("churn", "1.1.0", 3.0),
+            hit("churn", "1.0.0", 2.0),
+            hit("retention", "0.1.0", 1.5),
+            hit("ltv", "2.0.0", 1.0),
+        ];
+
+        let response = CardSearchResponse::from_hits(hits, 2);
+        let uids: Vec<_> = response.hits.iter().map(|hit| hit.uid.as_str()).collect();
+        assert_eq!(uids, vec!["churn-1.1.0", "retention-0.1.0"]);
+    }
+}
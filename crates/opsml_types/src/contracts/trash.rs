@@ -0,0 +1,179 @@
+use crate::RegistryType;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Why a card was moved to the trash
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum TrashReason {
+    /// Deleted by a user through the delete route
+    Deleted,
+    /// Trashed by a space retention policy
+    Retention,
+}
+
+impl TrashReason {
+    pub fn from_string(reason: &str) -> Option<Self> {
+        match reason {
+            "deleted" => Some(TrashReason::Deleted),
+            "retention" => Some(TrashReason::Retention),
+            _ => None,
+        }
+    }
+}
+
+impl Display for TrashReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrashReason::Deleted => write!(f, "deleted"),
+            TrashReason::Retention => write!(f, "retention"),
+        }
+    }
+}
+
+/// A card version that has been removed from its registry but can still be restored.
+/// Its artifacts and artifact key are kept until the card is purged
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct TrashedCard {
+    pub uid: String,
+    pub registry_type: RegistryType,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+    pub reason: TrashReason,
+    pub deleted_by: String,
+    pub deleted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct TrashQuery {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub space: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry_type: Option<RegistryType>,
+    /// Only return cards trashed before this time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_before: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct TrashListResponse {
+    pub cards: Vec<TrashedCard>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RestoreCardRequest {
+    pub uid: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RestoreCardResponse {
+    pub uid: String,
+    pub registry_type: RegistryType,
+    pub space: String,
+    pub name: String,
+    pub version: String,
+}
+
+/// Permanently remove trashed cards. With no filters every trashed card is purged
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct PurgeTrashRequest {
+    /// Purge only these cards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uids: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub space: Option<String>,
+    /// Purge only cards that have been in the trash for at least this many days
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub older_than_days: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct PurgeTrashResponse {
+    /// Uids whose row, artifacts and artifact key were removed
+    pub purged: Vec<String>,
+    /// Uids kept because a protected alias still points to them
+    pub skipped: Vec<String>,
+}
+
+/// Retention rules for a space, applied periodically by the server
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RetentionPolicy {
+    pub space: String,
+    /// Move all but the newest N versions of each card to the trash
+    #[serde(default)]
+    pub keep_last_versions: Option<u32>,
+    /// Purge cards that have been in the trash for this many days
+    #[serde(default)]
+    pub purge_trashed_after_days: Option<u32>,
+    /// Never trash or purge a version that a stage alias points to
+    #[serde(default = "default_protect_aliased")]
+    pub protect_aliased: bool,
+    #[serde(default)]
+    pub updated_by: String,
+    #[serde(default = "Utc::now")]
+    pub updated_at: DateTime<Utc>,
+}
+
+fn default_protect_aliased() -> bool {
+    true
+}
+
+impl RetentionPolicy {
+    pub fn new(space: &str) -> Self {
+        Self {
+            space: space.to_string(),
+            keep_last_versions: None,
+            purge_trashed_after_days: None,
+            protect_aliased: true,
+            updated_by: String::new(),
+            updated_at: Utc::now(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RetentionPolicyListResponse {
+    pub policies: Vec<RetentionPolicy>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct DeleteRetentionPolicyRequest {
+    pub space: String,
+}
+
+/// Outcome of one retention pass across all spaces with a policy
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RetentionRunReport {
+    /// Uids moved to the trash by `keep_last_versions`
+    pub trashed: Vec<String>,
+    /// Uids purged by `purge_trashed_after_days`
+    pub purged: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retention_policy_defaults() {
+        let policy: RetentionPolicy =
+            serde_json::from_str(r#"{"space": "team", "keep_last_versions": 5}"#).unwrap();
+        assert_eq!(policy.keep_last_versions, Some(5));
+        assert_eq!(policy.purge_trashed_after_days, None);
+        assert!(policy.protect_aliased);
+    }
+}
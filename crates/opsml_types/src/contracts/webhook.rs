@@ -0,0 +1,251 @@
+use crate::RegistryType;
+use chrono::{DateTime, Utc};
+use opsml_utils::create_uuid7;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Registry changes that are recorded in the event outbox and delivered to webhooks
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RegistryEventType {
+    CardCreated,
+    CardUpdated,
+    /// Moved to the trash. The card can be restored until it is purged
+    CardDeleted,
+    CardRestored,
+    /// Permanently removed along with its artifacts
+    CardPurged,
+    /// A stage alias was set, moved or removed
+    AliasMoved,
+    FileUploaded,
+}
+
+impl RegistryEventType {
+    pub fn from_string(event_type: &str) -> Option<Self> {
+        match event_type {
+            "card_created" => Some(RegistryEventType::CardCreated),
+            "card_updated" => Some(RegistryEventType::CardUpdated),
+            "card_deleted" => Some(RegistryEventType::CardDeleted),
+            "card_restored" => Some(RegistryEventType::CardRestored),
+            "card_purged" => Some(RegistryEventType::CardPurged),
+            "alias_moved" => Some(RegistryEventType::AliasMoved),
+            "file_uploaded" => Some(RegistryEventType::FileUploaded),
+            _ => None,
+        }
+    }
+}
+
+impl Display for RegistryEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistryEventType::CardCreated => write!(f, "card_created"),
+            RegistryEventType::CardUpdated => write!(f, "card_updated"),
+            RegistryEventType::CardDeleted => write!(f, "card_deleted"),
+            RegistryEventType::CardRestored => write!(f, "card_restored"),
+            RegistryEventType::CardPurged => write!(f, "card_purged"),
+            RegistryEventType::AliasMoved => write!(f, "alias_moved"),
+            RegistryEventType::FileUploaded => write!(f, "file_uploaded"),
+        }
+    }
+}
+
+/// A typed registry change. This is also the JSON body POSTed to webhooks
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RegistryEvent {
+    pub event_id: String,
+    pub event_type: RegistryEventType,
+    pub space: String,
+    pub registry_type: Option<RegistryType>,
+    /// Card uid, alias reference or file path the event is about
+    pub resource_id: String,
+    /// Event specific details, e.g. the name and version of a created card
+    pub payload: serde_json::Value,
+    pub username: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RegistryEvent {
+    pub fn new(
+        event_type: RegistryEventType,
+        space: &str,
+        registry_type: Option<RegistryType>,
+        resource_id: &str,
+        payload: serde_json::Value,
+        username: &str,
+    ) -> Self {
+        Self {
+            event_id: create_uuid7(),
+            event_type,
+            space: space.to_string(),
+            registry_type,
+            resource_id: resource_id.to_string(),
+            payload,
+            username: username.to_string(),
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// A registered webhook. The signing secret is only returned once, on creation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct Webhook {
+    pub id: String,
+    pub space: String,
+    pub url: String,
+    /// Event types delivered to this webhook. Empty means all event types
+    pub event_types: Vec<RegistryEventType>,
+    pub active: bool,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    pub fn matches(&self, event: &RegistryEvent) -> bool {
+        self.active
+            && self.space == event.space
+            && (self.event_types.is_empty() || self.event_types.contains(&event.event_type))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateWebhookRequest {
+    pub space: String,
+    pub url: String,
+    /// Event types to subscribe to. All event types when empty
+    #[serde(default)]
+    pub event_types: Vec<RegistryEventType>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct WebhookQuery {
+    pub space: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateWebhookResponse {
+    pub webhook: Webhook,
+    /// Secret used to sign deliveries. It cannot be retrieved again
+    pub secret: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct WebhookListResponse {
+    pub webhooks: Vec<Webhook>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    /// Waiting for its first attempt or a retry
+    Pending,
+    Delivered,
+    /// Gave up after the maximum number of attempts
+    Failed,
+}
+
+impl DeliveryStatus {
+    pub fn from_string(status: &str) -> Option<Self> {
+        match status {
+            "pending" => Some(DeliveryStatus::Pending),
+            "delivered" => Some(DeliveryStatus::Delivered),
+            "failed" => Some(DeliveryStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+impl Display for DeliveryStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeliveryStatus::Pending => write!(f, "pending"),
+            DeliveryStatus::Delivered => write!(f, "delivered"),
+            DeliveryStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+/// One event queued for one webhook
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub webhook_id: String,
+    pub event_id: String,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct WebhookDeliveryQuery {
+    /// Maximum number of deliveries to return, newest first
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct WebhookDeliveryListResponse {
+    pub deliveries: Vec<WebhookDelivery>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_event_type_round_trip() {
+        for event_type in [
+            RegistryEventType::CardCreated,
+            RegistryEventType::CardUpdated,
+            RegistryEventType::CardDeleted,
+            RegistryEventType::AliasMoved,
+            RegistryEventType::FileUploaded,
+        ] {
+            assert_eq!(
+                RegistryEventType::from_string(&event_type.to_string()),
+                Some(event_type)
+            );
+        }
+    }
+
+    #[test]
+    fn test_webhook_matches() {
+        let mut webhook = Webhook {
+            id: "hook".to_string(),
+            space: "space".to_string(),
+            url: "http://localhost".to_string(),
+            event_types: vec![],
+            active: true,
+            created_by: "guest".to_string(),
+            created_at: Utc::now(),
+        };
+
+        let event = RegistryEvent::new(
+            RegistryEventType::CardCreated,
+            "space",
+            Some(RegistryType::Model),
+            "uid",
+            serde_json::json!({}),
+            "guest",
+        );
+        assert!(webhook.matches(&event));
+
+        webhook.event_types = vec![RegistryEventType::AliasMoved];
+        assert!(!webhook.matches(&event));
+
+        webhook.event_types = vec![RegistryEventType::CardCreated];
+        webhook.space = "other".to_string();
+        assert!(!webhook.matches(&event));
+    }
+}
@@ -1,7 +1,9 @@
 use crate::RegistryType;
 use crate::contracts::ToolSpec;
 use crate::error::TypeError;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::collections::{HashMap, HashSet, VecDeque};
 
 /// Reference to a registered card by space/name/optional version.
@@ -250,6 +252,97 @@ impl WorkflowSpec {
     }
 }
 
+/// Run a registered workflow ServiceCard, or an inline spec when `spec` is set
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct WorkflowRunRequest {
+    #[serde(default)]
+    pub space: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<Object>))]
+    pub spec: Option<WorkflowSpec>,
+    /// Values for the workflow's declared inputs
+    #[serde(default)]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub inputs: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum StepStatus {
+    Succeeded,
+    Failed,
+    TimedOut,
+    /// The step's condition evaluated to false
+    Skipped,
+    /// The step never ran because an earlier step failed
+    Cancelled,
+}
+
+impl std::fmt::Display for StepStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StepStatus::Succeeded => write!(f, "Succeeded"),
+            StepStatus::Failed => write!(f, "Failed"),
+            StepStatus::TimedOut => write!(f, "TimedOut"),
+            StepStatus::Skipped => write!(f, "Skipped"),
+            StepStatus::Cancelled => write!(f, "Cancelled"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub enum WorkflowRunStatus {
+    Succeeded,
+    Failed,
+}
+
+/// What happened to a single step during a run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct StepTrace {
+    pub name: String,
+    pub status: StepStatus,
+    /// skill, agent, mcp, tool or prompt
+    pub action: String,
+    /// Resolved card as `space/name@version`, for skill, agent and mcp steps
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub card: Option<String>,
+    /// Inputs after templating
+    #[serde(default)]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub inputs: Map<String, Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Option<Object>))]
+    pub output: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct WorkflowRun {
+    pub status: WorkflowRunStatus,
+    /// Steps in the order they are declared in the spec
+    pub steps: Vec<StepTrace>,
+    /// The workflow's declared outputs. Empty when the run failed
+    #[serde(default)]
+    #[cfg_attr(feature = "utoipa", schema(value_type = Object))]
+    pub outputs: Map<String, Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -29,6 +29,11 @@ pub enum TypeError {
     #[error("Key not found")]
     MissingKeyError,
 
+    #[error(
+        "Artifact key is wrapped with key-encryption-key version {0} and must be opened by the server"
+    )]
+    WrappedArtifactKey(u32),
+
     #[error(transparent)]
     SerdeError(#[from] serde_json::Error),
 
@@ -421,6 +421,7 @@ pub enum SaveName {
     Figures,
     ServiceReload,
     Evaluation,
+    Manifest,
 }
 
 impl SaveName {
@@ -456,6 +457,7 @@ impl SaveName {
             "card_map" => Some(SaveName::CardMap),
             "figures" => Some(SaveName::Figures),
             "service_reload" => Some(SaveName::ServiceReload),
+            "manifest" => Some(SaveName::Manifest),
             _ => None,
         }
     }
@@ -492,6 +494,7 @@ impl SaveName {
             SaveName::Figures => "figures",
             SaveName::ServiceReload => "service_reload",
             SaveName::Evaluation => "evaluation",
+            SaveName::Manifest => "manifest",
         }
     }
 }
@@ -535,6 +538,7 @@ impl AsRef<Path> for SaveName {
             SaveName::Figures => Path::new("figures"),
             SaveName::ServiceReload => Path::new("service_reload"),
             SaveName::Evaluation => Path::new("evaluation"),
+            SaveName::Manifest => Path::new("manifest"),
         }
     }
 }
@@ -20947,6 +20947,7 @@ class ExperimentCard:
         self,
         path: Path | str,
         lpath: Optional[Path] = None,
+        progress: Optional[Callable[[int, int, int, int], None]] = None,
     ) -> None:
         """Download a specific artifact associated with the ExperimentCard
 
@@ -20956,6 +20957,9 @@ class ExperimentCard:
             lpath (Path | None):
                 Local path to save the artifact. If not provided, the artifact will be saved
                 to a directory called "artifacts"
+            progress (Callable[[int, int, int, int], None] | None):
+                Called with (bytes_transferred, total_bytes, files_completed, total_files)
+                as the download proceeds
 
         Examples:
 
@@ -22453,6 +22457,9 @@ class Experiment:
         """
         Log a metric
 
+        Metrics and parameters are buffered and written in bulk by a background task.
+        Any remaining buffer is flushed when the experiment exits, and flush errors are raised then.
+
         Args:
             name (str):
                 Name of the metric
@@ -22683,6 +22690,7 @@ def download_artifact(
     experiment_uid: str,
     path: Path | str,
     lpath: Optional[Path] = None,
+    progress: Optional[Callable[[int, int, int, int], None]] = None,
 ) -> None:
     """
     Download an artifact from an experiment
@@ -22693,6 +22701,9 @@ def download_artifact(
             Path of the artifact to download
         lpath (Path | None):
             Local path to download the artifact to. If None, the artifact will be downloaded to the current working directory.
+        progress (Callable[[int, int, int, int], None] | None):
+            Called with (bytes_transferred, total_bytes, files_completed, total_files) as the
+            download proceeds. Interrupted downloads resume from the partially written files.
     """
 
 class ProtocolBinding:
@@ -291,3 +291,19 @@ def test_experimentcard_register(
         loaded_card.uids.modelcard_uids = [modelcard.uid]
         loaded_card.uids.promptcard_uids = [prompt_card.uid]
         loaded_card.uids.service_card_uids = [service.uid]
+
+
+@pytest.mark.skipif(WINDOWS_EXCLUDE, reason="skipping")
+def test_experiment_buffered_metrics():
+    with OpsmlTestServer(cleanup=True):
+        with start_experiment(space="test") as exp:
+            for step in range(2000):
+                exp.log_metric(name="loss", value=1.0 / (step + 1), step=step)
+
+            exp.log_parameters({"lr": 0.01, "epochs": 10})
+
+        metrics = get_experiment_metrics(exp.card.uid, names=["loss"])
+        assert len(metrics) == 2000
+
+        parameters = get_experiment_parameters(exp.card.uid)
+        assert len(parameters) == 2